@@ -0,0 +1,683 @@
+# English (base language).
+# Every key used by the launcher must be here, other
+# languages fall back to these strings for missing keys.
+#
+# Syntax: `key = value`, with `{ $name }` placeholders
+# and `\n` for line breaks.
+
+app-title = Quantum Launcher
+app-tagline = A Minecraft Launcher\nby Mrmayman
+
+back = Back
+cancel = Cancel
+add = Add
+no = No
+
+launch-username = Username:
+launch-username-placeholder = Enter username...
+username-empty = Enter a username to play.
+username-space = Usernames can't contain spaces.
+username-too-short = Usernames need at least { $min } characters.
+username-too-long = Usernames can have at most { $max } characters.
+username-invalid-character = "{ $character }" isn't allowed. Use only letters, numbers and underscores.
+username-invalid-why = Servers kick players whose names break these rules, and some plugins fail to save their data. Singleplayer works either way.
+username-allow-anyway = Allow anyway (for servers that accept any name)
+launch-account = Account:
+launch-account-username = Type a username
+launch-account-offline = { $name } (offline)
+launch-instance-profile = This instance always launches as the offline profile { $name }. Change it in the instance's settings.
+launch-instances = Instances:
+launch-instances-loading = Loading instances...
+launch-new-instance = New Instance
+launch-shortcut-hint = Press F1 for keyboard shortcuts
+launch-delete-instance = Delete Instance
+launch-edit-instance = Edit Instance
+launch-manage-mods = Manage Mods
+launch-settings = Settings
+launch-servers = Servers
+launch-stats = Statistics
+launch-downloads = Downloads
+launch-favorite = Add to Favorites
+launch-unfavorite = Remove from Favorites
+launch-update-available = Update available: { $version }
+launch-worlds = Worlds
+launch-recent = Recently played
+launch-recent-as = As { $username }
+launch-game-options = Game options
+launch-verify = Verify Files
+launch-open-files = Open Files
+launch-play = Launch Game
+launch-without-mods = Launch without mods
+launch-with-options = Launch with options...
+launch-with-options-title = Launch { $instance } with options
+launch-with-options-hint = These are only used this time, and aren't saved to the instance. Leave a field empty to skip it.
+launch-with-options-java-args = Extra Java arguments
+launch-with-options-game-args = Extra game arguments
+launch-with-options-server = Server to join
+launch-card-unknown = Unknown
+launch-card-never-played = Never played
+launch-card-played-now = Played just now
+launch-card-played-minutes = Played { $count } min ago
+launch-card-played-hours = Played { $count } h ago
+launch-card-played-days = Played { $count } days ago
+
+java-install-starting-1 = Starting up (1/2)
+java-install-starting-2 = Starting up (2/2)
+java-install-downloading = Downloading ({ $progress }/{ $out-of }): { $name }
+java-install-done = Done!
+java-install-cancelling = Cancelling...
+
+edit-title = Editing { $mod-type } instance: { $instance }
+edit-java-override = Use a special Java install instead of the default one. (Enter path, leave blank if none)
+edit-java-override-placeholder = Enter Java override
+edit-java-args = Java arguments, added after the ones in the launcher settings:
+edit-log4shell-mitigation = Protect 1.7 to 1.18 from the Log4Shell exploit (turn off only if a mod needs the version's own logging config)
+edit-version-type = Version type, shown in the F3 screen and crash reports (like a modpack's name):
+edit-version-type-placeholder = The version's own type, like release or snapshot
+edit-game-dir = Game folder, which can be shared with other instances to share worlds, resource packs and options:
+edit-game-dir-placeholder = This instance's own .minecraft folder
+edit-game-dir-from-instance = Use another instance's folder
+edit-sync-folder = Keep saves, options and the mod list in this instance's "sync" folder too, for Syncthing or Dropbox to share with your other computers
+edit-java-version = Java version (downloaded automatically on first launch). Some mods need a newer Java than the default.
+edit-java-version-default = Default (from version)
+edit-java-version-major = Java { $version }
+edit-offline-profile = Offline profile
+edit-offline-profile-hint = Always launch this instance as one of your offline profiles, whatever is picked on the launch screen.
+edit-offline-profile-none = Account picked on the launch screen
+edit-memory = Allocated memory
+edit-memory-normal = For normal Minecraft, allocate 2 - 3 GB
+edit-memory-old = For old versions, allocate 512 MB - 1 GB
+edit-memory-heavy = For heavy modpacks or very high render distances, allocate 4 - 8 GB
+edit-memory-invalid = Type an amount like 4G or 4096M
+edit-memory-too-much = Warning: that's more than 90% of this computer's { $total } of memory, which can make everything slow down or the game crash
+edit-memory-flags = Java arguments: { $flags }
+edit-memory-initial = Initial memory (Java starts with this much, and grows up to the allocated memory)
+edit-snapshots = Snapshots
+edit-snapshots-description = Taken before installing, updating or removing a loader and before repairing the version details, so that you can roll back if the change breaks the game. The newest 5 are kept.
+edit-snapshots-small-files = Copy small mods into snapshots (otherwise removed mods have to be added back by hand)
+edit-snapshots-none = No snapshots yet
+edit-roll-back = Roll back
+edit-template = Template
+edit-template-description = Save this instance's version, loader, Modrinth mods, Java settings and game options, to create new instances set up the same way. Saving with a template's name replaces it.
+edit-template-placeholder = Template name
+edit-template-save = Save as template
+snapshot-details = Before { $reason } ({ $loader }, { $mods } mods)
+snapshot-loader-install = installing a loader
+snapshot-loader-update = updating the loader
+snapshot-loader-uninstall = removing the loader
+snapshot-version-json-redownload = repairing the version details
+snapshot-fabric = Fabric { $version }
+snapshot-vanilla = Vanilla
+
+mods-install-fabric = Install Fabric
+mods-install-quilt = Install Quilt
+mods-install-forge = Install Forge
+mods-install-optifine = Install OptiFine
+mods-uninstall = Uninstall { $mod-type }
+mods-loader-update = { $mod-type } { $version } is out
+mods-loader-update-button = Update loader
+mods-loader-updating = Updating...
+mods-installed = Installed mods:
+mods-open-folder = Go to mods folder
+mods-coming-soon = Mod management and store coming soon...
+mods-browse = Browse mods
+mods-export = Copy mod list as:
+mods-export-text = Text
+mods-export-markdown = Markdown
+mods-export-json = JSON
+mods-import = Install mods from a list:
+mods-import-placeholder = Paste a mod list JSON, or Modrinth slugs like sodium, lithium
+mods-import-button = Install all
+mod-browser-search-placeholder = Search Modrinth...
+mod-browser-search = Search
+mod-browser-searching = Searching...
+mod-browser-search-failed = Could not search for mods: { $error }
+mod-browser-no-results = No mods found.
+mod-browser-load-more = Load more
+mod-browser-loading-more = Loading more...
+mod-browser-hit-info = by { $author } · { $downloads } downloads
+mod-browser-loading = Loading...
+mod-browser-details-failed = Could not load this mod: { $error }
+mod-browser-install = Install
+mod-browser-open-page = Open on Modrinth
+mod-browser-downloads = { $count } downloads
+mod-browser-followers = { $count } followers
+mod-browser-updated = Updated { $date }
+mod-browser-license = License: { $license }
+
+create-loader-hint = To install Fabric/Forge/OptiFine/Quilt, click on Manage Mods after installing the instance
+create-select-version = Select Version
+create-selected-version = Version: { $version }
+create-loading-versions = Loading versions...
+create-version-filter = Search versions...
+create-version-favorites = Favorites
+version-type-release = Releases
+version-type-snapshot = Snapshots
+version-type-experimental = April Fools and experiments
+version-type-beta = Beta
+version-type-alpha = Alpha
+create-name-placeholder = Enter instance name...
+create-version-source-hint = Where to get versions from. BetterJSONs has fixed versions of old alphas (which often don't start from Mojang's list), and old versions Mojang doesn't list.
+create-custom-version-toggle = Advanced: custom version
+create-custom-version-hint = The path or URL of a version JSON, for clients and experimental builds that aren't listed. It must be a complete one, not one that inherits from another version.
+create-custom-version-placeholder = File path or https:// URL...
+create-server-pack-toggle = Advanced: from a server pack
+create-server-pack-hint = The path of a server pack zip. Its mods and configs are copied, except for mods Modrinth says are server-only. The selected version is used if the pack doesn't say which one it's for. Only Fabric packs are supported.
+create-server-pack-placeholder = Path of the server pack .zip...
+create-template-hint = Start from a template, with its loader, mods and settings. Any version can be picked, mods without a version for it are left out.
+create-template-none = No template
+create-template-delete = Delete template
+create-template-summary = { $loader } on { $version }, { $count ->
+    [one] 1 mod
+   *[other] { $count } mods
+}
+create-assets-hint = Download assets? If disabled, creating instance will be MUCH faster, but no sound or music will play in-game
+create-assets-toggle = Download assets?
+create-button = Create Instance
+create-server-hint = Pick the Minecraft version the server runs. Players need the same version to join
+create-server-name-placeholder = Enter server name...
+create-server-button = Create Server
+create-server-software-hint = Paper and Purpur are faster than the vanilla server and can run plugins. They only have some versions.
+create-progress-started = Started download
+create-patch-notes-title = What's new
+create-patch-notes-failed = Could not load the patch notes: { $error }
+create-progress-cancelling = Cancelling...
+
+progress-started = Started.
+progress-manifest = Downloading Manifest JSON.
+progress-version-json = Downloading Version JSON.
+progress-assets = Downloading asset { $progress } / { $out-of }.
+progress-libraries = Downloading library { $progress } / { $out-of }.
+progress-jar = Downloading Game Jar file.
+progress-logging-config = Downloading logging config.
+
+delete-confirm = Are you SURE you want to DELETE the Instance: { $instance }?
+delete-warning = All your data, including worlds will be lost.
+delete-yes = Yes, delete my data
+confirm-type-name = Type "{ $name }" to confirm:
+confirm-delete-mod = Delete the mod { $file-name }?
+confirm-delete-mod-warning = The mod file will be removed from the mods folder.
+confirm-delete-mod-yes = Yes, delete the mod
+confirm-delete-plugin = Delete the plugin { $file-name }?
+confirm-delete-plugin-warning = The plugin file will be removed from the plugins folder. Its settings in the plugins folder are kept.
+confirm-delete-plugin-yes = Yes, delete the plugin
+confirm-delete-server = Are you SURE you want to DELETE the server: { $server }?
+confirm-uninstall-loader = Uninstall { $loader } from { $instance }?
+confirm-uninstall-loader-warning = The instance will go back to vanilla. Your mods will be kept, but won't load.
+confirm-uninstall-loader-yes = Yes, uninstall
+confirm-move-data-dir = Move the launcher's data to { $dir }?
+confirm-move-data-dir-warning = Instances and servers will be moved there. This can take a while; don't close the launcher until it's done.
+confirm-move-data-dir-yes = Yes, move it
+confirm-restore-backup = Restore the launcher from { $path }?
+confirm-restore-backup-warning = Settings, instance configs and worlds in the backup will replace the current ones. Instances that aren't in the backup are kept.
+confirm-restore-backup-yes = Yes, restore it
+confirm-delete-java = Delete these unused Java installs: { $installs }?
+confirm-delete-java-warning = No instance or server needs them anymore. They'll be downloaded again if one does.
+confirm-delete-java-yes = Yes, delete them
+confirm-delete-world = Delete the world { $world }?
+confirm-delete-world-warning = The world's backups are kept, so it can still be restored from them.
+confirm-delete-world-yes = Yes, delete it
+confirm-restore-world = Restore { $world } from { $backup }?
+confirm-restore-world-warning = The world will be replaced by the backup. It's backed up first, so this can be undone.
+confirm-restore-world-yes = Yes, restore it
+confirm-roll-back = Roll { $instance } back to the snapshot from { $snapshot }?
+confirm-roll-back-warning = The loader, configs and options go back to how they were, and mods added since are deleted.
+confirm-roll-back-yes = Yes, roll back
+confirm-launch-again = { $instance } is already running. Launch it again?
+confirm-launch-again-warning = Two copies of one instance writing to the same worlds corrupt them, so the second copy runs in a separate game folder with its own worlds. Its settings and mods are copied over.
+confirm-launch-again-yes = Launch anyway with a separate game folder
+confirm-java-too-old = This version needs Java { $required }, but the instance is set to use Java { $selected }
+confirm-java-too-new = { $loader } for this version doesn't work on Java newer than { $max }, but the instance is set to use Java { $selected }
+confirm-java-warning = The game will probably crash while starting.
+confirm-java-yes = Launch anyway
+confirm-java-fix = Install Java { $version } and launch
+delete-outside-launcher = Tried to delete instance folder located outside Launcher. Potential attack avoided.
+
+fabric-select-version = Select Fabric Version for instance { $instance }
+fabric-versions-cached = Offline: showing the versions from the last time they were loaded
+fabric-progress-json = Downloading Fabric JSON...
+fabric-progress-library = Downloading library { $progress } / { $out-of }: { $name }
+fabric-progress-done = Finishing up...
+
+error-title = Error: { $error }
+error-copy = Copy Error
+error-restore-backup = Restore Last Working Copy
+error-redownload-version-json = Download Version Details Again and Launch
+error-copy-diagnostics = Copy Diagnostics
+error-report = Report This Error
+crash-title = QuantumLauncher closed unexpectedly
+crash-hint = The launcher crashed last time it was open. A crash report was saved; reporting it helps get the bug fixed.
+crash-message = What went wrong: { $message }
+crash-continue = Continue
+crash-copy = Copy Crash Report
+crash-report = Report This Crash
+crash-open-folder = Open Crash Reports
+error-hint-config = The instance's settings (config.json) couldn't be read. If a backup is available, restore it; otherwise check the file for mistakes.
+error-hint-version-json = The instance's version details (details.json) are missing or damaged. They can be downloaded again below.
+error-hint-classpath = Some of the game's libraries couldn't be set up. They may be missing or damaged, or a mod loader install didn't finish. Reinstalling the mod loader or creating the instance again usually fixes this.
+error-hint-java = Java couldn't be found or downloaded. Check your internet connection, or set a Java path in the instance's settings.
+error-hint-spawn = Java couldn't be started. If the instance has a custom Java path, check that it points to a java executable, or clear it to use the launcher's Java.
+error-report-description = Opens a GitHub issue with the error, launcher version, OS and instance config filled in. Check it for anything private before submitting.
+
+toast-info = Info
+toast-success = Done
+toast-error = Error
+toast-dismiss = Dismiss
+toast-instance-created = Instance created
+toast-server-created = Server created
+toast-create-cancelled = Cancelled, nothing was kept
+toast-template-saved = Saved template { $name }
+toast-template-save-failed = Could not save template: { $error }
+toast-template-deleted = Deleted template { $name }
+toast-template-unmatched = Could not install { $count ->
+    [one] 1 mod
+   *[other] { $count } mods
+} from the template: { $mods }
+toast-sync-failed = Could not sync { $instance }: { $error }
+toast-sync-conflict = { $instance } was changed on another computer too. The older copy was kept in { $path }
+toast-sync-missing-mods = { $instance } on your other computer has mods this one doesn't: { $mods }
+toast-server-pack-skipped = Left out { $count ->
+    [one] 1 server-only mod
+   *[other] { $count } server-only mods
+}: { $mods }
+toast-server-pack-review = Check { $count ->
+    [one] 1 mod
+   *[other] { $count } mods
+} that may not belong on a client: { $mods }
+toast-launch-cancelled = Java install cancelled
+toast-fabric-installed = Fabric installed
+toast-loader-updated = Updated the loader of { $instance } to { $version }
+toast-loader-update-failed = Could not update the loader: { $error }
+toast-fabric-install-failed = Could not install Fabric: { $error }
+toast-mod-delete-failed = Could not delete the mod: { $error }
+toast-plugin-delete-failed = Could not delete the plugin: { $error }
+toast-plugins-installed = Plugins installed: { $count }
+toast-plugins-install-failed = Could not install plugins: { $error }
+toast-mods-installed = Installed { $count ->
+    [one] 1 mod
+   *[other] { $count } mods
+}
+toast-mods-install-failed = Could not install the mod: { $error }
+toast-server-software-versions-failed = Could not load the versions of { $software }: { $error }
+toast-loader-uninstalled = Mod loader uninstalled
+toast-loader-uninstall-failed = Could not uninstall the mod loader: { $error }
+toast-server-start-failed = Could not start the server: { $error }
+toast-server-stopping = Stopping { $server }
+toast-server-command-failed = Could not send the command: { $error }
+toast-server-properties-saved = Server settings saved
+toast-server-properties-save-failed = Could not save the server settings: { $error }
+toast-server-schedule-saved = Schedule saved
+toast-server-schedule-save-failed = Could not save the schedule: { $error }
+toast-server-backup-started = Backing up { $server }
+toast-server-backup-done = Backed up { $server }
+toast-server-backup-failed = Could not back up { $server }: { $error }
+toast-world-backup-done = Backed up { $world }
+toast-world-backup-failed = Could not back up { $world }: { $error }
+toast-world-restore-done = World restored
+toast-world-restore-failed = Could not restore the world: { $error }
+toast-roll-back-done = Rolled back
+toast-roll-back-missing-mods = Rolled back, but these mods weren't in the snapshot and have to be added back by hand: { $mods }
+toast-roll-back-failed = Could not roll back: { $error }
+toast-roll-back-game-running = Close the game before rolling back
+toast-world-game-running = Close the game before changing its worlds.
+toast-world-rename-failed = Could not rename the world: { $error }
+toast-world-delete-failed = Could not delete the world: { $error }
+toast-world-copy-done = Copied the world as { $world }
+toast-world-copy-failed = Could not copy the world: { $error }
+toast-worlds-saved = Backup settings saved
+toast-worlds-save-failed = Could not save the backup settings: { $error }
+toast-game-options-saved = Game options saved
+toast-game-options-save-failed = Could not save the game options: { $error }
+toast-keybinds-imported = { $count ->
+    [one] Imported 1 keybind. Save to keep it.
+   *[other] Imported { $count } keybinds. Save to keep them.
+}
+toast-keybinds-import-none = That instance has no keybinds that work in this version
+toast-keybinds-import-failed = Could not import the keybinds: { $error }
+toast-game-options-copied = Copied the options of { $instance }
+toast-game-options-copy-failed = Could not copy the game options: { $error }
+toast-resource-pack-installed = Added { $pack }
+toast-resource-pack-install-failed = Could not add the resource pack: { $error }
+toast-server-restarting = Restarting { $server }
+toast-game-killed = Stopped { $instance }
+toast-game-kill-failed = Could not stop { $instance }: { $error }
+toast-game-crashed = { $instance } crashed. Check its log in the sidebar.
+toast-launch-without-mods-running = Close { $instance } before launching it without mods.
+toast-restore-mods-failed = Could not put back the mods of { $instance }: { $error }
+toast-log-shared = Uploaded to { $url }. The link was copied.
+toast-mod-list-copied = Mod list copied
+toast-mod-list-export-failed = Could not export the mod list: { $error }
+toast-mod-list-unmatched = These mods couldn't be installed: { $mods }
+toast-log-share-failed = Could not upload the log: { $error }
+toast-log-no-crash-report = The game did not write a crash report.
+toast-update-failed = Could not update: { $error }
+toast-launch-instance-missing = There's no instance called { $instance }
+toast-instance-removed = { $instance } was removed from the instances folder.
+toast-tray-failed = Could not show the tray icon: { $error }
+toast-config-save-failed = Could not save settings: { $error }
+toast-data-dir-moved = The launcher's data was moved
+toast-data-dir-move-failed = Could not move the launcher's data: { $error }
+toast-data-dir-games-running = Close all games and servers before moving the launcher's data.
+toast-file-restored = Restored the last working copy of the file
+toast-verify-fixed = Fixed
+toast-verify-fix-failed = Could not fix it: { $error }
+toast-version-json-downloading = Downloading the version details...
+toast-version-json-downloaded = Downloaded the version details
+toast-launcher-backup-done = Backed up the launcher
+toast-launcher-backup-failed = Could not back up the launcher: { $error }
+toast-launcher-restore-done = Restored the launcher from the backup
+toast-launcher-restore-failed = Could not restore the backup: { $error }
+toast-launcher-restore-games-running = Close all games and servers before restoring a backup.
+toast-profile-added = Added the offline profile { $name }
+toast-profile-exists = There's already an offline profile called { $name }.
+toast-profile-skin-invalid = Could not use the skin: { $error }
+
+settings-loading = Loading settings...
+settings-appearance = Appearance
+settings-theme = Theme:
+settings-theme-dark = Dark
+settings-theme-light = Light
+settings-theme-system = System
+settings-accent = Accent color:
+settings-accent-purple = Purple
+settings-accent-blue = Blue
+settings-accent-teal = Teal
+settings-accent-green = Green
+settings-accent-orange = Orange
+settings-accent-red = Red
+settings-style = Style:
+settings-style-rounded = Rounded
+settings-style-flat = Flat
+settings-language = Language:
+settings-show-news = Show Minecraft news on the launch screen
+settings-check-updates = Check for launcher updates when starting
+settings-accessibility = Accessibility
+settings-high-contrast = High contrast
+settings-large-text = Large text
+settings-large-text-hint = Restart the launcher for large text to fully apply.
+settings-reduce-motion = Reduce motion
+settings-reduce-motion-hint = Notifications stay until closed, and widgets don't grow while in use.
+settings-instance-view = Instance list:
+settings-instance-view-list = Dropdown
+settings-instance-view-grid = Grid with details
+settings-ui-scale = Interface scale: { $percent }%
+settings-account = Account
+settings-default-username = Default username:
+settings-offline-profiles = Offline profiles
+settings-offline-profiles-hint = Save usernames to switch between on the launch screen. Each gets the same UUID the game would give it offline, so your worlds and offline servers recognise you. They're separate from online accounts.
+settings-offline-profile = { $name }: { $username }
+settings-offline-profile-delete = Delete
+settings-offline-profile-name = Profile name
+settings-offline-profile-skin = Skin PNG file (optional)
+settings-offline-profile-add = Add profile
+settings-java = Java
+settings-java-installs = Manually added Java installs:
+settings-java-install-placeholder = Path to java executable
+settings-java-cleanup = Clean up unused Java installs
+settings-java-cleanup-running = Cleaning up...
+settings-java-cleanup-none = No unused Java installs found.
+settings-java-cleanup-removed = Removed { $removed }
+settings-java-cleanup-failed = Could not clean up: { $error }
+settings-java-args = Java arguments for every instance (use quotes for arguments with spaces):
+settings-downloads = Downloads
+settings-parallel-downloads = Parallel downloads: { $count }
+settings-mirror = Mirror for Mojang's servers (leave blank for none):
+settings-proxy = Proxy (leave blank for none):
+settings-behavior = Behavior
+settings-close-on-launch = Close launcher after the game starts
+settings-options-source = Start new instances with the options of
+settings-options-source-none = None (game defaults)
+settings-options-source-hint = Copies its options.txt, keybinds and server list to every new instance.
+settings-tray-icon = Show an icon in the system tray
+settings-tray-icon-hint = Launch favorite instances, stop games and open the launcher from the tray. With it, "Close launcher after the game starts" hides the window instead.
+settings-tray-icon-unavailable = The tray icon is only supported on Linux.
+settings-discord-rpc = Show the game you're playing on Discord
+settings-discord-rpc-hint = Shows the instance, its version and how long you've been playing on your Discord profile, while Discord is open.
+settings-discord-rpc-unavailable = This build of the launcher doesn't support Discord.
+discord-playing = Playing { $instance }
+settings-debug = Debugging
+settings-launcher-logs = Launcher Logs
+settings-verbose-logging = Verbose logging
+settings-verbose-logging-hint = Also logs launch commands (without access tokens), every download and how long each step took. Useful when reporting a bug. Running the launcher with --verbose does the same.
+launcher-logs-title = Launcher Logs
+launcher-logs-refresh = Refresh
+launcher-logs-copy = Copy
+launcher-logs-open = Open Logs Folder
+settings-data = Data
+settings-data-dir = Instances and servers are stored in:
+settings-cache-dir = Java installs and assets, which can be downloaded again, are stored in:
+settings-data-dir-move = Move Data Here
+settings-data-dir-hint = Everything is moved to the new folder, which can be on another drive. Settings stay where they are. The { $variable } environment variable can also set it.
+settings-data-dir-moving = Moving data...
+settings-data-dir-env = Set by the { $variable } environment variable.
+settings-portable = Portable mode is on, so the settings are kept next to the launcher.
+settings-backup = Backup
+settings-backup-hint = Saves your settings and instance configs into one file, to restore if something goes wrong, like a bad modpack update. Game files aren't included, as they can be downloaded again.
+settings-backup-include-saves = Include worlds
+settings-backup-create = Back Up Launcher
+settings-backup-running = Backing up...
+settings-backup-open = Open Backups Folder
+settings-backup-restore-path = Backup to restore:
+settings-backup-restore = Restore
+settings-backup-restoring = Restoring...
+
+shortcut-title = Keyboard shortcuts
+shortcut-previous-instance = Select previous instance
+shortcut-next-instance = Select next instance
+shortcut-launch = Launch selected instance
+shortcut-new-instance = New instance
+shortcut-back = Go back
+shortcut-toggle-help = Show or hide this list
+shortcut-search = Search instances, mods and worlds
+shortcut-key-up = Up arrow
+shortcut-key-down = Down arrow
+shortcut-key-enter = Enter
+shortcut-key-escape = Esc
+
+game-sidebar-title = Running games
+game-running = Running for { $uptime }\n{ $memory }
+game-exited = Exited ({ $status })
+game-log-title = Log of { $instance }
+game-log-missing = This game is no longer running.
+game-log-share = Share Log
+game-log-share-crash-report = Share Crash Report
+game-log-sharing = Uploading...
+game-kill = Kill game
+game-close = Close
+
+servers-title = Servers
+servers-empty = No servers yet.
+servers-new = New Server
+servers-delete = Delete Server
+servers-start = Start Server
+servers-stop = Stop Server
+servers-running = Running for { $uptime }
+servers-stopped = Stopped
+servers-open-console = Open Console
+servers-plugins = Plugins
+servers-external-title = Other servers
+servers-external-hint = Servers added here have their status shown on the launch screen, next to your own.
+servers-external-placeholder = Address, like play.example.com:25565
+servers-external-add = Add
+server-status-title = Servers
+server-status-checking = Checking...
+server-status-offline = Offline
+server-status-online = Online: { $online }/{ $max } players, { $latency } ms
+servers-properties = Server Settings
+servers-schedule = Backups and Restarts
+console-title = Console of { $server }
+console-input-placeholder = Type a command, like "say Hello"...
+console-send = Send
+console-show-more = Show older lines
+console-stopping = Saving the worlds and stopping...
+console-kill = Kill server
+eula-title = Before starting { $server }
+eula-description = Minecraft servers can only be run by people who accept Mojang's End User License Agreement (EULA). It covers things like what you may charge players for. Please read it before continuing.
+eula-read = Read the EULA
+eula-agree = I have read and agree to the Minecraft EULA
+eula-accept = Accept and start server
+plugins-title = Plugins of { $server }
+plugins-vanilla = Vanilla servers can't run plugins. Create a Paper or Purpur server to use them.
+plugins-restart-hint = New plugins are loaded the next time the server starts.
+plugins-input-placeholder = Plugin names, like luckperms
+plugins-input-hint = Use the name from the plugin's page address. Separate several plugins with spaces.
+plugins-install = Install
+plugins-installed = Installed plugins:
+plugins-empty = No plugins installed.
+plugins-open-folder = Go to plugins folder
+plugins-progress-starting = Starting...
+plugins-progress-resolving = Finding a version of { $name }...
+plugins-progress-downloading = Downloading { $file-name }...
+plugins-progress-done = Done
+server-properties-title = Settings for { $server }
+game-options-title = Game options for { $instance }
+game-options-hint = These are used from the next launch, even if the game has never been started.
+game-options-render-distance = Render distance (chunks)
+game-options-render-distance-invalid = The render distance must be a number from { $min } to { $max }.
+game-options-gui-scale = GUI scale
+game-options-gui-scale-auto = Auto
+game-options-max-fps = Max framerate
+game-options-max-fps-hint = { $unlimited } means unlimited.
+game-options-max-fps-invalid = The max framerate must be a number from { $min } to { $max }.
+game-options-vsync = Use VSync
+game-options-language = Language code
+game-options-language-invalid = The language must be a code like en_us.
+game-options-copy-source = Copy from another instance
+game-options-import-keybinds = Import keybinds
+game-options-copy-all = Copy all options and servers
+game-options-copy-all-hint = Copying all options saves them right away, replacing the changes above.
+game-options-resource-packs = Resource packs
+game-options-resource-packs-empty = No resource packs installed.
+game-options-resource-pack-placeholder = Path to a resource pack .zip or folder
+game-options-enable-new-pack = Turn it on, so it's active from the next launch
+game-options-save = Save
+server-properties-restart-hint = Changes take effect the next time the server starts.
+server-properties-edit-raw = Edit as text
+server-properties-edit-settings = Back to settings
+server-properties-save = Save
+server-properties-port = Port
+server-properties-port-invalid = The port must be a number from 1 to 65535.
+server-properties-gamemode = Game mode
+server-properties-gamemode-survival = Survival
+server-properties-gamemode-creative = Creative
+server-properties-gamemode-adventure = Adventure
+server-properties-gamemode-spectator = Spectator
+server-properties-difficulty = Difficulty
+server-properties-difficulty-peaceful = Peaceful
+server-properties-difficulty-easy = Easy
+server-properties-difficulty-normal = Normal
+server-properties-difficulty-hard = Hard
+server-properties-online-mode = Online mode
+server-properties-online-mode-hint = Only players with a Microsoft account can join. Turn this off for offline or LAN play.
+server-properties-motd = Message of the day
+server-properties-motd-placeholder = Shown in the multiplayer server list
+server-properties-motd-invalid = The message of the day can't have line breaks.
+server-properties-view-distance = View distance (chunks)
+server-properties-view-distance-invalid = The view distance must be a number from { $min } to { $max }.
+server-schedule-title = Backups and restarts for { $server }
+server-schedule-hint = These only happen while the launcher is open, counting from when the server starts. Leave a time empty to turn it off.
+server-schedule-never = Never
+server-schedule-backup-interval = Back up the world every (minutes)
+server-schedule-backup-retention = Backups to keep
+server-schedule-restart-interval = Restart the server every (minutes)
+server-schedule-interval-invalid = Enter a whole number of minutes, or leave it empty.
+server-schedule-retention-invalid = Keep at least 1 backup.
+server-schedule-save = Save
+server-schedule-backup-now = Back Up Now
+server-schedule-open-backups = Open Backups Folder
+
+news-title = News
+news-loading = Loading news...
+news-failed = Could not load news: { $error }
+news-read-more = Read more
+
+welcome-title = Welcome to Quantum Launcher!
+welcome-step = Step { $step } of { $total }
+welcome-next = Next
+welcome-skip = Skip for now
+welcome-data-dir = Where should your instances be stored?
+welcome-data-dir-hint = Instances and servers will be saved in this folder.
+welcome-data-dir-failed = Could not use this folder: { $error }
+welcome-account = Choose a username
+welcome-account-hint = This is the name other players see. You can change it later.
+welcome-theme = Pick a look
+welcome-first-instance = Create your first instance
+welcome-first-instance-hint = An instance is a separate Minecraft installation, with its own version, mods and worlds.
+
+stats-title = Statistics
+download-queue-title = Downloads
+download-queue-empty = Nothing is downloading.
+download-queue-speed = Speed: { $speed }/s
+download-queue-kind-instance = Instance
+download-queue-kind-mods = Mods for
+download-queue-kind-java = Java
+download-queue-active = Downloading
+download-queue-queued = Queued
+download-queue-paused = Paused
+download-queue-progress = { $status } · { $size } · { $files } files
+download-queue-pause = Pause
+download-queue-resume = Resume
+stats-empty = No games played yet. Playtime is counted from when a game is launched until it's closed.
+stats-total = Played { $time } in { $sessions } sessions
+stats-time = { $hours } h { $minutes } min
+stats-per-instance = Per instance
+stats-per-week = Sessions per week
+stats-this-week = This week
+stats-weeks-ago = { $count ->
+    [one] 1 week ago
+   *[other] { $count } weeks ago
+}
+stats-versions = Most played versions
+
+worlds-title = Worlds of { $instance }
+worlds-empty = This instance has no worlds yet.
+worlds-backup = Back Up Now
+worlds-open-backups = Open Backups Folder
+worlds-no-backups = No backups yet
+worlds-restore = Restore
+worlds-rename = Rename
+worlds-rename-save = Save
+worlds-rename-cancel = Cancel
+worlds-duplicate = Duplicate
+worlds-copy = Copy to Instance
+worlds-copy-target = Copy worlds to:
+worlds-delete = Delete
+worlds-hardcore = Hardcore
+worlds-folder = folder { $folder }
+worlds-unreadable = Could not read level.dat
+worlds-deleted = Deleted, only backups left
+worlds-backup-on-version-change = Back up all worlds before launching a different Minecraft version
+
+verify-title = Checking { $instance }
+verify-description = Checks that the instance's files are all there and not damaged. If something is wrong, try fixing it here before reporting a bug.
+verify-checking = Checking...
+verify-failed = Could not check the instance: { $error }
+verify-config = Instance settings (config.json)
+verify-version-json = Version details (details.json)
+verify-client-jar = Minecraft jar
+verify-libraries = Libraries
+verify-loader = Mod loader files
+verify-asset-index = Assets index
+verify-fix = Fix
+verify-fixing = Fixing...
+
+update-title = QuantumLauncher { $version } (you have { $current })
+update-changelog = What's new
+update-install = Download and install ({ $file })
+update-installing = Downloading and checking the update...
+update-open-page = Open release page
+update-no-download = There's no download for your system in this release, get it from the release page.
+update-restart = Updated! Restart the launcher to use the new version.
+update-installer-saved = The installer was saved to { $path }. Open it to finish updating.
+
+tray-show = Open QuantumLauncher
+tray-launch = Play { $instance }
+tray-kill = Stop { $instance }
+tray-running = Playing { $instance }
+tray-no-games = No games running
+
+search-placeholder = Search instances, versions, mods and worlds
+search-no-results = Nothing found.
+search-instance = Instance
+search-mod = Mod
+search-world = World
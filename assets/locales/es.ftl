@@ -0,0 +1,677 @@
+# Spanish. Missing keys fall back to English.
+
+app-title = Quantum Launcher
+app-tagline = Un launcher de Minecraft\npor Mrmayman
+
+back = Volver
+cancel = Cancelar
+add = Añadir
+no = No
+
+launch-username = Nombre de usuario:
+launch-username-placeholder = Escribe tu nombre de usuario...
+username-empty = Escribe un nombre de usuario para jugar.
+username-space = Los nombres de usuario no pueden tener espacios.
+username-too-short = Los nombres de usuario necesitan al menos { $min } caracteres.
+username-too-long = Los nombres de usuario pueden tener como máximo { $max } caracteres.
+username-invalid-character = "{ $character }" no está permitido. Usa solo letras, números y guiones bajos.
+username-invalid-why = Los servidores expulsan a los jugadores cuyos nombres rompen estas reglas, y algunos plugins no logran guardar sus datos. El modo un jugador funciona igual.
+username-allow-anyway = Permitir de todos modos (para servidores que aceptan cualquier nombre)
+launch-account = Cuenta:
+launch-account-username = Escribir un nombre de usuario
+launch-account-offline = { $name } (sin conexión)
+launch-instance-profile = Esta instancia siempre se inicia con el perfil sin conexión { $name }. Cámbialo en la configuración de la instancia.
+launch-instances = Instancias:
+launch-instances-loading = Cargando instancias...
+launch-new-instance = Nueva instancia
+launch-shortcut-hint = Pulsa F1 para ver los atajos de teclado
+launch-delete-instance = Borrar instancia
+launch-edit-instance = Editar instancia
+launch-manage-mods = Gestionar mods
+launch-settings = Ajustes
+launch-servers = Servidores
+launch-stats = Estadísticas
+launch-downloads = Descargas
+launch-favorite = Añadir a favoritos
+launch-unfavorite = Quitar de favoritos
+launch-update-available = Actualización disponible: { $version }
+launch-worlds = Mundos
+launch-recent = Jugado recientemente
+launch-recent-as = Como { $username }
+launch-game-options = Opciones del juego
+launch-verify = Verificar archivos
+launch-open-files = Abrir archivos
+launch-play = Jugar
+launch-without-mods = Jugar sin mods
+launch-with-options = Iniciar con opciones...
+launch-with-options-title = Iniciar { $instance } con opciones
+launch-with-options-hint = Solo se usan esta vez y no se guardan en la instancia. Deja un campo vacío para omitirlo.
+launch-with-options-java-args = Argumentos de Java extra
+launch-with-options-game-args = Argumentos del juego extra
+launch-with-options-server = Servidor al que unirse
+launch-card-unknown = Desconocido
+launch-card-never-played = Nunca jugado
+launch-card-played-now = Jugado ahora mismo
+launch-card-played-minutes = Jugado hace { $count } min
+launch-card-played-hours = Jugado hace { $count } h
+launch-card-played-days = Jugado hace { $count } días
+
+java-install-starting-1 = Iniciando (1/2)
+java-install-starting-2 = Iniciando (2/2)
+java-install-downloading = Descargando ({ $progress }/{ $out-of }): { $name }
+java-install-done = ¡Listo!
+java-install-cancelling = Cancelando...
+
+edit-title = Editando instancia { $mod-type }: { $instance }
+edit-java-override = Usar una instalación de Java concreta en vez de la predeterminada. (Escribe la ruta, déjalo vacío si no)
+edit-java-override-placeholder = Ruta de Java
+edit-java-args = Argumentos de Java, que van después de los de los ajustes del launcher:
+edit-log4shell-mitigation = Proteger 1.7 a 1.18 del exploit Log4Shell (desactívalo solo si un mod necesita la configuración de registro de la versión)
+edit-version-type = Tipo de versión, que se muestra en la pantalla F3 y en los informes de errores (como el nombre de un modpack):
+edit-version-type-placeholder = El tipo de la propia versión, como release o snapshot
+edit-game-dir = Carpeta del juego, que se puede compartir con otras instancias para compartir mundos, paquetes de recursos y opciones:
+edit-game-dir-placeholder = La carpeta .minecraft de esta instancia
+edit-game-dir-from-instance = Usar la carpeta de otra instancia
+edit-sync-folder = Guardar también los mundos, las opciones y la lista de mods en la carpeta "sync" de esta instancia, para que Syncthing o Dropbox los compartan con tus otros ordenadores
+edit-java-version = Versión de Java (se descarga automáticamente al iniciar). Algunos mods necesitan un Java más nuevo.
+edit-java-version-default = Predeterminada (según la versión)
+edit-java-version-major = Java { $version }
+edit-offline-profile = Perfil sin conexión
+edit-offline-profile-hint = Inicia siempre esta instancia con uno de tus perfiles sin conexión, sin importar lo elegido en la pantalla de inicio.
+edit-offline-profile-none = Cuenta elegida en la pantalla de inicio
+edit-memory = Memoria asignada
+edit-memory-normal = Para Minecraft normal, asigna 2 - 3 GB
+edit-memory-old = Para versiones antiguas, asigna 512 MB - 1 GB
+edit-memory-heavy = Para modpacks pesados o distancias de renderizado muy altas, asigna 4 - 8 GB
+edit-memory-invalid = Escribe una cantidad como 4G o 4096M
+edit-memory-too-much = Aviso: eso es más del 90% de los { $total } de memoria de este ordenador, lo que puede ralentizarlo todo o hacer que el juego se cierre
+edit-memory-flags = Argumentos de Java: { $flags }
+edit-memory-initial = Memoria inicial (Java empieza con esta cantidad y crece hasta la memoria asignada)
+edit-snapshots = Instantáneas
+edit-snapshots-description = Se toman antes de instalar, actualizar o quitar un cargador y antes de reparar los detalles de la versión, para que puedas volver atrás si el cambio rompe el juego. Se guardan las 5 más recientes.
+edit-snapshots-small-files = Copiar los mods pequeños en las instantáneas (si no, los mods quitados hay que añadirlos a mano)
+edit-snapshots-none = Aún no hay instantáneas
+edit-roll-back = Volver atrás
+edit-template = Plantilla
+edit-template-description = Guarda la versión, el cargador, los mods de Modrinth, la configuración de Java y las opciones del juego de esta instancia, para crear nuevas instancias configuradas igual. Guardar con el nombre de una plantilla la reemplaza.
+edit-template-placeholder = Nombre de la plantilla
+edit-template-save = Guardar como plantilla
+snapshot-details = Antes de { $reason } ({ $loader }, { $mods } mods)
+snapshot-loader-install = instalar un cargador
+snapshot-loader-update = actualizar el cargador
+snapshot-loader-uninstall = quitar el cargador
+snapshot-version-json-redownload = reparar los detalles de la versión
+snapshot-fabric = Fabric { $version }
+snapshot-vanilla = Vanilla
+
+mods-install-fabric = Instalar Fabric
+mods-install-quilt = Instalar Quilt
+mods-install-forge = Instalar Forge
+mods-install-optifine = Instalar OptiFine
+mods-uninstall = Desinstalar { $mod-type }
+mods-loader-update = Ya está disponible { $mod-type } { $version }
+mods-loader-update-button = Actualizar loader
+mods-loader-updating = Actualizando...
+mods-installed = Mods instalados:
+mods-open-folder = Abrir carpeta de mods
+mods-coming-soon = Gestión de mods y tienda próximamente...
+mods-browse = Explorar mods
+mods-export = Copiar la lista de mods como:
+mods-export-text = Texto
+mods-export-markdown = Markdown
+mods-export-json = JSON
+mods-import = Instalar mods de una lista:
+mods-import-placeholder = Pega una lista de mods en JSON, o slugs de Modrinth como sodium, lithium
+mods-import-button = Instalar todos
+mod-browser-search-placeholder = Buscar en Modrinth...
+mod-browser-search = Buscar
+mod-browser-searching = Buscando...
+mod-browser-search-failed = No se pudieron buscar mods: { $error }
+mod-browser-no-results = No se encontraron mods.
+mod-browser-load-more = Cargar más
+mod-browser-loading-more = Cargando más...
+mod-browser-hit-info = por { $author } · { $downloads } descargas
+mod-browser-loading = Cargando...
+mod-browser-details-failed = No se pudo cargar este mod: { $error }
+mod-browser-install = Instalar
+mod-browser-open-page = Abrir en Modrinth
+mod-browser-downloads = { $count } descargas
+mod-browser-followers = { $count } seguidores
+mod-browser-updated = Actualizado el { $date }
+mod-browser-license = Licencia: { $license }
+
+create-loader-hint = Para instalar Fabric/Forge/OptiFine/Quilt, pulsa Gestionar mods después de crear la instancia
+create-select-version = Elige una versión
+create-selected-version = Versión: { $version }
+create-loading-versions = Cargando versiones...
+create-version-filter = Buscar versiones...
+create-version-favorites = Favoritas
+version-type-release = Versiones estables
+version-type-snapshot = Snapshots
+version-type-experimental = Inocentadas y experimentos
+version-type-beta = Beta
+version-type-alpha = Alpha
+create-name-placeholder = Nombre de la instancia...
+create-version-source-hint = De dónde obtener las versiones. BetterJSONs tiene versiones arregladas de las alfas antiguas (que a menudo no arrancan desde la lista de Mojang) y versiones antiguas que Mojang no incluye.
+create-custom-version-toggle = Avanzado: versión personalizada
+create-custom-version-hint = La ruta o URL de un JSON de versión, para clientes y versiones experimentales que no aparecen en la lista. Debe estar completo, no heredar de otra versión.
+create-custom-version-placeholder = Ruta del archivo o URL https://...
+create-server-pack-toggle = Avanzado: desde un paquete de servidor
+create-server-pack-hint = La ruta de un zip de paquete de servidor. Se copian sus mods y configuraciones, excepto los mods que Modrinth indica que son solo para servidores. Se usa la versión seleccionada si el paquete no indica la suya. Solo se admiten paquetes de Fabric.
+create-server-pack-placeholder = Ruta del .zip del paquete de servidor...
+create-template-hint = Empieza desde una plantilla, con su cargador, mods y configuración. Se puede elegir cualquier versión, los mods sin versión para ella se omiten.
+create-template-none = Sin plantilla
+create-template-delete = Eliminar plantilla
+create-template-summary = { $loader } en { $version }, { $count ->
+    [one] 1 mod
+   *[other] { $count } mods
+}
+create-assets-hint = ¿Descargar recursos? Si se desactiva, crear la instancia será MUCHO más rápido, pero no habrá sonido ni música en el juego
+create-assets-toggle = ¿Descargar recursos?
+create-button = Crear instancia
+create-server-hint = Elige la versión de Minecraft del servidor. Los jugadores necesitan la misma versión para unirse
+create-server-name-placeholder = Nombre del servidor...
+create-server-button = Crear servidor
+create-server-software-hint = Paper y Purpur son más rápidos que el servidor vanilla y pueden usar plugins. Solo tienen algunas versiones.
+create-progress-started = Descarga iniciada
+create-patch-notes-title = Novedades
+create-patch-notes-failed = No se pudieron cargar las notas de la versión: { $error }
+create-progress-cancelling = Cancelando...
+
+progress-started = Iniciado.
+progress-manifest = Descargando el manifiesto JSON.
+progress-version-json = Descargando el JSON de la versión.
+progress-assets = Descargando recurso { $progress } / { $out-of }.
+progress-libraries = Descargando librería { $progress } / { $out-of }.
+progress-jar = Descargando el archivo jar del juego.
+progress-logging-config = Descargando la configuración de registro.
+
+delete-confirm = ¿SEGURO que quieres BORRAR la instancia: { $instance }?
+delete-warning = Se perderán todos tus datos, incluidos los mundos.
+confirm-type-name = Escribe "{ $name }" para confirmar:
+confirm-delete-mod = ¿Borrar el mod { $file-name }?
+confirm-delete-mod-warning = El archivo del mod se eliminará de la carpeta de mods.
+confirm-delete-mod-yes = Sí, borrar el mod
+confirm-delete-plugin = ¿Borrar el plugin { $file-name }?
+confirm-delete-plugin-warning = El archivo del plugin se eliminará de la carpeta de plugins. Sus ajustes en la carpeta de plugins se conservan.
+confirm-delete-plugin-yes = Sí, borrar el plugin
+confirm-delete-server = ¿Seguro que quieres BORRAR el servidor: { $server }?
+confirm-uninstall-loader = ¿Desinstalar { $loader } de { $instance }?
+confirm-uninstall-loader-warning = La instancia volverá a ser vanilla. Tus mods se conservarán, pero no se cargarán.
+confirm-uninstall-loader-yes = Sí, desinstalar
+confirm-move-data-dir = ¿Mover los datos del launcher a { $dir }?
+confirm-move-data-dir-warning = Se moverán las instancias y los servidores. Puede tardar un rato; no cierres el launcher hasta que termine.
+confirm-move-data-dir-yes = Sí, moverlos
+confirm-restore-backup = ¿Restaurar el launcher desde { $path }?
+confirm-restore-backup-warning = Los ajustes, las configuraciones de instancias y los mundos de la copia reemplazarán a los actuales. Las instancias que no estén en la copia se conservan.
+confirm-restore-backup-yes = Sí, restaurarla
+confirm-delete-java = ¿Borrar estas instalaciones de Java sin usar: { $installs }?
+confirm-delete-java-warning = Ninguna instancia ni servidor las necesita ya. Se descargarán de nuevo si alguno las necesita.
+confirm-delete-java-yes = Sí, borrarlas
+confirm-delete-world = ¿Borrar el mundo { $world }?
+confirm-delete-world-warning = Las copias de seguridad del mundo se conservan, así que todavía se puede restaurar desde ellas.
+confirm-delete-world-yes = Sí, borrarlo
+confirm-restore-world = ¿Restaurar { $world } desde { $backup }?
+confirm-restore-world-warning = El mundo se reemplazará por la copia. Antes se hace una copia de seguridad, así que se puede deshacer.
+confirm-restore-world-yes = Sí, restaurarlo
+confirm-roll-back = ¿Volver { $instance } a la instantánea del { $snapshot }?
+confirm-roll-back-warning = El cargador, las configuraciones y las opciones vuelven a como estaban, y se borran los mods añadidos desde entonces.
+confirm-roll-back-yes = Sí, volver atrás
+confirm-launch-again = { $instance } ya se está ejecutando. ¿Iniciarlo otra vez?
+confirm-launch-again-warning = Dos copias de una instancia escribiendo en los mismos mundos los corrompen, así que la segunda copia se ejecuta en una carpeta de juego aparte con sus propios mundos. Se copian sus ajustes y mods.
+confirm-launch-again-yes = Iniciar de todos modos en una carpeta aparte
+confirm-java-too-old = Esta versión necesita Java { $required }, pero la instancia está configurada para usar Java { $selected }
+confirm-java-too-new = { $loader } para esta versión no funciona con Java más nuevo que { $max }, pero la instancia está configurada para usar Java { $selected }
+confirm-java-warning = Lo más probable es que el juego se cierre al iniciar.
+confirm-java-yes = Iniciar de todos modos
+confirm-java-fix = Instalar Java { $version } e iniciar
+delete-yes = Sí, borrar mis datos
+
+fabric-select-version = Elige la versión de Fabric para la instancia { $instance }
+fabric-versions-cached = Sin conexión: se muestran las versiones de la última vez que se cargaron
+fabric-progress-json = Descargando el JSON de Fabric...
+fabric-progress-library = Descargando biblioteca { $progress } / { $out-of }: { $name }
+fabric-progress-done = Terminando...
+
+error-title = Error: { $error }
+error-copy = Copiar error
+error-restore-backup = Restaurar la última copia que funcionaba
+error-redownload-version-json = Volver a descargar los detalles de la versión e iniciar
+error-copy-diagnostics = Copiar diagnóstico
+error-report = Informar de este error
+crash-title = QuantumLauncher se cerró inesperadamente
+crash-hint = El launcher falló la última vez que estuvo abierto. Se guardó un informe del fallo; informar de él ayuda a corregir el error.
+crash-message = Qué salió mal: { $message }
+crash-continue = Continuar
+crash-copy = Copiar informe del fallo
+crash-report = Informar de este fallo
+crash-open-folder = Abrir informes de fallos
+error-hint-config = No se pudieron leer los ajustes de la instancia (config.json). Si hay una copia de seguridad, restáurala; si no, revisa el archivo por si tiene errores.
+error-hint-version-json = Los detalles de la versión de la instancia (details.json) faltan o están dañados. Se pueden volver a descargar abajo.
+error-hint-classpath = No se pudieron preparar algunas bibliotecas del juego. Puede que falten o estén dañadas, o que la instalación de un cargador de mods no terminara. Reinstalar el cargador de mods o volver a crear la instancia suele arreglarlo.
+error-hint-java = No se pudo encontrar ni descargar Java. Revisa tu conexión a internet, o indica una ruta de Java en los ajustes de la instancia.
+error-hint-spawn = No se pudo iniciar Java. Si la instancia tiene una ruta de Java personalizada, comprueba que apunte a un ejecutable de java, o bórrala para usar el Java del launcher.
+error-report-description = Abre una incidencia en GitHub con el error, la versión del launcher, el sistema operativo y la configuración de la instancia. Revísala por si tiene algo privado antes de enviarla.
+
+toast-info = Información
+toast-success = Hecho
+toast-error = Error
+toast-dismiss = Cerrar
+toast-instance-created = Instancia creada
+toast-server-created = Servidor creado
+toast-create-cancelled = Cancelado, no se guardó nada
+toast-template-saved = Plantilla { $name } guardada
+toast-template-save-failed = No se pudo guardar la plantilla: { $error }
+toast-template-deleted = Plantilla { $name } eliminada
+toast-template-unmatched = { $count ->
+    [one] No se pudo instalar 1 mod
+   *[other] No se pudieron instalar { $count } mods
+} de la plantilla: { $mods }
+toast-sync-failed = No se pudo sincronizar { $instance }: { $error }
+toast-sync-conflict = { $instance } también se cambió en otro ordenador. La copia más antigua se guardó en { $path }
+toast-sync-missing-mods = { $instance } en tu otro ordenador tiene mods que este no tiene: { $mods }
+toast-server-pack-skipped = { $count ->
+    [one] Se omitió 1 mod solo para servidores
+   *[other] Se omitieron { $count } mods solo para servidores
+}: { $mods }
+toast-server-pack-review = { $count ->
+    [one] Revisa 1 mod que puede no servir en un cliente
+   *[other] Revisa { $count } mods que pueden no servir en un cliente
+}: { $mods }
+toast-launch-cancelled = Instalación de Java cancelada
+toast-fabric-installed = Fabric instalado
+toast-loader-updated = Se actualizó el loader de { $instance } a { $version }
+toast-loader-update-failed = No se pudo actualizar el loader: { $error }
+toast-fabric-install-failed = No se pudo instalar Fabric: { $error }
+toast-mod-delete-failed = No se pudo borrar el mod: { $error }
+toast-plugin-delete-failed = No se pudo borrar el plugin: { $error }
+toast-plugins-installed = Plugins instalados: { $count }
+toast-plugins-install-failed = No se pudieron instalar los plugins: { $error }
+toast-mods-installed = { $count ->
+    [one] 1 mod instalado
+   *[other] { $count } mods instalados
+}
+toast-mods-install-failed = No se pudo instalar el mod: { $error }
+toast-server-software-versions-failed = No se pudieron cargar las versiones de { $software }: { $error }
+toast-loader-uninstalled = Cargador de mods desinstalado
+toast-loader-uninstall-failed = No se pudo desinstalar el cargador de mods: { $error }
+toast-server-start-failed = No se pudo iniciar el servidor: { $error }
+toast-server-stopping = Deteniendo { $server }
+toast-server-command-failed = No se pudo enviar el comando: { $error }
+toast-server-properties-saved = Ajustes del servidor guardados
+toast-server-properties-save-failed = No se pudieron guardar los ajustes del servidor: { $error }
+toast-server-schedule-saved = Programación guardada
+toast-server-schedule-save-failed = No se pudo guardar la programación: { $error }
+toast-server-backup-started = Haciendo una copia de seguridad de { $server }
+toast-server-backup-done = Copia de seguridad de { $server } hecha
+toast-server-backup-failed = No se pudo hacer la copia de seguridad de { $server }: { $error }
+toast-world-backup-done = Copia de seguridad de { $world } hecha
+toast-world-backup-failed = No se pudo hacer la copia de seguridad de { $world }: { $error }
+toast-world-restore-done = Mundo restaurado
+toast-world-restore-failed = No se pudo restaurar el mundo: { $error }
+toast-roll-back-done = Vuelta atrás hecha
+toast-roll-back-missing-mods = Vuelta atrás hecha, pero estos mods no estaban en la instantánea y hay que añadirlos a mano: { $mods }
+toast-roll-back-failed = No se pudo volver atrás: { $error }
+toast-roll-back-game-running = Cierra el juego antes de volver atrás
+toast-world-game-running = Cierra el juego antes de cambiar sus mundos.
+toast-world-rename-failed = No se pudo renombrar el mundo: { $error }
+toast-world-delete-failed = No se pudo borrar el mundo: { $error }
+toast-world-copy-done = Mundo copiado como { $world }
+toast-world-copy-failed = No se pudo copiar el mundo: { $error }
+toast-worlds-saved = Ajustes de copias de seguridad guardados
+toast-worlds-save-failed = No se pudieron guardar los ajustes de copias de seguridad: { $error }
+toast-game-options-saved = Opciones del juego guardadas
+toast-game-options-save-failed = No se pudieron guardar las opciones del juego: { $error }
+toast-keybinds-imported = { $count ->
+    [one] Se importó 1 control. Guarda para conservarlo.
+   *[other] Se importaron { $count } controles. Guarda para conservarlos.
+}
+toast-keybinds-import-none = Esa instancia no tiene controles que funcionen en esta versión
+toast-keybinds-import-failed = No se pudieron importar los controles: { $error }
+toast-game-options-copied = Se copiaron las opciones de { $instance }
+toast-game-options-copy-failed = No se pudieron copiar las opciones del juego: { $error }
+toast-resource-pack-installed = Se añadió { $pack }
+toast-resource-pack-install-failed = No se pudo añadir el paquete de recursos: { $error }
+toast-server-restarting = Reiniciando { $server }
+toast-game-killed = { $instance } detenido
+toast-game-kill-failed = No se pudo detener { $instance }: { $error }
+toast-game-crashed = { $instance } se ha cerrado inesperadamente. Mira su registro en la barra lateral.
+toast-launch-without-mods-running = Cierra { $instance } antes de iniciarlo sin mods.
+toast-restore-mods-failed = No se pudieron devolver los mods de { $instance }: { $error }
+toast-log-shared = Subido a { $url }. Se ha copiado el enlace.
+toast-mod-list-copied = Lista de mods copiada
+toast-mod-list-export-failed = No se pudo exportar la lista de mods: { $error }
+toast-mod-list-unmatched = No se pudieron instalar estos mods: { $mods }
+toast-log-share-failed = No se pudo subir el registro: { $error }
+toast-log-no-crash-report = El juego no escribió ningún informe de error.
+toast-update-failed = No se pudo actualizar: { $error }
+toast-launch-instance-missing = No hay ninguna instancia llamada { $instance }
+toast-instance-removed = { $instance } se quitó de la carpeta de instancias.
+toast-tray-failed = No se pudo mostrar el icono de la bandeja: { $error }
+toast-config-save-failed = No se pudieron guardar los ajustes: { $error }
+toast-data-dir-moved = Se movieron los datos del launcher
+toast-data-dir-move-failed = No se pudieron mover los datos del launcher: { $error }
+toast-data-dir-games-running = Cierra todos los juegos y servidores antes de mover los datos del launcher.
+toast-file-restored = Se restauró la última copia del archivo que funcionaba
+toast-verify-fixed = Arreglado
+toast-verify-fix-failed = No se pudo arreglar: { $error }
+toast-version-json-downloading = Descargando los detalles de la versión...
+toast-version-json-downloaded = Se descargaron los detalles de la versión
+toast-launcher-backup-done = Se hizo una copia de seguridad del launcher
+toast-launcher-backup-failed = No se pudo hacer una copia de seguridad del launcher: { $error }
+toast-launcher-restore-done = Se restauró el launcher desde la copia
+toast-launcher-restore-failed = No se pudo restaurar la copia: { $error }
+toast-launcher-restore-games-running = Cierra todos los juegos y servidores antes de restaurar una copia.
+toast-profile-added = Se añadió el perfil sin conexión { $name }
+toast-profile-exists = Ya hay un perfil sin conexión llamado { $name }.
+toast-profile-skin-invalid = No se pudo usar la skin: { $error }
+
+settings-loading = Cargando ajustes...
+settings-appearance = Apariencia
+settings-theme = Tema:
+settings-theme-dark = Oscuro
+settings-theme-light = Claro
+settings-theme-system = Sistema
+settings-accent = Color de acento:
+settings-accent-purple = Morado
+settings-accent-blue = Azul
+settings-accent-teal = Turquesa
+settings-accent-green = Verde
+settings-accent-orange = Naranja
+settings-accent-red = Rojo
+settings-style = Estilo:
+settings-style-rounded = Redondeado
+settings-style-flat = Plano
+settings-language = Idioma:
+settings-show-news = Mostrar noticias de Minecraft en la pantalla principal
+settings-check-updates = Buscar actualizaciones del launcher al iniciar
+settings-accessibility = Accesibilidad
+settings-high-contrast = Alto contraste
+settings-large-text = Texto grande
+settings-large-text-hint = Reinicia el launcher para aplicar del todo el texto grande.
+settings-reduce-motion = Reducir movimiento
+settings-reduce-motion-hint = Las notificaciones se quedan hasta cerrarlas y los controles no crecen al usarlos.
+settings-instance-view = Lista de instancias:
+settings-instance-view-list = Desplegable
+settings-instance-view-grid = Cuadrícula con detalles
+settings-ui-scale = Escala de la interfaz: { $percent }%
+settings-account = Cuenta
+settings-default-username = Nombre de usuario predeterminado:
+settings-offline-profiles = Perfiles sin conexión
+settings-offline-profiles-hint = Guarda nombres de usuario para cambiar entre ellos en la pantalla de inicio. Cada uno recibe el mismo UUID que le daría el juego sin conexión, así tus mundos y los servidores sin conexión te reconocen. Son independientes de las cuentas en línea.
+settings-offline-profile = { $name }: { $username }
+settings-offline-profile-delete = Eliminar
+settings-offline-profile-name = Nombre del perfil
+settings-offline-profile-skin = Archivo PNG de skin (opcional)
+settings-offline-profile-add = Añadir perfil
+settings-java = Java
+settings-java-installs = Instalaciones de Java añadidas manualmente:
+settings-java-install-placeholder = Ruta del ejecutable de java
+settings-java-cleanup = Borrar instalaciones de Java sin usar
+settings-java-cleanup-running = Limpiando...
+settings-java-cleanup-none = No hay instalaciones de Java sin usar.
+settings-java-cleanup-removed = Borrado: { $removed }
+settings-java-cleanup-failed = No se pudo limpiar: { $error }
+settings-java-args = Argumentos de Java para todas las instancias (usa comillas para los que tengan espacios):
+settings-downloads = Descargas
+settings-parallel-downloads = Descargas simultáneas: { $count }
+settings-mirror = Espejo para los servidores de Mojang (vacío para ninguno):
+settings-proxy = Proxy (vacío para ninguno):
+settings-behavior = Comportamiento
+settings-close-on-launch = Cerrar el launcher cuando empiece el juego
+settings-options-source = Iniciar las instancias nuevas con las opciones de
+settings-options-source-none = Ninguna (opciones del juego)
+settings-options-source-hint = Copia su options.txt, controles y lista de servidores a cada instancia nueva.
+settings-tray-icon = Mostrar un icono en la bandeja del sistema
+settings-tray-icon-hint = Inicia instancias favoritas, detén juegos y abre el launcher desde la bandeja. Con él, "Cerrar el launcher cuando empiece el juego" oculta la ventana en su lugar.
+settings-tray-icon-unavailable = El icono de la bandeja solo es compatible con Linux.
+settings-discord-rpc = Mostrar en Discord a qué estás jugando
+settings-discord-rpc-hint = Muestra la instancia, su versión y cuánto tiempo llevas jugando en tu perfil de Discord, mientras Discord esté abierto.
+settings-discord-rpc-unavailable = Esta versión del launcher no es compatible con Discord.
+discord-playing = Jugando a { $instance }
+settings-debug = Depuración
+settings-launcher-logs = Registros del launcher
+settings-verbose-logging = Registro detallado
+settings-verbose-logging-hint = También registra los comandos de inicio (sin tokens de acceso), cada descarga y cuánto tardó cada paso. Útil al informar de un error. Ejecutar el launcher con --verbose hace lo mismo.
+launcher-logs-title = Registros del launcher
+launcher-logs-refresh = Actualizar
+launcher-logs-copy = Copiar
+launcher-logs-open = Abrir carpeta de registros
+settings-data = Datos
+settings-data-dir = Las instancias y los servidores se guardan en:
+settings-cache-dir = Las instalaciones de Java y los recursos, que se pueden volver a descargar, se guardan en:
+settings-data-dir-move = Mover datos aquí
+settings-data-dir-hint = Todo se mueve a la nueva carpeta, que puede estar en otra unidad. Los ajustes se quedan donde están. También se puede indicar con la variable de entorno { $variable }.
+settings-data-dir-moving = Moviendo datos...
+settings-data-dir-env = Indicado por la variable de entorno { $variable }.
+settings-portable = El modo portátil está activado, así que los ajustes se guardan junto al launcher.
+settings-backup = Copia de seguridad
+settings-backup-hint = Guarda tus ajustes y las configuraciones de instancias en un archivo, para restaurarlos si algo sale mal, como una mala actualización de un modpack. Los archivos del juego no se incluyen, ya que se pueden volver a descargar.
+settings-backup-include-saves = Incluir mundos
+settings-backup-create = Hacer copia del launcher
+settings-backup-running = Haciendo copia...
+settings-backup-open = Abrir carpeta de copias
+settings-backup-restore-path = Copia a restaurar:
+settings-backup-restore = Restaurar
+settings-backup-restoring = Restaurando...
+
+shortcut-title = Atajos de teclado
+shortcut-previous-instance = Seleccionar la instancia anterior
+shortcut-next-instance = Seleccionar la instancia siguiente
+shortcut-launch = Iniciar la instancia seleccionada
+shortcut-new-instance = Nueva instancia
+shortcut-back = Volver
+shortcut-search = Buscar instancias, mods y mundos
+shortcut-toggle-help = Mostrar u ocultar esta lista
+shortcut-key-up = Flecha arriba
+shortcut-key-down = Flecha abajo
+shortcut-key-enter = Intro
+shortcut-key-escape = Esc
+
+game-sidebar-title = Juegos en ejecución
+game-running = En ejecución desde hace { $uptime }\n{ $memory }
+game-exited = Terminado ({ $status })
+game-log-title = Registro de { $instance }
+game-log-missing = Este juego ya no se está ejecutando.
+game-log-share = Compartir registro
+game-log-share-crash-report = Compartir informe de error
+game-log-sharing = Subiendo...
+game-kill = Forzar cierre
+game-close = Cerrar
+
+servers-title = Servidores
+servers-empty = Todavía no hay servidores.
+servers-new = Nuevo servidor
+servers-delete = Borrar servidor
+servers-start = Iniciar servidor
+servers-stop = Detener servidor
+servers-running = En marcha desde hace { $uptime }
+servers-stopped = Detenido
+servers-open-console = Abrir consola
+servers-plugins = Plugins
+servers-external-title = Otros servidores
+servers-external-hint = Los servidores añadidos aquí muestran su estado en la pantalla de inicio, junto a los tuyos.
+servers-external-placeholder = Dirección, como play.example.com:25565
+servers-external-add = Añadir
+server-status-title = Servidores
+server-status-checking = Comprobando...
+server-status-offline = Sin conexión
+server-status-online = En línea: { $online }/{ $max } jugadores, { $latency } ms
+servers-properties = Ajustes del servidor
+servers-schedule = Copias y reinicios
+console-title = Consola de { $server }
+console-input-placeholder = Escribe un comando, como "say Hola"...
+console-send = Enviar
+console-show-more = Mostrar líneas anteriores
+console-stopping = Guardando los mundos y deteniendo...
+console-kill = Forzar cierre del servidor
+eula-title = Antes de iniciar { $server }
+eula-description = Solo pueden ejecutar servidores de Minecraft quienes acepten el Acuerdo de Licencia de Usuario Final (EULA) de Mojang. Trata temas como lo que puedes cobrar a los jugadores. Léelo antes de continuar.
+eula-read = Leer el EULA
+eula-agree = He leído y acepto el EULA de Minecraft
+eula-accept = Aceptar e iniciar el servidor
+plugins-title = Plugins de { $server }
+plugins-vanilla = Los servidores vanilla no pueden usar plugins. Crea un servidor Paper o Purpur para usarlos.
+plugins-restart-hint = Los plugins nuevos se cargan la próxima vez que se inicie el servidor.
+plugins-input-placeholder = Nombres de plugins, como luckperms
+plugins-input-hint = Usa el nombre de la dirección de la página del plugin. Separa varios plugins con espacios.
+plugins-install = Instalar
+plugins-installed = Plugins instalados:
+plugins-empty = No hay plugins instalados.
+plugins-open-folder = Abrir carpeta de plugins
+plugins-progress-starting = Empezando...
+plugins-progress-resolving = Buscando una versión de { $name }...
+plugins-progress-downloading = Descargando { $file-name }...
+plugins-progress-done = Listo
+server-properties-title = Ajustes de { $server }
+game-options-title = Opciones del juego de { $instance }
+game-options-hint = Se usan desde el próximo inicio, aunque el juego nunca se haya abierto.
+game-options-render-distance = Distancia de renderizado (chunks)
+game-options-render-distance-invalid = La distancia de renderizado debe ser un número de { $min } a { $max }.
+game-options-gui-scale = Escala de la interfaz
+game-options-gui-scale-auto = Automática
+game-options-max-fps = FPS máximos
+game-options-max-fps-hint = { $unlimited } significa sin límite.
+game-options-max-fps-invalid = Los FPS máximos deben ser un número de { $min } a { $max }.
+game-options-vsync = Usar VSync
+game-options-language = Código de idioma
+game-options-language-invalid = El idioma debe ser un código como es_es.
+game-options-copy-source = Copiar de otra instancia
+game-options-import-keybinds = Importar controles
+game-options-copy-all = Copiar todas las opciones y servidores
+game-options-copy-all-hint = Copiar todas las opciones las guarda al momento, reemplazando los cambios de arriba.
+game-options-resource-packs = Paquetes de recursos
+game-options-resource-packs-empty = No hay paquetes de recursos instalados.
+game-options-resource-pack-placeholder = Ruta a un paquete de recursos .zip o carpeta
+game-options-enable-new-pack = Activarlo, para que se use desde el próximo inicio
+game-options-save = Guardar
+server-properties-restart-hint = Los cambios se aplicarán la próxima vez que se inicie el servidor.
+server-properties-edit-raw = Editar como texto
+server-properties-edit-settings = Volver a los ajustes
+server-properties-save = Guardar
+server-properties-port = Puerto
+server-properties-port-invalid = El puerto debe ser un número del 1 al 65535.
+server-properties-gamemode = Modo de juego
+server-properties-gamemode-survival = Supervivencia
+server-properties-gamemode-creative = Creativo
+server-properties-gamemode-adventure = Aventura
+server-properties-gamemode-spectator = Espectador
+server-properties-difficulty = Dificultad
+server-properties-difficulty-peaceful = Pacífico
+server-properties-difficulty-easy = Fácil
+server-properties-difficulty-normal = Normal
+server-properties-difficulty-hard = Difícil
+server-properties-online-mode = Modo en línea
+server-properties-online-mode-hint = Solo pueden entrar jugadores con una cuenta de Microsoft. Desactívalo para jugar sin conexión o en LAN.
+server-properties-motd = Mensaje del día
+server-properties-motd-placeholder = Se muestra en la lista de servidores multijugador
+server-properties-motd-invalid = El mensaje del día no puede tener saltos de línea.
+server-properties-view-distance = Distancia de renderizado (chunks)
+server-properties-view-distance-invalid = La distancia de renderizado debe ser un número del { $min } al { $max }.
+server-schedule-title = Copias de seguridad y reinicios de { $server }
+server-schedule-hint = Solo ocurren mientras el launcher está abierto, contando desde que arranca el servidor. Deja un tiempo vacío para desactivarlo.
+server-schedule-never = Nunca
+server-schedule-backup-interval = Copiar el mundo cada (minutos)
+server-schedule-backup-retention = Copias a conservar
+server-schedule-restart-interval = Reiniciar el servidor cada (minutos)
+server-schedule-interval-invalid = Escribe un número entero de minutos, o déjalo vacío.
+server-schedule-retention-invalid = Conserva al menos 1 copia.
+server-schedule-save = Guardar
+server-schedule-backup-now = Copiar ahora
+server-schedule-open-backups = Abrir carpeta de copias
+
+news-title = Noticias
+news-loading = Cargando noticias...
+news-failed = No se pudieron cargar las noticias: { $error }
+news-read-more = Leer más
+
+welcome-title = ¡Bienvenido a Quantum Launcher!
+welcome-step = Paso { $step } de { $total }
+welcome-next = Siguiente
+welcome-skip = Omitir por ahora
+welcome-data-dir = ¿Dónde quieres guardar tus instancias?
+welcome-data-dir-hint = Las instancias y los servidores se guardarán en esta carpeta.
+welcome-data-dir-failed = No se pudo usar esta carpeta: { $error }
+welcome-account = Elige un nombre de usuario
+welcome-account-hint = Es el nombre que ven los demás jugadores. Puedes cambiarlo más tarde.
+welcome-theme = Elige un estilo
+welcome-first-instance = Crea tu primera instancia
+welcome-first-instance-hint = Una instancia es una instalación de Minecraft independiente, con su propia versión, mods y mundos.
+
+stats-title = Estadísticas
+download-queue-title = Descargas
+download-queue-empty = No se está descargando nada.
+download-queue-speed = Velocidad: { $speed }/s
+download-queue-kind-instance = Instancia
+download-queue-kind-mods = Mods para
+download-queue-kind-java = Java
+download-queue-active = Descargando
+download-queue-queued = En cola
+download-queue-paused = En pausa
+download-queue-progress = { $status } · { $size } · { $files } archivos
+download-queue-pause = Pausar
+download-queue-resume = Reanudar
+stats-empty = Todavía no has jugado. El tiempo de juego se cuenta desde que se inicia un juego hasta que se cierra.
+stats-total = Has jugado { $time } en { $sessions } sesiones
+stats-time = { $hours } h { $minutes } min
+stats-per-instance = Por instancia
+stats-per-week = Sesiones por semana
+stats-this-week = Esta semana
+stats-weeks-ago = { $count ->
+    [one] Hace 1 semana
+   *[other] Hace { $count } semanas
+}
+stats-versions = Versiones más jugadas
+
+worlds-title = Mundos de { $instance }
+worlds-empty = Esta instancia todavía no tiene mundos.
+worlds-backup = Hacer copia ahora
+worlds-open-backups = Abrir carpeta de copias
+worlds-no-backups = Todavía no hay copias
+worlds-restore = Restaurar
+worlds-rename = Renombrar
+worlds-rename-save = Guardar
+worlds-rename-cancel = Cancelar
+worlds-duplicate = Duplicar
+worlds-copy = Copiar a instancia
+worlds-copy-target = Copiar mundos a:
+worlds-delete = Borrar
+worlds-hardcore = Extremo
+worlds-folder = carpeta { $folder }
+worlds-unreadable = No se pudo leer level.dat
+worlds-deleted = Borrado, solo quedan copias
+worlds-backup-on-version-change = Hacer copia de todos los mundos antes de iniciar otra versión de Minecraft
+
+verify-title = Comprobando { $instance }
+verify-description = Comprueba que los archivos de la instancia estén todos y no estén dañados. Si algo está mal, intenta arreglarlo aquí antes de informar de un error.
+verify-checking = Comprobando...
+verify-failed = No se pudo comprobar la instancia: { $error }
+verify-config = Ajustes de la instancia (config.json)
+verify-version-json = Detalles de la versión (details.json)
+verify-client-jar = Jar de Minecraft
+verify-libraries = Bibliotecas
+verify-loader = Archivos del cargador de mods
+verify-asset-index = Índice de recursos
+verify-fix = Arreglar
+verify-fixing = Arreglando...
+
+update-title = QuantumLauncher { $version } (tienes { $current })
+update-changelog = Novedades
+update-install = Descargar e instalar ({ $file })
+update-installing = Descargando y comprobando la actualización...
+update-open-page = Abrir página de la versión
+update-no-download = Esta versión no tiene descarga para tu sistema, consíguela en la página de la versión.
+update-restart = ¡Actualizado! Reinicia el launcher para usar la nueva versión.
+update-installer-saved = El instalador se guardó en { $path }. Ábrelo para terminar de actualizar.
+
+tray-show = Abrir QuantumLauncher
+tray-launch = Jugar a { $instance }
+tray-kill = Detener { $instance }
+tray-running = Jugando a { $instance }
+tray-no-games = Ningún juego en ejecución
+
+search-placeholder = Buscar instancias, versiones, mods y mundos
+search-no-results = No se encontró nada.
+search-instance = Instancia
+search-mod = Mod
+search-world = Mundo
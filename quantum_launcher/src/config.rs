@@ -1,20 +1,370 @@
-use quantum_launcher_backend::{error::LauncherError, file_utils, io_err};
+use std::{fmt::Display, path::PathBuf, sync::OnceLock};
+
+use quantum_launcher_backend::{
+    check_username,
+    error::LauncherError,
+    file_utils::{self, DownloadSettings},
+    io_err,
+    json_structs::migration::{self, Migration},
+    offline_profiles::OfflineProfile,
+    skins::SkinSource,
+    InstanceInfo, VersionType,
+};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{
+    i18n::{self, Language},
+    stylesheet::{
+        color::AccentColor,
+        styles::{LauncherTheme, LauncherThemeLightness, StylePreset},
+    },
+    tr,
+};
+
+pub const UI_SCALE_RANGE: std::ops::RangeInclusive<f64> = 0.75..=2.0;
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    migrate: fill_in_missing_fields,
+}];
+
+/// The only fields without defaults, which hand-edited
+/// configs are most likely to be missing.
+fn fill_in_missing_fields(config: &mut Map<String, Value>) {
+    config
+        .entry("java_installs")
+        .or_insert_with(|| Value::Array(Vec::new()));
+    config
+        .entry("username")
+        .or_insert_with(|| Value::String(String::new()));
+}
 
+/// Global launcher settings, stored in `QuantumLauncher/config.json`.
+///
+/// Fields added after the first release use `#[serde(default)]`
+/// so that older config files still load. Changes that defaults
+/// can't cover go in a [`Migration`] instead.
 #[derive(Serialize, Deserialize)]
 pub struct LauncherConfig {
+    #[serde(default)]
+    pub config_version: u32,
     pub java_installs: Vec<String>,
     pub username: String,
+    /// How many files to download at the same time.
+    #[serde(default = "default_parallel_downloads")]
+    pub parallel_downloads: usize,
+    /// Base URL of a mirror for Mojang's download servers.
+    #[serde(default)]
+    pub download_mirror: Option<String>,
+    /// Proxy URL for all of the launcher's network requests.
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// Close the launcher once the game has started.
+    #[serde(default)]
+    pub close_on_launch: bool,
+    #[serde(default)]
+    pub theme: ThemeChoice,
+    #[serde(default)]
+    pub accent_color: AccentColor,
+    #[serde(default)]
+    pub style_preset: StylePreset,
+    #[serde(default)]
+    pub language: Language,
+    /// Scale factor for the whole interface, for HiDPI screens.
+    #[serde(default = "default_ui_scale")]
+    pub ui_scale: f64,
+    #[serde(default)]
+    pub instance_view: InstanceView,
+    /// Show Mojang's news feed on the launch screen.
+    #[serde(default = "default_show_news")]
+    pub show_news: bool,
+    /// Where instances, Java installs, etc. are stored,
+    /// if not in the same directory as this config.
+    #[serde(default)]
+    pub data_dir: Option<PathBuf>,
+    /// Whether the first-run setup wizard is done.
+    /// Configs from before the wizard existed count as done.
+    #[serde(default = "default_setup_complete")]
+    pub setup_complete: bool,
+    /// The window's size and position when the launcher was last closed.
+    #[serde(default)]
+    pub window: Option<WindowGeometry>,
+    /// Which kinds of versions the create instance menu lists.
+    /// Releases are always listed.
+    #[serde(default = "default_show_version_type")]
+    pub show_snapshots: bool,
+    #[serde(default = "default_show_version_type")]
+    pub show_old_betas: bool,
+    #[serde(default = "default_show_version_type")]
+    pub show_old_alphas: bool,
+    #[serde(default = "default_show_version_type")]
+    pub show_experimental: bool,
+    #[serde(default)]
+    pub accessibility: Accessibility,
+    /// Addresses of servers not run by the launcher,
+    /// whose status is shown on the launch screen.
+    #[serde(default)]
+    pub external_servers: Vec<String>,
+    /// Java arguments for every instance, added
+    /// before the instance's own arguments.
+    #[serde(default)]
+    pub java_args: Vec<String>,
+    /// Show the game being played on the user's Discord profile.
+    #[serde(default)]
+    pub discord_rpc: bool,
+    /// Show an icon in the system tray, with quick actions.
+    #[serde(default)]
+    pub tray_icon: bool,
+    /// Instances that can be launched from the tray icon.
+    #[serde(default)]
+    pub favorite_instances: Vec<String>,
+    /// Minecraft versions listed first when creating
+    /// an instance, by ID.
+    #[serde(default)]
+    pub favorite_versions: Vec<String>,
+    /// The instance whose game options, keybinds and
+    /// server list are copied to new instances.
+    #[serde(default)]
+    pub options_source: Option<String>,
+    /// Look for a newer launcher on GitHub when starting.
+    #[serde(default = "default_check_for_updates")]
+    pub check_for_updates: bool,
+    /// Log debugging details, like launch commands and
+    /// downloads. Also turned on by `--verbose`.
+    #[serde(default)]
+    pub verbose_logging: bool,
+    /// Launch offline accounts even if the username breaks
+    /// Minecraft's rules, for servers that don't check.
+    #[serde(default)]
+    pub allow_invalid_username: bool,
+    /// Saved offline accounts, each with its own username.
+    #[serde(default)]
+    pub offline_profiles: Vec<OfflineProfile>,
+    /// The name of the offline profile picked on the launch
+    /// screen. `None` means launching as `username`.
+    #[serde(default)]
+    pub selected_profile: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Accessibility {
+    /// Black and white colors with thick outlines.
+    pub high_contrast: bool,
+    /// Bigger text everywhere. Needs a restart, as the
+    /// default text size can't be changed while running.
+    pub large_text: bool,
+    /// Toasts stay until dismissed, and widgets
+    /// don't change size while in use.
+    pub reduce_motion: bool,
+}
+
+impl Accessibility {
+    const LARGE_TEXT_SIZE: u16 = 20;
+    /// Small text like hints isn't shrunk below this with large text.
+    const LARGE_TEXT_MIN_SIZE: u16 = 18;
+
+    pub fn default_text_size(&self) -> u16 {
+        if self.large_text {
+            Self::LARGE_TEXT_SIZE
+        } else {
+            16
+        }
+    }
+
+    pub fn min_text_size(&self) -> u16 {
+        if self.large_text {
+            Self::LARGE_TEXT_MIN_SIZE
+        } else {
+            0
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct WindowGeometry {
+    pub width: f32,
+    pub height: f32,
+    /// `None` if the window hasn't been moved.
+    pub position: Option<(i32, i32)>,
+    pub maximized: bool,
+}
+
+impl WindowGeometry {
+    pub const DEFAULT_WIDTH: f32 = 220.0;
+    pub const DEFAULT_HEIGHT: f32 = 550.0;
+    /// Saved sizes smaller than this are ignored,
+    /// so the window can't end up too small to use.
+    const MIN_SIZE: f32 = 100.0;
+
+    pub fn size(&self) -> iced::Size {
+        if self.width < Self::MIN_SIZE || self.height < Self::MIN_SIZE {
+            iced::Size::new(Self::DEFAULT_WIDTH, Self::DEFAULT_HEIGHT)
+        } else {
+            iced::Size::new(self.width, self.height)
+        }
+    }
+
+    pub fn position(&self) -> iced::window::Position {
+        match self.position {
+            Some((x, y)) => iced::window::Position::Specific(iced::Point::new(x as f32, y as f32)),
+            None => iced::window::Position::Default,
+        }
+    }
+}
+
+impl Default for WindowGeometry {
+    fn default() -> Self {
+        Self {
+            width: Self::DEFAULT_WIDTH,
+            height: Self::DEFAULT_HEIGHT,
+            position: None,
+            maximized: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeChoice {
+    #[default]
+    Dark,
+    Light,
+    /// Follow the operating system's dark/light setting.
+    System,
+}
+
+impl ThemeChoice {
+    pub const ALL: [ThemeChoice; 3] = [ThemeChoice::Dark, ThemeChoice::Light, ThemeChoice::System];
+}
+
+impl Display for ThemeChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemeChoice::Dark => write!(f, "{}", tr!("settings-theme-dark")),
+            ThemeChoice::Light => write!(f, "{}", tr!("settings-theme-light")),
+            ThemeChoice::System => write!(f, "{}", tr!("settings-theme-system")),
+        }
+    }
+}
+
+/// How the launch screen shows the list of instances.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InstanceView {
+    /// A dropdown with the instance names.
+    #[default]
+    List,
+    /// Cards with the icon, version, loader and last played time.
+    Grid,
+}
+
+impl InstanceView {
+    pub const ALL: [InstanceView; 2] = [InstanceView::List, InstanceView::Grid];
+}
+
+impl Display for InstanceView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InstanceView::List => write!(f, "{}", tr!("settings-instance-view-list")),
+            InstanceView::Grid => write!(f, "{}", tr!("settings-instance-view-grid")),
+        }
+    }
+}
+
+fn default_parallel_downloads() -> usize {
+    DownloadSettings::DEFAULT.parallel_downloads
+}
+
+fn default_ui_scale() -> f64 {
+    1.0
+}
+
+fn default_show_news() -> bool {
+    true
+}
+
+fn default_check_for_updates() -> bool {
+    true
+}
+
+fn default_setup_complete() -> bool {
+    true
+}
+
+fn default_show_version_type() -> bool {
+    true
+}
+
+impl Default for LauncherConfig {
+    fn default() -> Self {
+        Self {
+            config_version: migration::current_version(MIGRATIONS),
+            java_installs: Default::default(),
+            username: Default::default(),
+            parallel_downloads: default_parallel_downloads(),
+            download_mirror: None,
+            proxy: None,
+            close_on_launch: false,
+            theme: ThemeChoice::default(),
+            accent_color: AccentColor::default(),
+            style_preset: StylePreset::default(),
+            language: Language::default(),
+            ui_scale: default_ui_scale(),
+            instance_view: InstanceView::default(),
+            show_news: default_show_news(),
+            data_dir: None,
+            setup_complete: false,
+            window: None,
+            show_snapshots: default_show_version_type(),
+            show_old_betas: default_show_version_type(),
+            show_old_alphas: default_show_version_type(),
+            show_experimental: default_show_version_type(),
+            accessibility: Accessibility::default(),
+            external_servers: Vec::new(),
+            java_args: Vec::new(),
+            discord_rpc: false,
+            tray_icon: false,
+            favorite_instances: Vec::new(),
+            favorite_versions: Vec::new(),
+            options_source: None,
+            check_for_updates: default_check_for_updates(),
+            verbose_logging: false,
+            allow_invalid_username: false,
+            offline_profiles: Vec::new(),
+            selected_profile: None,
+        }
+    }
 }
 
 impl LauncherConfig {
+    /// The offline profile `instance` launches as: its own,
+    /// or else the one picked on the launch screen.
+    pub fn launch_profile(&self, instance: Option<&InstanceInfo>) -> Option<&OfflineProfile> {
+        let find_profile = |name: &String| {
+            self.offline_profiles
+                .iter()
+                .find(|profile| profile.name == *name)
+        };
+        instance
+            .and_then(|instance| instance.offline_profile.as_ref())
+            .and_then(find_profile)
+            .or_else(|| self.selected_profile.as_ref().and_then(find_profile))
+    }
+
+    /// Whose skin to show for launching `instance`. `None` if it's
+    /// a username that no online account could have.
+    pub fn skin_source(&self, instance: Option<&InstanceInfo>) -> Option<SkinSource> {
+        match self.launch_profile(instance) {
+            Some(profile) => Some(profile.skin_source()),
+            None => check_username(&self.username)
+                .is_ok()
+                .then(|| SkinSource::Username(self.username.clone())),
+        }
+    }
+
     pub fn load() -> Result<Self, LauncherError> {
-        let config_path = file_utils::get_launcher_dir()?.join("config.json");
+        let config_path = file_utils::get_config_dir()?.join("config.json");
         if !config_path.exists() {
-            let config = LauncherConfig {
-                java_installs: Default::default(),
-                username: Default::default(),
-            };
+            let config = LauncherConfig::default();
 
             std::fs::write(&config_path, serde_json::to_string(&config)?.as_bytes())
                 .map_err(io_err!(config_path))?;
@@ -22,15 +372,80 @@ impl LauncherConfig {
             return Ok(config);
         }
 
-        let config = std::fs::read_to_string(&config_path).map_err(io_err!(config_path))?;
-        Ok(serde_json::from_str(&config)?)
+        let config: Self = migration::read_migrated(&config_path, MIGRATIONS)?;
+        config.apply_download_settings();
+        file_utils::set_data_dir(config.data_dir.clone());
+        i18n::set_language(config.language);
+        Ok(config)
     }
 
     pub fn save(&self) -> Result<(), LauncherError> {
-        let config_path = file_utils::get_launcher_dir()?.join("config.json");
+        let config_path = file_utils::get_config_dir()?.join("config.json");
         let config = serde_json::to_string(&self)?;
 
         std::fs::write(&config_path, config.as_bytes()).map_err(io_err!(config_path))?;
         Ok(())
     }
+
+    pub fn get_ui_scale(&self) -> f64 {
+        self.ui_scale
+            .clamp(*UI_SCALE_RANGE.start(), *UI_SCALE_RANGE.end())
+    }
+
+    pub fn shows_version_type(&self, version_type: VersionType) -> bool {
+        match version_type {
+            VersionType::Release => true,
+            VersionType::Snapshot => self.show_snapshots,
+            VersionType::Experimental => self.show_experimental,
+            VersionType::Beta => self.show_old_betas,
+            VersionType::Alpha => self.show_old_alphas,
+        }
+    }
+
+    pub fn set_shows_version_type(&mut self, version_type: VersionType, show: bool) {
+        match version_type {
+            VersionType::Release => {}
+            VersionType::Snapshot => self.show_snapshots = show,
+            VersionType::Experimental => self.show_experimental = show,
+            VersionType::Beta => self.show_old_betas = show,
+            VersionType::Alpha => self.show_old_alphas = show,
+        }
+    }
+
+    pub fn get_theme(&self) -> LauncherTheme {
+        LauncherTheme {
+            lightness: match self.theme {
+                ThemeChoice::Dark => LauncherThemeLightness::Dark,
+                ThemeChoice::Light => LauncherThemeLightness::Light,
+                ThemeChoice::System => get_system_theme(),
+            },
+            accent: self.accent_color,
+            style_preset: self.style_preset,
+            high_contrast: self.accessibility.high_contrast,
+            reduce_motion: self.accessibility.reduce_motion,
+        }
+    }
+
+    /// Passes the download-related settings on to the backend.
+    pub fn apply_download_settings(&self) {
+        file_utils::set_download_settings(DownloadSettings {
+            parallel_downloads: self.parallel_downloads,
+            mirror: self.download_mirror.clone(),
+            proxy: self.proxy.clone(),
+        });
+    }
+}
+
+/// Detects the operating system theme.
+///
+/// This is only checked once, as asking the OS
+/// can be slow and the theme is needed every frame.
+fn get_system_theme() -> LauncherThemeLightness {
+    static IS_LIGHT: OnceLock<bool> = OnceLock::new();
+    let is_light = *IS_LIGHT.get_or_init(|| dark_light::detect() == dark_light::Mode::Light);
+    if is_light {
+        LauncherThemeLightness::Light
+    } else {
+        LauncherThemeLightness::Dark
+    }
 }
@@ -0,0 +1,100 @@
+//! Bug reports for the error screen.
+//!
+//! The report has the error along with some info about
+//! the system and the instance, so that issues can be
+//! reproduced without asking the user a lot of questions.
+
+use std::fmt::Write;
+
+use quantum_launcher_backend::{
+    file_utils, json_structs::json_instance_config::InstanceConfigJson,
+};
+
+/// GitHub rejects very long URLs, so the report in
+/// the issue link is cut off after this many bytes.
+const MAX_URL_REPORT_LEN: usize = 6000;
+
+/// Builds a Markdown report of the error, for pasting into an issue.
+pub fn build_report(error: &str, instance: Option<&str>) -> String {
+    let mut report = String::new();
+
+    _ = writeln!(report, "### Error\n```\n{error}\n```\n");
+    _ = writeln!(report, "### System");
+    _ = writeln!(report, "- Launcher version: {}", env!("CARGO_PKG_VERSION"));
+    _ = writeln!(
+        report,
+        "- OS: {} ({})",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+
+    if let Some(instance) = instance {
+        _ = writeln!(report, "\n### Instance config");
+        match read_instance_config(instance) {
+            Some(config) => _ = writeln!(report, "```json\n{config}\n```"),
+            None => _ = writeln!(report, "(could not read config.json)"),
+        }
+    }
+
+    sanitize(&report)
+}
+
+/// A Markdown report of a crash dump from [`crash_dump`](quantum_launcher_backend::crash_dump).
+pub fn build_crash_report(dump: &str) -> String {
+    sanitize(&format!(
+        "### Crash report\n```\n{}\n```\n",
+        dump.trim_end()
+    ))
+}
+
+/// A link to open a new GitHub issue, with the report filled in.
+pub fn issue_url(error: &str, report: &str) -> String {
+    let repository = env!("CARGO_PKG_REPOSITORY").trim_end_matches('/');
+    let title = error.lines().next().unwrap_or_default();
+
+    let mut body = report;
+    if body.len() > MAX_URL_REPORT_LEN {
+        let mut end = MAX_URL_REPORT_LEN;
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        body = &body[..end];
+    }
+
+    format!(
+        "{repository}/issues/new?title={}&body={}",
+        percent_encode(title),
+        percent_encode(body)
+    )
+}
+
+fn read_instance_config(instance: &str) -> Option<String> {
+    let config_path = file_utils::get_launcher_dir()
+        .ok()?
+        .join("instances")
+        .join(instance)
+        .join("config.json");
+    let config = InstanceConfigJson::read(&config_path).ok()?;
+    serde_json::to_string_pretty(&config).ok()
+}
+
+/// Hides the home directory, as it usually contains the user's real name.
+fn sanitize(report: &str) -> String {
+    let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE"));
+    match home {
+        Ok(home) if !home.is_empty() => report.replace(&home, "~"),
+        _ => report.to_owned(),
+    }
+}
+
+fn percent_encode(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            output.push(byte as char);
+        } else {
+            _ = write!(output, "%{byte:02X}");
+        }
+    }
+    output
+}
@@ -0,0 +1,146 @@
+//! Translations for the launcher's user interface.
+//!
+//! Strings live in `assets/locales/*.ftl`, using a small subset
+//! of the Fluent syntax: `key = value` lines, `#` comments,
+//! `{ $name }` placeholders and `\n` for line breaks.
+//!
+//! Use the [`tr!`] macro to look up a string:
+//! ```ignore
+//! tr!("launch-play");
+//! tr!("delete-confirm", instance = &name);
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    sync::{OnceLock, RwLock},
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    fn resource(self) -> &'static str {
+        match self {
+            Language::English => include_str!("../../assets/locales/en.ftl"),
+            Language::Spanish => include_str!("../../assets/locales/es.ftl"),
+        }
+    }
+}
+
+impl Display for Language {
+    /// Languages are always shown in their own language,
+    /// so people can find theirs in the picker.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Language::English => write!(f, "English"),
+            Language::Spanish => write!(f, "Español"),
+        }
+    }
+}
+
+type Bundle = HashMap<&'static str, String>;
+
+static CURRENT_LANGUAGE: RwLock<Language> = RwLock::new(Language::English);
+static BUNDLES: OnceLock<HashMap<Language, Bundle>> = OnceLock::new();
+
+pub fn set_language(language: Language) {
+    *CURRENT_LANGUAGE.write().unwrap() = language;
+}
+
+fn parse_resource(resource: &'static str) -> Bundle {
+    resource
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            Some((key.trim(), value.trim().replace("\\n", "\n")))
+        })
+        .collect()
+}
+
+fn bundles() -> &'static HashMap<Language, Bundle> {
+    BUNDLES.get_or_init(|| {
+        Language::ALL
+            .iter()
+            .map(|language| (*language, parse_resource(language.resource())))
+            .collect()
+    })
+}
+
+/// Looks up `key` in the current language, falling back
+/// to English and then to the key itself, and fills in
+/// the `{ $name }` placeholders from `args`.
+///
+/// Usually called through the [`tr!`] macro.
+pub fn translate(key: &str, args: &[(&str, String)]) -> String {
+    let language = *CURRENT_LANGUAGE.read().unwrap();
+    let bundles = bundles();
+
+    let Some(template) = bundles
+        .get(&language)
+        .and_then(|bundle| bundle.get(key))
+        .or_else(|| bundles.get(&Language::English)?.get(key))
+    else {
+        warn!("Missing translation for {key}");
+        return key.to_owned();
+    };
+
+    args.iter().fold(template.clone(), |text, (name, value)| {
+        text.replace(&format!("{{ ${} }}", name.replace('_', "-")), value)
+    })
+}
+
+/// Translates a string from `assets/locales/`.
+///
+/// Placeholders are passed as `name = value` pairs,
+/// with underscores in the name standing for dashes
+/// (`out_of` fills in `{ $out-of }`).
+#[macro_export]
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::i18n::translate($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::translate(
+            $key,
+            &[$((stringify!($name), $value.to_string())),+],
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translations_have_english_keys() {
+        let english = parse_resource(Language::English.resource());
+        for language in Language::ALL {
+            for key in parse_resource(language.resource()).keys() {
+                assert!(
+                    english.contains_key(key),
+                    "{language:?} has key {key} that isn't in English"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_translate_placeholders() {
+        assert_eq!(
+            translate("delete-confirm", &[("instance", "test".to_owned())]),
+            "Are you SURE you want to DELETE the Instance: test?"
+        );
+    }
+}
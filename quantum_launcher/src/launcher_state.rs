@@ -1,155 +1,1735 @@
 use std::{
-    path::PathBuf,
-    process::Child,
-    sync::{mpsc::Receiver, Arc},
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+    process::{Child, ChildStdin, ExitStatus},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
+use iced::widget;
 use quantum_launcher_backend::{
-    error::LauncherResult, io_err, json_structs::json_instance_config::InstanceConfigJson,
-    DownloadProgress, FabricVersion, GameLaunchResult, JavaInstallMessage,
+    cancel::CancelToken,
+    crash_dump::{self, CrashDump},
+    discord_rpc::DiscordRpc,
+    download_queue::QueueSnapshot,
+    error::{HasErrorCode, IoError, LaunchFailure, LaunchPhase, LauncherError, LauncherResult},
+    events::Event,
+    file_utils,
+    game_options::{self, GameOptions, GameSettingError, GameSettings},
+    instance_mod_installer::{
+        mod_list::{ImportResult, ModListFormat},
+        modrinth::{InstalledMod, ModInstallProgress},
+        modrinth_search::{ProjectDetails, SearchHit, SearchPage},
+        plugins::PluginSource,
+        server_pack::ServerPackImport,
+    },
+    integrity::{Check, CheckResult},
+    java_check::JavaProblem,
+    json_structs::{
+        json_instance_config::InstanceConfigJson, json_java_list::JavaVersion,
+        json_manifest::VersionSource, json_news::NewsEntry, json_patch_notes::PatchNote,
+    },
+    offline_profiles::OfflineProfile,
+    playtime::PlaytimeStats,
+    progress::ProgressReceiver,
+    self_update::{Release, UpdateOutcome},
+    server_files::{
+        self, Difficulty, GameMode, ServerProperties, ServerSettingError, ServerSettings,
+    },
+    server_ping::ServerStatus,
+    server_schedule::ServerSchedule,
+    server_software::ServerSoftware,
+    skins::{Face, SkinSource},
+    snapshots::Snapshot,
+    templates::InstanceTemplate,
+    world_backup::WorldBackups,
+    DownloadProgress, FabricInstallProgress, FabricVersionList, GameLaunchResult,
+    JavaInstallMessage, ListedVersion, VersionType,
 };
+pub use quantum_launcher_backend::{FolderChanges, InstanceInfo, InstanceRegistry};
+use tracing::error;
 
-use crate::config::LauncherConfig;
+use crate::{
+    config::{InstanceView, LauncherConfig, ThemeChoice, WindowGeometry},
+    i18n::Language,
+    markdown,
+    message_handler::format_memory,
+    search::SearchPalette,
+    shortcuts::Shortcut,
+    single_instance::InstanceGuard,
+    stylesheet::{color::AccentColor, styles::StylePreset},
+    tr,
+    tray::Tray,
+};
 
 #[derive(Debug, Clone)]
 pub enum Message {
     OpenDir(PathBuf),
+    /// Opens the selected instance's game directory,
+    /// which may be shared with other instances.
+    LaunchOpenFiles,
     InstallFabricEnd(Result<(), String>),
     InstallFabricVersionSelected(String),
-    InstallFabricVersionsLoaded(Result<Vec<FabricVersion>, String>),
+    InstallFabricVersionsLoaded(Result<FabricVersionList, String>),
     LaunchInstanceSelected(String),
+    /// Launches a recently played instance as the
+    /// account it was last played with.
+    LaunchRecent(String),
+    LaunchWithOptionsOpen,
+    LaunchWithOptionsUsernameInput(String),
+    LaunchWithOptionsJavaArgsInput(String),
+    LaunchWithOptionsGameArgsInput(String),
+    LaunchWithOptionsServerInput(String),
+    LaunchWithOptionsStart,
     LaunchUsernameSet(String),
+    LaunchAccountSelected(AccountChoice),
+    SkinLoaded(SkinSource, Result<Option<Face>, String>),
     LaunchStart,
-    DeleteInstanceMenu,
-    DeleteInstance,
+    LaunchStartWithoutMods,
+    ConfirmOpen(DestructiveAction),
+    ConfirmInput(String),
+    ConfirmAccept,
+    ConfirmCancel,
+    UninstallLoaderEnd(String, Result<(), String>),
+    /// The unused Java installs, to confirm deleting them.
+    JavaCleanupFound(Result<Vec<String>, String>),
+    DeleteUnusedJavaEnd(Result<Vec<String>, String>),
     LaunchScreenOpen,
-    LaunchEnd(GameLaunchResult),
-    LaunchJavaInstallProgressUpdate,
+    LaunchEnd(String, GameLaunchResult),
+    LaunchJavaChecked(String, Result<Option<JavaProblem>, String>),
+    /// Switches the instance to the launcher's
+    /// Java of this major version, and launches it.
+    LaunchFixJava(String, usize),
+    LaunchJavaInstallProgress(JavaInstallMessage),
+    LaunchJavaInstallCancel,
     CreateInstanceScreenOpen,
-    CreateInstanceVersionsLoaded(Result<Arc<Vec<String>>, String>),
+    CreateInstanceVersionsLoaded(VersionSource, Result<Arc<Vec<ListedVersion>>, String>),
+    CreateInstanceVersionSourceSelected(VersionSource),
+    CreateInstanceCustomVersionToggle(bool),
+    CreateInstanceCustomVersionInput(String),
+    CreateInstanceServerPackToggle(bool),
+    CreateInstanceServerPackInput(String),
+    CreateInstanceServerPackEnd(Result<ServerPackImport, String>),
+    CreateInstanceTemplateSelected(TemplateChoice),
+    CreateInstanceTemplateDelete,
+    /// The template's mods that couldn't be installed, with why.
+    CreateInstanceTemplateEnd(Result<Vec<(String, String)>, String>),
     CreateInstanceVersionSelected(String),
+    CreateInstancePatchNotesLoaded(Result<Arc<Vec<PatchNote>>, String>),
+    CreateInstanceVersionFilterInput(String),
+    CreateInstanceVersionGroupToggle(VersionType),
+    CreateInstanceVersionTypeShow(VersionType, bool),
+    CreateInstanceVersionFavoriteToggle(String),
     CreateInstanceNameInput(String),
     CreateInstanceStart,
     CreateInstanceEnd(Result<(), String>),
     CreateInstanceChangeAssetToggle(bool),
-    CreateInstanceProgressUpdate,
+    CreateInstanceProgress(DownloadProgress),
+    CreateInstanceCancel,
     EditInstance,
     EditInstanceJavaOverride(String),
+    EditInstanceJavaVersionSelected(JavaVersionChoice),
     EditInstanceMemoryChanged(f32),
+    EditInstanceInitialMemoryChanged(f32),
+    EditInstanceMemoryInput(String),
+    EditInstanceJavaArgsInput(String),
+    EditInstanceGameDirInput(String),
+    EditInstanceVersionTypeInput(String),
+    EditInstanceGameDirFromInstance(String),
+    EditInstanceOfflineProfileSelected(InstanceProfileChoice),
     EditInstanceSave,
+    EditInstanceSnapshotSmallFilesToggle(bool),
+    EditInstanceLog4ShellToggle(bool),
+    EditInstanceSyncFolderToggle(bool),
+    EditInstanceRollBackEnd(String, Result<Vec<String>, String>),
+    EditInstanceTemplateNameInput(String),
+    EditInstanceTemplateSave,
+    EditInstanceTemplateSaved(Result<InstanceTemplate, String>),
     ManageModsScreenOpen,
+    ManageModsUpdateLoader,
+    /// The instance whose loader was updated.
+    ManageModsUpdateLoaderEnd(String, Result<(), String>),
+    ManageModsExport(ModListFormat),
+    ManageModsExportEnd(Result<String, String>),
+    ManageModsImportInput(String),
+    ManageModsImport,
+    ManageModsImportProgress(ModInstallProgress),
+    ManageModsImportEnd(String, Result<ImportResult, String>),
+    LoaderUpdateCheck,
+    ModBrowserOpen,
+    ModBrowserQueryInput(String),
+    /// Sent a moment after typing, with the search it was for.
+    ModBrowserSearchDebounced(u64),
+    ModBrowserSearch,
+    /// The search the page is for.
+    ModBrowserResults(u64, Result<SearchPage, String>),
+    ModBrowserScrolled(widget::scrollable::Viewport),
+    ModBrowserLoadMore,
+    /// The project ID of the mod to show.
+    ModBrowserSelect(String),
+    ModBrowserDetailsLoaded(String, Result<ProjectDetails, String>),
+    /// An icon or gallery image, by its URL.
+    ModBrowserImageLoaded(String, Result<Vec<u8>, String>),
+    ModBrowserInstall,
+    /// The instance the mods were installed into.
+    ModBrowserInstallEnd(String, Result<Vec<InstalledMod>, String>),
+    ModBrowserProgress(ModInstallProgress),
+    /// Instances with a newer loader, and its version.
+    LoaderUpdatesChecked(Vec<(String, String)>),
     InstallFabricClicked,
+    InstallFabricProgress(FabricInstallProgress),
     InstallFabricScreenOpen,
     ErrorCopy,
+    ErrorCopyDiagnostics,
+    ErrorReport,
+    ErrorRestoreBackup,
+    ErrorRedownloadVersionJson,
+    /// The instance whose version details were downloaded.
+    ErrorRedownloadVersionJsonEnd(String, Result<(), String>),
+    LauncherSettingsOpen,
+    StatsScreenOpen,
+    DownloadQueueScreenOpen,
+    DownloadQueueRefresh,
+    /// The ID of the job, for the rest of these.
+    DownloadQueuePause(u64),
+    DownloadQueueResume(u64),
+    DownloadQueueMoveUp(u64),
+    DownloadQueueMoveDown(u64),
+    WorldsScreenOpen,
+    GameOptionsScreenOpen,
+    GameOptionsRenderDistanceInput(String),
+    GameOptionsGuiScaleSelected(GuiScaleChoice),
+    GameOptionsMaxFpsInput(String),
+    GameOptionsVsyncToggle(bool),
+    GameOptionsLanguageInput(String),
+    GameOptionsCopySourceSelected(String),
+    GameOptionsImportKeybinds,
+    GameOptionsCopyAll,
+    GameOptionsResourcePackToggle(String, bool),
+    GameOptionsResourcePackInput(String),
+    GameOptionsEnableNewPackToggle(bool),
+    GameOptionsResourcePackInstall,
+    GameOptionsResourcePackInstallEnd(Result<String, String>),
+    GameOptionsSave,
+    VerifyScreenOpen,
+    VerifyEnd(Result<Vec<CheckResult>, String>),
+    VerifyFix(Check),
+    VerifyFixEnd(Result<(), String>),
+    WorldsBackup(String),
+    WorldsBackupEnd(String, Result<PathBuf, String>),
+    WorldsRestoreEnd(Result<(), String>),
+    WorldsRetentionInput(String),
+    WorldsBackupOnVersionChangeToggle(bool),
+    WorldsSave,
+    WorldsRenameStart(String),
+    WorldsRenameInput(String),
+    WorldsRenameSave,
+    WorldsRenameCancel,
+    WorldsDuplicate(String),
+    WorldsCopyTargetSelected(String),
+    WorldsCopy(String),
+    WorldsCopyEnd(Result<String, String>),
+    LauncherSettingsUsernameSet(String),
+    LauncherSettingsJavaInstallInput(String),
+    LauncherSettingsJavaInstallAdd,
+    LauncherSettingsJavaInstallRemove(usize),
+    LauncherSettingsJavaCleanup,
+    LauncherSettingsJavaArgsInput(String),
+    LauncherSettingsParallelDownloadsChanged(f32),
+    LauncherSettingsMirrorInput(String),
+    LauncherSettingsProxyInput(String),
+    LauncherSettingsCloseOnLaunchToggle(bool),
+    LauncherSettingsDiscordRpcToggle(bool),
+    LauncherSettingsThemeSelected(ThemeChoice),
+    LauncherSettingsAccentSelected(AccentColor),
+    LauncherSettingsStylePresetSelected(StylePreset),
+    LauncherSettingsLanguageSelected(Language),
+    LauncherSettingsUiScaleChanged(f64),
+    LauncherSettingsUiScaleApply,
+    LauncherSettingsInstanceViewSelected(InstanceView),
+    LauncherSettingsDataDirInput(String),
+    LauncherSettingsDataDirMove,
+    LauncherSettingsDataDirMoveEnd(Result<PathBuf, String>),
+    LauncherSettingsBackupSavesToggle(bool),
+    LauncherSettingsBackup,
+    LauncherSettingsBackupEnd(Result<PathBuf, String>),
+    LauncherSettingsRestoreInput(String),
+    LauncherSettingsRestore,
+    LauncherSettingsRestoreEnd(Result<(), String>),
+    Shortcut(Shortcut),
+    GameProcessesUpdate,
+    BackendEvent(Event),
+    GameLogOpen(u32),
+    TrayPoll,
+    SearchInput(String),
+    /// Opens a search result, or the highlighted one if `None`.
+    SearchOpenResult(Option<usize>),
+    InstanceFoldersChanged(FolderChanges),
+    OtherLaunchersPoll,
+    LaunchFavoriteToggle,
+    LauncherSettingsTrayIconToggle(bool),
+    LauncherSettingsOptionsSourceSelected(OptionsSourceChoice),
+    UpdateCheckResult(Result<Option<Release>, String>),
+    UpdateScreenOpen,
+    /// Opens the launcher logs screen, or refreshes it.
+    LauncherLogsOpen,
+    LauncherLogsCopy,
+    LauncherSettingsVerboseLoggingToggle(bool),
+    LauncherSettingsAllowInvalidUsernameToggle(bool),
+    LauncherSettingsProfileNameInput(String),
+    LauncherSettingsProfileUsernameInput(String),
+    LauncherSettingsProfileSkinInput(String),
+    LauncherSettingsProfileAdd,
+    LauncherSettingsProfileDelete(String),
+    CrashRecoveryCopy,
+    CrashRecoveryReport,
+    UpdateInstall,
+    UpdateInstallEnd(Result<UpdateOutcome, String>),
+    LauncherSettingsCheckUpdatesToggle(bool),
+    GameLogShare(u32),
+    GameLogShareCrashReport(u32),
+    GameLogShareEnd(Result<String, String>),
+    GameKill(u32),
+    GameClose(u32),
+    ToastDismiss(usize),
+    ToastsUpdate,
+    NewsLoaded(Result<Vec<NewsEntry>, String>),
+    OpenLink(String),
+    LauncherSettingsShowNewsToggle(bool),
+    LauncherSettingsHighContrastToggle(bool),
+    LauncherSettingsLargeTextToggle(bool),
+    LauncherSettingsReduceMotionToggle(bool),
+    WelcomeDataDirInput(String),
+    WelcomeNext,
+    WelcomeBack,
+    WelcomeFinish {
+        create_instance: bool,
+    },
+    WindowResized {
+        width: u32,
+        height: u32,
+    },
+    WindowMoved {
+        x: i32,
+        y: i32,
+    },
+    WindowCloseRequested,
+    WindowClose {
+        maximized: bool,
+    },
+    ServersScreenOpen,
+    ServerSelected(String),
+    ServerCreateScreenOpen,
+    ServerStart,
+    ServerStartEnd(String, GameLaunchResult),
+    ServerStop(u32),
+    ServerConsoleInput(String),
+    ServerConsoleSubmit,
+    ServerConsoleShowMore,
+    ServerEulaAgreeToggle(bool),
+    ServerEulaAccept,
+    ServerPropertiesOpen,
+    ServerPropertiesPortInput(String),
+    ServerPropertiesGameModeSelected(GameModeChoice),
+    ServerPropertiesDifficultySelected(DifficultyChoice),
+    ServerPropertiesOnlineModeToggle(bool),
+    ServerPropertiesMotdInput(String),
+    ServerPropertiesViewDistanceInput(String),
+    ServerPropertiesRawToggle,
+    ServerPropertiesRawEdit(widget::text_editor::Action),
+    ServerPropertiesSave,
+    ServerCreateSoftwareSelected(ServerSoftware),
+    ServerCreateSoftwareVersionsLoaded(ServerSoftware, Result<Option<Vec<String>>, String>),
+    ServerPluginsOpen,
+    ServerPluginsSourceSelected(PluginSource),
+    ServerPluginsInput(String),
+    ServerPluginsInstall,
+    ServerPluginsInstallEnd(String, Result<Vec<InstalledMod>, String>),
+    ServerPluginsProgress(ModInstallProgress),
+    ServerStatusRefresh,
+    ServerStatusLoaded(String, Result<ServerStatus, String>),
+    ServersExternalInput(String),
+    ServersExternalAdd,
+    ServersExternalRemove(String),
+    ServerScheduleOpen,
+    ServerScheduleBackupIntervalInput(String),
+    ServerScheduleBackupRetentionInput(String),
+    ServerScheduleRestartIntervalInput(String),
+    ServerScheduleSave,
+    ServerScheduleBackupNow,
+    ServerScheduleTick,
+    ServerBackupEnd(String, Result<PathBuf, String>),
 }
 
 #[derive(Default)]
 pub struct MenuLaunch {
     pub selected_instance: Option<String>,
     pub java_install_progress: Option<JavaInstallProgress>,
+    /// How to launch the selected instance the next time only,
+    /// like as the account a recently played instance last used.
+    pub overrides: LaunchOverrides,
+}
+
+/// Changes to how an instance is launched, for one launch.
+/// They're never saved to the instance's config.
+#[derive(Debug, Clone, Default)]
+pub struct LaunchOverrides {
+    /// Instead of the username in the launcher's config.
+    pub username: Option<String>,
+    /// The name of an offline profile to launch as, instead
+    /// of the usual one. Ignored if it's been deleted.
+    pub profile: Option<String>,
+    pub java_args: Vec<String>,
+    pub game_args: Vec<String>,
+    /// A server to join once the game has started.
+    pub server: Option<String>,
+}
+
+/// Asks for [`LaunchOverrides`] before launching an instance.
+pub struct MenuLaunchWithOptions {
+    pub selected_instance: String,
+    /// Empty for the launcher's username.
+    pub username: String,
+    /// The arguments as typed, split on launch.
+    pub java_args_input: String,
+    pub game_args_input: String,
+    /// Empty to not join a server.
+    pub server: String,
 }
 
 pub struct JavaInstallProgress {
     pub num: f32,
-    pub recv: Receiver<JavaInstallMessage>,
+    pub recv: ProgressReceiver<JavaInstallMessage>,
     pub message: String,
+    pub cancel: CancelToken,
 }
 
 pub struct MenuEditInstance {
     pub selected_instance: String,
     pub config: InstanceConfigJson,
+    /// `config` as it was last saved, in JSON, so that
+    /// it's only saved again once something changes.
+    pub saved_config: String,
     pub slider_value: f32,
     pub slider_text: String,
+    /// The allocated memory as typed, like `4G`.
+    pub memory_input: String,
+    /// The computer's total memory, for warning
+    /// when too much of it is allocated.
+    pub system_memory_mb: Option<usize>,
+    /// For the initial memory (`-Xms`), which
+    /// is kept at most the allocated memory.
+    pub initial_slider_value: f32,
+    pub initial_slider_text: String,
+    /// The Java arguments as typed, which are
+    /// split into `config.java_args`.
+    pub java_args_input: String,
+    /// The instances whose `.minecraft` this one can share.
+    pub other_instances: Vec<String>,
+    /// The names of the offline profiles it can launch as.
+    pub offline_profiles: Vec<String>,
+    /// Newest first.
+    pub snapshots: Vec<Snapshot>,
+    /// Saving is paused while rolling back, so that
+    /// the old config isn't written over the restored one.
+    pub rolling_back: bool,
+    /// What to call the template if this instance
+    /// is saved as one.
+    pub template_name_input: String,
+}
+
+impl MenuEditInstance {
+    /// Sets the allocated memory, keeping the
+    /// initial memory at most that much.
+    pub fn set_memory(&mut self, ram_in_mb: usize) {
+        self.config.ram_in_mb = ram_in_mb;
+        self.slider_value = f32::log2(ram_in_mb as f32);
+        self.slider_text = format_memory(ram_in_mb);
+        if self.initial_slider_value > self.slider_value {
+            self.set_initial_memory(self.slider_value);
+        }
+    }
+
+    pub fn set_initial_memory(&mut self, slider_value: f32) {
+        self.initial_slider_value = slider_value;
+        let initial_ram_in_mb = 2f32.powf(slider_value) as usize;
+        self.config.initial_ram_in_mb = Some(initial_ram_in_mb);
+        self.initial_slider_text = format_memory(initial_ram_in_mb);
+    }
+}
+
+/// An entry in the Java version dropdown of [`MenuEditInstance`].
+///
+/// `None` means the Java version from the version JSON is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JavaVersionChoice(pub Option<usize>);
+
+impl JavaVersionChoice {
+    pub fn all() -> Vec<Self> {
+        std::iter::once(JavaVersionChoice(None))
+            .chain(
+                JavaVersion::MANAGED_MAJOR_VERSIONS
+                    .iter()
+                    .map(|n| JavaVersionChoice(Some(*n))),
+            )
+            .collect()
+    }
+}
+
+impl Display for JavaVersionChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(major_version) => write!(
+                f,
+                "{}",
+                tr!("edit-java-version-major", version = major_version)
+            ),
+            None => write!(f, "{}", tr!("edit-java-version-default")),
+        }
+    }
 }
 
 pub struct MenuEditMods {
     pub selected_instance: String,
     pub config: InstanceConfigJson,
+    /// File names of the mods in the `mods` folder.
+    pub mods: Vec<String>,
+    pub updating_loader: bool,
+    /// A pasted mod list, or Modrinth slugs, to install.
+    pub import_input: String,
+    /// `Some` while the list is being installed.
+    pub import_progress: Option<PluginProgress>,
+}
+
+/// Searching Modrinth for mods to install into an instance,
+/// with the selected mod's details next to the results.
+pub struct MenuModBrowser {
+    pub selected_instance: String,
+    pub query: String,
+    /// `None` while searching. More pages are
+    /// added on as the user scrolls down.
+    pub results: Option<Result<Vec<SearchHit>, String>>,
+    /// How many results there are across all pages.
+    pub total_hits: usize,
+    pub loading_more: bool,
+    /// Goes up with every new search, so that results
+    /// of older ones (and typing in between) are ignored.
+    pub search_id: u64,
+    pub selected: Option<SelectedMod>,
+    /// Icons and gallery images, by URL.
+    pub images: HashMap<String, widget::image::Handle>,
+    /// `Some` while the selected mod is being installed.
+    pub progress: Option<PluginProgress>,
+}
+
+pub struct SelectedMod {
+    pub project_id: String,
+    /// `None` while loading.
+    pub details: Option<Result<ProjectDetails, String>>,
+    /// The description, split up for showing.
+    pub description: Vec<markdown::Block>,
 }
 
 pub struct MenuCreateInstance {
+    /// Whether a server is being created instead of a client instance.
+    pub is_server: bool,
     pub instance_name: String,
     pub selected_version: Option<String>,
-    pub versions: Vec<String>,
-    pub progress_receiver: Option<Receiver<DownloadProgress>>,
+    pub versions: Vec<ListedVersion>,
+    /// Only versions containing this are shown.
+    pub version_filter: String,
+    /// Groups in the version list that are folded away.
+    pub collapsed_groups: HashSet<VersionType>,
+    pub progress_receiver: Option<ProgressReceiver<DownloadProgress>>,
     pub progress_number: Option<f32>,
     pub progress_text: Option<String>,
+    /// Set while the instance is being created.
+    pub cancel: Option<CancelToken>,
     pub download_assets: bool,
+    /// Where the version list comes from. Servers always
+    /// use Mojang's.
+    pub version_source: VersionSource,
+    /// The file path or URL of a version JSON to create the
+    /// instance from instead of a listed version, when
+    /// "Advanced: custom version" is ticked.
+    pub custom_version_json: Option<String>,
+    /// The path of a server pack zip to create the instance
+    /// from, when "Advanced: from a server pack" is ticked.
+    /// The selected version is only used if the pack
+    /// doesn't say its own.
+    pub server_pack: Option<String>,
+    /// Shown next to the selected version, if it has any.
+    /// `None` while loading.
+    pub patch_notes: Option<Result<Arc<Vec<PatchNote>>, String>>,
+    /// Only used for servers.
+    pub server_software: ServerSoftware,
+    /// The versions [`Self::server_software`] has builds for,
+    /// or `None` if every version can be picked (or while
+    /// they're being loaded).
+    pub server_software_versions: Option<Vec<String>>,
+    /// The saved instance templates, for client instances.
+    pub templates: Vec<InstanceTemplate>,
+    /// The template to create the instance from, if any.
+    pub template: Option<InstanceTemplate>,
 }
 
-pub struct MenuDeleteInstance {
-    pub selected_instance: String,
+/// An action that can't be undone, so it has
+/// to be confirmed in a [`MenuConfirm`] first.
+#[derive(Debug, Clone)]
+pub enum DestructiveAction {
+    DeleteInstance {
+        instance: String,
+    },
+    DeleteMod {
+        instance: String,
+        file_name: String,
+    },
+    UninstallLoader {
+        instance: String,
+        loader: String,
+    },
+    DeleteServer {
+        server: String,
+    },
+    DeletePlugin {
+        server: String,
+        file_name: String,
+    },
+    /// Moves the launcher's data (instances, Java installs, etc.)
+    /// to another directory.
+    MoveDataDir {
+        to: PathBuf,
+    },
+    /// Restores a launcher backup, overwriting
+    /// the configs and worlds in it.
+    RestoreBackup {
+        path: PathBuf,
+    },
+    /// Deletes Java installs that no instance needs anymore.
+    DeleteJavaInstalls {
+        installs: Vec<String>,
+    },
+    DeleteWorld {
+        instance: String,
+        world: String,
+    },
+    /// Replaces a world with one of its backups.
+    RestoreWorld {
+        instance: String,
+        backup: PathBuf,
+    },
+    /// Launches an instance that's already running, in a
+    /// separate game folder so that its worlds aren't shared.
+    /// Puts an instance back the way it was
+    /// before a loader or version change.
+    RollBack {
+        instance: String,
+        snapshot: PathBuf,
+    },
+    LaunchAgain {
+        instance: String,
+    },
+    /// Launches an instance with a Java that
+    /// probably won't run its version or loader.
+    IncompatibleJava {
+        instance: String,
+        problem: JavaProblem,
+    },
+}
+
+impl DestructiveAction {
+    pub fn title(&self) -> String {
+        match self {
+            DestructiveAction::DeleteInstance { instance } => {
+                tr!("delete-confirm", instance = instance)
+            }
+            DestructiveAction::DeleteServer { server } => {
+                tr!("confirm-delete-server", server = server)
+            }
+            DestructiveAction::DeleteMod { file_name, .. } => {
+                tr!("confirm-delete-mod", file_name = file_name)
+            }
+            DestructiveAction::DeletePlugin { file_name, .. } => {
+                tr!("confirm-delete-plugin", file_name = file_name)
+            }
+            DestructiveAction::MoveDataDir { to } => {
+                tr!("confirm-move-data-dir", dir = to.to_string_lossy())
+            }
+            DestructiveAction::RestoreBackup { path } => {
+                tr!("confirm-restore-backup", path = path.to_string_lossy())
+            }
+            DestructiveAction::DeleteJavaInstalls { installs } => {
+                tr!("confirm-delete-java", installs = installs.join(", "))
+            }
+            DestructiveAction::DeleteWorld { world, .. } => {
+                tr!("confirm-delete-world", world = world)
+            }
+            DestructiveAction::RestoreWorld { backup, .. } => {
+                tr!(
+                    "confirm-restore-world",
+                    world = world_of_backup(backup),
+                    backup = backup.file_stem().unwrap_or_default().to_string_lossy()
+                )
+            }
+            DestructiveAction::UninstallLoader { instance, loader } => {
+                tr!(
+                    "confirm-uninstall-loader",
+                    loader = loader,
+                    instance = instance
+                )
+            }
+            DestructiveAction::RollBack { instance, snapshot } => {
+                tr!(
+                    "confirm-roll-back",
+                    instance = instance,
+                    snapshot = snapshot.file_name().unwrap_or_default().to_string_lossy()
+                )
+            }
+            DestructiveAction::LaunchAgain { instance } => {
+                tr!("confirm-launch-again", instance = instance)
+            }
+            DestructiveAction::IncompatibleJava { problem, .. } => match problem {
+                JavaProblem::TooOld { required, selected } => tr!(
+                    "confirm-java-too-old",
+                    required = required,
+                    selected = selected
+                ),
+                JavaProblem::TooNew {
+                    loader,
+                    max,
+                    selected,
+                } => tr!(
+                    "confirm-java-too-new",
+                    loader = loader,
+                    max = max,
+                    selected = selected
+                ),
+            },
+        }
+    }
+
+    pub fn warning(&self) -> String {
+        match self {
+            DestructiveAction::DeleteInstance { .. } | DestructiveAction::DeleteServer { .. } => {
+                tr!("delete-warning")
+            }
+            DestructiveAction::DeleteMod { .. } => tr!("confirm-delete-mod-warning"),
+            DestructiveAction::DeletePlugin { .. } => tr!("confirm-delete-plugin-warning"),
+            DestructiveAction::MoveDataDir { .. } => tr!("confirm-move-data-dir-warning"),
+            DestructiveAction::RestoreBackup { .. } => tr!("confirm-restore-backup-warning"),
+            DestructiveAction::DeleteJavaInstalls { .. } => tr!("confirm-delete-java-warning"),
+            DestructiveAction::DeleteWorld { .. } => tr!("confirm-delete-world-warning"),
+            DestructiveAction::RestoreWorld { .. } => tr!("confirm-restore-world-warning"),
+            DestructiveAction::UninstallLoader { .. } => tr!("confirm-uninstall-loader-warning"),
+            DestructiveAction::RollBack { .. } => tr!("confirm-roll-back-warning"),
+            DestructiveAction::LaunchAgain { .. } => tr!("confirm-launch-again-warning"),
+            DestructiveAction::IncompatibleJava { .. } => tr!("confirm-java-warning"),
+        }
+    }
+
+    pub fn confirm_label(&self) -> String {
+        match self {
+            DestructiveAction::DeleteInstance { .. } | DestructiveAction::DeleteServer { .. } => {
+                tr!("delete-yes")
+            }
+            DestructiveAction::DeleteMod { .. } => tr!("confirm-delete-mod-yes"),
+            DestructiveAction::DeletePlugin { .. } => tr!("confirm-delete-plugin-yes"),
+            DestructiveAction::MoveDataDir { .. } => tr!("confirm-move-data-dir-yes"),
+            DestructiveAction::RestoreBackup { .. } => tr!("confirm-restore-backup-yes"),
+            DestructiveAction::DeleteJavaInstalls { .. } => tr!("confirm-delete-java-yes"),
+            DestructiveAction::DeleteWorld { .. } => tr!("confirm-delete-world-yes"),
+            DestructiveAction::RestoreWorld { .. } => tr!("confirm-restore-world-yes"),
+            DestructiveAction::UninstallLoader { .. } => tr!("confirm-uninstall-loader-yes"),
+            DestructiveAction::RollBack { .. } => tr!("confirm-roll-back-yes"),
+            DestructiveAction::LaunchAgain { .. } => tr!("confirm-launch-again-yes"),
+            DestructiveAction::IncompatibleJava { .. } => tr!("confirm-java-yes"),
+        }
+    }
+
+    /// For the worst actions, the user has to type
+    /// this text (the instance name) to confirm.
+    pub fn required_input(&self) -> Option<&str> {
+        match self {
+            DestructiveAction::DeleteInstance { instance } => Some(instance),
+            DestructiveAction::DeleteServer { server } => Some(server),
+            DestructiveAction::DeleteMod { .. }
+            | DestructiveAction::DeletePlugin { .. }
+            | DestructiveAction::UninstallLoader { .. }
+            | DestructiveAction::MoveDataDir { .. }
+            | DestructiveAction::RestoreBackup { .. }
+            | DestructiveAction::DeleteJavaInstalls { .. }
+            | DestructiveAction::DeleteWorld { .. }
+            | DestructiveAction::RestoreWorld { .. }
+            | DestructiveAction::RollBack { .. }
+            | DestructiveAction::LaunchAgain { .. }
+            | DestructiveAction::IncompatibleJava { .. } => None,
+        }
+    }
+
+    /// Another way out than going ahead or cancelling,
+    /// shown as a button between the two.
+    pub fn alternative(&self) -> Option<(String, Message)> {
+        match self {
+            DestructiveAction::IncompatibleJava { instance, problem } => Some((
+                tr!("confirm-java-fix", version = problem.recommended()),
+                Message::LaunchFixJava(instance.clone(), problem.recommended()),
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// World backups are kept in a folder named after the world.
+pub fn world_of_backup(backup: &Path) -> String {
+    backup
+        .parent()
+        .and_then(Path::file_name)
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Asks the user to confirm a [`DestructiveAction`].
+pub struct MenuConfirm {
+    pub action: DestructiveAction,
+    pub input: String,
+}
+
+impl MenuConfirm {
+    pub fn is_confirmed(&self) -> bool {
+        self.action
+            .required_input()
+            .is_none_or(|required| self.input == required)
+    }
 }
 
 pub struct MenuInstallFabric {
     pub selected_instance: String,
     pub fabric_version: Option<String>,
     pub fabric_versions: Vec<String>,
+    /// Whether [`Self::fabric_versions`] is a saved
+    /// copy, because the launcher is offline.
+    pub versions_cached: bool,
+    /// `Some` while Fabric is being installed.
+    pub progress: Option<FabricProgress>,
+}
+
+pub struct FabricProgress {
+    pub receiver: ProgressReceiver<FabricInstallProgress>,
+    pub num: f32,
+    pub message: String,
+}
+
+#[derive(Default)]
+pub struct MenuLauncherSettings {
+    pub java_install_input: String,
+    /// The launcher-wide Java arguments as typed.
+    pub java_args_input: String,
+    pub java_cleanup_status: Option<String>,
+    /// The UI scale slider's position while it's being dragged.
+    /// It's only applied on release, so the slider
+    /// doesn't move out from under the mouse.
+    pub ui_scale: Option<f64>,
+    /// Where the launcher's data and cache are now. See
+    /// [`MenuLauncherSettings::find_dirs`].
+    pub data_dir: String,
+    pub cache_dir: String,
+    /// Where to move the launcher's data to.
+    pub data_dir_input: String,
+    pub moving_data_dir: bool,
+    /// Whether launcher backups include the instances' worlds.
+    pub backup_include_saves: bool,
+    pub backing_up: bool,
+    /// The path of the backup to restore.
+    pub restore_input: String,
+    pub restoring: bool,
+    /// The new offline profile's details as typed.
+    pub profile_name_input: String,
+    pub profile_username_input: String,
+    pub profile_skin_input: String,
+}
+
+impl MenuLauncherSettings {
+    /// Looks up where the data and cache directories are,
+    /// when the screen is opened and after they've moved.
+    pub fn find_dirs(&mut self) {
+        let path_text = |dir: Result<PathBuf, IoError>| {
+            dir.map(|dir| dir.to_string_lossy().to_string())
+                .unwrap_or_default()
+        };
+        self.data_dir = path_text(file_utils::get_launcher_dir());
+        self.cache_dir = path_text(file_utils::get_cache_dir());
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WelcomeStep {
+    DataDir,
+    Account,
+    Theme,
+    FirstInstance,
+}
+
+impl WelcomeStep {
+    pub const ALL: [WelcomeStep; 4] = [
+        WelcomeStep::DataDir,
+        WelcomeStep::Account,
+        WelcomeStep::Theme,
+        WelcomeStep::FirstInstance,
+    ];
+
+    pub fn number(self) -> usize {
+        Self::ALL.iter().position(|n| *n == self).unwrap() + 1
+    }
+
+    pub fn next(self) -> Option<Self> {
+        Self::ALL.get(self.number()).copied()
+    }
+
+    pub fn previous(self) -> Option<Self> {
+        Self::ALL.get(self.number().checked_sub(2)?).copied()
+    }
+}
+
+/// The setup wizard shown the first time the launcher is opened.
+pub struct MenuWelcome {
+    pub step: WelcomeStep,
+    pub data_dir_input: String,
+}
+
+impl MenuWelcome {
+    pub fn new() -> LauncherResult<Self> {
+        let data_dir = quantum_launcher_backend::file_utils::get_launcher_dir()?;
+        Ok(Self {
+            step: WelcomeStep::DataDir,
+            data_dir_input: data_dir.to_string_lossy().to_string(),
+        })
+    }
+}
+
+/// The list of Minecraft servers, where they're
+/// created, started and stopped.
+pub struct MenuServers {
+    pub servers: Vec<InstanceInfo>,
+    /// Where the servers are, for opening their files.
+    pub servers_dir: PathBuf,
+    pub selected_server: Option<String>,
+    pub java_install_progress: Option<JavaInstallProgress>,
+    /// The address of an external server to add.
+    pub external_server_input: String,
+}
+
+/// A server in the status panel of the launch screen,
+/// either run by the launcher or added by the user.
+pub struct ServerStatusEntry {
+    pub name: String,
+    pub address: String,
+    /// `None` until it has been pinged.
+    pub status: Option<Result<ServerStatus, String>>,
+}
+
+/// The live log of a game started by the launcher.
+pub struct MenuGameLog {
+    pub pid: u32,
+    /// Whether the log is being uploaded to mclo.gs.
+    pub sharing: bool,
+}
+
+/// The console of a running server, for reading
+/// its log and running commands.
+pub struct MenuServerConsole {
+    pub pid: u32,
+    pub command_input: String,
+    /// How many of the most recent log lines are shown.
+    /// Showing the whole log would make the UI slow,
+    /// so older lines are only shown when asked for.
+    pub shown_lines: usize,
+}
+
+impl MenuServerConsole {
+    pub const SHOWN_LINES_STEP: usize = 500;
+}
+
+/// Asks the user to accept Mojang's EULA before a server's
+/// first start. The server won't run without it.
+pub struct MenuServerEula {
+    pub server: String,
+    /// The "I agree" checkbox, which has to be ticked to continue.
+    pub agreed: bool,
+}
+
+/// Edits a server's `server.properties`, with inputs
+/// for the common settings and the whole file as text
+/// for everything else.
+pub struct MenuServerProperties {
+    pub server: String,
+    pub properties: ServerProperties,
+    /// The numbers are kept as typed, so that
+    /// they can be shown as invalid while editing.
+    pub port_input: String,
+    pub view_distance_input: String,
+    pub motd: String,
+    pub gamemode: GameMode,
+    pub difficulty: Difficulty,
+    pub online_mode: bool,
+    /// The file as text, when editing it by hand.
+    pub raw: Option<widget::text_editor::Content>,
+}
+
+impl MenuServerProperties {
+    /// Checks what was typed in and puts it into [`Self::properties`].
+    pub fn apply_settings(&mut self) -> Result<(), ServerSettingError> {
+        let settings = ServerSettings {
+            port: server_files::parse_port(&self.port_input)?,
+            gamemode: self.gamemode,
+            difficulty: self.difficulty,
+            online_mode: self.online_mode,
+            motd: self.motd.clone(),
+            view_distance: server_files::parse_view_distance(&self.view_distance_input)?,
+        };
+        self.properties.set_settings(&settings)
+    }
+}
+
+/// The plugins of a Paper or Purpur server.
+pub struct MenuServerPlugins {
+    pub server: String,
+    pub software: ServerSoftware,
+    /// File names of the plugins in the `plugins` folder.
+    pub plugins: Vec<String>,
+    pub plugins_dir: PathBuf,
+    pub source: PluginSource,
+    /// Slugs of plugins to install, separated by spaces.
+    pub plugin_input: String,
+    /// `Some` while plugins are being installed.
+    pub progress: Option<PluginProgress>,
+}
+
+pub struct PluginProgress {
+    pub receiver: ProgressReceiver<ModInstallProgress>,
+    pub message: String,
+}
+
+/// The results of checking an instance's files,
+/// with a button to fix each problem.
+pub struct MenuVerify {
+    pub selected_instance: String,
+    /// `None` while checking.
+    pub results: Option<Result<Vec<CheckResult>, String>>,
+    /// The check being fixed.
+    pub fixing: Option<Check>,
+}
+
+/// The worlds of an instance, and their backups.
+pub struct MenuWorlds {
+    pub selected_instance: String,
+    pub config: InstanceConfigJson,
+    pub worlds: Vec<WorldBackups>,
+    /// Where each world's backups are, in a folder named after it.
+    pub backups_dir: PathBuf,
+    /// Kept as typed, like in [`MenuServerSchedule`].
+    pub retention_input: String,
+    /// Whether a world is being backed up, restored or copied.
+    pub busy: bool,
+    /// The folder of the world being renamed, and the new name as typed.
+    pub rename: Option<(String, String)>,
+    /// The instance that worlds are copied to.
+    pub copy_target: Option<String>,
+}
+
+/// Edits an instance's `options.txt`, which also
+/// works before the game's first launch.
+pub struct MenuGameOptions {
+    pub selected_instance: String,
+    pub options: GameOptions,
+    /// Kept as typed, like in [`MenuServerProperties`].
+    pub render_distance_input: String,
+    pub max_fps_input: String,
+    pub gui_scale: u8,
+    pub vsync: bool,
+    pub language: String,
+    /// The instance that keybinds or options are copied from.
+    pub copy_source: Option<String>,
+    /// The packs in the instance's `resourcepacks` folder.
+    pub resource_packs: Vec<String>,
+    /// The path of a resource pack to add.
+    pub resource_pack_input: String,
+    /// Whether the added pack is turned on right away.
+    pub enable_new_pack: bool,
+    pub installing_pack: bool,
+}
+
+impl MenuGameOptions {
+    pub fn new(
+        selected_instance: String,
+        options: GameOptions,
+        resource_packs: Vec<String>,
+    ) -> Self {
+        let settings = options.settings();
+        Self {
+            selected_instance,
+            options,
+            render_distance_input: settings.render_distance.to_string(),
+            max_fps_input: settings.max_fps.to_string(),
+            gui_scale: settings.gui_scale,
+            vsync: settings.vsync,
+            language: settings.language,
+            copy_source: None,
+            resource_packs,
+            resource_pack_input: String::new(),
+            enable_new_pack: true,
+            installing_pack: false,
+        }
+    }
+
+    /// Checks what was typed in and puts it into [`Self::options`].
+    pub fn apply_settings(&mut self) -> Result<(), GameSettingError> {
+        let settings = GameSettings {
+            render_distance: game_options::parse_render_distance(&self.render_distance_input)?,
+            gui_scale: self.gui_scale,
+            max_fps: game_options::parse_max_fps(&self.max_fps_input)?,
+            vsync: self.vsync,
+            language: self.language.trim().to_owned(),
+        };
+        self.options.set_settings(&settings)
+    }
+}
+
+/// Shown on start after the launcher crashed,
+/// offering to report the crash.
+pub struct MenuCrashRecovery {
+    pub crash: CrashDump,
+}
+
+/// The launcher's own recent logs, for diagnosing problems.
+pub struct MenuLauncherLogs {
+    /// The lines when the screen was opened or refreshed.
+    pub lines: Vec<String>,
+}
+
+/// A newer launcher release, with its changelog.
+pub struct MenuUpdate {
+    pub release: Release,
+    pub installing: bool,
+    /// Set once the update is installed.
+    pub outcome: Option<UpdateOutcome>,
+}
+
+/// Playtime totals, worked out from the sessions in `stats.json`.
+pub struct MenuStats {
+    pub stats: PlaytimeStats,
+}
+
+/// The downloads of every operation, refreshed every second.
+pub struct MenuDownloadQueue {
+    pub queue: QueueSnapshot,
+    /// In bytes per second, between the last two refreshes.
+    pub speed: u64,
+    pub refreshed: Instant,
+}
+
+/// When a server is backed up and restarted while it runs.
+pub struct MenuServerSchedule {
+    pub server: String,
+    /// The numbers are kept as typed, like in [`MenuServerProperties`].
+    /// The intervals are in minutes, and empty to turn them off.
+    pub backup_interval_input: String,
+    pub backup_retention_input: String,
+    pub restart_interval_input: String,
+    /// Whether a backup started from this menu is being made.
+    pub backing_up: bool,
+    pub backups_dir: PathBuf,
+}
+
+impl MenuServerSchedule {
+    pub fn new(server: String, schedule: &ServerSchedule, backups_dir: PathBuf) -> Self {
+        let interval_input =
+            |minutes: Option<u32>| minutes.map(|n| n.to_string()).unwrap_or_default();
+        Self {
+            server,
+            backup_interval_input: interval_input(schedule.backup_interval_minutes),
+            backup_retention_input: schedule.backup_retention.to_string(),
+            restart_interval_input: interval_input(schedule.restart_interval_minutes),
+            backing_up: false,
+            backups_dir,
+        }
+    }
+
+    /// `Err(())` if it isn't a number of minutes or empty.
+    pub fn parse_interval(input: &str) -> Result<Option<u32>, ()> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(None);
+        }
+        match input.parse() {
+            Ok(0) | Err(_) => Err(()),
+            Ok(minutes) => Ok(Some(minutes)),
+        }
+    }
+
+    pub fn parse_retention(input: &str) -> Option<usize> {
+        input.trim().parse().ok().filter(|n| *n > 0)
+    }
+
+    /// The schedule that was typed in, if it's valid.
+    pub fn schedule(&self) -> Option<ServerSchedule> {
+        Some(ServerSchedule {
+            backup_interval_minutes: Self::parse_interval(&self.backup_interval_input).ok()?,
+            backup_retention: Self::parse_retention(&self.backup_retention_input)?,
+            restart_interval_minutes: Self::parse_interval(&self.restart_interval_input).ok()?,
+        })
+    }
+}
+
+/// An entry in the gamemode dropdown of [`MenuServerProperties`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameModeChoice(pub GameMode);
+
+impl GameModeChoice {
+    pub const ALL: [Self; 4] = [
+        Self(GameMode::ALL[0]),
+        Self(GameMode::ALL[1]),
+        Self(GameMode::ALL[2]),
+        Self(GameMode::ALL[3]),
+    ];
+}
+
+impl Display for GameModeChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self.0 {
+            GameMode::Survival => tr!("server-properties-gamemode-survival"),
+            GameMode::Creative => tr!("server-properties-gamemode-creative"),
+            GameMode::Adventure => tr!("server-properties-gamemode-adventure"),
+            GameMode::Spectator => tr!("server-properties-gamemode-spectator"),
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// An entry in the GUI scale dropdown of [`MenuGameOptions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuiScaleChoice(pub u8);
+
+impl GuiScaleChoice {
+    pub const ALL: [Self; 7] = [
+        Self(GameSettings::GUI_SCALES[0]),
+        Self(GameSettings::GUI_SCALES[1]),
+        Self(GameSettings::GUI_SCALES[2]),
+        Self(GameSettings::GUI_SCALES[3]),
+        Self(GameSettings::GUI_SCALES[4]),
+        Self(GameSettings::GUI_SCALES[5]),
+        Self(GameSettings::GUI_SCALES[6]),
+    ];
+}
+
+impl Display for GuiScaleChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            0 => write!(f, "{}", tr!("game-options-gui-scale-auto")),
+            scale => write!(f, "{scale}"),
+        }
+    }
+}
+
+/// An entry in the launcher settings' dropdown of
+/// instances to copy game options from, or none.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionsSourceChoice(pub Option<String>);
+
+impl Display for OptionsSourceChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(instance) => write!(f, "{instance}"),
+            None => write!(f, "{}", tr!("settings-options-source-none")),
+        }
+    }
+}
+
+/// Who to launch as, picked on the launch screen: the
+/// typed-in username, or a saved offline profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountChoice {
+    Username,
+    OfflineProfile(String),
+}
+
+impl AccountChoice {
+    pub fn all(profiles: &[OfflineProfile]) -> Vec<Self> {
+        std::iter::once(AccountChoice::Username)
+            .chain(
+                profiles
+                    .iter()
+                    .map(|profile| AccountChoice::OfflineProfile(profile.name.clone())),
+            )
+            .collect()
+    }
+}
+
+impl Display for AccountChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccountChoice::Username => write!(f, "{}", tr!("launch-account-username")),
+            AccountChoice::OfflineProfile(name) => {
+                write!(f, "{}", tr!("launch-account-offline", name = name.clone()))
+            }
+        }
+    }
+}
+
+/// An entry in the edit instance menu's dropdown of offline
+/// profiles, or none to use the launch screen's account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstanceProfileChoice(pub Option<String>);
+
+impl Display for InstanceProfileChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(name) => write!(f, "{name}"),
+            None => write!(f, "{}", tr!("edit-offline-profile-none")),
+        }
+    }
+}
+
+/// An entry in the create instance menu's dropdown of
+/// templates, or none to create a plain instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateChoice(pub Option<String>);
+
+impl Display for TemplateChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(name) => write!(f, "{name}"),
+            None => write!(f, "{}", tr!("create-template-none")),
+        }
+    }
+}
+
+/// An entry in the difficulty dropdown of [`MenuServerProperties`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DifficultyChoice(pub Difficulty);
+
+impl DifficultyChoice {
+    pub const ALL: [Self; 4] = [
+        Self(Difficulty::ALL[0]),
+        Self(Difficulty::ALL[1]),
+        Self(Difficulty::ALL[2]),
+        Self(Difficulty::ALL[3]),
+    ];
+}
+
+impl Display for DifficultyChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self.0 {
+            Difficulty::Peaceful => tr!("server-properties-difficulty-peaceful"),
+            Difficulty::Easy => tr!("server-properties-difficulty-easy"),
+            Difficulty::Normal => tr!("server-properties-difficulty-normal"),
+            Difficulty::Hard => tr!("server-properties-difficulty-hard"),
+        };
+        write!(f, "{name}")
+    }
 }
 
 pub enum State {
     Launch(MenuLaunch),
     EditInstance(MenuEditInstance),
     EditMods(MenuEditMods),
+    ModBrowser(MenuModBrowser),
     Create(MenuCreateInstance),
-    Error { error: String },
-    DeleteInstance(MenuDeleteInstance),
+    Error {
+        error: String,
+        /// The instance that was open when the error happened,
+        /// for including its config in bug reports.
+        instance: Option<String>,
+        /// A broken config file that can be put
+        /// back to its last working copy.
+        restore: Option<PathBuf>,
+        /// The step of launching that failed, for
+        /// suggesting how to fix it.
+        launch_phase: Option<LaunchPhase>,
+    },
+    Confirm(MenuConfirm),
     InstallFabric(MenuInstallFabric),
+    LauncherSettings(MenuLauncherSettings),
+    GameLog(MenuGameLog),
+    Welcome(MenuWelcome),
+    Servers(MenuServers),
+    ServerConsole(MenuServerConsole),
+    ServerEula(MenuServerEula),
+    ServerProperties(MenuServerProperties),
+    ServerPlugins(MenuServerPlugins),
+    ServerSchedule(MenuServerSchedule),
+    Stats(MenuStats),
+    DownloadQueue(MenuDownloadQueue),
+    Worlds(MenuWorlds),
+    GameOptions(MenuGameOptions),
+    LaunchWithOptions(MenuLaunchWithOptions),
+    Verify(MenuVerify),
+    Update(MenuUpdate),
+    LauncherLogs(MenuLauncherLogs),
+    CrashRecovery(MenuCrashRecovery),
+}
+
+impl State {
+    /// The instance this menu is about, if any.
+    pub fn selected_instance(&self) -> Option<&str> {
+        match self {
+            State::Launch(menu) => menu.selected_instance.as_deref(),
+            State::EditInstance(MenuEditInstance {
+                selected_instance, ..
+            })
+            | State::EditMods(MenuEditMods {
+                selected_instance, ..
+            })
+            | State::ModBrowser(MenuModBrowser {
+                selected_instance, ..
+            })
+            | State::InstallFabric(MenuInstallFabric {
+                selected_instance, ..
+            })
+            | State::Worlds(MenuWorlds {
+                selected_instance, ..
+            })
+            | State::GameOptions(MenuGameOptions {
+                selected_instance, ..
+            })
+            | State::LaunchWithOptions(MenuLaunchWithOptions {
+                selected_instance, ..
+            })
+            | State::Verify(MenuVerify {
+                selected_instance, ..
+            }) => Some(selected_instance),
+            State::Confirm(menu) => {
+                match &menu.action {
+                    DestructiveAction::DeleteInstance { instance }
+                    | DestructiveAction::DeleteMod { instance, .. }
+                    | DestructiveAction::UninstallLoader { instance, .. }
+                    | DestructiveAction::DeleteWorld { instance, .. }
+                    | DestructiveAction::RestoreWorld { instance, .. }
+                    | DestructiveAction::RollBack { instance, .. }
+                    | DestructiveAction::LaunchAgain { instance }
+                    | DestructiveAction::IncompatibleJava { instance, .. } => Some(instance),
+                    // Servers aren't in the `instances` folder.
+                    DestructiveAction::DeleteServer { .. }
+                    | DestructiveAction::DeletePlugin { .. } => None,
+                    DestructiveAction::MoveDataDir { .. }
+                    | DestructiveAction::RestoreBackup { .. }
+                    | DestructiveAction::DeleteJavaInstalls { .. } => None,
+                }
+            }
+            State::Error { instance, .. } => instance.as_deref(),
+            State::Create(_)
+            | State::LauncherSettings(_)
+            | State::GameLog(_)
+            | State::Welcome(_)
+            | State::Servers(_)
+            | State::ServerConsole(_)
+            | State::ServerEula(_)
+            | State::ServerProperties(_)
+            | State::ServerPlugins(_)
+            | State::ServerSchedule(_)
+            | State::Stats(_)
+            | State::DownloadQueue(_)
+            | State::Update(_)
+            | State::LauncherLogs(_)
+            | State::CrashRecovery(_) => None,
+        }
+    }
+}
+
+/// A game started by the launcher.
+///
+/// It's kept around after exiting (with `exit_status` set)
+/// so that the log can still be read, for example after a crash.
+pub struct GameProcess {
+    pub instance_name: String,
+    /// Whether this is a server, rather than a game client.
+    pub is_server: bool,
+    pub child: Arc<Mutex<Child>>,
+    pub pid: u32,
+    pub started: Instant,
+    /// The game's stdout and stderr, line by line.
+    pub log: Arc<Mutex<Vec<String>>>,
+    /// Memory usage in bytes.
+    pub memory: Option<u64>,
+    pub exit_status: Option<ExitStatus>,
+    /// Whether the user stopped the game, so that
+    /// it isn't reported as a crash.
+    pub killed: bool,
+    /// For sending commands to servers. `None` for game clients.
+    pub stdin: Option<ChildStdin>,
+    /// When the server was asked to stop, if it was.
+    /// It can take a while to save the worlds.
+    pub stopping: Option<Instant>,
+    /// When the server's worlds were last backed up by its
+    /// schedule, or when it started if they haven't been yet.
+    pub last_backup: Instant,
+    /// Whether the worlds are being backed up, with saving turned off.
+    pub backing_up: bool,
+    /// Whether the server was stopped by its schedule,
+    /// to be started again once it exits.
+    pub restarting: bool,
+}
+
+impl GameProcess {
+    /// Only the most recent lines are kept, so that
+    /// a long-running game doesn't fill up memory.
+    const MAX_LOG_LINES: usize = 10_000;
+
+    pub fn new(instance_name: String, child: Arc<Mutex<Child>>, is_server: bool) -> Self {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let (pid, stdin) = {
+            let mut child = child.lock().unwrap();
+            if let Some(stdout) = child.stdout.take() {
+                read_game_output(stdout, log.clone());
+            }
+            if let Some(stderr) = child.stderr.take() {
+                read_game_output(stderr, log.clone());
+            }
+            (child.id(), child.stdin.take())
+        };
+
+        Self {
+            instance_name,
+            is_server,
+            child,
+            pid,
+            started: Instant::now(),
+            log,
+            memory: None,
+            exit_status: None,
+            killed: false,
+            stdin,
+            stopping: None,
+            last_backup: Instant::now(),
+            backing_up: false,
+            restarting: false,
+        }
+    }
+
+    /// Runs a command on a server, showing it in the log like
+    /// a terminal would.
+    pub fn send_command(&mut self, command: &str) -> std::io::Result<()> {
+        let Some(stdin) = &mut self.stdin else {
+            return Err(std::io::ErrorKind::BrokenPipe.into());
+        };
+        quantum_launcher_backend::send_server_command(stdin, command)?;
+        push_log_line(&self.log, format!("> {command}"));
+        Ok(())
+    }
+
+    /// Whether this is the game client of `instance_name`, still running.
+    pub fn is_running_instance(&self, instance_name: &str) -> bool {
+        !self.is_server && self.instance_name == instance_name && self.exit_status.is_none()
+    }
+
+    /// Whether this is the server `server_name`, still running.
+    pub fn is_running_server(&self, server_name: &str) -> bool {
+        self.is_server && self.instance_name == server_name && self.exit_status.is_none()
+    }
+}
+
+fn read_game_output(output: impl Read + Send + 'static, log: Arc<Mutex<Vec<String>>>) {
+    std::thread::spawn(move || {
+        for line in BufReader::new(output).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            push_log_line(&log, line);
+        }
+    });
+}
+
+fn push_log_line(log: &Mutex<Vec<String>>, line: String) {
+    let mut log = log.lock().unwrap();
+    if log.len() >= GameProcess::MAX_LOG_LINES {
+        log.remove(0);
+    }
+    log.push(line);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Error,
+}
+
+/// A small notification shown above the current menu.
+///
+/// Unlike [`State::Error`], it doesn't replace the menu,
+/// so it's used for errors the user can recover from.
+pub struct Toast {
+    pub id: usize,
+    pub kind: ToastKind,
+    pub message: String,
+    pub created: Instant,
+}
+
+impl Toast {
+    /// How long info and success toasts stay up.
+    /// Errors stay until they're dismissed.
+    pub const DURATION: Duration = Duration::from_secs(5);
+
+    pub fn is_expired(&self) -> bool {
+        self.kind != ToastKind::Error && self.created.elapsed() > Self::DURATION
+    }
 }
 
 pub struct Launcher {
     pub state: State,
-    pub instances: Option<Vec<String>>,
+    pub instances: Option<InstanceRegistry>,
     pub config: Option<LauncherConfig>,
-    pub spawned_process: Option<Arc<std::sync::Mutex<Child>>>,
+    pub processes: Vec<GameProcess>,
+    pub system: sysinfo::System,
+    pub show_shortcut_help: bool,
+    pub toasts: Vec<Toast>,
+    pub next_toast_id: usize,
+    /// `None` while the news is loading.
+    pub news: Option<Result<Vec<NewsEntry>, String>>,
+    /// The current window size and position, saved to the config on exit.
+    pub window: WindowGeometry,
+    pub server_statuses: Vec<ServerStatusEntry>,
+    /// Started once there's something to show, if it's turned on.
+    pub discord: Option<DiscordRpc>,
+    /// A newer launcher release, if one was found.
+    pub update: Option<Release>,
+    /// `None` if it's turned off, or couldn't be shown.
+    pub tray: Option<Tray>,
+    /// Keeps other launchers from starting alongside this one.
+    pub instance_guard: Option<InstanceGuard>,
+    /// The search palette, if it's open.
+    pub search: Option<SearchPalette>,
+    /// Instances whose mod loader has a newer stable
+    /// version, and that version.
+    pub loader_updates: HashMap<String, String>,
+    /// Faces of skins, for showing who's playing. `None`
+    /// while loading, or if there's no skin.
+    pub skins: HashMap<SkinSource, Option<widget::image::Handle>>,
 }
 
 impl Launcher {
     pub fn new() -> LauncherResult<Self> {
-        // .config/QuantumLauncher/ OR AppData/Roaming/QuantumLauncher/
-        let dir_path = quantum_launcher_backend::file_utils::get_launcher_dir()?;
-        std::fs::create_dir_all(&dir_path).map_err(io_err!(dir_path))?;
-
-        // QuantumLauncher/instances/
-        let dir_path = dir_path.join("instances");
-        std::fs::create_dir_all(&dir_path).map_err(io_err!(dir_path))?;
-
-        let dir = std::fs::read_dir(&dir_path).map_err(io_err!(dir_path))?;
-
-        let subdirectories: Vec<String> = dir
-            .filter_map(|entry| {
-                if let Ok(entry) = entry {
-                    if entry.path().is_dir() {
-                        if let Some(file_name) = entry.file_name().to_str() {
-                            return Some(file_name.to_owned());
-                        }
-                    }
-                }
-                None
-            })
-            .collect();
+        // The config is loaded first, as it can change the data directory.
+        let config = LauncherConfig::load()?;
+        if let Err(err) = quantum_launcher_backend::file_utils::migrate_to_cache_dir() {
+            error!("Could not move downloaded files to the cache directory: {err}");
+        }
+        quantum_launcher_backend::self_update::remove_old_binary();
+        let state = if !config.setup_complete {
+            State::Welcome(MenuWelcome::new()?)
+        } else if let Some(crash) = crash_dump::take_unseen_crash() {
+            State::CrashRecovery(MenuCrashRecovery { crash })
+        } else {
+            State::Launch(MenuLaunch::default())
+        };
+        let window = config.window.unwrap_or_default();
 
         Ok(Self {
-            instances: Some(subdirectories),
-            state: State::Launch(MenuLaunch::default()),
-            spawned_process: None,
-            config: Some(LauncherConfig::load()?),
+            instances: Some(Self::load_instances()?),
+            state,
+            processes: Vec::new(),
+            system: sysinfo::System::new(),
+            config: Some(config),
+            show_shortcut_help: false,
+            toasts: Vec::new(),
+            next_toast_id: 0,
+            news: None,
+            window,
+            server_statuses: Vec::new(),
+            discord: None,
+            update: None,
+            tray: None,
+            instance_guard: None,
+            search: None,
+            loader_updates: HashMap::new(),
+            skins: HashMap::new(),
         })
     }
 
-    pub fn with_error(error: String) -> Self {
+    pub fn load_instances() -> LauncherResult<InstanceRegistry> {
+        InstanceRegistry::load()
+    }
+
+    pub fn with_error(error: String, restore: Option<PathBuf>) -> Self {
+        let config = LauncherConfig::load().ok();
+        let window = config
+            .as_ref()
+            .and_then(|config| config.window)
+            .unwrap_or_default();
         Self {
             state: State::Error {
                 error: format!("Error: {error}"),
+                instance: None,
+                restore,
+                launch_phase: None,
             },
             instances: None,
-            config: LauncherConfig::load().ok(),
-            spawned_process: None,
+            config,
+            processes: Vec::new(),
+            system: sysinfo::System::new(),
+            show_shortcut_help: false,
+            toasts: Vec::new(),
+            next_toast_id: 0,
+            news: None,
+            window,
+            server_statuses: Vec::new(),
+            discord: None,
+            update: None,
+            tray: None,
+            instance_guard: None,
+            search: None,
+            loader_updates: HashMap::new(),
+            skins: HashMap::new(),
         }
     }
 
     pub fn set_error(&mut self, error: String) {
-        self.state = State::Error { error }
+        let instance = self.state.selected_instance().map(str::to_owned);
+        self.state = State::Error {
+            error,
+            instance,
+            restore: None,
+            launch_phase: None,
+        }
+    }
+
+    /// Shows why the game or a server didn't start,
+    /// with a hint for fixing it.
+    pub fn set_launch_error(&mut self, failure: LaunchFailure) {
+        self.set_error(failure.message);
+        if let State::Error {
+            restore,
+            launch_phase,
+            ..
+        } = &mut self.state
+        {
+            *restore = failure.restorable_file;
+            *launch_phase = failure.phase;
+        }
+    }
+
+    /// Like [`Launcher::set_error`], but offers to restore
+    /// the file if the error is from a broken config.
+    pub fn set_launcher_error(&mut self, err: &LauncherError) {
+        self.set_error(err.to_string_with_code());
+        if let State::Error { restore, .. } = &mut self.state {
+            *restore = err.restorable_file().map(Path::to_owned);
+        }
+    }
+
+    pub fn notify(&mut self, kind: ToastKind, message: String) {
+        if kind == ToastKind::Error {
+            error!("{message}");
+        }
+        self.toasts.push(Toast {
+            id: self.next_toast_id,
+            kind,
+            message,
+            created: Instant::now(),
+        });
+        self.next_toast_id += 1;
     }
 
     pub fn go_to_launch_screen(&mut self) {
@@ -160,7 +1740,7 @@ impl Launcher {
         if let State::Launch(menu_launch) = &self.state {
             match self.edit_instance(menu_launch.selected_instance.clone().unwrap()) {
                 Ok(_) => {}
-                Err(err) => self.set_error(err.to_string()),
+                Err(err) => self.set_launcher_error(&err),
             }
         }
     }
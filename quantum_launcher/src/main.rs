@@ -1,66 +1,258 @@
-use std::time::Duration;
+use std::{path::Path, time::Duration};
 
+use config::LauncherConfig;
 use iced::{executor, widget, Application, Command, Settings, Subscription};
-use launcher_state::{Launcher, MenuInstallFabric, MenuLaunch, Message, State};
-use message_handler::{format_memory, open_file_explorer};
-use quantum_launcher_backend::{error::LauncherError, instance_mod_installer};
-use stylesheet::styles::LauncherTheme;
+use launcher_state::{
+    AccountChoice, InstanceRegistry, Launcher, MenuConfirm, MenuCreateInstance, MenuEditInstance,
+    MenuEditMods, MenuInstallFabric, MenuLaunch, MenuLauncherLogs, MenuLauncherSettings,
+    MenuModBrowser, MenuServerConsole, MenuServerPlugins, MenuServerProperties, MenuServers,
+    MenuWorlds, Message, State, ToastKind,
+};
+use message_handler::{non_empty, open_file_explorer};
+use quantum_launcher_backend::{
+    crash_dump, download_queue,
+    error::{HasErrorCode, LaunchPhase, LauncherError},
+    events, file_utils, instance_mod_installer,
+    json_structs::json_instance_config::parse_memory_mb,
+    logging::{self, LogConsole},
+    progress::ProgressReceiver,
+    split_arguments, InstanceFolderWatcher, JavaInstallMessage,
+};
+use single_instance::InstanceGuard;
+use stylesheet::styles::{text_size, LauncherTheme};
+use tracing::{error, warn};
 
 mod config;
+mod diagnostics;
+mod i18n;
 mod icon_manager;
 mod launcher_state;
+mod markdown;
 mod menu_renderer;
 mod message_handler;
+mod search;
+mod shortcuts;
+mod single_instance;
 mod stylesheet;
+mod tray;
 
 impl Application for Launcher {
     type Executor = executor::Default;
     type Message = Message;
     type Theme = LauncherTheme;
-    type Flags = ();
+    type Flags = Option<InstanceGuard>;
 
-    fn new(_flags: Self::Flags) -> (Self, iced::Command<Self::Message>) {
-        (
-            match Launcher::new() {
-                Ok(launcher) => launcher,
-                Err(error) => Launcher::with_error(error.to_string()),
-            },
-            Command::none(),
-        )
+    fn new(flags: Self::Flags) -> (Self, iced::Command<Self::Message>) {
+        let mut launcher = match Launcher::new() {
+            Ok(launcher) => launcher,
+            Err(error) => Launcher::with_error(
+                error.to_string_with_code(),
+                error.restorable_file().map(Path::to_owned),
+            ),
+        };
+        let mut commands = vec![
+            launcher.load_news(),
+            launcher.refresh_server_statuses(),
+            launcher.check_for_updates(),
+            launcher.check_for_loader_updates(),
+            launcher.load_skins(),
+        ];
+        launcher.start_tray();
+        launcher.instance_guard = flags;
+        commands.push(launcher.handle_args(&std::env::args().skip(1).collect::<Vec<_>>()));
+        if launcher.window.maximized {
+            commands.push(iced::window::maximize(iced::window::Id::MAIN, true));
+        }
+        (launcher, Command::batch(commands))
+    }
+
+    fn theme(&self) -> Self::Theme {
+        self.config
+            .as_ref()
+            .map(LauncherConfig::get_theme)
+            .unwrap_or_default()
+    }
+
+    fn scale_factor(&self) -> f64 {
+        self.config
+            .as_ref()
+            .map(LauncherConfig::get_ui_scale)
+            .unwrap_or(1.0)
     }
 
     fn title(&self) -> String {
-        "Quantum Launcher".to_owned()
+        tr!("app-title")
     }
 
     fn update(&mut self, message: Self::Message) -> iced::Command<Self::Message> {
         match message {
             Message::LaunchInstanceSelected(selected_instance) => {
-                self.select_launch_instance(selected_instance)
+                self.select_launch_instance(selected_instance);
+                return self.load_skins();
             }
             Message::LaunchUsernameSet(username) => self.set_username(username),
+            Message::LaunchAccountSelected(choice) => {
+                self.edit_config(|config| {
+                    config.selected_profile = match choice {
+                        AccountChoice::Username => None,
+                        AccountChoice::OfflineProfile(name) => Some(name),
+                    }
+                });
+                return self.load_skins();
+            }
+            Message::SkinLoaded(source, result) => self.add_skin(source, result),
             Message::LaunchStart => return self.launch_game(),
-            Message::LaunchEnd(result) => self.finish_launching(result),
-            Message::CreateInstanceScreenOpen => return self.go_to_create_screen(),
-            Message::CreateInstanceVersionsLoaded(result) => {
-                self.create_instance_finish_loading_versions_list(result)
+            Message::LaunchRecent(instance) => return self.launch_recent(instance),
+            Message::LaunchWithOptionsOpen => self.go_to_launch_with_options(),
+            Message::LaunchWithOptionsUsernameInput(username) => {
+                if let State::LaunchWithOptions(menu) = &mut self.state {
+                    menu.username = username;
+                }
+            }
+            Message::LaunchWithOptionsJavaArgsInput(input) => {
+                if let State::LaunchWithOptions(menu) = &mut self.state {
+                    menu.java_args_input = input;
+                }
+            }
+            Message::LaunchWithOptionsGameArgsInput(input) => {
+                if let State::LaunchWithOptions(menu) = &mut self.state {
+                    menu.game_args_input = input;
+                }
+            }
+            Message::LaunchWithOptionsServerInput(server) => {
+                if let State::LaunchWithOptions(menu) = &mut self.state {
+                    menu.server = server;
+                }
+            }
+            Message::LaunchWithOptionsStart => return self.launch_with_options(),
+            Message::LaunchStartWithoutMods => return self.launch_game_without_mods(),
+            Message::LaunchJavaChecked(instance, result) => {
+                return self.finish_checking_java(instance, result)
+            }
+            Message::LaunchFixJava(instance, major_version) => {
+                return self.launch_with_managed_java(instance, major_version)
+            }
+            Message::LaunchEnd(instance_name, result) => {
+                return self.finish_launching(instance_name, result)
+            }
+            Message::CreateInstanceScreenOpen => return self.go_to_create_screen(false),
+            Message::CreateInstanceVersionsLoaded(source, result) => {
+                self.create_instance_finish_loading_versions_list(source, result)
+            }
+            Message::CreateInstanceVersionSourceSelected(source) => {
+                return self.select_version_source(source)
+            }
+            Message::CreateInstanceCustomVersionToggle(toggle) => {
+                if let State::Create(menu) = &mut self.state {
+                    menu.custom_version_json = toggle.then(String::new);
+                    if toggle {
+                        menu.template = None;
+                    }
+                }
+            }
+            Message::CreateInstanceCustomVersionInput(input) => {
+                if let State::Create(menu) = &mut self.state {
+                    menu.custom_version_json = Some(input);
+                }
+            }
+            Message::CreateInstanceServerPackToggle(toggle) => {
+                if let State::Create(menu) = &mut self.state {
+                    menu.server_pack = toggle.then(String::new);
+                    if toggle {
+                        menu.template = None;
+                    }
+                }
+            }
+            Message::CreateInstanceServerPackInput(input) => {
+                if let State::Create(menu) = &mut self.state {
+                    menu.server_pack = Some(input);
+                }
+            }
+            Message::CreateInstanceServerPackEnd(result) => {
+                self.finish_creating_from_server_pack(result)
+            }
+            Message::CreateInstanceTemplateSelected(choice) => return self.select_template(choice),
+            Message::CreateInstanceTemplateDelete => self.delete_selected_template(),
+            Message::CreateInstanceTemplateEnd(result) => {
+                self.finish_creating_from_template(result)
+            }
+            Message::CreateInstancePatchNotesLoaded(result) => {
+                if let State::Create(menu) = &mut self.state {
+                    menu.patch_notes = Some(result);
+                }
             }
             Message::CreateInstanceVersionSelected(selected_version) => {
                 self.select_created_instance_version(selected_version)
             }
+            Message::CreateInstanceVersionFilterInput(filter) => {
+                if let State::Create(menu) = &mut self.state {
+                    menu.version_filter = filter;
+                }
+            }
+            Message::CreateInstanceVersionGroupToggle(version_type) => {
+                if let State::Create(menu) = &mut self.state {
+                    if !menu.collapsed_groups.remove(&version_type) {
+                        menu.collapsed_groups.insert(version_type);
+                    }
+                }
+            }
+            Message::CreateInstanceVersionTypeShow(version_type, show) => {
+                self.edit_config(|config| config.set_shows_version_type(version_type, show))
+            }
+            Message::CreateInstanceVersionFavoriteToggle(version) => {
+                self.toggle_favorite_version(version)
+            }
             Message::CreateInstanceNameInput(name) => self.update_created_instance_name(name),
             Message::CreateInstanceStart => return self.create_instance(),
             Message::CreateInstanceEnd(result) => match result {
-                Ok(_) => match Launcher::new() {
-                    Ok(launcher) => *self = launcher,
-                    Err(err) => self.set_error(err.to_string()),
-                },
-                Err(n) => self.state = State::Error { error: n },
+                Ok(_) if matches!(&self.state, State::Create(menu) if menu.is_server) => {
+                    self.go_to_servers_screen();
+                    self.notify(ToastKind::Success, tr!("toast-server-created"));
+                }
+                // The instance list is updated by the `InstanceCreated` event.
+                Ok(_) => {
+                    self.copy_options_to_new_instance();
+                    self.go_to_launch_screen();
+                    self.notify(ToastKind::Success, tr!("toast-instance-created"));
+                }
+                Err(_) if self.take_cancelled_creation() => {
+                    self.notify(ToastKind::Info, tr!("toast-create-cancelled"));
+                }
+                Err(n) => self.set_error(n),
             },
-            Message::CreateInstanceProgressUpdate => self.update_instance_creation_progress_bar(),
-            Message::DeleteInstanceMenu => self.confirm_instance_deletion(),
-            Message::DeleteInstance => self.delete_selected_instance(),
-            Message::LaunchScreenOpen => self.go_to_launch_screen(),
+            Message::CreateInstanceProgress(progress) => {
+                self.update_instance_creation_progress_bar(progress)
+            }
+            Message::CreateInstanceCancel => self.cancel_instance_creation(),
+            Message::ConfirmOpen(action) => {
+                self.state = State::Confirm(MenuConfirm {
+                    action,
+                    input: String::new(),
+                })
+            }
+            Message::ConfirmInput(input) => {
+                if let State::Confirm(menu) = &mut self.state {
+                    menu.input = input;
+                }
+            }
+            Message::ConfirmAccept => return self.accept_confirmation(),
+            Message::ConfirmCancel => self.cancel_confirmation(),
+            Message::UninstallLoaderEnd(instance, result) => {
+                match result {
+                    Ok(()) => self.notify(ToastKind::Success, tr!("toast-loader-uninstalled")),
+                    Err(err) => self.notify(
+                        ToastKind::Error,
+                        tr!("toast-loader-uninstall-failed", error = err),
+                    ),
+                }
+                self.go_to_edit_mods_menu_wrapped(instance);
+            }
+            Message::JavaCleanupFound(result) => self.confirm_java_cleanup(result),
+            Message::DeleteUnusedJavaEnd(result) => self.finish_java_cleanup(result),
+            Message::LaunchScreenOpen => {
+                self.go_to_launch_screen();
+                return self.load_skins();
+            }
             Message::EditInstance => {
                 self.edit_instance_wrapped();
             }
@@ -69,43 +261,117 @@ impl Application for Launcher {
                     menu_edit_instance.config.java_override = Some(n);
                 }
             }
+            Message::EditInstanceJavaVersionSelected(choice) => {
+                if let State::EditInstance(menu_edit_instance) = &mut self.state {
+                    menu_edit_instance.config.java_version = choice.0;
+                }
+            }
             Message::EditInstanceMemoryChanged(new_slider_value) => {
                 if let State::EditInstance(menu_edit_instance) = &mut self.state {
-                    menu_edit_instance.slider_value = new_slider_value;
-                    menu_edit_instance.config.ram_in_mb = 2f32.powf(new_slider_value) as usize;
-                    menu_edit_instance.slider_text =
-                        format_memory(menu_edit_instance.config.ram_in_mb);
-                }
-            }
-            Message::EditInstanceSave => {
-                if let State::EditInstance(menu_edit_instance) = &self.state {
-                    if let Err(err) = Launcher::save_config(
-                        &menu_edit_instance.selected_instance,
-                        &menu_edit_instance.config,
-                    ) {
-                        self.set_error(err.to_string())
+                    let ram_in_mb = 2f32.powf(new_slider_value) as usize;
+                    menu_edit_instance.set_memory(ram_in_mb);
+                    menu_edit_instance.memory_input = format!("{ram_in_mb}M");
+                }
+            }
+            Message::EditInstanceMemoryInput(input) => {
+                if let State::EditInstance(menu_edit_instance) = &mut self.state {
+                    if let Some(ram_in_mb) = parse_memory_mb(&input) {
+                        menu_edit_instance.set_memory(ram_in_mb);
                     }
+                    menu_edit_instance.memory_input = input;
+                }
+            }
+            Message::EditInstanceInitialMemoryChanged(new_slider_value) => {
+                if let State::EditInstance(menu_edit_instance) = &mut self.state {
+                    let new_slider_value = new_slider_value.min(menu_edit_instance.slider_value);
+                    menu_edit_instance.set_initial_memory(new_slider_value);
+                }
+            }
+            Message::EditInstanceJavaArgsInput(input) => {
+                if let State::EditInstance(menu_edit_instance) = &mut self.state {
+                    menu_edit_instance.config.java_args = split_arguments(&input);
+                    menu_edit_instance.java_args_input = input;
+                }
+            }
+            Message::EditInstanceVersionTypeInput(input) => {
+                if let State::EditInstance(menu_edit_instance) = &mut self.state {
+                    menu_edit_instance.config.version_type = (!input.is_empty()).then_some(input);
+                }
+            }
+            Message::EditInstanceGameDirInput(input) => {
+                if let State::EditInstance(menu_edit_instance) = &mut self.state {
+                    menu_edit_instance.config.game_dir = (!input.is_empty()).then_some(input);
                 }
             }
-            Message::ManageModsScreenOpen => {
-                if let State::Launch(menu_launch) = &self.state {
-                    if let Err(err) =
-                        self.go_to_edit_mods_menu(menu_launch.selected_instance.clone().unwrap())
-                    {
-                        self.set_error(err.to_string())
+            Message::EditInstanceOfflineProfileSelected(choice) => {
+                if let State::EditInstance(menu_edit_instance) = &mut self.state {
+                    menu_edit_instance.config.offline_profile = choice.0;
+                }
+            }
+            Message::EditInstanceGameDirFromInstance(instance) => {
+                if let State::EditInstance(menu_edit_instance) = &mut self.state {
+                    match file_utils::get_launcher_dir() {
+                        Ok(launcher_dir) => {
+                            let game_dir = launcher_dir
+                                .join("instances")
+                                .join(instance)
+                                .join(".minecraft");
+                            menu_edit_instance.config.game_dir =
+                                Some(game_dir.to_string_lossy().into_owned());
+                        }
+                        Err(err) => self.set_error(err.to_string_with_code()),
                     }
                 }
             }
+            Message::EditInstanceSave => self.save_edited_instance(),
+            Message::EditInstanceSnapshotSmallFilesToggle(toggle) => {
+                if let State::EditInstance(menu_edit_instance) = &mut self.state {
+                    menu_edit_instance.config.snapshot_small_files = toggle;
+                }
+            }
+            Message::EditInstanceLog4ShellToggle(toggle) => {
+                if let State::EditInstance(menu_edit_instance) = &mut self.state {
+                    menu_edit_instance.config.log4shell_mitigation = toggle;
+                }
+            }
+            Message::EditInstanceSyncFolderToggle(toggle) => {
+                if let State::EditInstance(menu_edit_instance) = &mut self.state {
+                    menu_edit_instance.config.sync_folder = toggle;
+                }
+            }
+            Message::EditInstanceRollBackEnd(instance, result) => {
+                self.finish_rolling_back(instance, result)
+            }
+            Message::EditInstanceTemplateNameInput(input) => {
+                if let State::EditInstance(menu_edit_instance) = &mut self.state {
+                    menu_edit_instance.template_name_input = input;
+                }
+            }
+            Message::EditInstanceTemplateSave => return self.save_template(),
+            Message::EditInstanceTemplateSaved(result) => self.finish_saving_template(result),
+            Message::ManageModsScreenOpen => match &self.state {
+                State::Launch(menu_launch) => self
+                    .go_to_edit_mods_menu_wrapped(menu_launch.selected_instance.clone().unwrap()),
+                State::ModBrowser(menu) => {
+                    self.go_to_edit_mods_menu_wrapped(menu.selected_instance.clone())
+                }
+                _ => {}
+            },
             Message::InstallFabricScreenOpen => {
                 if let State::EditMods(menu) = &self.state {
+                    let selected_instance = menu.selected_instance.clone();
                     self.state = State::InstallFabric(MenuInstallFabric {
-                        selected_instance: menu.selected_instance.clone(),
+                        selected_instance: selected_instance.clone(),
                         fabric_version: None,
                         fabric_versions: Vec::new(),
+                        versions_cached: false,
+                        progress: None,
                     });
 
                     return Command::perform(
-                        instance_mod_installer::fabric::get_list_of_versions(),
+                        instance_mod_installer::fabric::get_list_of_versions_for_instance_wrapped(
+                            selected_instance,
+                        ),
                         Message::InstallFabricVersionsLoaded,
                     );
                 }
@@ -114,9 +380,11 @@ impl Application for Launcher {
                 Ok(list_of_versions) => {
                     if let State::InstallFabric(menu) = &mut self.state {
                         menu.fabric_versions = list_of_versions
+                            .versions
                             .iter()
                             .map(|ver| ver.version.clone())
                             .collect();
+                        menu.versions_cached = list_of_versions.cached;
                     }
                 }
                 Err(err) => self.set_error(err),
@@ -126,32 +394,46 @@ impl Application for Launcher {
                     menu.fabric_version = Some(selection);
                 }
             }
-            Message::InstallFabricClicked => {
-                if let State::InstallFabric(menu) = &self.state {
-                    return Command::perform(
-                        instance_mod_installer::fabric::install_wrapped(
-                            menu.fabric_version.clone().unwrap(),
-                            menu.selected_instance.to_owned(),
-                        ),
-                        Message::InstallFabricEnd,
-                    );
-                }
+            Message::InstallFabricClicked => return self.install_fabric(),
+            Message::InstallFabricProgress(progress) => {
+                self.update_fabric_install_progress(progress)
             }
             Message::InstallFabricEnd(result) => match result {
-                Ok(_) => self.go_to_launch_screen(),
-                Err(err) => self.set_error(err),
+                Ok(_) => {
+                    self.go_to_launch_screen();
+                    self.notify(ToastKind::Success, tr!("toast-fabric-installed"));
+                }
+                Err(err) => {
+                    if let State::InstallFabric(menu) = &mut self.state {
+                        menu.progress = None;
+                    }
+                    self.notify(
+                        ToastKind::Error,
+                        tr!("toast-fabric-install-failed", error = err),
+                    )
+                }
             },
             Message::OpenDir(dir) => match dir.to_str() {
                 Some(dir) => open_file_explorer(dir),
-                None => self.set_error(LauncherError::PathBufToString(dir).to_string()),
+                None => self.notify(
+                    ToastKind::Error,
+                    LauncherError::PathBufToString(dir).to_string(),
+                ),
             },
-            Message::LaunchJavaInstallProgressUpdate => {
+            Message::LaunchOpenFiles => self.open_game_dir(),
+            Message::LaunchJavaInstallCancel => self.cancel_java_install(),
+            Message::LaunchJavaInstallProgress(message) => {
                 if let State::Launch(MenuLaunch {
                     java_install_progress,
                     ..
+                })
+                | State::Servers(MenuServers {
+                    java_install_progress,
+                    ..
                 }) = &mut self.state
                 {
-                    let install_finished = receive_java_install_progress(java_install_progress);
+                    let install_finished =
+                        receive_java_install_progress(java_install_progress, message);
 
                     if install_finished {
                         *java_install_progress = None;
@@ -164,35 +446,710 @@ impl Application for Launcher {
                 }
             }
             Message::ErrorCopy => {
-                if let State::Error { error } = &self.state {
+                if let State::Error { error, .. } = &self.state {
                     return iced::clipboard::write(format!("QuantumLauncher Error: {error}"));
                 }
             }
+            Message::ErrorCopyDiagnostics => {
+                if let State::Error {
+                    error, instance, ..
+                } = &self.state
+                {
+                    return iced::clipboard::write(diagnostics::build_report(
+                        error,
+                        instance.as_deref(),
+                    ));
+                }
+            }
+            Message::CrashRecoveryCopy => {
+                if let State::CrashRecovery(menu) = &self.state {
+                    return iced::clipboard::write(diagnostics::build_crash_report(
+                        &menu.crash.text,
+                    ));
+                }
+            }
+            Message::CrashRecoveryReport => {
+                if let State::CrashRecovery(menu) = &self.state {
+                    let report = diagnostics::build_crash_report(&menu.crash.text);
+                    let title = format!("Crash: {}", menu.crash.message);
+                    open_file_explorer(&diagnostics::issue_url(&title, &report));
+                }
+            }
+            Message::ErrorReport => {
+                if let State::Error {
+                    error, instance, ..
+                } = &self.state
+                {
+                    let report = diagnostics::build_report(error, instance.as_deref());
+                    open_file_explorer(&diagnostics::issue_url(error, &report));
+                }
+            }
+            Message::ErrorRestoreBackup => self.restore_broken_file(),
+            Message::ErrorRedownloadVersionJson => return self.redownload_version_json(),
+            Message::ErrorRedownloadVersionJsonEnd(instance, result) => {
+                return self.finish_redownloading_version_json(instance, result)
+            }
+            Message::LauncherSettingsOpen => {
+                self.go_to_launcher_settings();
+                return self.load_skins();
+            }
+            Message::LauncherLogsOpen => {
+                self.state = State::LauncherLogs(MenuLauncherLogs {
+                    lines: logging::recent_logs(),
+                });
+            }
+            Message::LauncherLogsCopy => {
+                if let State::LauncherLogs(menu) = &self.state {
+                    return iced::clipboard::write(menu.lines.join("\n"));
+                }
+            }
+            Message::StatsScreenOpen => self.go_to_stats_screen(),
+            Message::DownloadQueueScreenOpen => self.go_to_download_queue(),
+            Message::DownloadQueueRefresh => self.refresh_download_queue(),
+            Message::DownloadQueuePause(id) => {
+                self.update_download_queue(|| download_queue::pause(id))
+            }
+            Message::DownloadQueueResume(id) => {
+                self.update_download_queue(|| download_queue::resume(id))
+            }
+            Message::DownloadQueueMoveUp(id) => {
+                self.update_download_queue(|| download_queue::move_up(id))
+            }
+            Message::DownloadQueueMoveDown(id) => {
+                self.update_download_queue(|| download_queue::move_down(id))
+            }
+            Message::WorldsScreenOpen => {
+                if let State::Launch(MenuLaunch {
+                    selected_instance: Some(instance),
+                    ..
+                }) = &self.state
+                {
+                    self.go_to_worlds_screen(instance.clone());
+                }
+            }
+            Message::GameOptionsScreenOpen => {
+                if let State::Launch(MenuLaunch {
+                    selected_instance: Some(instance),
+                    ..
+                }) = &self.state
+                {
+                    self.go_to_game_options(instance.clone());
+                }
+            }
+            Message::GameOptionsRenderDistanceInput(input) => {
+                if let State::GameOptions(menu) = &mut self.state {
+                    menu.render_distance_input = input;
+                }
+            }
+            Message::GameOptionsGuiScaleSelected(choice) => {
+                if let State::GameOptions(menu) = &mut self.state {
+                    menu.gui_scale = choice.0;
+                }
+            }
+            Message::GameOptionsMaxFpsInput(input) => {
+                if let State::GameOptions(menu) = &mut self.state {
+                    menu.max_fps_input = input;
+                }
+            }
+            Message::GameOptionsVsyncToggle(vsync) => {
+                if let State::GameOptions(menu) = &mut self.state {
+                    menu.vsync = vsync;
+                }
+            }
+            Message::GameOptionsLanguageInput(language) => {
+                if let State::GameOptions(menu) = &mut self.state {
+                    menu.language = language;
+                }
+            }
+            Message::GameOptionsCopySourceSelected(instance) => {
+                if let State::GameOptions(menu) = &mut self.state {
+                    menu.copy_source = Some(instance);
+                }
+            }
+            Message::GameOptionsImportKeybinds => self.import_keybinds(),
+            Message::GameOptionsCopyAll => self.copy_all_game_options(),
+            Message::GameOptionsResourcePackToggle(pack, enabled) => {
+                if let State::GameOptions(menu) = &mut self.state {
+                    menu.options.set_resource_pack_enabled(&pack, enabled);
+                }
+            }
+            Message::GameOptionsResourcePackInput(input) => {
+                if let State::GameOptions(menu) = &mut self.state {
+                    menu.resource_pack_input = input;
+                }
+            }
+            Message::GameOptionsEnableNewPackToggle(enable) => {
+                if let State::GameOptions(menu) = &mut self.state {
+                    menu.enable_new_pack = enable;
+                }
+            }
+            Message::GameOptionsResourcePackInstall => return self.install_resource_pack(),
+            Message::GameOptionsResourcePackInstallEnd(result) => {
+                self.finish_installing_resource_pack(result)
+            }
+            Message::LauncherSettingsOptionsSourceSelected(choice) => {
+                self.edit_config(|config| config.options_source = choice.0);
+            }
+            Message::GameOptionsSave => self.save_game_options(),
+            Message::VerifyScreenOpen => {
+                if let State::Launch(MenuLaunch {
+                    selected_instance: Some(instance),
+                    ..
+                }) = &self.state
+                {
+                    return self.go_to_verify_screen(instance.clone());
+                }
+            }
+            Message::VerifyEnd(result) => {
+                if let State::Verify(menu) = &mut self.state {
+                    menu.results = Some(result);
+                }
+            }
+            Message::VerifyFix(check) => return self.fix_instance(check),
+            Message::VerifyFixEnd(result) => return self.finish_fixing_instance(result),
+            Message::WorldsBackup(world) => return self.backup_world(world),
+            Message::WorldsBackupEnd(world, result) => self.finish_world_backup(&world, result),
+            Message::WorldsRestoreEnd(result) => self.finish_restoring_world(result),
+            Message::WorldsRetentionInput(input) => {
+                if let State::Worlds(menu) = &mut self.state {
+                    menu.retention_input = input;
+                }
+            }
+            Message::WorldsBackupOnVersionChangeToggle(toggle) => {
+                if let State::Worlds(menu) = &mut self.state {
+                    menu.config.backup_worlds_on_version_change = toggle;
+                }
+            }
+            Message::WorldsSave => self.save_world_backup_settings(),
+            Message::WorldsRenameStart(world) => {
+                if let State::Worlds(menu) = &mut self.state {
+                    let name = menu
+                        .worlds
+                        .iter()
+                        .find(|n| n.world == world)
+                        .and_then(|n| n.info.as_ref())
+                        .map_or_else(|| world.clone(), |info| info.name.clone());
+                    menu.rename = Some((world, name));
+                }
+            }
+            Message::WorldsRenameInput(input) => {
+                if let State::Worlds(MenuWorlds {
+                    rename: Some((_, name)),
+                    ..
+                }) = &mut self.state
+                {
+                    *name = input;
+                }
+            }
+            Message::WorldsRenameSave => self.rename_world(),
+            Message::WorldsRenameCancel => {
+                if let State::Worlds(menu) = &mut self.state {
+                    menu.rename = None;
+                }
+            }
+            Message::WorldsDuplicate(world) => return self.copy_world(world, None),
+            Message::WorldsCopyTargetSelected(instance) => {
+                if let State::Worlds(menu) = &mut self.state {
+                    menu.copy_target = Some(instance);
+                }
+            }
+            Message::WorldsCopy(world) => {
+                if let State::Worlds(menu) = &self.state {
+                    let target = menu.copy_target.clone();
+                    return self.copy_world(world, target);
+                }
+            }
+            Message::WorldsCopyEnd(result) => self.finish_copying_world(result),
+            Message::LauncherSettingsUsernameSet(username) => {
+                self.edit_config(|config| config.username = username)
+            }
+            Message::LauncherSettingsJavaInstallInput(input) => {
+                if let State::LauncherSettings(menu) = &mut self.state {
+                    menu.java_install_input = input;
+                }
+            }
+            Message::LauncherSettingsJavaInstallAdd => self.add_java_install(),
+            Message::LauncherSettingsJavaInstallRemove(index) => self.edit_config(|config| {
+                if index < config.java_installs.len() {
+                    config.java_installs.remove(index);
+                }
+            }),
+            Message::LauncherSettingsJavaCleanup => {
+                if let State::LauncherSettings(menu) = &mut self.state {
+                    menu.java_cleanup_status = Some(tr!("settings-java-cleanup-running"));
+                }
+                return Command::perform(
+                    quantum_launcher_backend::find_unused_java_installs_wrapped(),
+                    Message::JavaCleanupFound,
+                );
+            }
+            Message::LauncherSettingsParallelDownloadsChanged(value) => {
+                self.edit_config(|config| config.parallel_downloads = value as usize)
+            }
+            Message::LauncherSettingsJavaArgsInput(input) => {
+                self.edit_config(|config| config.java_args = split_arguments(&input));
+                if let State::LauncherSettings(menu) = &mut self.state {
+                    menu.java_args_input = input;
+                }
+            }
+            Message::LauncherSettingsMirrorInput(mirror) => {
+                self.edit_config(|config| config.download_mirror = non_empty(mirror))
+            }
+            Message::LauncherSettingsProxyInput(proxy) => {
+                self.edit_config(|config| config.proxy = non_empty(proxy))
+            }
+            Message::LauncherSettingsDiscordRpcToggle(toggle) => {
+                self.edit_config(|config| config.discord_rpc = toggle);
+                self.update_discord_presence();
+            }
+            Message::LauncherSettingsCloseOnLaunchToggle(toggle) => {
+                self.edit_config(|config| config.close_on_launch = toggle)
+            }
+            Message::LauncherSettingsThemeSelected(theme) => {
+                self.edit_config(|config| config.theme = theme)
+            }
+            Message::LauncherSettingsAccentSelected(accent) => {
+                self.edit_config(|config| config.accent_color = accent)
+            }
+            Message::LauncherSettingsStylePresetSelected(preset) => {
+                self.edit_config(|config| config.style_preset = preset)
+            }
+            Message::LauncherSettingsLanguageSelected(language) => {
+                i18n::set_language(language);
+                self.edit_config(|config| config.language = language)
+            }
+            Message::LauncherSettingsUiScaleChanged(scale) => {
+                if let State::LauncherSettings(menu) = &mut self.state {
+                    menu.ui_scale = Some(scale);
+                }
+            }
+            Message::LauncherSettingsUiScaleApply => {
+                if let State::LauncherSettings(MenuLauncherSettings {
+                    ui_scale: Some(scale),
+                    ..
+                }) = self.state
+                {
+                    self.edit_config(|config| config.ui_scale = scale);
+                }
+            }
+            Message::LauncherSettingsInstanceViewSelected(view) => {
+                self.edit_config(|config| config.instance_view = view)
+            }
+            Message::LauncherSettingsDataDirInput(input) => {
+                if let State::LauncherSettings(menu) = &mut self.state {
+                    menu.data_dir_input = input;
+                }
+            }
+            Message::LauncherSettingsDataDirMove => self.confirm_moving_data_dir(),
+            Message::LauncherSettingsDataDirMoveEnd(result) => self.finish_moving_data_dir(result),
+            Message::LauncherSettingsBackupSavesToggle(include_saves) => {
+                if let State::LauncherSettings(menu) = &mut self.state {
+                    menu.backup_include_saves = include_saves;
+                }
+            }
+            Message::LauncherSettingsBackup => return self.back_up_launcher(),
+            Message::LauncherSettingsBackupEnd(result) => self.finish_launcher_backup(result),
+            Message::LauncherSettingsRestoreInput(input) => {
+                if let State::LauncherSettings(menu) = &mut self.state {
+                    menu.restore_input = input;
+                }
+            }
+            Message::LauncherSettingsRestore => self.confirm_restoring_backup(),
+            Message::LauncherSettingsRestoreEnd(result) => self.finish_restoring_backup(result),
+            Message::Shortcut(shortcut) => return self.handle_shortcut(shortcut),
+            Message::GameProcessesUpdate => return self.update_game_processes(),
+            Message::BackendEvent(event) => self.handle_backend_event(event),
+            Message::GameLogOpen(pid) => self.open_game_log(pid),
+            Message::GameLogShare(pid) => return self.share_game_log(pid, false),
+            Message::GameLogShareCrashReport(pid) => return self.share_game_log(pid, true),
+            Message::GameLogShareEnd(result) => return self.finish_sharing_game_log(result),
+            Message::GameKill(pid) => self.kill_game(pid),
+            Message::GameClose(pid) => self.close_game(pid),
+            Message::ToastDismiss(id) => self.dismiss_toast(id),
+            Message::ToastsUpdate => self.remove_expired_toasts(),
+            Message::LauncherSettingsHighContrastToggle(toggle) => {
+                self.edit_config(|config| config.accessibility.high_contrast = toggle)
+            }
+            Message::LauncherSettingsLargeTextToggle(toggle) => {
+                self.edit_config(|config| config.accessibility.large_text = toggle)
+            }
+            Message::LauncherSettingsReduceMotionToggle(toggle) => {
+                self.edit_config(|config| config.accessibility.reduce_motion = toggle)
+            }
+            Message::NewsLoaded(result) => self.news = Some(result),
+            Message::OpenLink(link) => open_file_explorer(&link),
+            Message::WelcomeDataDirInput(input) => {
+                if let State::Welcome(menu) = &mut self.state {
+                    menu.data_dir_input = input;
+                }
+            }
+            Message::WelcomeNext => self.welcome_next(),
+            Message::WindowResized { width, height } => {
+                self.window.width = width as f32;
+                self.window.height = height as f32;
+            }
+            Message::WindowMoved { x, y } => self.window.position = Some((x, y)),
+            Message::WindowCloseRequested => return Launcher::request_close(),
+            Message::WindowClose { maximized } => return self.close_window(maximized),
+            Message::WelcomeBack => {
+                if let State::Welcome(menu) = &mut self.state {
+                    if let Some(step) = menu.step.previous() {
+                        menu.step = step;
+                    }
+                }
+            }
+            Message::ServersScreenOpen => self.go_to_servers_screen(),
+            Message::ServerSelected(server) => {
+                if let State::Servers(menu) = &mut self.state {
+                    menu.selected_server = Some(server);
+                }
+            }
+            Message::ServerCreateScreenOpen => return self.go_to_create_screen(true),
+            Message::ServerStart => return self.start_server(),
+            Message::ServerStartEnd(server, result) => self.finish_starting_server(server, result),
+            Message::ServerStop(pid) => self.stop_server(pid),
+            Message::ServerConsoleInput(input) => {
+                if let State::ServerConsole(menu) = &mut self.state {
+                    menu.command_input = input;
+                }
+            }
+            Message::ServerConsoleSubmit => self.send_server_command(),
+            Message::ServerEulaAgreeToggle(agreed) => {
+                if let State::ServerEula(menu) = &mut self.state {
+                    menu.agreed = agreed;
+                }
+            }
+            Message::ServerEulaAccept => return self.accept_server_eula(),
+            Message::ServerPropertiesOpen => self.go_to_server_properties(),
+            Message::ServerPropertiesPortInput(input) => {
+                if let State::ServerProperties(menu) = &mut self.state {
+                    menu.port_input = input;
+                }
+            }
+            Message::ServerPropertiesGameModeSelected(choice) => {
+                if let State::ServerProperties(menu) = &mut self.state {
+                    menu.gamemode = choice.0;
+                }
+            }
+            Message::ServerPropertiesDifficultySelected(choice) => {
+                if let State::ServerProperties(menu) = &mut self.state {
+                    menu.difficulty = choice.0;
+                }
+            }
+            Message::ServerPropertiesOnlineModeToggle(online_mode) => {
+                if let State::ServerProperties(menu) = &mut self.state {
+                    menu.online_mode = online_mode;
+                }
+            }
+            Message::ServerPropertiesMotdInput(motd) => {
+                if let State::ServerProperties(menu) = &mut self.state {
+                    menu.motd = motd;
+                }
+            }
+            Message::ServerPropertiesViewDistanceInput(input) => {
+                if let State::ServerProperties(menu) = &mut self.state {
+                    menu.view_distance_input = input;
+                }
+            }
+            Message::ServerPropertiesRawToggle => self.toggle_server_properties_raw(),
+            Message::ServerPropertiesRawEdit(action) => {
+                if let State::ServerProperties(MenuServerProperties { raw: Some(raw), .. }) =
+                    &mut self.state
+                {
+                    raw.perform(action);
+                }
+            }
+            Message::ServerPropertiesSave => self.save_server_properties(),
+            Message::ServerCreateSoftwareSelected(software) => {
+                return self.select_server_software(software)
+            }
+            Message::ServerCreateSoftwareVersionsLoaded(software, result) => {
+                self.finish_loading_server_software_versions(software, result)
+            }
+            Message::ServerPluginsOpen => {
+                if let State::Servers(MenuServers {
+                    selected_server: Some(server),
+                    ..
+                }) = &self.state
+                {
+                    self.go_to_server_plugins_wrapped(server.clone());
+                }
+            }
+            Message::ServerPluginsSourceSelected(source) => {
+                if let State::ServerPlugins(menu) = &mut self.state {
+                    menu.source = source;
+                }
+            }
+            Message::ServerPluginsInput(input) => {
+                if let State::ServerPlugins(menu) = &mut self.state {
+                    menu.plugin_input = input;
+                }
+            }
+            Message::ServerPluginsInstall => return self.install_plugins(),
+            Message::ServerPluginsInstallEnd(server, result) => {
+                self.finish_installing_plugins(server, result)
+            }
+            Message::ServerPluginsProgress(progress) => {
+                self.update_plugin_install_progress(progress)
+            }
+            Message::ServerScheduleOpen => self.go_to_server_schedule(),
+            Message::ServerScheduleBackupIntervalInput(input) => {
+                if let State::ServerSchedule(menu) = &mut self.state {
+                    menu.backup_interval_input = input;
+                }
+            }
+            Message::ServerScheduleBackupRetentionInput(input) => {
+                if let State::ServerSchedule(menu) = &mut self.state {
+                    menu.backup_retention_input = input;
+                }
+            }
+            Message::ServerScheduleRestartIntervalInput(input) => {
+                if let State::ServerSchedule(menu) = &mut self.state {
+                    menu.restart_interval_input = input;
+                }
+            }
+            Message::ServerScheduleSave => self.save_server_schedule(),
+            Message::ServerScheduleBackupNow => {
+                if let State::ServerSchedule(menu) = &self.state {
+                    return self.backup_server(menu.server.clone());
+                }
+            }
+            Message::ServerScheduleTick => return self.run_server_schedules(),
+            Message::ServerBackupEnd(server, result) => self.finish_server_backup(server, result),
+            Message::ServerStatusRefresh => return self.refresh_server_statuses(),
+            Message::ServerStatusLoaded(address, result) => {
+                self.finish_server_status(&address, result)
+            }
+            Message::ServersExternalInput(input) => {
+                if let State::Servers(menu) = &mut self.state {
+                    menu.external_server_input = input;
+                }
+            }
+            Message::ServersExternalAdd => return self.add_external_server(),
+            Message::ServersExternalRemove(address) => self.remove_external_server(&address),
+            Message::ServerConsoleShowMore => {
+                if let State::ServerConsole(menu) = &mut self.state {
+                    menu.shown_lines += MenuServerConsole::SHOWN_LINES_STEP;
+                }
+            }
+            Message::WelcomeFinish { create_instance } => {
+                self.edit_config(|config| config.setup_complete = true);
+                if create_instance {
+                    return self.go_to_create_screen(false);
+                }
+                self.go_to_launch_screen();
+            }
+            Message::TrayPoll => return self.handle_tray_actions(),
+            Message::SearchInput(query) => self.set_search_query(query),
+            Message::SearchOpenResult(index) => self.open_search_result(index),
+            Message::InstanceFoldersChanged(changes) => self.apply_instance_folder_changes(changes),
+            Message::OtherLaunchersPoll => return self.handle_other_launchers(),
+            Message::LaunchFavoriteToggle => self.toggle_favorite_instance(),
+            Message::LauncherSettingsTrayIconToggle(toggle) => {
+                self.edit_config(|config| config.tray_icon = toggle);
+                if toggle {
+                    self.start_tray();
+                } else {
+                    self.tray = None;
+                }
+            }
+            Message::UpdateCheckResult(result) => self.finish_update_check(result),
+            Message::LoaderUpdateCheck => return self.check_for_loader_updates(),
+            Message::ModBrowserOpen => {
+                if let State::EditMods(menu) = &self.state {
+                    return self.go_to_mod_browser(menu.selected_instance.clone());
+                }
+            }
+            Message::ModBrowserQueryInput(query) => return self.set_mod_browser_query(query),
+            Message::ModBrowserSearchDebounced(search_id) => {
+                return self.search_mods_debounced(search_id)
+            }
+            Message::ModBrowserSearch => return self.search_mods(),
+            Message::ModBrowserResults(search_id, result) => {
+                return self.finish_searching_mods(search_id, result)
+            }
+            Message::ModBrowserScrolled(viewport) => return self.scroll_mod_browser(viewport),
+            Message::ModBrowserLoadMore => return self.load_more_mods(),
+            Message::ModBrowserSelect(project_id) => return self.select_browsed_mod(project_id),
+            Message::ModBrowserDetailsLoaded(project_id, result) => {
+                return self.finish_loading_mod_details(project_id, result)
+            }
+            Message::ModBrowserImageLoaded(url, result) => self.add_mod_browser_image(url, result),
+            Message::ModBrowserInstall => return self.install_browsed_mod(),
+            Message::ModBrowserInstallEnd(instance, result) => {
+                self.finish_installing_browsed_mod(instance, result)
+            }
+            Message::ModBrowserProgress(progress) => self.update_mod_browser_progress(progress),
+            Message::LoaderUpdatesChecked(updates) => {
+                self.loader_updates = updates.into_iter().collect();
+            }
+            Message::ManageModsUpdateLoader => return self.update_loader(),
+            Message::ManageModsUpdateLoaderEnd(instance, result) => {
+                self.finish_updating_loader(instance, result)
+            }
+            Message::ManageModsExport(format) => return self.export_mod_list(format),
+            Message::ManageModsExportEnd(result) => return self.finish_exporting_mod_list(result),
+            Message::ManageModsImportInput(input) => {
+                if let State::EditMods(menu) = &mut self.state {
+                    menu.import_input = input;
+                }
+            }
+            Message::ManageModsImport => return self.import_mod_list(),
+            Message::ManageModsImportProgress(progress) => self.update_import_progress(progress),
+            Message::ManageModsImportEnd(instance, result) => {
+                self.finish_importing_mod_list(instance, result)
+            }
+            Message::UpdateScreenOpen => self.go_to_update_screen(),
+            Message::UpdateInstall => return self.install_update(),
+            Message::UpdateInstallEnd(result) => self.finish_installing_update(result),
+            Message::LauncherSettingsVerboseLoggingToggle(toggle) => {
+                self.edit_config(|config| config.verbose_logging = toggle);
+                logging::set_verbose(toggle);
+            }
+            Message::LauncherSettingsAllowInvalidUsernameToggle(toggle) => {
+                self.edit_config(|config| config.allow_invalid_username = toggle);
+            }
+            Message::LauncherSettingsProfileNameInput(input) => {
+                if let State::LauncherSettings(menu) = &mut self.state {
+                    menu.profile_name_input = input;
+                }
+            }
+            Message::LauncherSettingsProfileUsernameInput(input) => {
+                if let State::LauncherSettings(menu) = &mut self.state {
+                    menu.profile_username_input = input;
+                }
+            }
+            Message::LauncherSettingsProfileSkinInput(input) => {
+                if let State::LauncherSettings(menu) = &mut self.state {
+                    menu.profile_skin_input = input;
+                }
+            }
+            Message::LauncherSettingsProfileAdd => {
+                self.add_offline_profile();
+                return self.load_skins();
+            }
+            Message::LauncherSettingsProfileDelete(name) => {
+                self.edit_config(|config| {
+                    config
+                        .offline_profiles
+                        .retain(|profile| profile.name != name);
+                    if config.selected_profile.as_ref() == Some(&name) {
+                        config.selected_profile = None;
+                    }
+                });
+            }
+            Message::LauncherSettingsCheckUpdatesToggle(toggle) => {
+                self.edit_config(|config| config.check_for_updates = toggle);
+                return self.check_for_updates();
+            }
+            Message::LauncherSettingsShowNewsToggle(toggle) => {
+                self.edit_config(|config| config.show_news = toggle);
+                return self.load_news();
+            }
         }
         Command::none()
     }
 
     fn subscription(&self) -> iced::Subscription<Self::Message> {
+        Subscription::batch([
+            self.progress_subscription(),
+            self.game_processes_subscription(),
+            backend_events(),
+            self.toasts_subscription(),
+            self.server_status_subscription(),
+            self.download_queue_subscription(),
+            self.server_schedule_subscription(),
+            loader_update_subscription(),
+            self.tray_subscription(),
+            self.instance_folders_subscription(),
+            self.other_launchers_subscription(),
+            iced::event::listen_with(window_event),
+            shortcuts::subscription(),
+        ])
+    }
+
+    fn view(&self) -> iced::Element<'_, Self::Message, Self::Theme, iced::Renderer> {
+        let mut view = widget::row![widget::container(self.menu_view()).width(iced::Length::Fill)];
+        if !self.processes.is_empty() {
+            view = view.push(menu_renderer::game_processes_sidebar(&self.processes));
+        }
+        if self.show_shortcut_help {
+            view = view.push(menu_renderer::shortcut_help());
+        }
+
+        let mut view = widget::column![view];
+        if let Some(search) = &self.search {
+            view = widget::column![menu_renderer::search_palette(search), view];
+        }
+        if self.toasts.is_empty() {
+            view.into()
+        } else {
+            widget::column![menu_renderer::toasts(&self.toasts), view].into()
+        }
+    }
+}
+
+impl Launcher {
+    fn progress_subscription(&self) -> Subscription<Message> {
         const UPDATES_PER_SECOND: u64 = 15;
 
-        if let State::Create(menu) = &self.state {
-            if menu.progress_receiver.is_none() {
-                return Subscription::none();
-            }
-            return iced::time::every(Duration::from_millis(1000 / UPDATES_PER_SECOND))
-                .map(|_| Message::CreateInstanceProgressUpdate);
+        if let State::Create(MenuCreateInstance {
+            progress_receiver: Some(receiver),
+            ..
+        }) = &self.state
+        {
+            return progress_updates(receiver, Message::CreateInstanceProgress);
+        }
+
+        if let State::InstallFabric(MenuInstallFabric {
+            progress: Some(progress),
+            ..
+        }) = &self.state
+        {
+            return progress_updates(&progress.receiver, Message::InstallFabricProgress);
         }
 
         if let State::Launch(MenuLaunch {
-            java_install_progress: Some(_),
+            java_install_progress: Some(progress),
+            ..
+        })
+        | State::Servers(MenuServers {
+            java_install_progress: Some(progress),
             ..
         }) = &self.state
         {
-            return iced::time::every(Duration::from_millis(1000 / UPDATES_PER_SECOND))
-                .map(|_| Message::LaunchJavaInstallProgressUpdate);
+            return progress_updates(&progress.recv, Message::LaunchJavaInstallProgress);
+        }
+
+        if let State::ModBrowser(MenuModBrowser {
+            progress: Some(progress),
+            ..
+        }) = &self.state
+        {
+            return progress_updates(&progress.receiver, Message::ModBrowserProgress);
         }
 
-        if let State::EditInstance(_) = &self.state {
+        if let State::EditMods(MenuEditMods {
+            import_progress: Some(progress),
+            ..
+        }) = &self.state
+        {
+            return progress_updates(&progress.receiver, Message::ManageModsImportProgress);
+        }
+
+        if let State::ServerPlugins(MenuServerPlugins {
+            progress: Some(progress),
+            ..
+        }) = &self.state
+        {
+            return progress_updates(&progress.receiver, Message::ServerPluginsProgress);
+        }
+
+        if let State::EditInstance(MenuEditInstance {
+            rolling_back: false,
+            ..
+        }) = &self.state
+        {
             return iced::time::every(Duration::from_millis(1000 / UPDATES_PER_SECOND))
                 .map(|_| Message::EditInstanceSave);
         }
@@ -200,51 +1157,267 @@ impl Application for Launcher {
         Subscription::none()
     }
 
-    fn view(&self) -> iced::Element<'_, Self::Message, Self::Theme, iced::Renderer> {
+    fn server_status_subscription(&self) -> Subscription<Message> {
+        const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+        // The statuses are only shown on the launch screen.
+        if matches!(self.state, State::Launch(_)) {
+            iced::time::every(REFRESH_INTERVAL).map(|_| Message::ServerStatusRefresh)
+        } else {
+            Subscription::none()
+        }
+    }
+
+    fn download_queue_subscription(&self) -> Subscription<Message> {
+        const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+        if matches!(self.state, State::DownloadQueue(_)) {
+            iced::time::every(REFRESH_INTERVAL).map(|_| Message::DownloadQueueRefresh)
+        } else {
+            Subscription::none()
+        }
+    }
+
+    fn server_schedule_subscription(&self) -> Subscription<Message> {
+        // Schedules are in minutes, so this is precise enough.
+        const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+        if self
+            .processes
+            .iter()
+            .any(|process| process.is_server && process.exit_status.is_none())
+        {
+            iced::time::every(CHECK_INTERVAL).map(|_| Message::ServerScheduleTick)
+        } else {
+            Subscription::none()
+        }
+    }
+
+    fn toasts_subscription(&self) -> Subscription<Message> {
+        let reduce_motion = self
+            .config
+            .as_ref()
+            .is_some_and(|config| config.accessibility.reduce_motion);
+        if !reduce_motion
+            && self
+                .toasts
+                .iter()
+                .any(|toast| toast.kind != ToastKind::Error)
+        {
+            iced::time::every(Duration::from_secs(1)).map(|_| Message::ToastsUpdate)
+        } else {
+            Subscription::none()
+        }
+    }
+
+    /// Instances and servers copied in or deleted by hand.
+    fn instance_folders_subscription(&self) -> Subscription<Message> {
+        if self.instances.is_none() {
+            return Subscription::none();
+        }
+        iced::subscription::run_with_id(
+            "instance-folders",
+            iced::futures::stream::unfold(None, |watcher| async move {
+                let mut watcher = match watcher {
+                    Some(watcher) => watcher,
+                    None => match InstanceFolderWatcher::new() {
+                        Ok(watcher) => watcher,
+                        Err(err) => {
+                            error!("Could not watch the instance folders: {err}");
+                            return None;
+                        }
+                    },
+                };
+                let changes = watcher.next_change().await?;
+                Some((Message::InstanceFoldersChanged(changes), Some(watcher)))
+            }),
+        )
+    }
+
+    fn tray_subscription(&self) -> Subscription<Message> {
+        // Clicks in the tray menu come in on another thread.
+        const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+        if self.tray.is_some() {
+            iced::time::every(POLL_INTERVAL).map(|_| Message::TrayPoll)
+        } else {
+            Subscription::none()
+        }
+    }
+
+    fn other_launchers_subscription(&self) -> Subscription<Message> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+        if self.instance_guard.is_some() {
+            iced::time::every(POLL_INTERVAL).map(|_| Message::OtherLaunchersPoll)
+        } else {
+            Subscription::none()
+        }
+    }
+
+    fn game_processes_subscription(&self) -> Subscription<Message> {
+        // Often enough for the log to feel live.
+        const UPDATES_PER_SECOND: u64 = 4;
+
+        if self
+            .processes
+            .iter()
+            .any(|process| process.exit_status.is_none())
+        {
+            iced::time::every(Duration::from_millis(1000 / UPDATES_PER_SECOND))
+                .map(|_| Message::GameProcessesUpdate)
+        } else {
+            Subscription::none()
+        }
+    }
+
+    fn menu_view(&self) -> menu_renderer::Element<'_> {
         match &self.state {
-            State::Launch(menu) => menu.view(self.config.as_ref(), self.instances.as_deref()),
+            State::Launch(menu) => menu.view(
+                self.config.as_ref(),
+                self.instances.as_ref().map(InstanceRegistry::instances),
+                self.news.as_ref(),
+                &self.server_statuses,
+                self.update.as_ref(),
+                &self.skins,
+            ),
             State::EditInstance(menu) => menu.view(),
-            State::EditMods(menu) => menu.view(),
-            State::Create(menu) => menu.view(),
-            State::DeleteInstance(menu) => menu.view(),
-            State::Error { error } => widget::column!(
-                widget::text(format!("Error: {}", error)),
-                widget::button("Back").on_press(Message::LaunchScreenOpen),
-                widget::button("Copy Error").on_press(Message::ErrorCopy),
+            State::EditMods(menu) => menu.view(self.loader_updates.get(&menu.selected_instance)),
+            State::ModBrowser(menu) => menu.view(),
+            State::Create(menu) => menu.view(self.config.as_ref()),
+            State::Confirm(menu) => menu.view(),
+            State::Error {
+                error,
+                instance,
+                restore,
+                launch_phase,
+            } => widget::column!(
+                widget::text(tr!("error-title", error = error)),
+                widget::text(
+                    launch_phase
+                        .map(menu_renderer::launch_error_hint)
+                        .unwrap_or_default()
+                ),
+                widget::button(widget::text(tr!("error-restore-backup")))
+                    .on_press_maybe(restore.is_some().then_some(Message::ErrorRestoreBackup)),
+                widget::button(widget::text(tr!("error-redownload-version-json"))).on_press_maybe(
+                    (*launch_phase == Some(LaunchPhase::ReadVersionJson)
+                        && instance.as_deref().is_some_and(|instance| self
+                            .instances
+                            .as_ref()
+                            .is_some_and(|instances| instances.get(instance).is_some())))
+                    .then_some(Message::ErrorRedownloadVersionJson)
+                ),
+                widget::button(widget::text(tr!("back"))).on_press(Message::LaunchScreenOpen),
+                widget::button(widget::text(tr!("error-copy"))).on_press(Message::ErrorCopy),
+                widget::button(widget::text(tr!("error-copy-diagnostics")))
+                    .on_press(Message::ErrorCopyDiagnostics),
+                widget::button(widget::text(tr!("error-report"))).on_press(Message::ErrorReport),
+                widget::text(tr!("error-report-description")).size(text_size(12)),
             )
             .into(),
             State::InstallFabric(menu) => menu.view(),
+            State::LauncherSettings(menu) => menu.view(
+                self.config.as_ref(),
+                self.instances.as_ref().map(InstanceRegistry::instances),
+                &self.skins,
+            ),
+            State::GameLog(menu) => menu.view(&self.processes),
+            State::Welcome(menu) => menu.view(self.config.as_ref()),
+            State::Servers(menu) => menu.view(&self.processes, self.config.as_ref()),
+            State::ServerConsole(menu) => menu.view(&self.processes),
+            State::ServerEula(menu) => menu.view(),
+            State::ServerProperties(menu) => menu.view(),
+            State::ServerPlugins(menu) => menu.view(),
+            State::ServerSchedule(menu) => menu.view(),
+            State::Stats(menu) => menu.view(),
+            State::DownloadQueue(menu) => menu.view(),
+            State::Update(menu) => menu.view(),
+            State::LauncherLogs(menu) => menu.view(),
+            State::CrashRecovery(menu) => menu.view(),
+            State::Worlds(menu) => {
+                menu.view(self.instances.as_ref().map(InstanceRegistry::instances))
+            }
+            State::Verify(menu) => menu.view(),
+            State::LaunchWithOptions(menu) => menu.view(self.config.as_ref()),
+            State::GameOptions(menu) => {
+                menu.view(self.instances.as_ref().map(InstanceRegistry::instances))
+            }
         }
     }
 }
 
+/// Turns the updates sent through a progress channel into
+/// messages, as they arrive.
+fn progress_updates<T: Send + 'static>(
+    receiver: &ProgressReceiver<T>,
+    to_message: fn(T) -> Message,
+) -> Subscription<Message> {
+    let receiver = receiver.clone();
+    iced::subscription::run_with_id(
+        receiver.id(),
+        iced::futures::stream::unfold(receiver, move |receiver| async move {
+            let progress = receiver.recv().await?;
+            Some((to_message(progress), receiver))
+        }),
+    )
+}
+
+/// Loaders don't get releases often, so a
+/// few checks a day are plenty.
+fn loader_update_subscription() -> Subscription<Message> {
+    const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+    iced::time::every(CHECK_INTERVAL).map(|_| Message::LoaderUpdateCheck)
+}
+
+/// Every [`events::Event`], as it's emitted.
+fn backend_events() -> Subscription<Message> {
+    iced::subscription::run_with_id(
+        "backend-events",
+        iced::futures::stream::unfold(events::subscribe(), |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((Message::BackendEvent(event), receiver)),
+                    Err(events::RecvError::Lagged(missed)) => {
+                        warn!("Missed {missed} backend events");
+                    }
+                    Err(events::RecvError::Closed) => return None,
+                }
+            }
+        }),
+    )
+}
+
 fn receive_java_install_progress(
     java_install_progress: &mut Option<launcher_state::JavaInstallProgress>,
+    message: JavaInstallMessage,
 ) -> bool {
     let Some(java_install_progress) = java_install_progress else {
         return true;
     };
 
-    if let Ok(message) = java_install_progress.recv.try_recv() {
-        match message {
-            quantum_launcher_backend::JavaInstallMessage::P1Started => {
-                java_install_progress.num = 0.0;
-                java_install_progress.message = "Starting up (2/2)".to_owned();
-            }
-            quantum_launcher_backend::JavaInstallMessage::P2 {
-                progress,
-                out_of,
-                name,
-            } => {
-                java_install_progress.num = (progress as f32) / (out_of as f32);
-                java_install_progress.message =
-                    format!("Downloading ({progress}/{out_of}): {name}");
-            }
-            quantum_launcher_backend::JavaInstallMessage::P3Done => {
-                java_install_progress.num = 1.0;
-                java_install_progress.message = "Done!".to_owned();
-                return true;
-            }
+    match message {
+        JavaInstallMessage::P1Started => {
+            java_install_progress.num = 0.0;
+            java_install_progress.message = tr!("java-install-starting-2");
+        }
+        JavaInstallMessage::P2 {
+            progress,
+            out_of,
+            name,
+        } => {
+            java_install_progress.num = (progress as f32) / (out_of as f32);
+            java_install_progress.message = tr!(
+                "java-install-downloading",
+                progress = progress,
+                out_of = out_of,
+                name = name
+            );
+        }
+        JavaInstallMessage::P3Done => {
+            java_install_progress.num = 1.0;
+            java_install_progress.message = tr!("java-install-done");
+            return true;
         }
     }
     false
@@ -264,17 +1437,56 @@ fn receive_java_install_progress(
 //         .map(|n| n.path().to_owned())
 // }
 
+fn window_event(event: iced::Event, _status: iced::event::Status) -> Option<Message> {
+    let iced::Event::Window(id, event) = event else {
+        return None;
+    };
+    if id != iced::window::Id::MAIN {
+        return None;
+    }
+
+    match event {
+        iced::window::Event::Resized { width, height } => {
+            Some(Message::WindowResized { width, height })
+        }
+        iced::window::Event::Moved { x, y } => Some(Message::WindowMoved { x, y }),
+        iced::window::Event::CloseRequested => Some(Message::WindowCloseRequested),
+        _ => None,
+    }
+}
+
 fn main() {
-    const WINDOW_HEIGHT: f32 = 550.0;
-    const WINDOW_WIDTH: f32 = 220.0;
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--portable") {
+        file_utils::set_portable(true);
+    }
+    logging::init(LogConsole::Stdout);
+    crash_dump::install_panic_hook("QuantumLauncher", env!("CARGO_PKG_VERSION"));
+    let Some(instance_guard) = InstanceGuard::acquire(&args) else {
+        return;
+    };
+    let config = LauncherConfig::load().ok();
+    if args.iter().any(|arg| arg == "--verbose")
+        || config.as_ref().is_some_and(|config| config.verbose_logging)
+    {
+        logging::set_verbose(true);
+    }
+    let window = config
+        .as_ref()
+        .and_then(|config| config.window)
+        .unwrap_or_default();
+    let accessibility = config
+        .map(|config| config.accessibility)
+        .unwrap_or_default();
+    stylesheet::styles::set_min_text_size(accessibility.min_text_size());
 
     Launcher::run(Settings {
         window: iced::window::Settings {
-            size: iced::Size {
-                width: WINDOW_WIDTH,
-                height: WINDOW_HEIGHT,
-            },
+            size: window.size(),
+            position: window.position(),
             resizable: true,
+            // The window size and position are saved before closing.
+            exit_on_close_request: false,
             ..Default::default()
         },
         fonts: vec![
@@ -286,6 +1498,8 @@ fn main() {
                 .into(),
         ],
         default_font: iced::Font::with_name("Inter"),
+        default_text_size: accessibility.default_text_size().into(),
+        flags: Some(instance_guard),
         ..Default::default()
     })
     .unwrap();
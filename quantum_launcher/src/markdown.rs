@@ -0,0 +1,220 @@
+//! Splitting Markdown (like a mod's description) into blocks that
+//! can be shown with plain text widgets.
+//!
+//! Only the common parts are supported: headings, paragraphs,
+//! lists, quotes, code blocks and rules. Inline formatting, links
+//! and HTML tags are turned into plain text, and images are left
+//! out, as the gallery shows them anyway.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    /// The level (1 to 6), and the text.
+    Heading(u8, String),
+    Paragraph(String),
+    ListItem(String),
+    Quote(String),
+    Code(String),
+    Rule,
+}
+
+pub fn parse(markdown: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut paragraph = String::new();
+    let mut code: Option<String> = None;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if let Some(code_text) = &mut code {
+            if trimmed.starts_with("```") {
+                blocks.push(Block::Code(code_text.trim_end().to_owned()));
+                code = None;
+            } else {
+                code_text.push_str(line);
+                code_text.push('\n');
+            }
+            continue;
+        }
+
+        let block = if trimmed.starts_with("```") {
+            code = Some(String::new());
+            None
+        } else if trimmed.is_empty() {
+            None
+        } else if let Some((level, heading)) = heading(trimmed) {
+            Some(Block::Heading(level, inline_text(heading)))
+        } else if is_rule(trimmed) {
+            Some(Block::Rule)
+        } else if let Some(item) = list_item(trimmed) {
+            Some(Block::ListItem(inline_text(item)))
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            Some(Block::Quote(inline_text(quote.trim_start())))
+        } else {
+            if !paragraph.is_empty() {
+                paragraph.push(' ');
+            }
+            paragraph.push_str(trimmed);
+            continue;
+        };
+
+        // Anything but more paragraph text ends the paragraph.
+        flush_paragraph(&mut blocks, &mut paragraph);
+        if let Some(block) = block {
+            if !matches!(&block, Block::Heading(_, text) | Block::ListItem(text) | Block::Quote(text) if text.is_empty())
+            {
+                blocks.push(block);
+            }
+        }
+    }
+    flush_paragraph(&mut blocks, &mut paragraph);
+    if let Some(code_text) = code {
+        blocks.push(Block::Code(code_text.trim_end().to_owned()));
+    }
+    blocks
+}
+
+fn flush_paragraph(blocks: &mut Vec<Block>, paragraph: &mut String) {
+    let text = inline_text(paragraph);
+    if !text.is_empty() {
+        blocks.push(Block::Paragraph(text));
+    }
+    paragraph.clear();
+}
+
+fn heading(line: &str) -> Option<(u8, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let text = line[level..].strip_prefix(' ')?;
+    (1..=6)
+        .contains(&level)
+        .then(|| (level as u8, text.trim_end_matches('#').trim()))
+}
+
+fn is_rule(line: &str) -> bool {
+    let line: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    line.len() >= 3
+        && ["-", "*", "_"]
+            .iter()
+            .any(|marker| line.chars().all(|c| c.to_string() == *marker))
+}
+
+fn list_item(line: &str) -> Option<&str> {
+    if let Some(item) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+    {
+        return Some(item);
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+    line[digits..]
+        .strip_prefix(". ")
+        .or_else(|| line[digits..].strip_prefix(") "))
+}
+
+/// Plain text from a line of Markdown: links become their
+/// text, and images, HTML tags and formatting are removed.
+fn inline_text(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if let Some(image) = rest.strip_prefix('!') {
+            if let Some((_, end)) = split_link(image) {
+                rest = &image[end..];
+                continue;
+            }
+        }
+        if c == '[' {
+            if let Some((label, end)) = split_link(rest) {
+                result.push_str(&inline_text(label));
+                rest = &rest[end..];
+                continue;
+            }
+        }
+        if c == '<' {
+            if let Some(end) = rest.find('>') {
+                rest = &rest[end + 1..];
+                continue;
+            }
+        }
+        if !matches!(c, '*' | '`') && !rest.starts_with("__") && !rest.starts_with("~~") {
+            result.push(c);
+        }
+        let len = if rest.starts_with("__") || rest.starts_with("~~") {
+            2
+        } else {
+            c.len_utf8()
+        };
+        rest = &rest[len..];
+    }
+
+    let result = result
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    result.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// For `[label](url)` at the start of `text`, the label
+/// and where the link ends. Labels can have links in them,
+/// like badges: `[![alt](image)](url)`.
+fn split_link(text: &str) -> Option<(&str, usize)> {
+    let mut depth = 0;
+    let mut label_end = None;
+    for (i, c) in text.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    label_end = Some(i);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let label_end = label_end?;
+    let url = text[label_end + 1..].strip_prefix('(')?;
+    let url_len = url.find(')')?;
+    Some((&text[1..label_end], label_end + 2 + url_len + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let markdown = "\
+# Sodium&nbsp;[![badge](https://img)](https://link)
+
+A **modern** rendering engine,
+with [docs](https://docs) and <b>HTML</b>.
+
+<p align=\"center\"><img src=\"banner.png\"></p>
+
+- Faster __chunks__
+2. `Fewer` bugs
+> Quoted
+---
+```
+code here
+```";
+        assert_eq!(
+            parse(markdown),
+            [
+                Block::Heading(1, "Sodium".to_owned()),
+                Block::Paragraph("A modern rendering engine, with docs and HTML.".to_owned()),
+                Block::ListItem("Faster chunks".to_owned()),
+                Block::ListItem("Fewer bugs".to_owned()),
+                Block::Quote("Quoted".to_owned()),
+                Block::Rule,
+                Block::Code("code here".to_owned()),
+            ]
+        );
+    }
+}
@@ -1,16 +1,59 @@
-use std::ops::RangeInclusive;
+use std::{collections::HashMap, ops::RangeInclusive};
 
-use iced::widget::{self, column, row};
-use quantum_launcher_backend::file_utils;
+use iced::{
+    widget::{self, column, row, Column, Row},
+    Length,
+};
+use quantum_launcher_backend::{
+    cancel::CancelToken,
+    check_username, crash_dump, discord_rpc,
+    download_queue::{Job, JobKind, JobStatus},
+    error::LaunchPhase,
+    file_utils,
+    game_options::{self, GameSettings},
+    instance_mod_installer::{
+        mod_list::ModListFormat, modrinth_search::SearchHit, plugins::PluginSource,
+    },
+    integrity::{Check, CheckStatus},
+    json_structs::{
+        json_instance_config::parse_memory_mb, json_manifest::VersionSource, json_news::NewsEntry,
+    },
+    launcher_backup, logging,
+    self_update::{Release, UpdateOutcome},
+    server_files::{self, ServerSettings},
+    server_software::ServerSoftware,
+    skins::SkinSource,
+    snapshots::{Snapshot, SnapshotReason},
+    world_backup::WorldBackups,
+    ListedVersion, UsernameProblem, VersionType, USERNAME_LENGTH,
+};
 
 use crate::{
-    config::LauncherConfig,
+    config::{InstanceView, LauncherConfig, ThemeChoice, UI_SCALE_RANGE},
+    i18n::Language,
     icon_manager,
     launcher_state::{
-        Launcher, MenuCreateInstance, MenuDeleteInstance, MenuEditInstance, MenuEditMods,
-        MenuInstallFabric, MenuLaunch, Message,
+        AccountChoice, DestructiveAction, DifficultyChoice, GameModeChoice, GameProcess,
+        GuiScaleChoice, InstanceInfo, InstanceProfileChoice, JavaInstallProgress,
+        JavaVersionChoice, Launcher, MenuConfirm, MenuCrashRecovery, MenuCreateInstance,
+        MenuDownloadQueue, MenuEditInstance, MenuEditMods, MenuGameLog, MenuGameOptions,
+        MenuInstallFabric, MenuLaunch, MenuLaunchWithOptions, MenuLauncherLogs,
+        MenuLauncherSettings, MenuModBrowser, MenuServerConsole, MenuServerEula, MenuServerPlugins,
+        MenuServerProperties, MenuServerSchedule, MenuServers, MenuStats, MenuUpdate, MenuVerify,
+        MenuWelcome, MenuWorlds, Message, OptionsSourceChoice, SelectedMod, ServerStatusEntry,
+        TemplateChoice, Toast, ToastKind, WelcomeStep,
     },
-    stylesheet::styles::LauncherTheme,
+    markdown::Block,
+    message_handler::{
+        format_bytes, format_duration, format_last_played, format_memory, format_playtime,
+    },
+    search::{SearchPalette, SearchTarget},
+    shortcuts::Shortcut,
+    stylesheet::{
+        color::AccentColor,
+        styles::{text_size, LauncherTheme, StylePreset},
+    },
+    tr, tray,
 };
 
 pub type Element<'a> =
@@ -18,261 +61,3469 @@ pub type Element<'a> =
 
 fn button_with_icon<'element>(
     icon: Element<'element>,
-    text: &'element str,
+    text: String,
 ) -> iced::widget::Button<'element, Message, LauncherTheme> {
-    widget::button(row![icon, text].spacing(10).padding(5))
+    widget::button(row![icon, widget::text(text)].spacing(10).padding(5))
 }
 
-impl MenuLaunch {
+/// A Cancel button, greyed out once cancelled.
+fn cancel_button<'element>(cancel: Option<&CancelToken>, message: Message) -> Element<'element> {
+    widget::button(widget::text(tr!("cancel")))
+        .on_press_maybe(cancel.is_some_and(|n| !n.is_cancelled()).then_some(message))
+        .into()
+}
+
+/// Installing Java for a game or server being started.
+fn java_install_progress(progress: &JavaInstallProgress) -> Column<'_, Message, LauncherTheme> {
+    column![
+        widget::progress_bar(0.0..=1.0, progress.num),
+        widget::text(&progress.message),
+        cancel_button(Some(&progress.cancel), Message::LaunchJavaInstallCancel),
+    ]
+    .spacing(5)
+}
+
+/// The panel listing all keyboard shortcuts, toggled with F1.
+pub fn shortcut_help<'element>() -> Element<'element> {
+    let shortcuts = Column::with_children(Shortcut::ALL.iter().map(|shortcut| {
+        column![
+            widget::text(shortcut.keys()).size(text_size(14)),
+            widget::text(shortcut.description()),
+        ]
+        .into()
+    }))
+    .spacing(10);
+
+    widget::container(
+        column![widget::text(tr!("shortcut-title")).size(20), shortcuts]
+            .padding(10)
+            .spacing(20),
+    )
+    .width(250)
+    .into()
+}
+
+/// What to try when launching failed at `phase`.
+pub fn launch_error_hint(phase: LaunchPhase) -> String {
+    match phase {
+        LaunchPhase::ReadConfig => tr!("error-hint-config"),
+        LaunchPhase::ReadVersionJson => tr!("error-hint-version-json"),
+        LaunchPhase::Classpath => tr!("error-hint-classpath"),
+        LaunchPhase::Java => tr!("error-hint-java"),
+        LaunchPhase::Spawn => tr!("error-hint-spawn"),
+    }
+}
+
+/// Shown under username fields while the name breaks
+/// Minecraft's rules. Names with spaces or nothing at all
+/// can't launch; anything else can if `allow_invalid` is set.
+fn username_feedback<'element>(username: &str, allow_invalid: bool) -> Option<Element<'element>> {
+    let problem = check_username(username).err()?;
+    let reason = match problem {
+        UsernameProblem::Empty => tr!("username-empty"),
+        UsernameProblem::Space => tr!("username-space"),
+        UsernameProblem::TooShort => {
+            tr!(
+                "username-too-short",
+                min = USERNAME_LENGTH.start().to_string()
+            )
+        }
+        UsernameProblem::TooLong => {
+            tr!("username-too-long", max = USERNAME_LENGTH.end().to_string())
+        }
+        UsernameProblem::InvalidCharacter(character) => {
+            tr!(
+                "username-invalid-character",
+                character = character.to_string()
+            )
+        }
+    };
+
+    let feedback = column![widget::text(reason).size(text_size(14))].spacing(5);
+    if problem.is_fatal() {
+        return Some(feedback.into());
+    }
+    Some(
+        feedback
+            .push(widget::text(tr!("username-invalid-why")).size(text_size(14)))
+            .push(
+                widget::checkbox(tr!("username-allow-anyway"), allow_invalid)
+                    .on_toggle(Message::LauncherSettingsAllowInvalidUsernameToggle),
+            )
+            .into(),
+    )
+}
+
+/// The face of a skin, once it's loaded.
+fn skin_face<'element>(
+    skins: &HashMap<SkinSource, Option<widget::image::Handle>>,
+    source: Option<&SkinSource>,
+    size: u16,
+) -> Option<Element<'element>> {
+    let handle = skins.get(source?)?.clone()?;
+    Some(
+        widget::image(handle)
+            .width(size)
+            .height(size)
+            .filter_method(widget::image::FilterMethod::Nearest)
+            .into(),
+    )
+}
+
+pub fn search_palette(search: &SearchPalette) -> Element<'_> {
+    let results = search.results();
+    let results: Element = if results.is_empty() && !search.query.trim().is_empty() {
+        widget::text(tr!("search-no-results")).into()
+    } else {
+        Column::with_children(results.into_iter().enumerate().map(|(i, entry)| {
+            let kind = match entry.target {
+                SearchTarget::Instance => tr!("search-instance"),
+                SearchTarget::Mod => tr!("search-mod"),
+                SearchTarget::World => tr!("search-world"),
+            };
+            // Enter opens the highlighted result.
+            let marker = if i == search.selected { ">" } else { "" };
+            let label = row![
+                widget::text(marker).width(10),
+                widget::text(kind).size(text_size(14)).width(80),
+                widget::text(&entry.title).width(Length::Fill),
+                widget::text(&entry.detail).size(text_size(14)),
+            ]
+            .spacing(10);
+            widget::button(label)
+                .width(Length::Fill)
+                .on_press(Message::SearchOpenResult(Some(i)))
+                .into()
+        }))
+        .spacing(5)
+        .into()
+    };
+
+    widget::container(
+        column![
+            widget::text_input(&tr!("search-placeholder"), &search.query)
+                .id(SearchPalette::input_id())
+                .on_input(Message::SearchInput)
+                .on_submit(Message::SearchOpenResult(None)),
+            results,
+        ]
+        .padding(10)
+        .spacing(10),
+    )
+    .width(Length::Fill)
+    .into()
+}
+
+pub fn toasts(toasts: &[Toast]) -> Element<'_> {
+    Column::with_children(toasts.iter().map(|toast| {
+        let title = match toast.kind {
+            ToastKind::Info => tr!("toast-info"),
+            ToastKind::Success => tr!("toast-success"),
+            ToastKind::Error => tr!("toast-error"),
+        };
+
+        widget::container(
+            row![
+                column![
+                    widget::text(title).size(text_size(14)),
+                    widget::text(&toast.message)
+                ]
+                .spacing(5)
+                .width(Length::Fill),
+                widget::button(widget::text(tr!("toast-dismiss")))
+                    .on_press(Message::ToastDismiss(toast.id)),
+            ]
+            .spacing(10)
+            .padding(10),
+        )
+        .width(Length::Fill)
+        .into()
+    }))
+    .padding(10)
+    .spacing(5)
+    .into()
+}
+
+/// The panel listing the games started by the launcher.
+pub fn game_processes_sidebar(processes: &[GameProcess]) -> Element<'_> {
+    let processes = Column::with_children(processes.iter().map(|process| {
+        let status = if let Some(exit_status) = process.exit_status {
+            widget::text(tr!("game-exited", status = exit_status)).size(text_size(14))
+        } else {
+            widget::text(tr!(
+                "game-running",
+                uptime = format_duration(process.started.elapsed()),
+                memory = process
+                    .memory
+                    .map(|bytes| format_memory((bytes / (1024 * 1024)) as usize))
+                    .unwrap_or_default()
+            ))
+            .size(text_size(14))
+        };
+
+        widget::button(column![widget::text(&process.instance_name), status].spacing(5))
+            .width(Length::Fill)
+            .on_press(Message::GameLogOpen(process.pid))
+            .into()
+    }))
+    .spacing(10);
+
+    widget::container(
+        column![widget::text(tr!("game-sidebar-title")).size(20), processes]
+            .padding(10)
+            .spacing(20),
+    )
+    .width(200)
+    .into()
+}
+
+impl MenuServers {
     pub fn view<'element>(
         &'element self,
+        processes: &'element [GameProcess],
         config: Option<&'element LauncherConfig>,
-        instances: Option<&'element [String]>,
     ) -> Element<'element> {
-        let pick_list = if let Some(instances) = instances {
-            column![
-                widget::text("Instances:"),
-                widget::pick_list(
-                    instances,
-                    self.selected_instance.as_ref(),
-                    Message::LaunchInstanceSelected,
-                )
-                .width(200),
-                button_with_icon(icon_manager::create(), "New Instance")
-                    .on_press(Message::CreateInstanceScreenOpen),
-                button_with_icon(icon_manager::delete(), "Delete Instance").on_press_maybe(
-                    (self.selected_instance.is_some()).then_some(Message::DeleteInstanceMenu)
-                ),
-                button_with_icon(icon_manager::settings(), "Edit Instance").on_press_maybe(
-                    (self.selected_instance.is_some()).then_some(Message::EditInstance)
-                ),
-                button_with_icon(icon_manager::download(), "Manage Mods").on_press_maybe(
-                    (self.selected_instance.is_some()).then_some(Message::ManageModsScreenOpen)
-                ),
-                button_with_icon(icon_manager::folder(), "Open Files").on_press_maybe(
-                    (self.selected_instance.is_some()).then(|| {
-                        let launcher_dir = file_utils::get_launcher_dir().unwrap();
-                        Message::OpenDir(
-                            launcher_dir
-                                .join("instances")
-                                .join(self.selected_instance.as_ref().unwrap())
-                                .join(".minecraft"),
-                        )
-                    })
+        let running = self.selected_server.as_ref().and_then(|server| {
+            processes
+                .iter()
+                .find(|process| process.is_running_server(server))
+        });
+
+        let start_stop = if let Some(process) = running {
+            button_with_icon(icon_manager::delete(), tr!("servers-stop")).on_press_maybe(
+                process
+                    .stopping
+                    .is_none()
+                    .then_some(Message::ServerStop(process.pid)),
+            )
+        } else {
+            button_with_icon(icon_manager::play(), tr!("servers-start")).on_press_maybe(
+                (self.selected_server.is_some() && self.java_install_progress.is_none())
+                    .then_some(Message::ServerStart),
+            )
+        };
+
+        let status: Element = if let Some(progress) = &self.java_install_progress {
+            java_install_progress(progress).into()
+        } else if let Some(process) = running {
+            let status = if process.stopping.is_some() {
+                tr!("console-stopping")
+            } else {
+                tr!(
+                    "servers-running",
+                    uptime = format_duration(process.started.elapsed())
                 )
+            };
+            column![
+                widget::text(status),
+                widget::button(widget::text(tr!("servers-open-console")))
+                    .on_press(Message::GameLogOpen(process.pid)),
             ]
+            .spacing(5)
+            .into()
+        } else if self.selected_server.is_some() {
+            widget::text(tr!("servers-stopped")).into()
         } else {
-            column![widget::text("Loading instances...")]
+            column![].into()
         };
 
-        let java_progress_bar = if let Some(progress) = &self.java_install_progress {
-            widget::column!(
-                widget::progress_bar(0.0..=1.0, progress.num),
-                widget::text(&progress.message)
-            )
+        let server_picker: Element = if self.servers.is_empty() {
+            widget::text(tr!("servers-empty")).into()
         } else {
-            widget::column!(widget::text("A Minecraft Launcher\nby Mrmayman"))
+            widget::pick_list(
+                self.servers
+                    .iter()
+                    .map(|server| server.name.clone())
+                    .collect::<Vec<_>>(),
+                self.selected_server.clone(),
+                Message::ServerSelected,
+            )
+            .width(200)
+            .into()
         };
 
         column![
-            column![
-                widget::text("Username:"),
-                widget::text_input("Enter username...", &config.as_ref().unwrap().username)
-                    .on_input(Message::LaunchUsernameSet)
-                    .width(200),
-            ]
-            .spacing(5),
-            pick_list.spacing(5),
-            button_with_icon(icon_manager::play(), "Launch Game")
-                .on_press_maybe((self.selected_instance.is_some()).then_some(Message::LaunchStart)),
-            java_progress_bar
+            button_with_icon(icon_manager::back(), tr!("back")).on_press(Message::LaunchScreenOpen),
+            widget::text(tr!("servers-title")).size(20),
+            server_picker,
+            button_with_icon(icon_manager::create(), tr!("servers-new"))
+                .on_press(Message::ServerCreateScreenOpen),
+            button_with_icon(icon_manager::delete(), tr!("servers-delete")).on_press_maybe(
+                self.selected_server
+                    .as_ref()
+                    .filter(|_| running.is_none())
+                    .map(|server| {
+                        Message::ConfirmOpen(DestructiveAction::DeleteServer {
+                            server: server.clone(),
+                        })
+                    })
+            ),
+            button_with_icon(icon_manager::folder(), tr!("launch-open-files")).on_press_maybe(
+                self.selected_server
+                    .as_ref()
+                    .map(|server| Message::OpenDir(self.servers_dir.join(server)))
+            ),
+            button_with_icon(icon_manager::download(), tr!("servers-plugins")).on_press_maybe(
+                self.selected_server
+                    .is_some()
+                    .then_some(Message::ServerPluginsOpen)
+            ),
+            button_with_icon(icon_manager::settings(), tr!("servers-properties")).on_press_maybe(
+                self.selected_server
+                    .is_some()
+                    .then_some(Message::ServerPropertiesOpen)
+            ),
+            button_with_icon(icon_manager::settings(), tr!("servers-schedule")).on_press_maybe(
+                self.selected_server
+                    .is_some()
+                    .then_some(Message::ServerScheduleOpen)
+            ),
+            start_stop,
+            status,
+            self.view_external_servers(config),
         ]
         .padding(10)
-        .spacing(20)
+        .spacing(10)
         .into()
     }
-}
 
-impl MenuEditInstance {
-    pub fn view<'element>(&self) -> Element<'element> {
-        // 2 ^ 8 = 256 MB
-        const MEM_256_MB_IN_TWOS_EXPONENT: f32 = 8.0;
-        // 2 ^ 13 = 8192 MB
-        const MEM_8192_MB_IN_TWOS_EXPONENT: f32 = 13.0;
+    fn view_external_servers<'element>(
+        &'element self,
+        config: Option<&'element LauncherConfig>,
+    ) -> Element<'element> {
+        let addresses = config.map_or(&[][..], |config| &config.external_servers);
+        let list = Column::with_children(addresses.iter().map(|address| {
+            row![
+                widget::button(icon_manager::delete())
+                    .on_press(Message::ServersExternalRemove(address.clone())),
+                widget::text(address),
+            ]
+            .spacing(10)
+            .into()
+        }))
+        .spacing(5);
 
-        widget::scrollable(
-            column![
-                widget::button(row![icon_manager::back(), widget::text("Back")]
-                    .spacing(10)
-                    .padding(5)
-                ).on_press(Message::LaunchScreenOpen),
-                widget::text(format!("Editing {} instance: {}", self.config.mod_type, self.selected_instance)),
-                widget::container(
-                    column![
-                        widget::text("Use a special Java install instead of the default one. (Enter path, leave blank if none)"),
-                        widget::text_input(
-                            "Enter Java override",
-                            self.config
-                                .java_override
-                                .as_deref()
-                                .unwrap_or_default()
-                        )
-                        .on_input(Message::EditInstanceJavaOverride)
-                    ]
-                    .padding(10)
-                    .spacing(10)
-                ),
-                widget::container(
-                    column![
-                        widget::text("Allocated memory"),
-                        widget::text("For normal Minecraft, allocate 2 - 3 GB"),
-                        widget::text("For old versions, allocate 512 MB - 1 GB"),
-                        widget::text("For heavy modpacks or very high render distances, allocate 4 - 8 GB"),
-                        widget::slider(MEM_256_MB_IN_TWOS_EXPONENT..=MEM_8192_MB_IN_TWOS_EXPONENT, self.slider_value, Message::EditInstanceMemoryChanged).step(0.1),
-                        widget::text(&self.slider_text),
-                    ]
-                    .padding(10)
-                    .spacing(5),
-                ),
+        let can_add = !self.external_server_input.trim().is_empty();
+        column![
+            widget::text(tr!("servers-external-title")).size(20),
+            widget::text(tr!("servers-external-hint")).size(text_size(14)),
+            list,
+            row![
+                widget::text_input(
+                    &tr!("servers-external-placeholder"),
+                    &self.external_server_input
+                )
+                .on_input(Message::ServersExternalInput)
+                .on_submit(Message::ServersExternalAdd)
+                .width(250),
+                widget::button(widget::text(tr!("servers-external-add")))
+                    .on_press_maybe(can_add.then_some(Message::ServersExternalAdd)),
             ]
-            .padding(10)
-            .spacing(20)
-        ).into()
+            .spacing(10),
+        ]
+        .spacing(10)
+        .into()
     }
 }
 
-impl MenuEditMods {
-    pub fn view(&self) -> Element {
-        let mod_installer = if self.config.mod_type == "Vanilla" {
-            widget::column![
-                widget::button("Install Fabric").on_press(Message::InstallFabricScreenOpen),
-                widget::button("Install Quilt"),
-                widget::button("Install Forge"),
-                widget::button("Install OptiFine")
-            ]
-            .spacing(5)
+impl MenuServerConsole {
+    pub fn view<'element>(&self, processes: &'element [GameProcess]) -> Element<'element> {
+        let back_button = button_with_icon(icon_manager::back(), tr!("back"))
+            .on_press(Message::ServersScreenOpen);
+
+        let Some(process) = processes.iter().find(|n| n.pid == self.pid) else {
+            return column![back_button, widget::text(tr!("game-log-missing"))]
+                .padding(10)
+                .spacing(10)
+                .into();
+        };
+
+        let (log, has_older_lines) = {
+            let log = process.log.lock().unwrap();
+            let start = log.len().saturating_sub(self.shown_lines);
+            (log[start..].join("\n"), start > 0)
+        };
+
+        let is_running = process.exit_status.is_none();
+        let action = if !is_running {
+            widget::button(widget::text(tr!("game-close"))).on_press(Message::GameClose(self.pid))
+        } else if process.stopping.is_some() {
+            // If saving hangs, the server can still be killed.
+            widget::button(widget::text(tr!("console-kill"))).on_press(Message::GameKill(self.pid))
         } else {
-            widget::column![
-                widget::button(
-                    widget::row![
-                        icon_manager::delete(),
-                        widget::text(format!("Uninstall {}", self.config.mod_type))
-                    ]
-                    .spacing(10)
-                    .padding(5)
-                ) // TODO: Add uninstall option for fabric.
-            ]
+            widget::button(widget::text(tr!("servers-stop")))
+                .on_press(Message::ServerStop(self.pid))
         };
 
-        widget::column![
-            widget::button(
-                widget::row![icon_manager::back(), widget::text("Back")]
+        let status = if let Some(exit_status) = process.exit_status {
+            tr!("game-exited", status = exit_status)
+        } else if process.stopping.is_some() {
+            tr!("console-stopping")
+        } else {
+            tr!(
+                "servers-running",
+                uptime = format_duration(process.started.elapsed())
+            )
+        };
+
+        let mut command_input =
+            widget::text_input(&tr!("console-input-placeholder"), &self.command_input)
+                .font(iced::Font::MONOSPACE);
+        if is_running {
+            command_input = command_input
+                .on_input(Message::ServerConsoleInput)
+                .on_submit(Message::ServerConsoleSubmit);
+        }
+
+        column![
+            row![back_button, action].spacing(10),
+            widget::text(tr!("console-title", server = process.instance_name)).size(20),
+            widget::text(status).size(text_size(14)),
+            widget::scrollable(
+                column![]
+                    .push_maybe(has_older_lines.then(|| {
+                        widget::button(widget::text(tr!("console-show-more")).size(text_size(14)))
+                            .on_press(Message::ServerConsoleShowMore)
+                    }))
+                    .push(
+                        widget::text(log)
+                            .font(iced::Font::MONOSPACE)
+                            .size(text_size(12))
+                            .width(Length::Fill)
+                    )
                     .spacing(10)
-                    .padding(5)
             )
-            .on_press(Message::LaunchScreenOpen),
-            mod_installer,
-            widget::button("Go to mods folder"),
-            widget::text("Mod management and store coming soon...")
+            .direction(widget::scrollable::Direction::Vertical(
+                widget::scrollable::Properties::new().alignment(widget::scrollable::Alignment::End)
+            ))
+            .height(Length::Fill),
+            row![
+                command_input,
+                widget::button(widget::text(tr!("console-send")))
+                    .on_press_maybe(is_running.then_some(Message::ServerConsoleSubmit)),
+            ]
+            .spacing(10),
         ]
         .padding(10)
-        .spacing(20)
+        .spacing(10)
         .into()
     }
 }
 
-impl MenuCreateInstance {
-    pub fn view(&self) -> Element {
-        let progress_bar = if let Some(progress_number) = self.progress_number {
-            if let Some(progress_text) = &self.progress_text {
-                column![
-                    widget::progress_bar(RangeInclusive::new(0.0, 10.0), progress_number),
-                    widget::text(progress_text),
+impl MenuServerEula {
+    pub fn view(&self) -> Element<'_> {
+        column![
+            widget::text(tr!("eula-title", server = self.server)).size(20),
+            widget::text(tr!("eula-description")),
+            widget::button(widget::text(tr!("eula-read")))
+                .on_press(Message::OpenLink(server_files::EULA_URL.to_owned())),
+            widget::checkbox(tr!("eula-agree"), self.agreed)
+                .on_toggle(Message::ServerEulaAgreeToggle),
+            row![
+                widget::button(widget::text(tr!("eula-accept")))
+                    .on_press_maybe(self.agreed.then_some(Message::ServerEulaAccept)),
+                widget::button(widget::text(tr!("back"))).on_press(Message::ServersScreenOpen),
+            ]
+            .spacing(10),
+        ]
+        .padding(10)
+        .spacing(10)
+        .into()
+    }
+}
+
+impl MenuServerPlugins {
+    pub fn view(&self) -> Element<'_> {
+        let back_button = button_with_icon(icon_manager::back(), tr!("back"))
+            .on_press(Message::ServersScreenOpen);
+        let title = widget::text(tr!("plugins-title", server = self.server)).size(20);
+
+        if !self.software.supports_plugins() {
+            return column![back_button, title, widget::text(tr!("plugins-vanilla"))]
+                .padding(10)
+                .spacing(10)
+                .into();
+        }
+
+        let installer: Element = if let Some(progress) = &self.progress {
+            widget::text(&progress.message).into()
+        } else {
+            let can_install = !self.plugin_input.trim().is_empty();
+            column![
+                row![
+                    widget::pick_list(
+                        PluginSource::ALL,
+                        Some(self.source),
+                        Message::ServerPluginsSourceSelected
+                    ),
+                    widget::text_input(&tr!("plugins-input-placeholder"), &self.plugin_input)
+                        .on_input(Message::ServerPluginsInput)
+                        .on_submit(Message::ServerPluginsInstall),
+                    button_with_icon(icon_manager::download(), tr!("plugins-install"))
+                        .on_press_maybe(can_install.then_some(Message::ServerPluginsInstall)),
                 ]
-            } else {
-                column![]
-            }
+                .spacing(10),
+                widget::text(tr!("plugins-input-hint")).size(text_size(14)),
+            ]
+            .spacing(5)
+            .into()
+        };
+
+        let plugins: Element = if self.plugins.is_empty() {
+            widget::text(tr!("plugins-empty")).into()
         } else {
-            column![]
+            Column::with_children(self.plugins.iter().map(|file_name| {
+                row![
+                    widget::button(icon_manager::delete()).on_press_maybe(
+                        self.progress.is_none().then(|| {
+                            Message::ConfirmOpen(DestructiveAction::DeletePlugin {
+                                server: self.server.clone(),
+                                file_name: file_name.clone(),
+                            })
+                        })
+                    ),
+                    widget::text(file_name),
+                ]
+                .spacing(10)
+                .into()
+            }))
+            .spacing(5)
+            .into()
         };
 
         widget::scrollable(
             column![
-                widget::button(
-                    row![icon_manager::back(), widget::text("Back")]
-                        .spacing(10)
-                        .padding(5)
-                ).on_press(Message::LaunchScreenOpen),
+                back_button,
+                title,
+                widget::text(tr!("plugins-restart-hint")).size(text_size(14)),
+                installer,
+                widget::text(tr!("plugins-installed")),
+                plugins,
+                button_with_icon(icon_manager::folder(), tr!("plugins-open-folder"))
+                    .on_press(Message::OpenDir(self.plugins_dir.clone())),
+            ]
+            .padding(10)
+            .spacing(10),
+        )
+        .into()
+    }
+}
+
+fn check_name(check: Check) -> String {
+    match check {
+        Check::Config => tr!("verify-config"),
+        Check::VersionJson => tr!("verify-version-json"),
+        Check::ClientJar => tr!("verify-client-jar"),
+        Check::Libraries => tr!("verify-libraries"),
+        Check::LoaderJson => tr!("verify-loader"),
+        Check::AssetIndex => tr!("verify-asset-index"),
+    }
+}
+
+impl MenuVerify {
+    pub fn view(&self) -> Element<'_> {
+        let results: Element = match &self.results {
+            None => widget::text(tr!("verify-checking")).into(),
+            Some(Err(err)) => widget::text(tr!("verify-failed", error = err)).into(),
+            Some(Ok(results)) => Column::with_children(results.iter().map(|result| {
+                let (mark, detail) = match &result.status {
+                    CheckStatus::Passed => ("✓", None),
+                    CheckStatus::Failed(reason) => ("✗", Some(reason.as_str())),
+                    CheckStatus::Skipped => ("–", None),
+                };
+                let fix = matches!(result.status, CheckStatus::Failed(_)).then(|| {
+                    widget::button(widget::text(if self.fixing == Some(result.check) {
+                        tr!("verify-fixing")
+                    } else {
+                        tr!("verify-fix")
+                    }))
+                    .on_press_maybe(
+                        self.fixing
+                            .is_none()
+                            .then_some(Message::VerifyFix(result.check)),
+                    )
+                });
                 column![
-                    widget::text("To install Fabric/Forge/OptiFine/Quilt, click on Manage Mods after installing the instance"),
-                    widget::text("Select Version"),
-                    widget::pick_list(
-                        self.versions.as_slice(),
-                        self.selected_version.as_ref(),
-                        Message::CreateInstanceVersionSelected
-                    ),
+                    row![widget::text(mark), widget::text(check_name(result.check))]
+                        .push_maybe(fix)
+                        .spacing(10)
+                        .align_items(iced::Alignment::Center)
                 ]
-                .spacing(10),
-                widget::text_input("Enter instance name...", &self.instance_name)
-                    .on_input(Message::CreateInstanceNameInput),
-                widget::text("Download assets? If disabled, creating instance will be MUCH faster, but no sound or music will play in-game"),
-                widget::checkbox("Download assets?", self.download_assets).on_toggle(Message::CreateInstanceChangeAssetToggle),
-                widget::button(row![icon_manager::create(), widget::text("Create Instance")]
+                .push_maybe(detail.map(|detail| widget::text(detail).size(text_size(12))))
+                .spacing(5)
+                .into()
+            }))
+            .spacing(10)
+            .into(),
+        };
+
+        widget::scrollable(
+            column![
+                widget::button(
+                    row![icon_manager::back(), widget::text(tr!("back"))]
                         .spacing(10)
                         .padding(5)
-                ).on_press_maybe((self.selected_version.is_some() && !self.instance_name.is_empty()).then(|| Message::CreateInstanceStart)),
-                progress_bar,
+                )
+                .on_press(Message::LaunchScreenOpen),
+                widget::text(tr!("verify-title", instance = self.selected_instance)),
+                widget::text(tr!("verify-description")),
+                results,
             ]
-            .spacing(10)
-            .padding(10),
+            .padding(10)
+            .spacing(20),
         )
         .into()
     }
 }
 
-impl MenuDeleteInstance {
-    pub fn view(&self) -> Element {
-        column![
-            widget::text(format!(
-                "Are you SURE you want to DELETE the Instance: {}?",
-                &self.selected_instance
-            )),
-            widget::text("All your data, including worlds will be lost."),
-            widget::button("Yes, delete my data").on_press(Message::DeleteInstance),
-            widget::button("No").on_press(Message::LaunchScreenOpen),
-        ]
-        .padding(10)
-        .spacing(10)
-        .into()
-    }
-}
+impl MenuWorlds {
+    pub fn view<'a>(&'a self, instances: Option<&'a [InstanceInfo]>) -> Element<'a> {
+        let retention_error = MenuServerSchedule::parse_retention(&self.retention_input)
+            .is_none()
+            .then(|| widget::text(tr!("server-schedule-retention-invalid")).size(text_size(14)));
+        let copy_targets: Vec<String> = instances
+            .unwrap_or_default()
+            .iter()
+            .map(|instance| instance.name.clone())
+            .filter(|name| *name != self.selected_instance)
+            .collect();
 
-impl MenuInstallFabric {
-    pub fn view(&self) -> Element {
-        column![
-            widget::button(
-                row![icon_manager::back(), widget::text("Back")]
-                    .spacing(10)
-                    .padding(5)
+        let worlds = Column::with_children(self.worlds.iter().map(|world| {
+            let backups = Column::with_children(world.backups.iter().map(|backup| {
+                row![
+                    widget::text(backup.file_stem().unwrap_or_default().to_string_lossy())
+                        .width(250),
+                    widget::button(widget::text(tr!("worlds-restore"))).on_press_maybe(
+                        (!self.busy).then(|| {
+                            Message::ConfirmOpen(DestructiveAction::RestoreWorld {
+                                instance: self.selected_instance.clone(),
+                                backup: backup.clone(),
+                            })
+                        })
+                    ),
+                ]
+                .spacing(10)
+                .align_items(iced::Alignment::Center)
+                .into()
+            }))
+            .spacing(5);
+
+            let world_backups_dir = self.backups_dir.join(&world.world);
+            let name = world.info.as_ref().map_or(&world.world, |info| &info.name);
+            let title: Element = match &self.rename {
+                Some((renamed, input)) if *renamed == world.world => row![
+                    widget::text_input(&world.world, input)
+                        .on_input(Message::WorldsRenameInput)
+                        .on_submit(Message::WorldsRenameSave)
+                        .width(250),
+                    widget::button(widget::text(tr!("worlds-rename-save"))).on_press_maybe(
+                        (!input.trim().is_empty()).then_some(Message::WorldsRenameSave)
+                    ),
+                    widget::button(widget::text(tr!("worlds-rename-cancel")))
+                        .on_press(Message::WorldsRenameCancel),
+                ]
+                .spacing(10)
+                .into(),
+                _ => widget::text(name).size(18).into(),
+            };
+            let can_edit = world.exists && !self.busy;
+
+            column![
+                title,
+                widget::text(world_details(world)).size(text_size(14)),
+                row![
+                    widget::button(widget::text(tr!("worlds-rename"))).on_press_maybe(
+                        (can_edit && world.info.is_some())
+                            .then(|| Message::WorldsRenameStart(world.world.clone()))
+                    ),
+                    widget::button(widget::text(tr!("worlds-duplicate"))).on_press_maybe(
+                        can_edit.then(|| Message::WorldsDuplicate(world.world.clone()))
+                    ),
+                    widget::button(widget::text(tr!("worlds-copy"))).on_press_maybe(
+                        (can_edit && self.copy_target.is_some())
+                            .then(|| Message::WorldsCopy(world.world.clone()))
+                    ),
+                    button_with_icon(icon_manager::delete(), tr!("worlds-delete")).on_press_maybe(
+                        can_edit.then(|| {
+                            Message::ConfirmOpen(DestructiveAction::DeleteWorld {
+                                instance: self.selected_instance.clone(),
+                                world: world.world.clone(),
+                            })
+                        })
+                    ),
+                ]
+                .spacing(10),
+                row![
+                    widget::button(widget::text(tr!("worlds-backup"))).on_press_maybe(
+                        can_edit.then(|| Message::WorldsBackup(world.world.clone()))
+                    ),
+                    button_with_icon(icon_manager::folder(), tr!("worlds-open-backups"))
+                        .on_press_maybe(
+                            world_backups_dir
+                                .is_dir()
+                                .then(|| Message::OpenDir(world_backups_dir.clone()))
+                        ),
+                ]
+                .spacing(10),
+            ]
+            .push_maybe(
+                world
+                    .backups
+                    .is_empty()
+                    .then(|| widget::text(tr!("worlds-no-backups")).size(text_size(14))),
             )
-            .on_press(Message::LaunchScreenOpen),
-            widget::text(format!(
-                "Select Fabric Version for instance {}",
-                &self.selected_instance
+            .push(backups)
+            .spacing(10)
+            .into()
+        }))
+        .spacing(20);
+
+        widget::scrollable(
+            column![
+                button_with_icon(icon_manager::back(), tr!("back"))
+                    .on_press(Message::LaunchScreenOpen),
+                widget::text(tr!("worlds-title", instance = self.selected_instance)).size(20),
+                widget::text(tr!("server-schedule-backup-retention")),
+                widget::text_input("5", &self.retention_input)
+                    .on_input(Message::WorldsRetentionInput)
+                    .width(200),
+            ]
+            .push_maybe(retention_error)
+            .push(
+                widget::checkbox(
+                    tr!("worlds-backup-on-version-change"),
+                    self.config.backup_worlds_on_version_change,
+                )
+                .on_toggle(Message::WorldsBackupOnVersionChangeToggle),
+            )
+            .push(
+                widget::button(widget::text(tr!("server-schedule-save"))).on_press_maybe(
+                    MenuServerSchedule::parse_retention(&self.retention_input)
+                        .is_some()
+                        .then_some(Message::WorldsSave),
+                ),
+            )
+            .push(
+                row![
+                    widget::text(tr!("worlds-copy-target")),
+                    widget::pick_list(
+                        copy_targets,
+                        self.copy_target.clone(),
+                        Message::WorldsCopyTargetSelected
+                    ),
+                ]
+                .spacing(10)
+                .align_items(iced::Alignment::Center),
+            )
+            .push(if self.worlds.is_empty() {
+                Element::from(widget::text(tr!("worlds-empty")))
+            } else {
+                worlds.into()
+            })
+            .padding(10)
+            .spacing(10),
+        )
+        .into()
+    }
+}
+
+/// Like `1.20.4 · Survival · Played 3 days ago`.
+fn world_details(world: &WorldBackups) -> String {
+    let Some(info) = &world.info else {
+        return if world.exists {
+            tr!("worlds-unreadable")
+        } else {
+            tr!("worlds-deleted")
+        };
+    };
+    let mut details = Vec::new();
+    details.extend(info.version.clone());
+    details.extend(info.game_mode.map(|mode| GameModeChoice(mode).to_string()));
+    if info.hardcore {
+        details.push(tr!("worlds-hardcore"));
+    }
+    details.push(format_last_played(info.last_played));
+    if info.name != world.world {
+        details.push(tr!("worlds-folder", folder = world.world.as_str()));
+    }
+    details.join(" · ")
+}
+
+impl MenuUpdate {
+    pub fn view(&self) -> Element<'_> {
+        let release = &self.release;
+        let status: Element = match &self.outcome {
+            Some(UpdateOutcome::Replaced) => widget::text(tr!("update-restart")).into(),
+            Some(UpdateOutcome::SavedInstaller(path)) => widget::text(tr!(
+                "update-installer-saved",
+                path = path.to_string_lossy().to_string()
+            ))
+            .into(),
+            None if self.installing => widget::text(tr!("update-installing")).into(),
+            None => row![]
+                .push_maybe(release.asset.as_ref().map(|asset| {
+                    widget::button(widget::text(tr!(
+                        "update-install",
+                        file = asset.name.clone()
+                    )))
+                    .on_press(Message::UpdateInstall)
+                }))
+                .push(
+                    widget::button(widget::text(tr!("update-open-page")))
+                        .on_press(Message::OpenLink(release.page_url.clone())),
+                )
+                .spacing(10)
+                .into(),
+        };
+
+        column![
+            button_with_icon(icon_manager::back(), tr!("back"))
+                .on_press_maybe((!self.installing).then_some(Message::LaunchScreenOpen)),
+            widget::text(tr!(
+                "update-title",
+                version = release.version.clone(),
+                current = env!("CARGO_PKG_VERSION")
+            ))
+            .size(20),
+        ]
+        .push_maybe(
+            release
+                .asset
+                .is_none()
+                .then(|| widget::text(tr!("update-no-download"))),
+        )
+        .push(status)
+        .push(widget::text(tr!("update-changelog")).size(16))
+        .push(
+            widget::scrollable(
+                widget::text(&release.changelog)
+                    .size(text_size(14))
+                    .width(Length::Fill),
+            )
+            .height(Length::Fill),
+        )
+        .padding(10)
+        .spacing(10)
+        .into()
+    }
+}
+
+impl MenuDownloadQueue {
+    pub fn view(&self) -> Element<'_> {
+        let jobs: Element = if self.queue.jobs.is_empty() {
+            widget::text(tr!("download-queue-empty")).into()
+        } else {
+            let last = self.queue.jobs.len() - 1;
+            Column::with_children(
+                self.queue
+                    .jobs
+                    .iter()
+                    .enumerate()
+                    .map(|(i, job)| download_job(job, i == 0, i == last)),
+            )
+            .spacing(10)
+            .into()
+        };
+
+        column![
+            button_with_icon(icon_manager::back(), tr!("back")).on_press(Message::LaunchScreenOpen),
+            widget::text(tr!("download-queue-title")).size(20),
+            widget::text(tr!(
+                "download-queue-speed",
+                speed = format_bytes(self.speed)
+            )),
+            widget::scrollable(jobs).height(Length::Fill),
+        ]
+        .padding(10)
+        .spacing(10)
+        .into()
+    }
+}
+
+fn download_job(job: &Job, is_first: bool, is_last: bool) -> Element<'_> {
+    let kind = match job.kind {
+        JobKind::Instance => tr!("download-queue-kind-instance"),
+        JobKind::Mods => tr!("download-queue-kind-mods"),
+        JobKind::Java => tr!("download-queue-kind-java"),
+    };
+    let status = match job.status {
+        JobStatus::Active => tr!("download-queue-active"),
+        JobStatus::Queued => tr!("download-queue-queued"),
+        JobStatus::Paused => tr!("download-queue-paused"),
+    };
+    let pause = if job.status == JobStatus::Paused {
+        widget::button(widget::text(tr!("download-queue-resume")))
+            .on_press(Message::DownloadQueueResume(job.id))
+    } else {
+        widget::button(widget::text(tr!("download-queue-pause")))
+            .on_press(Message::DownloadQueuePause(job.id))
+    };
+
+    row![
+        column![
+            widget::text(format!("{kind}: {}", job.name)),
+            widget::text(tr!(
+                "download-queue-progress",
+                status = status,
+                size = format_bytes(job.bytes),
+                files = job.files
+            ))
+            .size(text_size(14)),
+        ]
+        .width(Length::Fill),
+        widget::button(widget::text("↑"))
+            .on_press_maybe((!is_first).then_some(Message::DownloadQueueMoveUp(job.id))),
+        widget::button(widget::text("↓"))
+            .on_press_maybe((!is_last).then_some(Message::DownloadQueueMoveDown(job.id))),
+        pause,
+    ]
+    .spacing(10)
+    .into()
+}
+
+impl MenuStats {
+    /// How many weeks the sessions chart goes back.
+    const CHART_WEEKS: usize = 8;
+    const TOP_VERSIONS: usize = 5;
+
+    pub fn view(&self) -> Element<'_> {
+        let back =
+            button_with_icon(icon_manager::back(), tr!("back")).on_press(Message::LaunchScreenOpen);
+        if self.stats.sessions.is_empty() {
+            return column![
+                back,
+                widget::text(tr!("stats-title")).size(20),
+                widget::text(tr!("stats-empty")),
+            ]
+            .padding(10)
+            .spacing(10)
+            .into();
+        }
+
+        let time_rows = |totals: Vec<(String, u64)>| {
+            Column::with_children(totals.into_iter().map(|(name, seconds)| {
+                row![
+                    widget::text(name).width(200),
+                    widget::text(format_playtime(seconds)),
+                ]
+                .into()
+            }))
+            .spacing(5)
+        };
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |n| n.as_secs());
+        let weeks = self.stats.sessions_per_week(now, Self::CHART_WEEKS);
+        let most_sessions = weeks.iter().copied().max().unwrap_or_default().max(1);
+        let chart = Column::with_children(weeks.iter().enumerate().map(|(i, &count)| {
+            let weeks_ago = Self::CHART_WEEKS - 1 - i;
+            let label = if weeks_ago == 0 {
+                tr!("stats-this-week")
+            } else {
+                tr!("stats-weeks-ago", count = weeks_ago)
+            };
+            row![
+                widget::text(label).width(150),
+                widget::progress_bar(0.0..=most_sessions as f32, count as f32)
+                    .width(200)
+                    .height(12),
+                widget::text(count),
+            ]
+            .spacing(10)
+            .align_items(iced::Alignment::Center)
+            .into()
+        }))
+        .spacing(5);
+
+        let mut versions = self.stats.per_version();
+        versions.truncate(Self::TOP_VERSIONS);
+
+        widget::scrollable(
+            column![
+                back,
+                widget::text(tr!("stats-title")).size(20),
+                widget::text(tr!(
+                    "stats-total",
+                    time = format_playtime(self.stats.total_seconds()),
+                    sessions = self.stats.sessions.len()
+                )),
+                widget::text(tr!("stats-per-instance")).size(18),
+                time_rows(self.stats.per_instance()),
+                widget::text(tr!("stats-per-week")).size(18),
+                chart,
+                widget::text(tr!("stats-versions")).size(18),
+                time_rows(versions),
+            ]
+            .padding(10)
+            .spacing(10),
+        )
+        .into()
+    }
+}
+
+impl MenuServerSchedule {
+    pub fn view(&self) -> Element<'_> {
+        let interval_error = |input: &str| {
+            MenuServerSchedule::parse_interval(input)
+                .err()
+                .map(|()| widget::text(tr!("server-schedule-interval-invalid")).size(text_size(14)))
+        };
+        let retention_error = MenuServerSchedule::parse_retention(&self.backup_retention_input)
+            .is_none()
+            .then(|| widget::text(tr!("server-schedule-retention-invalid")).size(text_size(14)));
+
+        widget::scrollable(
+            column![
+                button_with_icon(icon_manager::back(), tr!("back"))
+                    .on_press(Message::ServersScreenOpen),
+                widget::text(tr!("server-schedule-title", server = self.server)).size(20),
+                widget::text(tr!("server-schedule-hint")).size(text_size(14)),
+                widget::text(tr!("server-schedule-backup-interval")),
+                widget::text_input(&tr!("server-schedule-never"), &self.backup_interval_input)
+                    .on_input(Message::ServerScheduleBackupIntervalInput)
+                    .width(200),
+            ]
+            .push_maybe(interval_error(&self.backup_interval_input))
+            .push(widget::text(tr!("server-schedule-backup-retention")))
+            .push(
+                widget::text_input("5", &self.backup_retention_input)
+                    .on_input(Message::ServerScheduleBackupRetentionInput)
+                    .width(200),
+            )
+            .push_maybe(retention_error)
+            .push(widget::text(tr!("server-schedule-restart-interval")))
+            .push(
+                widget::text_input(&tr!("server-schedule-never"), &self.restart_interval_input)
+                    .on_input(Message::ServerScheduleRestartIntervalInput)
+                    .width(200),
+            )
+            .push_maybe(interval_error(&self.restart_interval_input))
+            .push(
+                widget::button(widget::text(tr!("server-schedule-save"))).on_press_maybe(
+                    self.schedule()
+                        .is_some()
+                        .then_some(Message::ServerScheduleSave),
+                ),
+            )
+            .push(
+                row![
+                    widget::button(widget::text(tr!("server-schedule-backup-now"))).on_press_maybe(
+                        (!self.backing_up).then_some(Message::ServerScheduleBackupNow)
+                    ),
+                    button_with_icon(icon_manager::folder(), tr!("server-schedule-open-backups"))
+                        .on_press_maybe(
+                            self.backups_dir
+                                .is_dir()
+                                .then(|| Message::OpenDir(self.backups_dir.clone()))
+                        ),
+                ]
+                .spacing(10),
+            )
+            .padding(10)
+            .spacing(10),
+        )
+        .into()
+    }
+}
+
+impl MenuServerProperties {
+    pub fn view(&self) -> Element<'_> {
+        let header = column![
+            row![
+                button_with_icon(icon_manager::back(), tr!("back"))
+                    .on_press(Message::ServersScreenOpen),
+                widget::button(widget::text(if self.raw.is_some() {
+                    tr!("server-properties-edit-settings")
+                } else {
+                    tr!("server-properties-edit-raw")
+                }))
+                .on_press(Message::ServerPropertiesRawToggle),
+            ]
+            .spacing(10),
+            widget::text(tr!("server-properties-title", server = self.server)).size(20),
+            widget::text(tr!("server-properties-restart-hint")).size(text_size(14)),
+        ]
+        .spacing(10);
+
+        let save_button = |enabled: bool| {
+            widget::button(widget::text(tr!("server-properties-save")))
+                .on_press_maybe(enabled.then_some(Message::ServerPropertiesSave))
+        };
+
+        if let Some(raw) = &self.raw {
+            return column![
+                header,
+                widget::text_editor(raw)
+                    .on_action(Message::ServerPropertiesRawEdit)
+                    .font(iced::Font::MONOSPACE)
+                    .height(Length::Fill),
+                save_button(true),
+            ]
+            .padding(10)
+            .spacing(10)
+            .into();
+        }
+
+        let port_error = server_files::parse_port(&self.port_input)
+            .err()
+            .map(|_| tr!("server-properties-port-invalid"));
+        let view_distance_error = server_files::parse_view_distance(&self.view_distance_input)
+            .err()
+            .map(|_| {
+                let range = ServerSettings::VIEW_DISTANCE_RANGE;
+                tr!(
+                    "server-properties-view-distance-invalid",
+                    min = range.start(),
+                    max = range.end()
+                )
+            });
+        let motd_error = server_files::validate_motd(&self.motd)
+            .err()
+            .map(|_| tr!("server-properties-motd-invalid"));
+        let is_valid =
+            port_error.is_none() && view_distance_error.is_none() && motd_error.is_none();
+
+        let error_text =
+            |error: Option<String>| error.map(|error| widget::text(error).size(text_size(14)));
+
+        widget::scrollable(
+            column![
+                header,
+                widget::text(tr!("server-properties-port")),
+                widget::text_input("25565", &self.port_input)
+                    .on_input(Message::ServerPropertiesPortInput)
+                    .width(200),
+            ]
+            .push_maybe(error_text(port_error))
+            .push(widget::text(tr!("server-properties-gamemode")))
+            .push(widget::pick_list(
+                GameModeChoice::ALL,
+                Some(GameModeChoice(self.gamemode)),
+                Message::ServerPropertiesGameModeSelected,
+            ))
+            .push(widget::text(tr!("server-properties-difficulty")))
+            .push(widget::pick_list(
+                DifficultyChoice::ALL,
+                Some(DifficultyChoice(self.difficulty)),
+                Message::ServerPropertiesDifficultySelected,
+            ))
+            .push(
+                widget::checkbox(tr!("server-properties-online-mode"), self.online_mode)
+                    .on_toggle(Message::ServerPropertiesOnlineModeToggle),
+            )
+            .push(widget::text(tr!("server-properties-online-mode-hint")).size(text_size(14)))
+            .push(widget::text(tr!("server-properties-motd")))
+            .push(
+                widget::text_input(&tr!("server-properties-motd-placeholder"), &self.motd)
+                    .on_input(Message::ServerPropertiesMotdInput),
+            )
+            .push_maybe(error_text(motd_error))
+            .push(widget::text(tr!("server-properties-view-distance")))
+            .push(
+                widget::text_input("10", &self.view_distance_input)
+                    .on_input(Message::ServerPropertiesViewDistanceInput)
+                    .width(200),
+            )
+            .push_maybe(error_text(view_distance_error))
+            .push(save_button(is_valid))
+            .padding(10)
+            .spacing(10),
+        )
+        .into()
+    }
+}
+
+impl MenuLaunch {
+    /// Who the game is launched as: a username, or an
+    /// offline profile if any are saved.
+    fn view_account<'element>(
+        &self,
+        config: &'element LauncherConfig,
+        instances: Option<&'element [InstanceInfo]>,
+        skins: &HashMap<SkinSource, Option<widget::image::Handle>>,
+    ) -> Element<'element> {
+        let instance =
+            instances
+                .zip(self.selected_instance.as_ref())
+                .and_then(|(instances, selected)| {
+                    instances.iter().find(|instance| instance.name == *selected)
+                });
+        let face = skin_face(skins, config.skin_source(instance).as_ref(), 48);
+        let account = self.view_account_picker(config, instance);
+        match face {
+            Some(face) => row![face, account]
+                .spacing(10)
+                .align_items(iced::Alignment::Center)
+                .into(),
+            None => account,
+        }
+    }
+
+    fn view_account_picker<'element>(
+        &self,
+        config: &'element LauncherConfig,
+        instance: Option<&InstanceInfo>,
+    ) -> Element<'element> {
+        let instance_profile = instance
+            .and_then(|instance| instance.offline_profile.as_ref())
+            .filter(|name| {
+                config
+                    .offline_profiles
+                    .iter()
+                    .any(|profile| profile.name == **name)
+            });
+        if let Some(name) = instance_profile {
+            return column![
+                widget::text(tr!("launch-account")),
+                widget::text(tr!("launch-instance-profile", name = name.clone()))
+                    .size(text_size(14)),
+            ]
+            .width(200)
+            .spacing(5)
+            .into();
+        }
+
+        let username_input =
+            column![
+                widget::text_input(&tr!("launch-username-placeholder"), &config.username)
+                    .on_input(Message::LaunchUsernameSet)
+            ]
+            .push_maybe(username_feedback(
+                &config.username,
+                config.allow_invalid_username,
+            ))
+            .spacing(5);
+        if config.offline_profiles.is_empty() {
+            return column![widget::text(tr!("launch-username")), username_input]
+                .width(200)
+                .spacing(5)
+                .into();
+        }
+
+        let selected = match &config.selected_profile {
+            Some(name) => AccountChoice::OfflineProfile(name.clone()),
+            None => AccountChoice::Username,
+        };
+        let show_username = selected == AccountChoice::Username;
+        column![
+            widget::text(tr!("launch-account")),
+            widget::pick_list(
+                AccountChoice::all(&config.offline_profiles),
+                Some(selected),
+                Message::LaunchAccountSelected
+            )
+            .width(Length::Fill),
+        ]
+        .push_maybe(show_username.then_some(username_input))
+        .width(200)
+        .spacing(5)
+        .into()
+    }
+}
+
+impl MenuLaunchWithOptions {
+    pub fn view(&self, config: Option<&LauncherConfig>) -> Element<'_> {
+        let default_username = config.map(|config| config.username.as_str());
+        // An empty field means the default username, which is fine.
+        let username_feedback = if self.username.is_empty() {
+            None
+        } else {
+            username_feedback(
+                &self.username,
+                config.is_some_and(|config| config.allow_invalid_username),
+            )
+        };
+
+        column![
+            button_with_icon(icon_manager::back(), tr!("back")).on_press(Message::LaunchScreenOpen),
+            widget::text(tr!(
+                "launch-with-options-title",
+                instance = self.selected_instance.clone()
+            ))
+            .size(20),
+            widget::text(tr!("launch-with-options-hint")).size(text_size(14)),
+            widget::text(tr!("launch-username")),
+            widget::text_input(default_username.unwrap_or_default(), &self.username)
+                .on_input(Message::LaunchWithOptionsUsernameInput)
+                .width(200),
+        ]
+        .push_maybe(username_feedback)
+        .push(widget::text(tr!("launch-with-options-java-args")))
+        .push(
+            widget::text_input("-Dfile.encoding=UTF-8", &self.java_args_input)
+                .on_input(Message::LaunchWithOptionsJavaArgsInput),
+        )
+        .push(widget::text(tr!("launch-with-options-game-args")))
+        .push(
+            widget::text_input("--demo", &self.game_args_input)
+                .on_input(Message::LaunchWithOptionsGameArgsInput),
+        )
+        .push(widget::text(tr!("launch-with-options-server")))
+        .push(
+            widget::text_input("localhost:25565", &self.server)
+                .on_input(Message::LaunchWithOptionsServerInput)
+                .on_submit(Message::LaunchWithOptionsStart)
+                .width(300),
+        )
+        .push(
+            button_with_icon(icon_manager::play(), tr!("launch-play"))
+                .on_press(Message::LaunchWithOptionsStart),
+        )
+        .padding(10)
+        .spacing(10)
+        .into()
+    }
+}
+
+impl MenuGameOptions {
+    pub fn view<'a>(&'a self, instances: Option<&'a [InstanceInfo]>) -> Element<'a> {
+        let render_distance_error =
+            game_options::parse_render_distance(&self.render_distance_input)
+                .err()
+                .map(|_| {
+                    let range = GameSettings::RENDER_DISTANCE_RANGE;
+                    tr!(
+                        "game-options-render-distance-invalid",
+                        min = range.start(),
+                        max = range.end()
+                    )
+                });
+        let max_fps_error = game_options::parse_max_fps(&self.max_fps_input)
+            .err()
+            .map(|_| {
+                let range = GameSettings::MAX_FPS_RANGE;
+                tr!(
+                    "game-options-max-fps-invalid",
+                    min = range.start(),
+                    max = range.end()
+                )
+            });
+        let language_error = game_options::validate_language(self.language.trim())
+            .err()
+            .map(|_| tr!("game-options-language-invalid"));
+        let is_valid =
+            render_distance_error.is_none() && max_fps_error.is_none() && language_error.is_none();
+
+        let error_text =
+            |error: Option<String>| error.map(|error| widget::text(error).size(text_size(14)));
+
+        let copy_sources: Vec<String> = instances
+            .unwrap_or_default()
+            .iter()
+            .map(|instance| instance.name.clone())
+            .filter(|name| *name != self.selected_instance)
+            .collect();
+        let enabled_packs = self.options.resource_packs();
+
+        widget::scrollable(
+            column![
+                button_with_icon(icon_manager::back(), tr!("back"))
+                    .on_press(Message::LaunchScreenOpen),
+                widget::text(tr!("game-options-title", instance = self.selected_instance)).size(20),
+                widget::text(tr!("game-options-hint")).size(text_size(14)),
+                widget::text(tr!("game-options-render-distance")),
+                widget::text_input("12", &self.render_distance_input)
+                    .on_input(Message::GameOptionsRenderDistanceInput)
+                    .width(200),
+            ]
+            .push_maybe(error_text(render_distance_error))
+            .push(widget::text(tr!("game-options-gui-scale")))
+            .push(widget::pick_list(
+                GuiScaleChoice::ALL,
+                Some(GuiScaleChoice(self.gui_scale)),
+                Message::GameOptionsGuiScaleSelected,
+            ))
+            .push(widget::text(tr!("game-options-max-fps")))
+            .push(
+                widget::text_input("120", &self.max_fps_input)
+                    .on_input(Message::GameOptionsMaxFpsInput)
+                    .width(200),
+            )
+            .push(
+                widget::text(tr!(
+                    "game-options-max-fps-hint",
+                    unlimited = GameSettings::UNLIMITED_FPS
+                ))
+                .size(text_size(14)),
+            )
+            .push_maybe(error_text(max_fps_error))
+            .push(
+                widget::checkbox(tr!("game-options-vsync"), self.vsync)
+                    .on_toggle(Message::GameOptionsVsyncToggle),
+            )
+            .push(widget::text(tr!("game-options-language")))
+            .push(
+                widget::text_input("en_us", &self.language)
+                    .on_input(Message::GameOptionsLanguageInput)
+                    .width(200),
+            )
+            .push_maybe(error_text(language_error))
+            .push(widget::text(tr!("game-options-copy-source")))
+            .push(widget::pick_list(
+                copy_sources,
+                self.copy_source.clone(),
+                Message::GameOptionsCopySourceSelected,
+            ))
+            .push(
+                row![
+                    widget::button(widget::text(tr!("game-options-import-keybinds")))
+                        .on_press_maybe(
+                            self.copy_source
+                                .is_some()
+                                .then_some(Message::GameOptionsImportKeybinds)
+                        ),
+                    widget::button(widget::text(tr!("game-options-copy-all"))).on_press_maybe(
+                        self.copy_source
+                            .is_some()
+                            .then_some(Message::GameOptionsCopyAll)
+                    ),
+                ]
+                .spacing(10),
+            )
+            .push(widget::text(tr!("game-options-copy-all-hint")).size(text_size(14)))
+            .push(widget::text(tr!("game-options-resource-packs")).size(20))
+            .push_maybe(
+                self.resource_packs
+                    .is_empty()
+                    .then(|| widget::text(tr!("game-options-resource-packs-empty"))),
+            )
+            .push(Column::with_children(self.resource_packs.iter().map(
+                |pack| {
+                    widget::checkbox(pack, enabled_packs.contains(pack))
+                        .on_toggle(|enabled| {
+                            Message::GameOptionsResourcePackToggle(pack.clone(), enabled)
+                        })
+                        .into()
+                },
+            )))
+            .push(
+                row![
+                    widget::text_input(
+                        &tr!("game-options-resource-pack-placeholder"),
+                        &self.resource_pack_input
+                    )
+                    .on_input(Message::GameOptionsResourcePackInput),
+                    widget::button(widget::text(tr!("add"))).on_press_maybe(
+                        (!self.installing_pack && !self.resource_pack_input.trim().is_empty())
+                            .then_some(Message::GameOptionsResourcePackInstall)
+                    ),
+                ]
+                .spacing(10),
+            )
+            .push(
+                widget::checkbox(tr!("game-options-enable-new-pack"), self.enable_new_pack)
+                    .on_toggle(Message::GameOptionsEnableNewPackToggle),
+            )
+            .push(
+                widget::button(widget::text(tr!("game-options-save")))
+                    .on_press_maybe(is_valid.then_some(Message::GameOptionsSave)),
+            )
+            .padding(10)
+            .spacing(10),
+        )
+        .into()
+    }
+}
+
+impl MenuGameLog {
+    pub fn view<'element>(&self, processes: &'element [GameProcess]) -> Element<'element> {
+        /// Showing the whole log would make the UI slow.
+        const SHOWN_LINES: usize = 500;
+
+        let back_button =
+            button_with_icon(icon_manager::back(), tr!("back")).on_press(Message::LaunchScreenOpen);
+
+        let Some(process) = processes.iter().find(|n| n.pid == self.pid) else {
+            return column![back_button, widget::text(tr!("game-log-missing"))]
+                .padding(10)
+                .spacing(10)
+                .into();
+        };
+
+        let log = {
+            let log = process.log.lock().unwrap();
+            log[log.len().saturating_sub(SHOWN_LINES)..].join("\n")
+        };
+
+        let action = if process.exit_status.is_some() {
+            widget::button(widget::text(tr!("game-close"))).on_press(Message::GameClose(self.pid))
+        } else {
+            widget::button(widget::text(tr!("game-kill"))).on_press(Message::GameKill(self.pid))
+        };
+
+        let share_button = widget::button(widget::text(if self.sharing {
+            tr!("game-log-sharing")
+        } else {
+            tr!("game-log-share")
+        }))
+        .on_press_maybe((!self.sharing).then_some(Message::GameLogShare(self.pid)));
+        // The game only writes a crash report when it crashes.
+        let crashed = process
+            .exit_status
+            .is_some_and(|status| !status.success() && !process.killed);
+        let share_crash_button = crashed.then(|| {
+            widget::button(widget::text(tr!("game-log-share-crash-report"))).on_press_maybe(
+                (!self.sharing).then_some(Message::GameLogShareCrashReport(self.pid)),
+            )
+        });
+
+        column![
+            row![back_button, action, share_button]
+                .push_maybe(share_crash_button)
+                .spacing(10),
+            widget::text(tr!("game-log-title", instance = process.instance_name)).size(20),
+            widget::scrollable(
+                widget::text(log)
+                    .font(iced::Font::MONOSPACE)
+                    .size(text_size(12))
+                    .width(Length::Fill)
+            )
+            .height(Length::Fill),
+        ]
+        .padding(10)
+        .spacing(10)
+        .into()
+    }
+}
+
+impl MenuCrashRecovery {
+    pub fn view(&self) -> Element<'_> {
+        let crashes_dir = crash_dump::get_crashes_dir().unwrap_or_default();
+
+        column![
+            widget::text(tr!("crash-title")).size(20),
+            widget::text(tr!("crash-hint")),
+            widget::text(tr!("crash-message", message = self.crash.message.as_str())),
+            row![
+                widget::button(widget::text(tr!("crash-continue")))
+                    .on_press(Message::LaunchScreenOpen),
+                widget::button(widget::text(tr!("crash-copy")))
+                    .on_press(Message::CrashRecoveryCopy),
+                widget::button(widget::text(tr!("crash-report")))
+                    .on_press(Message::CrashRecoveryReport),
+                button_with_icon(icon_manager::folder(), tr!("crash-open-folder"))
+                    .on_press(Message::OpenDir(crashes_dir)),
+            ]
+            .spacing(10),
+            widget::scrollable(
+                widget::text(&self.crash.text)
+                    .font(iced::Font::MONOSPACE)
+                    .size(text_size(12))
+                    .width(Length::Fill)
+            )
+            .height(Length::Fill),
+        ]
+        .padding(10)
+        .spacing(10)
+        .into()
+    }
+}
+
+impl MenuLauncherLogs {
+    pub fn view(&self) -> Element<'_> {
+        let logs_dir = logging::get_logs_dir().unwrap_or_default();
+
+        column![
+            row![
+                button_with_icon(icon_manager::back(), tr!("back"))
+                    .on_press(Message::LauncherSettingsOpen),
+                widget::button(widget::text(tr!("launcher-logs-refresh")))
+                    .on_press(Message::LauncherLogsOpen),
+                widget::button(widget::text(tr!("launcher-logs-copy")))
+                    .on_press(Message::LauncherLogsCopy),
+                button_with_icon(icon_manager::folder(), tr!("launcher-logs-open")).on_press_maybe(
+                    logs_dir
+                        .is_dir()
+                        .then(|| Message::OpenDir(logs_dir.clone()))
+                ),
+            ]
+            .spacing(10),
+            widget::text(tr!("launcher-logs-title")).size(20),
+            widget::scrollable(
+                widget::text(self.lines.join("\n"))
+                    .font(iced::Font::MONOSPACE)
+                    .size(text_size(12))
+                    .width(Length::Fill)
+            )
+            .height(Length::Fill),
+        ]
+        .padding(10)
+        .spacing(10)
+        .into()
+    }
+}
+
+impl MenuWelcome {
+    pub fn view<'element>(
+        &'element self,
+        config: Option<&'element LauncherConfig>,
+    ) -> Element<'element> {
+        let Some(config) = config else {
+            return column![widget::text(tr!("settings-loading"))]
+                .padding(10)
+                .into();
+        };
+
+        let content: Element = match self.step {
+            WelcomeStep::DataDir => column![
+                widget::text(tr!("welcome-data-dir")).size(20),
+                widget::text(tr!("welcome-data-dir-hint")),
+                widget::text_input("", &self.data_dir_input)
+                    .on_input(Message::WelcomeDataDirInput)
+                    .on_submit(Message::WelcomeNext),
+            ]
+            .spacing(10)
+            .into(),
+            WelcomeStep::Account => column![
+                widget::text(tr!("welcome-account")).size(20),
+                widget::text(tr!("welcome-account-hint")),
+                widget::text_input(&tr!("launch-username-placeholder"), &config.username)
+                    .on_input(Message::LauncherSettingsUsernameSet)
+                    .on_submit(Message::WelcomeNext),
+            ]
+            .push_maybe(username_feedback(
+                &config.username,
+                config.allow_invalid_username,
+            ))
+            .spacing(10)
+            .into(),
+            WelcomeStep::Theme => column![
+                widget::text(tr!("welcome-theme")).size(20),
+                widget::text(tr!("settings-theme")),
+                widget::pick_list(
+                    ThemeChoice::ALL,
+                    Some(config.theme),
+                    Message::LauncherSettingsThemeSelected
+                ),
+                widget::text(tr!("settings-accent")),
+                widget::pick_list(
+                    AccentColor::ALL,
+                    Some(config.accent_color),
+                    Message::LauncherSettingsAccentSelected
+                ),
+                widget::text(tr!("settings-language")),
+                widget::pick_list(
+                    Language::ALL,
+                    Some(config.language),
+                    Message::LauncherSettingsLanguageSelected
+                ),
+            ]
+            .spacing(10)
+            .into(),
+            WelcomeStep::FirstInstance => column![
+                widget::text(tr!("welcome-first-instance")).size(20),
+                widget::text(tr!("welcome-first-instance-hint")),
+                row![
+                    button_with_icon(icon_manager::create(), tr!("launch-new-instance")).on_press(
+                        Message::WelcomeFinish {
+                            create_instance: true
+                        }
+                    ),
+                    widget::button(widget::text(tr!("welcome-skip"))).on_press(
+                        Message::WelcomeFinish {
+                            create_instance: false
+                        }
+                    ),
+                ]
+                .spacing(10),
+            ]
+            .spacing(10)
+            .into(),
+        };
+
+        let navigation = row![widget::button(widget::text(tr!("back")))
+            .on_press_maybe(self.step.previous().map(|_| Message::WelcomeBack))]
+        .push_maybe(self.step.next().map(|_| {
+            widget::button(widget::text(tr!("welcome-next"))).on_press(Message::WelcomeNext)
+        }))
+        .spacing(10);
+
+        column![
+            widget::text(tr!("welcome-title")).size(30),
+            widget::text(tr!(
+                "welcome-step",
+                step = self.step.number(),
+                total = WelcomeStep::ALL.len()
+            )),
+            content,
+            navigation,
+        ]
+        .padding(20)
+        .spacing(20)
+        .into()
+    }
+}
+
+impl MenuLaunch {
+    pub fn view<'element>(
+        &'element self,
+        config: Option<&'element LauncherConfig>,
+        instances: Option<&'element [InstanceInfo]>,
+        news: Option<&'element Result<Vec<NewsEntry>, String>>,
+        server_statuses: &'element [ServerStatusEntry],
+        update: Option<&'element Release>,
+        skins: &HashMap<SkinSource, Option<widget::image::Handle>>,
+    ) -> Element<'element> {
+        let config = config.unwrap();
+        let is_grid = config.instance_view == InstanceView::Grid;
+
+        let instance_picker: Element = match instances {
+            Some(instances) if !is_grid => widget::pick_list(
+                instances
+                    .iter()
+                    .map(|instance| instance.name.clone())
+                    .collect::<Vec<_>>(),
+                self.selected_instance.clone(),
+                Message::LaunchInstanceSelected,
+            )
+            .width(200)
+            .into(),
+            _ => column![].into(),
+        };
+
+        let pick_list = if instances.is_some() {
+            column![
+                widget::text(tr!("launch-instances")),
+                instance_picker,
+                button_with_icon(icon_manager::create(), tr!("launch-new-instance"))
+                    .on_press(Message::CreateInstanceScreenOpen),
+                button_with_icon(icon_manager::delete(), tr!("launch-delete-instance"))
+                    .on_press_maybe(self.selected_instance.as_ref().map(|instance| {
+                        Message::ConfirmOpen(DestructiveAction::DeleteInstance {
+                            instance: instance.clone(),
+                        })
+                    })),
+                button_with_icon(icon_manager::settings(), tr!("launch-edit-instance"))
+                    .on_press_maybe(
+                        (self.selected_instance.is_some()).then_some(Message::EditInstance)
+                    ),
+                widget::button(widget::text(
+                    if self
+                        .selected_instance
+                        .as_ref()
+                        .is_some_and(|n| config.favorite_instances.contains(n))
+                    {
+                        tr!("launch-unfavorite")
+                    } else {
+                        tr!("launch-favorite")
+                    }
+                ))
+                .on_press_maybe(
+                    (self.selected_instance.is_some()).then_some(Message::LaunchFavoriteToggle)
+                ),
+                button_with_icon(icon_manager::download(), tr!("launch-manage-mods"))
+                    .on_press_maybe(
+                        (self.selected_instance.is_some()).then_some(Message::ManageModsScreenOpen)
+                    ),
+                button_with_icon(icon_manager::folder(), tr!("launch-worlds")).on_press_maybe(
+                    (self.selected_instance.is_some()).then_some(Message::WorldsScreenOpen)
+                ),
+                button_with_icon(icon_manager::settings(), tr!("launch-game-options"))
+                    .on_press_maybe(
+                        (self.selected_instance.is_some())
+                            .then_some(Message::GameOptionsScreenOpen)
+                    ),
+                button_with_icon(icon_manager::settings(), tr!("launch-verify")).on_press_maybe(
+                    (self.selected_instance.is_some()).then_some(Message::VerifyScreenOpen)
+                ),
+                button_with_icon(icon_manager::settings(), tr!("launch-settings"))
+                    .on_press(Message::LauncherSettingsOpen),
+                button_with_icon(icon_manager::play(), tr!("launch-servers"))
+                    .on_press(Message::ServersScreenOpen),
+                button_with_icon(icon_manager::play(), tr!("launch-stats"))
+                    .on_press(Message::StatsScreenOpen),
+                button_with_icon(icon_manager::download(), tr!("launch-downloads"))
+                    .on_press(Message::DownloadQueueScreenOpen),
+                button_with_icon(icon_manager::folder(), tr!("launch-open-files")).on_press_maybe(
+                    self.selected_instance
+                        .is_some()
+                        .then_some(Message::LaunchOpenFiles)
+                )
+            ]
+        } else {
+            column![widget::text(tr!("launch-instances-loading"))]
+        };
+
+        let java_progress_bar = if let Some(progress) = &self.java_install_progress {
+            java_install_progress(progress)
+        } else {
+            widget::column!(
+                widget::text(tr!("app-tagline")),
+                widget::text(tr!("launch-shortcut-hint")).size(text_size(14))
+            )
+            .spacing(10)
+        };
+
+        let update_button = update.map(|release| {
+            widget::button(widget::text(tr!(
+                "launch-update-available",
+                version = release.version.clone()
+            )))
+            .on_press(Message::UpdateScreenOpen)
+        });
+
+        let controls = column![]
+            .push_maybe(update_button)
+            .push(self.view_account(config, instances, skins))
+            .push(pick_list.spacing(5))
+            .push(
+                button_with_icon(icon_manager::play(), tr!("launch-play")).on_press_maybe(
+                    (self.selected_instance.is_some()).then_some(Message::LaunchStart),
+                ),
+            )
+            .push(
+                widget::button(widget::text(tr!("launch-with-options")).size(text_size(14)))
+                    .on_press_maybe(
+                        (self.selected_instance.is_some() && self.java_install_progress.is_none())
+                            .then_some(Message::LaunchWithOptionsOpen),
+                    ),
+            )
+            .push(
+                widget::button(widget::text(tr!("launch-without-mods")).size(text_size(14)))
+                    .on_press_maybe(
+                        (self.selected_instance.is_some() && self.java_install_progress.is_none())
+                            .then_some(Message::LaunchStartWithoutMods),
+                    ),
+            )
+            .push(java_progress_bar)
+            .push_maybe((!server_statuses.is_empty()).then(|| server_status_panel(server_statuses)))
+            .padding(10)
+            .spacing(20);
+
+        let mut view = row![controls];
+        if let (Some(instances), true) = (instances, is_grid) {
+            view = view
+                .push(widget::scrollable(self.view_instance_grid(instances)).width(Length::Fill));
+        }
+        if config.show_news {
+            view = view.push(news_panel(news));
+        }
+        column![]
+            .push_maybe(instances.and_then(|instances| self.view_recently_played(instances)))
+            .push(view)
+            .into()
+    }
+
+    /// Big buttons for launching the last played instances,
+    /// each as the account it was last played with.
+    fn view_recently_played<'element>(
+        &self,
+        instances: &'element [InstanceInfo],
+    ) -> Option<Element<'element>> {
+        const SHOWN: usize = 4;
+
+        let mut recent: Vec<&InstanceInfo> = instances
+            .iter()
+            .filter(|instance| instance.last_played.is_some())
+            .collect();
+        if recent.is_empty() {
+            return None;
+        }
+        recent.sort_by_key(|instance| std::cmp::Reverse(instance.last_played));
+
+        let can_launch = self.java_install_progress.is_none();
+        let buttons = Row::with_children(recent.into_iter().take(SHOWN).map(|instance| {
+            let details = column![
+                widget::text(&instance.name).size(18),
+                widget::text(format_last_played(instance.last_played)).size(text_size(14)),
+            ]
+            .push_maybe(instance.last_username.as_ref().map(|username| {
+                widget::text(tr!("launch-recent-as", username = username.clone()))
+                    .size(text_size(14))
+            }))
+            .spacing(2);
+            widget::button(row![icon_manager::play(), details].spacing(10))
+                .padding(10)
+                .width(220)
+                .on_press_maybe(can_launch.then(|| Message::LaunchRecent(instance.name.clone())))
+                .into()
+        }))
+        .spacing(10);
+
+        Some(
+            column![widget::text(tr!("launch-recent")).size(20), buttons]
+                .padding([10, 10, 0, 10])
+                .spacing(10)
+                .into(),
+        )
+    }
+
+    fn view_instance_grid<'element>(
+        &self,
+        instances: &'element [InstanceInfo],
+    ) -> Element<'element> {
+        const CARDS_PER_ROW: usize = 3;
+
+        Column::with_children(instances.chunks(CARDS_PER_ROW).map(|chunk| {
+            Row::with_children(chunk.iter().map(|instance| {
+                let is_selected = self.selected_instance.as_ref() == Some(&instance.name);
+                instance_card(instance, is_selected)
+            }))
+            .spacing(10)
+            .into()
+        }))
+        .padding(10)
+        .spacing(10)
+        .into()
+    }
+}
+
+fn server_status_panel(entries: &[ServerStatusEntry]) -> Element<'_> {
+    let entries = Column::with_children(entries.iter().map(|entry| {
+        let status: Element = match &entry.status {
+            None => widget::text(tr!("server-status-checking"))
+                .size(text_size(14))
+                .into(),
+            Some(Err(_)) => widget::text(tr!("server-status-offline"))
+                .size(text_size(14))
+                .into(),
+            Some(Ok(status)) => column![widget::text(tr!(
+                "server-status-online",
+                online = status.players_online,
+                max = status.players_max,
+                latency = status.latency.as_millis()
+            ))
+            .size(text_size(14)),]
+            .push_maybe(
+                (!status.motd.trim().is_empty())
+                    .then(|| widget::text(status.motd.trim()).size(text_size(12))),
+            )
+            .into(),
+        };
+        column![widget::text(&entry.name), status].spacing(2).into()
+    }))
+    .spacing(10);
+
+    column![widget::text(tr!("server-status-title")).size(20), entries]
+        .spacing(10)
+        .width(200)
+        .into()
+}
+
+fn news_panel(news: Option<&Result<Vec<NewsEntry>, String>>) -> Element<'_> {
+    let entries: Element = match news {
+        None => widget::text(tr!("news-loading")).into(),
+        Some(Err(err)) => widget::text(tr!("news-failed", error = err)).into(),
+        Some(Ok(entries)) => Column::with_children(entries.iter().map(|entry| {
+            column![
+                widget::text(&entry.title).size(18),
+                widget::text(format!("{} - {}", entry.date, entry.category)).size(text_size(12)),
+                widget::text(&entry.text).size(text_size(14)),
+            ]
+            .push_maybe(entry.readMoreLink.as_ref().map(|link| {
+                widget::button(widget::text(tr!("news-read-more")).size(text_size(14)))
+                    .on_press(Message::OpenLink(link.clone()))
+            }))
+            .spacing(5)
+            .into()
+        }))
+        .spacing(20)
+        .into(),
+    };
+
+    widget::scrollable(
+        column![widget::text(tr!("news-title")).size(20), entries]
+            .padding(10)
+            .spacing(10),
+    )
+    .width(300)
+    .into()
+}
+
+fn instance_card(instance: &InstanceInfo, is_selected: bool) -> Element<'_> {
+    const ICON_SIZE: u16 = 64;
+
+    let icon: Element = if let Some(icon) = &instance.icon {
+        widget::image(icon)
+            .width(ICON_SIZE)
+            .height(ICON_SIZE)
+            .into()
+    } else {
+        // Use the first letter of the name as a placeholder icon.
+        let letter = instance
+            .name
+            .chars()
+            .next()
+            .unwrap_or_default()
+            .to_uppercase()
+            .to_string();
+        widget::container(widget::text(letter).size(32))
+            .width(ICON_SIZE)
+            .height(ICON_SIZE)
+            .center_x()
+            .center_y()
+            .into()
+    };
+
+    let name: Element = if is_selected {
+        row![icon_manager::play(), widget::text(&instance.name)]
+            .spacing(5)
+            .into()
+    } else {
+        widget::text(&instance.name).into()
+    };
+
+    let unknown = || tr!("launch-card-unknown");
+    widget::button(
+        column![
+            icon,
+            name,
+            widget::text(instance.version.clone().unwrap_or_else(unknown)).size(text_size(14)),
+            widget::text(instance.mod_type.clone().unwrap_or_else(unknown)).size(text_size(14)),
+            widget::text(format_last_played(instance.last_played)).size(text_size(14)),
+        ]
+        .spacing(5),
+    )
+    .width(160)
+    .padding(10)
+    .on_press(Message::LaunchInstanceSelected(instance.name.clone()))
+    .into()
+}
+
+impl MenuEditInstance {
+    /// Why the typed memory is wrong or too much, if it is.
+    fn memory_warning(&self) -> Option<String> {
+        if parse_memory_mb(&self.memory_input).is_none() {
+            return Some(tr!("edit-memory-invalid"));
+        }
+        let system_memory_mb = self.system_memory_mb?;
+        // The OS and the launcher need some too.
+        (self.config.ram_in_mb * 10 > system_memory_mb * 9).then(|| {
+            tr!(
+                "edit-memory-too-much",
+                total = format_memory(system_memory_mb)
+            )
+        })
+    }
+
+    pub fn view<'element>(&self) -> Element<'element> {
+        // 2 ^ 8 = 256 MB
+        const MEM_256_MB_IN_TWOS_EXPONENT: f32 = 8.0;
+        // 2 ^ 13 = 8192 MB
+        const MEM_8192_MB_IN_TWOS_EXPONENT: f32 = 13.0;
+
+        widget::scrollable(
+            column![
+                widget::button(
+                    row![icon_manager::back(), widget::text(tr!("back"))]
+                        .spacing(10)
+                        .padding(5)
+                )
+                .on_press(Message::LaunchScreenOpen),
+                widget::text(tr!(
+                    "edit-title",
+                    mod_type = self.config.mod_type,
+                    instance = self.selected_instance
+                )),
+                widget::container(
+                    column![
+                        widget::text(tr!("edit-java-override")),
+                        widget::text_input(
+                            &tr!("edit-java-override-placeholder"),
+                            self.config.java_override.as_deref().unwrap_or_default()
+                        )
+                        .on_input(Message::EditInstanceJavaOverride)
+                    ]
+                    .padding(10)
+                    .spacing(10)
+                ),
+                widget::container(
+                    column![
+                        widget::text(tr!("edit-java-args")),
+                        widget::text_input("-Dfile.encoding=UTF-8", &self.java_args_input)
+                            .on_input(Message::EditInstanceJavaArgsInput),
+                        widget::checkbox(
+                            tr!("edit-log4shell-mitigation"),
+                            self.config.log4shell_mitigation
+                        )
+                        .on_toggle(Message::EditInstanceLog4ShellToggle),
+                    ]
+                    .padding(10)
+                    .spacing(10)
+                ),
+                widget::container(
+                    column![
+                        widget::text(tr!("edit-version-type")),
+                        widget::text_input(
+                            &tr!("edit-version-type-placeholder"),
+                            self.config.version_type.as_deref().unwrap_or_default()
+                        )
+                        .on_input(Message::EditInstanceVersionTypeInput)
+                    ]
+                    .padding(10)
+                    .spacing(10)
+                ),
+                widget::container(
+                    column![
+                        widget::text(tr!("edit-game-dir")),
+                        widget::text_input(
+                            &tr!("edit-game-dir-placeholder"),
+                            self.config.game_dir.as_deref().unwrap_or_default()
+                        )
+                        .on_input(Message::EditInstanceGameDirInput),
+                        widget::pick_list(
+                            self.other_instances.clone(),
+                            None::<String>,
+                            Message::EditInstanceGameDirFromInstance
+                        )
+                        .placeholder(tr!("edit-game-dir-from-instance")),
+                        widget::checkbox(tr!("edit-sync-folder"), self.config.sync_folder)
+                            .on_toggle(Message::EditInstanceSyncFolderToggle),
+                    ]
+                    .padding(10)
+                    .spacing(10)
+                ),
+                widget::container(
+                    column![
+                        widget::text(tr!("edit-java-version")),
+                        widget::pick_list(
+                            JavaVersionChoice::all(),
+                            Some(JavaVersionChoice(self.config.java_version)),
+                            Message::EditInstanceJavaVersionSelected
+                        )
+                    ]
+                    .padding(10)
+                    .spacing(10)
+                ),
+                widget::container(
+                    column![
+                        widget::text(tr!("edit-offline-profile")),
+                        widget::text(tr!("edit-offline-profile-hint")).size(text_size(14)),
+                        widget::pick_list(
+                            std::iter::once(InstanceProfileChoice(None))
+                                .chain(
+                                    self.offline_profiles
+                                        .iter()
+                                        .map(|name| InstanceProfileChoice(Some(name.clone())))
+                                )
+                                .collect::<Vec<_>>(),
+                            Some(InstanceProfileChoice(self.config.offline_profile.clone())),
+                            Message::EditInstanceOfflineProfileSelected
+                        )
+                    ]
+                    .padding(10)
+                    .spacing(10)
+                ),
+                widget::container(
+                    column![
+                        widget::text(tr!("edit-memory")),
+                        widget::text(tr!("edit-memory-normal")),
+                        widget::text(tr!("edit-memory-old")),
+                        widget::text(tr!("edit-memory-heavy")),
+                        widget::slider(
+                            MEM_256_MB_IN_TWOS_EXPONENT..=MEM_8192_MB_IN_TWOS_EXPONENT,
+                            self.slider_value,
+                            Message::EditInstanceMemoryChanged
+                        )
+                        .step(0.1),
+                        row![
+                            widget::text_input("4G", &self.memory_input)
+                                .on_input(Message::EditInstanceMemoryInput)
+                                .width(120),
+                            widget::text(&self.slider_text),
+                        ]
+                        .spacing(10)
+                        .align_items(iced::Alignment::Center),
+                    ]
+                    .push_maybe(self.memory_warning().map(widget::text))
+                    .push(widget::text(tr!("edit-memory-initial")))
+                    .push(
+                        widget::slider(
+                            MEM_256_MB_IN_TWOS_EXPONENT..=MEM_8192_MB_IN_TWOS_EXPONENT,
+                            self.initial_slider_value,
+                            Message::EditInstanceInitialMemoryChanged,
+                        )
+                        .step(0.1),
+                    )
+                    .push(widget::text(&self.initial_slider_text))
+                    .push(widget::text(tr!(
+                        "edit-memory-flags",
+                        flags = self.config.get_ram_arguments().join(" ")
+                    )))
+                    .padding(10)
+                    .spacing(5),
+                ),
+                widget::container(
+                    column![
+                        widget::text(tr!("edit-snapshots")),
+                        widget::text(tr!("edit-snapshots-description")).size(text_size(14)),
+                        widget::checkbox(
+                            tr!("edit-snapshots-small-files"),
+                            self.config.snapshot_small_files
+                        )
+                        .on_toggle(Message::EditInstanceSnapshotSmallFilesToggle),
+                    ]
+                    .push_maybe(
+                        self.snapshots
+                            .is_empty()
+                            .then(|| widget::text(tr!("edit-snapshots-none")))
+                    )
+                    .push(Column::with_children(self.snapshots.iter().map(
+                        |snapshot| {
+                            snapshot_row(&self.selected_instance, snapshot, self.rolling_back)
+                        }
+                    )))
+                    .padding(10)
+                    .spacing(10)
+                ),
+                widget::container(
+                    column![
+                        widget::text(tr!("edit-template")),
+                        widget::text(tr!("edit-template-description")).size(text_size(14)),
+                        row![
+                            widget::text_input(
+                                &tr!("edit-template-placeholder"),
+                                &self.template_name_input
+                            )
+                            .on_input(Message::EditInstanceTemplateNameInput),
+                            widget::button(widget::text(tr!("edit-template-save"))).on_press_maybe(
+                                (!self.template_name_input.trim().is_empty())
+                                    .then_some(Message::EditInstanceTemplateSave)
+                            ),
+                        ]
+                        .spacing(10),
+                    ]
+                    .padding(10)
+                    .spacing(10)
+                ),
+            ]
+            .padding(10)
+            .spacing(20),
+        )
+        .into()
+    }
+}
+
+fn snapshot_row<'element>(
+    instance: &str,
+    snapshot: &Snapshot,
+    rolling_back: bool,
+) -> Element<'element> {
+    let reason = match snapshot.reason {
+        SnapshotReason::LoaderInstall => tr!("snapshot-loader-install"),
+        SnapshotReason::LoaderUpdate => tr!("snapshot-loader-update"),
+        SnapshotReason::LoaderUninstall => tr!("snapshot-loader-uninstall"),
+        SnapshotReason::VersionJsonRedownload => tr!("snapshot-version-json-redownload"),
+    };
+    let loader = match &snapshot.loader {
+        Some(version) => tr!("snapshot-fabric", version = version),
+        None => tr!("snapshot-vanilla"),
+    };
+    row![
+        column![
+            widget::text(
+                snapshot
+                    .path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+            ),
+            widget::text(tr!(
+                "snapshot-details",
+                reason = reason,
+                loader = loader,
+                mods = snapshot.mods
+            ))
+            .size(text_size(14)),
+        ]
+        .width(350),
+        widget::button(widget::text(tr!("edit-roll-back"))).on_press_maybe((!rolling_back).then(
+            || {
+                Message::ConfirmOpen(DestructiveAction::RollBack {
+                    instance: instance.to_owned(),
+                    snapshot: snapshot.path.clone(),
+                })
+            }
+        )),
+    ]
+    .spacing(10)
+    .align_items(iced::Alignment::Center)
+    .into()
+}
+
+impl MenuEditMods {
+    /// `loader_update` is a newer version of the installed loader.
+    pub fn view<'a>(&'a self, loader_update: Option<&'a String>) -> Element<'a> {
+        let mod_installer = if self.config.mod_type == "Vanilla" {
+            widget::column![
+                widget::button(widget::text(tr!("mods-install-fabric")))
+                    .on_press(Message::InstallFabricScreenOpen),
+                widget::button(widget::text(tr!("mods-install-quilt"))),
+                widget::button(widget::text(tr!("mods-install-forge"))),
+                widget::button(widget::text(tr!("mods-install-optifine")))
+            ]
+            .spacing(5)
+        } else {
+            widget::column![widget::button(
+                widget::row![
+                    icon_manager::delete(),
+                    widget::text(tr!("mods-uninstall", mod_type = self.config.mod_type))
+                ]
+                .spacing(10)
+                .padding(5)
+            )
+            .on_press_maybe((self.config.mod_type == "Fabric").then(|| {
+                Message::ConfirmOpen(DestructiveAction::UninstallLoader {
+                    instance: self.selected_instance.clone(),
+                    loader: self.config.mod_type.clone(),
+                })
+            }))]
+        };
+
+        let mods = Column::with_children(self.mods.iter().map(|file_name| {
+            row![
+                widget::button(icon_manager::delete()).on_press(Message::ConfirmOpen(
+                    DestructiveAction::DeleteMod {
+                        instance: self.selected_instance.clone(),
+                        file_name: file_name.clone(),
+                    }
+                )),
+                widget::text(file_name),
+            ]
+            .push_maybe(
+                self.config
+                    .managed_mod(file_name)
+                    .map(|managed| widget::text(&managed.version).size(14)),
+            )
+            .spacing(10)
+            .into()
+        }))
+        .spacing(5);
+
+        widget::scrollable(
+            widget::column![
+                widget::button(
+                    widget::row![icon_manager::back(), widget::text(tr!("back"))]
+                        .spacing(10)
+                        .padding(5)
+                )
+                .on_press(Message::LaunchScreenOpen),
+                mod_installer,
+            ]
+            .push_maybe(loader_update.map(|version| self.loader_update_badge(version)))
+            .push_maybe((self.config.mod_type != "Vanilla").then(|| {
+                widget::button(widget::text(tr!("mods-browse"))).on_press(Message::ModBrowserOpen)
+            }))
+            .push(widget::text(tr!("mods-installed")))
+            .push(mods)
+            .push(
+                row![
+                    widget::text(tr!("mods-export")),
+                    widget::button(widget::text(tr!("mods-export-text")))
+                        .on_press(Message::ManageModsExport(ModListFormat::Text)),
+                    widget::button(widget::text(tr!("mods-export-markdown")))
+                        .on_press(Message::ManageModsExport(ModListFormat::Markdown)),
+                    widget::button(widget::text(tr!("mods-export-json")))
+                        .on_press(Message::ManageModsExport(ModListFormat::Json)),
+                ]
+                .spacing(10)
+                .align_items(iced::Alignment::Center),
+            )
+            .push(self.import_view())
+            .push(widget::button(widget::text(tr!("mods-open-folder"))))
+            .push(widget::text(tr!("mods-coming-soon")))
+            .padding(10)
+            .spacing(20),
+        )
+        .into()
+    }
+
+    fn import_view(&self) -> Element<'_> {
+        if let Some(progress) = &self.import_progress {
+            return widget::text(&progress.message).into();
+        }
+        column![
+            widget::text(tr!("mods-import")),
+            row![
+                widget::text_input(&tr!("mods-import-placeholder"), &self.import_input)
+                    .on_input(Message::ManageModsImportInput)
+                    .on_submit(Message::ManageModsImport),
+                widget::button(widget::text(tr!("mods-import-button"))).on_press_maybe(
+                    (self.config.mod_type != "Vanilla" && !self.import_input.trim().is_empty())
+                        .then_some(Message::ManageModsImport)
+                ),
+            ]
+            .spacing(10),
+        ]
+        .spacing(5)
+        .into()
+    }
+
+    fn loader_update_badge(&self, version: &str) -> Element<'_> {
+        row![
+            widget::text(tr!(
+                "mods-loader-update",
+                mod_type = self.config.mod_type,
+                version = version
+            )),
+            widget::button(widget::text(if self.updating_loader {
+                tr!("mods-loader-updating")
+            } else {
+                tr!("mods-loader-update-button")
+            }))
+            .on_press_maybe((!self.updating_loader).then_some(Message::ManageModsUpdateLoader)),
+        ]
+        .spacing(10)
+        .into()
+    }
+}
+
+impl MenuModBrowser {
+    pub fn view(&self) -> Element<'_> {
+        let results: Element = match &self.results {
+            None => widget::text(tr!("mod-browser-searching")).into(),
+            Some(Err(err)) => widget::text(tr!("mod-browser-search-failed", error = err)).into(),
+            Some(Ok(hits)) if hits.is_empty() => widget::text(tr!("mod-browser-no-results")).into(),
+            Some(Ok(hits)) => Column::with_children(hits.iter().map(|hit| self.search_hit(hit)))
+                .push_maybe(self.load_more(hits.len()))
+                .spacing(5)
+                .into(),
+        };
+
+        let search_panel = widget::scrollable(
+            column![
+                widget::button(
+                    widget::row![icon_manager::back(), widget::text(tr!("back"))]
+                        .spacing(10)
+                        .padding(5)
+                )
+                .on_press(Message::ManageModsScreenOpen),
+                row![
+                    widget::text_input(&tr!("mod-browser-search-placeholder"), &self.query)
+                        .on_input(Message::ModBrowserQueryInput)
+                        .on_submit(Message::ModBrowserSearch),
+                    widget::button(widget::text(tr!("mod-browser-search")))
+                        .on_press(Message::ModBrowserSearch),
+                ]
+                .spacing(5),
+                results,
+            ]
+            .padding(10)
+            .spacing(10),
+        )
+        .on_scroll(Message::ModBrowserScrolled)
+        .width(Length::FillPortion(2));
+
+        let mut view = row![search_panel];
+        if let Some(selected) = &self.selected {
+            view = view.push(
+                widget::scrollable(self.details_panel(selected)).width(Length::FillPortion(3)),
+            );
+        }
+        view.into()
+    }
+
+    /// Shown under the results while there are more pages.
+    /// They load on their own when scrolled to, but the button
+    /// is there for when the results don't fill the screen.
+    fn load_more(&self, loaded: usize) -> Option<Element<'_>> {
+        if self.loading_more {
+            Some(widget::text(tr!("mod-browser-loading-more")).into())
+        } else if loaded < self.total_hits {
+            Some(
+                widget::button(widget::text(tr!("mod-browser-load-more")))
+                    .on_press(Message::ModBrowserLoadMore)
+                    .into(),
+            )
+        } else {
+            None
+        }
+    }
+
+    fn search_hit<'a>(&'a self, hit: &'a SearchHit) -> Element<'a> {
+        let is_selected = self
+            .selected
+            .as_ref()
+            .is_some_and(|selected| selected.project_id == hit.project_id);
+
+        widget::button(
+            row![self.mod_icon(hit.icon_url.as_ref(), 48)]
+                .push(
+                    column![
+                        widget::text(&hit.title).size(18),
+                        widget::text(tr!(
+                            "mod-browser-hit-info",
+                            author = hit.author,
+                            downloads = hit.downloads
+                        ))
+                        .size(text_size(12)),
+                        widget::text(&hit.description).size(text_size(14)),
+                    ]
+                    .spacing(2),
+                )
+                .spacing(10),
+        )
+        .width(Length::Fill)
+        .on_press_maybe((!is_selected).then(|| Message::ModBrowserSelect(hit.project_id.clone())))
+        .into()
+    }
+
+    fn details_panel<'a>(&'a self, selected: &'a SelectedMod) -> Element<'a> {
+        let details = match &selected.details {
+            None => return widget::text(tr!("mod-browser-loading")).into(),
+            Some(Err(err)) => {
+                return widget::text(tr!("mod-browser-details-failed", error = err)).into()
+            }
+            Some(Ok(details)) => details,
+        };
+
+        let installer: Element = if let Some(progress) = &self.progress {
+            widget::text(&progress.message).into()
+        } else {
+            widget::button(
+                widget::row![
+                    icon_manager::download(),
+                    widget::text(tr!("mod-browser-install"))
+                ]
+                .spacing(10)
+                .padding(5),
+            )
+            .on_press(Message::ModBrowserInstall)
+            .into()
+        };
+
+        let mut stats = vec![
+            tr!("mod-browser-downloads", count = details.downloads),
+            tr!("mod-browser-followers", count = details.followers),
+            tr!(
+                "mod-browser-updated",
+                date = details.updated.get(..10).unwrap_or_default()
+            ),
+        ];
+        if let Some(license) = &details.license {
+            stats.push(tr!("mod-browser-license", license = license.name));
+        }
+        if !details.categories.is_empty() {
+            stats.push(details.categories.join(", "));
+        }
+
+        let gallery = Row::with_children(
+            details
+                .gallery
+                .iter()
+                .filter_map(|image| self.images.get(&image.url))
+                .map(|handle| widget::image(handle.clone()).height(150).into()),
+        )
+        .spacing(10);
+
+        column![
+            row![
+                self.mod_icon(details.icon_url.as_ref(), 64),
+                column![
+                    widget::text(&details.title).size(24),
+                    widget::text(&details.description).size(text_size(14)),
+                ]
+                .spacing(5),
+            ]
+            .spacing(10),
+            widget::text(stats.join("  ·  ")).size(text_size(12)),
+            row![
+                installer,
+                widget::button(widget::text(tr!("mod-browser-open-page")))
+                    .on_press(Message::OpenLink(details.page_url())),
+            ]
+            .spacing(10),
+            widget::scrollable(gallery).direction(widget::scrollable::Direction::Horizontal(
+                widget::scrollable::Properties::default()
+            )),
+            markdown_view(&selected.description),
+        ]
+        .padding(10)
+        .spacing(15)
+        .into()
+    }
+
+    fn mod_icon(&self, url: Option<&String>, size: u16) -> Element<'_> {
+        match url.and_then(|url| self.images.get(url)) {
+            Some(icon) => widget::image(icon.clone()).width(size).height(size).into(),
+            None => widget::Space::new(size, size).into(),
+        }
+    }
+}
+
+/// Shows a mod description parsed by [`crate::markdown::parse`].
+fn markdown_view(blocks: &[Block]) -> Element<'_> {
+    Column::with_children(blocks.iter().map(|block| -> Element {
+        match block {
+            Block::Heading(level, text) => widget::text(text)
+                .size(match level {
+                    1 => 24,
+                    2 => 20,
+                    _ => 18,
+                })
+                .into(),
+            Block::Paragraph(text) => widget::text(text).size(text_size(14)).into(),
+            Block::ListItem(text) => widget::text(format!("• {text}")).size(text_size(14)).into(),
+            Block::Quote(text) => widget::text(format!("│ {text}")).size(text_size(14)).into(),
+            Block::Code(text) => widget::text(text)
+                .size(text_size(13))
+                .font(iced::Font::MONOSPACE)
+                .into(),
+            Block::Rule => widget::horizontal_rule(1).into(),
+        }
+    }))
+    .spacing(8)
+    .into()
+}
+
+impl MenuCreateInstance {
+    pub fn view(&self, config: Option<&LauncherConfig>) -> Element<'_> {
+        let progress_bar = if let Some(progress_number) = self.progress_number {
+            if let Some(progress_text) = &self.progress_text {
+                column![
+                    widget::progress_bar(RangeInclusive::new(0.0, 10.0), progress_number),
+                    widget::text(progress_text),
+                    cancel_button(self.cancel.as_ref(), Message::CreateInstanceCancel),
+                ]
+                .spacing(5)
+            } else {
+                column![]
+            }
+        } else {
+            column![]
+        };
+
+        // Servers don't have mod loaders or assets, but can be Paper or Purpur.
+        let (back, hint, name_placeholder, create_label) = if self.is_server {
+            (
+                Message::ServersScreenOpen,
+                tr!("create-server-hint"),
+                tr!("create-server-name-placeholder"),
+                tr!("create-server-button"),
+            )
+        } else {
+            (
+                Message::LaunchScreenOpen,
+                tr!("create-loader-hint"),
+                tr!("create-name-placeholder"),
+                tr!("create-button"),
+            )
+        };
+        let assets_toggle = if self.is_server {
+            column![
+                widget::text(tr!("create-server-software-hint")),
+                widget::pick_list(
+                    ServerSoftware::ALL,
+                    Some(self.server_software),
+                    Message::ServerCreateSoftwareSelected
+                ),
+            ]
+            .spacing(10)
+        } else {
+            column![
+                widget::text(tr!("create-version-source-hint")),
+                widget::pick_list(
+                    VersionSource::ALL,
+                    Some(self.version_source),
+                    Message::CreateInstanceVersionSourceSelected
+                ),
+                widget::checkbox(
+                    tr!("create-custom-version-toggle"),
+                    self.custom_version_json.is_some()
+                )
+                .on_toggle(Message::CreateInstanceCustomVersionToggle),
+                widget::checkbox(tr!("create-server-pack-toggle"), self.server_pack.is_some())
+                    .on_toggle(Message::CreateInstanceServerPackToggle),
+                widget::text(tr!("create-assets-hint")),
+                widget::checkbox(tr!("create-assets-toggle"), self.download_assets)
+                    .on_toggle(Message::CreateInstanceChangeAssetToggle),
+            ]
+            .spacing(10)
+        };
+
+        let main = widget::scrollable(
+            column![
+                widget::button(
+                    row![icon_manager::back(), widget::text(tr!("back"))]
+                        .spacing(10)
+                        .padding(5)
+                )
+                .on_press(back),
+                self.version_column(hint, config),
+                widget::text_input(&name_placeholder, &self.instance_name)
+                    .on_input(Message::CreateInstanceNameInput),
+                assets_toggle,
+                widget::button(
+                    row![icon_manager::create(), widget::text(create_label)]
+                        .spacing(10)
+                        .padding(5)
+                )
+                .on_press_maybe(
+                    (self.has_version() && !self.instance_name.is_empty() && self.cancel.is_none())
+                        .then(|| Message::CreateInstanceStart)
+                ),
+                progress_bar,
+            ]
+            .spacing(10)
+            .padding(10),
+        )
+        .width(Length::Fill);
+
+        row![main].push_maybe(self.patch_notes_panel()).into()
+    }
+
+    /// What changed in the selected version, if
+    /// Mojang published patch notes for it.
+    fn patch_notes_panel(&self) -> Option<Element<'_>> {
+        if self.custom_version_json.is_some() {
+            return None;
+        }
+        let selected = self.selected_version.as_ref()?;
+
+        let notes: Element = match self.patch_notes.as_ref()? {
+            Err(err) => widget::text(tr!("create-patch-notes-failed", error = err))
+                .size(text_size(14))
+                .into(),
+            Ok(notes) => {
+                let note = notes.iter().find(|note| &note.version == selected)?;
+                column![
+                    widget::text(&note.title).size(18),
+                    widget::text(note.day()).size(text_size(12)),
+                    widget::text(&note.shortText).size(text_size(14)),
+                ]
+                .spacing(5)
+                .into()
+            }
+        };
+
+        Some(
+            widget::scrollable(
+                column![
+                    widget::text(tr!("create-patch-notes-title")).size(20),
+                    notes
+                ]
+                .padding(10)
+                .spacing(10),
+            )
+            .width(300)
+            .into(),
+        )
+    }
+}
+
+impl MenuCreateInstance {
+    fn has_version(&self) -> bool {
+        if let Some(server_pack) = &self.server_pack {
+            return !server_pack.trim().is_empty();
+        }
+        match &self.custom_version_json {
+            Some(custom) => !custom.trim().is_empty(),
+            None => self.selected_version.is_some(),
+        }
+    }
+
+    /// The version list, or where to get the
+    /// custom version JSON from.
+    fn version_column(&self, hint: String, config: Option<&LauncherConfig>) -> Element<'_> {
+        if let Some(custom) = &self.custom_version_json {
+            return column![
+                widget::text(tr!("create-custom-version-hint")),
+                widget::text_input(&tr!("create-custom-version-placeholder"), custom)
+                    .on_input(Message::CreateInstanceCustomVersionInput),
+            ]
+            .spacing(10)
+            .into();
+        }
+
+        let server_pack = self.server_pack.as_ref().map(|server_pack| {
+            column![
+                widget::text(tr!("create-server-pack-hint")),
+                widget::text_input(&tr!("create-server-pack-placeholder"), server_pack)
+                    .on_input(Message::CreateInstanceServerPackInput),
+            ]
+            .spacing(10)
+        });
+
+        column![]
+            .push_maybe(self.template_picker())
+            .push_maybe(server_pack)
+            .push(widget::text(hint))
+            .push(widget::text(match &self.selected_version {
+                Some(version) => tr!("create-selected-version", version = version),
+                None => tr!("create-select-version"),
+            }))
+            .push(self.version_picker(config))
+            .spacing(10)
+            .into()
+    }
+
+    /// The saved templates to create the instance from, if there are any.
+    fn template_picker(&self) -> Option<Element<'_>> {
+        if self.is_server || self.server_pack.is_some() || self.templates.is_empty() {
+            return None;
+        }
+        let choices: Vec<TemplateChoice> = std::iter::once(TemplateChoice(None))
+            .chain(
+                self.templates
+                    .iter()
+                    .map(|template| TemplateChoice(Some(template.name.clone()))),
+            )
+            .collect();
+        let selected = TemplateChoice(self.template.as_ref().map(|n| n.name.clone()));
+
+        Some(
+            column![
+                widget::text(tr!("create-template-hint")),
+                row![
+                    widget::pick_list(
+                        choices,
+                        Some(selected),
+                        Message::CreateInstanceTemplateSelected
+                    ),
+                    widget::button(widget::text(tr!("create-template-delete"))).on_press_maybe(
+                        self.template
+                            .is_some()
+                            .then_some(Message::CreateInstanceTemplateDelete)
+                    ),
+                ]
+                .spacing(10),
+            ]
+            .push_maybe(self.template.as_ref().map(|template| {
+                widget::text(tr!(
+                    "create-template-summary",
+                    loader = template.loader.clone(),
+                    version = template.minecraft_version.clone(),
+                    count = template.mods.len()
+                ))
+                .size(text_size(14))
+            }))
+            .spacing(10)
+            .into(),
+        )
+    }
+
+    /// A filterable list of versions, grouped by type.
+    fn version_picker(&self, config: Option<&LauncherConfig>) -> Element<'_> {
+        let is_loading_software_versions = self.is_server
+            && self.server_software != ServerSoftware::Vanilla
+            && self.server_software_versions.is_none();
+        if self.versions.is_empty() || is_loading_software_versions {
+            return widget::text(tr!("create-loading-versions")).into();
+        }
+
+        let is_shown =
+            |version_type| config.is_none_or(|config| config.shows_version_type(version_type));
+        // A column, as the launcher window is narrow by default.
+        let toggles =
+            Column::with_children(VersionType::ALL[1..].iter().copied().map(|version_type| {
+                widget::checkbox(version_type_name(version_type), is_shown(version_type))
+                    .on_toggle(move |show| {
+                        Message::CreateInstanceVersionTypeShow(version_type, show)
+                    })
+                    .into()
+            }))
+            .spacing(5);
+
+        let filter = self.version_filter.trim().to_lowercase();
+        let matches = |version: &&ListedVersion| {
+            is_shown(version.version_type)
+                && version.id.to_lowercase().contains(&filter)
+                && self
+                    .server_software_versions
+                    .as_ref()
+                    .is_none_or(|versions| versions.contains(&version.id))
+        };
+
+        let mut search = widget::text_input(&tr!("create-version-filter"), &self.version_filter)
+            .on_input(Message::CreateInstanceVersionFilterInput);
+        // Pressing enter picks the best match, so typing
+        // "1.16.5" and enter is all it takes.
+        if let Some(first) = self.versions.iter().find(matches) {
+            if !filter.is_empty() {
+                search = search.on_submit(Message::CreateInstanceVersionSelected(first.id.clone()));
+            }
+        }
+
+        let is_favorite = |version: &ListedVersion| {
+            config.is_some_and(|config| config.favorite_versions.contains(&version.id))
+        };
+        let version_button = |version: &ListedVersion| -> Element {
+            let is_selected = self.selected_version.as_ref() == Some(&version.id);
+            let label: Element = if is_selected {
+                row![icon_manager::play(), widget::text(&version.id)]
+                    .spacing(5)
+                    .into()
+            } else {
+                widget::text(&version.id).into()
+            };
+            row![
+                widget::button(label)
+                    .width(Length::Fill)
+                    .on_press(Message::CreateInstanceVersionSelected(version.id.clone())),
+                widget::button(if is_favorite(version) { "★" } else { "☆" }).on_press(
+                    Message::CreateInstanceVersionFavoriteToggle(version.id.clone())
+                ),
+            ]
+            .spacing(5)
+            .into()
+        };
+
+        // Favorites are shown even if their type is hidden.
+        let favorites: Vec<&ListedVersion> = self
+            .versions
+            .iter()
+            .filter(|version| is_favorite(version))
+            .filter(|version| {
+                version.id.to_lowercase().contains(&filter)
+                    && self
+                        .server_software_versions
+                        .as_ref()
+                        .is_none_or(|versions| versions.contains(&version.id))
+            })
+            .collect();
+        let favorites = (!favorites.is_empty()).then(|| -> Element {
+            column![
+                widget::text(format!(
+                    "★ {} ({})",
+                    tr!("create-version-favorites"),
+                    favorites.len()
+                )),
+                Column::with_children(favorites.into_iter().map(version_button))
+                    .spacing(2)
+                    .padding([0, 0, 0, 15]),
+            ]
+            .spacing(5)
+            .into()
+        });
+
+        let groups = VersionType::ALL.into_iter().filter_map(|version_type| {
+            let versions: Vec<&ListedVersion> = self
+                .versions
+                .iter()
+                .filter(|version| version.version_type == version_type)
+                .filter(matches)
+                .collect();
+            if versions.is_empty() {
+                return None;
+            }
+
+            // While searching, every match is shown, even in folded groups.
+            let expanded = !filter.is_empty() || !self.collapsed_groups.contains(&version_type);
+            let header = widget::button(widget::text(format!(
+                "{} {} ({})",
+                if expanded { "▼" } else { "▶" },
+                version_type_name(version_type),
+                versions.len()
+            )))
+            .width(Length::Fill)
+            .on_press(Message::CreateInstanceVersionGroupToggle(version_type));
+
+            let mut group = column![header].spacing(5);
+            if expanded {
+                group = group.push(
+                    Column::with_children(versions.into_iter().map(version_button))
+                        .spacing(2)
+                        .padding([0, 0, 0, 15]),
+                );
+            }
+            Some(group.into())
+        });
+
+        let groups = Column::with_children(favorites.into_iter().chain(groups))
+            .spacing(5)
+            .padding([0, 15, 0, 0]);
+        column![toggles, search, widget::scrollable(groups).height(300)]
+            .spacing(10)
+            .into()
+    }
+}
+
+fn version_type_name(version_type: VersionType) -> String {
+    match version_type {
+        VersionType::Release => tr!("version-type-release"),
+        VersionType::Snapshot => tr!("version-type-snapshot"),
+        VersionType::Experimental => tr!("version-type-experimental"),
+        VersionType::Beta => tr!("version-type-beta"),
+        VersionType::Alpha => tr!("version-type-alpha"),
+    }
+}
+
+impl MenuConfirm {
+    pub fn view(&self) -> Element<'_> {
+        let input = self.action.required_input().map(|required| {
+            column![
+                widget::text(tr!("confirm-type-name", name = required)),
+                widget::text_input(required, &self.input)
+                    .on_input(Message::ConfirmInput)
+                    .on_submit(Message::ConfirmAccept),
+            ]
+            .spacing(5)
+        });
+
+        column![
+            widget::text(self.action.title()).size(20),
+            widget::text(self.action.warning()),
+        ]
+        .push_maybe(input)
+        .push(
+            row![widget::button(widget::text(self.action.confirm_label()))
+                .on_press_maybe(self.is_confirmed().then_some(Message::ConfirmAccept)),]
+            .push_maybe(
+                self.action
+                    .alternative()
+                    .map(|(label, message)| widget::button(widget::text(label)).on_press(message)),
+            )
+            .push(widget::button(widget::text(tr!("no"))).on_press(Message::ConfirmCancel))
+            .spacing(10),
+        )
+        .padding(10)
+        .spacing(10)
+        .into()
+    }
+}
+
+impl MenuInstallFabric {
+    pub fn view(&self) -> Element<'_> {
+        column![
+            widget::button(
+                row![icon_manager::back(), widget::text(tr!("back"))]
+                    .spacing(10)
+                    .padding(5)
+            )
+            .on_press(Message::LaunchScreenOpen),
+            widget::text(tr!(
+                "fabric-select-version",
+                instance = self.selected_instance
             )),
             widget::pick_list(
                 self.fabric_versions.as_slice(),
                 self.fabric_version.as_ref(),
                 Message::InstallFabricVersionSelected
             ),
-            widget::button("Install Fabric").on_press_maybe(
-                self.fabric_version
-                    .is_some()
+            widget::button(widget::text(tr!("mods-install-fabric"))).on_press_maybe(
+                (self.fabric_version.is_some() && self.progress.is_none())
                     .then(|| Message::InstallFabricClicked)
             ),
         ]
+        .push_maybe(
+            self.versions_cached
+                .then(|| widget::text(tr!("fabric-versions-cached")).size(text_size(14))),
+        )
+        .push(match &self.progress {
+            Some(progress) => column![
+                widget::progress_bar(0.0..=1.0, progress.num),
+                widget::text(&progress.message),
+            ],
+            None => column![],
+        })
         .padding(10)
         .spacing(20)
         .into()
     }
 }
+
+impl MenuLauncherSettings {
+    fn view_offline_profiles<'element>(
+        &'element self,
+        config: &'element LauncherConfig,
+        skins: &HashMap<SkinSource, Option<widget::image::Handle>>,
+    ) -> Element<'element> {
+        let mut profiles = column![].spacing(5);
+        for profile in &config.offline_profiles {
+            let details = column![
+                widget::text(tr!(
+                    "settings-offline-profile",
+                    name = profile.name.clone(),
+                    username = profile.username.clone()
+                )),
+                widget::text(&profile.uuid)
+                    .font(iced::Font::MONOSPACE)
+                    .size(text_size(12)),
+            ]
+            .width(Length::Fill);
+            let delete = widget::button(widget::text(tr!("settings-offline-profile-delete")))
+                .on_press(Message::LauncherSettingsProfileDelete(profile.name.clone()));
+            profiles = profiles.push(
+                row![]
+                    .push_maybe(skin_face(skins, Some(&profile.skin_source()), 32))
+                    .push(details)
+                    .push(delete)
+                    .spacing(10)
+                    .align_items(iced::Alignment::Center),
+            );
+        }
+
+        let username = self.profile_username_input.trim();
+        let can_add = !self.profile_name_input.trim().is_empty()
+            && check_username(username).map_or_else(
+                |problem| !problem.is_fatal() && config.allow_invalid_username,
+                |()| true,
+            );
+
+        column![
+            widget::text(tr!("settings-offline-profiles")).size(16),
+            widget::text(tr!("settings-offline-profiles-hint")).size(text_size(14)),
+            profiles,
+            widget::text_input(
+                &tr!("settings-offline-profile-name"),
+                &self.profile_name_input
+            )
+            .on_input(Message::LauncherSettingsProfileNameInput),
+            widget::text_input(
+                &tr!("launch-username-placeholder"),
+                &self.profile_username_input
+            )
+            .on_input(Message::LauncherSettingsProfileUsernameInput),
+        ]
+        .push_maybe(if username.is_empty() {
+            None
+        } else {
+            username_feedback(username, config.allow_invalid_username)
+        })
+        .push(
+            widget::text_input(
+                &tr!("settings-offline-profile-skin"),
+                &self.profile_skin_input,
+            )
+            .on_input(Message::LauncherSettingsProfileSkinInput),
+        )
+        .push(
+            widget::button(widget::text(tr!("settings-offline-profile-add")))
+                .on_press_maybe(can_add.then_some(Message::LauncherSettingsProfileAdd)),
+        )
+        .spacing(10)
+        .into()
+    }
+
+    fn view_data_dir(&self) -> Element<'_> {
+        let mut data = column![
+            widget::text(tr!("settings-data")).size(20),
+            widget::text(tr!("settings-data-dir")),
+            widget::text(&self.data_dir).font(iced::Font::MONOSPACE),
+            widget::text(tr!("settings-cache-dir")),
+            widget::text(&self.cache_dir).font(iced::Font::MONOSPACE),
+        ]
+        .spacing(10);
+        if file_utils::is_portable() {
+            data = data.push(widget::text(tr!("settings-portable")).size(text_size(14)));
+        }
+
+        data = if file_utils::get_data_dir_from_env().is_some() {
+            data.push(widget::text(tr!(
+                "settings-data-dir-env",
+                variable = file_utils::DATA_DIR_ENV
+            )))
+        } else {
+            let can_move = !self.moving_data_dir
+                && !self.data_dir_input.trim().is_empty()
+                && self.data_dir_input.trim() != self.data_dir;
+            data.push(
+                row![
+                    widget::text_input("", &self.data_dir_input)
+                        .on_input(Message::LauncherSettingsDataDirInput)
+                        .on_submit(Message::LauncherSettingsDataDirMove),
+                    widget::button(widget::text(tr!("settings-data-dir-move")))
+                        .on_press_maybe(can_move.then_some(Message::LauncherSettingsDataDirMove)),
+                ]
+                .spacing(10),
+            )
+            .push(
+                widget::text(if self.moving_data_dir {
+                    tr!("settings-data-dir-moving")
+                } else {
+                    tr!(
+                        "settings-data-dir-hint",
+                        variable = file_utils::DATA_DIR_ENV
+                    )
+                })
+                .size(text_size(14)),
+            )
+        };
+
+        widget::container(data.padding(10)).into()
+    }
+
+    fn view_backup(&self) -> Element<'_> {
+        let backups_dir = launcher_backup::get_backups_dir().unwrap_or_default();
+        let can_restore = !self.restoring && !self.restore_input.trim().is_empty();
+
+        widget::container(
+            column![
+                widget::text(tr!("settings-backup")).size(20),
+                widget::text(tr!("settings-backup-hint")).size(text_size(14)),
+                widget::checkbox(
+                    tr!("settings-backup-include-saves"),
+                    self.backup_include_saves
+                )
+                .on_toggle(Message::LauncherSettingsBackupSavesToggle),
+                row![
+                    widget::button(widget::text(if self.backing_up {
+                        tr!("settings-backup-running")
+                    } else {
+                        tr!("settings-backup-create")
+                    }))
+                    .on_press_maybe((!self.backing_up).then_some(Message::LauncherSettingsBackup)),
+                    button_with_icon(icon_manager::folder(), tr!("settings-backup-open"))
+                        .on_press_maybe(
+                            backups_dir
+                                .is_dir()
+                                .then(|| Message::OpenDir(backups_dir.clone()))
+                        ),
+                ]
+                .spacing(10),
+                widget::text(tr!("settings-backup-restore-path")),
+                row![
+                    widget::text_input("", &self.restore_input)
+                        .on_input(Message::LauncherSettingsRestoreInput)
+                        .on_submit(Message::LauncherSettingsRestore),
+                    widget::button(widget::text(if self.restoring {
+                        tr!("settings-backup-restoring")
+                    } else {
+                        tr!("settings-backup-restore")
+                    }))
+                    .on_press_maybe(can_restore.then_some(Message::LauncherSettingsRestore)),
+                ]
+                .spacing(10),
+            ]
+            .padding(10)
+            .spacing(10),
+        )
+        .into()
+    }
+
+    pub fn view<'element>(
+        &'element self,
+        config: Option<&'element LauncherConfig>,
+        instances: Option<&'element [InstanceInfo]>,
+        skins: &HashMap<SkinSource, Option<widget::image::Handle>>,
+    ) -> Element<'element> {
+        let Some(config) = config else {
+            return column![widget::text(tr!("settings-loading"))]
+                .padding(10)
+                .into();
+        };
+
+        let options_sources: Vec<OptionsSourceChoice> = std::iter::once(None)
+            .chain(
+                instances
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|instance| Some(instance.name.clone())),
+            )
+            .map(OptionsSourceChoice)
+            .collect();
+
+        let ui_scale = self.ui_scale.unwrap_or(config.get_ui_scale());
+
+        let java_installs =
+            Column::with_children(config.java_installs.iter().enumerate().map(|(i, path)| {
+                row![
+                    widget::button(icon_manager::delete())
+                        .on_press(Message::LauncherSettingsJavaInstallRemove(i)),
+                    widget::text(path),
+                ]
+                .spacing(10)
+                .into()
+            }))
+            .spacing(5);
+
+        widget::scrollable(
+            column![
+                button_with_icon(icon_manager::back(), tr!("back"))
+                    .on_press(Message::LaunchScreenOpen),
+                widget::container(
+                    column![
+                        widget::text(tr!("settings-appearance")).size(20),
+                        widget::text(tr!("settings-theme")),
+                        widget::pick_list(
+                            ThemeChoice::ALL,
+                            Some(config.theme),
+                            Message::LauncherSettingsThemeSelected
+                        ),
+                        widget::text(tr!("settings-accent")),
+                        widget::pick_list(
+                            AccentColor::ALL,
+                            Some(config.accent_color),
+                            Message::LauncherSettingsAccentSelected
+                        ),
+                        widget::text(tr!("settings-style")),
+                        widget::pick_list(
+                            StylePreset::ALL,
+                            Some(config.style_preset),
+                            Message::LauncherSettingsStylePresetSelected
+                        ),
+                        widget::text(tr!("settings-language")),
+                        widget::pick_list(
+                            Language::ALL,
+                            Some(config.language),
+                            Message::LauncherSettingsLanguageSelected
+                        ),
+                        widget::checkbox(tr!("settings-show-news"), config.show_news)
+                            .on_toggle(Message::LauncherSettingsShowNewsToggle),
+                        widget::checkbox(tr!("settings-check-updates"), config.check_for_updates)
+                            .on_toggle(Message::LauncherSettingsCheckUpdatesToggle),
+                        widget::text(tr!("settings-instance-view")),
+                        widget::pick_list(
+                            InstanceView::ALL,
+                            Some(config.instance_view),
+                            Message::LauncherSettingsInstanceViewSelected
+                        ),
+                        widget::text(tr!(
+                            "settings-ui-scale",
+                            percent = (ui_scale * 100.0).round()
+                        )),
+                        widget::slider(
+                            UI_SCALE_RANGE,
+                            ui_scale,
+                            Message::LauncherSettingsUiScaleChanged
+                        )
+                        .step(0.05)
+                        .on_release(Message::LauncherSettingsUiScaleApply),
+                    ]
+                    .padding(10)
+                    .spacing(10)
+                ),
+                widget::container(
+                    column![
+                        widget::text(tr!("settings-accessibility")).size(20),
+                        widget::checkbox(
+                            tr!("settings-high-contrast"),
+                            config.accessibility.high_contrast
+                        )
+                        .on_toggle(Message::LauncherSettingsHighContrastToggle),
+                        widget::checkbox(
+                            tr!("settings-large-text"),
+                            config.accessibility.large_text
+                        )
+                        .on_toggle(Message::LauncherSettingsLargeTextToggle),
+                        widget::text(tr!("settings-large-text-hint")).size(text_size(14)),
+                        widget::checkbox(
+                            tr!("settings-reduce-motion"),
+                            config.accessibility.reduce_motion
+                        )
+                        .on_toggle(Message::LauncherSettingsReduceMotionToggle),
+                        widget::text(tr!("settings-reduce-motion-hint")).size(text_size(14)),
+                    ]
+                    .padding(10)
+                    .spacing(10)
+                ),
+                widget::container(
+                    column![
+                        widget::text(tr!("settings-account")).size(20),
+                        widget::text(tr!("settings-default-username")),
+                        widget::text_input(&tr!("launch-username-placeholder"), &config.username)
+                            .on_input(Message::LauncherSettingsUsernameSet),
+                    ]
+                    .push_maybe(username_feedback(
+                        &config.username,
+                        config.allow_invalid_username
+                    ))
+                    .push_maybe(skin_face(skins, config.skin_source(None).as_ref(), 48))
+                    .push(self.view_offline_profiles(config, skins))
+                    .padding(10)
+                    .spacing(10)
+                ),
+                widget::container(
+                    column![
+                        widget::text(tr!("settings-java")).size(20),
+                        widget::text(tr!("settings-java-installs")),
+                        java_installs,
+                        row![
+                            widget::text_input(
+                                &tr!("settings-java-install-placeholder"),
+                                &self.java_install_input
+                            )
+                            .on_input(Message::LauncherSettingsJavaInstallInput)
+                            .on_submit(Message::LauncherSettingsJavaInstallAdd),
+                            widget::button(widget::text(tr!("add")))
+                                .on_press(Message::LauncherSettingsJavaInstallAdd),
+                        ]
+                        .spacing(10),
+                        widget::button(widget::text(tr!("settings-java-cleanup")))
+                            .on_press(Message::LauncherSettingsJavaCleanup),
+                        widget::text(self.java_cleanup_status.as_deref().unwrap_or_default()),
+                        widget::text(tr!("settings-java-args")),
+                        widget::text_input("-Dfile.encoding=UTF-8", &self.java_args_input)
+                            .on_input(Message::LauncherSettingsJavaArgsInput),
+                    ]
+                    .padding(10)
+                    .spacing(10)
+                ),
+                widget::container(
+                    column![
+                        widget::text(tr!("settings-downloads")).size(20),
+                        widget::text(tr!(
+                            "settings-parallel-downloads",
+                            count = config.parallel_downloads
+                        )),
+                        widget::slider(
+                            1.0..=32.0,
+                            config.parallel_downloads as f32,
+                            Message::LauncherSettingsParallelDownloadsChanged
+                        ),
+                        widget::text(tr!("settings-mirror")),
+                        widget::text_input(
+                            "https://example.com",
+                            config.download_mirror.as_deref().unwrap_or_default()
+                        )
+                        .on_input(Message::LauncherSettingsMirrorInput),
+                        widget::text(tr!("settings-proxy")),
+                        widget::text_input(
+                            "http://127.0.0.1:8080",
+                            config.proxy.as_deref().unwrap_or_default()
+                        )
+                        .on_input(Message::LauncherSettingsProxyInput),
+                    ]
+                    .padding(10)
+                    .spacing(10)
+                ),
+                self.view_data_dir(),
+                self.view_backup(),
+                widget::container(
+                    column![
+                        widget::text(tr!("settings-behavior")).size(20),
+                        widget::checkbox(tr!("settings-close-on-launch"), config.close_on_launch)
+                            .on_toggle(Message::LauncherSettingsCloseOnLaunchToggle),
+                        widget::text(tr!("settings-options-source")),
+                        widget::pick_list(
+                            options_sources,
+                            Some(OptionsSourceChoice(config.options_source.clone())),
+                            Message::LauncherSettingsOptionsSourceSelected
+                        ),
+                        widget::text(tr!("settings-options-source-hint")).size(text_size(14)),
+                        widget::checkbox(tr!("settings-tray-icon"), config.tray_icon)
+                            .on_toggle_maybe(
+                                tray::SUPPORTED.then_some(Message::LauncherSettingsTrayIconToggle)
+                            ),
+                        widget::text(if tray::SUPPORTED {
+                            tr!("settings-tray-icon-hint")
+                        } else {
+                            tr!("settings-tray-icon-unavailable")
+                        })
+                        .size(text_size(14)),
+                        widget::checkbox(tr!("settings-discord-rpc"), config.discord_rpc)
+                            .on_toggle_maybe(
+                                discord_rpc::CLIENT_ID
+                                    .is_some()
+                                    .then_some(Message::LauncherSettingsDiscordRpcToggle)
+                            ),
+                        widget::text(if discord_rpc::CLIENT_ID.is_some() {
+                            tr!("settings-discord-rpc-hint")
+                        } else {
+                            tr!("settings-discord-rpc-unavailable")
+                        })
+                        .size(text_size(14)),
+                    ]
+                    .padding(10)
+                    .spacing(10)
+                ),
+                widget::container(
+                    column![
+                        widget::text(tr!("settings-debug")).size(20),
+                        widget::checkbox(tr!("settings-verbose-logging"), config.verbose_logging)
+                            .on_toggle(Message::LauncherSettingsVerboseLoggingToggle),
+                        widget::text(tr!("settings-verbose-logging-hint")).size(text_size(14)),
+                        widget::button(widget::text(tr!("settings-launcher-logs")))
+                            .on_press(Message::LauncherLogsOpen),
+                    ]
+                    .padding(10)
+                    .spacing(10)
+                ),
+            ]
+            .padding(10)
+            .spacing(20),
+        )
+        .into()
+    }
+}
@@ -1,230 +1,3974 @@
-use std::sync::{mpsc, Arc};
+use std::{
+    collections::HashMap,
+    future::Future,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
-use iced::Command;
+use iced::{widget, Command};
 use quantum_launcher_backend::{
-    error::LauncherResult, file_utils, io_err,
-    json_structs::json_instance_config::InstanceConfigJson, DownloadProgress, GameLaunchResult,
+    cancel::CancelToken,
+    check_username,
+    discord_rpc::{Activity, DiscordRpc},
+    download_queue,
+    error::{HasErrorCode, LauncherError, LauncherResult},
+    events::{self, Event},
+    file_utils,
+    game_options::{self, GameOptions},
+    instance_mod_installer::{
+        self,
+        mod_list::{self, ImportResult, ModListFormat},
+        modrinth::{self, InstalledMod, ModInstallProgress},
+        modrinth_search::{self, ProjectDetails, SearchPage},
+        plugins,
+        server_pack::{self, ServerPackImport},
+    },
+    integrity::{self, Check},
+    io_err,
+    java_check::{self, JavaProblem},
+    join_arguments,
+    json_structs::{
+        json_instance_config::InstanceConfigJson, json_manifest::VersionSource, json_news,
+        json_patch_notes, validation,
+    },
+    launcher_backup, log_upload,
+    offline_profiles::OfflineProfile,
+    playtime::{PlaytimeStats, Session},
+    progress,
+    self_update::{self, Release, UpdateOutcome},
+    server_files::{self, ServerProperties},
+    server_ping::{self, ServerStatus},
+    server_schedule::{self, ServerSchedule},
+    server_software::{self, ServerSoftware},
+    skins::{self, Face, SkinSource},
+    snapshots, split_arguments, sync_folder,
+    templates::{self, InstanceTemplate},
+    world_backup, worlds, DownloadProgress, FabricInstallProgress, GameLaunchResult, LaunchOptions,
+    ListedVersion, VersionType,
 };
+use tracing::{error, info};
 
-use crate::launcher_state::{
-    JavaInstallProgress, Launcher, MenuCreateInstance, MenuDeleteInstance, MenuEditInstance,
-    MenuEditMods, Message, State,
+use crate::{
+    config::LauncherConfig,
+    launcher_state::{
+        DestructiveAction, FabricProgress, FolderChanges, GameProcess, InstanceRegistry,
+        JavaInstallProgress, LaunchOverrides, Launcher, MenuConfirm, MenuCreateInstance,
+        MenuDownloadQueue, MenuEditInstance, MenuEditMods, MenuGameLog, MenuGameOptions,
+        MenuInstallFabric, MenuLaunch, MenuLaunchWithOptions, MenuLauncherSettings, MenuModBrowser,
+        MenuServerConsole, MenuServerEula, MenuServerPlugins, MenuServerProperties,
+        MenuServerSchedule, MenuServers, MenuStats, MenuUpdate, MenuVerify, MenuWorlds, Message,
+        PluginProgress, SelectedMod, ServerStatusEntry, State, TemplateChoice, ToastKind,
+        WelcomeStep,
+    },
+    markdown,
+    search::{SearchPalette, SearchTarget},
+    shortcuts::Shortcut,
+    single_instance::InstanceGuard,
+    tr,
+    tray::{Tray, TrayAction, TrayMenu},
 };
 
 impl Launcher {
     pub fn select_launch_instance(&mut self, instance_name: String) {
         if let State::Launch(ref mut menu_launch) = self.state {
-            menu_launch.selected_instance = Some(instance_name)
+            menu_launch.selected_instance = Some(instance_name);
+            menu_launch.overrides = LaunchOverrides::default();
+        }
+    }
+
+    pub fn launch_recent(&mut self, instance_name: String) -> Command<Message> {
+        let last_profile = self
+            .instances
+            .as_ref()
+            .and_then(|instances| instances.get(&instance_name))
+            .and_then(|info| info.last_profile.clone());
+        self.select_launch_instance(instance_name);
+        if let State::Launch(menu_launch) = &mut self.state {
+            menu_launch.overrides.profile = last_profile;
         }
+        self.launch_game()
+    }
+
+    pub fn go_to_launch_with_options(&mut self) {
+        if let State::Launch(MenuLaunch {
+            selected_instance: Some(instance),
+            ..
+        }) = &self.state
+        {
+            self.state = State::LaunchWithOptions(MenuLaunchWithOptions {
+                selected_instance: instance.clone(),
+                username: String::new(),
+                java_args_input: String::new(),
+                game_args_input: String::new(),
+                server: String::new(),
+            });
+        }
+    }
+
+    /// Launches the instance with what was typed
+    /// into [`MenuLaunchWithOptions`], this time only.
+    pub fn launch_with_options(&mut self) -> Command<Message> {
+        let State::LaunchWithOptions(menu) = &self.state else {
+            return Command::none();
+        };
+        let overrides = LaunchOverrides {
+            username: non_empty(menu.username.trim().to_owned()),
+            profile: None,
+            java_args: split_arguments(&menu.java_args_input),
+            game_args: split_arguments(&menu.game_args_input),
+            server: non_empty(menu.server.trim().to_owned()),
+        };
+        self.state = State::Launch(MenuLaunch {
+            selected_instance: Some(menu.selected_instance.clone()),
+            overrides,
+            ..Default::default()
+        });
+        self.launch_game()
     }
 
     pub fn set_username(&mut self, username: String) {
         self.config.as_mut().unwrap().username = username;
     }
 
+    /// Sets who to launch `instance_name` as, if the launch doesn't
+    /// override the username: `profile` (if it still exists), the
+    /// instance's own offline profile, then the one picked on the
+    /// launch screen, then the typed username.
+    fn launch_account(
+        options: LaunchOptions,
+        config: &LauncherConfig,
+        instances: Option<&InstanceRegistry>,
+        instance_name: &str,
+        profile: Option<&str>,
+    ) -> LaunchOptions {
+        let profile = profile
+            .and_then(|name| {
+                config
+                    .offline_profiles
+                    .iter()
+                    .find(|profile| profile.name == name)
+            })
+            .or_else(|| {
+                config.launch_profile(instances.and_then(|instances| instances.get(instance_name)))
+            });
+        match profile {
+            Some(profile) => options.offline_profile(profile),
+            None => options.username(config.username.clone()),
+        }
+    }
+
+    /// Loads the skins shown on the current screen,
+    /// unless they're loaded already.
+    pub fn load_skins(&mut self) -> Command<Message> {
+        let Some(config) = &self.config else {
+            return Command::none();
+        };
+        let sources: Vec<SkinSource> = match &self.state {
+            State::Launch(menu) => {
+                let instance = menu
+                    .selected_instance
+                    .as_ref()
+                    .zip(self.instances.as_ref())
+                    .and_then(|(name, instances)| instances.get(name));
+                config.skin_source(instance).into_iter().collect()
+            }
+            State::LauncherSettings(_) => config
+                .offline_profiles
+                .iter()
+                .map(OfflineProfile::skin_source)
+                .chain(config.skin_source(None))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let mut commands = Vec::new();
+        for source in sources {
+            if self.skins.contains_key(&source) {
+                continue;
+            }
+            self.skins.insert(source.clone(), None);
+            commands.push(Command::perform(
+                skins::load_face_wrapped(source.clone()),
+                move |result| Message::SkinLoaded(source.clone(), result),
+            ));
+        }
+        Command::batch(commands)
+    }
+
+    pub fn add_skin(&mut self, source: SkinSource, result: Result<Option<Face>, String>) {
+        match result {
+            Ok(Some(face)) => {
+                let handle = widget::image::Handle::from_pixels(face.size, face.size, face.pixels);
+                self.skins.insert(source, Some(handle));
+            }
+            Ok(None) => {}
+            Err(err) => error!("Could not load skin {source:?}: {err}"),
+        }
+    }
+
+    pub fn add_offline_profile(&mut self) {
+        let State::LauncherSettings(menu) = &mut self.state else {
+            return;
+        };
+        let name = menu.profile_name_input.trim().to_owned();
+        let username = menu.profile_username_input.trim().to_owned();
+        let skin = non_empty(menu.profile_skin_input.trim().to_owned()).map(PathBuf::from);
+        let allow_invalid_username = self
+            .config
+            .as_ref()
+            .is_some_and(|config| config.allow_invalid_username);
+        let username_allowed = check_username(&username).map_or_else(
+            |problem| !problem.is_fatal() && allow_invalid_username,
+            |()| true,
+        );
+        if name.is_empty() || !username_allowed {
+            return;
+        }
+        if let Some(Err(err)) = skin.as_deref().map(skins::check_skin_file) {
+            self.notify(
+                ToastKind::Error,
+                tr!("toast-profile-skin-invalid", error = err.to_string()),
+            );
+            return;
+        }
+        if self
+            .config
+            .as_ref()
+            .is_some_and(|config| config.offline_profiles.iter().any(|p| p.name == name))
+        {
+            self.notify(ToastKind::Error, tr!("toast-profile-exists", name = name));
+            return;
+        }
+
+        menu.profile_name_input.clear();
+        menu.profile_username_input.clear();
+        menu.profile_skin_input.clear();
+        let profile = OfflineProfile::new(name.clone(), username, skin);
+        self.edit_config(|config| config.offline_profiles.push(profile));
+        self.notify(ToastKind::Success, tr!("toast-profile-added", name = name));
+    }
+
+    /// Launches the selected instance, asking first if it's
+    /// already running, since two copies writing to the
+    /// same worlds corrupt them.
     pub fn launch_game(&mut self) -> Command<Message> {
+        if let State::Launch(MenuLaunch {
+            selected_instance: Some(instance),
+            ..
+        }) = &self.state
+        {
+            if self.is_instance_running(instance) {
+                self.state = State::Confirm(MenuConfirm {
+                    action: DestructiveAction::LaunchAgain {
+                        instance: instance.clone(),
+                    },
+                    input: String::new(),
+                });
+                return Command::none();
+            }
+            let instance = instance.clone();
+            return Command::perform(java_check::check_wrapped(instance.clone()), move |result| {
+                Message::LaunchJavaChecked(instance.clone(), result)
+            });
+        }
+        Command::none()
+    }
+
+    /// Launches the instance, unless its Java won't run it,
+    /// in which case the user is asked first.
+    pub fn finish_checking_java(
+        &mut self,
+        instance: String,
+        result: Result<Option<JavaProblem>, String>,
+    ) -> Command<Message> {
+        // The user may have picked another instance meanwhile.
+        let State::Launch(MenuLaunch {
+            selected_instance: Some(selected),
+            ..
+        }) = &self.state
+        else {
+            return Command::none();
+        };
+        if *selected != instance {
+            return Command::none();
+        }
+        match result {
+            Ok(Some(problem)) => {
+                self.state = State::Confirm(MenuConfirm {
+                    action: DestructiveAction::IncompatibleJava { instance, problem },
+                    input: String::new(),
+                });
+                Command::none()
+            }
+            Ok(None) => self.start_game(std::convert::identity),
+            // Launching gives a better error, if it fails too.
+            Err(err) => {
+                error!("Could not check the Java of {instance}: {err}");
+                self.start_game(std::convert::identity)
+            }
+        }
+    }
+
+    /// Switches the instance from the Java it was set
+    /// to, to one the launcher installs, and launches it.
+    pub fn launch_with_managed_java(
+        &mut self,
+        instance: String,
+        major_version: usize,
+    ) -> Command<Message> {
+        self.go_to_launch_screen();
+        self.select_launch_instance(instance.clone());
+        if let Err(err) = Launcher::use_managed_java(&instance, major_version) {
+            self.set_error(err.to_string_with_code());
+            return Command::none();
+        }
+        self.start_game(std::convert::identity)
+    }
+
+    /// Launches the selected instance with only its mod loader,
+    /// putting the mods back once it exits.
+    pub fn launch_game_without_mods(&mut self) -> Command<Message> {
+        if let State::Launch(MenuLaunch {
+            selected_instance: Some(instance),
+            ..
+        }) = &self.state
+        {
+            // Its mods folder is in use.
+            if self.is_instance_running(instance) {
+                self.notify(
+                    ToastKind::Error,
+                    tr!("toast-launch-without-mods-running", instance = instance),
+                );
+                return Command::none();
+            }
+        }
+        self.start_game(LaunchOptions::without_mods)
+    }
+
+    /// `options` adds to how the game is launched, like
+    /// [`LaunchOptions::separate_game_dir`].
+    fn start_game(&mut self, options: fn(LaunchOptions) -> LaunchOptions) -> Command<Message> {
         if let State::Launch(ref mut menu_launch) = self.state {
             match self.config.as_ref().unwrap().save() {
                 Ok(_) => {
                     let selected_instance = menu_launch.selected_instance.clone().unwrap();
-                    let username = self.config.as_ref().unwrap().username.clone();
+                    let overrides = std::mem::take(&mut menu_launch.overrides);
+                    let launch_options = LaunchOptions::new(selected_instance.clone());
+                    let launch_options = match overrides.username {
+                        Some(username) => launch_options.username(username),
+                        None => Self::launch_account(
+                            launch_options,
+                            self.config.as_ref().unwrap(),
+                            self.instances.as_ref(),
+                            &selected_instance,
+                            overrides.profile.as_deref(),
+                        ),
+                    };
+                    let java_args = self.config.as_ref().unwrap().java_args.clone();
+                    let allow_invalid_username =
+                        self.config.as_ref().unwrap().allow_invalid_username;
 
-                    let (sender, receiver) = std::sync::mpsc::channel();
+                    let (sender, receiver) = progress::channel();
+                    let cancel = CancelToken::new();
                     menu_launch.java_install_progress = Some(JavaInstallProgress {
                         num: 0.0,
                         recv: receiver,
-                        message: "Starting up (1/2)".to_owned(),
+                        message: tr!("java-install-starting-1"),
+                        cancel: cancel.clone(),
                     });
 
+                    let mut launch_options = launch_options
+                        .java_args(java_args)
+                        .override_java_args(overrides.java_args)
+                        .game_args(overrides.game_args)
+                        .java_install_progress(sender)
+                        .cancel(cancel);
+                    if let Some(server) = overrides.server {
+                        launch_options = launch_options.join_server(server);
+                    }
+                    if allow_invalid_username {
+                        launch_options = launch_options.allow_invalid_username();
+                    }
+                    let options = options(launch_options);
                     return Command::perform(
-                        quantum_launcher_backend::launch_wrapped(
-                            selected_instance,
-                            username,
-                            Some(sender),
-                        ),
-                        Message::LaunchEnd,
+                        quantum_launcher_backend::launch_wrapped(options),
+                        move |result| Message::LaunchEnd(selected_instance.clone(), result),
                     );
                 }
-                Err(err) => self.set_error(err.to_string()),
+                Err(err) => self.set_error(err.to_string_with_code()),
             };
         }
         Command::none()
     }
 
-    pub fn finish_launching(&mut self, result: GameLaunchResult) {
+    pub fn finish_launching(
+        &mut self,
+        instance_name: String,
+        result: GameLaunchResult,
+    ) -> Command<Message> {
         match result {
-            GameLaunchResult::Ok(child) => self.spawned_process = Some(child),
-            GameLaunchResult::Err(err) => self.set_error(err),
+            GameLaunchResult::Ok(child) => {
+                self.processes
+                    .push(GameProcess::new(instance_name, child, false));
+                self.update_discord_presence();
+                if self.config.as_ref().is_some_and(|n| n.close_on_launch) {
+                    // With a tray icon the launcher can be brought back,
+                    // and keeps track of the game meanwhile.
+                    if self.tray.is_some() {
+                        return iced::window::change_mode(
+                            iced::window::Id::MAIN,
+                            iced::window::Mode::Hidden,
+                        );
+                    }
+                    return Launcher::request_close();
+                }
+            }
+            GameLaunchResult::Err(_) if self.take_cancelled_java_install() => {
+                self.notify(ToastKind::Info, tr!("toast-launch-cancelled"));
+            }
+            GameLaunchResult::Err(err) => self.set_launch_error(err),
         }
+        Command::none()
     }
 
-    pub fn go_to_create_screen(&mut self) -> Command<Message> {
-        const SKIP_LISTING_VERSIONS: bool = false;
+    pub fn open_game_dir(&mut self) {
+        let State::Launch(MenuLaunch {
+            selected_instance: Some(instance),
+            ..
+        }) = &self.state
+        else {
+            return;
+        };
+        let result = quantum_launcher_backend::get_game_dir(instance).and_then(|dir| {
+            dir.to_str()
+                .map(str::to_owned)
+                .ok_or(LauncherError::PathBufToString(dir))
+        });
+        match result {
+            Ok(dir) => open_file_explorer(&dir),
+            Err(err) => self.notify(ToastKind::Error, err.to_string_with_code()),
+        }
+    }
 
-        self.state = State::Create(MenuCreateInstance {
-            instance_name: Default::default(),
-            selected_version: None,
-            versions: Vec::new(),
-            progress_receiver: None,
-            progress_number: None,
-            progress_text: None,
-            download_assets: true,
+    /// Cancels the Java install of the game or server being started.
+    pub fn cancel_java_install(&mut self) {
+        if let State::Launch(MenuLaunch {
+            java_install_progress: Some(progress),
+            ..
+        })
+        | State::Servers(MenuServers {
+            java_install_progress: Some(progress),
+            ..
+        }) = &mut self.state
+        {
+            progress.cancel.cancel();
+            progress.message = tr!("java-install-cancelling");
+        }
+    }
+
+    /// Clears the Java install progress, returning whether
+    /// it stopped because the user cancelled it.
+    fn take_cancelled_java_install(&mut self) -> bool {
+        let (State::Launch(MenuLaunch {
+            java_install_progress,
+            ..
+        })
+        | State::Servers(MenuServers {
+            java_install_progress,
+            ..
+        })) = &mut self.state
+        else {
+            return false;
+        };
+        java_install_progress
+            .take()
+            .is_some_and(|progress| progress.cancel.is_cancelled())
+    }
+
+    /// Re-reads an instance's info after it changed,
+    /// was created or was deleted.
+    pub fn reload_instance_info(&mut self, instance_name: &str) {
+        let Some(instances) = &mut self.instances else {
+            return;
+        };
+        if let Err(err) = instances.invalidate(instance_name) {
+            error!("Could not reload {instance_name}: {err}");
+        }
+    }
+
+    pub fn go_to_servers_screen(&mut self) {
+        let servers_dir = match file_utils::get_launcher_dir() {
+            Ok(launcher_dir) => launcher_dir.join("servers"),
+            Err(err) => {
+                self.set_error(err.to_string_with_code());
+                return;
+            }
+        };
+        match quantum_launcher_backend::list_servers() {
+            Ok(servers) => {
+                self.state = State::Servers(MenuServers {
+                    servers,
+                    servers_dir,
+                    selected_server: None,
+                    java_install_progress: None,
+                    external_server_input: String::new(),
+                })
+            }
+            Err(err) => self.set_error(err.to_string_with_code()),
+        }
+    }
+
+    pub fn start_server(&mut self) -> Command<Message> {
+        let State::Servers(menu) = &mut self.state else {
+            return Command::none();
+        };
+        let Some(server) = menu.selected_server.clone() else {
+            return Command::none();
+        };
+
+        match server_files::is_eula_accepted(&server) {
+            Ok(true) => {}
+            Ok(false) => {
+                self.state = State::ServerEula(MenuServerEula {
+                    server,
+                    agreed: false,
+                });
+                return Command::none();
+            }
+            Err(err) => {
+                self.notify(
+                    ToastKind::Error,
+                    tr!(
+                        "toast-server-start-failed",
+                        error = err.to_string_with_code()
+                    ),
+                );
+                return Command::none();
+            }
+        }
+
+        let (sender, receiver) = progress::channel();
+        let cancel = CancelToken::new();
+        menu.java_install_progress = Some(JavaInstallProgress {
+            num: 0.0,
+            recv: receiver,
+            message: tr!("java-install-starting-1"),
+            cancel: cancel.clone(),
         });
 
-        if SKIP_LISTING_VERSIONS {
-            Command::none()
-        } else {
-            Command::perform(
-                quantum_launcher_backend::list_versions(),
-                Message::CreateInstanceVersionsLoaded,
-            )
+        Command::perform(
+            quantum_launcher_backend::launch_server_wrapped(
+                server.clone(),
+                Some(sender),
+                Some(cancel),
+            ),
+            move |result| Message::ServerStartEnd(server.clone(), result),
+        )
+    }
+
+    /// Saves the user's acceptance of the EULA,
+    /// then starts the server they wanted to start.
+    pub fn accept_server_eula(&mut self) -> Command<Message> {
+        let State::ServerEula(menu) = &self.state else {
+            return Command::none();
+        };
+        if !menu.agreed {
+            return Command::none();
+        }
+        let server = menu.server.clone();
+
+        if let Err(err) = server_files::set_eula_accepted(&server, true) {
+            self.set_error(err.to_string_with_code());
+            return Command::none();
+        }
+
+        self.go_to_servers_screen();
+        if let State::Servers(menu) = &mut self.state {
+            menu.selected_server = Some(server);
         }
+        self.start_server()
     }
 
-    pub fn create_instance_finish_loading_versions_list(
-        &mut self,
-        result: Result<Arc<Vec<String>>, String>,
-    ) {
+    pub fn finish_starting_server(&mut self, server_name: String, result: GameLaunchResult) {
+        let cancelled = self.take_cancelled_java_install();
         match result {
-            Ok(version_list) => {
-                if let State::Create(menu) = &mut self.state {
-                    menu.versions.extend_from_slice(&version_list)
+            GameLaunchResult::Ok(child) => {
+                let process = GameProcess::new(server_name, child, true);
+                // A restarted server takes the place of its old
+                // entry, keeping its console open if it was.
+                if let Some(index) = self.processes.iter().position(|n| {
+                    n.restarting
+                        && n.exit_status.is_some()
+                        && n.instance_name == process.instance_name
+                }) {
+                    let old = self.processes.remove(index);
+                    if let State::ServerConsole(menu) = &mut self.state {
+                        if menu.pid == old.pid {
+                            menu.pid = process.pid;
+                        }
+                    }
                 }
+                self.processes.push(process);
             }
-            Err(n) => self.state = State::Error { error: n },
+            GameLaunchResult::Err(_) if cancelled => {
+                self.notify(ToastKind::Info, tr!("toast-launch-cancelled"));
+            }
+            GameLaunchResult::Err(err) => self.set_launch_error(err),
         }
     }
 
-    pub fn select_created_instance_version(&mut self, selected_version: String) {
-        if let State::Create(menu) = &mut self.state {
-            menu.selected_version = Some(selected_version)
+    /// Asks a server to stop with the `stop` command, so that it
+    /// saves the worlds first. It can still be killed if it hangs.
+    pub fn stop_server(&mut self, pid: u32) {
+        let Some(process) = self.processes.iter_mut().find(|n| n.pid == pid) else {
+            return;
+        };
+        let server_name = process.instance_name.clone();
+        let Some(stdin) = &mut process.stdin else {
+            return;
+        };
+
+        if let Err(err) = quantum_launcher_backend::stop_server(stdin) {
+            self.notify(
+                ToastKind::Error,
+                tr!(
+                    "toast-game-kill-failed",
+                    instance = server_name,
+                    error = err
+                ),
+            );
+            return;
         }
+        process.killed = true;
+        process.stopping = Some(Instant::now());
+        self.notify(
+            ToastKind::Info,
+            tr!("toast-server-stopping", server = server_name),
+        );
     }
 
-    pub fn update_created_instance_name(&mut self, name: String) {
-        if let State::Create(menu) = &mut self.state {
-            menu.instance_name = name
+    /// Pings the launcher's servers and the user's external
+    /// servers, for the status panel of the launch screen.
+    pub fn refresh_server_statuses(&mut self) -> Command<Message> {
+        let mut targets = Vec::new();
+        match quantum_launcher_backend::list_servers() {
+            Ok(servers) => targets.extend(servers.into_iter().map(|server| {
+                let port = ServerProperties::load(&server.name)
+                    .map(|properties| properties.settings().port)
+                    .unwrap_or(server_ping::DEFAULT_PORT);
+                (server.name, format!("localhost:{port}"))
+            })),
+            Err(err) => error!("Could not list servers: {err}"),
         }
+        if let Some(config) = &self.config {
+            targets.extend(
+                config
+                    .external_servers
+                    .iter()
+                    .map(|address| (address.clone(), address.clone())),
+            );
+        }
+
+        // Old statuses are kept until the new ones
+        // arrive, so the panel doesn't flicker.
+        let old_statuses = std::mem::take(&mut self.server_statuses);
+        self.server_statuses = targets
+            .into_iter()
+            .map(|(name, address)| ServerStatusEntry {
+                status: old_statuses
+                    .iter()
+                    .find(|entry| entry.address == address)
+                    .and_then(|entry| entry.status.clone()),
+                name,
+                address,
+            })
+            .collect();
+
+        let mut addresses: Vec<String> = self
+            .server_statuses
+            .iter()
+            .map(|entry| entry.address.clone())
+            .collect();
+        addresses.sort();
+        addresses.dedup();
+        Command::batch(addresses.into_iter().map(|address| {
+            Command::perform(server_ping::ping_wrapped(address.clone()), move |result| {
+                Message::ServerStatusLoaded(address.clone(), result)
+            })
+        }))
     }
 
-    pub fn create_instance(&mut self) -> Command<Message> {
-        if let State::Create(menu) = &mut self.state {
-            let (sender, receiver) = mpsc::channel::<DownloadProgress>();
-            menu.progress_receiver = Some(receiver);
-            menu.progress_number = Some(0.0);
-            menu.progress_text = Some("Started download".to_owned());
+    pub fn finish_server_status(&mut self, address: &str, result: Result<ServerStatus, String>) {
+        for entry in self
+            .server_statuses
+            .iter_mut()
+            .filter(|entry| entry.address == address)
+        {
+            entry.status = Some(result.clone());
+        }
+    }
 
-            // Create Instance asynchronously using iced Command.
-            return Command::perform(
-                quantum_launcher_backend::create_instance(
-                    menu.instance_name.to_owned(),
-                    menu.selected_version.to_owned().unwrap(),
-                    Some(sender),
-                    menu.download_assets,
+    pub fn add_external_server(&mut self) -> Command<Message> {
+        let State::Servers(menu) = &mut self.state else {
+            return Command::none();
+        };
+        let address = menu.external_server_input.trim().to_owned();
+        if let Err(err) = server_ping::parse_address(&address) {
+            self.notify(ToastKind::Error, err.to_string_with_code());
+            return Command::none();
+        }
+        menu.external_server_input.clear();
+
+        self.edit_config(|config| {
+            if !config.external_servers.contains(&address) {
+                config.external_servers.push(address);
+            }
+        });
+        self.refresh_server_statuses()
+    }
+
+    pub fn remove_external_server(&mut self, address: &str) {
+        self.edit_config(|config| config.external_servers.retain(|n| n != address));
+        self.server_statuses
+            .retain(|entry| entry.address != address);
+    }
+
+    pub fn go_to_server_properties(&mut self) {
+        let State::Servers(MenuServers {
+            selected_server: Some(server),
+            ..
+        }) = &self.state
+        else {
+            return;
+        };
+        let server = server.clone();
+
+        match ServerProperties::load(&server) {
+            Ok(properties) => {
+                let settings = properties.settings();
+                self.state = State::ServerProperties(MenuServerProperties {
+                    server,
+                    properties,
+                    port_input: settings.port.to_string(),
+                    view_distance_input: settings.view_distance.to_string(),
+                    motd: settings.motd,
+                    gamemode: settings.gamemode,
+                    difficulty: settings.difficulty,
+                    online_mode: settings.online_mode,
+                    raw: None,
+                });
+            }
+            Err(err) => self.set_error(err.to_string_with_code()),
+        }
+    }
+
+    /// Switches between the inputs and editing the file as text,
+    /// carrying over the changes made so far.
+    pub fn toggle_server_properties_raw(&mut self) {
+        let State::ServerProperties(menu) = &mut self.state else {
+            return;
+        };
+
+        if let Some(raw) = menu.raw.take() {
+            menu.properties = ServerProperties::from_raw(&raw.text());
+            let settings = menu.properties.settings();
+            menu.port_input = settings.port.to_string();
+            menu.view_distance_input = settings.view_distance.to_string();
+            menu.motd = settings.motd;
+            menu.gamemode = settings.gamemode;
+            menu.difficulty = settings.difficulty;
+            menu.online_mode = settings.online_mode;
+            return;
+        }
+
+        if let Err(err) = menu.apply_settings() {
+            self.notify(ToastKind::Error, err.to_string_with_code());
+            return;
+        }
+        menu.raw = Some(widget::text_editor::Content::with_text(
+            &menu.properties.to_raw(),
+        ));
+    }
+
+    pub fn save_server_properties(&mut self) {
+        let State::ServerProperties(menu) = &mut self.state else {
+            return;
+        };
+
+        if let Some(raw) = &menu.raw {
+            menu.properties = ServerProperties::from_raw(&raw.text());
+        } else if let Err(err) = menu.apply_settings() {
+            self.notify(ToastKind::Error, err.to_string_with_code());
+            return;
+        }
+
+        let server = menu.server.clone();
+        if let Err(err) = menu.properties.save(&server) {
+            self.notify(
+                ToastKind::Error,
+                tr!(
+                    "toast-server-properties-save-failed",
+                    error = err.to_string_with_code()
                 ),
-                Message::CreateInstanceEnd,
             );
+            return;
+        }
+
+        self.notify(ToastKind::Success, tr!("toast-server-properties-saved"));
+        self.go_to_servers_screen();
+        if let State::Servers(menu) = &mut self.state {
+            menu.selected_server = Some(server);
         }
-        Command::none()
     }
 
-    pub fn delete_selected_instance(&mut self) {
-        if let State::DeleteInstance(menu) = &self.state {
-            match quantum_launcher_backend::file_utils::get_launcher_dir() {
-                Ok(launcher_dir) => {
-                    let instances_dir = launcher_dir.join("instances");
-                    let deleted_instance_dir = instances_dir.join(&menu.selected_instance);
+    pub fn send_server_command(&mut self) {
+        let State::ServerConsole(menu) = &mut self.state else {
+            return;
+        };
+        let command = std::mem::take(&mut menu.command_input);
+        if command.trim().is_empty() {
+            return;
+        }
+        let Some(process) = self.processes.iter_mut().find(|n| n.pid == menu.pid) else {
+            return;
+        };
 
-                    if !deleted_instance_dir.starts_with(&instances_dir) {
-                        self.set_error("Tried to delete instance folder located outside Launcher. Potential attack avoided.".to_owned());
-                        return;
-                    }
+        if let Err(err) = process.send_command(&command) {
+            self.notify(
+                ToastKind::Error,
+                tr!("toast-server-command-failed", error = err),
+            );
+        }
+    }
 
-                    if let Err(err) = std::fs::remove_dir_all(&deleted_instance_dir) {
-                        self.set_error(err.to_string());
-                        return;
+    /// Checks which games have exited and updates their memory usage.
+    /// Servers stopped by their schedule are started again.
+    pub fn update_game_processes(&mut self) -> Command<Message> {
+        let mut crashed = Vec::new();
+        let mut restarts = Vec::new();
+        let mut exited = Vec::new();
+        for process in self
+            .processes
+            .iter_mut()
+            .filter(|process| process.exit_status.is_none())
+        {
+            match process.child.lock().unwrap().try_wait() {
+                Ok(Some(status)) => {
+                    info!("{} exited with {status}", process.instance_name);
+                    process.exit_status = Some(status);
+                    process.memory = None;
+                    if !process.is_server {
+                        exited.push(process.instance_name.clone());
+                        events::emit(Event::GameExited {
+                            instance: process.instance_name.clone(),
+                            exit_code: status.code(),
+                            started: unix_time_of(process.started),
+                            seconds_played: process.started.elapsed().as_secs(),
+                        });
                     }
-
-                    match Launcher::new() {
-                        Ok(launcher) => *self = launcher,
-                        Err(err) => self.set_error(err.to_string()),
+                    if process.restarting {
+                        restarts.push(process.instance_name.clone());
+                    } else if !status.success() && !process.killed {
+                        crashed.push(process.instance_name.clone());
                     }
+                    continue;
                 }
-                Err(err) => self.set_error(err.to_string()),
+                Ok(None) => {}
+                Err(err) => error!("Could not check if game is running: {err}"),
+            }
+
+            let pid = sysinfo::Pid::from_u32(process.pid);
+            self.system.refresh_process(pid);
+            process.memory = self.system.process(pid).map(sysinfo::Process::memory);
+        }
+
+        // Games launched without mods get them back once
+        // no copy of the instance is running anymore.
+        for instance_name in exited {
+            if self.is_instance_running(&instance_name) {
+                continue;
+            }
+            if let Err(err) = quantum_launcher_backend::restore_disabled_mods(&instance_name) {
+                self.notify(
+                    ToastKind::Error,
+                    tr!(
+                        "toast-restore-mods-failed",
+                        instance = instance_name,
+                        error = err.to_string_with_code()
+                    ),
+                );
+            }
+            if let Err(err) = sync_folder::push(&instance_name) {
+                self.notify(
+                    ToastKind::Error,
+                    tr!(
+                        "toast-sync-failed",
+                        instance = instance_name,
+                        error = err.to_string_with_code()
+                    ),
+                );
             }
         }
+
+        for instance_name in crashed {
+            self.notify(
+                ToastKind::Error,
+                tr!("toast-game-crashed", instance = instance_name),
+            );
+        }
+
+        Command::batch(restarts.into_iter().map(|server| {
+            self.notify(
+                ToastKind::Info,
+                tr!("toast-server-restarting", server = server),
+            );
+            Command::perform(
+                quantum_launcher_backend::launch_server_wrapped(server.clone(), None, None),
+                move |result| Message::ServerStartEnd(server.clone(), result),
+            )
+        }))
     }
 
-    pub fn confirm_instance_deletion(&mut self) {
-        if let State::Launch(ref mut menu_launch) = self.state {
-            self.state = State::DeleteInstance(MenuDeleteInstance {
-                selected_instance: menu_launch.selected_instance.clone().unwrap(),
-            })
+    /// Shows the tray icon, if it's turned on and not shown yet.
+    pub fn start_tray(&mut self) {
+        if !self.config.as_ref().is_some_and(|n| n.tray_icon) || self.tray.is_some() {
+            return;
+        }
+        match Tray::start() {
+            Ok(tray) => self.tray = Some(tray),
+            Err(err) => {
+                error!("Could not show tray icon: {err}");
+                self.notify(ToastKind::Error, tr!("toast-tray-failed", error = err));
+                return;
+            }
         }
+        self.refresh_tray_menu();
     }
 
-    pub fn update_instance_creation_progress_bar(&mut self) {
-        if let State::Create(menu) = &mut self.state {
-            if let Some(Ok(progress)) = menu.progress_receiver.as_ref().map(|n| n.try_recv()) {
-                if let Some(progress_text) = &mut menu.progress_text {
-                    *progress_text = progress.to_string()
+    fn refresh_tray_menu(&mut self) {
+        let Some(tray) = &mut self.tray else {
+            return;
+        };
+        // Favorites that were deleted or renamed are left out.
+        let favorites = self
+            .config
+            .as_ref()
+            .map(|config| {
+                config
+                    .favorite_instances
+                    .iter()
+                    .filter(|name| {
+                        self.instances
+                            .as_ref()
+                            .is_some_and(|instances| instances.get(name).is_some())
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        let running = self
+            .processes
+            .iter()
+            .filter(|process| process.exit_status.is_none())
+            .map(|process| (process.pid, process.instance_name.clone()))
+            .collect();
+        tray.set_menu(TrayMenu { favorites, running });
+    }
+
+    pub fn handle_tray_actions(&mut self) -> Command<Message> {
+        let actions = self.tray.as_ref().map(Tray::poll).unwrap_or_default();
+        let mut commands = Vec::new();
+        for action in actions {
+            match action {
+                TrayAction::ShowWindow => {
+                    commands.push(iced::window::change_mode(
+                        iced::window::Id::MAIN,
+                        iced::window::Mode::Windowed,
+                    ));
+                    commands.push(iced::window::gain_focus(iced::window::Id::MAIN));
                 }
-                if let Some(progress_num) = &mut menu.progress_number {
-                    *progress_num = progress.into();
+                TrayAction::Launch(instance) => {
+                    self.go_to_launch_screen();
+                    self.select_launch_instance(instance);
+                    commands.push(self.launch_game());
                 }
+                TrayAction::Kill(pid) => self.kill_game(pid),
             }
         }
+        self.refresh_tray_menu();
+        Command::batch(commands)
     }
 
-    pub fn edit_instance(&mut self, selected_instance: String) -> LauncherResult<()> {
-        let launcher_dir = file_utils::get_launcher_dir()?;
-        let config_path = launcher_dir
-            .join("instances")
-            .join(&selected_instance)
-            .join("config.json");
+    /// Shows the window when another launcher was started, and
+    /// does what was asked of it, like launching an instance.
+    pub fn handle_other_launchers(&mut self) -> Command<Message> {
+        let starts = self
+            .instance_guard
+            .as_ref()
+            .map(InstanceGuard::poll)
+            .unwrap_or_default();
+        if starts.is_empty() {
+            return Command::none();
+        }
+        let mut commands = vec![
+            iced::window::change_mode(iced::window::Id::MAIN, iced::window::Mode::Windowed),
+            iced::window::gain_focus(iced::window::Id::MAIN),
+        ];
+        commands.extend(starts.iter().map(|args| self.handle_args(args)));
+        Command::batch(commands)
+    }
 
-        let config_json = std::fs::read_to_string(&config_path).map_err(io_err!(config_path))?;
-        let config_json: InstanceConfigJson = serde_json::from_str(&config_json)?;
+    /// Handles the command line arguments: `--launch <instance>`
+    /// starts the instance, like from a desktop shortcut.
+    pub fn handle_args(&mut self, args: &[String]) -> Command<Message> {
+        let instance = args
+            .iter()
+            .position(|arg| arg == "--launch")
+            .and_then(|i| args.get(i + 1));
+        let Some(instance) = instance else {
+            return Command::none();
+        };
+        let exists = self
+            .instances
+            .as_ref()
+            .is_some_and(|instances| instances.get(instance).is_some());
+        if !exists {
+            self.notify(
+                ToastKind::Error,
+                tr!("toast-launch-instance-missing", instance = instance),
+            );
+            return Command::none();
+        }
+        self.go_to_launch_screen();
+        self.select_launch_instance(instance.clone());
+        self.launch_game()
+    }
 
-        let slider_value = f32::log2(config_json.ram_in_mb as f32);
-        let memory_mb = config_json.ram_in_mb;
+    pub fn toggle_favorite_instance(&mut self) {
+        let State::Launch(MenuLaunch {
+            selected_instance: Some(instance),
+            ..
+        }) = &self.state
+        else {
+            return;
+        };
+        let instance = instance.clone();
+        self.edit_config(|config| {
+            if config.favorite_instances.contains(&instance) {
+                config.favorite_instances.retain(|n| *n != instance);
+            } else {
+                config.favorite_instances.push(instance);
+            }
+        });
+    }
 
-        self.state = State::EditInstance(MenuEditInstance {
-            selected_instance,
-            config: config_json,
-            slider_value,
-            slider_text: format_memory(memory_mb),
+    pub fn toggle_favorite_version(&mut self, version: String) {
+        self.edit_config(|config| {
+            if config.favorite_versions.contains(&version) {
+                config.favorite_versions.retain(|n| *n != version);
+            } else {
+                config.favorite_versions.push(version);
+            }
         });
-        Ok(())
     }
 
-    pub fn save_config(instance_name: &str, config: &InstanceConfigJson) -> LauncherResult<()> {
-        let launcher_dir = file_utils::get_launcher_dir()?;
-        let config_path = launcher_dir
-            .join("instances")
-            .join(instance_name)
-            .join("config.json");
+    /// Reacts to what the backend (or another part of the
+    /// launcher) did, from [`events::subscribe`].
+    pub fn handle_backend_event(&mut self, event: Event) {
+        match event {
+            Event::InstanceCreated { instance }
+            | Event::LoaderInstalled { instance, .. }
+            | Event::GameStarted { instance, .. } => self.reload_instance_info(&instance),
+            Event::GameExited {
+                instance,
+                started,
+                seconds_played,
+                ..
+            } => self.record_game_exit(instance, started, seconds_played),
+            Event::SyncConflict { instance, kept } => self.notify(
+                ToastKind::Info,
+                tr!(
+                    "toast-sync-conflict",
+                    instance = instance,
+                    path = kept.display()
+                ),
+            ),
+            Event::SyncMissingMods { instance, mods } => self.notify(
+                ToastKind::Error,
+                tr!(
+                    "toast-sync-missing-mods",
+                    instance = instance,
+                    mods = mods.join(", ")
+                ),
+            ),
+            _ => {}
+        }
+    }
 
-        let config_json = serde_json::to_string(config)?;
-        std::fs::write(&config_path, config_json).map_err(io_err!(config_path))?;
-        Ok(())
+    fn record_game_exit(&mut self, instance: String, started: u64, seconds_played: u64) {
+        self.update_discord_presence();
+
+        let version = self
+            .instances
+            .as_ref()
+            .and_then(|instances| instances.get(&instance))
+            .and_then(|info| info.version.clone());
+        let session = Session {
+            instance,
+            version,
+            started,
+            seconds: seconds_played,
+        };
+        if let Err(err) = PlaytimeStats::record(session) {
+            error!("Could not save playtime: {err}");
+        }
     }
 
-    pub fn go_to_edit_mods_menu(&mut self, selected_instance: String) -> LauncherResult<()> {
-        let launcher_dir = file_utils::get_launcher_dir()?;
-        let config_path = launcher_dir
-            .join("instances")
-            .join(&selected_instance)
-            .join("config.json");
+    /// Shows the most recently started game on Discord, or
+    /// nothing if no game is running. Called when games
+    /// start and exit, and when the setting changes.
+    pub fn update_discord_presence(&mut self) {
+        if !self.config.as_ref().is_some_and(|n| n.discord_rpc) {
+            // Dropping it clears the presence, as Discord
+            // forgets it once the connection closes.
+            self.discord = None;
+            return;
+        }
 
-        let config_json = std::fs::read_to_string(&config_path).map_err(io_err!(config_path))?;
-        let config_json: InstanceConfigJson = serde_json::from_str(&config_json)?;
+        let activity = self
+            .processes
+            .iter()
+            .filter(|process| !process.is_server && process.exit_status.is_none())
+            .max_by_key(|process| process.started)
+            .map(|process| {
+                let info = self
+                    .instances
+                    .as_ref()
+                    .and_then(|instances| instances.get(&process.instance_name));
+                let version = info.and_then(|n| n.version.clone()).unwrap_or_default();
+                let state = match info.and_then(|n| n.mod_type.as_deref()) {
+                    Some(mod_type) if mod_type != "Vanilla" => format!("{version} {mod_type}"),
+                    _ => version,
+                };
+                Activity {
+                    details: tr!("discord-playing", instance = process.instance_name),
+                    state,
+                    started: unix_time_of(process.started),
+                }
+            });
 
-        self.state = State::EditMods(MenuEditMods {
-            selected_instance,
-            config: config_json,
+        if activity.is_some() && self.discord.is_none() {
+            self.discord = DiscordRpc::start();
+        }
+        if let Some(discord) = &mut self.discord {
+            discord.set_activity(activity);
+        }
+    }
+
+    pub fn go_to_stats_screen(&mut self) {
+        match PlaytimeStats::load() {
+            Ok(stats) => self.state = State::Stats(MenuStats { stats }),
+            Err(err) => self.set_error(err.to_string_with_code()),
+        }
+    }
+
+    pub fn go_to_download_queue(&mut self) {
+        self.state = State::DownloadQueue(MenuDownloadQueue {
+            queue: download_queue::snapshot(),
+            speed: 0,
+            refreshed: Instant::now(),
         });
-        Ok(())
+    }
+
+    pub fn refresh_download_queue(&mut self) {
+        if let State::DownloadQueue(menu) = &mut self.state {
+            let queue = download_queue::snapshot();
+            let elapsed = menu.refreshed.elapsed().as_secs_f64();
+            if elapsed > 0.0 {
+                let downloaded = queue.total_bytes - menu.queue.total_bytes;
+                menu.speed = (downloaded as f64 / elapsed) as u64;
+            }
+            menu.queue = queue;
+            menu.refreshed = Instant::now();
+        }
+    }
+
+    /// Shows a change to the queue (like pausing a job) straight
+    /// away, without waiting for the next refresh.
+    pub fn update_download_queue(&mut self, change: impl FnOnce()) {
+        change();
+        if let State::DownloadQueue(menu) = &mut self.state {
+            let total_bytes = menu.queue.total_bytes;
+            menu.queue = download_queue::snapshot();
+            // The speed is worked out from the bytes
+            // since the last refresh, so keep those.
+            menu.queue.total_bytes = total_bytes;
+        }
+    }
+
+    /// Opens the integrity check screen, and checks the instance.
+    pub fn go_to_verify_screen(&mut self, instance: String) -> Command<Message> {
+        self.state = State::Verify(MenuVerify {
+            selected_instance: instance.clone(),
+            results: None,
+            fixing: None,
+        });
+        Command::perform(
+            integrity::verify_instance_wrapped(instance),
+            Message::VerifyEnd,
+        )
+    }
+
+    pub fn fix_instance(&mut self, check: Check) -> Command<Message> {
+        let State::Verify(menu) = &mut self.state else {
+            return Command::none();
+        };
+        menu.fixing = Some(check);
+        Command::perform(
+            integrity::fix_wrapped(menu.selected_instance.clone(), check),
+            Message::VerifyFixEnd,
+        )
+    }
+
+    /// Checks the instance again after a fix,
+    /// to show whether it worked.
+    pub fn finish_fixing_instance(&mut self, result: Result<(), String>) -> Command<Message> {
+        let State::Verify(menu) = &self.state else {
+            return Command::none();
+        };
+        let instance = menu.selected_instance.clone();
+        match result {
+            Ok(()) => self.notify(ToastKind::Success, tr!("toast-verify-fixed")),
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!("toast-verify-fix-failed", error = err),
+            ),
+        }
+        self.reload_instance_info(&instance);
+        self.go_to_verify_screen(instance)
+    }
+
+    pub fn go_to_worlds_screen(&mut self, instance: String) {
+        let result = file_utils::get_launcher_dir()
+            .map_err(|err| err.to_string_with_code())
+            .and_then(|launcher_dir| {
+                let instance_dir = launcher_dir.join("instances").join(&instance);
+                let config = InstanceConfigJson::read(&instance_dir.join("config.json"))
+                    .map_err(|err| err.to_string_with_code())?;
+                Ok((config, instance_dir.join("world_backups")))
+            })
+            .and_then(|(config, backups_dir)| {
+                let worlds = world_backup::list_worlds(&instance)
+                    .map_err(|err| err.to_string_with_code())?;
+                Ok((config, worlds, backups_dir))
+            });
+        match result {
+            Ok((config, worlds, backups_dir)) => {
+                self.state = State::Worlds(MenuWorlds {
+                    selected_instance: instance,
+                    retention_input: config.world_backup_retention.to_string(),
+                    config,
+                    worlds,
+                    backups_dir,
+                    busy: false,
+                    rename: None,
+                    copy_target: None,
+                });
+            }
+            Err(err) => self.set_error(err),
+        }
+    }
+
+    /// Lists the worlds again, after they were backed up or restored.
+    fn refresh_worlds(&mut self) {
+        let State::Worlds(menu) = &mut self.state else {
+            return;
+        };
+        match world_backup::list_worlds(&menu.selected_instance) {
+            Ok(worlds) => menu.worlds = worlds,
+            Err(err) => error!("Could not list worlds: {err}"),
+        }
+    }
+
+    pub fn backup_world(&mut self, world: String) -> Command<Message> {
+        let State::Worlds(menu) = &mut self.state else {
+            return Command::none();
+        };
+        menu.busy = true;
+        Command::perform(
+            world_backup::backup_world_wrapped(menu.selected_instance.clone(), world.clone()),
+            move |result| Message::WorldsBackupEnd(world.clone(), result),
+        )
+    }
+
+    pub fn finish_world_backup(&mut self, world: &str, result: Result<PathBuf, String>) {
+        if let State::Worlds(menu) = &mut self.state {
+            menu.busy = false;
+        }
+        self.refresh_worlds();
+        match result {
+            Ok(_) => self.notify(
+                ToastKind::Success,
+                tr!("toast-world-backup-done", world = world),
+            ),
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!("toast-world-backup-failed", world = world, error = err),
+            ),
+        }
+    }
+
+    fn restore_world(&mut self, instance: String, backup: PathBuf) -> Command<Message> {
+        self.go_to_worlds_screen(instance.clone());
+        // The game would save over the restored world.
+        if self.is_instance_running(&instance) {
+            self.notify(ToastKind::Error, tr!("toast-world-game-running"));
+            return Command::none();
+        }
+        if let State::Worlds(menu) = &mut self.state {
+            menu.busy = true;
+        }
+        Command::perform(
+            world_backup::restore_world_wrapped(instance, backup),
+            Message::WorldsRestoreEnd,
+        )
+    }
+
+    pub fn finish_restoring_world(&mut self, result: Result<(), String>) {
+        if let State::Worlds(menu) = &mut self.state {
+            menu.busy = false;
+        }
+        self.refresh_worlds();
+        match result {
+            Ok(()) => self.notify(ToastKind::Success, tr!("toast-world-restore-done")),
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!("toast-world-restore-failed", error = err),
+            ),
+        }
+    }
+
+    fn go_to_edit_instance(&mut self, instance: String) {
+        if let Err(err) = self.edit_instance(instance) {
+            self.set_error(err.to_string_with_code());
+        }
+    }
+
+    fn roll_back(&mut self, instance: String, snapshot: PathBuf) -> Command<Message> {
+        self.go_to_edit_instance(instance.clone());
+        if self.is_instance_running(&instance) {
+            self.notify(ToastKind::Error, tr!("toast-roll-back-game-running"));
+            return Command::none();
+        }
+        if let State::EditInstance(menu) = &mut self.state {
+            menu.rolling_back = true;
+        }
+        Command::perform(
+            snapshots::roll_back_wrapped(instance.clone(), snapshot),
+            move |result| Message::EditInstanceRollBackEnd(instance.clone(), result),
+        )
+    }
+
+    pub fn finish_rolling_back(&mut self, instance: String, result: Result<Vec<String>, String>) {
+        // Reloads the restored config.
+        self.go_to_edit_instance(instance);
+        match result {
+            Ok(missing) if missing.is_empty() => {
+                self.notify(ToastKind::Success, tr!("toast-roll-back-done"))
+            }
+            Ok(missing) => self.notify(
+                ToastKind::Info,
+                tr!("toast-roll-back-missing-mods", mods = missing.join(", ")),
+            ),
+            Err(err) => self.notify(ToastKind::Error, tr!("toast-roll-back-failed", error = err)),
+        }
+    }
+
+    fn is_instance_running(&self, instance: &str) -> bool {
+        self.processes
+            .iter()
+            .any(|process| process.is_running_instance(instance))
+    }
+
+    pub fn rename_world(&mut self) {
+        let State::Worlds(MenuWorlds {
+            selected_instance,
+            rename: Some((world, name)),
+            ..
+        }) = &self.state
+        else {
+            return;
+        };
+        let name = name.trim().to_owned();
+        if name.is_empty() {
+            return;
+        }
+        if self.is_instance_running(selected_instance) {
+            self.notify(ToastKind::Error, tr!("toast-world-game-running"));
+            return;
+        }
+        if let Err(err) = worlds::rename_world(selected_instance, world, &name) {
+            self.notify(
+                ToastKind::Error,
+                tr!(
+                    "toast-world-rename-failed",
+                    error = err.to_string_with_code()
+                ),
+            );
+            return;
+        }
+        if let State::Worlds(menu) = &mut self.state {
+            menu.rename = None;
+        }
+        self.refresh_worlds();
+    }
+
+    /// Copies a world to another instance, or
+    /// duplicates it if `to_instance` is `None`.
+    pub fn copy_world(&mut self, world: String, to_instance: Option<String>) -> Command<Message> {
+        let State::Worlds(menu) = &mut self.state else {
+            return Command::none();
+        };
+        menu.busy = true;
+        let to_instance = to_instance.unwrap_or_else(|| menu.selected_instance.clone());
+        Command::perform(
+            worlds::copy_world_wrapped(menu.selected_instance.clone(), world, to_instance),
+            Message::WorldsCopyEnd,
+        )
+    }
+
+    pub fn finish_copying_world(&mut self, result: Result<String, String>) {
+        if let State::Worlds(menu) = &mut self.state {
+            menu.busy = false;
+        }
+        self.refresh_worlds();
+        match result {
+            Ok(world) => self.notify(
+                ToastKind::Success,
+                tr!("toast-world-copy-done", world = world),
+            ),
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!("toast-world-copy-failed", error = err),
+            ),
+        }
+    }
+
+    pub fn go_to_game_options(&mut self, instance: String) {
+        let result = GameOptions::load(&instance).and_then(|options| {
+            let resource_packs = game_options::list_resource_packs(&instance)?;
+            Ok((options, resource_packs))
+        });
+        match result {
+            Ok((options, resource_packs)) => {
+                self.state =
+                    State::GameOptions(MenuGameOptions::new(instance, options, resource_packs));
+            }
+            Err(err) => self.set_error(err.to_string_with_code()),
+        }
+    }
+
+    pub fn install_resource_pack(&mut self) -> Command<Message> {
+        let State::GameOptions(menu) = &mut self.state else {
+            return Command::none();
+        };
+        let path = menu.resource_pack_input.trim();
+        if path.is_empty() {
+            return Command::none();
+        }
+        menu.installing_pack = true;
+        Command::perform(
+            game_options::install_resource_pack_wrapped(
+                menu.selected_instance.clone(),
+                PathBuf::from(path),
+                menu.enable_new_pack,
+            ),
+            Message::GameOptionsResourcePackInstallEnd,
+        )
+    }
+
+    pub fn finish_installing_resource_pack(&mut self, result: Result<String, String>) {
+        let State::GameOptions(menu) = &mut self.state else {
+            return;
+        };
+        menu.installing_pack = false;
+        match result {
+            Ok(file_name) => {
+                // The file was written already, this
+                // keeps the menu from undoing it on save.
+                if menu.enable_new_pack {
+                    menu.options.set_resource_pack_enabled(&file_name, true);
+                }
+                if !menu.resource_packs.contains(&file_name) {
+                    menu.resource_packs.push(file_name.clone());
+                    menu.resource_packs.sort_unstable();
+                }
+                menu.resource_pack_input.clear();
+                self.notify(
+                    ToastKind::Success,
+                    tr!("toast-resource-pack-installed", pack = file_name),
+                );
+            }
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!("toast-resource-pack-install-failed", error = err),
+            ),
+        }
+    }
+
+    /// Copies the keybinds of the chosen instance into the
+    /// menu. They're written to the file on save.
+    pub fn import_keybinds(&mut self) {
+        let State::GameOptions(MenuGameOptions {
+            options,
+            copy_source: Some(source),
+            ..
+        }) = &mut self.state
+        else {
+            return;
+        };
+
+        match GameOptions::load(source) {
+            Ok(from) => match options.import_keybinds(&from) {
+                0 => self.notify(ToastKind::Error, tr!("toast-keybinds-import-none")),
+                count => self.notify(
+                    ToastKind::Info,
+                    tr!("toast-keybinds-imported", count = count),
+                ),
+            },
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!(
+                    "toast-keybinds-import-failed",
+                    error = err.to_string_with_code()
+                ),
+            ),
+        }
+    }
+
+    /// Replaces the instance's options with those of the chosen
+    /// instance, and reloads the menu to show them.
+    pub fn copy_all_game_options(&mut self) {
+        let State::GameOptions(MenuGameOptions {
+            selected_instance,
+            copy_source: Some(source),
+            ..
+        }) = &self.state
+        else {
+            return;
+        };
+        let (instance, source) = (selected_instance.clone(), source.clone());
+
+        match game_options::copy_options(&source, &instance) {
+            Ok(()) => {
+                self.go_to_game_options(instance);
+                self.notify(
+                    ToastKind::Success,
+                    tr!("toast-game-options-copied", instance = source),
+                );
+            }
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!(
+                    "toast-game-options-copy-failed",
+                    error = err.to_string_with_code()
+                ),
+            ),
+        }
+    }
+
+    /// Gives the instance that was just created the options of
+    /// the instance picked in the launcher settings, if any.
+    pub fn copy_options_to_new_instance(&mut self) {
+        let State::Create(menu) = &self.state else {
+            return;
+        };
+        let Some(source) = self
+            .config
+            .as_ref()
+            .and_then(|config| config.options_source.clone())
+        else {
+            return;
+        };
+        // The source may have been deleted or renamed since.
+        if self
+            .instances
+            .as_ref()
+            .and_then(|instances| instances.get(&source))
+            .is_none()
+        {
+            return;
+        }
+
+        if let Err(err) = game_options::copy_options(&source, &menu.instance_name) {
+            self.notify(
+                ToastKind::Error,
+                tr!(
+                    "toast-game-options-copy-failed",
+                    error = err.to_string_with_code()
+                ),
+            );
+        }
+    }
+
+    pub fn save_game_options(&mut self) {
+        let State::GameOptions(menu) = &mut self.state else {
+            return;
+        };
+        if let Err(err) = menu.apply_settings() {
+            self.notify(ToastKind::Error, err.to_string_with_code());
+            return;
+        }
+        match menu.options.save(&menu.selected_instance) {
+            Ok(()) => self.notify(ToastKind::Success, tr!("toast-game-options-saved")),
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!(
+                    "toast-game-options-save-failed",
+                    error = err.to_string_with_code()
+                ),
+            ),
+        }
+    }
+
+    pub fn save_world_backup_settings(&mut self) {
+        let State::Worlds(menu) = &mut self.state else {
+            return;
+        };
+        let Some(retention) = MenuServerSchedule::parse_retention(&menu.retention_input) else {
+            return;
+        };
+        menu.config.world_backup_retention = retention;
+        match Launcher::save_config(&menu.selected_instance, &mut menu.config) {
+            Ok(()) => self.notify(ToastKind::Success, tr!("toast-worlds-saved")),
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!(
+                    "toast-worlds-save-failed",
+                    error = err.to_string_with_code()
+                ),
+            ),
+        }
+    }
+
+    pub fn go_to_server_schedule(&mut self) {
+        let State::Servers(MenuServers {
+            selected_server: Some(server),
+            ..
+        }) = &self.state
+        else {
+            return;
+        };
+        let server = server.clone();
+
+        let result = server_files::get_server_dir(&server).and_then(|server_dir| {
+            Ok((ServerSchedule::load(&server)?, server_dir.join("backups")))
+        });
+        match result {
+            Ok((schedule, backups_dir)) => {
+                self.state =
+                    State::ServerSchedule(MenuServerSchedule::new(server, &schedule, backups_dir));
+            }
+            Err(err) => self.set_error(err.to_string_with_code()),
+        }
+    }
+
+    pub fn save_server_schedule(&mut self) {
+        let State::ServerSchedule(menu) = &self.state else {
+            return;
+        };
+        let Some(schedule) = menu.schedule() else {
+            return;
+        };
+
+        let server = menu.server.clone();
+        if let Err(err) = schedule.save(&server) {
+            self.notify(
+                ToastKind::Error,
+                tr!(
+                    "toast-server-schedule-save-failed",
+                    error = err.to_string_with_code()
+                ),
+            );
+            return;
+        }
+
+        self.notify(ToastKind::Success, tr!("toast-server-schedule-saved"));
+        self.go_to_servers_screen();
+        if let State::Servers(menu) = &mut self.state {
+            menu.selected_server = Some(server);
+        }
+    }
+
+    /// Backs up and restarts the running servers
+    /// whose schedules say it's time to.
+    pub fn run_server_schedules(&mut self) -> Command<Message> {
+        let mut backups = Vec::new();
+        let mut restarts = Vec::new();
+        for process in self.processes.iter().filter(|process| {
+            process.is_server
+                && process.exit_status.is_none()
+                && process.stopping.is_none()
+                && !process.backing_up
+        }) {
+            let schedule = match ServerSchedule::load(&process.instance_name) {
+                Ok(schedule) => schedule,
+                Err(err) => {
+                    error!(
+                        "Could not load the schedule of {}: {err}",
+                        process.instance_name
+                    );
+                    continue;
+                }
+            };
+
+            if schedule
+                .restart_interval()
+                .is_some_and(|interval| process.started.elapsed() >= interval)
+            {
+                restarts.push(process.pid);
+            } else if schedule
+                .backup_interval()
+                .is_some_and(|interval| process.last_backup.elapsed() >= interval)
+            {
+                backups.push(process.instance_name.clone());
+            }
+        }
+
+        for pid in restarts {
+            info!("Restarting server {pid} on schedule");
+            self.stop_server(pid);
+            if let Some(process) = self.processes.iter_mut().find(|n| n.pid == pid) {
+                process.restarting = process.stopping.is_some();
+            }
+        }
+        Command::batch(backups.into_iter().map(|server| self.backup_server(server)))
+    }
+
+    /// Backs up a server's worlds. If it's running, saving is turned
+    /// off until the backup is done, so that the files don't change
+    /// while they're being zipped.
+    pub fn backup_server(&mut self, server: String) -> Command<Message> {
+        /// `save-all flush` is run by the server in the background,
+        /// so it's given some time to write everything out.
+        const SAVE_WAIT: Duration = Duration::from_secs(5);
+
+        let mut wait = Duration::ZERO;
+        if let Some(process) = self
+            .processes
+            .iter_mut()
+            .find(|process| process.is_running_server(&server))
+        {
+            if process.backing_up {
+                return Command::none();
+            }
+            let result = process
+                .send_command("save-off")
+                .and_then(|()| process.send_command("save-all flush"));
+            if let Err(err) = result {
+                self.notify(
+                    ToastKind::Error,
+                    tr!("toast-server-backup-failed", server = server, error = err),
+                );
+                return Command::none();
+            }
+            process.backing_up = true;
+            wait = SAVE_WAIT;
+        }
+
+        if let State::ServerSchedule(menu) = &mut self.state {
+            if menu.server == server {
+                menu.backing_up = true;
+            }
+        }
+        self.notify(
+            ToastKind::Info,
+            tr!("toast-server-backup-started", server = server),
+        );
+        let server_name = server.clone();
+        Command::perform(
+            async move {
+                tokio::time::sleep(wait).await;
+                server_schedule::backup_worlds_wrapped(server_name).await
+            },
+            move |result| Message::ServerBackupEnd(server.clone(), result),
+        )
+    }
+
+    pub fn finish_server_backup(&mut self, server: String, result: Result<PathBuf, String>) {
+        if let Some(process) = self
+            .processes
+            .iter_mut()
+            .find(|process| process.is_running_server(&server) && process.backing_up)
+        {
+            // Failed backups aren't tried again until the next
+            // one is due, so that they don't keep saving off.
+            process.backing_up = false;
+            process.last_backup = Instant::now();
+            if let Err(err) = process.send_command("save-on") {
+                error!("Could not turn saving back on for {server}: {err}");
+            }
+        }
+        if let State::ServerSchedule(menu) = &mut self.state {
+            if menu.server == server {
+                menu.backing_up = false;
+            }
+        }
+
+        match result {
+            Ok(_) => self.notify(
+                ToastKind::Success,
+                tr!("toast-server-backup-done", server = server),
+            ),
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!("toast-server-backup-failed", server = server, error = err),
+            ),
+        }
+    }
+
+    pub fn welcome_next(&mut self) {
+        let State::Welcome(menu) = &mut self.state else {
+            return;
+        };
+        if menu.step == WelcomeStep::DataDir {
+            let data_dir = PathBuf::from(menu.data_dir_input.trim());
+            if let Err(err) = self.set_data_dir(data_dir) {
+                self.notify(
+                    ToastKind::Error,
+                    tr!("welcome-data-dir-failed", error = err),
+                );
+                return;
+            }
+        }
+
+        if let State::Welcome(menu) = &mut self.state {
+            if let Some(step) = menu.step.next() {
+                menu.step = step;
+            }
+        }
+    }
+
+    /// Moves the launcher's data (instances, Java installs, etc.)
+    /// to `data_dir`. Existing data isn't copied over.
+    fn set_data_dir(&mut self, data_dir: PathBuf) -> LauncherResult<()> {
+        std::fs::create_dir_all(&data_dir).map_err(io_err!(data_dir))?;
+        let data_dir = (data_dir != file_utils::get_config_dir()?).then_some(data_dir);
+
+        file_utils::set_data_dir(data_dir.clone());
+        self.edit_config(|config| config.data_dir = data_dir);
+        if let State::LauncherSettings(menu) = &mut self.state {
+            menu.find_dirs();
+        }
+        self.instances = Some(Launcher::load_instances()?);
+        Ok(())
+    }
+
+    /// Closes the launcher, saving the window size and position first.
+    ///
+    /// Whether the window is maximized has to be asked
+    /// for, so this finishes in [`Launcher::close_window`].
+    pub fn request_close() -> Command<Message> {
+        iced::window::fetch_maximized(iced::window::Id::MAIN, |maximized| Message::WindowClose {
+            maximized,
+        })
+    }
+
+    pub fn close_window(&mut self, maximized: bool) -> Command<Message> {
+        let mut window = self.window;
+        if maximized {
+            // Keep the size from before the window was maximized.
+            if let Some(saved) = self.config.as_ref().and_then(|n| n.window) {
+                window.width = saved.width;
+                window.height = saved.height;
+                window.position = saved.position;
+            }
+        }
+        window.maximized = maximized;
+        self.edit_config(|config| config.window = Some(window));
+
+        iced::window::close(iced::window::Id::MAIN)
+    }
+
+    /// Looks for a newer launcher, unless turned off in the settings.
+    pub fn check_for_updates(&mut self) -> Command<Message> {
+        let check = self.config.as_ref().is_some_and(|n| n.check_for_updates);
+        if !check || self.update.is_some() {
+            return Command::none();
+        }
+        Command::perform(
+            self_update::check_for_update_wrapped(env!("CARGO_PKG_VERSION").to_owned()),
+            Message::UpdateCheckResult,
+        )
+    }
+
+    pub fn finish_update_check(&mut self, result: Result<Option<Release>, String>) {
+        match result {
+            Ok(Some(release)) => self.update = Some(release),
+            Ok(None) => {}
+            // Not worth bothering the user about, as it's usually being offline.
+            Err(err) => error!("Could not check for launcher updates: {err}"),
+        }
+    }
+
+    pub fn go_to_update_screen(&mut self) {
+        if let Some(release) = self.update.clone() {
+            self.state = State::Update(MenuUpdate {
+                release,
+                installing: false,
+                outcome: None,
+            });
+        }
+    }
+
+    pub fn install_update(&mut self) -> Command<Message> {
+        let State::Update(menu) = &mut self.state else {
+            return Command::none();
+        };
+        menu.installing = true;
+        Command::perform(
+            self_update::install_update_wrapped(menu.release.clone()),
+            Message::UpdateInstallEnd,
+        )
+    }
+
+    pub fn finish_installing_update(&mut self, result: Result<UpdateOutcome, String>) {
+        if let State::Update(menu) = &mut self.state {
+            menu.installing = false;
+        }
+        match result {
+            Ok(outcome) => {
+                if let UpdateOutcome::SavedInstaller(path) = &outcome {
+                    open_file_explorer(&path.to_string_lossy());
+                }
+                if let State::Update(menu) = &mut self.state {
+                    menu.outcome = Some(outcome);
+                }
+            }
+            Err(err) => self.notify(ToastKind::Error, tr!("toast-update-failed", error = err)),
+        }
+    }
+
+    /// Looks for newer versions of the Fabric
+    /// instances' loaders, in the background.
+    pub fn check_for_loader_updates(&mut self) -> Command<Message> {
+        let Some(instances) = &self.instances else {
+            return Command::none();
+        };
+        let fabric_instances: Vec<String> = instances
+            .instances()
+            .iter()
+            .filter(|instance| instance.mod_type.as_deref() == Some("Fabric"))
+            .map(|instance| instance.name.clone())
+            .collect();
+        if fabric_instances.is_empty() {
+            return Command::none();
+        }
+        Command::perform(
+            instance_mod_installer::fabric::check_for_updates_wrapped(fabric_instances),
+            Message::LoaderUpdatesChecked,
+        )
+    }
+
+    pub fn update_loader(&mut self) -> Command<Message> {
+        let State::EditMods(menu) = &mut self.state else {
+            return Command::none();
+        };
+        let Some(version) = self.loader_updates.get(&menu.selected_instance) else {
+            return Command::none();
+        };
+        menu.updating_loader = true;
+        let instance = menu.selected_instance.clone();
+        Command::perform(
+            instance_mod_installer::fabric::update_wrapped(version.clone(), instance.clone(), None),
+            move |result| Message::ManageModsUpdateLoaderEnd(instance.clone(), result),
+        )
+    }
+
+    pub fn finish_updating_loader(&mut self, instance: String, result: Result<(), String>) {
+        if let State::EditMods(menu) = &mut self.state {
+            menu.updating_loader = false;
+        }
+        match result {
+            Ok(()) => {
+                if let Some(version) = self.loader_updates.remove(&instance) {
+                    self.notify(
+                        ToastKind::Success,
+                        tr!(
+                            "toast-loader-updated",
+                            instance = instance,
+                            version = version
+                        ),
+                    );
+                }
+            }
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!("toast-loader-update-failed", error = err),
+            ),
+        }
+    }
+
+    pub fn export_mod_list(&self, format: ModListFormat) -> Command<Message> {
+        let State::EditMods(menu) = &self.state else {
+            return Command::none();
+        };
+        Command::perform(
+            mod_list::export_wrapped(menu.selected_instance.clone(), format),
+            Message::ManageModsExportEnd,
+        )
+    }
+
+    pub fn import_mod_list(&mut self) -> Command<Message> {
+        let State::EditMods(menu) = &mut self.state else {
+            return Command::none();
+        };
+        if menu.import_progress.is_some() || menu.import_input.trim().is_empty() {
+            return Command::none();
+        }
+
+        let (sender, receiver) = progress::channel();
+        menu.import_progress = Some(PluginProgress {
+            receiver,
+            message: tr!("plugins-progress-starting"),
+        });
+        let instance = menu.selected_instance.clone();
+        Command::perform(
+            mod_list::import_wrapped(instance.clone(), menu.import_input.clone(), Some(sender)),
+            move |result| Message::ManageModsImportEnd(instance.clone(), result),
+        )
+    }
+
+    pub fn update_import_progress(&mut self, message: ModInstallProgress) {
+        if let State::EditMods(MenuEditMods {
+            import_progress: Some(progress),
+            ..
+        }) = &mut self.state
+        {
+            progress.message = plugin_progress_text(&message);
+        }
+    }
+
+    pub fn finish_importing_mod_list(
+        &mut self,
+        instance: String,
+        result: Result<ImportResult, String>,
+    ) {
+        if let State::EditMods(menu) = &mut self.state {
+            if menu.selected_instance == instance {
+                menu.import_progress = None;
+                if result.is_ok() {
+                    // Shows the new mods.
+                    self.go_to_edit_mods_menu_wrapped(instance);
+                }
+            }
+        }
+        match result {
+            Ok(result) => {
+                self.notify(
+                    ToastKind::Success,
+                    tr!("toast-mods-installed", count = result.installed.len()),
+                );
+                if !result.unmatched.is_empty() {
+                    let unmatched: Vec<String> = result
+                        .unmatched
+                        .iter()
+                        .map(|(entry, reason)| format!("{entry} ({reason})"))
+                        .collect();
+                    self.notify(
+                        ToastKind::Error,
+                        tr!("toast-mod-list-unmatched", mods = unmatched.join(", ")),
+                    );
+                }
+            }
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!("toast-mods-install-failed", error = err),
+            ),
+        }
+    }
+
+    /// Copies the exported list, for pasting into a bug report.
+    pub fn finish_exporting_mod_list(
+        &mut self,
+        result: Result<String, String>,
+    ) -> Command<Message> {
+        match result {
+            Ok(list) => {
+                self.notify(ToastKind::Success, tr!("toast-mod-list-copied"));
+                iced::clipboard::write(list)
+            }
+            Err(err) => {
+                self.notify(
+                    ToastKind::Error,
+                    tr!("toast-mod-list-export-failed", error = err),
+                );
+                Command::none()
+            }
+        }
+    }
+
+    /// Starts loading the news feed, if it's enabled and not loaded yet.
+    pub fn load_news(&mut self) -> Command<Message> {
+        let show_news = self.config.as_ref().is_some_and(|n| n.show_news);
+        if !show_news || matches!(self.news, Some(Ok(_))) {
+            return Command::none();
+        }
+        self.news = None;
+        Command::perform(json_news::get_news_wrapped(), Message::NewsLoaded)
+    }
+
+    pub fn dismiss_toast(&mut self, id: usize) {
+        self.toasts.retain(|toast| toast.id != id);
+    }
+
+    pub fn remove_expired_toasts(&mut self) {
+        self.toasts.retain(|toast| !toast.is_expired());
+    }
+
+    pub fn open_game_log(&mut self, pid: u32) {
+        let is_server = self
+            .processes
+            .iter()
+            .any(|process| process.pid == pid && process.is_server);
+        self.state = if is_server {
+            State::ServerConsole(MenuServerConsole {
+                pid,
+                command_input: String::new(),
+                shown_lines: MenuServerConsole::SHOWN_LINES_STEP,
+            })
+        } else {
+            State::GameLog(MenuGameLog {
+                pid,
+                sharing: false,
+            })
+        };
+    }
+
+    /// Uploads the game's log, or its latest crash report,
+    /// to mclo.gs. The link is copied when it's done.
+    pub fn share_game_log(&mut self, pid: u32, crash_report: bool) -> Command<Message> {
+        let Some(process) = self.processes.iter().find(|n| n.pid == pid) else {
+            return Command::none();
+        };
+
+        let text = if crash_report {
+            let started = UNIX_EPOCH + Duration::from_secs(unix_time_of(process.started));
+            let report = log_upload::find_crash_report(&process.instance_name, started)
+                .ok_or_else(|| tr!("toast-log-no-crash-report"))
+                .and_then(|path| {
+                    log_upload::read_crash_report(&path).map_err(|err| err.to_string_with_code())
+                });
+            match report {
+                Ok(report) => report,
+                Err(err) => {
+                    self.notify(ToastKind::Error, err);
+                    return Command::none();
+                }
+            }
+        } else {
+            process.log.lock().unwrap().join("\n")
+        };
+
+        if let State::GameLog(menu) = &mut self.state {
+            menu.sharing = true;
+        }
+        Command::perform(
+            log_upload::upload_log_wrapped(text),
+            Message::GameLogShareEnd,
+        )
+    }
+
+    pub fn finish_sharing_game_log(&mut self, result: Result<String, String>) -> Command<Message> {
+        if let State::GameLog(menu) = &mut self.state {
+            menu.sharing = false;
+        }
+        match result {
+            Ok(url) => {
+                self.notify(
+                    ToastKind::Success,
+                    tr!("toast-log-shared", url = url.clone()),
+                );
+                iced::clipboard::write(url)
+            }
+            Err(err) => {
+                self.notify(ToastKind::Error, tr!("toast-log-share-failed", error = err));
+                Command::none()
+            }
+        }
+    }
+
+    pub fn kill_game(&mut self, pid: u32) {
+        let Some(process) = self.processes.iter_mut().find(|n| n.pid == pid) else {
+            return;
+        };
+        let instance_name = process.instance_name.clone();
+        let result = process.child.lock().unwrap().kill();
+        if let Err(err) = result {
+            self.notify(
+                ToastKind::Error,
+                tr!(
+                    "toast-game-kill-failed",
+                    instance = instance_name,
+                    error = err
+                ),
+            );
+            return;
+        }
+        process.killed = true;
+        process.restarting = false;
+        self.notify(
+            ToastKind::Info,
+            tr!("toast-game-killed", instance = instance_name),
+        );
+    }
+
+    /// Removes a game that has exited from the sidebar.
+    pub fn close_game(&mut self, pid: u32) {
+        self.processes
+            .retain(|n| n.pid != pid || n.exit_status.is_none());
+        match &self.state {
+            State::GameLog(menu) if menu.pid == pid => self.go_to_launch_screen(),
+            State::ServerConsole(menu) if menu.pid == pid => self.go_to_servers_screen(),
+            _ => {}
+        }
+    }
+
+    pub fn go_to_create_screen(&mut self, is_server: bool) -> Command<Message> {
+        const SKIP_LISTING_VERSIONS: bool = false;
+
+        let templates = if is_server {
+            Vec::new()
+        } else {
+            templates::list().unwrap_or_else(|err| {
+                error!("Could not list the instance templates: {err}");
+                Vec::new()
+            })
+        };
+
+        self.state = State::Create(MenuCreateInstance {
+            is_server,
+            instance_name: Default::default(),
+            selected_version: None,
+            versions: Vec::new(),
+            version_filter: String::new(),
+            // Most people want a release, so the rest start folded.
+            collapsed_groups: VersionType::ALL[1..].iter().copied().collect(),
+            progress_receiver: None,
+            progress_number: None,
+            progress_text: None,
+            cancel: None,
+            download_assets: true,
+            version_source: VersionSource::Mojang,
+            custom_version_json: None,
+            server_pack: None,
+            patch_notes: None,
+            server_software: ServerSoftware::Vanilla,
+            server_software_versions: None,
+            templates,
+            template: None,
+        });
+
+        let patch_notes = Command::perform(
+            json_patch_notes::get_patch_notes_wrapped(),
+            Message::CreateInstancePatchNotesLoaded,
+        );
+        if SKIP_LISTING_VERSIONS {
+            patch_notes
+        } else {
+            Command::batch([load_versions(VersionSource::Mojang), patch_notes])
+        }
+    }
+
+    /// Lists the versions from `source` instead.
+    pub fn select_version_source(&mut self, source: VersionSource) -> Command<Message> {
+        let State::Create(menu) = &mut self.state else {
+            return Command::none();
+        };
+        if menu.version_source == source {
+            return Command::none();
+        }
+        menu.version_source = source;
+        menu.versions.clear();
+        menu.selected_version = None;
+        // Templates are made with Mojang's versions.
+        if source != VersionSource::Mojang {
+            menu.template = None;
+        }
+        load_versions(source)
+    }
+
+    /// Creates the instance from a template (or not), with
+    /// the template's version picked until another one is.
+    pub fn select_template(&mut self, choice: TemplateChoice) -> Command<Message> {
+        let State::Create(menu) = &mut self.state else {
+            return Command::none();
+        };
+        menu.template = choice
+            .0
+            .and_then(|name| menu.templates.iter().find(|n| n.name == name).cloned());
+        let Some(template) = &menu.template else {
+            return Command::none();
+        };
+        let version = template.minecraft_version.clone();
+        let command = self.select_version_source(VersionSource::Mojang);
+        self.select_created_instance_version(version);
+        command
+    }
+
+    pub fn delete_selected_template(&mut self) {
+        let State::Create(menu) = &mut self.state else {
+            return;
+        };
+        let Some(template) = menu.template.take() else {
+            return;
+        };
+        match templates::delete(&template.name) {
+            Ok(()) => {
+                menu.templates.retain(|n| n.name != template.name);
+                self.notify(
+                    ToastKind::Success,
+                    tr!("toast-template-deleted", name = template.name),
+                );
+            }
+            Err(err) => self.notify(ToastKind::Error, err.to_string_with_code()),
+        }
+    }
+
+    pub fn create_instance_finish_loading_versions_list(
+        &mut self,
+        source: VersionSource,
+        result: Result<Arc<Vec<ListedVersion>>, String>,
+    ) {
+        match result {
+            Ok(version_list) => {
+                if let State::Create(menu) = &mut self.state {
+                    // The user may have picked another source while loading.
+                    if menu.version_source == source {
+                        menu.versions.extend_from_slice(&version_list)
+                    }
+                }
+            }
+            Err(n) => self.set_error(n),
+        }
+    }
+
+    pub fn select_server_software(&mut self, software: ServerSoftware) -> Command<Message> {
+        let State::Create(menu) = &mut self.state else {
+            return Command::none();
+        };
+        menu.server_software = software;
+        menu.server_software_versions = None;
+        if software == ServerSoftware::Vanilla {
+            return Command::none();
+        }
+
+        Command::perform(
+            server_software::list_versions_wrapped(software),
+            move |result| Message::ServerCreateSoftwareVersionsLoaded(software, result),
+        )
+    }
+
+    pub fn finish_loading_server_software_versions(
+        &mut self,
+        software: ServerSoftware,
+        result: Result<Option<Vec<String>>, String>,
+    ) {
+        let State::Create(menu) = &mut self.state else {
+            return;
+        };
+        // The user may have picked something else while loading.
+        if menu.server_software != software {
+            return;
+        }
+
+        match result {
+            Ok(versions) => {
+                if let (Some(versions), Some(selected)) = (&versions, &menu.selected_version) {
+                    if !versions.contains(selected) {
+                        menu.selected_version = None;
+                    }
+                }
+                menu.server_software_versions = versions;
+            }
+            Err(err) => {
+                menu.server_software = ServerSoftware::Vanilla;
+                self.notify(
+                    ToastKind::Error,
+                    tr!(
+                        "toast-server-software-versions-failed",
+                        software = software.name(),
+                        error = err
+                    ),
+                );
+            }
+        }
+    }
+
+    pub fn select_created_instance_version(&mut self, selected_version: String) {
+        if let State::Create(menu) = &mut self.state {
+            menu.selected_version = Some(selected_version)
+        }
+    }
+
+    pub fn update_created_instance_name(&mut self, name: String) {
+        if let State::Create(menu) = &mut self.state {
+            menu.instance_name = name
+        }
+    }
+
+    pub fn create_instance(&mut self) -> Command<Message> {
+        if let State::Create(menu) = &mut self.state {
+            let (sender, receiver) = progress::channel::<DownloadProgress>();
+            let cancel = CancelToken::new();
+            menu.progress_receiver = Some(receiver);
+            menu.progress_number = Some(0.0);
+            menu.progress_text = Some(tr!("create-progress-started"));
+            menu.cancel = Some(cancel.clone());
+
+            if menu.is_server {
+                return Command::perform(
+                    quantum_launcher_backend::create_server_wrapped(
+                        menu.instance_name.to_owned(),
+                        menu.selected_version.to_owned().unwrap(),
+                        menu.server_software,
+                        Some(sender),
+                        Some(cancel),
+                    ),
+                    Message::CreateInstanceEnd,
+                );
+            }
+
+            if let Some(server_pack) = &menu.server_pack {
+                return Command::perform(
+                    server_pack::create_from_server_pack_wrapped(
+                        menu.instance_name.to_owned(),
+                        PathBuf::from(server_pack.trim()),
+                        menu.selected_version.to_owned(),
+                        Some(sender),
+                        menu.download_assets,
+                        Some(cancel),
+                    ),
+                    Message::CreateInstanceServerPackEnd,
+                );
+            }
+
+            if let Some(template) = &menu.template {
+                return Command::perform(
+                    templates::create_from_template_wrapped(
+                        menu.instance_name.to_owned(),
+                        template.clone(),
+                        menu.selected_version.to_owned().unwrap(),
+                        Some(sender),
+                        menu.download_assets,
+                        Some(cancel),
+                    ),
+                    Message::CreateInstanceTemplateEnd,
+                );
+            }
+
+            if let Some(custom_version_json) = &menu.custom_version_json {
+                return Command::perform(
+                    quantum_launcher_backend::create_instance_from_json_wrapped(
+                        menu.instance_name.to_owned(),
+                        custom_version_json.to_owned(),
+                        Some(sender),
+                        menu.download_assets,
+                        Some(cancel),
+                    ),
+                    Message::CreateInstanceEnd,
+                );
+            }
+
+            // Create Instance asynchronously using iced Command.
+            return Command::perform(
+                quantum_launcher_backend::create_instance_wrapped(
+                    menu.instance_name.to_owned(),
+                    menu.selected_version.to_owned().unwrap(),
+                    menu.version_source,
+                    Some(sender),
+                    menu.download_assets,
+                    Some(cancel),
+                ),
+                Message::CreateInstanceEnd,
+            );
+        }
+        Command::none()
+    }
+
+    pub fn accept_confirmation(&mut self) -> Command<Message> {
+        let State::Confirm(menu) = &self.state else {
+            return Command::none();
+        };
+        if !menu.is_confirmed() {
+            return Command::none();
+        }
+
+        match menu.action.clone() {
+            DestructiveAction::DeleteInstance { instance } => {
+                return self.delete_instance(&instance)
+            }
+            DestructiveAction::DeleteServer { server } => {
+                if let Err(err) = quantum_launcher_backend::delete_server(&server) {
+                    self.set_error(err.to_string_with_code());
+                    return Command::none();
+                }
+                self.go_to_servers_screen();
+            }
+            DestructiveAction::DeleteMod {
+                instance,
+                file_name,
+            } => {
+                if let Err(err) = delete_mod(&instance, &file_name) {
+                    self.notify(
+                        ToastKind::Error,
+                        tr!("toast-mod-delete-failed", error = err),
+                    );
+                }
+                self.go_to_edit_mods_menu_wrapped(instance);
+            }
+            DestructiveAction::DeletePlugin { server, file_name } => {
+                if let Err(err) = delete_plugin(&server, &file_name) {
+                    self.notify(
+                        ToastKind::Error,
+                        tr!("toast-plugin-delete-failed", error = err),
+                    );
+                }
+                self.go_to_server_plugins_wrapped(server);
+            }
+            DestructiveAction::UninstallLoader { instance, .. } => {
+                return Command::perform(
+                    instance_mod_installer::fabric::uninstall_wrapped(instance.clone()),
+                    move |result| Message::UninstallLoaderEnd(instance.clone(), result),
+                );
+            }
+            DestructiveAction::MoveDataDir { to } => return self.move_data_dir(to),
+            DestructiveAction::RestoreBackup { path } => return self.restore_backup(path),
+            DestructiveAction::DeleteJavaInstalls { installs } => {
+                self.go_to_launcher_settings();
+                if let State::LauncherSettings(menu) = &mut self.state {
+                    menu.java_cleanup_status = Some(tr!("settings-java-cleanup-running"));
+                }
+                return Command::perform(
+                    quantum_launcher_backend::delete_java_installs_wrapped(installs),
+                    Message::DeleteUnusedJavaEnd,
+                );
+            }
+            DestructiveAction::DeleteWorld { instance, world } => {
+                self.go_to_worlds_screen(instance.clone());
+                if self.is_instance_running(&instance) {
+                    self.notify(ToastKind::Error, tr!("toast-world-game-running"));
+                } else if let Err(err) = worlds::delete_world(&instance, &world) {
+                    self.notify(
+                        ToastKind::Error,
+                        tr!(
+                            "toast-world-delete-failed",
+                            error = err.to_string_with_code()
+                        ),
+                    );
+                }
+                self.refresh_worlds();
+            }
+            DestructiveAction::RestoreWorld { instance, backup } => {
+                return self.restore_world(instance, backup)
+            }
+            DestructiveAction::RollBack { instance, snapshot } => {
+                return self.roll_back(instance, snapshot)
+            }
+            DestructiveAction::IncompatibleJava { instance, .. } => {
+                self.go_to_launch_screen();
+                self.select_launch_instance(instance);
+                return self.start_game(std::convert::identity);
+            }
+            DestructiveAction::LaunchAgain { instance } => {
+                self.go_to_launch_screen();
+                self.select_launch_instance(instance);
+                return self.start_game(LaunchOptions::separate_game_dir);
+            }
+        }
+        Command::none()
+    }
+
+    pub fn cancel_confirmation(&mut self) {
+        let State::Confirm(menu) = &self.state else {
+            return;
+        };
+        match menu.action.clone() {
+            DestructiveAction::DeleteInstance { .. } => self.go_to_launch_screen(),
+            DestructiveAction::DeleteServer { .. } => self.go_to_servers_screen(),
+            DestructiveAction::DeletePlugin { server, .. } => {
+                self.go_to_server_plugins_wrapped(server)
+            }
+            DestructiveAction::DeleteMod { instance, .. }
+            | DestructiveAction::UninstallLoader { instance, .. } => {
+                self.go_to_edit_mods_menu_wrapped(instance)
+            }
+            DestructiveAction::MoveDataDir { .. }
+            | DestructiveAction::RestoreBackup { .. }
+            | DestructiveAction::DeleteJavaInstalls { .. } => self.go_to_launcher_settings(),
+            DestructiveAction::DeleteWorld { instance, .. }
+            | DestructiveAction::RestoreWorld { instance, .. } => {
+                self.go_to_worlds_screen(instance)
+            }
+            DestructiveAction::RollBack { instance, .. } => self.go_to_edit_instance(instance),
+            DestructiveAction::LaunchAgain { instance }
+            | DestructiveAction::IncompatibleJava { instance, .. } => {
+                self.go_to_launch_screen();
+                self.select_launch_instance(instance);
+            }
+        }
+    }
+
+    /// Updates the instance (and server) list when folders were
+    /// added or removed outside the launcher, and leaves the
+    /// menu of an instance that's gone.
+    pub fn apply_instance_folder_changes(&mut self, mut changes: FolderChanges) {
+        let servers = changes.servers.take();
+        if let Some(instances) = &mut self.instances {
+            if instances.apply(changes) {
+                info!("The instances folder changed, updated instances");
+                self.leave_removed_instance();
+            }
+        }
+
+        if let (Some(servers), State::Servers(menu)) = (servers, &mut self.state) {
+            if menu
+                .selected_server
+                .as_ref()
+                .is_some_and(|selected| !servers.iter().any(|n| n.name == *selected))
+            {
+                menu.selected_server = None;
+            }
+            menu.servers = servers;
+        }
+    }
+
+    fn leave_removed_instance(&mut self) {
+        let Some(instance) = self.state.selected_instance().map(str::to_owned) else {
+            return;
+        };
+        let exists = self
+            .instances
+            .as_ref()
+            .is_some_and(|instances| instances.get(&instance).is_some());
+        if exists {
+            return;
+        }
+        match &mut self.state {
+            State::Launch(menu) => menu.selected_instance = None,
+            // Bug reports can still be made about it.
+            State::Error { .. } => {}
+            _ => {
+                self.go_to_launch_screen();
+                self.notify(
+                    ToastKind::Info,
+                    tr!("toast-instance-removed", instance = instance),
+                );
+            }
+        }
+    }
+
+    fn delete_instance(&mut self, instance: &str) -> Command<Message> {
+        if let Err(err) = quantum_launcher_backend::delete_instance(instance) {
+            self.set_error(err.to_string_with_code());
+            return Command::none();
+        }
+
+        self.reload_instance_info(instance);
+        self.go_to_launch_screen();
+
+        // The deleted instance may have been the last one
+        // using a particular Java version.
+        Command::perform(
+            quantum_launcher_backend::delete_unused_java_installs_wrapped(),
+            Message::DeleteUnusedJavaEnd,
+        )
+    }
+
+    pub fn install_fabric(&mut self) -> Command<Message> {
+        if let State::InstallFabric(menu) = &mut self.state {
+            let Some(fabric_version) = menu.fabric_version.clone() else {
+                return Command::none();
+            };
+            let (sender, receiver) = progress::channel::<FabricInstallProgress>();
+            menu.progress = Some(FabricProgress {
+                receiver,
+                num: 0.0,
+                message: fabric_progress_text(&FabricInstallProgress::DownloadingJson),
+            });
+
+            return Command::perform(
+                instance_mod_installer::fabric::install_wrapped(
+                    fabric_version,
+                    menu.selected_instance.to_owned(),
+                    Some(sender),
+                ),
+                Message::InstallFabricEnd,
+            );
+        }
+        Command::none()
+    }
+
+    pub fn update_fabric_install_progress(&mut self, message: FabricInstallProgress) {
+        if let State::InstallFabric(MenuInstallFabric {
+            progress: Some(progress),
+            ..
+        }) = &mut self.state
+        {
+            progress.num = (&message).into();
+            progress.message = fabric_progress_text(&message);
+        }
+    }
+
+    pub fn cancel_instance_creation(&mut self) {
+        if let State::Create(MenuCreateInstance {
+            cancel: Some(cancel),
+            progress_text,
+            ..
+        }) = &mut self.state
+        {
+            cancel.cancel();
+            *progress_text = Some(tr!("create-progress-cancelling"));
+        }
+    }
+
+    /// Clears the progress of creating an instance, returning
+    /// whether it stopped because the user cancelled it.
+    pub fn take_cancelled_creation(&mut self) -> bool {
+        let State::Create(menu) = &mut self.state else {
+            return false;
+        };
+        menu.progress_receiver = None;
+        menu.progress_number = None;
+        menu.progress_text = None;
+        menu.cancel
+            .take()
+            .is_some_and(|cancel| cancel.is_cancelled())
+    }
+
+    /// Like `CreateInstanceEnd`, but also tells the user which
+    /// of the pack's mods were left out or need checking.
+    pub fn finish_creating_from_server_pack(&mut self, result: Result<ServerPackImport, String>) {
+        match result {
+            Ok(import) => {
+                self.copy_options_to_new_instance();
+                self.go_to_launch_screen();
+                self.notify(ToastKind::Success, tr!("toast-instance-created"));
+                if !import.skipped.is_empty() {
+                    self.notify(
+                        ToastKind::Info,
+                        tr!(
+                            "toast-server-pack-skipped",
+                            count = import.skipped.len(),
+                            mods = import.skipped.join(", ")
+                        ),
+                    );
+                }
+                if !import.review.is_empty() {
+                    let mods: Vec<String> = import
+                        .review
+                        .iter()
+                        .map(|(name, reason)| format!("{name} ({reason})"))
+                        .collect();
+                    self.notify(
+                        ToastKind::Error,
+                        tr!(
+                            "toast-server-pack-review",
+                            count = import.review.len(),
+                            mods = mods.join(", ")
+                        ),
+                    );
+                }
+            }
+            Err(_) if self.take_cancelled_creation() => {
+                self.notify(ToastKind::Info, tr!("toast-create-cancelled"));
+            }
+            Err(n) => self.set_error(n),
+        }
+    }
+
+    pub fn finish_creating_from_template(&mut self, result: Result<Vec<(String, String)>, String>) {
+        match result {
+            // The template's options are used instead
+            // of copying them from another instance.
+            Ok(unmatched) => {
+                self.go_to_launch_screen();
+                self.notify(ToastKind::Success, tr!("toast-instance-created"));
+                if !unmatched.is_empty() {
+                    let mods: Vec<String> = unmatched
+                        .iter()
+                        .map(|(name, reason)| format!("{name} ({reason})"))
+                        .collect();
+                    self.notify(
+                        ToastKind::Error,
+                        tr!(
+                            "toast-template-unmatched",
+                            count = unmatched.len(),
+                            mods = mods.join(", ")
+                        ),
+                    );
+                }
+            }
+            Err(_) if self.take_cancelled_creation() => {
+                self.notify(ToastKind::Info, tr!("toast-create-cancelled"));
+            }
+            Err(n) => self.set_error(n),
+        }
+    }
+
+    pub fn update_instance_creation_progress_bar(&mut self, progress: DownloadProgress) {
+        if let State::Create(menu) = &mut self.state {
+            if let Some(progress_text) = &mut menu.progress_text {
+                *progress_text = download_progress_text(&progress)
+            }
+            if let Some(progress_num) = &mut menu.progress_number {
+                *progress_num = progress.into();
+            }
+        }
+    }
+
+    pub fn edit_instance(&mut self, selected_instance: String) -> LauncherResult<()> {
+        let launcher_dir = file_utils::get_launcher_dir()?;
+        let config_path = launcher_dir
+            .join("instances")
+            .join(&selected_instance)
+            .join("config.json");
+
+        let config_json = InstanceConfigJson::read(&config_path)?;
+
+        let slider_value = f32::log2(config_json.ram_in_mb as f32);
+        let memory_mb = config_json.ram_in_mb;
+        let initial_memory_mb = config_json.get_initial_ram_in_mb();
+        let java_args_input = join_arguments(&config_json.java_args);
+        self.system.refresh_memory();
+        let system_memory_mb = (self.system.total_memory() / (1024 * 1024)) as usize;
+        let other_instances = self
+            .instances
+            .iter()
+            .flat_map(|registry| registry.instances())
+            .map(|instance| instance.name.clone())
+            .filter(|name| *name != selected_instance)
+            .collect();
+        let offline_profiles = self
+            .config
+            .iter()
+            .flat_map(|config| &config.offline_profiles)
+            .map(|profile| profile.name.clone())
+            .collect();
+        let snapshots = snapshots::list(&selected_instance).unwrap_or_else(|err| {
+            error!("Could not list the snapshots of {selected_instance}: {err}");
+            Vec::new()
+        });
+        let template_name_input = selected_instance.clone();
+        let saved_config = serde_json::to_string(&config_json)?;
+
+        self.state = State::EditInstance(MenuEditInstance {
+            selected_instance,
+            config: config_json,
+            saved_config,
+            slider_value,
+            slider_text: format_memory(memory_mb),
+            memory_input: format!("{memory_mb}M"),
+            system_memory_mb: (system_memory_mb > 0).then_some(system_memory_mb),
+            initial_slider_value: f32::log2(initial_memory_mb as f32),
+            initial_slider_text: format_memory(initial_memory_mb),
+            java_args_input,
+            other_instances,
+            offline_profiles,
+            snapshots,
+            rolling_back: false,
+            template_name_input,
+        });
+        Ok(())
+    }
+
+    /// Saves the edit screen's config, if it's changed.
+    pub fn save_edited_instance(&mut self) {
+        let State::EditInstance(menu) = &mut self.state else {
+            return;
+        };
+        let result = serde_json::to_string(&menu.config)
+            .map_err(LauncherError::from)
+            .and_then(|config| {
+                if config == menu.saved_config {
+                    return Ok(false);
+                }
+                Launcher::save_config(&menu.selected_instance, &mut menu.config)?;
+                menu.saved_config = serde_json::to_string(&menu.config)?;
+                Ok(true)
+            });
+        match result {
+            Ok(true) => {
+                let instance = menu.selected_instance.clone();
+                self.reload_instance_info(&instance);
+            }
+            Ok(false) => {}
+            Err(err) => self.set_error(err.to_string_with_code()),
+        }
+    }
+
+    pub fn save_template(&mut self) -> Command<Message> {
+        let State::EditInstance(menu) = &self.state else {
+            return Command::none();
+        };
+        Command::perform(
+            templates::save_wrapped(
+                menu.selected_instance.clone(),
+                menu.template_name_input.trim().to_owned(),
+            ),
+            Message::EditInstanceTemplateSaved,
+        )
+    }
+
+    pub fn finish_saving_template(&mut self, result: Result<InstanceTemplate, String>) {
+        match result {
+            Ok(template) => self.notify(
+                ToastKind::Success,
+                tr!("toast-template-saved", name = template.name),
+            ),
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!("toast-template-save-failed", error = err),
+            ),
+        }
+    }
+
+    /// Saves settings changed on a screen that keeps the
+    /// config open. Mods installed and launches made since
+    /// it was opened are kept (see [`InstanceConfigJson::keep_tracked`]).
+    pub fn save_config(instance_name: &str, config: &mut InstanceConfigJson) -> LauncherResult<()> {
+        let launcher_dir = file_utils::get_launcher_dir()?;
+        let config_path = launcher_dir
+            .join("instances")
+            .join(instance_name)
+            .join("config.json");
+
+        config.keep_tracked(InstanceConfigJson::read(&config_path)?);
+        config.save(&config_path)?;
+        Ok(())
+    }
+
+    fn use_managed_java(instance_name: &str, major_version: usize) -> LauncherResult<()> {
+        let config_path = file_utils::get_launcher_dir()?
+            .join("instances")
+            .join(instance_name)
+            .join("config.json");
+        let mut config = InstanceConfigJson::read(&config_path)?;
+        config.java_override = None;
+        config.java_version = Some(major_version);
+        config.save(&config_path)?;
+        Ok(())
+    }
+
+    pub fn go_to_edit_mods_menu(&mut self, selected_instance: String) -> LauncherResult<()> {
+        let launcher_dir = file_utils::get_launcher_dir()?;
+        let config_path = launcher_dir
+            .join("instances")
+            .join(&selected_instance)
+            .join("config.json");
+
+        let config_json = InstanceConfigJson::read(&config_path)?;
+
+        // The mods folder is only made once a mod loader is installed.
+        let mods = list_files(&get_mods_dir(&selected_instance)?)?;
+
+        self.state = State::EditMods(MenuEditMods {
+            selected_instance,
+            config: config_json,
+            mods,
+            updating_loader: false,
+            import_input: String::new(),
+            import_progress: None,
+        });
+        Ok(())
+    }
+
+    pub fn go_to_edit_mods_menu_wrapped(&mut self, selected_instance: String) {
+        if let Err(err) = self.go_to_edit_mods_menu(selected_instance) {
+            self.set_error(err.to_string_with_code())
+        }
+    }
+
+    pub fn go_to_server_plugins(&mut self, server: String) -> LauncherResult<()> {
+        let server_dir = server_files::get_server_dir(&server)?;
+        let config_path = server_dir.join("config.json");
+        let config_json = InstanceConfigJson::read(&config_path)?;
+
+        // The plugins folder is made by the server on its first start.
+        let plugins_dir = server_dir.join("plugins");
+        let plugins = list_files(&plugins_dir)?;
+
+        let (source, plugin_input) = match &mut self.state {
+            State::ServerPlugins(menu) => (menu.source, std::mem::take(&mut menu.plugin_input)),
+            _ => Default::default(),
+        };
+        self.state = State::ServerPlugins(MenuServerPlugins {
+            server,
+            software: ServerSoftware::from_mod_type(&config_json.mod_type).unwrap_or_default(),
+            plugins,
+            plugins_dir,
+            source,
+            plugin_input,
+            progress: None,
+        });
+        Ok(())
+    }
+
+    pub fn go_to_server_plugins_wrapped(&mut self, server: String) {
+        if let Err(err) = self.go_to_server_plugins(server) {
+            self.set_error(err.to_string_with_code())
+        }
+    }
+
+    pub fn install_plugins(&mut self) -> Command<Message> {
+        let State::ServerPlugins(menu) = &mut self.state else {
+            return Command::none();
+        };
+        let plugins: Vec<String> = menu
+            .plugin_input
+            .split_whitespace()
+            .map(str::to_owned)
+            .collect();
+        if plugins.is_empty() || menu.progress.is_some() {
+            return Command::none();
+        }
+
+        let (sender, receiver) = progress::channel();
+        menu.progress = Some(PluginProgress {
+            receiver,
+            message: tr!("plugins-progress-starting"),
+        });
+
+        let server = menu.server.clone();
+        Command::perform(
+            plugins::install_plugins_wrapped(server.clone(), menu.source, plugins, Some(sender)),
+            move |result| Message::ServerPluginsInstallEnd(server.clone(), result),
+        )
+    }
+
+    pub fn update_plugin_install_progress(&mut self, message: ModInstallProgress) {
+        if let State::ServerPlugins(MenuServerPlugins {
+            progress: Some(progress),
+            ..
+        }) = &mut self.state
+        {
+            progress.message = plugin_progress_text(&message);
+        }
+    }
+
+    pub fn finish_installing_plugins(
+        &mut self,
+        server: String,
+        result: Result<Vec<InstalledMod>, String>,
+    ) {
+        match result {
+            Ok(installed) => {
+                if let State::ServerPlugins(menu) = &mut self.state {
+                    menu.plugin_input.clear();
+                }
+                self.notify(
+                    ToastKind::Success,
+                    tr!("toast-plugins-installed", count = installed.len()),
+                );
+            }
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!("toast-plugins-install-failed", error = err),
+            ),
+        }
+        if matches!(&self.state, State::ServerPlugins(menu) if menu.server == server) {
+            self.go_to_server_plugins_wrapped(server);
+        }
+    }
+}
+
+/// How many gallery images of the selected mod are loaded.
+const GALLERY_IMAGES: usize = 6;
+/// How long to wait after the last key press before searching.
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(400);
+/// How far down (from 0 to 1) the results have to be
+/// scrolled before the next page is loaded.
+const LOAD_MORE_AT: f32 = 0.9;
+
+impl Launcher {
+    pub fn go_to_mod_browser(&mut self, selected_instance: String) -> Command<Message> {
+        self.state = State::ModBrowser(MenuModBrowser {
+            selected_instance,
+            query: String::new(),
+            results: None,
+            total_hits: 0,
+            loading_more: false,
+            search_id: 0,
+            selected: None,
+            images: HashMap::new(),
+            progress: None,
+        });
+        self.search_mods()
+    }
+
+    /// Searches again once the user stops typing for a moment,
+    /// instead of on every key press.
+    pub fn set_mod_browser_query(&mut self, query: String) -> Command<Message> {
+        let State::ModBrowser(menu) = &mut self.state else {
+            return Command::none();
+        };
+        menu.query = query;
+        menu.search_id += 1;
+        let search_id = menu.search_id;
+        Command::perform(tokio::time::sleep(SEARCH_DEBOUNCE), move |()| {
+            Message::ModBrowserSearchDebounced(search_id)
+        })
+    }
+
+    pub fn search_mods_debounced(&mut self, search_id: u64) -> Command<Message> {
+        match &self.state {
+            State::ModBrowser(menu) if menu.search_id == search_id => self.search_mods(),
+            _ => Command::none(),
+        }
+    }
+
+    pub fn search_mods(&mut self) -> Command<Message> {
+        let State::ModBrowser(menu) = &mut self.state else {
+            return Command::none();
+        };
+        menu.search_id += 1;
+        menu.results = None;
+        menu.total_hits = 0;
+        menu.loading_more = false;
+        search_page(menu, 0)
+    }
+
+    /// Loads the next page once the results are scrolled near the bottom.
+    pub fn scroll_mod_browser(
+        &mut self,
+        viewport: widget::scrollable::Viewport,
+    ) -> Command<Message> {
+        if viewport.relative_offset().y < LOAD_MORE_AT {
+            return Command::none();
+        }
+        self.load_more_mods()
+    }
+
+    pub fn load_more_mods(&mut self) -> Command<Message> {
+        let State::ModBrowser(menu) = &mut self.state else {
+            return Command::none();
+        };
+        let Some(Ok(hits)) = &menu.results else {
+            return Command::none();
+        };
+        if menu.loading_more || hits.len() >= menu.total_hits {
+            return Command::none();
+        }
+        let offset = hits.len();
+        menu.loading_more = true;
+        search_page(menu, offset)
+    }
+
+    pub fn finish_searching_mods(
+        &mut self,
+        search_id: u64,
+        result: Result<SearchPage, String>,
+    ) -> Command<Message> {
+        let State::ModBrowser(menu) = &mut self.state else {
+            return Command::none();
+        };
+        if menu.search_id != search_id {
+            return Command::none();
+        }
+        menu.loading_more = false;
+
+        let page = match result {
+            Ok(page) => page,
+            Err(err) => {
+                if let Some(Ok(_)) = &menu.results {
+                    // Keep the pages that did load.
+                    self.notify(
+                        ToastKind::Error,
+                        tr!("mod-browser-search-failed", error = err),
+                    );
+                } else {
+                    menu.results = Some(Err(err));
+                }
+                return Command::none();
+            }
+        };
+
+        let icons: Vec<String> = page
+            .hits
+            .iter()
+            .filter_map(|hit| hit.icon_url.clone())
+            .filter(|url| !menu.images.contains_key(url))
+            .collect();
+        menu.total_hits = page.total_hits;
+        match &mut menu.results {
+            Some(Ok(hits)) if page.offset > 0 => hits.extend(page.hits),
+            _ => menu.results = Some(Ok(page.hits)),
+        }
+        load_images(icons, modrinth_search::get_icon_wrapped)
+    }
+
+    pub fn select_browsed_mod(&mut self, project_id: String) -> Command<Message> {
+        let State::ModBrowser(menu) = &mut self.state else {
+            return Command::none();
+        };
+        menu.selected = Some(SelectedMod {
+            project_id: project_id.clone(),
+            details: None,
+            description: Vec::new(),
+        });
+        Command::perform(
+            modrinth_search::get_project_wrapped(project_id.clone()),
+            move |result| Message::ModBrowserDetailsLoaded(project_id.clone(), result),
+        )
+    }
+
+    pub fn finish_loading_mod_details(
+        &mut self,
+        project_id: String,
+        result: Result<ProjectDetails, String>,
+    ) -> Command<Message> {
+        let State::ModBrowser(MenuModBrowser {
+            selected: Some(selected),
+            images,
+            ..
+        }) = &mut self.state
+        else {
+            return Command::none();
+        };
+        // Another mod was picked while this one was loading.
+        if selected.project_id != project_id {
+            return Command::none();
+        }
+
+        let mut icons = Vec::new();
+        let mut urls = Vec::new();
+        if let Ok(details) = &result {
+            selected.description = markdown::parse(&details.body);
+            icons.extend(details.icon_url.clone());
+            urls.extend(
+                details
+                    .gallery
+                    .iter()
+                    .take(GALLERY_IMAGES)
+                    .map(|image| image.url.clone()),
+            );
+            icons.retain(|url| !images.contains_key(url));
+            urls.retain(|url| !images.contains_key(url));
+        }
+        selected.details = Some(result);
+        Command::batch([
+            load_images(icons, modrinth_search::get_icon_wrapped),
+            load_images(urls, modrinth_search::download_image_wrapped),
+        ])
+    }
+
+    pub fn add_mod_browser_image(&mut self, url: String, result: Result<Vec<u8>, String>) {
+        match result {
+            Ok(bytes) => {
+                if let State::ModBrowser(menu) = &mut self.state {
+                    menu.images
+                        .insert(url, widget::image::Handle::from_memory(bytes));
+                }
+            }
+            Err(err) => error!("Could not load image {url}: {err}"),
+        }
+    }
+
+    pub fn install_browsed_mod(&mut self) -> Command<Message> {
+        let State::ModBrowser(MenuModBrowser {
+            selected_instance,
+            selected: Some(selected),
+            progress,
+            ..
+        }) = &mut self.state
+        else {
+            return Command::none();
+        };
+        if progress.is_some() {
+            return Command::none();
+        }
+
+        let (sender, receiver) = progress::channel();
+        *progress = Some(PluginProgress {
+            receiver,
+            message: tr!("plugins-progress-starting"),
+        });
+
+        let instance = selected_instance.clone();
+        Command::perform(
+            modrinth::install_mods_wrapped(
+                instance.clone(),
+                vec![selected.project_id.clone()],
+                Some(sender),
+            ),
+            move |result| Message::ModBrowserInstallEnd(instance.clone(), result),
+        )
+    }
+
+    pub fn update_mod_browser_progress(&mut self, message: ModInstallProgress) {
+        if let State::ModBrowser(MenuModBrowser {
+            progress: Some(progress),
+            ..
+        }) = &mut self.state
+        {
+            progress.message = plugin_progress_text(&message);
+        }
+    }
+
+    pub fn finish_installing_browsed_mod(
+        &mut self,
+        instance: String,
+        result: Result<Vec<InstalledMod>, String>,
+    ) {
+        if let State::ModBrowser(menu) = &mut self.state {
+            if menu.selected_instance == instance {
+                menu.progress = None;
+            }
+        }
+        match result {
+            Ok(installed) => self.notify(
+                ToastKind::Success,
+                tr!("toast-mods-installed", count = installed.len()),
+            ),
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!("toast-mods-install-failed", error = err),
+            ),
+        }
+    }
+}
+
+fn search_page(menu: &MenuModBrowser, offset: usize) -> Command<Message> {
+    let search_id = menu.search_id;
+    Command::perform(
+        modrinth_search::search_wrapped(menu.selected_instance.clone(), menu.query.clone(), offset),
+        move |result| Message::ModBrowserResults(search_id, result),
+    )
+}
+
+fn load_images<F>(urls: Vec<String>, download: fn(String) -> F) -> Command<Message>
+where
+    F: Future<Output = Result<Vec<u8>, String>> + Send + 'static,
+{
+    Command::batch(urls.into_iter().map(|url| {
+        Command::perform(download(url.clone()), move |result| {
+            Message::ModBrowserImageLoaded(url.clone(), result)
+        })
+    }))
+}
+
+impl Launcher {
+    pub fn handle_shortcut(&mut self, shortcut: Shortcut) -> Command<Message> {
+        match shortcut {
+            Shortcut::ToggleHelp => self.show_shortcut_help = !self.show_shortcut_help,
+            Shortcut::Search => return self.toggle_search(),
+            Shortcut::Back => {
+                if self.search.is_some() {
+                    self.search = None;
+                } else if self.show_shortcut_help {
+                    self.show_shortcut_help = false;
+                } else if matches!(
+                    &self.state,
+                    State::ServerConsole(_)
+                        | State::ServerEula(_)
+                        | State::ServerProperties(_)
+                        | State::ServerPlugins(_)
+                        | State::ServerSchedule(_)
+                ) || matches!(&self.state, State::Create(menu) if menu.is_server)
+                {
+                    self.go_to_servers_screen();
+                } else if matches!(self.state, State::LauncherLogs(_)) {
+                    self.go_to_launcher_settings();
+                } else if !matches!(self.state, State::Launch(_) | State::Welcome(_)) {
+                    self.go_to_launch_screen();
+                }
+            }
+            Shortcut::SelectPreviousInstance | Shortcut::SelectNextInstance => {
+                let next = shortcut == Shortcut::SelectNextInstance;
+                match &mut self.search {
+                    Some(search) => search.move_selection(next),
+                    None => self.select_adjacent_instance(next),
+                }
+            }
+            Shortcut::Launch => {
+                if let State::Launch(MenuLaunch {
+                    selected_instance: Some(_),
+                    java_install_progress: None,
+                    ..
+                }) = &self.state
+                {
+                    return self.launch_game();
+                }
+            }
+            Shortcut::NewInstance => match &self.state {
+                State::Launch(_) => return self.go_to_create_screen(false),
+                State::Servers(_) => return self.go_to_create_screen(true),
+                _ => {}
+            },
+        }
+        Command::none()
+    }
+
+    /// Opens the search palette, or closes it if it's open.
+    /// It can't be opened before there are instances to search.
+    fn toggle_search(&mut self) -> Command<Message> {
+        if self.search.take().is_some() {
+            return Command::none();
+        }
+        let Some(instances) = &self.instances else {
+            return Command::none();
+        };
+        if matches!(self.state, State::Welcome(_)) {
+            return Command::none();
+        }
+        self.search = Some(SearchPalette::open(instances.instances()));
+        widget::text_input::focus(SearchPalette::input_id())
+    }
+
+    pub fn set_search_query(&mut self, query: String) {
+        if let Some(search) = &mut self.search {
+            search.query = query;
+            search.selected = 0;
+        }
+    }
+
+    /// Goes to the screen for a search result: the instance on
+    /// the launch screen, or its mods or worlds screen.
+    pub fn open_search_result(&mut self, index: Option<usize>) {
+        let Some(search) = self.search.take() else {
+            return;
+        };
+        let index = index.unwrap_or(search.selected);
+        let Some(entry) = search.results().get(index).map(|n| (*n).clone()) else {
+            return;
+        };
+        match entry.target {
+            SearchTarget::Instance => {
+                self.go_to_launch_screen();
+                self.select_launch_instance(entry.instance);
+            }
+            SearchTarget::Mod => self.go_to_edit_mods_menu_wrapped(entry.instance),
+            SearchTarget::World => self.go_to_worlds_screen(entry.instance),
+        }
+    }
+
+    /// Moves the instance selection on the launch screen up or down.
+    /// If nothing is selected, the first (or last) instance is picked.
+    fn select_adjacent_instance(&mut self, next: bool) {
+        let (State::Launch(menu), Some(instances)) = (&mut self.state, &self.instances) else {
+            return;
+        };
+        let instances = instances.instances();
+        if instances.is_empty() {
+            return;
+        }
+
+        let current = menu
+            .selected_instance
+            .as_ref()
+            .and_then(|selected| instances.iter().position(|n| &n.name == selected));
+        let index = match (current, next) {
+            (Some(index), true) => (index + 1).min(instances.len() - 1),
+            (Some(index), false) => index.saturating_sub(1),
+            (None, true) => 0,
+            (None, false) => instances.len() - 1,
+        };
+        menu.selected_instance = Some(instances[index].name.clone());
+    }
+
+    pub fn go_to_launcher_settings(&mut self) {
+        let java_args = self
+            .config
+            .as_ref()
+            .map(|config| join_arguments(&config.java_args))
+            .unwrap_or_default();
+        let mut menu = MenuLauncherSettings {
+            java_args_input: java_args,
+            ..Default::default()
+        };
+        menu.find_dirs();
+        menu.data_dir_input.clone_from(&menu.data_dir);
+        self.state = State::LauncherSettings(menu);
+    }
+
+    /// Asks to confirm moving the launcher's data to the typed-in directory.
+    pub fn confirm_moving_data_dir(&mut self) {
+        let State::LauncherSettings(menu) = &self.state else {
+            return;
+        };
+        let to = PathBuf::from(menu.data_dir_input.trim());
+        if to.as_os_str().is_empty() {
+            return;
+        }
+        if self
+            .processes
+            .iter()
+            .any(|process| process.exit_status.is_none())
+        {
+            self.notify(ToastKind::Error, tr!("toast-data-dir-games-running"));
+            return;
+        }
+        self.state = State::Confirm(MenuConfirm {
+            action: DestructiveAction::MoveDataDir { to },
+            input: String::new(),
+        });
+    }
+
+    /// Moves the launcher's data to `to` in the background,
+    /// switching over to it in [`Launcher::finish_moving_data_dir`].
+    fn move_data_dir(&mut self, to: PathBuf) -> Command<Message> {
+        let from = match file_utils::get_launcher_dir() {
+            Ok(from) => from,
+            Err(err) => {
+                self.set_error(err.to_string_with_code());
+                return Command::none();
+            }
+        };
+
+        self.go_to_launcher_settings();
+        if let State::LauncherSettings(menu) = &mut self.state {
+            menu.data_dir_input = to.to_string_lossy().to_string();
+            menu.moving_data_dir = true;
+        }
+        Command::perform(
+            file_utils::move_data_dir_wrapped(from, to),
+            Message::LauncherSettingsDataDirMoveEnd,
+        )
+    }
+
+    pub fn finish_moving_data_dir(&mut self, result: Result<PathBuf, String>) {
+        if let State::LauncherSettings(menu) = &mut self.state {
+            menu.moving_data_dir = false;
+        }
+        let result = result.and_then(|data_dir| {
+            self.set_data_dir(data_dir)
+                .map_err(|err| err.to_string_with_code())
+        });
+        match result {
+            Ok(()) => self.notify(ToastKind::Success, tr!("toast-data-dir-moved")),
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!("toast-data-dir-move-failed", error = err),
+            ),
+        }
+    }
+
+    pub fn back_up_launcher(&mut self) -> Command<Message> {
+        let State::LauncherSettings(menu) = &mut self.state else {
+            return Command::none();
+        };
+        menu.backing_up = true;
+        Command::perform(
+            launcher_backup::create_backup_wrapped(menu.backup_include_saves),
+            Message::LauncherSettingsBackupEnd,
+        )
+    }
+
+    pub fn finish_launcher_backup(&mut self, result: Result<PathBuf, String>) {
+        if let State::LauncherSettings(menu) = &mut self.state {
+            menu.backing_up = false;
+        }
+        match result {
+            Ok(path) => {
+                if let State::LauncherSettings(menu) = &mut self.state {
+                    menu.restore_input = path.to_string_lossy().to_string();
+                }
+                self.notify(ToastKind::Success, tr!("toast-launcher-backup-done"));
+            }
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!("toast-launcher-backup-failed", error = err),
+            ),
+        }
+    }
+
+    /// Asks to confirm restoring the backup at the typed-in path.
+    pub fn confirm_restoring_backup(&mut self) {
+        let State::LauncherSettings(menu) = &self.state else {
+            return;
+        };
+        let path = PathBuf::from(menu.restore_input.trim());
+        if path.as_os_str().is_empty() {
+            return;
+        }
+        if self
+            .processes
+            .iter()
+            .any(|process| process.exit_status.is_none())
+        {
+            self.notify(
+                ToastKind::Error,
+                tr!("toast-launcher-restore-games-running"),
+            );
+            return;
+        }
+        self.state = State::Confirm(MenuConfirm {
+            action: DestructiveAction::RestoreBackup { path },
+            input: String::new(),
+        });
+    }
+
+    fn restore_backup(&mut self, path: PathBuf) -> Command<Message> {
+        self.go_to_launcher_settings();
+        if let State::LauncherSettings(menu) = &mut self.state {
+            menu.restore_input = path.to_string_lossy().to_string();
+            menu.restoring = true;
+        }
+        Command::perform(
+            launcher_backup::restore_backup_wrapped(path),
+            Message::LauncherSettingsRestoreEnd,
+        )
+    }
+
+    /// Reloads the restored config and instances. The data
+    /// directory is kept, as the backup was unpacked into it.
+    pub fn finish_restoring_backup(&mut self, result: Result<(), String>) {
+        if let State::LauncherSettings(menu) = &mut self.state {
+            menu.restoring = false;
+        }
+        let result = result.and_then(|()| {
+            let data_dir = self.config.as_ref().and_then(|n| n.data_dir.clone());
+            self.config = Some(LauncherConfig::load().map_err(|err| err.to_string_with_code())?);
+            self.edit_config(|config| config.data_dir = data_dir);
+            self.instances =
+                Some(Launcher::load_instances().map_err(|err| err.to_string_with_code())?);
+            Ok(())
+        });
+        match result {
+            Ok(()) => self.notify(ToastKind::Success, tr!("toast-launcher-restore-done")),
+            Err(err) => self.notify(
+                ToastKind::Error,
+                tr!("toast-launcher-restore-failed", error = err),
+            ),
+        }
+    }
+
+    /// Puts a broken config file back to its last working
+    /// copy, then reloads everything that might have used it.
+    /// Downloads the version details of the instance that
+    /// failed to launch, so it can be launched again.
+    pub fn redownload_version_json(&mut self) -> Command<Message> {
+        let State::Error {
+            instance: Some(instance),
+            ..
+        } = &self.state
+        else {
+            return Command::none();
+        };
+        let instance = instance.clone();
+        self.notify(ToastKind::Info, tr!("toast-version-json-downloading"));
+        Command::perform(
+            quantum_launcher_backend::redownload_version_json_wrapped(instance.clone()),
+            move |result| Message::ErrorRedownloadVersionJsonEnd(instance.clone(), result),
+        )
+    }
+
+    pub fn finish_redownloading_version_json(
+        &mut self,
+        instance: String,
+        result: Result<(), String>,
+    ) -> Command<Message> {
+        if let Err(err) = result {
+            self.set_error(err);
+            return Command::none();
+        }
+        self.notify(ToastKind::Success, tr!("toast-version-json-downloaded"));
+        self.state = State::Launch(MenuLaunch {
+            selected_instance: Some(instance),
+            ..Default::default()
+        });
+        self.launch_game()
+    }
+
+    pub fn restore_broken_file(&mut self) {
+        let State::Error {
+            restore: Some(path),
+            ..
+        } = &self.state
+        else {
+            return;
+        };
+        if let Err(err) = validation::restore_backup(path) {
+            self.set_error(err.to_string_with_code());
+            return;
+        }
+
+        match LauncherConfig::load() {
+            Ok(config) => self.config = Some(config),
+            Err(err) => {
+                self.set_launcher_error(&err);
+                return;
+            }
+        }
+        match Launcher::load_instances() {
+            Ok(instances) => self.instances = Some(instances),
+            Err(err) => {
+                self.set_launcher_error(&err);
+                return;
+            }
+        }
+        self.go_to_launch_screen();
+        self.notify(ToastKind::Success, tr!("toast-file-restored"));
+    }
+
+    /// Edits the launcher config and saves it immediately.
+    pub fn edit_config(&mut self, edit: impl FnOnce(&mut LauncherConfig)) {
+        let Some(config) = &mut self.config else {
+            return;
+        };
+        edit(config);
+        config.apply_download_settings();
+        if let Err(err) = config.save() {
+            self.notify(
+                ToastKind::Error,
+                tr!("toast-config-save-failed", error = err),
+            );
+        }
+    }
+
+    pub fn add_java_install(&mut self) {
+        if let State::LauncherSettings(menu) = &mut self.state {
+            let java_install = std::mem::take(&mut menu.java_install_input);
+            if !java_install.is_empty() {
+                self.edit_config(|config| config.java_installs.push(java_install));
+            }
+        }
+    }
+
+    /// Lists the unused Java installs for the user to
+    /// confirm deleting, if there are any.
+    pub fn confirm_java_cleanup(&mut self, result: Result<Vec<String>, String>) {
+        let State::LauncherSettings(menu) = &mut self.state else {
+            return;
+        };
+        match result {
+            Ok(installs) if installs.is_empty() => {
+                menu.java_cleanup_status = Some(tr!("settings-java-cleanup-none"));
+            }
+            Ok(installs) => {
+                menu.java_cleanup_status = None;
+                self.state = State::Confirm(MenuConfirm {
+                    action: DestructiveAction::DeleteJavaInstalls { installs },
+                    input: String::new(),
+                });
+            }
+            Err(err) => {
+                error!("Could not find unused Java installs: {err}");
+                menu.java_cleanup_status = Some(tr!("settings-java-cleanup-failed", error = err));
+            }
+        }
+    }
+
+    pub fn finish_java_cleanup(&mut self, result: Result<Vec<String>, String>) {
+        let status = match result {
+            Ok(removed) if removed.is_empty() => tr!("settings-java-cleanup-none"),
+            Ok(removed) => {
+                info!("Removed unused Java installs: {removed:?}");
+                tr!(
+                    "settings-java-cleanup-removed",
+                    removed = removed.join(", ")
+                )
+            }
+            Err(err) => {
+                error!("Could not remove unused Java installs: {err}");
+                tr!("settings-java-cleanup-failed", error = err)
+            }
+        };
+        if let State::LauncherSettings(menu) = &mut self.state {
+            menu.java_cleanup_status = Some(status);
+        }
+    }
+}
+
+fn load_versions(source: VersionSource) -> Command<Message> {
+    Command::perform(
+        quantum_launcher_backend::list_versions(source),
+        move |result| Message::CreateInstanceVersionsLoaded(source, result),
+    )
+}
+
+fn get_mods_dir(instance: &str) -> LauncherResult<PathBuf> {
+    Ok(quantum_launcher_backend::get_game_dir(instance)?.join("mods"))
+}
+
+/// The sorted names of the files in `dir`, which may not exist yet.
+fn list_files(dir: &Path) -> LauncherResult<Vec<String>> {
+    let mut files: Vec<String> = match std::fs::read_dir(dir) {
+        Ok(dir) => dir
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                entry.path().is_file().then_some(())?;
+                entry.file_name().to_str().map(str::to_owned)
+            })
+            .collect(),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(err) => return Err(io_err!(dir.to_owned())(err).into()),
+    };
+    files.sort();
+    Ok(files)
+}
+
+fn delete_plugin(server: &str, file_name: &str) -> Result<(), String> {
+    let plugins_dir = server_files::get_server_dir(server)
+        .map_err(|err| err.to_string())?
+        .join("plugins");
+    let plugin_path = plugins_dir.join(file_name);
+    // Make sure a weird file name can't delete something outside the plugins folder.
+    if plugin_path.parent() != Some(plugins_dir.as_path()) {
+        return Err(tr!("delete-outside-launcher"));
+    }
+    std::fs::remove_file(&plugin_path)
+        .map_err(io_err!(plugin_path))
+        .map_err(|err| err.to_string())
+}
+
+/// The translated version of the backend's plugin install progress text.
+pub fn plugin_progress_text(progress: &ModInstallProgress) -> String {
+    match progress {
+        ModInstallProgress::Resolving { name } => tr!("plugins-progress-resolving", name = name),
+        ModInstallProgress::Downloading { file_name } => {
+            tr!("plugins-progress-downloading", file_name = file_name)
+        }
+        ModInstallProgress::Done => tr!("plugins-progress-done"),
+    }
+}
+
+fn delete_mod(instance: &str, file_name: &str) -> Result<(), String> {
+    let mods_dir = get_mods_dir(instance).map_err(|err| err.to_string())?;
+    let mod_path = mods_dir.join(file_name);
+    // Make sure a weird file name can't delete something outside the mods folder.
+    if mod_path.parent() != Some(mods_dir.as_path()) {
+        return Err(tr!("delete-outside-launcher"));
+    }
+    std::fs::remove_file(&mod_path)
+        .map_err(io_err!(mod_path))
+        .map_err(|err| err.to_string())?;
+
+    let config_path = file_utils::get_launcher_dir()
+        .map_err(|err| err.to_string_with_code())?
+        .join("instances")
+        .join(instance)
+        .join("config.json");
+    let mut config =
+        InstanceConfigJson::read(&config_path).map_err(|err| err.to_string_with_code())?;
+    if config.managed_mod(file_name).is_some() {
+        config.forget_mod(file_name);
+        config
+            .save(&config_path)
+            .map_err(|err| err.to_string_with_code())?;
+    }
+    Ok(())
+}
+
+/// The translated version of the backend's Fabric install progress text.
+pub fn fabric_progress_text(progress: &FabricInstallProgress) -> String {
+    match progress {
+        FabricInstallProgress::DownloadingJson => tr!("fabric-progress-json"),
+        FabricInstallProgress::DownloadingLibrary {
+            progress,
+            out_of,
+            name,
+        } => tr!(
+            "fabric-progress-library",
+            progress = progress + 1,
+            out_of = out_of,
+            name = name
+        ),
+        FabricInstallProgress::Done => tr!("fabric-progress-done"),
+    }
+}
+
+/// The translated version of the backend's progress text.
+pub fn download_progress_text(progress: &DownloadProgress) -> String {
+    match progress {
+        DownloadProgress::Started => tr!("progress-started"),
+        DownloadProgress::DownloadingJsonManifest => tr!("progress-manifest"),
+        DownloadProgress::DownloadingVersionJson => tr!("progress-version-json"),
+        DownloadProgress::DownloadingAssets { progress, out_of } => {
+            tr!("progress-assets", progress = progress, out_of = out_of)
+        }
+        DownloadProgress::DownloadingLibraries { progress, out_of } => {
+            tr!("progress-libraries", progress = progress, out_of = out_of)
+        }
+        DownloadProgress::DownloadingJar => tr!("progress-jar"),
+        DownloadProgress::DownloadingLoggingConfig => tr!("progress-logging-config"),
+    }
+}
+
+/// Turns an empty text box into `None`.
+pub fn non_empty(input: String) -> Option<String> {
+    (!input.is_empty()).then_some(input)
+}
+
+/// Formats a Unix timestamp as "5 minutes ago", "3 days ago", etc.
+pub fn format_last_played(last_played: Option<u64>) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+
+    let Some(last_played) = last_played else {
+        return tr!("launch-card-never-played");
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let elapsed = now.saturating_sub(last_played);
+
+    if elapsed < MINUTE {
+        tr!("launch-card-played-now")
+    } else if elapsed < HOUR {
+        tr!("launch-card-played-minutes", count = elapsed / MINUTE)
+    } else if elapsed < DAY {
+        tr!("launch-card-played-hours", count = elapsed / HOUR)
+    } else {
+        tr!("launch-card-played-days", count = elapsed / DAY)
+    }
+}
+
+/// Seconds since the Unix epoch at the moment `instant` was.
+fn unix_time_of(instant: Instant) -> u64 {
+    SystemTime::now()
+        .checked_sub(instant.elapsed())
+        .and_then(|n| n.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |n| n.as_secs())
+}
+
+/// Formats a total playtime like `12 h 5 min`.
+pub fn format_playtime(seconds: u64) -> String {
+    tr!(
+        "stats-time",
+        hours = seconds / 3600,
+        minutes = (seconds / 60) % 60
+    )
+}
+
+/// Formats a duration like `1:05:03`, or `5:03` if under an hour.
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, (seconds / 60) % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+/// Formats a size in bytes, like `1.50 MB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.2} {}", UNITS[unit])
     }
 }
 
@@ -238,6 +3982,8 @@ pub fn format_memory(memory_bytes: usize) -> String {
     }
 }
 
+// The file explorer outlives the launcher, so it's never waited on.
+#[allow(clippy::zombie_processes)]
 pub fn open_file_explorer(path: &str) {
     use std::process::Command;
 
@@ -257,5 +4003,5 @@ pub fn open_file_explorer(path: &str) {
     }
 
     #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
-    eprintln!("[error] Opening file explorer not supported on this platform.")
+    error!("Opening file explorer not supported on this platform.")
 }
@@ -0,0 +1,167 @@
+//! The search palette (Ctrl + K), for finding instances by
+//! name or version, and the instances with a mod or world,
+//! then jumping straight to them.
+
+use iced::widget::text_input;
+use quantum_launcher_backend::InstanceInfo;
+
+/// How many results are shown at once.
+const MAX_RESULTS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SearchTarget {
+    Instance,
+    Mod,
+    World,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchEntry {
+    pub target: SearchTarget,
+    pub instance: String,
+    /// What's searched for, like the mod's file name.
+    pub title: String,
+    /// Also searched, like the instance's version.
+    pub detail: String,
+}
+
+pub struct SearchPalette {
+    pub query: String,
+    /// The highlighted result, picked with Enter.
+    pub selected: usize,
+    entries: Vec<SearchEntry>,
+}
+
+impl SearchPalette {
+    pub fn input_id() -> text_input::Id {
+        text_input::Id::new("search")
+    }
+
+    /// Builds the index from the instances, their mods and worlds.
+    pub fn open(instances: &[InstanceInfo]) -> Self {
+        let mut entries = Vec::new();
+        for instance in instances {
+            let detail = match (&instance.version, &instance.mod_type) {
+                (Some(version), Some(mod_type)) if mod_type != "Vanilla" => {
+                    format!("{version} {mod_type}")
+                }
+                (Some(version), _) => version.clone(),
+                (None, _) => String::new(),
+            };
+            entries.push(SearchEntry {
+                target: SearchTarget::Instance,
+                instance: instance.name.clone(),
+                title: instance.name.clone(),
+                detail,
+            });
+
+            let Ok(dot_minecraft) = quantum_launcher_backend::get_game_dir(&instance.name) else {
+                continue;
+            };
+            for (target, folder, is_dir) in [
+                (SearchTarget::Mod, "mods", false),
+                (SearchTarget::World, "saves", true),
+            ] {
+                let Ok(dir) = std::fs::read_dir(dot_minecraft.join(folder)) else {
+                    continue;
+                };
+                entries.extend(dir.filter_map(|entry| {
+                    let entry = entry.ok()?;
+                    (entry.path().is_dir() == is_dir).then_some(())?;
+                    Some(SearchEntry {
+                        target,
+                        instance: instance.name.clone(),
+                        title: entry.file_name().to_str()?.to_owned(),
+                        detail: instance.name.clone(),
+                    })
+                }));
+            }
+        }
+
+        Self {
+            query: String::new(),
+            selected: 0,
+            entries,
+        }
+    }
+
+    /// The entries matching every word of the query, best first:
+    /// names starting with the query, then instances before
+    /// mods and worlds.
+    pub fn results(&self) -> Vec<&SearchEntry> {
+        let query = self.query.to_lowercase();
+        let words: Vec<&str> = query.split_whitespace().collect();
+        if words.is_empty() {
+            return Vec::new();
+        }
+
+        let mut results: Vec<&SearchEntry> = self
+            .entries
+            .iter()
+            .filter(|entry| {
+                let text = format!("{} {}", entry.title, entry.detail).to_lowercase();
+                words.iter().all(|word| text.contains(word))
+            })
+            .collect();
+        results.sort_by_key(|entry| {
+            (
+                !entry.title.to_lowercase().starts_with(words[0]),
+                entry.target,
+                entry.title.to_lowercase(),
+            )
+        });
+        results.truncate(MAX_RESULTS);
+        results
+    }
+
+    /// Moves the highlight up or down, wrapping around.
+    pub fn move_selection(&mut self, next: bool) {
+        let count = self.results().len();
+        if count == 0 {
+            return;
+        }
+        self.selected = if next {
+            (self.selected + 1) % count
+        } else {
+            (self.selected + count - 1) % count
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(target: SearchTarget, title: &str, detail: &str) -> SearchEntry {
+        SearchEntry {
+            target,
+            instance: detail.to_owned(),
+            title: title.to_owned(),
+            detail: detail.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_search_results() {
+        let mut palette = SearchPalette {
+            query: String::new(),
+            selected: 0,
+            entries: vec![
+                entry(SearchTarget::World, "Sodium Test World", "Survival"),
+                entry(SearchTarget::Mod, "sodium-fabric-0.5.8.jar", "Survival"),
+                entry(SearchTarget::Instance, "Survival", "1.20.4 Fabric"),
+                entry(SearchTarget::Instance, "Old", "b1.7.3"),
+            ],
+        };
+        assert!(palette.results().is_empty());
+
+        palette.query = "sodium".to_owned();
+        let titles: Vec<&str> = palette.results().iter().map(|n| n.title.as_str()).collect();
+        assert_eq!(titles, ["sodium-fabric-0.5.8.jar", "Sodium Test World"]);
+
+        // Every word has to match, in the name or the details.
+        palette.query = "survival fabric".to_owned();
+        let titles: Vec<&str> = palette.results().iter().map(|n| n.title.as_str()).collect();
+        assert_eq!(titles, ["Survival", "sodium-fabric-0.5.8.jar"]);
+    }
+}
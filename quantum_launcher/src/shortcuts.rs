@@ -0,0 +1,91 @@
+//! Keyboard shortcuts for the launcher.
+//!
+//! Keys are only handled when no widget (like a focused
+//! text box) has already used them.
+
+use iced::{
+    keyboard::{self, key::Named, Key, Modifiers},
+    Subscription,
+};
+
+use crate::{launcher_state::Message, tr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shortcut {
+    SelectPreviousInstance,
+    SelectNextInstance,
+    Launch,
+    NewInstance,
+    Back,
+    Search,
+    ToggleHelp,
+}
+
+impl Shortcut {
+    pub const ALL: [Shortcut; 7] = [
+        Shortcut::SelectPreviousInstance,
+        Shortcut::SelectNextInstance,
+        Shortcut::Launch,
+        Shortcut::NewInstance,
+        Shortcut::Back,
+        Shortcut::Search,
+        Shortcut::ToggleHelp,
+    ];
+
+    fn from_key(key: &Key, modifiers: Modifiers) -> Option<Self> {
+        match key.as_ref() {
+            Key::Named(Named::ArrowUp) => Some(Shortcut::SelectPreviousInstance),
+            Key::Named(Named::ArrowDown) => Some(Shortcut::SelectNextInstance),
+            Key::Named(Named::Enter) => Some(Shortcut::Launch),
+            Key::Named(Named::Escape) => Some(Shortcut::Back),
+            Key::Named(Named::F1) => Some(Shortcut::ToggleHelp),
+            Key::Character("n") if modifiers.command() => Some(Shortcut::NewInstance),
+            Key::Character("k") if modifiers.command() => Some(Shortcut::Search),
+            Key::Character("?") => Some(Shortcut::ToggleHelp),
+            _ => None,
+        }
+    }
+
+    /// The keys for this shortcut, as shown in the help panel.
+    pub fn keys(self) -> String {
+        match self {
+            Shortcut::SelectPreviousInstance => tr!("shortcut-key-up"),
+            Shortcut::SelectNextInstance => tr!("shortcut-key-down"),
+            Shortcut::Launch => tr!("shortcut-key-enter"),
+            Shortcut::NewInstance => {
+                if cfg!(target_os = "macos") {
+                    "Cmd + N".to_owned()
+                } else {
+                    "Ctrl + N".to_owned()
+                }
+            }
+            Shortcut::Back => tr!("shortcut-key-escape"),
+            Shortcut::Search => {
+                if cfg!(target_os = "macos") {
+                    "Cmd + K".to_owned()
+                } else {
+                    "Ctrl + K".to_owned()
+                }
+            }
+            Shortcut::ToggleHelp => "F1 / ?".to_owned(),
+        }
+    }
+
+    pub fn description(self) -> String {
+        match self {
+            Shortcut::SelectPreviousInstance => tr!("shortcut-previous-instance"),
+            Shortcut::SelectNextInstance => tr!("shortcut-next-instance"),
+            Shortcut::Launch => tr!("shortcut-launch"),
+            Shortcut::NewInstance => tr!("shortcut-new-instance"),
+            Shortcut::Back => tr!("shortcut-back"),
+            Shortcut::Search => tr!("shortcut-search"),
+            Shortcut::ToggleHelp => tr!("shortcut-toggle-help"),
+        }
+    }
+}
+
+pub fn subscription() -> Subscription<Message> {
+    keyboard::on_key_press(|key, modifiers| {
+        Shortcut::from_key(&key, modifiers).map(Message::Shortcut)
+    })
+}
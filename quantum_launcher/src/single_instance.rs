@@ -0,0 +1,172 @@
+//! Making sure only one launcher runs at a time, as two
+//! would overwrite each other's config and instance files.
+//!
+//! The first launcher listens on a local port, written to
+//! `QuantumLauncher/launcher.lock`. Starting another one
+//! sends its arguments there instead, which brings the
+//! running launcher's window to the front.
+
+use std::{
+    io::{BufRead, BufReader, ErrorKind, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    path::PathBuf,
+    sync::mpsc::{Receiver, Sender},
+    time::Duration,
+};
+
+use quantum_launcher_backend::file_utils;
+use tracing::{error, info};
+
+/// Sent first, so that other programs on the
+/// port aren't mistaken for a launcher.
+const HANDSHAKE: &str = "quantum_launcher";
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Held by the running launcher. The lock file
+/// is deleted when it's dropped.
+pub struct InstanceGuard {
+    lock_path: PathBuf,
+    /// The arguments of each launcher started since.
+    receiver: Receiver<Vec<String>>,
+}
+
+impl InstanceGuard {
+    /// Becomes the running launcher, or hands `args`
+    /// to the one already running and returns `None`.
+    ///
+    /// If anything goes wrong, like the config directory
+    /// being read-only, the launcher starts anyway.
+    pub fn acquire(args: &[String]) -> Option<Self> {
+        let lock_path = match file_utils::get_config_dir() {
+            Ok(dir) => dir.join("launcher.lock"),
+            Err(err) => {
+                error!("Could not check for a running launcher: {err}");
+                return Some(Self::unlocked());
+            }
+        };
+
+        // A lock file whose launcher doesn't answer was left
+        // behind by a crash, so it's taken over.
+        for _ in 0..2 {
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&lock_path)
+            {
+                Ok(file) => return Some(Self::listen(lock_path, file)),
+                Err(err) if err.kind() == ErrorKind::AlreadyExists => {
+                    if forward_args(&lock_path, args).is_ok() {
+                        info!("The launcher is already running, showing it instead");
+                        return None;
+                    }
+                    _ = std::fs::remove_file(&lock_path);
+                }
+                Err(err) => {
+                    error!("Could not create {lock_path:?}: {err}");
+                    break;
+                }
+            }
+        }
+        Some(Self::unlocked())
+    }
+
+    /// A guard that guards nothing, for when the lock can't be made.
+    fn unlocked() -> Self {
+        Self {
+            lock_path: PathBuf::new(),
+            receiver: std::sync::mpsc::channel().1,
+        }
+    }
+
+    fn listen(lock_path: PathBuf, mut file: std::fs::File) -> Self {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .and_then(|listener| Ok((listener.local_addr()?.port(), listener)))
+        {
+            Ok((port, listener)) => {
+                if let Err(err) = writeln!(file, "{port}") {
+                    error!("Could not write {lock_path:?}: {err}");
+                }
+                listener
+            }
+            Err(err) => {
+                error!("Could not listen for other launchers: {err}");
+                return Self {
+                    lock_path,
+                    receiver,
+                };
+            }
+        };
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if let Err(err) = receive_args(stream, &sender) {
+                    error!("Could not talk to another launcher: {err}");
+                }
+            }
+        });
+        Self {
+            lock_path,
+            receiver,
+        }
+    }
+
+    /// The arguments of launchers started since the last call.
+    pub fn poll(&self) -> Vec<Vec<String>> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+impl Drop for InstanceGuard {
+    fn drop(&mut self) {
+        if !self.lock_path.as_os_str().is_empty() {
+            _ = std::fs::remove_file(&self.lock_path);
+        }
+    }
+}
+
+fn forward_args(lock_path: &std::path::Path, args: &[String]) -> std::io::Result<()> {
+    let mut port = std::fs::read_to_string(lock_path)?;
+    if port.is_empty() {
+        // The other launcher may have only just made the file.
+        std::thread::sleep(Duration::from_millis(300));
+        port = std::fs::read_to_string(lock_path)?;
+    }
+    let port: u16 = port
+        .trim()
+        .parse()
+        .map_err(|_| std::io::Error::new(ErrorKind::InvalidData, "invalid lock file"))?;
+
+    let mut stream = TcpStream::connect_timeout(&(Ipv4Addr::LOCALHOST, port).into(), TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    writeln!(stream, "{HANDSHAKE}")?;
+    writeln!(stream, "{}", serde_json::to_string(args)?)?;
+
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    if reply.trim() == HANDSHAKE {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            ErrorKind::InvalidData,
+            "not a launcher",
+        ))
+    }
+}
+
+fn receive_args(stream: TcpStream, sender: &Sender<Vec<String>>) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    if line.trim() != HANDSHAKE {
+        return Ok(());
+    }
+    line.clear();
+    reader.read_line(&mut line)?;
+    let args: Vec<String> = serde_json::from_str(&line)?;
+
+    writeln!(&stream, "{HANDSHAKE}")?;
+    _ = sender.send(args);
+    Ok(())
+}
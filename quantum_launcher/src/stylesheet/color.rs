@@ -1,11 +1,18 @@
-use super::styles::{BORDER_RADIUS, BORDER_WIDTH};
+use std::fmt::Display;
 
+use serde::{Deserialize, Serialize};
+
+use crate::tr;
+
+#[derive(Clone, Copy)]
 pub struct Pallete {
     dark: [u8; 3],
     second_dark: [u8; 3],
     mid: [u8; 3],
     second_light: [u8; 3],
     light: [u8; 3],
+    /// The color with the most contrast to `dark`.
+    white: [u8; 3],
 }
 
 pub const DARK_PURPLE: Pallete = Pallete {
@@ -14,8 +21,88 @@ pub const DARK_PURPLE: Pallete = Pallete {
     mid: [0xc4, 0x7a, 0xc0],
     light: [0xe3, 0x9e, 0xc1],
     second_light: [0xde, 0xba, 0xc0],
+    white: [0xff, 0xff, 0xff],
+};
+
+/// The light theme is the dark one turned inside out,
+/// so `dark` is the background and `light` is the text.
+pub const LIGHT_PURPLE: Pallete = Pallete {
+    dark: [0xf6, 0xf0, 0xf5],
+    second_dark: [0xdc, 0xc1, 0xdb],
+    mid: [0xc4, 0x7a, 0xc0],
+    light: [0x4a, 0x2d, 0x4c],
+    second_light: [0x77, 0x56, 0x7a],
+    white: [0x1a, 0x10, 0x1c],
+};
+
+/// Pure black and white with a yellow highlight, for the
+/// high contrast accessibility option. The accent color is ignored.
+pub const HIGH_CONTRAST_DARK: Pallete = Pallete {
+    dark: [0x00, 0x00, 0x00],
+    second_dark: [0x1a, 0x1a, 0x1a],
+    mid: [0xff, 0xff, 0x00],
+    light: [0xff, 0xff, 0xff],
+    second_light: [0xff, 0xff, 0x00],
+    white: [0xff, 0xff, 0xff],
 };
 
+pub const HIGH_CONTRAST_LIGHT: Pallete = Pallete {
+    dark: [0xff, 0xff, 0xff],
+    second_dark: [0xe6, 0xe6, 0xe6],
+    mid: [0x00, 0x00, 0xc0],
+    light: [0x00, 0x00, 0x00],
+    second_light: [0x00, 0x00, 0x7a],
+    white: [0x00, 0x00, 0x00],
+};
+
+/// The main color of the launcher, chosen in the settings.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AccentColor {
+    #[default]
+    Purple,
+    Blue,
+    Teal,
+    Green,
+    Orange,
+    Red,
+}
+
+impl AccentColor {
+    pub const ALL: [AccentColor; 6] = [
+        AccentColor::Purple,
+        AccentColor::Blue,
+        AccentColor::Teal,
+        AccentColor::Green,
+        AccentColor::Orange,
+        AccentColor::Red,
+    ];
+
+    fn rgb(self) -> [u8; 3] {
+        match self {
+            AccentColor::Purple => DARK_PURPLE.mid,
+            AccentColor::Blue => [0x6a, 0x8f, 0xd8],
+            AccentColor::Teal => [0x4f, 0xb3, 0xaa],
+            AccentColor::Green => [0x7d, 0xb8, 0x5f],
+            AccentColor::Orange => [0xe0, 0x93, 0x4f],
+            AccentColor::Red => [0xd8, 0x63, 0x63],
+        }
+    }
+}
+
+impl Display for AccentColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            AccentColor::Purple => tr!("settings-accent-purple"),
+            AccentColor::Blue => tr!("settings-accent-blue"),
+            AccentColor::Teal => tr!("settings-accent-teal"),
+            AccentColor::Green => tr!("settings-accent-green"),
+            AccentColor::Orange => tr!("settings-accent-orange"),
+            AccentColor::Red => tr!("settings-accent-red"),
+        };
+        write!(f, "{name}")
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Clone, Copy)]
 pub enum Color {
@@ -36,27 +123,58 @@ impl IntoIcedColor for [u8; 3] {
     }
 }
 
+/// Blends `a` into `b`, where `amount` 0.0 is fully `a` and 1.0 is fully `b`.
+fn mix(a: [u8; 3], b: [u8; 3], amount: f32) -> [u8; 3] {
+    let channel = |i: usize| (a[i] as f32 + (b[i] as f32 - a[i] as f32) * amount).round() as u8;
+    [channel(0), channel(1), channel(2)]
+}
+
 impl Pallete {
-    pub fn get(self, color: Color) -> iced::Color {
+    /// Builds the palette for an accent color.
+    ///
+    /// Purple uses the hand-picked palettes, the others
+    /// are generated by blending the accent with the
+    /// background and text colors.
+    pub fn new(accent: AccentColor, is_light: bool) -> Self {
+        let base = if is_light { LIGHT_PURPLE } else { DARK_PURPLE };
+        if accent == AccentColor::Purple {
+            return base;
+        }
+
+        let accent = accent.rgb();
+        if is_light {
+            Self {
+                dark: mix(base.dark, accent, 0.05),
+                second_dark: mix(base.dark, accent, 0.4),
+                mid: accent,
+                light: mix(accent, base.white, 0.65),
+                second_light: mix(accent, base.white, 0.4),
+                white: base.white,
+            }
+        } else {
+            Self {
+                dark: base.dark,
+                second_dark: mix(base.dark, accent, 0.5),
+                mid: accent,
+                light: mix(accent, base.white, 0.3),
+                second_light: mix(accent, base.white, 0.55),
+                white: base.white,
+            }
+        }
+    }
+
+    pub fn get(&self, color: Color) -> iced::Color {
         match color {
             Color::Dark => self.dark.into_color(),
             Color::SecondDark => self.second_dark.into_color(),
             Color::Light => self.light.into_color(),
             Color::SecondLight => self.second_light.into_color(),
             Color::Mid => self.mid.into_color(),
-            Color::White => [255u8, 255u8, 255u8].into_color(),
+            Color::White => self.white.into_color(),
         }
     }
 
-    pub fn get_bg(self, color: Color) -> iced::Background {
+    pub fn get_bg(&self, color: Color) -> iced::Background {
         iced::Background::Color(self.get(color))
     }
-
-    pub fn get_border(self, color: Color) -> iced::Border {
-        iced::Border {
-            color: self.get(color),
-            width: BORDER_WIDTH,
-            radius: BORDER_RADIUS.into(),
-        }
-    }
 }
@@ -1,30 +1,122 @@
+use std::{
+    fmt::Display,
+    sync::atomic::{AtomicU16, Ordering},
+};
+
 use iced::widget;
+use serde::{Deserialize, Serialize};
 
-use super::color::{Color, DARK_PURPLE};
+use crate::tr;
 
-pub const BORDER_WIDTH: f32 = 2.0;
-pub const BORDER_RADIUS: f32 = 8.0;
+use super::color::{AccentColor, Color, Pallete, HIGH_CONTRAST_DARK, HIGH_CONTRAST_LIGHT};
 
-#[allow(dead_code)]
-#[derive(Clone, Default)]
-pub enum LauncherTheme {
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum LauncherThemeLightness {
     Light,
     #[default] // Highly opinionated, I know.
     Dark,
 }
 
+/// The shape of the widgets, chosen in the settings.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StylePreset {
+    #[default]
+    Rounded,
+    Flat,
+}
+
+impl StylePreset {
+    pub const ALL: [StylePreset; 2] = [StylePreset::Rounded, StylePreset::Flat];
+
+    pub fn border_radius(self) -> f32 {
+        match self {
+            StylePreset::Rounded => 8.0,
+            StylePreset::Flat => 0.0,
+        }
+    }
+
+    pub fn border_width(self) -> f32 {
+        match self {
+            StylePreset::Rounded => 2.0,
+            StylePreset::Flat => 1.0,
+        }
+    }
+}
+
+impl Display for StylePreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            StylePreset::Rounded => tr!("settings-style-rounded"),
+            StylePreset::Flat => tr!("settings-style-flat"),
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The look of the whole launcher. Every widget's
+/// appearance is derived from this in one place.
+#[derive(Clone, Default)]
+pub struct LauncherTheme {
+    pub lightness: LauncherThemeLightness,
+    pub accent: AccentColor,
+    pub style_preset: StylePreset,
+    pub high_contrast: bool,
+    /// Avoid widgets changing size while in use.
+    pub reduce_motion: bool,
+}
+
+impl LauncherTheme {
+    pub fn palette(&self) -> Pallete {
+        let is_light = self.lightness == LauncherThemeLightness::Light;
+        match (self.high_contrast, is_light) {
+            (true, true) => HIGH_CONTRAST_LIGHT,
+            (true, false) => HIGH_CONTRAST_DARK,
+            (false, _) => Pallete::new(self.accent, is_light),
+        }
+    }
+
+    pub fn get_border(&self, color: Color) -> iced::Border {
+        if self.high_contrast {
+            // Every widget gets a thick outline in the text color,
+            // so its edges are visible against the background.
+            return iced::Border {
+                color: self.palette().get(Color::Light),
+                width: self.style_preset.border_width() + 1.0,
+                radius: self.style_preset.border_radius().into(),
+            };
+        }
+        iced::Border {
+            color: self.palette().get(color),
+            width: self.style_preset.border_width(),
+            radius: self.style_preset.border_radius().into(),
+        }
+    }
+}
+
+static MIN_TEXT_SIZE: AtomicU16 = AtomicU16::new(0);
+
+/// Sets the smallest size [`text_size`] will return,
+/// for the large text accessibility option.
+pub fn set_min_text_size(size: u16) {
+    MIN_TEXT_SIZE.store(size, Ordering::Relaxed);
+}
+
+/// The size for small text (like hints and details),
+/// raised to the minimum if large text is enabled.
+pub fn text_size(size: u16) -> u16 {
+    size.max(MIN_TEXT_SIZE.load(Ordering::Relaxed))
+}
+
 impl widget::container::StyleSheet for LauncherTheme {
     type Style = LauncherTheme;
 
-    fn appearance(&self, style: &Self::Style) -> widget::container::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => widget::container::Appearance {
-                text_color: Some(DARK_PURPLE.get(Color::Light)),
-                background: Some(iced::Background::Color(DARK_PURPLE.get(Color::Dark))),
-                border: DARK_PURPLE.get_border(Color::SecondDark),
-                ..Default::default()
-            },
+    fn appearance(&self, _style: &Self::Style) -> widget::container::Appearance {
+        let palette = self.palette();
+        widget::container::Appearance {
+            text_color: Some(palette.get(Color::Light)),
+            background: Some(iced::Background::Color(palette.get(Color::Dark))),
+            border: self.get_border(Color::SecondDark),
+            ..Default::default()
         }
     }
 }
@@ -32,51 +124,43 @@ impl widget::container::StyleSheet for LauncherTheme {
 impl widget::button::StyleSheet for LauncherTheme {
     type Style = LauncherTheme;
 
-    fn active(&self, style: &Self::Style) -> widget::button::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => widget::button::Appearance {
-                background: Some(iced::Background::Color(DARK_PURPLE.get(Color::SecondDark))),
-                text_color: DARK_PURPLE.get(Color::White),
-                border: DARK_PURPLE.get_border(Color::SecondDark),
-                ..Default::default()
-            },
+    fn active(&self, _style: &Self::Style) -> widget::button::Appearance {
+        let palette = self.palette();
+        widget::button::Appearance {
+            background: Some(iced::Background::Color(palette.get(Color::SecondDark))),
+            text_color: palette.get(Color::White),
+            border: self.get_border(Color::SecondDark),
+            ..Default::default()
         }
     }
 
-    fn hovered(&self, style: &Self::Style) -> widget::button::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => widget::button::Appearance {
-                background: Some(iced::Background::Color(DARK_PURPLE.get(Color::Mid))),
-                text_color: DARK_PURPLE.get(Color::Dark),
-                border: DARK_PURPLE.get_border(Color::Mid),
-                ..Default::default()
-            },
+    fn hovered(&self, _style: &Self::Style) -> widget::button::Appearance {
+        let palette = self.palette();
+        widget::button::Appearance {
+            background: Some(iced::Background::Color(palette.get(Color::Mid))),
+            text_color: palette.get(Color::Dark),
+            border: self.get_border(Color::Mid),
+            ..Default::default()
         }
     }
 
-    fn pressed(&self, style: &Self::Style) -> widget::button::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => widget::button::Appearance {
-                background: Some(iced::Background::Color(DARK_PURPLE.get(Color::White))),
-                text_color: DARK_PURPLE.get(Color::Dark),
-                border: DARK_PURPLE.get_border(Color::White),
-                ..Default::default()
-            },
+    fn pressed(&self, _style: &Self::Style) -> widget::button::Appearance {
+        let palette = self.palette();
+        widget::button::Appearance {
+            background: Some(iced::Background::Color(palette.get(Color::White))),
+            text_color: palette.get(Color::Dark),
+            border: self.get_border(Color::White),
+            ..Default::default()
         }
     }
 
-    fn disabled(&self, style: &Self::Style) -> widget::button::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => widget::button::Appearance {
-                background: Some(iced::Background::Color(DARK_PURPLE.get(Color::SecondDark))),
-                text_color: DARK_PURPLE.get(Color::SecondLight),
-                border: DARK_PURPLE.get_border(Color::SecondDark),
-                ..Default::default()
-            },
+    fn disabled(&self, _style: &Self::Style) -> widget::button::Appearance {
+        let palette = self.palette();
+        widget::button::Appearance {
+            background: Some(iced::Background::Color(palette.get(Color::SecondDark))),
+            text_color: palette.get(Color::SecondLight),
+            border: self.get_border(Color::SecondDark),
+            ..Default::default()
         }
     }
 }
@@ -84,11 +168,8 @@ impl widget::button::StyleSheet for LauncherTheme {
 impl widget::text::StyleSheet for LauncherTheme {
     type Style = LauncherTheme;
 
-    fn appearance(&self, style: Self::Style) -> widget::text::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => widget::text::Appearance { color: None },
-        }
+    fn appearance(&self, _style: Self::Style) -> widget::text::Appearance {
+        widget::text::Appearance { color: None }
     }
 }
 
@@ -97,33 +178,29 @@ impl widget::pick_list::StyleSheet for LauncherTheme {
 
     fn active(
         &self,
-        style: &<Self as widget::pick_list::StyleSheet>::Style,
+        _style: &<Self as widget::pick_list::StyleSheet>::Style,
     ) -> widget::pick_list::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => widget::pick_list::Appearance {
-                text_color: DARK_PURPLE.get(Color::Dark),
-                placeholder_color: DARK_PURPLE.get(Color::SecondDark),
-                handle_color: DARK_PURPLE.get(Color::Dark),
-                background: iced::Background::Color(DARK_PURPLE.get(Color::Light)),
-                border: DARK_PURPLE.get_border(Color::Mid),
-            },
+        let palette = self.palette();
+        widget::pick_list::Appearance {
+            text_color: palette.get(Color::Dark),
+            placeholder_color: palette.get(Color::SecondDark),
+            handle_color: palette.get(Color::Dark),
+            background: iced::Background::Color(palette.get(Color::Light)),
+            border: self.get_border(Color::Mid),
         }
     }
 
     fn hovered(
         &self,
-        style: &<Self as widget::pick_list::StyleSheet>::Style,
+        _style: &<Self as widget::pick_list::StyleSheet>::Style,
     ) -> widget::pick_list::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => widget::pick_list::Appearance {
-                text_color: DARK_PURPLE.get(Color::Dark),
-                placeholder_color: DARK_PURPLE.get(Color::SecondDark),
-                handle_color: DARK_PURPLE.get(Color::Dark),
-                background: DARK_PURPLE.get_bg(Color::SecondLight),
-                border: DARK_PURPLE.get_border(Color::SecondLight),
-            },
+        let palette = self.palette();
+        widget::pick_list::Appearance {
+            text_color: palette.get(Color::Dark),
+            placeholder_color: palette.get(Color::SecondDark),
+            handle_color: palette.get(Color::Dark),
+            background: palette.get_bg(Color::SecondLight),
+            border: self.get_border(Color::SecondLight),
         }
     }
 }
@@ -131,16 +208,14 @@ impl widget::pick_list::StyleSheet for LauncherTheme {
 impl widget::overlay::menu::StyleSheet for LauncherTheme {
     type Style = LauncherTheme;
 
-    fn appearance(&self, style: &Self::Style) -> iced::overlay::menu::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => iced::overlay::menu::Appearance {
-                text_color: DARK_PURPLE.get(Color::White),
-                background: DARK_PURPLE.get_bg(Color::SecondDark),
-                border: DARK_PURPLE.get_border(Color::Mid),
-                selected_text_color: DARK_PURPLE.get(Color::Dark),
-                selected_background: DARK_PURPLE.get_bg(Color::SecondLight),
-            },
+    fn appearance(&self, _style: &Self::Style) -> iced::overlay::menu::Appearance {
+        let palette = self.palette();
+        iced::overlay::menu::Appearance {
+            text_color: palette.get(Color::White),
+            background: palette.get_bg(Color::SecondDark),
+            border: self.get_border(Color::Mid),
+            selected_text_color: palette.get(Color::Dark),
+            selected_background: palette.get_bg(Color::SecondLight),
         }
     }
 }
@@ -148,53 +223,49 @@ impl widget::overlay::menu::StyleSheet for LauncherTheme {
 impl widget::scrollable::StyleSheet for LauncherTheme {
     type Style = LauncherTheme;
 
-    fn active(&self, style: &Self::Style) -> widget::scrollable::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => widget::scrollable::Appearance {
-                container: widget::container::Appearance {
-                    text_color: None,
-                    background: None,
-                    border: DARK_PURPLE.get_border(Color::SecondDark),
-                    shadow: Default::default(),
-                },
-                scrollbar: widget::scrollable::Scrollbar {
-                    background: Some(DARK_PURPLE.get_bg(Color::Dark)),
-                    border: DARK_PURPLE.get_border(Color::SecondDark),
-                    scroller: widget::scrollable::Scroller {
-                        color: DARK_PURPLE.get(Color::White),
-                        border: DARK_PURPLE.get_border(Color::Light),
-                    },
+    fn active(&self, _style: &Self::Style) -> widget::scrollable::Appearance {
+        let palette = self.palette();
+        widget::scrollable::Appearance {
+            container: widget::container::Appearance {
+                text_color: None,
+                background: None,
+                border: self.get_border(Color::SecondDark),
+                shadow: Default::default(),
+            },
+            scrollbar: widget::scrollable::Scrollbar {
+                background: Some(palette.get_bg(Color::Dark)),
+                border: self.get_border(Color::SecondDark),
+                scroller: widget::scrollable::Scroller {
+                    color: palette.get(Color::White),
+                    border: self.get_border(Color::Light),
                 },
-                gap: None,
             },
+            gap: None,
         }
     }
 
     fn hovered(
         &self,
-        style: &Self::Style,
+        _style: &Self::Style,
         _is_mouse_over_scrollbar: bool,
     ) -> widget::scrollable::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => widget::scrollable::Appearance {
-                container: widget::container::Appearance {
-                    text_color: None,
-                    background: None,
-                    border: DARK_PURPLE.get_border(Color::Mid),
-                    shadow: Default::default(),
-                },
-                scrollbar: widget::scrollable::Scrollbar {
-                    background: Some(DARK_PURPLE.get_bg(Color::Dark)),
-                    border: DARK_PURPLE.get_border(Color::SecondDark),
-                    scroller: widget::scrollable::Scroller {
-                        color: DARK_PURPLE.get(Color::White),
-                        border: DARK_PURPLE.get_border(Color::Light),
-                    },
+        let palette = self.palette();
+        widget::scrollable::Appearance {
+            container: widget::container::Appearance {
+                text_color: None,
+                background: None,
+                border: self.get_border(Color::Mid),
+                shadow: Default::default(),
+            },
+            scrollbar: widget::scrollable::Scrollbar {
+                background: Some(palette.get_bg(Color::Dark)),
+                border: self.get_border(Color::SecondDark),
+                scroller: widget::scrollable::Scroller {
+                    color: palette.get(Color::White),
+                    border: self.get_border(Color::Light),
                 },
-                gap: None,
             },
+            gap: None,
         }
     }
 }
@@ -202,64 +273,98 @@ impl widget::scrollable::StyleSheet for LauncherTheme {
 impl widget::text_input::StyleSheet for LauncherTheme {
     type Style = LauncherTheme;
 
-    fn active(&self, style: &Self::Style) -> widget::text_input::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => widget::text_input::Appearance {
-                background: DARK_PURPLE.get_bg(Color::SecondDark),
-                border: DARK_PURPLE.get_border(Color::Mid),
-                icon_color: Default::default(),
-            },
+    fn active(&self, _style: &Self::Style) -> widget::text_input::Appearance {
+        let palette = self.palette();
+        widget::text_input::Appearance {
+            background: palette.get_bg(Color::SecondDark),
+            border: self.get_border(Color::Mid),
+            icon_color: Default::default(),
         }
     }
 
-    fn focused(&self, style: &Self::Style) -> widget::text_input::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => widget::text_input::Appearance {
-                background: DARK_PURPLE.get_bg(Color::SecondDark),
-                border: DARK_PURPLE.get_border(Color::Mid),
-                icon_color: Default::default(),
-            },
+    fn focused(&self, _style: &Self::Style) -> widget::text_input::Appearance {
+        let palette = self.palette();
+        widget::text_input::Appearance {
+            background: palette.get_bg(Color::SecondDark),
+            border: self.get_border(Color::Mid),
+            icon_color: Default::default(),
         }
     }
 
-    fn placeholder_color(&self, style: &Self::Style) -> iced::Color {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => DARK_PURPLE.get(Color::SecondLight),
-        }
+    fn placeholder_color(&self, _style: &Self::Style) -> iced::Color {
+        let palette = self.palette();
+        palette.get(Color::SecondLight)
+    }
+
+    fn value_color(&self, _style: &Self::Style) -> iced::Color {
+        let palette = self.palette();
+        palette.get(Color::White)
+    }
+
+    fn disabled_color(&self, _style: &Self::Style) -> iced::Color {
+        let palette = self.palette();
+        palette.get(Color::SecondDark)
     }
 
-    fn value_color(&self, style: &Self::Style) -> iced::Color {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => DARK_PURPLE.get(Color::White),
+    fn selection_color(&self, _style: &Self::Style) -> iced::Color {
+        let palette = self.palette();
+        palette.get(Color::SecondLight)
+    }
+
+    fn disabled(&self, _style: &Self::Style) -> widget::text_input::Appearance {
+        let palette = self.palette();
+        widget::text_input::Appearance {
+            background: palette.get_bg(Color::Dark),
+            border: self.get_border(Color::SecondDark),
+            icon_color: Default::default(),
         }
     }
+}
 
-    fn disabled_color(&self, style: &Self::Style) -> iced::Color {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => DARK_PURPLE.get(Color::SecondDark),
+impl widget::text_editor::StyleSheet for LauncherTheme {
+    type Style = LauncherTheme;
+
+    fn active(&self, _style: &Self::Style) -> widget::text_editor::Appearance {
+        let palette = self.palette();
+        widget::text_editor::Appearance {
+            background: palette.get_bg(Color::SecondDark),
+            border: self.get_border(Color::Mid),
         }
     }
 
-    fn selection_color(&self, style: &Self::Style) -> iced::Color {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => DARK_PURPLE.get(Color::SecondLight),
+    fn focused(&self, _style: &Self::Style) -> widget::text_editor::Appearance {
+        let palette = self.palette();
+        widget::text_editor::Appearance {
+            background: palette.get_bg(Color::SecondDark),
+            border: self.get_border(Color::Mid),
         }
     }
 
-    fn disabled(&self, style: &Self::Style) -> widget::text_input::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => widget::text_input::Appearance {
-                background: DARK_PURPLE.get_bg(Color::Dark),
-                border: DARK_PURPLE.get_border(Color::SecondDark),
-                icon_color: Default::default(),
-            },
+    fn placeholder_color(&self, _style: &Self::Style) -> iced::Color {
+        let palette = self.palette();
+        palette.get(Color::SecondLight)
+    }
+
+    fn value_color(&self, _style: &Self::Style) -> iced::Color {
+        let palette = self.palette();
+        palette.get(Color::White)
+    }
+
+    fn disabled_color(&self, _style: &Self::Style) -> iced::Color {
+        let palette = self.palette();
+        palette.get(Color::SecondDark)
+    }
+
+    fn selection_color(&self, _style: &Self::Style) -> iced::Color {
+        let palette = self.palette();
+        palette.get(Color::SecondLight)
+    }
+
+    fn disabled(&self, _style: &Self::Style) -> widget::text_editor::Appearance {
+        let palette = self.palette();
+        widget::text_editor::Appearance {
+            background: palette.get_bg(Color::Dark),
+            border: self.get_border(Color::SecondDark),
         }
     }
 }
@@ -267,14 +372,25 @@ impl widget::text_input::StyleSheet for LauncherTheme {
 impl widget::progress_bar::StyleSheet for LauncherTheme {
     type Style = LauncherTheme;
 
-    fn appearance(&self, style: &Self::Style) -> widget::progress_bar::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => widget::progress_bar::Appearance {
-                background: DARK_PURPLE.get_bg(Color::SecondDark),
-                bar: DARK_PURPLE.get_bg(Color::Light),
-                border_radius: BORDER_RADIUS.into(),
-            },
+    fn appearance(&self, _style: &Self::Style) -> widget::progress_bar::Appearance {
+        let palette = self.palette();
+        widget::progress_bar::Appearance {
+            background: palette.get_bg(Color::SecondDark),
+            bar: palette.get_bg(Color::Light),
+            border_radius: self.style_preset.border_radius().into(),
+        }
+    }
+}
+
+impl widget::rule::StyleSheet for LauncherTheme {
+    type Style = LauncherTheme;
+
+    fn appearance(&self, _style: &Self::Style) -> widget::rule::Appearance {
+        widget::rule::Appearance {
+            color: self.palette().get(Color::SecondDark),
+            width: 1,
+            radius: 0.0.into(),
+            fill_mode: widget::rule::FillMode::Full,
         }
     }
 }
@@ -282,65 +398,56 @@ impl widget::progress_bar::StyleSheet for LauncherTheme {
 impl widget::slider::StyleSheet for LauncherTheme {
     type Style = LauncherTheme;
 
-    fn active(&self, style: &Self::Style) -> widget::slider::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => widget::slider::Appearance {
-                rail: widget::slider::Rail {
-                    colors: (
-                        DARK_PURPLE.get(Color::Mid),
-                        DARK_PURPLE.get(Color::SecondDark),
-                    ),
-                    width: 4.0,
-                    border_radius: BORDER_RADIUS.into(),
-                },
-                handle: widget::slider::Handle {
-                    shape: widget::slider::HandleShape::Circle { radius: 8.0 },
-                    color: DARK_PURPLE.get(Color::SecondLight),
-                    border_width: 2.0,
-                    border_color: DARK_PURPLE.get(Color::Light),
-                },
+    fn active(&self, _style: &Self::Style) -> widget::slider::Appearance {
+        let palette = self.palette();
+        widget::slider::Appearance {
+            rail: widget::slider::Rail {
+                colors: (palette.get(Color::Mid), palette.get(Color::SecondDark)),
+                width: 4.0,
+                border_radius: self.style_preset.border_radius().into(),
+            },
+            handle: widget::slider::Handle {
+                shape: widget::slider::HandleShape::Circle { radius: 8.0 },
+                color: palette.get(Color::SecondLight),
+                border_width: 2.0,
+                border_color: palette.get(Color::Light),
             },
         }
     }
 
-    fn hovered(&self, style: &Self::Style) -> widget::slider::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => widget::slider::Appearance {
-                rail: widget::slider::Rail {
-                    colors: (DARK_PURPLE.get(Color::Light), DARK_PURPLE.get(Color::Mid)),
-                    width: 4.0,
-                    border_radius: BORDER_RADIUS.into(),
-                },
-                handle: widget::slider::Handle {
-                    shape: widget::slider::HandleShape::Circle { radius: 8.0 },
-                    color: DARK_PURPLE.get(Color::SecondLight),
-                    border_width: 2.0,
-                    border_color: DARK_PURPLE.get(Color::White),
-                },
+    fn hovered(&self, _style: &Self::Style) -> widget::slider::Appearance {
+        let palette = self.palette();
+        widget::slider::Appearance {
+            rail: widget::slider::Rail {
+                colors: (palette.get(Color::Light), palette.get(Color::Mid)),
+                width: 4.0,
+                border_radius: self.style_preset.border_radius().into(),
+            },
+            handle: widget::slider::Handle {
+                shape: widget::slider::HandleShape::Circle { radius: 8.0 },
+                color: palette.get(Color::SecondLight),
+                border_width: 2.0,
+                border_color: palette.get(Color::White),
             },
         }
     }
 
     fn dragging(&self, style: &Self::Style) -> widget::slider::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => widget::slider::Appearance {
-                rail: widget::slider::Rail {
-                    colors: (
-                        DARK_PURPLE.get(Color::Mid),
-                        DARK_PURPLE.get(Color::SecondDark),
-                    ),
-                    width: 6.0,
-                    border_radius: BORDER_RADIUS.into(),
-                },
-                handle: widget::slider::Handle {
-                    shape: widget::slider::HandleShape::Circle { radius: 12.0 },
-                    color: DARK_PURPLE.get(Color::White),
-                    border_width: 2.0,
-                    border_color: DARK_PURPLE.get(Color::White),
-                },
+        if self.reduce_motion {
+            return self.hovered(style);
+        }
+        let palette = self.palette();
+        widget::slider::Appearance {
+            rail: widget::slider::Rail {
+                colors: (palette.get(Color::Mid), palette.get(Color::SecondDark)),
+                width: 6.0,
+                border_radius: self.style_preset.border_radius().into(),
+            },
+            handle: widget::slider::Handle {
+                shape: widget::slider::HandleShape::Circle { radius: 12.0 },
+                color: palette.get(Color::White),
+                border_width: 2.0,
+                border_color: palette.get(Color::White),
             },
         }
     }
@@ -349,13 +456,11 @@ impl widget::slider::StyleSheet for LauncherTheme {
 impl iced::application::StyleSheet for LauncherTheme {
     type Style = LauncherTheme;
 
-    fn appearance(&self, style: &Self::Style) -> iced::application::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => iced::application::Appearance {
-                background_color: DARK_PURPLE.get(Color::Dark),
-                text_color: DARK_PURPLE.get(Color::Light),
-            },
+    fn appearance(&self, _style: &Self::Style) -> iced::application::Appearance {
+        let palette = self.palette();
+        iced::application::Appearance {
+            background_color: palette.get(Color::Dark),
+            text_color: palette.get(Color::Light),
         }
     }
 }
@@ -363,43 +468,39 @@ impl iced::application::StyleSheet for LauncherTheme {
 impl iced::widget::checkbox::StyleSheet for LauncherTheme {
     type Style = LauncherTheme;
 
-    fn active(&self, style: &Self::Style, is_checked: bool) -> widget::checkbox::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => iced::widget::checkbox::Appearance {
-                background: if is_checked {
-                    DARK_PURPLE.get_bg(Color::Light)
-                } else {
-                    DARK_PURPLE.get_bg(Color::Dark)
-                },
-                icon_color: if is_checked {
-                    DARK_PURPLE.get(Color::Dark)
-                } else {
-                    DARK_PURPLE.get(Color::Light)
-                },
-                border: DARK_PURPLE.get_border(Color::SecondLight),
-                text_color: None,
+    fn active(&self, _style: &Self::Style, is_checked: bool) -> widget::checkbox::Appearance {
+        let palette = self.palette();
+        iced::widget::checkbox::Appearance {
+            background: if is_checked {
+                palette.get_bg(Color::Light)
+            } else {
+                palette.get_bg(Color::Dark)
             },
+            icon_color: if is_checked {
+                palette.get(Color::Dark)
+            } else {
+                palette.get(Color::Light)
+            },
+            border: self.get_border(Color::SecondLight),
+            text_color: None,
         }
     }
 
-    fn hovered(&self, style: &Self::Style, is_checked: bool) -> widget::checkbox::Appearance {
-        match style {
-            LauncherTheme::Light => todo!(),
-            LauncherTheme::Dark => iced::widget::checkbox::Appearance {
-                background: if is_checked {
-                    DARK_PURPLE.get_bg(Color::White)
-                } else {
-                    DARK_PURPLE.get_bg(Color::SecondDark)
-                },
-                icon_color: if is_checked {
-                    DARK_PURPLE.get(Color::SecondDark)
-                } else {
-                    DARK_PURPLE.get(Color::White)
-                },
-                border: DARK_PURPLE.get_border(Color::Light),
-                text_color: None,
+    fn hovered(&self, _style: &Self::Style, is_checked: bool) -> widget::checkbox::Appearance {
+        let palette = self.palette();
+        iced::widget::checkbox::Appearance {
+            background: if is_checked {
+                palette.get_bg(Color::White)
+            } else {
+                palette.get_bg(Color::SecondDark)
+            },
+            icon_color: if is_checked {
+                palette.get(Color::SecondDark)
+            } else {
+                palette.get(Color::White)
             },
+            border: self.get_border(Color::Light),
+            text_color: None,
         }
     }
 }
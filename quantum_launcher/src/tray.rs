@@ -0,0 +1,407 @@
+//! The optional system tray icon, showing the running games,
+//! with quick actions for launching favorite instances,
+//! showing the launcher window and stopping games.
+//!
+//! It's a [StatusNotifierItem](https://www.freedesktop.org/wiki/Specifications/StatusNotifierItem/)
+//! over D-Bus, so it's only on Linux, in desktops that
+//! show them (KDE, or GNOME with the AppIndicator extension).
+
+use std::sync::mpsc::Receiver;
+
+use tracing::error;
+
+/// Whether this platform has a tray icon.
+pub const SUPPORTED: bool = cfg!(target_os = "linux");
+
+/// What was clicked in the tray.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrayAction {
+    ShowWindow,
+    Launch(String),
+    Kill(u32),
+}
+
+/// What the tray menu lists.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrayMenu {
+    pub favorites: Vec<String>,
+    /// The pid and instance name of each running game.
+    pub running: Vec<(u32, String)>,
+}
+
+pub struct Tray {
+    actions: Receiver<TrayAction>,
+    menu: TrayMenu,
+    #[cfg(target_os = "linux")]
+    connection: zbus::blocking::Connection,
+}
+
+impl Tray {
+    /// Shows the tray icon. Fails if there's no
+    /// D-Bus session, or on other platforms.
+    #[cfg(target_os = "linux")]
+    pub fn start() -> Result<Self, String> {
+        let (sender, actions) = std::sync::mpsc::channel();
+        let connection = linux::connect(sender).map_err(|err| err.to_string())?;
+        Ok(Self {
+            actions,
+            menu: TrayMenu::default(),
+            connection,
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn start() -> Result<Self, String> {
+        Err("the tray icon isn't supported on this platform".to_owned())
+    }
+
+    /// Updates the menu and tooltip, if anything changed.
+    pub fn set_menu(&mut self, menu: TrayMenu) {
+        if menu == self.menu {
+            return;
+        }
+        #[cfg(target_os = "linux")]
+        if let Err(err) = linux::set_menu(&self.connection, &menu) {
+            error!("Could not update tray menu: {err}");
+        }
+        self.menu = menu;
+    }
+
+    /// The actions clicked since the last call.
+    pub fn poll(&self) -> Vec<TrayAction> {
+        self.actions.try_iter().collect()
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::{collections::HashMap, sync::mpsc::Sender};
+
+    use zbus::{
+        blocking::{connection, Connection},
+        interface,
+        zvariant::{ObjectPath, OwnedValue, StructureBuilder, Value},
+    };
+
+    use super::{TrayAction, TrayMenu};
+    use crate::tr;
+
+    const ITEM_PATH: &str = "/StatusNotifierItem";
+    const MENU_PATH: &str = "/MenuBar";
+    const MENU_INTERFACE: &str = "com.canonical.dbusmenu";
+
+    pub fn connect(sender: Sender<TrayAction>) -> zbus::Result<Connection> {
+        let name = format!("org.kde.StatusNotifierItem-{}-1", std::process::id());
+        let connection = connection::Builder::session()?
+            .name(name.as_str())?
+            .serve_at(
+                ITEM_PATH,
+                Item {
+                    sender: sender.clone(),
+                    tooltip: String::new(),
+                },
+            )?
+            .serve_at(
+                MENU_PATH,
+                Menu {
+                    sender,
+                    items: menu_items(&TrayMenu::default()),
+                    revision: 0,
+                },
+            )?
+            .build()?;
+
+        connection.call_method(
+            Some("org.kde.StatusNotifierWatcher"),
+            "/StatusNotifierWatcher",
+            Some("org.kde.StatusNotifierWatcher"),
+            "RegisterStatusNotifierItem",
+            &(name.as_str()),
+        )?;
+        Ok(connection)
+    }
+
+    pub fn set_menu(connection: &Connection, menu: &TrayMenu) -> zbus::Result<()> {
+        let item = connection.object_server().interface::<_, Item>(ITEM_PATH)?;
+        item.get_mut().tooltip = if menu.running.is_empty() {
+            tr!("tray-no-games")
+        } else {
+            menu.running
+                .iter()
+                .map(|(_, instance)| tr!("tray-running", instance = instance))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        connection.emit_signal(
+            None::<()>,
+            ITEM_PATH,
+            "org.kde.StatusNotifierItem",
+            "NewToolTip",
+            &(),
+        )?;
+
+        let menu_ref = connection.object_server().interface::<_, Menu>(MENU_PATH)?;
+        let revision = {
+            let mut menu_iface = menu_ref.get_mut();
+            menu_iface.items = menu_items(menu);
+            menu_iface.revision += 1;
+            menu_iface.revision
+        };
+        connection.emit_signal(
+            None::<()>,
+            MENU_PATH,
+            MENU_INTERFACE,
+            "LayoutUpdated",
+            &(revision, 0i32),
+        )
+    }
+
+    struct MenuItem {
+        label: String,
+        /// `None` for separators and headings.
+        action: Option<TrayAction>,
+        separator: bool,
+    }
+
+    fn menu_items(menu: &TrayMenu) -> Vec<MenuItem> {
+        let item = |label: String, action: Option<TrayAction>| MenuItem {
+            label,
+            action,
+            separator: false,
+        };
+        let separator = || MenuItem {
+            label: String::new(),
+            action: None,
+            separator: true,
+        };
+
+        let mut items = vec![item(tr!("tray-show"), Some(TrayAction::ShowWindow))];
+        if !menu.favorites.is_empty() {
+            items.push(separator());
+            items.extend(menu.favorites.iter().map(|instance| {
+                item(
+                    tr!("tray-launch", instance = instance),
+                    Some(TrayAction::Launch(instance.clone())),
+                )
+            }));
+        }
+        if !menu.running.is_empty() {
+            items.push(separator());
+            items.extend(menu.running.iter().map(|(pid, instance)| {
+                item(
+                    tr!("tray-kill", instance = instance),
+                    Some(TrayAction::Kill(*pid)),
+                )
+            }));
+        }
+        items
+    }
+
+    /// The icon name, icon pixmaps (width, height, ARGB data),
+    /// title and description.
+    type ToolTip = (String, Vec<(i32, i32, Vec<u8>)>, String, String);
+
+    struct Item {
+        sender: Sender<TrayAction>,
+        tooltip: String,
+    }
+
+    #[interface(name = "org.kde.StatusNotifierItem")]
+    impl Item {
+        fn activate(&self, _x: i32, _y: i32) {
+            _ = self.sender.send(TrayAction::ShowWindow);
+        }
+
+        fn secondary_activate(&self, _x: i32, _y: i32) {
+            _ = self.sender.send(TrayAction::ShowWindow);
+        }
+
+        /// The menu is shown by the tray itself, from [`Self::menu`].
+        fn context_menu(&self, _x: i32, _y: i32) {}
+
+        fn scroll(&self, _delta: i32, _orientation: &str) {}
+
+        #[zbus(property)]
+        fn category(&self) -> &str {
+            "ApplicationStatus"
+        }
+
+        #[zbus(property)]
+        fn id(&self) -> &str {
+            "quantum-launcher"
+        }
+
+        #[zbus(property)]
+        fn title(&self) -> String {
+            tr!("app-title")
+        }
+
+        #[zbus(property)]
+        fn status(&self) -> &str {
+            "Active"
+        }
+
+        #[zbus(property)]
+        fn icon_name(&self) -> &str {
+            "applications-games"
+        }
+
+        #[zbus(property)]
+        fn tool_tip(&self) -> ToolTip {
+            (
+                String::new(),
+                Vec::new(),
+                tr!("app-title"),
+                self.tooltip.clone(),
+            )
+        }
+
+        #[zbus(property)]
+        fn item_is_menu(&self) -> bool {
+            false
+        }
+
+        #[zbus(property)]
+        fn menu(&self) -> ObjectPath<'_> {
+            ObjectPath::from_static_str_unchecked(MENU_PATH)
+        }
+    }
+
+    /// The `com.canonical.dbusmenu` menu. Item `0` is the
+    /// root, and the items are numbered from `1`.
+    struct Menu {
+        sender: Sender<TrayAction>,
+        items: Vec<MenuItem>,
+        revision: u32,
+    }
+
+    type Layout = (i32, HashMap<String, OwnedValue>, Vec<OwnedValue>);
+
+    impl Menu {
+        fn properties(&self, id: i32) -> HashMap<String, OwnedValue> {
+            let mut properties = HashMap::new();
+            let mut insert = |key: &str, value: Value<'_>| {
+                if let Ok(value) = OwnedValue::try_from(value) {
+                    properties.insert(key.to_owned(), value);
+                }
+            };
+            if id == 0 {
+                insert("children-display", Value::from("submenu"));
+                return properties;
+            }
+            let Some(item) = usize::try_from(id - 1).ok().and_then(|n| self.items.get(n)) else {
+                return properties;
+            };
+            if item.separator {
+                insert("type", Value::from("separator"));
+            } else {
+                insert("label", Value::from(item.label.as_str()));
+                insert("enabled", Value::from(item.action.is_some()));
+            }
+            properties
+        }
+
+        fn ids(&self) -> impl Iterator<Item = i32> {
+            (1..=self.items.len()).map(|n| n as i32)
+        }
+    }
+
+    #[interface(name = "com.canonical.dbusmenu")]
+    impl Menu {
+        fn get_layout(
+            &self,
+            parent_id: i32,
+            _recursion_depth: i32,
+            _property_names: Vec<String>,
+        ) -> (u32, Layout) {
+            // The menu has no submenus.
+            let children = if parent_id == 0 {
+                self.ids()
+                    .filter_map(|id| {
+                        let child = StructureBuilder::new()
+                            .add_field(id)
+                            .add_field(self.properties(id))
+                            .add_field(Vec::<OwnedValue>::new())
+                            .build();
+                        OwnedValue::try_from(Value::from(child)).ok()
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            (
+                self.revision,
+                (parent_id, self.properties(parent_id), children),
+            )
+        }
+
+        fn get_group_properties(
+            &self,
+            ids: Vec<i32>,
+            _property_names: Vec<String>,
+        ) -> Vec<(i32, HashMap<String, OwnedValue>)> {
+            let ids = if ids.is_empty() {
+                self.ids().collect()
+            } else {
+                ids
+            };
+            ids.into_iter()
+                .map(|id| (id, self.properties(id)))
+                .collect()
+        }
+
+        fn get_property(&self, id: i32, name: &str) -> OwnedValue {
+            self.properties(id)
+                .remove(name)
+                .unwrap_or_else(|| OwnedValue::from(0u32))
+        }
+
+        fn event(&self, id: i32, event_id: &str, _data: OwnedValue, _timestamp: u32) {
+            if event_id != "clicked" {
+                return;
+            }
+            let action = usize::try_from(id - 1)
+                .ok()
+                .and_then(|n| self.items.get(n))
+                .and_then(|item| item.action.clone());
+            if let Some(action) = action {
+                _ = self.sender.send(action);
+            }
+        }
+
+        fn event_group(&self, events: Vec<(i32, String, OwnedValue, u32)>) -> Vec<i32> {
+            for (id, event_id, data, timestamp) in events {
+                self.event(id, &event_id, data, timestamp);
+            }
+            Vec::new()
+        }
+
+        fn about_to_show(&self, _id: i32) -> bool {
+            false
+        }
+
+        fn about_to_show_group(&self, _ids: Vec<i32>) -> (Vec<i32>, Vec<i32>) {
+            (Vec::new(), Vec::new())
+        }
+
+        #[zbus(property)]
+        fn version(&self) -> u32 {
+            3
+        }
+
+        #[zbus(property)]
+        fn text_direction(&self) -> &str {
+            "ltr"
+        }
+
+        #[zbus(property)]
+        fn status(&self) -> &str {
+            "normal"
+        }
+
+        #[zbus(property)]
+        fn icon_theme_path(&self) -> Vec<String> {
+            Vec::new()
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! Stopping long-running operations (like creating an instance
+//! or installing Java) partway through.
+//!
+//! The operation checks a [`CancelToken`] between steps, and
+//! once it's cancelled, removes what it had downloaded so far
+//! and errors with [`Cancelled`].
+
+use std::{
+    fmt::Display,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// A handle for cancelling an operation. Clones share
+/// the same state, so one can be given to the operation
+/// and another kept for a Cancel button.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The error for an operation stopped with [`CancelToken::cancel`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cancelled by the user")
+    }
+}
+
+/// Errors with [`Cancelled`] if `token` was cancelled.
+/// Operations that can't be cancelled pass `None`.
+pub fn check(token: Option<&CancelToken>) -> Result<(), Cancelled> {
+    if token.is_some_and(CancelToken::is_cancelled) {
+        Err(Cancelled)
+    } else {
+        Ok(())
+    }
+}
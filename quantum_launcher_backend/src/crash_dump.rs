@@ -0,0 +1,121 @@
+//! Crash dumps for when the launcher itself panics, so that
+//! there's something to go on besides "the window vanished".
+//!
+//! Each dump goes in `QuantumLauncher/crashes/`, with the panic,
+//! backtrace, launcher version, OS and the last log lines.
+//! The newest one is remembered until the next start, where
+//! the launcher can offer to report it ([`take_unseen_crash`]).
+
+use std::{
+    backtrace::Backtrace, fmt::Write, panic::PanicHookInfo, path::PathBuf, time::SystemTime,
+};
+
+use tracing::error;
+
+use crate::{error::IoError, file_utils, logging};
+
+/// How many of the last log lines go in a dump.
+const LOG_LINES: usize = 200;
+/// Holds the file name of the dump not shown to the user yet.
+const UNSEEN_FILE: &str = "unseen.txt";
+
+pub fn get_crashes_dir() -> Result<PathBuf, IoError> {
+    Ok(file_utils::get_config_dir()?.join("crashes"))
+}
+
+/// A crash from an earlier run.
+pub struct CrashDump {
+    pub path: PathBuf,
+    /// The panic message, like `index out of bounds`.
+    pub message: String,
+    pub text: String,
+}
+
+/// Writes a crash dump whenever `program` panics, after
+/// printing the panic like Rust normally does.
+pub fn install_panic_hook(program: &'static str, version: &'static str) {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        let message = panic_message(info);
+        error!("{program} crashed: {message}");
+        match write_crash_dump(program, version, info, &message) {
+            Ok(path) => eprintln!("A crash report was saved to {path:?}"),
+            Err(err) => eprintln!("Could not save crash report: {err}"),
+        }
+    }));
+}
+
+fn panic_message(info: &PanicHookInfo) -> String {
+    let payload = info.payload();
+    payload
+        .downcast_ref::<&str>()
+        .map(|n| (*n).to_owned())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_owned())
+}
+
+fn write_crash_dump(
+    program: &str,
+    version: &str,
+    info: &PanicHookInfo,
+    message: &str,
+) -> std::io::Result<PathBuf> {
+    let mut dump = String::new();
+    _ = writeln!(dump, "{program} {version} crashed");
+    _ = writeln!(
+        dump,
+        "OS: {} ({})",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    _ = writeln!(
+        dump,
+        "Thread: {}",
+        std::thread::current().name().unwrap_or("unnamed")
+    );
+    _ = writeln!(dump, "\nPanic: {message}");
+    if let Some(location) = info.location() {
+        _ = writeln!(dump, "Location: {location}");
+    }
+    _ = writeln!(dump, "\nBacktrace:\n{}", Backtrace::force_capture());
+
+    let logs = logging::recent_logs();
+    _ = writeln!(dump, "Last log lines:");
+    for line in &logs[logs.len().saturating_sub(LOG_LINES)..] {
+        _ = writeln!(dump, "{line}");
+    }
+
+    let dir = get_crashes_dir().map_err(|err| std::io::Error::other(err.to_string()))?;
+    std::fs::create_dir_all(&dir)?;
+    let file_name = format!(
+        "crash-{}.txt",
+        file_utils::format_timestamp(SystemTime::now())
+    );
+    let path = dir.join(&file_name);
+    std::fs::write(&path, dump)?;
+    std::fs::write(dir.join(UNSEEN_FILE), &file_name)?;
+    Ok(path)
+}
+
+/// The crash dump from the last run, if the launcher crashed
+/// and it hasn't been returned before.
+pub fn take_unseen_crash() -> Option<CrashDump> {
+    let dir = get_crashes_dir().ok()?;
+    let unseen_path = dir.join(UNSEEN_FILE);
+    let file_name = std::fs::read_to_string(&unseen_path).ok()?;
+    _ = std::fs::remove_file(&unseen_path);
+
+    let path = dir.join(file_name.trim());
+    let text = std::fs::read_to_string(&path).ok()?;
+    let message = text
+        .lines()
+        .find_map(|line| line.strip_prefix("Panic: "))
+        .unwrap_or_default()
+        .to_owned();
+    Some(CrashDump {
+        path,
+        message,
+        text,
+    })
+}
@@ -0,0 +1,281 @@
+//! Discord Rich Presence, which shows the game being
+//! played on the user's Discord profile.
+//!
+//! It talks to the Discord app over its local IPC socket
+//! (a named pipe on Windows), so nothing happens if Discord
+//! isn't running, and nothing is sent over the network.
+
+use std::{
+    fmt::Display,
+    io::{Read, Write},
+    sync::mpsc::{self, Receiver, Sender},
+};
+
+use serde_json::{json, Value};
+use tracing::{error, info};
+
+/// The launcher's application ID on Discord's developer portal,
+/// set with the `QUANTUM_LAUNCHER_DISCORD_CLIENT_ID` environment
+/// variable when building. Without it, Rich Presence is off.
+pub const CLIENT_ID: Option<&str> = option_env!("QUANTUM_LAUNCHER_DISCORD_CLIENT_ID");
+
+const OP_HANDSHAKE: u32 = 0;
+const OP_FRAME: u32 = 1;
+const OP_CLOSE: u32 = 2;
+/// Discord's answers are small, so anything
+/// bigger means the stream is out of sync.
+const MAX_FRAME_LEN: usize = 64 * 1024;
+
+/// What's shown on the user's profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Activity {
+    /// Like `Playing My Instance`.
+    pub details: String,
+    /// Like `1.20.4 Fabric`.
+    pub state: String,
+    /// When the game started, in seconds since the Unix epoch.
+    /// Discord counts the elapsed time up from this.
+    pub started: u64,
+}
+
+/// Keeps the presence up to date from a background
+/// thread, connecting to Discord whenever it's needed.
+///
+/// The thread stops when this is dropped.
+pub struct DiscordRpc {
+    sender: Sender<Option<Activity>>,
+    current: Option<Activity>,
+}
+
+impl DiscordRpc {
+    /// Starts the background thread. Returns `None` if this
+    /// build has no [`CLIENT_ID`].
+    pub fn start() -> Option<Self> {
+        let client_id = CLIENT_ID?;
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || run(client_id, receiver));
+        Some(Self {
+            sender,
+            current: None,
+        })
+    }
+
+    /// Shows `activity`, or clears the presence if it's `None`.
+    /// Nothing is sent if it's the same as before.
+    pub fn set_activity(&mut self, activity: Option<Activity>) {
+        if activity == self.current {
+            return;
+        }
+        self.current.clone_from(&activity);
+        _ = self.sender.send(activity);
+    }
+}
+
+fn run(client_id: &str, receiver: Receiver<Option<Activity>>) {
+    let mut connection: Option<Connection> = None;
+    for activity in receiver {
+        // Discord might have been restarted since the last
+        // update, so connecting is tried again once.
+        for _ in 0..2 {
+            if connection.is_none() {
+                // Discord not running is normal, so it isn't logged.
+                connection = Connection::connect(client_id).ok();
+            }
+            let Some(current) = &mut connection else {
+                break;
+            };
+            match current.set_activity(activity.as_ref()) {
+                Ok(()) => break,
+                Err(err) => {
+                    error!("Could not update Discord status: {err}");
+                    connection = None;
+                }
+            }
+        }
+    }
+}
+
+trait IpcStream: Read + Write + Send {}
+impl<T: Read + Write + Send> IpcStream for T {}
+
+struct Connection {
+    stream: Box<dyn IpcStream>,
+    nonce: u64,
+}
+
+impl Connection {
+    fn connect(client_id: &str) -> Result<Self, RpcError> {
+        let stream = ipc_paths()
+            .into_iter()
+            .find_map(|path| open_stream(&path).ok())
+            .ok_or(RpcError::NotRunning)?;
+        let mut connection = Self { stream, nonce: 0 };
+
+        write_frame(
+            &mut connection.stream,
+            OP_HANDSHAKE,
+            &json!({ "v": 1, "client_id": client_id }),
+        )?;
+        connection.receive()?;
+        info!("Connected to Discord");
+        Ok(connection)
+    }
+
+    fn set_activity(&mut self, activity: Option<&Activity>) -> Result<(), RpcError> {
+        self.nonce += 1;
+        let activity = activity.map(|activity| {
+            json!({
+                "details": activity.details,
+                "state": activity.state,
+                "timestamps": { "start": activity.started },
+            })
+        });
+        write_frame(
+            &mut self.stream,
+            OP_FRAME,
+            &json!({
+                "cmd": "SET_ACTIVITY",
+                "args": { "pid": std::process::id(), "activity": activity },
+                "nonce": self.nonce.to_string(),
+            }),
+        )?;
+        self.receive()?;
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Result<Value, RpcError> {
+        let (op, payload) = read_frame(&mut self.stream)?;
+        if op == OP_CLOSE {
+            return Err(RpcError::Closed);
+        }
+        if payload.get("evt").and_then(Value::as_str) == Some("ERROR") {
+            let message = payload
+                .pointer("/data/message")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            return Err(RpcError::Discord(message.to_owned()));
+        }
+        Ok(payload)
+    }
+}
+
+/// Each frame is its opcode and length (little-endian
+/// `u32`s), then that many bytes of JSON.
+fn write_frame(stream: &mut impl Write, op: u32, payload: &Value) -> Result<(), RpcError> {
+    let payload = serde_json::to_vec(payload)?;
+    let mut frame = Vec::with_capacity(payload.len() + 8);
+    frame.extend_from_slice(&op.to_le_bytes());
+    frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    frame.extend_from_slice(&payload);
+    stream.write_all(&frame)?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn read_frame(stream: &mut impl Read) -> Result<(u32, Value), RpcError> {
+    let mut header = [0; 8];
+    stream.read_exact(&mut header)?;
+    let op = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+    let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(RpcError::Discord("response too long".to_owned()));
+    }
+
+    let mut payload = vec![0; len];
+    stream.read_exact(&mut payload)?;
+    Ok((op, serde_json::from_slice(&payload)?))
+}
+
+/// Discord listens on the first free one of `discord-ipc-0`
+/// to `discord-ipc-9`. On Linux, Flatpak and Snap installs
+/// have theirs in a subfolder.
+#[cfg(unix)]
+fn ipc_paths() -> Vec<std::path::PathBuf> {
+    let base_dirs: Vec<std::path::PathBuf> = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .map(std::path::PathBuf::from)
+        .chain(std::iter::once(std::path::PathBuf::from("/tmp")))
+        .collect();
+
+    let mut paths = Vec::new();
+    for base_dir in &base_dirs {
+        for sub_dir in ["", "app/com.discordapp.Discord", "snap.discord"] {
+            for i in 0..10 {
+                paths.push(base_dir.join(sub_dir).join(format!("discord-ipc-{i}")));
+            }
+        }
+    }
+    paths
+}
+
+#[cfg(windows)]
+fn ipc_paths() -> Vec<std::path::PathBuf> {
+    (0..10)
+        .map(|i| std::path::PathBuf::from(format!(r"\\.\pipe\discord-ipc-{i}")))
+        .collect()
+}
+
+#[cfg(unix)]
+fn open_stream(path: &std::path::Path) -> std::io::Result<Box<dyn IpcStream>> {
+    Ok(Box::new(std::os::unix::net::UnixStream::connect(path)?))
+}
+
+#[cfg(windows)]
+fn open_stream(path: &std::path::Path) -> std::io::Result<Box<dyn IpcStream>> {
+    let pipe = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)?;
+    Ok(Box::new(pipe))
+}
+
+#[derive(Debug)]
+pub enum RpcError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    NotRunning,
+    Closed,
+    Discord(String),
+}
+
+impl From<std::io::Error> for RpcError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for RpcError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl Display for RpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RpcError::Io(err) => write!(f, "discord rpc: {err}"),
+            RpcError::Json(err) => write!(f, "discord rpc: invalid json: {err}"),
+            RpcError::NotRunning => write!(f, "discord rpc: discord isn't running"),
+            RpcError::Closed => write!(f, "discord rpc: discord closed the connection"),
+            RpcError::Discord(message) => write!(f, "discord rpc: {message}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_round_trip() {
+        let payload = json!({ "cmd": "SET_ACTIVITY", "nonce": "1" });
+        let mut buf = Vec::new();
+        write_frame(&mut buf, OP_FRAME, &payload).unwrap();
+        assert_eq!(&buf[..4], &OP_FRAME.to_le_bytes());
+
+        let (op, read) = read_frame(&mut buf.as_slice()).unwrap();
+        assert_eq!(op, OP_FRAME);
+        assert_eq!(read, payload);
+    }
+}
@@ -0,0 +1,84 @@
+//! Laying out the assets of old versions, which don't read
+//! the hashed `objects` store.
+//!
+//! Asset indexes marked `virtual` (1.6 to 1.7.2) want the
+//! files by name in `virtual/<index>`, and ones marked
+//! `map_to_resources` (before 1.6) want them in the game
+//! directory's `resources` folder. Without this, those
+//! versions have no sounds, language files or icons.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+use tracing::info;
+
+use crate::{error::LauncherResult, io_err};
+
+#[derive(Deserialize)]
+struct AssetIndex {
+    #[serde(default)]
+    r#virtual: bool,
+    #[serde(default)]
+    map_to_resources: bool,
+    objects: BTreeMap<String, AssetObject>,
+}
+
+#[derive(Deserialize)]
+struct AssetObject {
+    hash: String,
+    size: u64,
+}
+
+/// Copies the assets to where the version expects them, if
+/// its index is a legacy one, and returns that folder. It's
+/// what `${game_assets}` points to in the game arguments.
+///
+/// For newer versions this does nothing and returns
+/// `assets_dir`. Files already in place are skipped,
+/// so it's cheap to run before every launch.
+pub fn materialize(assets_dir: &Path, index_id: &str, game_dir: &Path) -> LauncherResult<PathBuf> {
+    let index_path = assets_dir.join("indexes").join(format!("{index_id}.json"));
+    if !index_path.is_file() {
+        return Ok(assets_dir.to_owned());
+    }
+    let index = std::fs::read_to_string(&index_path).map_err(io_err!(index_path))?;
+    let index: AssetIndex = serde_json::from_str(&index)?;
+
+    let target_dir = if index.map_to_resources {
+        game_dir.join("resources")
+    } else if index.r#virtual {
+        assets_dir.join("virtual").join(index_id)
+    } else {
+        return Ok(assets_dir.to_owned());
+    };
+
+    let objects_dir = assets_dir.join("objects");
+    let mut copied = 0;
+    for (name, object) in &index.objects {
+        let dest = target_dir.join(name);
+        if std::fs::metadata(&dest).is_ok_and(|n| n.len() == object.size) {
+            continue;
+        }
+        let Some(prefix) = object.hash.get(0..2) else {
+            continue;
+        };
+        let src = objects_dir.join(prefix).join(&object.hash);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent).map_err(io_err!(parent))?;
+        }
+        // Hard links save copying the whole store, but
+        // don't work across drives.
+        _ = std::fs::remove_file(&dest);
+        if std::fs::hard_link(&src, &dest).is_err() {
+            std::fs::copy(&src, &dest).map_err(io_err!(src))?;
+        }
+        copied += 1;
+    }
+    if copied > 0 {
+        info!("Laid out {copied} legacy assets in {target_dir:?}");
+    }
+    Ok(target_dir)
+}
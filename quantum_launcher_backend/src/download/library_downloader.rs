@@ -3,6 +3,8 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use tracing::info;
+
 use crate::{
     download::progress::DownloadProgress,
     error::IoError,
@@ -16,9 +18,9 @@ use super::{constants::OS_NAME, DownloadError, GameDownloader};
 
 impl GameDownloader {
     pub async fn download_libraries(&self) -> Result<(), DownloadError> {
-        println!("[info] Starting download of libraries.");
+        info!("Starting download of libraries.");
 
-        self.prepare_library_directories()?;
+        self.prepare_library_directories().await?;
 
         let total_libraries = self.version_json.libraries.len();
 
@@ -29,8 +31,8 @@ impl GameDownloader {
             })?;
 
             if !GameDownloader::download_libraries_library_is_allowed(library) {
-                println!(
-                    "[info] Skipping library {}",
+                info!(
+                    "Skipping library {}",
                     serde_json::to_string_pretty(&library)?
                 );
                 continue;
@@ -42,11 +44,11 @@ impl GameDownloader {
         Ok(())
     }
 
-    fn prepare_library_directories(&self) -> Result<(), IoError> {
-        let library_path = self.instance_dir.join("libraries");
-        std::fs::create_dir_all(&library_path).map_err(io_err!(library_path))?;
-        let natives_path = library_path.join("natives");
-        std::fs::create_dir_all(&natives_path).map_err(io_err!(natives_path))?;
+    async fn prepare_library_directories(&self) -> Result<(), IoError> {
+        let natives_path = self.instance_dir.join("libraries").join("natives");
+        tokio::fs::create_dir_all(&natives_path)
+            .await
+            .map_err(io_err!(natives_path))?;
         Ok(())
     }
 
@@ -95,16 +97,20 @@ impl GameDownloader {
             )
             .to_path_buf();
 
-        println!(
-            "[info] Downloading library {library_number}/{number_of_libraries}: {}",
+        info!(
+            "Downloading library {library_number}/{number_of_libraries}: {}",
             artifact.path
         );
 
-        std::fs::create_dir_all(&lib_dir_path).map_err(io_err!(lib_dir_path))?;
+        tokio::fs::create_dir_all(&lib_dir_path)
+            .await
+            .map_err(io_err!(lib_dir_path))?;
         let library_downloaded =
             file_utils::download_file_to_bytes(&self.network_client, &artifact.url).await?;
 
-        std::fs::write(&lib_file_path, library_downloaded).map_err(io_err!(lib_file_path))?;
+        tokio::fs::write(&lib_file_path, library_downloaded)
+            .await
+            .map_err(io_err!(lib_file_path))?;
 
         Ok(())
     }
@@ -125,8 +131,12 @@ impl GameDownloader {
             let library =
                 file_utils::download_file_to_bytes(&self.network_client, &download.url).await?;
 
-            zip_extract::extract(Cursor::new(&library), &natives_dir, true)
-                .map_err(DownloadError::NativesExtractError)?;
+            let natives_dir = natives_dir.clone();
+            file_utils::run_blocking(move || {
+                zip_extract::extract(Cursor::new(&library), &natives_dir, true)
+            })
+            .await
+            .map_err(DownloadError::NativesExtractError)?;
         }
 
         if let Some(extract) = extract {
@@ -139,10 +149,13 @@ impl GameDownloader {
 
                 if exclusion_path.exists() {
                     if exclusion_path.is_dir() {
-                        std::fs::remove_dir_all(&exclusion_path)
+                        tokio::fs::remove_dir_all(&exclusion_path)
+                            .await
                             .map_err(io_err!(exclusion_path))?;
                     } else {
-                        std::fs::remove_file(&exclusion_path).map_err(io_err!(exclusion_path))?;
+                        tokio::fs::remove_file(&exclusion_path)
+                            .await
+                            .map_err(io_err!(exclusion_path))?;
                     }
                 }
             }
@@ -151,7 +164,7 @@ impl GameDownloader {
         Ok(())
     }
 
-    fn download_libraries_library_is_allowed(library: &Library) -> bool {
+    pub(crate) fn download_libraries_library_is_allowed(library: &Library) -> bool {
         let mut allowed: bool = true;
 
         if let Some(ref rules) = library.rules {
@@ -1,25 +1,33 @@
 pub mod constants;
+pub mod legacy_assets;
 mod library_downloader;
 pub mod progress;
+pub mod verify;
 
-use std::{
-    fmt::Display,
-    path::PathBuf,
-    sync::mpsc::{SendError, Sender},
-};
+use std::{fmt::Display, path::PathBuf};
 
 use reqwest::Client;
 use serde_json::Value;
+use tokio::task::{JoinError, JoinSet};
+use tracing::info;
 use zip_extract::ZipExtractError;
 
 use crate::{
+    cancel::{self, CancelToken, Cancelled},
+    download_queue,
     error::IoError,
+    events::{self, Event},
     file_utils::{self, RequestError},
+    instance::server_software::ServerSoftware,
     io_err,
     json_structs::{
-        json_instance_config::InstanceConfigJson, json_manifest::Manifest,
-        json_profiles::ProfileJson, json_version::VersionDetails, JsonDownloadError,
+        json_instance_config::{default_world_backup_retention, InstanceConfigJson},
+        json_manifest::{Manifest, VersionSource},
+        json_profiles::ProfileJson,
+        json_version::VersionDetails,
+        JsonDownloadError, JsonFileError,
     },
+    progress::{ProgressSender, SendError},
 };
 
 use self::{constants::DEFAULT_RAM_MB_FOR_INSTANCE, progress::DownloadProgress};
@@ -28,7 +36,7 @@ use self::{constants::DEFAULT_RAM_MB_FOR_INSTANCE, progress::DownloadProgress};
 ///
 /// # Example
 /// ```
-/// // progress_sender: Option<mspc::Sender<Progress>>
+/// // progress_sender: Option<ProgressSender<DownloadProgress>>
 /// // Btw don't run this doctest! It will burn 600 MB of disk space.
 /// // let game_downloader = GameDownloader::new("1.20.4", &version, progress_sender)?;
 /// // game_downloader.download_jar()?;
@@ -40,10 +48,15 @@ use self::{constants::DEFAULT_RAM_MB_FOR_INSTANCE, progress::DownloadProgress};
 /// // json_file.write_all(serde_json::to_string(&game_downloader.version_json)?.as_bytes())?;
 /// ```
 pub struct GameDownloader {
+    pub instance_name: String,
     pub instance_dir: PathBuf,
     pub version_json: VersionDetails,
+    pub version_source: VersionSource,
+    /// Set for instances made from a [`CustomVersionJson`].
+    pub custom_version_json: Option<CustomVersionJson>,
     network_client: Client,
-    sender: Option<Sender<DownloadProgress>>,
+    sender: Option<ProgressSender<DownloadProgress>>,
+    cancel: Option<CancelToken>,
 }
 
 impl GameDownloader {
@@ -51,33 +64,83 @@ impl GameDownloader {
     ///
     /// For information on what order to download things in, check the `GameDownloader` struct documentation.
     ///
-    /// `sender: Option<Sender<Progress>>` is an optional mspc::Sender
+    /// `sender: Option<ProgressSender<DownloadProgress>>` is an optional sender
     /// that can be used if you are running this asynchronously or
     /// on a separate thread, and want to communicate progress with main thread.
     ///
     /// Leave as `None` if not required.
+    ///
+    /// With a `cancel` token, each step errors with
+    /// [`DownloadError::Cancelled`] once it's cancelled.
     pub async fn new(
         instance_name: &str,
         version: &str,
-        sender: Option<Sender<DownloadProgress>>,
+        version_source: VersionSource,
+        sender: Option<ProgressSender<DownloadProgress>>,
+        cancel: Option<CancelToken>,
     ) -> Result<GameDownloader, DownloadError> {
         let Some(instance_dir) = GameDownloader::new_get_instance_dir(instance_name)? else {
             return Err(DownloadError::InstanceAlreadyExists);
         };
-        let network_client = Client::new();
-        let version_json =
-            GameDownloader::new_download_version_json(&network_client, version, &sender).await?;
+        let network_client = file_utils::create_client();
+        let version_json = GameDownloader::new_download_version_json(
+            &network_client,
+            version,
+            version_source,
+            &sender,
+        )
+        .await?;
+
+        Ok(Self {
+            instance_name: instance_name.to_owned(),
+            instance_dir,
+            network_client,
+            version_json,
+            version_source,
+            custom_version_json: None,
+            sender,
+            cancel,
+        })
+    }
+
+    /// Like [`GameDownloader::new`], but the version JSON is
+    /// one the user supplied instead of one from a manifest.
+    ///
+    /// The JSON is read before the instance folder is made,
+    /// so a broken one doesn't leave an empty instance behind.
+    pub async fn new_custom(
+        instance_name: &str,
+        custom_version_json: CustomVersionJson,
+        sender: Option<ProgressSender<DownloadProgress>>,
+        cancel: Option<CancelToken>,
+    ) -> Result<GameDownloader, DownloadError> {
+        info!("Reading custom version JSON {custom_version_json}");
+        if let Some(sender) = &sender {
+            sender.send(DownloadProgress::DownloadingVersionJson)?;
+        }
+        let network_client = file_utils::create_client();
+        let version_json = custom_version_json
+            .read::<DownloadError>(&network_client)
+            .await?;
+        let version_json = serde_json::from_str(&version_json)?;
 
+        let Some(instance_dir) = GameDownloader::new_get_instance_dir(instance_name)? else {
+            return Err(DownloadError::InstanceAlreadyExists);
+        };
         Ok(Self {
+            instance_name: instance_name.to_owned(),
             instance_dir,
             network_client,
             version_json,
+            version_source: VersionSource::default(),
+            custom_version_json: Some(custom_version_json),
             sender,
+            cancel,
         })
     }
 
     pub async fn download_jar(&self) -> Result<(), DownloadError> {
-        println!("[info] Downloading game jar file.");
+        info!("Downloading game jar file.");
         self.send_progress(DownloadProgress::DownloadingJar)?;
 
         let jar_bytes = file_utils::download_file_to_bytes(
@@ -91,17 +154,21 @@ impl GameDownloader {
             .join(".minecraft")
             .join("versions")
             .join(&self.version_json.id);
-        std::fs::create_dir_all(&version_dir).map_err(io_err!(version_dir))?;
+        tokio::fs::create_dir_all(&version_dir)
+            .await
+            .map_err(io_err!(version_dir))?;
 
         let jar_path = version_dir.join(format!("{}.jar", self.version_json.id));
-        std::fs::write(&jar_path, jar_bytes).map_err(io_err!(jar_path))?;
+        tokio::fs::write(&jar_path, jar_bytes)
+            .await
+            .map_err(io_err!(jar_path))?;
 
         Ok(())
     }
 
     pub async fn download_logging_config(&self) -> Result<(), DownloadError> {
         if let Some(ref logging) = self.version_json.logging {
-            println!("[info] Downloading logging configuration.");
+            info!("Downloading logging configuration.");
             self.send_progress(DownloadProgress::DownloadingLoggingConfig)?;
 
             let log_config_name = format!("logging-{}", logging.client.file.id);
@@ -111,29 +178,35 @@ impl GameDownloader {
                     .await?;
 
             let config_path = self.instance_dir.join(log_config_name);
-            std::fs::write(&config_path, log_config.as_bytes()).map_err(io_err!(config_path))?;
+            tokio::fs::write(&config_path, log_config.as_bytes())
+                .await
+                .map_err(io_err!(config_path))?;
         }
         Ok(())
     }
 
     pub async fn download_assets(&self) -> Result<(), DownloadError> {
-        const OBJECTS_URL: &str = "https://resources.download.minecraft.net";
+        info!("Downloading assets.");
 
-        println!("[info] Downloading assets.");
-
-        let launcher_dir = file_utils::get_launcher_dir()?;
-
-        let assets_dir = launcher_dir.join("assets");
-        std::fs::create_dir_all(&assets_dir).map_err(io_err!(assets_dir))?;
+        let assets_dir = file_utils::get_cache_dir()?.join("assets");
+        tokio::fs::create_dir_all(&assets_dir)
+            .await
+            .map_err(io_err!(assets_dir))?;
 
         let current_assets_dir = assets_dir.join(&self.version_json.assetIndex.id);
         let current_assets_dir_exists = current_assets_dir.exists();
-        std::fs::create_dir_all(&current_assets_dir).map_err(io_err!(current_assets_dir))?;
+        tokio::fs::create_dir_all(&current_assets_dir)
+            .await
+            .map_err(io_err!(current_assets_dir))?;
 
         let assets_indexes_path = current_assets_dir.join("indexes");
-        std::fs::create_dir_all(&assets_indexes_path).map_err(io_err!(assets_indexes_path))?;
+        tokio::fs::create_dir_all(&assets_indexes_path)
+            .await
+            .map_err(io_err!(assets_indexes_path))?;
         let assets_objects_path = current_assets_dir.join("objects");
-        std::fs::create_dir_all(&assets_objects_path).map_err(io_err!(assets_objects_path))?;
+        tokio::fs::create_dir_all(&assets_objects_path)
+            .await
+            .map_err(io_err!(assets_objects_path))?;
 
         let lock_path = current_assets_dir.join("download.lock");
         let lock_exists = lock_path.exists();
@@ -144,7 +217,9 @@ impl GameDownloader {
         }
 
         let lock_contents = "If you see this, the asset downloading hasn't finished. This will be deleted once finished.";
-        std::fs::write(&lock_path, lock_contents).map_err(io_err!(lock_path))?;
+        tokio::fs::write(&lock_path, lock_contents)
+            .await
+            .map_err(io_err!(lock_path))?;
 
         let asset_index =
             GameDownloader::download_json(&self.network_client, &self.version_json.assetIndex.url)
@@ -153,10 +228,11 @@ impl GameDownloader {
         let assets_indexes_json_path =
             assets_indexes_path.join(format!("{}.json", self.version_json.assetIndex.id));
 
-        std::fs::write(
+        tokio::fs::write(
             &assets_indexes_json_path,
             asset_index.to_string().as_bytes(),
         )
+        .await
         .map_err(io_err!(assets_indexes_json_path))?;
 
         let objects =
@@ -167,37 +243,84 @@ impl GameDownloader {
                 ))?;
         let objects_len = objects.len();
 
-        for (object_number, (_, object_data)) in objects.iter().enumerate() {
-            let obj_hash =
-                object_data["hash"]
-                    .as_str()
-                    .ok_or(DownloadError::SerdeFieldNotFound(
-                        "asset_index.objects[].hash".to_owned(),
-                    ))?;
+        let parallel_downloads = file_utils::get_download_settings()
+            .parallel_downloads
+            .max(1);
+        let mut tasks = JoinSet::new();
+        let mut finished = 0;
 
-            let obj_id = &obj_hash[0..2];
+        for object_data in objects.values() {
+            let obj_hash = object_data["hash"]
+                .as_str()
+                .ok_or(DownloadError::SerdeFieldNotFound(
+                    "asset_index.objects[].hash".to_owned(),
+                ))?
+                .to_owned();
 
-            println!("[info] Downloading asset {object_number}/{objects_len}");
-            self.send_progress(DownloadProgress::DownloadingAssets {
-                progress: object_number,
-                out_of: objects_len,
-            })?;
+            if tasks.len() >= parallel_downloads {
+                self.finish_asset_download(&mut tasks, &mut finished, objects_len)
+                    .await?;
+            }
 
-            let obj_folder = assets_objects_path.join(obj_id);
-            std::fs::create_dir_all(&obj_folder).map_err(io_err!(obj_folder))?;
+            let obj_folder = assets_objects_path.join(&obj_hash[0..2]);
+            tasks.spawn(download_queue::in_current_job(
+                GameDownloader::download_asset(self.network_client.clone(), obj_folder, obj_hash),
+            ));
+        }
 
-            let obj_data = file_utils::download_file_to_bytes(
-                &self.network_client,
-                &format!("{}/{}/{}", OBJECTS_URL, obj_id, obj_hash),
-            )
-            .await?;
+        while !tasks.is_empty() {
+            self.finish_asset_download(&mut tasks, &mut finished, objects_len)
+                .await?;
+        }
 
-            let obj_file_path = obj_folder.join(obj_hash);
+        tokio::fs::remove_file(&lock_path)
+            .await
+            .map_err(io_err!(lock_path))?;
+        Ok(())
+    }
 
-            std::fs::write(&obj_file_path, &obj_data).map_err(io_err!(obj_file_path))?;
-        }
+    async fn download_asset(
+        network_client: Client,
+        obj_folder: PathBuf,
+        obj_hash: String,
+    ) -> Result<(), DownloadError> {
+        const OBJECTS_URL: &str = "https://resources.download.minecraft.net";
+
+        tokio::fs::create_dir_all(&obj_folder)
+            .await
+            .map_err(io_err!(obj_folder))?;
+
+        let obj_id = &obj_hash[0..2];
+        let obj_data = file_utils::download_file_to_bytes(
+            &network_client,
+            &format!("{}/{}/{}", OBJECTS_URL, obj_id, obj_hash),
+        )
+        .await?;
+
+        let obj_file_path = obj_folder.join(&obj_hash);
+        tokio::fs::write(&obj_file_path, &obj_data)
+            .await
+            .map_err(io_err!(obj_file_path))?;
+        Ok(())
+    }
 
-        std::fs::remove_file(&lock_path).map_err(io_err!(lock_path))?;
+    /// Waits for one of the running asset downloads to finish.
+    async fn finish_asset_download(
+        &self,
+        tasks: &mut JoinSet<Result<(), DownloadError>>,
+        finished: &mut usize,
+        objects_len: usize,
+    ) -> Result<(), DownloadError> {
+        if let Some(result) = tasks.join_next().await {
+            result.map_err(DownloadError::AssetTaskFailed)??;
+            *finished += 1;
+
+            info!("Downloaded asset {finished}/{objects_len}");
+            self.send_progress(DownloadProgress::DownloadingAssets {
+                progress: *finished,
+                out_of: objects_len,
+            })?;
+        }
         Ok(())
     }
 
@@ -236,34 +359,52 @@ impl GameDownloader {
     pub fn create_config_json(&self) -> Result<(), DownloadError> {
         let config_json = InstanceConfigJson {
             java_override: None,
+            java_version: None,
             ram_in_mb: DEFAULT_RAM_MB_FOR_INSTANCE,
+            initial_ram_in_mb: None,
             mod_type: "Vanilla".to_owned(),
+            last_played: None,
+            last_username: None,
+            last_profile: None,
+            last_java: None,
+            offline_profile: None,
+            java_args: Vec::new(),
+            world_backup_retention: default_world_backup_retention(),
+            backup_worlds_on_version_change: false,
+            snapshot_small_files: true,
+            log4shell_mitigation: true,
+            sync_folder: false,
+            last_launched_version: None,
+            game_dir: None,
+            version_type: None,
+            version_source: self.version_source,
+            custom_version_json: self.custom_version_json.as_ref().map(ToString::to_string),
+            mods: Vec::new(),
+            config_version: InstanceConfigJson::VERSION,
         };
-        let config_json = serde_json::to_string(&config_json)?;
-
-        let config_json_path = self.instance_dir.join("config.json");
-        std::fs::write(&config_json_path, config_json).map_err(io_err!(config_json_path))?;
+        config_json.save(&self.instance_dir.join("config.json"))?;
 
         Ok(())
     }
 
-    async fn new_download_version_json(
+    pub(crate) async fn new_download_version_json(
         network_client: &Client,
         version: &str,
-        sender: &Option<Sender<DownloadProgress>>,
+        source: VersionSource,
+        sender: &Option<ProgressSender<DownloadProgress>>,
     ) -> Result<VersionDetails, DownloadError> {
-        println!("[info] Started downloading version manifest JSON.");
+        info!("Started downloading version manifest JSON.");
         if let Some(sender) = sender {
             sender.send(DownloadProgress::DownloadingJsonManifest)?;
         }
-        let manifest = Manifest::download().await?;
+        let manifest = Manifest::download_from(source).await?;
 
         let version = match manifest.versions.iter().find(|n| n.id == version) {
             Some(n) => n,
             None => return Err(DownloadError::VersionNotFoundInManifest(version.to_owned())),
         };
 
-        println!("[info] Started downloading version details JSON.");
+        info!("Started downloading version details JSON.");
         if let Some(sender) = sender {
             sender.send(DownloadProgress::DownloadingVersionJson)?;
         }
@@ -274,7 +415,7 @@ impl GameDownloader {
     }
 
     fn new_get_instance_dir(instance_name: &str) -> Result<Option<PathBuf>, IoError> {
-        println!("[info] Initializing instance folder.");
+        info!("Initializing instance folder.");
         let launcher_dir = file_utils::get_launcher_dir()?;
         let instances_dir = launcher_dir.join("instances");
         std::fs::create_dir_all(&instances_dir).map_err(io_err!(instances_dir))?;
@@ -288,7 +429,14 @@ impl GameDownloader {
         Ok(Some(current_instance_dir))
     }
 
-    fn send_progress(&self, progress: DownloadProgress) -> Result<(), SendError<DownloadProgress>> {
+    /// Also where cancelling is checked, as progress
+    /// is sent between every step.
+    fn send_progress(&self, progress: DownloadProgress) -> Result<(), DownloadError> {
+        cancel::check(self.cancel.as_ref())?;
+        events::emit(Event::DownloadProgress {
+            instance: self.instance_name.clone(),
+            progress: progress.clone(),
+        });
         if let Some(ref sender) = self.sender {
             sender.send(progress)?;
         }
@@ -296,9 +444,56 @@ impl GameDownloader {
     }
 }
 
+/// A version JSON from somewhere other than a manifest, for
+/// clients and experimental builds the launcher doesn't list.
+/// It has to be a complete one, not one that `inheritsFrom`
+/// another version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CustomVersionJson {
+    File(PathBuf),
+    Url(String),
+}
+
+impl CustomVersionJson {
+    /// A URL if `input` starts with `http://` or
+    /// `https://`, and a file path otherwise.
+    pub fn parse(input: &str) -> Self {
+        let input = input.trim();
+        if input.starts_with("http://") || input.starts_with("https://") {
+            CustomVersionJson::Url(input.to_owned())
+        } else {
+            CustomVersionJson::File(PathBuf::from(input))
+        }
+    }
+
+    pub async fn read<E: From<IoError> + From<RequestError>>(
+        &self,
+        client: &Client,
+    ) -> Result<String, E> {
+        match self {
+            CustomVersionJson::File(path) => Ok(tokio::fs::read_to_string(path)
+                .await
+                .map_err(io_err!(path))?),
+            CustomVersionJson::Url(url) => {
+                Ok(file_utils::download_file_to_string(client, url).await?)
+            }
+        }
+    }
+}
+
+impl Display for CustomVersionJson {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CustomVersionJson::File(path) => write!(f, "{}", path.display()),
+            CustomVersionJson::Url(url) => write!(f, "{url}"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum DownloadError {
     Json(serde_json::Error),
+    JsonFile(JsonFileError),
     Request(RequestError),
     Io(IoError),
     InstanceAlreadyExists,
@@ -307,6 +502,13 @@ pub enum DownloadError {
     SerdeFieldNotFound(String),
     NativesExtractError(ZipExtractError),
     NativesOutsideDirRemove,
+    AssetTaskFailed(JoinError),
+    NoServerJar(String),
+    NoServerSoftwareBuild {
+        software: ServerSoftware,
+        version: String,
+    },
+    Cancelled(Cancelled),
 }
 
 impl From<serde_json::Error> for DownloadError {
@@ -315,6 +517,12 @@ impl From<serde_json::Error> for DownloadError {
     }
 }
 
+impl From<JsonFileError> for DownloadError {
+    fn from(value: JsonFileError) -> Self {
+        Self::JsonFile(value)
+    }
+}
+
 impl From<RequestError> for DownloadError {
     fn from(value: RequestError) -> Self {
         Self::Request(value)
@@ -333,6 +541,12 @@ impl From<SendError<DownloadProgress>> for DownloadError {
     }
 }
 
+impl From<Cancelled> for DownloadError {
+    fn from(value: Cancelled) -> Self {
+        Self::Cancelled(value)
+    }
+}
+
 impl From<JsonDownloadError> for DownloadError {
     fn from(value: JsonDownloadError) -> Self {
         match value {
@@ -346,6 +560,7 @@ impl Display for DownloadError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             DownloadError::Json(err) => write!(f, "download error: json error {err}"),
+            DownloadError::JsonFile(err) => write!(f, "download error: {err}"),
             DownloadError::Request(err) => write!(f, "download error: {err}"),
             DownloadError::Io(err) => write!(f, "download error: {err}"),
             DownloadError::InstanceAlreadyExists => {
@@ -356,6 +571,10 @@ impl Display for DownloadError {
             DownloadError::SerdeFieldNotFound(err) => write!(f, "download error: serde field not found \"{err}\""),
             DownloadError::NativesExtractError(err) => write!(f, "download error: could not extract native libraries: {err}"),
             DownloadError::NativesOutsideDirRemove => write!(f, "download error: tried to remove natives outside folder. POTENTIAL SECURITY RISK AVOIDED"),
+            DownloadError::AssetTaskFailed(err) => write!(f, "download error: asset download task failed: {err}"),
+            DownloadError::NoServerJar(version) => write!(f, "download error: Minecraft {version} has no server jar"),
+            DownloadError::NoServerSoftwareBuild { software, version } => write!(f, "download error: {software} has no build for Minecraft {version}"),
+            DownloadError::Cancelled(err) => write!(f, "download {err}"),
         }
     }
 }
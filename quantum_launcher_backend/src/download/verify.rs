@@ -0,0 +1,256 @@
+//! Checking an instance's libraries and natives before
+//! launching, and downloading them again if they're broken.
+//!
+//! Hashing and extracting is spread over all CPU cores,
+//! so that even large instances are checked in a second or two.
+
+use std::{
+    io::{Cursor, Read},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+};
+
+use reqwest::Client;
+use tokio::task::JoinSet;
+use tracing::{info, warn};
+
+use crate::{
+    download_queue,
+    error::{LauncherError, LauncherResult},
+    file_utils, io_err,
+    json_structs::json_version::{LibraryDownloads, VersionDetails},
+    logging,
+};
+
+use super::{constants::OS_NAME, GameDownloader};
+
+/// A library file to check, from `details.json`.
+#[derive(Debug, Clone)]
+pub struct LibraryFile {
+    pub path: PathBuf,
+    pub url: String,
+    pub sha1: String,
+}
+
+impl LibraryFile {
+    /// Whether the file exists and matches its hash.
+    fn is_intact(&self) -> bool {
+        let Ok(bytes) = std::fs::read(&self.path) else {
+            return false;
+        };
+        file_utils::sha1_hex(&bytes).eq_ignore_ascii_case(&self.sha1)
+    }
+}
+
+/// A natives jar, which is extracted into
+/// `libraries/natives` instead of being kept.
+#[derive(Debug, Clone)]
+struct NativesJar {
+    url: String,
+    exclude: Vec<String>,
+}
+
+/// Checks the instance's libraries and natives, and downloads
+/// the missing or broken ones again. Nothing is downloaded
+/// if everything is fine, so this works offline.
+pub async fn repair_instance(
+    instance_dir: &Path,
+    version_json: &VersionDetails,
+) -> LauncherResult<()> {
+    let _timer = logging::time_step("Checking libraries");
+
+    let broken = find_broken_libraries(instance_dir, version_json).await;
+    if !broken.is_empty() {
+        warn!(
+            "{} libraries are missing or broken, downloading them again",
+            broken.len()
+        );
+        download_libraries(broken).await?;
+    }
+
+    let natives_dir = instance_dir.join("libraries").join("natives");
+    let natives = natives_jars(version_json);
+    if !natives.is_empty() && is_empty_dir(&natives_dir).await {
+        warn!("The natives are missing, extracting them again");
+        extract_natives(natives, &natives_dir).await?;
+    }
+    Ok(())
+}
+
+/// The libraries (not natives) the instance needs on this OS.
+pub fn library_files(instance_dir: &Path, version_json: &VersionDetails) -> Vec<LibraryFile> {
+    let libraries_dir = instance_dir.join("libraries");
+    version_json
+        .libraries
+        .iter()
+        .filter(|library| GameDownloader::download_libraries_library_is_allowed(library))
+        .filter_map(|library| match library.downloads.as_ref()? {
+            LibraryDownloads::Normal { artifact, .. } => Some(LibraryFile {
+                path: libraries_dir.join(&artifact.path),
+                url: artifact.url.clone(),
+                sha1: artifact.sha1.clone(),
+            }),
+            LibraryDownloads::Native { .. } => None,
+        })
+        .collect()
+}
+
+/// The libraries that are missing or don't match their hash,
+/// hashing one chunk of them on each CPU core.
+pub async fn find_broken_libraries(
+    instance_dir: &Path,
+    version_json: &VersionDetails,
+) -> Vec<LibraryFile> {
+    let libraries = library_files(instance_dir, version_json);
+    let workers = std::thread::available_parallelism().map_or(4, NonZeroUsize::get);
+    let chunk_size = libraries.len().div_ceil(workers).max(1);
+
+    let mut tasks = JoinSet::new();
+    for chunk in libraries.chunks(chunk_size) {
+        let chunk = chunk.to_vec();
+        tasks.spawn_blocking(move || {
+            chunk
+                .into_iter()
+                .filter(|library| !library.is_intact())
+                .collect::<Vec<_>>()
+        });
+    }
+
+    let mut broken = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        match result {
+            Ok(chunk) => broken.extend(chunk),
+            Err(err) => std::panic::resume_unwind(err.into_panic()),
+        }
+    }
+    broken
+}
+
+async fn download_libraries(libraries: Vec<LibraryFile>) -> LauncherResult<()> {
+    let client = file_utils::create_client();
+    let parallel_downloads = file_utils::get_download_settings()
+        .parallel_downloads
+        .max(1);
+
+    let mut tasks = JoinSet::new();
+    for library in libraries {
+        if tasks.len() >= parallel_downloads {
+            join_next(&mut tasks).await?;
+        }
+        tasks.spawn(download_queue::in_current_job(download_library(
+            client.clone(),
+            library,
+        )));
+    }
+    while !tasks.is_empty() {
+        join_next(&mut tasks).await?;
+    }
+    Ok(())
+}
+
+async fn download_library(client: Client, library: LibraryFile) -> LauncherResult<()> {
+    info!("Downloading library {:?}", library.path);
+    let bytes = file_utils::download_file_to_bytes(&client, &library.url).await?;
+    if let Some(parent) = library.path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(io_err!(parent))?;
+    }
+    tokio::fs::write(&library.path, bytes)
+        .await
+        .map_err(io_err!(library.path))?;
+    Ok(())
+}
+
+fn natives_jars(version_json: &VersionDetails) -> Vec<NativesJar> {
+    let classifier = format!("natives-{OS_NAME}");
+    version_json
+        .libraries
+        .iter()
+        .filter(|library| GameDownloader::download_libraries_library_is_allowed(library))
+        .filter_map(|library| match library.downloads.as_ref()? {
+            LibraryDownloads::Native { classifiers } => Some(NativesJar {
+                url: classifiers.get(&classifier)?.url.clone(),
+                exclude: library
+                    .extract
+                    .as_ref()
+                    .map(|extract| extract.exclude.clone())
+                    .unwrap_or_default(),
+            }),
+            LibraryDownloads::Normal { .. } => None,
+        })
+        .collect()
+}
+
+/// Downloads and extracts several natives jars at once.
+///
+/// Excluded files (like `META-INF/`) are skipped while extracting
+/// rather than deleted afterwards, so the jars never write the
+/// same file at the same time.
+async fn extract_natives(jars: Vec<NativesJar>, natives_dir: &Path) -> LauncherResult<()> {
+    tokio::fs::create_dir_all(natives_dir)
+        .await
+        .map_err(io_err!(natives_dir))?;
+    let client = file_utils::create_client();
+    let parallel_downloads = file_utils::get_download_settings()
+        .parallel_downloads
+        .max(1);
+
+    let mut tasks = JoinSet::new();
+    for jar in jars {
+        if tasks.len() >= parallel_downloads {
+            join_next(&mut tasks).await?;
+        }
+        let client = client.clone();
+        let natives_dir = natives_dir.to_owned();
+        tasks.spawn(download_queue::in_current_job(async move {
+            let bytes = file_utils::download_file_to_bytes(&client, &jar.url).await?;
+            file_utils::run_blocking(move || extract_jar(&bytes, &natives_dir, &jar.exclude)).await
+        }));
+    }
+    while !tasks.is_empty() {
+        join_next(&mut tasks).await?;
+    }
+    Ok(())
+}
+
+fn extract_jar(bytes: &[u8], natives_dir: &Path, exclude: &[String]) -> LauncherResult<()> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+        .map_err(|err| LauncherError::NativesExtractError(err.into()))?;
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|err| LauncherError::NativesExtractError(err.into()))?;
+        // Also rejects paths that would escape `natives_dir`.
+        let Some(name) = file.enclosed_name().map(Path::to_owned) else {
+            continue;
+        };
+        if file.is_dir() || exclude.iter().any(|n| name.starts_with(n)) {
+            continue;
+        }
+
+        let path = natives_dir.join(&name);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(io_err!(parent))?;
+        }
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents).map_err(io_err!(path))?;
+        std::fs::write(&path, contents).map_err(io_err!(path))?;
+    }
+    Ok(())
+}
+
+async fn join_next(tasks: &mut JoinSet<LauncherResult<()>>) -> LauncherResult<()> {
+    match tasks.join_next().await {
+        Some(Ok(result)) => result,
+        Some(Err(err)) => std::panic::resume_unwind(err.into_panic()),
+        None => Ok(()),
+    }
+}
+
+async fn is_empty_dir(dir: &Path) -> bool {
+    match tokio::fs::read_dir(dir).await {
+        Ok(mut entries) => matches!(entries.next_entry().await, Ok(None)),
+        Err(_) => true,
+    }
+}
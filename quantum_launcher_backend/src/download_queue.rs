@@ -0,0 +1,282 @@
+//! One queue for the downloads of every operation (creating
+//! instances, installing mods or Java), so that they can be
+//! seen, paused and reordered in one place.
+//!
+//! An operation does its downloads inside [`run`], which adds
+//! a job to the end of the queue. Only the first
+//! [`MAX_ACTIVE_JOBS`] unpaused jobs download at a time; the
+//! others wait (before each chunk of a file) until they move up.
+//! Downloads outside of a job, like search results or version
+//! lists, skip the queue.
+
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, MutexGuard, OnceLock,
+    },
+};
+
+use tokio::sync::Notify;
+
+/// How many jobs download at the same time.
+pub const MAX_ACTIVE_JOBS: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    Instance,
+    Mods,
+    Java,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStatus {
+    Active,
+    /// Waiting for jobs in front of it to finish (or be paused).
+    Queued,
+    Paused,
+}
+
+/// A job in the queue, as of the last [`snapshot`].
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub id: u64,
+    pub kind: JobKind,
+    /// Like the instance name or Java version.
+    pub name: String,
+    pub status: JobStatus,
+    /// How much has been downloaded so far.
+    pub bytes: u64,
+    /// How many files have finished downloading.
+    pub files: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct QueueSnapshot {
+    /// In order, with the first jobs downloading.
+    pub jobs: Vec<Job>,
+    /// Bytes downloaded since the launcher started, by jobs
+    /// and everything else, for working out the speed.
+    pub total_bytes: u64,
+}
+
+struct Entry {
+    id: u64,
+    kind: JobKind,
+    name: String,
+    paused: bool,
+    bytes: u64,
+    files: usize,
+}
+
+static QUEUE: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+static TOTAL_BYTES: AtomicU64 = AtomicU64::new(0);
+
+tokio::task_local! {
+    static CURRENT_JOB: u64;
+}
+
+fn queue() -> MutexGuard<'static, Vec<Entry>> {
+    QUEUE.lock().unwrap()
+}
+
+/// Wakes up jobs waiting for their turn, after the queue changes.
+fn changed() -> &'static Notify {
+    static CHANGED: OnceLock<Notify> = OnceLock::new();
+    CHANGED.get_or_init(Notify::new)
+}
+
+/// Removes the job from the queue when it's done,
+/// including when its future is dropped partway.
+struct JobGuard(u64);
+
+impl Drop for JobGuard {
+    fn drop(&mut self) {
+        queue().retain(|entry| entry.id != self.0);
+        changed().notify_waiters();
+    }
+}
+
+/// Runs `operation` as a job in the queue. Its downloads
+/// wait for the job's turn.
+pub async fn run<F: Future>(kind: JobKind, name: String, operation: F) -> F::Output {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    queue().push(Entry {
+        id,
+        kind,
+        name,
+        paused: false,
+        bytes: 0,
+        files: 0,
+    });
+    let _guard = JobGuard(id);
+    CURRENT_JOB.scope(id, operation).await
+}
+
+/// Keeps a spawned task in the job of the task spawning it,
+/// since tasks don't share the job otherwise.
+pub fn in_current_job<F: Future>(task: F) -> impl Future<Output = F::Output> {
+    let job = current_job();
+    async move {
+        match job {
+            Some(id) => CURRENT_JOB.scope(id, task).await,
+            None => task.await,
+        }
+    }
+}
+
+fn current_job() -> Option<u64> {
+    CURRENT_JOB.try_with(|id| *id).ok()
+}
+
+fn is_turn(queue: &[Entry], id: u64) -> bool {
+    queue
+        .iter()
+        .filter(|entry| !entry.paused)
+        .take(MAX_ACTIVE_JOBS)
+        .any(|entry| entry.id == id)
+        // Not in the queue, so nothing to wait for.
+        || !queue.iter().any(|entry| entry.id == id)
+}
+
+/// Waits until the current job may download. Returns
+/// straight away outside of a job.
+pub(crate) async fn wait_for_turn() {
+    let Some(id) = current_job() else {
+        return;
+    };
+    loop {
+        // Listen before checking, so that a change in
+        // between isn't missed.
+        let mut notified = std::pin::pin!(changed().notified());
+        notified.as_mut().enable();
+        if is_turn(&queue(), id) {
+            return;
+        }
+        notified.await;
+    }
+}
+
+pub(crate) fn add_bytes(bytes: usize) {
+    TOTAL_BYTES.fetch_add(bytes as u64, Ordering::Relaxed);
+    if let Some(id) = current_job() {
+        if let Some(entry) = queue().iter_mut().find(|entry| entry.id == id) {
+            entry.bytes += bytes as u64;
+        }
+    }
+}
+
+pub(crate) fn file_finished() {
+    if let Some(id) = current_job() {
+        if let Some(entry) = queue().iter_mut().find(|entry| entry.id == id) {
+            entry.files += 1;
+        }
+    }
+}
+
+pub fn snapshot() -> QueueSnapshot {
+    let queue = queue();
+    let mut unpaused = 0;
+    let jobs = queue
+        .iter()
+        .map(|entry| Job {
+            id: entry.id,
+            kind: entry.kind,
+            name: entry.name.clone(),
+            status: if entry.paused {
+                JobStatus::Paused
+            } else {
+                unpaused += 1;
+                if unpaused <= MAX_ACTIVE_JOBS {
+                    JobStatus::Active
+                } else {
+                    JobStatus::Queued
+                }
+            },
+            bytes: entry.bytes,
+            files: entry.files,
+        })
+        .collect();
+    QueueSnapshot {
+        jobs,
+        total_bytes: TOTAL_BYTES.load(Ordering::Relaxed),
+    }
+}
+
+fn update(change: impl FnOnce(&mut Vec<Entry>)) {
+    change(&mut queue());
+    changed().notify_waiters();
+}
+
+/// Stops the job's downloads (between chunks)
+/// and lets the next job in the queue start.
+pub fn pause(id: u64) {
+    set_paused(id, true);
+}
+
+pub fn resume(id: u64) {
+    set_paused(id, false);
+}
+
+fn set_paused(id: u64, paused: bool) {
+    update(|queue| {
+        if let Some(entry) = queue.iter_mut().find(|entry| entry.id == id) {
+            entry.paused = paused;
+        }
+    });
+}
+
+/// Moves the job one place towards the front of the queue.
+pub fn move_up(id: u64) {
+    update(|queue| {
+        if let Some(index) = queue.iter().position(|entry| entry.id == id) {
+            if index > 0 {
+                queue.swap(index, index - 1);
+            }
+        }
+    });
+}
+
+/// Moves the job one place towards the back of the queue.
+pub fn move_down(id: u64) {
+    update(|queue| {
+        if let Some(index) = queue.iter().position(|entry| entry.id == id) {
+            if index + 1 < queue.len() {
+                queue.swap(index, index + 1);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: u64, paused: bool) -> Entry {
+        Entry {
+            id,
+            kind: JobKind::Mods,
+            name: String::new(),
+            paused,
+            bytes: 0,
+            files: 0,
+        }
+    }
+
+    #[test]
+    fn test_paused_jobs_let_the_next_one_go() {
+        let queue = [
+            entry(0, false),
+            entry(1, true),
+            entry(2, false),
+            entry(3, false),
+        ];
+        assert!(is_turn(&queue, 0));
+        assert!(!is_turn(&queue, 1));
+        assert!(is_turn(&queue, 2));
+        assert!(!is_turn(&queue, 3));
+        // Finished jobs aren't held up.
+        assert!(is_turn(&queue, 4));
+    }
+}
@@ -1,30 +1,56 @@
-use std::{fmt::Display, path::PathBuf, string::FromUtf8Error, sync::mpsc::SendError};
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    string::FromUtf8Error,
+};
 
 use serde_json::Error as SerdeJsonError;
 use zip_extract::ZipExtractError;
 
 use crate::{
-    download::progress::DownloadProgress,
+    cancel::Cancelled,
+    download::{progress::DownloadProgress, DownloadError},
     file_utils::RequestError,
+    instance::{
+        instance_mod_installer::{
+            fabric::FabricInstallError, modrinth::ModInstallError, server_pack::ServerPackError,
+        },
+        launch_options::UsernameProblem,
+        server_schedule::BackupError,
+        snapshots::SnapshotError,
+        sync_folder::SyncError,
+        templates::TemplateError,
+        worlds::WorldError,
+    },
     java_install::JavaInstallError,
     json_structs::{json_version::VersionDetails, JsonDownloadError, JsonFileError},
+    launcher_backup::LauncherBackupError,
+    log_upload::LogUploadError,
+    nbt::NbtError,
+    progress::SendError,
+    self_update::UpdateError,
+    server_ping::PingError,
+    skins::SkinError,
 };
 
 #[derive(Debug)]
 pub enum LauncherError {
     ConfigDirNotFound,
     InstanceNotFound,
-    UsernameIsInvalid(String),
+    UsernameIsInvalid(String, UsernameProblem),
     InstanceAlreadyExists,
     RequestError(RequestError),
     SerdeJsonError(serde_json::Error),
     SerdeFieldNotFound(&'static str),
     VersionNotFoundInManifest(String),
+    /// The instance has no version details, or
+    /// anything else that says its Minecraft version.
+    InstanceVersionUnknown,
     JavaVersionIsEmptyError,
     JavaVersionConvertCmdOutputToStringError(FromUtf8Error),
     JavaVersionImproperVersionPlacement(String),
     JavaVersionParseToNumberError(String),
-    VersionJsonNoArgumentsField(VersionDetails),
+    VersionJsonNoArgumentsField(Box<VersionDetails>),
     PathBufToString(PathBuf),
     RequiredJavaVersionNotFound(usize),
     DownloadProgressMspcError(SendError<DownloadProgress>),
@@ -35,13 +61,73 @@ pub enum LauncherError {
     TempFileError(std::io::Error),
     NativesExtractError(ZipExtractError),
     NativesOutsideDirRemove,
+    InstanceOutsideDirRemove,
     JsonDownloadError(JsonDownloadError),
     JsonFileError(JsonFileError),
     JavaInstall(JavaInstallError),
+    EulaNotAccepted,
+    DataDirConflict(PathBuf),
+    /// Neither a `.zip` file nor a folder with a `pack.mcmeta`.
+    ResourcePackInvalid(PathBuf),
+    WorldBackup(Box<BackupError>),
+    Sync(Box<SyncError>),
+    /// Watching the instance folders for changes failed.
+    FolderWatch(notify::Error),
+    /// An error while starting the game or a server,
+    /// with the step it happened in.
+    Launch {
+        phase: LaunchPhase,
+        /// The file the step was about, like `config.json`.
+        path: Option<PathBuf>,
+        cause: Box<LauncherError>,
+    },
 }
 
 pub type LauncherResult<T> = Result<T, LauncherError>;
 
+/// A step of starting the game (or a server), so that
+/// the GUI can suggest how to fix what went wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchPhase {
+    /// Reading the instance's `config.json`.
+    ReadConfig,
+    /// Reading the version's `details.json`.
+    ReadVersionJson,
+    /// Building the classpath and arguments from the libraries.
+    Classpath,
+    /// Finding Java, or installing it.
+    Java,
+    /// Starting the Java process.
+    Spawn,
+}
+
+impl Display for LaunchPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LaunchPhase::ReadConfig => write!(f, "could not read the instance config"),
+            LaunchPhase::ReadVersionJson => write!(f, "could not read the version details"),
+            LaunchPhase::Classpath => write!(f, "could not set up the game's libraries"),
+            LaunchPhase::Java => write!(f, "could not get Java"),
+            LaunchPhase::Spawn => write!(f, "could not start Java"),
+        }
+    }
+}
+
+/// Adds the launch step (and the file it was about) to an error.
+pub(crate) trait LaunchContext<T> {
+    fn in_phase(self, phase: LaunchPhase, path: Option<&Path>) -> LauncherResult<T>;
+}
+
+impl<T, E: Into<LauncherError>> LaunchContext<T> for Result<T, E> {
+    fn in_phase(self, phase: LaunchPhase, path: Option<&Path>) -> LauncherResult<T> {
+        self.map_err(|err| LauncherError::Launch {
+            phase,
+            path: path.map(Path::to_owned),
+            cause: Box::new(err.into()),
+        })
+    }
+}
+
 macro_rules! impl_error {
     ($from:ident, $to:ident) => {
         impl From<$from> for LauncherError {
@@ -57,11 +143,57 @@ impl_error!(SerdeJsonError, SerdeJsonError);
 impl_error!(FromUtf8Error, JavaVersionConvertCmdOutputToStringError);
 impl_error!(JsonFileError, JsonFileError);
 impl_error!(IoError, IoError);
+impl_error!(RequestError, RequestError);
 impl_error!(JavaInstallError, JavaInstall);
 
 type ProgressSendError = SendError<DownloadProgress>;
 impl_error!(ProgressSendError, DownloadProgressMspcError);
 
+impl LauncherError {
+    /// See [`JsonFileError::restorable_file`].
+    pub fn restorable_file(&self) -> Option<&Path> {
+        match self {
+            LauncherError::JsonFileError(err) => err.restorable_file(),
+            LauncherError::Launch { cause, .. } => cause.restorable_file(),
+            _ => None,
+        }
+    }
+
+    /// The step of launching that failed, if it's a launch error.
+    pub fn launch_phase(&self) -> Option<LaunchPhase> {
+        match self {
+            LauncherError::Launch { phase, .. } => Some(*phase),
+            _ => None,
+        }
+    }
+}
+
+/// Why launching the game or a server failed, for the GUI.
+#[derive(Debug, Clone)]
+pub struct LaunchFailure {
+    /// The error message, with its code.
+    pub message: String,
+    pub phase: Option<LaunchPhase>,
+    /// See [`LauncherError::restorable_file`].
+    pub restorable_file: Option<PathBuf>,
+}
+
+impl From<LauncherError> for LaunchFailure {
+    fn from(err: LauncherError) -> Self {
+        Self {
+            message: err.to_string_with_code(),
+            phase: err.launch_phase(),
+            restorable_file: err.restorable_file().map(Path::to_owned),
+        }
+    }
+}
+
+impl Display for LaunchFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
 impl Display for LauncherError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -81,6 +213,9 @@ impl Display for LauncherError {
             LauncherError::VersionNotFoundInManifest(n) => {
                 write!(f, "version {n} was not found in manifest JSON")
             }
+            LauncherError::InstanceVersionUnknown => {
+                write!(f, "could not tell which Minecraft version the instance is")
+            }
             LauncherError::JavaVersionIsEmptyError => write!(
                 f,
                 "got empty or invalid response when checking Java version"
@@ -106,7 +241,9 @@ impl Display for LauncherError {
                 f,
                 "the Java version ({ver}) required by the Minecraft version was not found"
             ),
-            LauncherError::UsernameIsInvalid(n) => write!(f, "username is invalid: {n}"),
+            LauncherError::UsernameIsInvalid(n, problem) => {
+                write!(f, "username {n:?} is invalid, {problem}")
+            }
             LauncherError::DownloadProgressMspcError(n) => {
                 write!(f, "could not send download progress: {n}")
             }
@@ -125,10 +262,32 @@ impl Display for LauncherError {
                 write!(f, "could not extract natives jar file as zip: {err}")
             }
             LauncherError::NativesOutsideDirRemove => write!(f, "tried to delete natives file outside QuantumLauncher/instances/INSTANCE/libraries/natives. POTENTIAL ATTACK AVOIDED"),
+            LauncherError::InstanceOutsideDirRemove => write!(f, "tried to delete instance folder located outside QuantumLauncher/instances. POTENTIAL ATTACK AVOIDED"),
             LauncherError::RequestError(err) => write!(f, "{err}"),
             LauncherError::JsonDownloadError(err) => write!(f, "{err}"),
             LauncherError::JsonFileError(err) => write!(f, "{err}"),
             LauncherError::JavaInstall(err) => write!(f, "{err}"),
+            LauncherError::EulaNotAccepted => write!(
+                f,
+                "the Minecraft EULA has to be accepted before starting the server (see eula.txt)"
+            ),
+            LauncherError::DataDirConflict(path) => write!(
+                f,
+                "could not move the launcher's data, {path:?} already exists"
+            ),
+            LauncherError::ResourcePackInvalid(path) => write!(
+                f,
+                "{path:?} is not a resource pack, it must be a .zip file or a folder with a pack.mcmeta"
+            ),
+            LauncherError::WorldBackup(err) => write!(f, "{err}"),
+            LauncherError::Sync(err) => write!(f, "{err}"),
+            LauncherError::FolderWatch(err) => {
+                write!(f, "could not watch the instance folders: {err}")
+            }
+            LauncherError::Launch { phase, path, cause } => match path {
+                Some(path) => write!(f, "{phase} ({path:?}): {cause}"),
+                None => write!(f, "{phase}: {cause}"),
+            },
         }
     }
 }
@@ -160,3 +319,411 @@ macro_rules! io_err {
         }
     };
 }
+
+/// A stable identifier for a kind of error.
+///
+/// Error messages can change between versions, but codes don't,
+/// so scripts and bug reports can rely on them. The number doubles
+/// as the exit status of the command line interface, so it fits in a `u8`.
+///
+/// Once released, a code is never renumbered or reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ErrorCode {
+    pub number: u8,
+    pub name: &'static str,
+}
+
+impl ErrorCode {
+    pub const fn new(number: u8, name: &'static str) -> Self {
+        Self { number, name }
+    }
+
+    pub const CONFIG_DIR_NOT_FOUND: Self = Self::new(10, "config_dir_not_found");
+
+    pub const INSTANCE_NOT_FOUND: Self = Self::new(20, "instance_not_found");
+    pub const INSTANCE_ALREADY_EXISTS: Self = Self::new(21, "instance_already_exists");
+    pub const USERNAME_INVALID: Self = Self::new(22, "username_invalid");
+    pub const INSTANCE_OUTSIDE_DIR: Self = Self::new(23, "instance_outside_dir");
+    pub const VERSION_NOT_FOUND: Self = Self::new(24, "version_not_found");
+    pub const VERSION_JSON_INVALID: Self = Self::new(25, "version_json_invalid");
+    pub const SERVER_JAR_UNAVAILABLE: Self = Self::new(26, "server_jar_unavailable");
+    pub const EULA_NOT_ACCEPTED: Self = Self::new(27, "eula_not_accepted");
+    pub const SERVER_SETTING_INVALID: Self = Self::new(28, "server_setting_invalid");
+    pub const WORLD_NOT_FOUND: Self = Self::new(29, "world_not_found");
+
+    pub const REQUEST_FAILED: Self = Self::new(30, "request_failed");
+    pub const HTTP_ERROR: Self = Self::new(31, "http_error");
+    pub const RESPONSE_INVALID: Self = Self::new(32, "response_invalid");
+    pub const SERVER_UNREACHABLE: Self = Self::new(33, "server_unreachable");
+    pub const SERVER_ADDRESS_INVALID: Self = Self::new(34, "server_address_invalid");
+    pub const UPDATE_UNAVAILABLE: Self = Self::new(35, "update_unavailable");
+    pub const UPDATE_INVALID: Self = Self::new(36, "update_invalid");
+
+    pub const JAVA_VERSION_UNREADABLE: Self = Self::new(40, "java_version_unreadable");
+    pub const JAVA_NOT_FOUND: Self = Self::new(41, "java_not_found");
+    pub const JAVA_DOWNLOAD_UNAVAILABLE: Self = Self::new(42, "java_download_unavailable");
+    pub const JAVA_START_FAILED: Self = Self::new(43, "java_start_failed");
+
+    pub const IO_ERROR: Self = Self::new(50, "io_error");
+    pub const INVALID_PATH: Self = Self::new(51, "invalid_path");
+    pub const NATIVES_EXTRACT_FAILED: Self = Self::new(52, "natives_extract_failed");
+    pub const NATIVES_OUTSIDE_DIR: Self = Self::new(53, "natives_outside_dir");
+    pub const TEMP_FILE_FAILED: Self = Self::new(54, "temp_file_failed");
+    pub const BACKUP_FAILED: Self = Self::new(55, "backup_failed");
+    pub const DATA_DIR_CONFLICT: Self = Self::new(56, "data_dir_conflict");
+    pub const BACKUP_INVALID: Self = Self::new(57, "backup_invalid");
+    pub const WORLD_INVALID: Self = Self::new(58, "world_invalid");
+    /// Another machine is playing a synced instance.
+    pub const INSTANCE_LOCKED: Self = Self::new(59, "instance_locked");
+
+    pub const JSON_INVALID: Self = Self::new(60, "json_invalid");
+    pub const JSON_FIELD_MISSING: Self = Self::new(61, "json_field_missing");
+    pub const CONFIG_VERSION_UNSUPPORTED: Self = Self::new(62, "config_version_unsupported");
+    /// A setting for an instance's `options.txt`.
+    pub const GAME_SETTING_INVALID: Self = Self::new(63, "game_setting_invalid");
+
+    pub const FABRIC_VERSION_NOT_FOUND: Self = Self::new(70, "fabric_version_not_found");
+    pub const MOD_LOADER_MISSING: Self = Self::new(71, "mod_loader_missing");
+    pub const MOD_NOT_FOUND: Self = Self::new(72, "mod_not_found");
+    pub const MOD_VERSION_NOT_FOUND: Self = Self::new(73, "mod_version_not_found");
+    pub const MOD_FILE_NAME_INVALID: Self = Self::new(74, "mod_file_name_invalid");
+    pub const MOD_LOADER_UNSUPPORTED: Self = Self::new(75, "mod_loader_unsupported");
+    pub const SERVER_PACK_INVALID: Self = Self::new(76, "server_pack_invalid");
+    pub const RESOURCE_PACK_INVALID: Self = Self::new(77, "resource_pack_invalid");
+    pub const SKIN_INVALID: Self = Self::new(78, "skin_invalid");
+    pub const FOLDER_WATCH_FAILED: Self = Self::new(79, "folder_watch_failed");
+
+    /// Something went wrong inside the launcher itself,
+    /// like a progress channel or background task failing.
+    pub const INTERNAL: Self = Self::new(90, "internal");
+    /// The user stopped the operation, see [`crate::cancel`].
+    pub const CANCELLED: Self = Self::new(91, "cancelled");
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "E{:03} {}", self.number, self.name)
+    }
+}
+
+/// An error that can be identified by an [`ErrorCode`].
+pub trait HasErrorCode: Display {
+    fn code(&self) -> ErrorCode;
+
+    /// The error message followed by its code,
+    /// for errors that get turned into a `String`.
+    fn to_string_with_code(&self) -> String {
+        format!("{self} ({})", self.code())
+    }
+}
+
+impl HasErrorCode for LauncherError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            LauncherError::ConfigDirNotFound => ErrorCode::CONFIG_DIR_NOT_FOUND,
+            LauncherError::InstanceNotFound => ErrorCode::INSTANCE_NOT_FOUND,
+            LauncherError::UsernameIsInvalid(..) => ErrorCode::USERNAME_INVALID,
+            LauncherError::InstanceAlreadyExists => ErrorCode::INSTANCE_ALREADY_EXISTS,
+            LauncherError::SerdeJsonError(err) => err.code(),
+            LauncherError::SerdeFieldNotFound(_) => ErrorCode::JSON_FIELD_MISSING,
+            LauncherError::VersionNotFoundInManifest(_) | LauncherError::InstanceVersionUnknown => {
+                ErrorCode::VERSION_NOT_FOUND
+            }
+            LauncherError::JavaVersionIsEmptyError
+            | LauncherError::JavaVersionConvertCmdOutputToStringError(_)
+            | LauncherError::JavaVersionImproperVersionPlacement(_)
+            | LauncherError::JavaVersionParseToNumberError(_) => ErrorCode::JAVA_VERSION_UNREADABLE,
+            LauncherError::VersionJsonNoArgumentsField(_) => ErrorCode::VERSION_JSON_INVALID,
+            LauncherError::PathBufToString(_) | LauncherError::PathParentError(_) => {
+                ErrorCode::INVALID_PATH
+            }
+            LauncherError::RequiredJavaVersionNotFound(_) => ErrorCode::JAVA_NOT_FOUND,
+            LauncherError::DownloadProgressMspcError(_) => ErrorCode::INTERNAL,
+            LauncherError::CommandError(_) => ErrorCode::JAVA_START_FAILED,
+            LauncherError::LatestFabricVersionNotFound => ErrorCode::FABRIC_VERSION_NOT_FOUND,
+            LauncherError::TempFileError(_) => ErrorCode::TEMP_FILE_FAILED,
+            LauncherError::NativesExtractError(_) => ErrorCode::NATIVES_EXTRACT_FAILED,
+            LauncherError::NativesOutsideDirRemove => ErrorCode::NATIVES_OUTSIDE_DIR,
+            LauncherError::InstanceOutsideDirRemove => ErrorCode::INSTANCE_OUTSIDE_DIR,
+            LauncherError::IoError(err) => err.code(),
+            LauncherError::RequestError(err) => err.code(),
+            LauncherError::JsonDownloadError(err) => err.code(),
+            LauncherError::JsonFileError(err) => err.code(),
+            LauncherError::JavaInstall(err) => err.code(),
+            LauncherError::EulaNotAccepted => ErrorCode::EULA_NOT_ACCEPTED,
+            LauncherError::DataDirConflict(_) => ErrorCode::DATA_DIR_CONFLICT,
+            LauncherError::ResourcePackInvalid(_) => ErrorCode::RESOURCE_PACK_INVALID,
+            LauncherError::WorldBackup(err) => err.code(),
+            LauncherError::Sync(err) => err.code(),
+            LauncherError::FolderWatch(_) => ErrorCode::FOLDER_WATCH_FAILED,
+            LauncherError::Launch { cause, .. } => cause.code(),
+        }
+    }
+}
+
+impl HasErrorCode for IoError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            IoError::Io { .. } => ErrorCode::IO_ERROR,
+            IoError::ConfigDirNotFound => ErrorCode::CONFIG_DIR_NOT_FOUND,
+        }
+    }
+}
+
+impl HasErrorCode for RequestError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            RequestError::DownloadError { .. } => ErrorCode::HTTP_ERROR,
+            RequestError::ReqwestError(_) => ErrorCode::REQUEST_FAILED,
+        }
+    }
+}
+
+impl HasErrorCode for SerdeJsonError {
+    fn code(&self) -> ErrorCode {
+        ErrorCode::JSON_INVALID
+    }
+}
+
+impl HasErrorCode for JsonDownloadError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            JsonDownloadError::RequestError(err) => err.code(),
+            JsonDownloadError::SerdeError(_) => ErrorCode::RESPONSE_INVALID,
+        }
+    }
+}
+
+impl HasErrorCode for JsonFileError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            JsonFileError::SerdeError(err) => err.code(),
+            JsonFileError::Io(err) => err.code(),
+            JsonFileError::UnsupportedVersion { .. } => ErrorCode::CONFIG_VERSION_UNSUPPORTED,
+            JsonFileError::Invalid(_) => ErrorCode::JSON_INVALID,
+        }
+    }
+}
+
+impl HasErrorCode for Cancelled {
+    fn code(&self) -> ErrorCode {
+        ErrorCode::CANCELLED
+    }
+}
+
+impl HasErrorCode for JavaInstallError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            JavaInstallError::JsonDownload(err) => err.code(),
+            JavaInstallError::Request(err) => err.code(),
+            JavaInstallError::NoUrlForJavaFiles => ErrorCode::JAVA_DOWNLOAD_UNAVAILABLE,
+            JavaInstallError::Serde(err) => err.code(),
+            JavaInstallError::JsonFile(err) => err.code(),
+            JavaInstallError::Io(err) => err.code(),
+            JavaInstallError::Cancelled(err) => err.code(),
+        }
+    }
+}
+
+impl HasErrorCode for DownloadError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            DownloadError::Json(err) => err.code(),
+            DownloadError::JsonFile(err) => err.code(),
+            DownloadError::Request(err) => err.code(),
+            DownloadError::Io(err) => err.code(),
+            DownloadError::InstanceAlreadyExists => ErrorCode::INSTANCE_ALREADY_EXISTS,
+            DownloadError::SendProgress(_) | DownloadError::AssetTaskFailed(_) => {
+                ErrorCode::INTERNAL
+            }
+            DownloadError::VersionNotFoundInManifest(_) => ErrorCode::VERSION_NOT_FOUND,
+            DownloadError::SerdeFieldNotFound(_) => ErrorCode::JSON_FIELD_MISSING,
+            DownloadError::NativesExtractError(_) => ErrorCode::NATIVES_EXTRACT_FAILED,
+            DownloadError::NativesOutsideDirRemove => ErrorCode::NATIVES_OUTSIDE_DIR,
+            DownloadError::NoServerJar(_) | DownloadError::NoServerSoftwareBuild { .. } => {
+                ErrorCode::SERVER_JAR_UNAVAILABLE
+            }
+            DownloadError::Cancelled(err) => err.code(),
+        }
+    }
+}
+
+impl HasErrorCode for BackupError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            BackupError::Launcher(err) => err.code(),
+            BackupError::Io(err) => err.code(),
+            BackupError::Zip(_) | BackupError::NoWorld(_) => ErrorCode::BACKUP_FAILED,
+            BackupError::InvalidBackup(_) => ErrorCode::BACKUP_INVALID,
+        }
+    }
+}
+
+impl HasErrorCode for SnapshotError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            SnapshotError::Launcher(err) => err.code(),
+            SnapshotError::Io(err) => err.code(),
+            SnapshotError::Json(err) => err.code(),
+            SnapshotError::Fabric(err) => err.code(),
+            SnapshotError::InvalidSnapshot(_) => ErrorCode::BACKUP_INVALID,
+        }
+    }
+}
+
+impl HasErrorCode for SyncError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            SyncError::Launcher(err) => err.code(),
+            SyncError::Io(err) => err.code(),
+            SyncError::Json(err) => err.code(),
+            SyncError::Locked { .. } => ErrorCode::INSTANCE_LOCKED,
+        }
+    }
+}
+
+impl HasErrorCode for NbtError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            NbtError::Io(err) => err.code(),
+            NbtError::Invalid(_) => ErrorCode::WORLD_INVALID,
+        }
+    }
+}
+
+impl HasErrorCode for WorldError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            WorldError::Launcher(err) => err.code(),
+            WorldError::Io(err) => err.code(),
+            WorldError::Nbt(err) => err.code(),
+            WorldError::NotFound(_) => ErrorCode::WORLD_NOT_FOUND,
+        }
+    }
+}
+
+impl HasErrorCode for LauncherBackupError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            LauncherBackupError::Io(err) => err.code(),
+            LauncherBackupError::Json(err) => err.code(),
+            LauncherBackupError::Zip(_) => ErrorCode::BACKUP_FAILED,
+            LauncherBackupError::NotABackup
+            | LauncherBackupError::UnsupportedFormat(_)
+            | LauncherBackupError::UnexpectedFile(_)
+            | LauncherBackupError::InvalidJson(_) => ErrorCode::BACKUP_INVALID,
+        }
+    }
+}
+
+impl HasErrorCode for LogUploadError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            LogUploadError::Request(err) => err.code(),
+            LogUploadError::Io(err) => err.code(),
+            LogUploadError::Json(_) | LogUploadError::Rejected(_) => ErrorCode::RESPONSE_INVALID,
+        }
+    }
+}
+
+impl HasErrorCode for TemplateError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            TemplateError::Io(err) => err.code(),
+            TemplateError::Json(err) => err.code(),
+            TemplateError::JsonFile(err) => err.code(),
+            TemplateError::Launcher(err) => err.code(),
+            TemplateError::Download(err) => err.code(),
+            TemplateError::Fabric(err) => err.code(),
+            TemplateError::Mods(err) => err.code(),
+            TemplateError::InvalidName(_) => ErrorCode::INVALID_PATH,
+            TemplateError::UnsupportedLoader(_) => ErrorCode::MOD_LOADER_UNSUPPORTED,
+            TemplateError::LoaderUnavailable(_) => ErrorCode::FABRIC_VERSION_NOT_FOUND,
+        }
+    }
+}
+
+impl HasErrorCode for SkinError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            SkinError::Io(err) => err.code(),
+            SkinError::Request(err) => err.code(),
+            SkinError::Response(_) => ErrorCode::RESPONSE_INVALID,
+            SkinError::Png(_) | SkinError::Size(..) => ErrorCode::SKIN_INVALID,
+        }
+    }
+}
+
+impl HasErrorCode for UpdateError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            UpdateError::Request(err) => err.code(),
+            UpdateError::Io(err) => err.code(),
+            UpdateError::Json(_) => ErrorCode::RESPONSE_INVALID,
+            UpdateError::NoDownload | UpdateError::PackageManaged => ErrorCode::UPDATE_UNAVAILABLE,
+            UpdateError::Zip(_)
+            | UpdateError::NoChecksum
+            | UpdateError::ChecksumMismatch
+            | UpdateError::BadSignature => ErrorCode::UPDATE_INVALID,
+        }
+    }
+}
+
+impl HasErrorCode for PingError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            PingError::Io(_) | PingError::Timeout => ErrorCode::SERVER_UNREACHABLE,
+            PingError::InvalidAddress(_) => ErrorCode::SERVER_ADDRESS_INVALID,
+            PingError::InvalidResponse(_) | PingError::Json(_) => ErrorCode::RESPONSE_INVALID,
+        }
+    }
+}
+
+impl HasErrorCode for FabricInstallError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            FabricInstallError::Io(err) => err.code(),
+            FabricInstallError::Json(err) => err.code(),
+            FabricInstallError::JsonFile(err) => err.code(),
+            FabricInstallError::RequestError(err) => err.code(),
+        }
+    }
+}
+
+impl HasErrorCode for ModInstallError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            ModInstallError::Io(err) => err.code(),
+            ModInstallError::Json(err) => err.code(),
+            ModInstallError::JsonFile(err) => err.code(),
+            ModInstallError::RequestError(err) => err.code(),
+            ModInstallError::NoModLoader | ModInstallError::NoPluginSupport => {
+                ErrorCode::MOD_LOADER_MISSING
+            }
+            ModInstallError::ModNotFound(_) | ModInstallError::HangarProjectNotFound(_) => {
+                ErrorCode::MOD_NOT_FOUND
+            }
+            ModInstallError::NoCompatibleVersion { .. } => ErrorCode::MOD_VERSION_NOT_FOUND,
+            ModInstallError::InvalidFileName(_) => ErrorCode::MOD_FILE_NAME_INVALID,
+        }
+    }
+}
+
+impl HasErrorCode for ServerPackError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            ServerPackError::Io(err) => err.code(),
+            ServerPackError::Json(err) => err.code(),
+            ServerPackError::JsonFile(err) => err.code(),
+            ServerPackError::Request(err) => err.code(),
+            ServerPackError::Launcher(err) => err.code(),
+            ServerPackError::Download(err) => err.code(),
+            ServerPackError::Fabric(err) => err.code(),
+            ServerPackError::UnknownVersion => ErrorCode::VERSION_NOT_FOUND,
+            ServerPackError::UnsupportedLoader(_) => ErrorCode::MOD_LOADER_UNSUPPORTED,
+            ServerPackError::Zip(_) | ServerPackError::InvalidPath(_) => {
+                ErrorCode::SERVER_PACK_INVALID
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Events about what the launcher is doing, like instances
+//! being created or games starting, broadcast to anyone
+//! interested (the GUI, Discord presence, playtime stats).
+//!
+//! Unlike [`crate::progress`], which is for one operation
+//! and its progress bar, every [`subscribe`]r gets every event
+//! from every operation.
+
+use std::{path::PathBuf, sync::OnceLock};
+
+use tokio::sync::broadcast;
+
+use crate::DownloadProgress;
+
+pub use tokio::sync::broadcast::{error::RecvError, Receiver};
+
+/// How many events a slow subscriber can fall behind
+/// before it starts missing them (getting [`RecvError::Lagged`]).
+const CAPACITY: usize = 256;
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    DownloadStarted {
+        instance: String,
+    },
+    DownloadProgress {
+        instance: String,
+        progress: DownloadProgress,
+    },
+    /// Sent whether the download worked or not.
+    DownloadFinished {
+        instance: String,
+        success: bool,
+    },
+    InstanceCreated {
+        instance: String,
+    },
+    LoaderInstalled {
+        instance: String,
+        /// Like `Fabric`.
+        loader: String,
+        version: String,
+    },
+    GameStarted {
+        instance: String,
+        pid: u32,
+    },
+    /// The backend doesn't wait for the game, so this is
+    /// sent by whoever launched it, once they see it exit.
+    GameExited {
+        instance: String,
+        exit_code: Option<i32>,
+        /// Unix time, in seconds.
+        started: u64,
+        seconds_played: u64,
+    },
+    /// Another machine changed a synced instance too, see
+    /// [`crate::sync_folder`]. The copy that lost is in `kept`.
+    SyncConflict {
+        instance: String,
+        kept: PathBuf,
+    },
+    /// Mods the last machine to sync the
+    /// instance has, and this one doesn't.
+    SyncMissingMods {
+        instance: String,
+        mods: Vec<String>,
+    },
+}
+
+fn sender() -> &'static broadcast::Sender<Event> {
+    static SENDER: OnceLock<broadcast::Sender<Event>> = OnceLock::new();
+    SENDER.get_or_init(|| broadcast::channel(CAPACITY).0)
+}
+
+/// Sends `event` to all current subscribers.
+/// Does nothing if there are none.
+pub fn emit(event: Event) {
+    _ = sender().send(event);
+}
+
+/// Receives all events emitted from now on.
+pub fn subscribe() -> Receiver<Event> {
+    sender().subscribe()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscribers_get_every_event() {
+        let mut first = subscribe();
+        let mut second = subscribe();
+        emit(Event::InstanceCreated {
+            instance: "test".to_owned(),
+        });
+        for receiver in [&mut first, &mut second] {
+            assert!(matches!(
+                receiver.try_recv(),
+                Ok(Event::InstanceCreated { instance }) if instance == "test"
+            ));
+        }
+    }
+}
@@ -1,40 +1,396 @@
 use std::{
+    borrow::Cow,
     fmt::Display,
+    fs::File,
+    io::Write,
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        RwLock,
+    },
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::{Client, StatusCode};
+use tracing::{debug, error, info};
+use zip::{result::ZipError, write::FileOptions, CompressionMethod, ZipWriter};
+
+use crate::{
+    download_queue,
+    error::{HasErrorCode, IoError, LauncherError, LauncherResult},
+    io_err,
 };
 
-use reqwest::Client;
+static DATA_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+static PORTABLE: AtomicBool = AtomicBool::new(false);
 
-use crate::{error::IoError, io_err};
+/// An environment variable to store the launcher's data
+/// in another directory. It takes priority over [`set_data_dir`].
+pub const DATA_DIR_ENV: &str = "QUANTUM_LAUNCHER_DATA_DIR";
 
+/// A file that turns on portable mode when it's
+/// next to the launcher's executable.
+pub const PORTABLE_MARKER: &str = "portable.txt";
+
+/// Returns the directory where the launcher keeps its
+/// data (instances, Java installs, assets, etc).
+///
+/// This is [`get_config_dir`] unless a custom data directory
+/// was set with [`DATA_DIR_ENV`] or [`set_data_dir`].
 pub fn get_launcher_dir() -> Result<PathBuf, IoError> {
-    let config_directory = dirs::config_dir().ok_or(IoError::ConfigDirNotFound)?;
-    let launcher_directory = config_directory.join("QuantumLauncher");
-    std::fs::create_dir_all(&launcher_directory).map_err(|err| IoError::Io {
-        error: err,
-        path: launcher_directory.clone(),
-    })?;
+    let data_dir = get_data_dir_from_env().or_else(|| DATA_DIR.read().unwrap().clone());
+    let launcher_directory = match data_dir {
+        Some(data_dir) => data_dir,
+        None => get_default_launcher_dir()?,
+    };
+    std::fs::create_dir_all(&launcher_directory).map_err(io_err!(launcher_directory))?;
 
     Ok(launcher_directory)
 }
 
-pub async fn download_file_to_string(client: &Client, url: &str) -> Result<String, RequestError> {
-    let response = client.get(url).send().await?;
-    if response.status().is_success() {
-        Ok(response.text().await?)
+/// Returns the directory where the launcher's `config.json` is.
+///
+/// `.config/QuantumLauncher/` OR `AppData/Roaming/QuantumLauncher/`,
+/// or `QuantumLauncher/` next to the executable in portable mode.
+pub fn get_config_dir() -> Result<PathBuf, IoError> {
+    let config_directory = get_default_launcher_dir()?;
+    std::fs::create_dir_all(&config_directory).map_err(io_err!(config_directory))?;
+
+    Ok(config_directory)
+}
+
+fn get_default_launcher_dir() -> Result<PathBuf, IoError> {
+    let config_directory = if is_portable() {
+        get_exe_dir().ok_or(IoError::ConfigDirNotFound)?
     } else {
-        Err(RequestError::DownloadError {
-            code: response.status(),
-            url: response.url().clone(),
-        })
+        dirs::config_dir().ok_or(IoError::ConfigDirNotFound)?
+    };
+    Ok(config_directory.join("QuantumLauncher"))
+}
+
+/// Makes the launcher keep everything, including its config,
+/// in a folder next to its executable instead of the user's
+/// config directory, so that it can be carried around on a
+/// USB stick. Used for the `--portable` flag.
+///
+/// Portable mode is also on if there's a [`PORTABLE_MARKER`]
+/// file next to the executable.
+pub fn set_portable(portable: bool) {
+    PORTABLE.store(portable, Ordering::Relaxed);
+}
+
+pub fn is_portable() -> bool {
+    PORTABLE.load(Ordering::Relaxed)
+        || get_exe_dir().is_some_and(|dir| dir.join(PORTABLE_MARKER).is_file())
+}
+
+fn get_exe_dir() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    // Symlinks (like one in `/usr/bin`) are followed to
+    // the real executable, where the marker file would be.
+    let exe = exe.canonicalize().unwrap_or(exe);
+    exe.parent().map(Path::to_owned)
+}
+
+/// Makes the launcher store its data in `dir` instead of the
+/// default directory. `None` goes back to the default.
+pub fn set_data_dir(dir: Option<PathBuf>) {
+    *DATA_DIR.write().unwrap() = dir;
+}
+
+/// The data directory set with [`DATA_DIR_ENV`], if any.
+pub fn get_data_dir_from_env() -> Option<PathBuf> {
+    std::env::var_os(DATA_DIR_ENV)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+pub async fn move_data_dir_wrapped(from: PathBuf, to: PathBuf) -> Result<PathBuf, String> {
+    run_blocking(move || move_data_dir(&from, &to).map(|()| to))
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Moves the launcher's data (everything but `config.json`)
+/// from `from` into `to`. It doesn't change where the launcher
+/// looks for it; use [`set_data_dir`] for that afterwards.
+///
+/// Nothing is moved if `to` already has any of the same
+/// files or folders, so that nothing is overwritten.
+pub fn move_data_dir(from: &Path, to: &Path) -> LauncherResult<()> {
+    std::fs::create_dir_all(to).map_err(io_err!(to))?;
+    let to = to.canonicalize().map_err(io_err!(to))?;
+    let from = from.canonicalize().map_err(io_err!(from))?;
+    if from == to {
+        return Ok(());
+    }
+
+    // In portable mode, the cache is in the data directory,
+    // but it stays where it is.
+    let cache_dir = get_cache_dir()?;
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(&from).map_err(io_err!(from))? {
+        let path = entry.map_err(io_err!(from))?.path();
+        // The new directory can be inside the old one,
+        // in which case it mustn't be moved into itself.
+        if path.file_name().is_some_and(|n| n == "config.json")
+            || to.starts_with(&path)
+            || path == cache_dir
+        {
+            continue;
+        }
+        let dest = to.join(path.file_name().unwrap_or_default());
+        if dest.exists() {
+            return Err(LauncherError::DataDirConflict(dest));
+        }
+        entries.push((path, dest));
+    }
+
+    for (path, dest) in entries {
+        move_path(&path, &dest)?;
+    }
+    Ok(())
+}
+
+/// Moves a file or folder, even to another drive.
+fn move_path(path: &Path, dest: &Path) -> Result<(), IoError> {
+    info!("Moving {path:?} to {dest:?}");
+    // Renaming only works on the same drive, and moving
+    // to another drive is often the whole point.
+    if std::fs::rename(path, dest).is_err() {
+        if path.is_dir() {
+            copy_dir_recursive(path, dest)?;
+            std::fs::remove_dir_all(path).map_err(io_err!(path))?;
+        } else {
+            std::fs::copy(path, dest).map_err(io_err!(path))?;
+            std::fs::remove_file(path).map_err(io_err!(path))?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the directory for files the launcher can download
+/// again (Java installs, assets, cached news), kept apart from
+/// the user's data so that backups and disk cleanup tools can
+/// tell them apart.
+///
+/// `.cache/QuantumLauncher/` OR `AppData/Local/QuantumLauncher/`,
+/// or `QuantumLauncher/cache/` next to the executable in portable mode.
+/// Moving the data directory doesn't move this.
+pub fn get_cache_dir() -> Result<PathBuf, IoError> {
+    let cache_dir = match dirs::cache_dir() {
+        Some(cache_dir) if !is_portable() => cache_dir.join("QuantumLauncher"),
+        _ => get_default_launcher_dir()?.join("cache"),
+    };
+    std::fs::create_dir_all(&cache_dir).map_err(io_err!(cache_dir))?;
+    Ok(cache_dir)
+}
+
+/// What older versions of the launcher kept in the data
+/// directory, that's now in [`get_cache_dir`].
+const MOVED_TO_CACHE: &[&str] = &["java_installs", "assets"];
+
+/// Moves Java installs and assets from where older versions
+/// of the launcher kept them into [`get_cache_dir`]. Called
+/// on startup, after the data directory is set.
+pub fn migrate_to_cache_dir() -> Result<(), IoError> {
+    migrate_dirs_to_cache(
+        &get_launcher_dir()?,
+        &get_cache_dir()?,
+        &get_default_launcher_dir()?.join("cache"),
+    )
+}
+
+/// Moves [`MOVED_TO_CACHE`] from `launcher_dir` into `cache_dir`,
+/// then removes `old_cache_dir`, where older versions cached the
+/// news. Only the launcher's own old location is removed, as the
+/// `cache` folder of a custom data directory could be anything.
+fn migrate_dirs_to_cache(
+    launcher_dir: &Path,
+    cache_dir: &Path,
+    old_cache_dir: &Path,
+) -> Result<(), IoError> {
+    let mut all_moved = true;
+    for name in MOVED_TO_CACHE {
+        let old_path = launcher_dir.join(name);
+        let new_path = cache_dir.join(name);
+        if !old_path.is_dir() {
+            continue;
+        }
+        if new_path.exists() {
+            all_moved = false;
+        } else {
+            move_path(&old_path, &new_path)?;
+        }
+    }
+
+    if all_moved && old_cache_dir.is_dir() && old_cache_dir != cache_dir {
+        std::fs::remove_dir_all(old_cache_dir).map_err(io_err!(old_cache_dir))?;
+    }
+    Ok(())
+}
+
+/// The SHA-1 hash of `bytes` in lowercase hex, like
+/// the hashes in version JSONs and on Modrinth.
+pub fn sha1_hex(bytes: &[u8]) -> String {
+    ring::digest::digest(&ring::digest::SHA1_FOR_LEGACY_USE_ONLY, bytes)
+        .as_ref()
+        .iter()
+        .map(|n| format!("{n:02x}"))
+        .collect()
+}
+
+/// Runs blocking work, like reading files or copying folders,
+/// on a thread meant for it, so that it doesn't hold up
+/// the async runtime (and the GUI waiting on it).
+pub async fn run_blocking<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    match tokio::task::spawn_blocking(f).await {
+        Ok(result) => result,
+        Err(err) => std::panic::resume_unwind(err.into_panic()),
+    }
+}
+
+pub fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), IoError> {
+    // Create the destination directory if it doesn't exist
+    if !dst.exists() {
+        std::fs::create_dir_all(dst).map_err(io_err!(dst))?;
+    }
+
+    // Iterate over the directory entries
+    for entry in std::fs::read_dir(src).map_err(io_err!(src))? {
+        let entry = entry.map_err(io_err!(src))?;
+        let path = entry.path();
+        let dest_path = dst.join(entry.file_name());
+
+        if path.is_dir() {
+            // Recursively copy the subdirectory
+            copy_dir_recursive(&path, &dest_path)?;
+        } else {
+            // Copy the file to the destination directory
+            std::fs::copy(&path, &dest_path).map_err(io_err!(path))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Network settings used by all downloads made by the launcher.
+///
+/// Set them with [`set_download_settings`].
+#[derive(Debug, Clone)]
+pub struct DownloadSettings {
+    /// How many files (such as assets) are downloaded at the same time.
+    pub parallel_downloads: usize,
+    /// Base URL of a mirror (eg: `https://bmclapi2.bangbang93.com`) to
+    /// download Mojang's files from, instead of Mojang's own servers.
+    ///
+    /// The mirror has to serve files at the same paths as Mojang.
+    pub mirror: Option<String>,
+    /// URL of a proxy that all requests are sent through.
+    pub proxy: Option<String>,
+}
+
+impl DownloadSettings {
+    pub const DEFAULT: Self = Self {
+        parallel_downloads: 8,
+        mirror: None,
+        proxy: None,
+    };
+}
+
+impl Default for DownloadSettings {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+static DOWNLOAD_SETTINGS: RwLock<DownloadSettings> = RwLock::new(DownloadSettings::DEFAULT);
+
+/// Mojang's download servers, which get replaced by the
+/// mirror in [`DownloadSettings`] if one is set.
+const MOJANG_HOSTS: &[&str] = &[
+    "https://launchermeta.mojang.com",
+    "https://launcher.mojang.com",
+    "https://piston-meta.mojang.com",
+    "https://piston-data.mojang.com",
+    "https://libraries.minecraft.net",
+    "https://resources.download.minecraft.net",
+];
+
+pub fn set_download_settings(settings: DownloadSettings) {
+    *DOWNLOAD_SETTINGS.write().unwrap() = settings;
+}
+
+pub fn get_download_settings() -> DownloadSettings {
+    DOWNLOAD_SETTINGS.read().unwrap().clone()
+}
+
+/// Creates a network client that respects the proxy
+/// set in the [`DownloadSettings`].
+pub fn create_client() -> Client {
+    // Some APIs (like Modrinth) ask clients to identify themselves.
+    let builder = || Client::builder().user_agent(USER_AGENT);
+    let default_client = || builder().build().unwrap_or_default();
+
+    let Some(proxy) = get_download_settings().proxy else {
+        return default_client();
+    };
+
+    match reqwest::Proxy::all(&proxy).and_then(|proxy| builder().proxy(proxy).build()) {
+        Ok(client) => client,
+        Err(err) => {
+            error!("Invalid proxy {proxy}, not using it: {err}");
+            default_client()
+        }
+    }
+}
+
+const USER_AGENT: &str = concat!(
+    "QuantumLauncher/",
+    env!("CARGO_PKG_VERSION"),
+    " (github.com/Mrmayman/quantum-launcher-rs)"
+);
+
+fn apply_mirror(url: &str) -> Cow<'_, str> {
+    let Some(mirror) = get_download_settings().mirror else {
+        return Cow::Borrowed(url);
+    };
+
+    match MOJANG_HOSTS.iter().find(|host| url.starts_with(*host)) {
+        Some(host) => Cow::Owned(format!(
+            "{}{}",
+            mirror.trim_end_matches('/'),
+            &url[host.len()..]
+        )),
+        None => Cow::Borrowed(url),
     }
 }
 
+pub async fn download_file_to_string(client: &Client, url: &str) -> Result<String, RequestError> {
+    let bytes = download_file_to_bytes(client, url).await?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Downloads `url`, taking turns with other jobs
+/// in the [`download_queue`] if it's part of one.
 pub async fn download_file_to_bytes(client: &Client, url: &str) -> Result<Vec<u8>, RequestError> {
-    let response = client.get(url).send().await?;
+    download_queue::wait_for_turn().await;
+    let start = Instant::now();
+    let mut response = client.get(apply_mirror(url).as_ref()).send().await?;
     if response.status().is_success() {
-        Ok(response.bytes().await?.to_vec())
+        let status = response.status();
+        let mut bytes = Vec::with_capacity(response.content_length().unwrap_or(0) as usize);
+        // Read in chunks, so that pausing stops a big file partway.
+        while let Some(chunk) = response.chunk().await? {
+            download_queue::add_bytes(chunk.len());
+            bytes.extend_from_slice(&chunk);
+            download_queue::wait_for_turn().await;
+        }
+        download_queue::file_finished();
+        log_request(url, status, bytes.len(), start);
+        Ok(bytes)
     } else {
+        log_request(url, response.status(), 0, start);
         Err(RequestError::DownloadError {
             code: response.status(),
             url: response.url().clone(),
@@ -42,6 +398,14 @@ pub async fn download_file_to_bytes(client: &Client, url: &str) -> Result<Vec<u8
     }
 }
 
+/// A summary of a finished request, for verbose logging.
+fn log_request(url: &str, status: StatusCode, bytes: usize, start: Instant) {
+    debug!(
+        "GET {url}: {status}, {bytes} bytes in {:.2?}",
+        start.elapsed()
+    );
+}
+
 #[derive(Debug)]
 pub enum RequestError {
     DownloadError {
@@ -108,3 +472,149 @@ pub fn set_executable(_path: &Path) -> Result<(), IoError> {
 //         }
 //     }
 // }
+
+/// Adds `dir` and everything in it to `zip`, named relative
+/// to `base`. Files named in `skip` are left out.
+pub(crate) fn add_dir_to_zip<E: From<IoError> + From<ZipError>>(
+    zip: &mut ZipWriter<File>,
+    base: &Path,
+    dir: &Path,
+    skip: &[&str],
+) -> Result<(), E> {
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    zip.add_directory(zip_entry_name(base, dir), options)?;
+
+    for entry in std::fs::read_dir(dir).map_err(io_err!(dir))? {
+        let path = entry.map_err(io_err!(dir))?.path();
+        if path.is_dir() {
+            add_dir_to_zip::<E>(zip, base, &path, skip)?;
+        } else if path
+            .file_name()
+            .is_some_and(|n| !skip.iter().any(|skip| n == *skip))
+        {
+            add_file_to_zip::<E>(zip, base, &path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Adds the file at `path` to `zip`, named relative to `base`.
+pub(crate) fn add_file_to_zip<E: From<IoError> + From<ZipError>>(
+    zip: &mut ZipWriter<File>,
+    base: &Path,
+    path: &Path,
+) -> Result<(), E> {
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    zip.start_file(zip_entry_name(base, path), options)?;
+    let bytes = std::fs::read(path).map_err(io_err!(path))?;
+    zip.write_all(&bytes).map_err(io_err!(path))?;
+    Ok(())
+}
+
+/// Zip files always use `/`, even on Windows.
+fn zip_entry_name(base: &Path, path: &Path) -> String {
+    path.strip_prefix(base)
+        .unwrap_or(path)
+        .components()
+        .map(|n| n.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Like `2024-05-01_13-45-00`, in UTC, for naming files
+/// (like backups) so that they sort by name in order of age.
+pub fn format_timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |n| n.as_secs() as i64);
+    let (days, secs_of_day) = (secs.div_euclid(86400), secs.rem_euclid(86400));
+
+    // Howard Hinnant's `civil_from_days`.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}_{:02}-{:02}-{:02}",
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_timestamp() {
+        let time = UNIX_EPOCH + std::time::Duration::from_secs(1_714_571_100);
+        assert_eq!(format_timestamp(time), "2024-05-01_13-45-00");
+        assert_eq!(format_timestamp(UNIX_EPOCH), "1970-01-01_00-00-00");
+    }
+
+    #[test]
+    fn test_sha1_hex() {
+        assert_eq!(sha1_hex(b"abc"), "a9993e364706816aba3e25717850c26c9cd0d89d");
+    }
+
+    #[test]
+    fn test_migrate_keeps_custom_cache() {
+        let root = std::env::temp_dir().join(format!("ql_cache_test_{}", std::process::id()));
+        let (launcher_dir, cache_dir, old_cache_dir) = (
+            root.join("data"),
+            root.join("cache"),
+            root.join("default/cache"),
+        );
+        std::fs::create_dir_all(launcher_dir.join("assets/indexes")).unwrap();
+        std::fs::create_dir_all(launcher_dir.join("cache")).unwrap();
+        std::fs::create_dir_all(&old_cache_dir).unwrap();
+
+        migrate_dirs_to_cache(&launcher_dir, &cache_dir, &old_cache_dir).unwrap();
+        assert!(cache_dir.join("assets/indexes").is_dir());
+        assert!(!launcher_dir.join("assets").exists());
+        assert!(!old_cache_dir.exists());
+        // Only the launcher's own old cache is removed.
+        assert!(launcher_dir.join("cache").is_dir());
+
+        // Nothing is removed if something couldn't be moved.
+        std::fs::create_dir_all(launcher_dir.join("java_installs")).unwrap();
+        std::fs::create_dir_all(cache_dir.join("java_installs")).unwrap();
+        std::fs::create_dir_all(&old_cache_dir).unwrap();
+        migrate_dirs_to_cache(&launcher_dir, &cache_dir, &old_cache_dir).unwrap();
+        assert!(old_cache_dir.is_dir());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_move_data_dir_into_subfolder() {
+        let from = std::env::temp_dir().join(format!("ql_move_test_{}", std::process::id()));
+        let to = from.join("data");
+        std::fs::create_dir_all(from.join("instances/test")).unwrap();
+        std::fs::write(from.join("config.json"), "{}").unwrap();
+        std::fs::write(from.join("instances/test/config.json"), "{}").unwrap();
+
+        move_data_dir(&from, &to).unwrap();
+        assert!(to.join("instances/test/config.json").exists());
+        assert!(!from.join("instances").exists());
+        // The config stays in the config directory.
+        assert!(from.join("config.json").exists());
+        assert!(!to.join("config.json").exists());
+
+        // Moving back refuses to overwrite anything.
+        std::fs::create_dir_all(from.join("instances")).unwrap();
+        assert!(matches!(
+            move_data_dir(&to, &from),
+            Err(LauncherError::DataDirConflict(_))
+        ));
+
+        std::fs::remove_dir_all(&from).unwrap();
+    }
+}
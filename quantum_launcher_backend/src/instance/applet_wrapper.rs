@@ -0,0 +1,62 @@
+//! Launching versions from before Alpha (pre-classic, Classic,
+//! Indev and Infdev), which ran as applets in a web page.
+//!
+//! They're started through launchwrapper instead, whose tweakers
+//! put the applet in a window and give it what the web page used
+//! to (like where to save worlds).
+
+use tracing::info;
+
+use crate::json_structs::json_version::VersionDetails;
+
+const MAIN_CLASS: &str = "net.minecraft.launchwrapper.Launch";
+const ALPHA_TWEAKER: &str = "net.minecraft.launchwrapper.AlphaVanillaTweaker";
+const INDEV_TWEAKER: &str = "net.minecraft.launchwrapper.IndevVanillaTweaker";
+
+/// The first parts of the ids of applet versions in the
+/// manifest, like `rd-132211`, `c0.30_01c` and `in-20100223`.
+const APPLET_VERSION_PREFIXES: &[&str] = &["rd-", "c0.", "in-", "inf-"];
+
+pub fn needs_applet_wrapper(version_json: &VersionDetails) -> bool {
+    version_json.r#type == "old_alpha"
+        && APPLET_VERSION_PREFIXES
+            .iter()
+            .any(|prefix| version_json.id.starts_with(prefix))
+}
+
+/// The main class to start instead of the version's own,
+/// which might be the applet itself.
+pub fn main_class() -> &'static str {
+    MAIN_CLASS
+}
+
+/// Adds what the applet needs to the arguments. The version's
+/// own tweaker is kept, if its JSON already has one.
+pub fn add_arguments(
+    version_json: &VersionDetails,
+    game_directory: &str,
+    java_arguments: &mut Vec<String>,
+    game_arguments: &mut Vec<String>,
+) {
+    info!("Launching {} through the applet wrapper", version_json.id);
+
+    // Old versions sort with comparators that newer Java rejects.
+    java_arguments.push("-Djava.util.Arrays.useLegacyMergeSort=true".to_owned());
+    // Where the applet saves its worlds and options.
+    java_arguments.push(format!(
+        "-Dminecraft.applet.TargetDirectory={game_directory}"
+    ));
+
+    if !game_arguments.iter().any(|n| n == "--tweakClass") {
+        game_arguments.push("--tweakClass".to_owned());
+        game_arguments.push(tweaker(&version_json.id).to_owned());
+    }
+}
+
+fn tweaker(version_id: &str) -> &'static str {
+    if version_id.starts_with("in-") || version_id.starts_with("inf-") {
+        INDEV_TWEAKER
+    } else {
+        ALPHA_TWEAKER
+    }
+}
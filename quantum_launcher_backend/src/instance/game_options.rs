@@ -0,0 +1,516 @@
+//! An instance's `options.txt`, where the game keeps
+//! its video, language and control settings.
+//!
+//! The game only writes the file on first launch, but fills in
+//! anything missing from it, so settings saved here before then
+//! are what a fresh instance starts with. Lines are kept as they
+//! are and only the changed keys are touched, like in
+//! [`super::server_files`].
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use tracing::info;
+
+use crate::{
+    error::{ErrorCode, HasErrorCode, LauncherError, LauncherResult},
+    file_utils, io_err,
+    json_structs::json_version::VersionDetails,
+};
+
+use super::instance_launch::{get_config, get_game_dir, get_instance_dir};
+
+/// The `options.txt` of an instance.
+pub struct GameOptions {
+    lines: Vec<String>,
+    /// Whether the file is in the format of 1.13 and above,
+    /// with keybinds stored by name (`key.keyboard.w`) rather
+    /// than key code (`17`), and resource packs with a `file/` prefix.
+    modern_format: bool,
+}
+
+impl GameOptions {
+    /// Loads the file, or an empty one if the game hasn't made it yet.
+    pub fn load(instance_name: &str) -> LauncherResult<Self> {
+        let path = get_options_path(instance_name)?;
+        let lines = if path.exists() {
+            let contents = std::fs::read_to_string(&path).map_err(io_err!(path))?;
+            contents.lines().map(ToOwned::to_owned).collect()
+        } else {
+            Vec::new()
+        };
+
+        // 1.13 switched both this file and the version
+        // JSON's arguments to a new format.
+        let instance_dir = get_instance_dir(instance_name)?;
+        let modern_format = VersionDetails::read(&instance_dir.join("details.json"))?
+            .arguments
+            .is_some();
+
+        Ok(Self {
+            lines,
+            modern_format,
+        })
+    }
+
+    pub fn save(&self, instance_name: &str) -> LauncherResult<()> {
+        let path = get_options_path(instance_name)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(io_err!(parent))?;
+        }
+        let mut contents = self.lines.join("\n");
+        contents.push('\n');
+        std::fs::write(&path, contents).map_err(io_err!(path))?;
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.lines.iter().find_map(|line| parse_line(line, key))
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        let new_line = format!("{key}:{value}");
+        match self
+            .lines
+            .iter_mut()
+            .find(|line| parse_line(line, key).is_some())
+        {
+            Some(line) => *line = new_line,
+            None => self.lines.push(new_line),
+        }
+    }
+
+    /// The settings that have an editor in the launcher.
+    /// Missing or invalid ones are the game's defaults.
+    pub fn settings(&self) -> GameSettings {
+        let defaults = GameSettings::default();
+        GameSettings {
+            render_distance: self
+                .get("renderDistance")
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(defaults.render_distance),
+            gui_scale: self
+                .get("guiScale")
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(defaults.gui_scale),
+            max_fps: self
+                .get("maxFps")
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(defaults.max_fps),
+            vsync: self
+                .get("enableVsync")
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(defaults.vsync),
+            language: self
+                .get("lang")
+                .map(ToOwned::to_owned)
+                .unwrap_or(defaults.language),
+        }
+    }
+
+    pub fn set_settings(&mut self, settings: &GameSettings) -> Result<(), GameSettingError> {
+        settings.validate()?;
+        self.set("renderDistance", &settings.render_distance.to_string());
+        self.set("guiScale", &settings.gui_scale.to_string());
+        self.set("maxFps", &settings.max_fps.to_string());
+        self.set("enableVsync", &settings.vsync.to_string());
+        self.set("lang", &settings.language);
+        Ok(())
+    }
+
+    /// Copies the keybinds (`key_*` lines) of another instance,
+    /// returning how many were copied.
+    ///
+    /// Keybinds stored in the other format (see [`GameOptions::load`])
+    /// would reset the game's controls, so they're skipped.
+    pub fn import_keybinds(&mut self, from: &GameOptions) -> usize {
+        self.copy_from(from, |key| key.starts_with("key_"))
+    }
+
+    /// Copies every option of another instance, like
+    /// [`GameOptions::import_keybinds`] does for keybinds.
+    ///
+    /// The `version` line is left out, as it tells the game
+    /// which version wrote the file, and so how to upgrade it.
+    pub fn import_all(&mut self, from: &GameOptions) -> usize {
+        self.copy_from(from, |key| key != "version")
+    }
+
+    /// Like [`Self::import_all`], from the lines
+    /// of another `options.txt`.
+    pub fn import_lines(&mut self, lines: Vec<String>) -> usize {
+        let from = GameOptions {
+            lines,
+            modern_format: self.modern_format,
+        };
+        self.import_all(&from)
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    fn copy_from(&mut self, from: &GameOptions, filter: impl Fn(&str) -> bool) -> usize {
+        let options: Vec<(String, String)> = from
+            .lines
+            .iter()
+            .filter_map(|line| line.split_once(':'))
+            .filter(|(key, value)| {
+                filter(key)
+                    && (!key.starts_with("key_") || is_named_key(value) == self.modern_format)
+            })
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect();
+
+        for (key, value) in &options {
+            self.set(key, value);
+        }
+        options.len()
+    }
+
+    /// The file names of the enabled resource packs,
+    /// from lowest to highest priority.
+    pub fn resource_packs(&self) -> Vec<String> {
+        let packs: Vec<String> = self
+            .get(RESOURCE_PACKS_KEY)
+            .and_then(|list| serde_json::from_str(list).ok())
+            .unwrap_or_default();
+        if !self.modern_format {
+            return packs;
+        }
+        // Built-in packs like `vanilla` don't have the prefix.
+        packs
+            .iter()
+            .filter_map(|pack| pack.strip_prefix("file/"))
+            .map(ToOwned::to_owned)
+            .collect()
+    }
+
+    /// Turns a resource pack in the `resourcepacks` folder on or off.
+    /// Packs that are turned on go above the others.
+    pub fn set_resource_pack_enabled(&mut self, file_name: &str, enabled: bool) {
+        let entry = if self.modern_format {
+            format!("file/{file_name}")
+        } else {
+            file_name.to_owned()
+        };
+        let mut packs: Vec<String> = self
+            .get(RESOURCE_PACKS_KEY)
+            .and_then(|list| serde_json::from_str(list).ok())
+            .unwrap_or_default();
+        if self.modern_format && packs.is_empty() {
+            packs.push("vanilla".to_owned());
+        }
+
+        packs.retain(|pack| *pack != entry);
+        if enabled {
+            packs.push(entry);
+        }
+        let list = serde_json::to_string(&packs).expect("a list of strings is valid JSON");
+        self.set(RESOURCE_PACKS_KEY, &list);
+    }
+}
+
+/// The resource packs in an instance's `resourcepacks` folder.
+pub fn list_resource_packs(instance_name: &str) -> LauncherResult<Vec<String>> {
+    let dir = get_game_dir(instance_name)?.join(RESOURCE_PACKS_DIR);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut packs = Vec::new();
+    for entry in std::fs::read_dir(&dir).map_err(io_err!(dir))? {
+        let path = entry.map_err(io_err!(dir))?.path();
+        if is_resource_pack(&path) {
+            packs.push(path.file_name().unwrap().to_string_lossy().to_string());
+        }
+    }
+    packs.sort_unstable();
+    Ok(packs)
+}
+
+pub async fn install_resource_pack_wrapped(
+    instance_name: String,
+    path: PathBuf,
+    enable: bool,
+) -> Result<String, String> {
+    file_utils::run_blocking(move || install_resource_pack(&instance_name, &path, enable))
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Copies a resource pack into the instance's `resourcepacks`
+/// folder, replacing one with the same name. With `enable`,
+/// it's also turned on in `options.txt` (which is made with
+/// the defaults if the game hasn't been started yet), so
+/// it's active from the first launch.
+///
+/// Returns the file name of the pack.
+pub fn install_resource_pack(
+    instance_name: &str,
+    path: &Path,
+    enable: bool,
+) -> LauncherResult<String> {
+    let file_name = match path.file_name() {
+        Some(name) if is_resource_pack(path) => name.to_string_lossy().to_string(),
+        _ => return Err(LauncherError::ResourcePackInvalid(path.to_owned())),
+    };
+
+    let dir = get_game_dir(instance_name)?.join(RESOURCE_PACKS_DIR);
+    std::fs::create_dir_all(&dir).map_err(io_err!(dir))?;
+    let to = dir.join(&file_name);
+    if path.is_dir() {
+        file_utils::copy_dir_recursive(path, &to)?;
+    } else {
+        std::fs::copy(path, &to).map_err(io_err!(to))?;
+    }
+
+    if enable {
+        let mut options = GameOptions::load(instance_name)?;
+        if options.lines.is_empty() {
+            options
+                .set_settings(&GameSettings::default())
+                .expect("the defaults are valid");
+        }
+        options.set_resource_pack_enabled(&file_name, true);
+        options.save(instance_name)?;
+    }
+    info!("Installed resource pack {file_name} to {instance_name}");
+    Ok(file_name)
+}
+
+fn is_resource_pack(path: &Path) -> bool {
+    if path.is_dir() {
+        path.join("pack.mcmeta").is_file()
+    } else {
+        path.is_file()
+            && path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+    }
+}
+
+/// Copies the options, keybinds and multiplayer server list
+/// of one instance to another, so it doesn't start with
+/// the game's defaults.
+pub fn copy_options(from_instance: &str, to_instance: &str) -> LauncherResult<()> {
+    let from = GameOptions::load(from_instance)?;
+    let mut to = GameOptions::load(to_instance)?;
+    to.import_all(&from);
+    to.save(to_instance)?;
+
+    let from_servers = get_game_dir(from_instance)?.join(SERVERS_FILE);
+    if from_servers.is_file() {
+        let to_servers = get_game_dir(to_instance)?.join(SERVERS_FILE);
+        std::fs::copy(&from_servers, &to_servers).map_err(io_err!(to_servers))?;
+    }
+    Ok(())
+}
+
+/// The multiplayer server list.
+const SERVERS_FILE: &str = "servers.dat";
+const RESOURCE_PACKS_DIR: &str = "resourcepacks";
+/// A JSON list of the enabled resource packs.
+const RESOURCE_PACKS_KEY: &str = "resourcePacks";
+
+/// The most commonly changed settings in `options.txt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameSettings {
+    /// In chunks.
+    pub render_distance: u8,
+    /// `0` picks the largest scale that fits the window.
+    pub gui_scale: u8,
+    /// [`GameSettings::UNLIMITED_FPS`] turns the limit off.
+    pub max_fps: u16,
+    pub vsync: bool,
+    /// A language code like `en_us`.
+    pub language: String,
+}
+
+impl Default for GameSettings {
+    /// The defaults of a fresh game.
+    fn default() -> Self {
+        Self {
+            render_distance: 12,
+            gui_scale: 0,
+            max_fps: 120,
+            vsync: true,
+            language: "en_us".to_owned(),
+        }
+    }
+}
+
+impl GameSettings {
+    pub const RENDER_DISTANCE_RANGE: std::ops::RangeInclusive<u8> = 2..=32;
+    pub const MAX_FPS_RANGE: std::ops::RangeInclusive<u16> = 10..=Self::UNLIMITED_FPS;
+    /// The end of the game's slider, shown as "Unlimited".
+    pub const UNLIMITED_FPS: u16 = 260;
+    pub const GUI_SCALES: [u8; 7] = [0, 1, 2, 3, 4, 5, 6];
+
+    pub fn validate(&self) -> Result<(), GameSettingError> {
+        parse_render_distance(&self.render_distance.to_string())?;
+        parse_max_fps(&self.max_fps.to_string())?;
+        validate_language(&self.language)
+    }
+}
+
+/// Parses a render distance typed in by the user.
+pub fn parse_render_distance(input: &str) -> Result<u8, GameSettingError> {
+    match input.trim().parse::<u8>() {
+        Ok(distance) if GameSettings::RENDER_DISTANCE_RANGE.contains(&distance) => Ok(distance),
+        _ => Err(GameSettingError::RenderDistance(input.to_owned())),
+    }
+}
+
+/// Parses a frame rate limit typed in by the user.
+pub fn parse_max_fps(input: &str) -> Result<u16, GameSettingError> {
+    match input.trim().parse::<u16>() {
+        Ok(fps) if GameSettings::MAX_FPS_RANGE.contains(&fps) => Ok(fps),
+        _ => Err(GameSettingError::MaxFps(input.to_owned())),
+    }
+}
+
+/// Language codes are like `en_us`, or `en_US` before 1.11.
+pub fn validate_language(language: &str) -> Result<(), GameSettingError> {
+    let is_valid = language.split_once('_').is_some_and(|(lang, region)| {
+        !lang.is_empty()
+            && !region.is_empty()
+            && language
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    });
+    if is_valid {
+        Ok(())
+    } else {
+        Err(GameSettingError::Language(language.to_owned()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameSettingError {
+    RenderDistance(String),
+    MaxFps(String),
+    Language(String),
+}
+
+impl Display for GameSettingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameSettingError::RenderDistance(input) => write!(
+                f,
+                "invalid render distance {input:?}, it must be from {} to {}",
+                GameSettings::RENDER_DISTANCE_RANGE.start(),
+                GameSettings::RENDER_DISTANCE_RANGE.end()
+            ),
+            GameSettingError::MaxFps(input) => write!(
+                f,
+                "invalid max framerate {input:?}, it must be from {} to {}",
+                GameSettings::MAX_FPS_RANGE.start(),
+                GameSettings::MAX_FPS_RANGE.end()
+            ),
+            GameSettingError::Language(input) => {
+                write!(f, "invalid language {input:?}, it must be like en_us")
+            }
+        }
+    }
+}
+
+impl HasErrorCode for GameSettingError {
+    fn code(&self) -> ErrorCode {
+        ErrorCode::GAME_SETTING_INVALID
+    }
+}
+
+fn get_options_path(instance_name: &str) -> LauncherResult<PathBuf> {
+    let instance_dir = get_instance_dir(instance_name)?;
+    let config = get_config(&instance_dir).map_err(LauncherError::from)?;
+    Ok(config.get_game_dir(&instance_dir).join("options.txt"))
+}
+
+fn is_named_key(value: &str) -> bool {
+    value.starts_with("key.")
+}
+
+/// Gets the value of `line` if it sets `key`.
+fn parse_line<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let (line_key, value) = line.split_once(':')?;
+    (line_key == key).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(contents: &str, modern_format: bool) -> GameOptions {
+        GameOptions {
+            lines: contents.lines().map(ToOwned::to_owned).collect(),
+            modern_format,
+        }
+    }
+
+    #[test]
+    fn test_import_keybinds_skips_other_format() {
+        let from = options(
+            "key_key.forward:key.keyboard.w\nkey_key.jump:57\nrenderDistance:8",
+            true,
+        );
+        let mut to = options("version:3465\nkey_key.forward:key.keyboard.up", true);
+
+        assert_eq!(to.import_keybinds(&from), 1);
+        assert_eq!(to.get("key_key.forward"), Some("key.keyboard.w"));
+        assert_eq!(to.get("key_key.jump"), None);
+        assert_eq!(to.get("renderDistance"), None);
+
+        let mut to = options("version:1343\nlang:en_us", false);
+        assert_eq!(to.import_all(&from), 2);
+        assert_eq!(to.get("version"), Some("1343"));
+        assert_eq!(to.get("renderDistance"), Some("8"));
+        assert_eq!(to.get("key_key.jump"), Some("57"));
+        assert_eq!(to.get("key_key.forward"), None);
+    }
+
+    #[test]
+    fn test_import_lines_keeps_version() {
+        let mut to = options("version:3465\nrenderDistance:12", true);
+        let lines = vec!["renderDistance:6".to_owned(), "version:1343".to_owned()];
+
+        assert_eq!(to.import_lines(lines), 1);
+        assert_eq!(to.get("renderDistance"), Some("6"));
+        assert_eq!(to.get("version"), Some("3465"));
+    }
+
+    #[test]
+    fn test_resource_packs() {
+        let mut modern = options("", true);
+        modern.set_resource_pack_enabled("Faithful.zip", true);
+        assert_eq!(
+            modern.get("resourcePacks"),
+            Some(r#"["vanilla","file/Faithful.zip"]"#)
+        );
+        assert_eq!(modern.resource_packs(), ["Faithful.zip"]);
+
+        let mut legacy = options(r#"resourcePacks:["A.zip","B.zip"]"#, false);
+        legacy.set_resource_pack_enabled("A.zip", true);
+        legacy.set_resource_pack_enabled("B.zip", false);
+        assert_eq!(legacy.resource_packs(), ["A.zip"]);
+    }
+
+    #[test]
+    fn test_settings_round_trip() {
+        let mut file = options("maxFps:abc", true);
+        assert_eq!(file.settings(), GameSettings::default());
+
+        let settings = GameSettings {
+            render_distance: 16,
+            gui_scale: 2,
+            max_fps: GameSettings::UNLIMITED_FPS,
+            vsync: false,
+            language: "es_es".to_owned(),
+        };
+        file.set_settings(&settings).unwrap();
+        assert_eq!(file.settings(), settings);
+        assert_eq!(file.get("maxFps"), Some("260"));
+        assert!(validate_language("english").is_err());
+    }
+}
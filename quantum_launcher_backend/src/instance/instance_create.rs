@@ -1,37 +1,197 @@
-use std::sync::mpsc::Sender;
+use tracing::{error, info};
 
-use crate::download::{progress::DownloadProgress, DownloadError, GameDownloader};
+use crate::{
+    cancel::CancelToken,
+    download::{progress::DownloadProgress, CustomVersionJson, DownloadError, GameDownloader},
+    download_queue::{self, JobKind},
+    error::HasErrorCode,
+    events::{self, Event},
+    json_structs::json_manifest::VersionSource,
+    logging,
+    progress::ProgressSender,
+};
 
-pub async fn create_instance(
+/// What an instance's version JSON comes from.
+enum VersionToInstall {
+    Listed {
+        version: String,
+        source: VersionSource,
+    },
+    Custom(CustomVersionJson),
+}
+
+pub async fn create_instance_wrapped(
     instance_name: String,
     version: String,
-    progress_sender: Option<Sender<DownloadProgress>>,
+    version_source: VersionSource,
+    progress_sender: Option<ProgressSender<DownloadProgress>>,
     download_assets: bool,
+    cancel: Option<CancelToken>,
 ) -> Result<(), String> {
-    create(&instance_name, version, progress_sender, download_assets)
-        .await
-        .map_err(|n| n.to_string())
+    create_instance(
+        &instance_name,
+        version,
+        version_source,
+        progress_sender,
+        download_assets,
+        cancel,
+    )
+    .await
+    .map_err(|n| n.to_string_with_code())
 }
 
-async fn create(
+/// Creates an instance, downloading the game.
+///
+/// If `cancel` is cancelled, the partly created
+/// instance is removed and this errors with
+/// [`DownloadError::Cancelled`].
+pub async fn create_instance(
     instance_name: &str,
     version: String,
-    progress_sender: Option<Sender<DownloadProgress>>,
+    version_source: VersionSource,
+    progress_sender: Option<ProgressSender<DownloadProgress>>,
     download_assets: bool,
+    cancel: Option<CancelToken>,
 ) -> Result<(), DownloadError> {
-    println!("[info] Started creating instance.");
+    create(
+        instance_name,
+        VersionToInstall::Listed {
+            version,
+            source: version_source,
+        },
+        progress_sender,
+        download_assets,
+        cancel,
+    )
+    .await
+}
+
+/// `custom_version_json` is a file path or URL, see [`CustomVersionJson::parse`].
+pub async fn create_instance_from_json_wrapped(
+    instance_name: String,
+    custom_version_json: String,
+    progress_sender: Option<ProgressSender<DownloadProgress>>,
+    download_assets: bool,
+    cancel: Option<CancelToken>,
+) -> Result<(), String> {
+    create_instance_from_json(
+        &instance_name,
+        CustomVersionJson::parse(&custom_version_json),
+        progress_sender,
+        download_assets,
+        cancel,
+    )
+    .await
+    .map_err(|n| n.to_string_with_code())
+}
+
+/// Creates an instance from a version JSON the user supplied,
+/// downloading the client and libraries it lists. Like
+/// [`create_instance`] otherwise.
+pub async fn create_instance_from_json(
+    instance_name: &str,
+    custom_version_json: CustomVersionJson,
+    progress_sender: Option<ProgressSender<DownloadProgress>>,
+    download_assets: bool,
+    cancel: Option<CancelToken>,
+) -> Result<(), DownloadError> {
+    create(
+        instance_name,
+        VersionToInstall::Custom(custom_version_json),
+        progress_sender,
+        download_assets,
+        cancel,
+    )
+    .await
+}
+
+async fn create(
+    instance_name: &str,
+    version: VersionToInstall,
+    progress_sender: Option<ProgressSender<DownloadProgress>>,
+    download_assets: bool,
+    cancel: Option<CancelToken>,
+) -> Result<(), DownloadError> {
+    info!("Started creating instance.");
 
     if let Some(ref sender) = progress_sender {
         sender.send(DownloadProgress::Started)?;
     }
+    events::emit(Event::DownloadStarted {
+        instance: instance_name.to_owned(),
+    });
+    let result = download_queue::run(
+        JobKind::Instance,
+        instance_name.to_owned(),
+        create_instance_inner(
+            instance_name,
+            version,
+            progress_sender,
+            download_assets,
+            cancel,
+        ),
+    )
+    .await;
+    events::emit(Event::DownloadFinished {
+        instance: instance_name.to_owned(),
+        success: result.is_ok(),
+    });
+    if result.is_ok() {
+        events::emit(Event::InstanceCreated {
+            instance: instance_name.to_owned(),
+        });
+    }
+    result
+}
+
+async fn create_instance_inner(
+    instance_name: &str,
+    version: VersionToInstall,
+    progress_sender: Option<ProgressSender<DownloadProgress>>,
+    download_assets: bool,
+    cancel: Option<CancelToken>,
+) -> Result<(), DownloadError> {
+    let _timer = logging::time_step("Creating the instance");
+    let game_downloader = {
+        let _timer = logging::time_step("Downloading the version details");
+        match version {
+            VersionToInstall::Listed { version, source } => {
+                GameDownloader::new(instance_name, &version, source, progress_sender, cancel)
+                    .await?
+            }
+            VersionToInstall::Custom(json) => {
+                GameDownloader::new_custom(instance_name, json, progress_sender, cancel).await?
+            }
+        }
+    };
 
-    let game_downloader = GameDownloader::new(instance_name, &version, progress_sender).await?;
+    let result = download_instance(&game_downloader, download_assets).await;
+    if let Err(DownloadError::Cancelled(_)) = result {
+        info!("Instance creation cancelled, removing {instance_name}");
+        let instance_dir = &game_downloader.instance_dir;
+        if let Err(err) = tokio::fs::remove_dir_all(instance_dir).await {
+            error!("Could not remove cancelled instance {instance_dir:?}: {err}");
+        }
+    }
+    result
+}
 
+async fn download_instance(
+    game_downloader: &GameDownloader,
+    download_assets: bool,
+) -> Result<(), DownloadError> {
     game_downloader.download_logging_config().await?;
-    game_downloader.download_jar().await?;
-    game_downloader.download_libraries().await?;
+    {
+        let _timer = logging::time_step("Downloading the game jar");
+        game_downloader.download_jar().await?;
+    }
+    {
+        let _timer = logging::time_step("Downloading libraries");
+        game_downloader.download_libraries().await?;
+    }
 
     if download_assets {
+        let _timer = logging::time_step("Downloading assets");
         game_downloader.download_assets().await?;
     }
 
@@ -1,80 +1,297 @@
+use tracing::{debug, error, info};
+
 use crate::{
-    error::{IoError, LauncherError, LauncherResult},
-    file_utils, io_err,
-    java_install::{self, JavaInstallMessage},
+    download::{legacy_assets, verify, CustomVersionJson},
+    error::{
+        HasErrorCode, LaunchContext, LaunchFailure, LaunchPhase, LauncherError, LauncherResult,
+    },
+    events::{self, Event},
+    file_utils, io_err, java_install,
     json_structs::{
         json_fabric::FabricJSON,
         json_instance_config::InstanceConfigJson,
-        json_java_list::JavaVersion,
+        json_manifest::{Manifest, VersionSource},
         json_version::{LibraryDownloads, VersionDetails},
         JsonFileError,
     },
+    logging,
+};
+
+use super::{
+    applet_wrapper,
+    launch_arguments::{ArgumentVars, InstanceContext},
+    launch_options::{check_username, Account, LaunchCommand, LaunchOptions},
+    launch_presets,
+    snapshots::{self, SnapshotReason},
+    sync_folder, world_backup,
 };
 use std::{
     path::{Path, PathBuf},
-    process::{Child, Command},
-    sync::{mpsc::Sender, Arc, Mutex},
+    process::{Child, Command, Stdio},
+    sync::{Arc, Mutex},
 };
 
 const CLASSPATH_SEPARATOR: char = if cfg!(unix) { ':' } else { ';' };
 
-pub type GameLaunchResult = Result<Arc<Mutex<Child>>, String>;
+pub type GameLaunchResult = Result<Arc<Mutex<Child>>, LaunchFailure>;
 
-/// Wraps the [`launch`] function to give a `Result<Arc<Mutex<Child>>, String`
+/// Wraps the [`launch`] function to give a `Result<Arc<Mutex<Child>>, LaunchFailure>`
 /// instead of a `Result<Child, LauncherError>` to make it easier to
 /// use with the iced GUI toolkit.
-///
-/// Launches the specified instance with the specified username.
-/// Will error if instance isn't created.
-///
-/// This auto downloads the required version of Java
-/// if it's not already installed.
-///
-/// If you want, you can hook this up to a progress bar
-/// (since installing Java takes a while), by using a
-/// `std::sync::mpsc::channel::<JavaInstallMessage>()`, giving the
-/// sender to this function and polling the receiver frequently.
-/// If not needed, simply pass `None` to the function.
-pub async fn launch_wrapped(
-    instance_name: String,
-    username: String,
-    java_install_progress_sender: Option<Sender<JavaInstallMessage>>,
-) -> GameLaunchResult {
-    match launch(&instance_name, &username, java_install_progress_sender).await {
+pub async fn launch_wrapped(options: LaunchOptions) -> GameLaunchResult {
+    match launch(options).await {
         Ok(child) => GameLaunchResult::Ok(Arc::new(Mutex::new(child))),
-        Err(err) => GameLaunchResult::Err(err.to_string()),
+        Err(err) => GameLaunchResult::Err(err.into()),
     }
 }
 
-/// Launches the specified instance with the specified username.
+/// Launches an instance, as described by the [`LaunchOptions`].
 /// Will error if instance isn't created.
 ///
 /// This auto downloads the required version of Java
-/// if it's not already installed.
+/// if it's not already installed. If you want, you can hook
+/// this up to a progress bar (since installing Java takes a while)
+/// with [`LaunchOptions::java_install_progress`].
 ///
-/// If you want, you can hook this up to a progress bar
-/// (since installing Java takes a while), by using a
-/// `std::sync::mpsc::channel::<JavaInstallMessage>()`, giving the
-/// sender to this function and polling the receiver frequently.
-/// If not needed, simply pass `None` to the function.
-pub async fn launch(
-    instance_name: &str,
-    username: &str,
-    java_install_progress_sender: Option<Sender<JavaInstallMessage>>,
-) -> LauncherResult<Child> {
-    if username.contains(' ') || username.is_empty() {
-        return Err(LauncherError::UsernameIsInvalid(username.to_owned()));
+/// The game's stdout and stderr are piped, so the caller
+/// must read them, or the game will hang once the pipe fills up.
+pub async fn launch(options: LaunchOptions) -> LauncherResult<Child> {
+    let instance_name = options.instance_name.clone();
+    let username = options.account.username.clone();
+    let profile = options.profile.clone();
+    // Not in `build_command`, so dry runs don't lock the sync folder.
+    file_utils::run_blocking({
+        let instance_name = instance_name.clone();
+        move || sync_folder::pull(&instance_name)
+    })
+    .await
+    .map_err(|err| LauncherError::Sync(Box::new(err)))?;
+    let BuiltCommand {
+        launch_command,
+        mut config_json,
+        config_path,
+        managed_java,
+    } = build_command(options).await?;
+
+    info!("Launching {instance_name}");
+    if logging::is_verbose() {
+        debug!("Launch command: {launch_command}");
+    }
+
+    // The game's output is piped so the launcher can show its log.
+    let mut command = Command::new(&launch_command.program);
+    command
+        .args(&launch_command.args)
+        .envs(launch_command.env.iter().map(|(k, v)| (k, v)))
+        .current_dir(&launch_command.current_dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let result = command
+        .spawn()
+        .map_err(LauncherError::CommandError)
+        .in_phase(LaunchPhase::Spawn, Some(&launch_command.program))?;
+
+    config_json.last_username = Some(username);
+    config_json.last_profile = profile;
+    // The instance's version or Java setting changed, so the
+    // Java it used before may not be needed anymore.
+    let java_changed = config_json.last_java.is_some() && config_json.last_java != managed_java;
+    config_json.last_java = managed_java;
+    if let Err(err) = save_last_played(&mut config_json, &config_path) {
+        error!("Could not save last played time: {err}");
+    }
+    if java_changed {
+        tokio::spawn(async {
+            if let Err(err) = java_install::delete_unused_java_installs().await {
+                error!("Could not remove unused Java installs: {err}");
+            }
+        });
+    }
+    events::emit(Event::GameStarted {
+        instance: instance_name,
+        pid: result.id(),
+    });
+
+    Ok(result)
+}
+
+/// Does everything [`launch`] does (including installing
+/// Java, if needed) except starting the game, and returns
+/// the command that would start it.
+pub async fn launch_dry_run(options: LaunchOptions) -> LauncherResult<LaunchCommand> {
+    Ok(build_command(options).await?.launch_command)
+}
+
+/// What [`build_command`] works out.
+struct BuiltCommand {
+    launch_command: LaunchCommand,
+    config_json: InstanceConfigJson,
+    config_path: PathBuf,
+    /// The launcher-managed Java install the game
+    /// runs on (eg: `java_21`), if it isn't overridden.
+    managed_java: Option<String>,
+}
+
+async fn build_command(options: LaunchOptions) -> LauncherResult<BuiltCommand> {
+    let username = &options.account.username;
+    // Microsoft accounts' names are checked by Microsoft.
+    let is_offline = options.account.user_type == "legacy";
+    if let Err(problem) = check_username(username) {
+        if problem.is_fatal() || (is_offline && !options.allow_invalid_username) {
+            return Err(LauncherError::UsernameIsInvalid(username.clone(), problem));
+        }
     }
+    let _timer = logging::time_step("Preparing the launch");
+
+    // Reading the instance, backing up worlds and migrating
+    // assets can take a while, so it's kept off the async runtime.
+    let PreparedLaunch {
+        config_json,
+        config_path,
+        version_json,
+        instance_dir,
+        minecraft_dir,
+        mut java_arguments,
+        game_arguments,
+        fabric_json,
+    } = file_utils::run_blocking({
+        let options = options.clone();
+        move || prepare_launch(&options)
+    })
+    .await?;
+
+    // Before the classpath is built, as it leaves out missing libraries.
+    verify::repair_instance(&instance_dir, &version_json)
+        .await
+        .in_phase(LaunchPhase::Classpath, None)?;
+    let java_arguments = file_utils::run_blocking({
+        let version_json = version_json.clone();
+        move || {
+            setup_classpath_and_mainclass(
+                &mut java_arguments,
+                &version_json,
+                instance_dir,
+                fabric_json,
+            )
+            .map(|()| java_arguments)
+        }
+    })
+    .await
+    .in_phase(LaunchPhase::Classpath, None)?;
+
+    let (java, managed_java) = match &config_json.java_override {
+        Some(java_override) if !java_override.is_empty() => (PathBuf::from(java_override), None),
+        _ => {
+            let version = config_json.get_java_version(&version_json);
+            let managed_java = version.to_string();
+            let java = java_install::get_java(
+                version,
+                options.java_install_progress,
+                options.cancel.as_ref(),
+            )
+            .await
+            .in_phase(LaunchPhase::Java, None)?;
+            (java, Some(managed_java))
+        }
+    };
+
+    let mut args: Vec<String> = options.wrapper.iter().skip(1).cloned().collect();
+    let program = match options.wrapper.first() {
+        Some(wrapper) => {
+            args.push(java.to_string_lossy().into_owned());
+            PathBuf::from(wrapper)
+        }
+        None => java,
+    };
+    args.extend(java_arguments);
+    args.extend(game_arguments);
+
+    let launch_command = LaunchCommand {
+        program,
+        args,
+        env: options.env,
+        current_dir: minecraft_dir,
+    };
+    Ok(BuiltCommand {
+        launch_command,
+        config_json,
+        config_path,
+        managed_java,
+    })
+}
+
+/// Everything needed to start the game, besides
+/// Java and the classpath.
+struct PreparedLaunch {
+    config_json: InstanceConfigJson,
+    config_path: PathBuf,
+    version_json: VersionDetails,
+    instance_dir: PathBuf,
+    minecraft_dir: PathBuf,
+    java_arguments: Vec<String>,
+    game_arguments: Vec<String>,
+    fabric_json: Option<FabricJSON>,
+}
 
+/// The blocking part of [`launch`].
+fn prepare_launch(options: &LaunchOptions) -> LauncherResult<PreparedLaunch> {
+    let instance_name = options.instance_name.as_str();
     let instance_dir = get_instance_dir(instance_name)?;
-    let minecraft_dir = instance_dir.join(".minecraft");
+    let config_path = instance_dir.join("config.json");
+    let mut config_json =
+        get_config(&instance_dir).in_phase(LaunchPhase::ReadConfig, Some(&config_path))?;
+
+    // In case the launcher closed before the game
+    // that was launched without mods did.
+    restore_mods_in(&config_json.get_game_dir(&instance_dir))?;
+    let minecraft_dir = if options.separate_game_dir {
+        prepare_separate_game_dir(&instance_dir, &config_json)?
+    } else {
+        config_json.get_game_dir(&instance_dir)
+    };
     std::fs::create_dir_all(&minecraft_dir).map_err(io_err!(minecraft_dir))?;
+    if options.without_mods {
+        let mods_dir = minecraft_dir.join("mods");
+        if mods_dir.exists() {
+            info!("Launching without mods");
+            let disabled_dir = minecraft_dir.join(DISABLED_MODS_DIR);
+            std::fs::rename(&mods_dir, &disabled_dir).map_err(io_err!(mods_dir))?;
+        }
+    }
 
-    let config_json = get_config(&instance_dir)?;
-
-    let version_json = read_version_json(&instance_dir)?;
+    let version_json = read_version_json(&instance_dir).in_phase(
+        LaunchPhase::ReadVersionJson,
+        Some(&instance_dir.join("details.json")),
+    )?;
 
-    let game_arguments = get_arguments(&version_json, username, minecraft_dir, &instance_dir)?;
+    let version_changed = config_json
+        .last_launched_version
+        .as_ref()
+        .is_some_and(|version| *version != version_json.id);
+    if version_changed && config_json.backup_worlds_on_version_change {
+        info!("Minecraft version changed, backing up worlds");
+        world_backup::backup_all_worlds(instance_name)
+            .map_err(|err| LauncherError::WorldBackup(Box::new(err)))?;
+    }
+    config_json.last_launched_version = Some(version_json.id.clone());
+
+    let version_type = config_json
+        .version_type
+        .as_deref()
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or(&version_json.r#type);
+    let mut game_arguments = get_arguments(
+        &version_json,
+        &options.account,
+        version_type,
+        minecraft_dir.clone(),
+        &instance_dir,
+    )
+    .in_phase(LaunchPhase::Classpath, None)?;
+    if let Some(server) = &options.server {
+        game_arguments.extend(server_arguments(&version_json, server));
+    }
+    game_arguments.extend(options.game_args.iter().cloned());
 
     let natives_path = instance_dir.join("libraries").join("natives");
 
@@ -88,40 +305,76 @@ pub async fn launch(
                 .to_str()
                 .ok_or(LauncherError::PathBufToString(natives_path.clone()))?
         ),
-        config_json.get_ram_argument(),
     ];
+    java_arguments.extend(config_json.get_ram_arguments());
 
     if version_json.r#type == "old_beta" || version_json.r#type == "old_alpha" {
         java_arguments.push("-Dhttp.proxyHost=betacraft.uk".to_owned());
     }
+    if applet_wrapper::needs_applet_wrapper(&version_json) {
+        applet_wrapper::add_arguments(
+            &version_json,
+            &minecraft_dir.to_string_lossy(),
+            &mut java_arguments,
+            &mut game_arguments,
+        );
+    }
 
-    let fabric_json = setup_fabric(&config_json, &instance_dir, &mut java_arguments)?;
+    java_arguments.extend(launch_presets::get_java_args(
+        &config_json.mod_type,
+        &version_json.id,
+    ));
 
-    setup_logging(&version_json, &instance_dir, &mut java_arguments)?;
-    setup_classpath_and_mainclass(
-        &mut java_arguments,
-        &version_json,
-        instance_dir,
-        fabric_json,
-    )?;
+    // After the defaults, so they can be overridden.
+    java_arguments.extend(options.java_args.iter().cloned());
+    java_arguments.extend(config_json.java_args.iter().cloned());
+    java_arguments.extend(options.override_java_args.iter().cloned());
 
-    let mut command = if let Some(java_override) = config_json.java_override {
-        Command::new(java_override)
-    } else {
-        let version = if let Some(version) = version_json.javaVersion {
-            version.into()
-        } else {
-            JavaVersion::Java8
-        };
-        Command::new(java_install::get_java(version, java_install_progress_sender).await?)
-    };
+    let fabric_json = setup_fabric(&config_json, &instance_dir, &mut java_arguments)
+        .in_phase(LaunchPhase::Classpath, None)?;
 
-    println!("[info] Java args: {java_arguments:?}\n\n[info] Game args: {game_arguments:?}\n");
+    setup_logging(
+        &version_json,
+        &instance_dir,
+        config_json.log4shell_mitigation,
+        &mut java_arguments,
+    )
+    .in_phase(LaunchPhase::Classpath, None)?;
 
-    let command = command.args(java_arguments.iter().chain(game_arguments.iter()));
-    let result = command.spawn().map_err(LauncherError::CommandError)?;
+    Ok(PreparedLaunch {
+        config_json,
+        config_path,
+        version_json,
+        instance_dir,
+        minecraft_dir,
+        java_arguments,
+        game_arguments,
+        fabric_json,
+    })
+}
 
-    Ok(result)
+/// Game arguments for joining `address` (`host` or `host:port`)
+/// on startup. Versions since 23w14a have Quick Play for this,
+/// older ones use `--server` and `--port`.
+fn server_arguments(version_json: &VersionDetails, address: &str) -> Vec<String> {
+    let has_quick_play = version_json.arguments.as_ref().is_some_and(|arguments| {
+        arguments
+            .game
+            .iter()
+            .any(|arg| arg.to_string().contains("quickPlayMultiplayer"))
+    });
+    if has_quick_play {
+        return vec!["--quickPlayMultiplayer".to_owned(), address.to_owned()];
+    }
+    match address.rsplit_once(':') {
+        Some((host, port)) => vec![
+            "--server".to_owned(),
+            host.to_owned(),
+            "--port".to_owned(),
+            port.to_owned(),
+        ],
+        None => vec!["--server".to_owned(), address.to_owned()],
+    }
 }
 
 fn setup_fabric(
@@ -152,38 +405,191 @@ fn setup_classpath_and_mainclass(
     java_arguments.push(get_class_path(version_json, instance_dir, &fabric_json)?);
     java_arguments.push(if let Some(ref fabric_json) = fabric_json {
         fabric_json.mainClass.clone()
+    } else if applet_wrapper::needs_applet_wrapper(version_json) {
+        applet_wrapper::main_class().to_owned()
     } else {
         version_json.mainClass.clone()
     });
     Ok(())
 }
 
+/// How to stop Log4Shell (CVE-2021-44228) in a range
+/// of versions, as Mojang recommends.
+enum Log4ShellFix {
+    /// A logging config that doesn't look up `${...}` in
+    /// messages, used instead of the version's own.
+    Config {
+        file_name: &'static str,
+        contents: &'static str,
+    },
+    /// Log4j 2.10 and newer can turn off lookups with a flag.
+    NoLookupsFlag,
+}
+
+/// The versions released in `from..until`, by release time,
+/// and their fix. 1.18.1 and newer have a fixed Log4j.
+const LOG4SHELL_FIXES: &[(&str, &str, Log4ShellFix)] = &[
+    // 1.7 to 1.11.2
+    (
+        "2013-09-26",
+        "2017-06-02",
+        Log4ShellFix::Config {
+            file_name: "log4j2_17-111.xml",
+            contents: include_str!("log4j/log4j2_17-111.xml"),
+        },
+    ),
+    // 1.12 to 1.16.5
+    (
+        "2017-06-02",
+        "2021-06-08",
+        Log4ShellFix::Config {
+            file_name: "log4j2_112-116.xml",
+            contents: include_str!("log4j/log4j2_112-116.xml"),
+        },
+    ),
+    // 1.17 to 1.18
+    ("2021-06-08", "2021-12-10", Log4ShellFix::NoLookupsFlag),
+];
+
+fn get_log4shell_fix(version_json: &VersionDetails) -> Option<&'static Log4ShellFix> {
+    // The times are ISO 8601, so they sort as text.
+    let released = version_json.releaseTime.as_str();
+    LOG4SHELL_FIXES
+        .iter()
+        .find(|(from, until, _)| released >= *from && released < *until)
+        .map(|(_, _, fix)| fix)
+}
+
+/// Points the game to its logging config, which for versions with
+/// a vulnerable Log4j is one that isn't, unless `mitigate_log4shell`
+/// is off. Versions without a logging config use the one in the
+/// game's jar, which can't be fixed without changing how the game
+/// logs, so they're left alone.
 fn setup_logging(
     version_json: &VersionDetails,
     instance_dir: &Path,
+    mitigate_log4shell: bool,
     java_arguments: &mut Vec<String>,
 ) -> Result<(), LauncherError> {
-    if let Some(ref logging) = version_json.logging {
-        let logging_path = instance_dir.join(format!("logging-{}", logging.client.file.id));
-        let logging_path = logging_path
-            .to_str()
-            .ok_or(LauncherError::PathBufToString(logging_path.clone()))?;
-        java_arguments.push(format!("-Dlog4j.configurationFile=\"{}\"", logging_path))
+    let Some(ref logging) = version_json.logging else {
+        return Ok(());
+    };
+    let mut logging_path = instance_dir.join(format!("logging-{}", logging.client.file.id));
+    let fix = get_log4shell_fix(version_json).filter(|_| mitigate_log4shell);
+    match fix {
+        Some(Log4ShellFix::Config {
+            file_name,
+            contents,
+        }) => {
+            logging_path = instance_dir.join(file_name);
+            std::fs::write(&logging_path, contents).map_err(io_err!(logging_path))?;
+        }
+        Some(Log4ShellFix::NoLookupsFlag) => {
+            java_arguments.push("-Dlog4j2.formatMsgNoLookups=true".to_owned());
+        }
+        None => {}
     }
+    let logging_path = logging_path
+        .to_str()
+        .ok_or(LauncherError::PathBufToString(logging_path.clone()))?;
+    java_arguments.push(format!("-Dlog4j.configurationFile=\"{}\"", logging_path));
     Ok(())
 }
 
-fn get_fabric_json(instance_dir: &Path) -> Result<FabricJSON, JsonFileError> {
+pub(super) fn get_fabric_json(instance_dir: &Path) -> Result<FabricJSON, JsonFileError> {
     let json_path = instance_dir.join("fabric.json");
     let fabric_json = std::fs::read_to_string(&json_path).map_err(io_err!(json_path))?;
     Ok(serde_json::from_str(&fabric_json)?)
 }
 
-fn get_config(instance_dir: &Path) -> Result<InstanceConfigJson, JsonFileError> {
-    let config_file_path = instance_dir.join("config.json");
-    let config_json =
-        std::fs::read_to_string(&config_file_path).map_err(io_err!(config_file_path))?;
-    Ok(serde_json::from_str(&config_json)?)
+/// The folder the instance's game runs in, usually its
+/// `.minecraft`. See `game_dir` in [`InstanceConfigJson`].
+pub fn get_game_dir(instance_name: &str) -> LauncherResult<PathBuf> {
+    let instance_dir = get_instance_dir(instance_name)?;
+    Ok(get_config(&instance_dir)?.get_game_dir(&instance_dir))
+}
+
+/// Where the `mods` folder is moved to while the game runs
+/// [`LaunchOptions::without_mods`].
+const DISABLED_MODS_DIR: &str = "mods.disabled";
+
+/// Puts back the mods of a game launched
+/// [`LaunchOptions::without_mods`], once it has exited.
+/// Does nothing if they weren't moved aside.
+pub fn restore_disabled_mods(instance_name: &str) -> LauncherResult<()> {
+    restore_mods_in(&get_game_dir(instance_name)?)
+}
+
+fn restore_mods_in(game_dir: &Path) -> LauncherResult<()> {
+    let disabled_dir = game_dir.join(DISABLED_MODS_DIR);
+    if !disabled_dir.is_dir() {
+        return Ok(());
+    }
+    let mods_dir = game_dir.join("mods");
+    // Mod loaders make an empty `mods` folder when there isn't one,
+    // and anything added while the game ran is kept too.
+    if mods_dir.is_dir() {
+        for entry in std::fs::read_dir(&mods_dir).map_err(io_err!(mods_dir))? {
+            let path = entry.map_err(io_err!(mods_dir))?.path();
+            let to = disabled_dir.join(path.file_name().unwrap_or_default());
+            if !to.exists() {
+                std::fs::rename(&path, &to).map_err(io_err!(path))?;
+            }
+        }
+        std::fs::remove_dir_all(&mods_dir).map_err(io_err!(mods_dir))?;
+    }
+    info!("Restored the mods folder");
+    std::fs::rename(&disabled_dir, &mods_dir).map_err(io_err!(disabled_dir))?;
+    Ok(())
+}
+
+/// The folder a second copy of the instance runs in, when it's
+/// launched while already running. It's kept between launches,
+/// so its worlds stay around.
+const SEPARATE_GAME_DIR: &str = ".minecraft-separate";
+/// Copied from the instance's game folder into the separate one
+/// before every launch, so that both play the same way.
+const SHARED_GAME_FILES: &[&str] = &["options.txt", "mods", "config", "resourcepacks"];
+
+fn prepare_separate_game_dir(
+    instance_dir: &Path,
+    config_json: &InstanceConfigJson,
+) -> LauncherResult<PathBuf> {
+    let game_dir = config_json.get_game_dir(instance_dir);
+    let separate_dir = instance_dir.join(SEPARATE_GAME_DIR);
+    info!("Launching in a separate game folder: {separate_dir:?}");
+
+    for name in SHARED_GAME_FILES {
+        let from = game_dir.join(name);
+        let to = separate_dir.join(name);
+        if from.is_dir() {
+            if to.exists() {
+                std::fs::remove_dir_all(&to).map_err(io_err!(to))?;
+            }
+            file_utils::copy_dir_recursive(&from, &to)?;
+        } else if from.is_file() {
+            std::fs::create_dir_all(&separate_dir).map_err(io_err!(separate_dir))?;
+            std::fs::copy(&from, &to).map_err(io_err!(from))?;
+        }
+    }
+    Ok(separate_dir)
+}
+
+pub(super) fn get_config(instance_dir: &Path) -> Result<InstanceConfigJson, JsonFileError> {
+    InstanceConfigJson::read(&instance_dir.join("config.json"))
+}
+
+pub(super) fn save_last_played(
+    config_json: &mut InstanceConfigJson,
+    config_path: &Path,
+) -> Result<(), JsonFileError> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    config_json.last_played = Some(now);
+
+    config_json.save(config_path)
 }
 
 fn get_class_path(
@@ -244,103 +650,74 @@ fn get_class_path(
 
 fn get_arguments(
     version_json: &VersionDetails,
-    username: &str,
+    account: &Account,
+    version_type: &str,
     minecraft_dir: PathBuf,
     instance_dir: &Path,
 ) -> LauncherResult<Vec<String>> {
-    let mut game_arguments: Vec<String> =
-        if let Some(ref arguments) = version_json.minecraftArguments {
-            arguments.split(' ').map(ToOwned::to_owned).collect()
-        } else if let Some(ref arguments) = version_json.arguments {
-            arguments
-                .game
-                .iter()
-                .filter_map(|arg| arg.as_str())
-                .map(ToOwned::to_owned)
-                .collect()
-        } else {
-            return Err(LauncherError::VersionJsonNoArgumentsField(
-                version_json.clone(),
-            ));
-        };
-    for argument in game_arguments.iter_mut() {
-        replace_var(argument, "auth_player_name", username);
-        replace_var(argument, "version_name", &version_json.id);
-        let minecraft_dir_path = match minecraft_dir.to_str() {
-            Some(n) => n,
-            None => return Err(LauncherError::PathBufToString(minecraft_dir)),
-        };
-        replace_var(argument, "game_directory", minecraft_dir_path);
-
-        let assets_path = file_utils::get_launcher_dir()?
-            .join("assets")
-            .join(&version_json.assetIndex.id);
-
-        let old_assets_path = instance_dir.join("assets");
-
-        if old_assets_path.exists() {
-            migrate_to_new_assets_path(&old_assets_path, &assets_path)?;
-        }
-
-        let assets_path = match assets_path.to_str() {
-            Some(n) => n,
-            None => return Err(LauncherError::PathBufToString(assets_path)),
-        };
-
-        replace_var(argument, "assets_root", assets_path);
-        replace_var(argument, "game_assets", assets_path);
-        replace_var(argument, "auth_xuid", "0");
-        replace_var(
-            argument,
-            "auth_uuid",
-            "00000000-0000-0000-0000-000000000000",
-        );
-        replace_var(argument, "auth_access_token", "0");
-        replace_var(argument, "clientid", "0");
-        replace_var(argument, "user_type", "legacy");
-        replace_var(argument, "version_type", "release");
-        replace_var(argument, "assets_index_name", &version_json.assetIndex.id);
-        replace_var(argument, "user_properties", "{}");
+    let game_arguments: Vec<String> = if let Some(ref arguments) = version_json.minecraftArguments {
+        arguments.split(' ').map(ToOwned::to_owned).collect()
+    } else if let Some(ref arguments) = version_json.arguments {
+        arguments
+            .game
+            .iter()
+            .filter_map(|arg| arg.as_str())
+            .map(ToOwned::to_owned)
+            .collect()
+    } else {
+        return Err(LauncherError::VersionJsonNoArgumentsField(Box::new(
+            version_json.clone(),
+        )));
+    };
+    let minecraft_dir_path = minecraft_dir
+        .to_str()
+        .ok_or_else(|| LauncherError::PathBufToString(minecraft_dir.clone()))?;
+
+    let assets_path = file_utils::get_cache_dir()?
+        .join("assets")
+        .join(&version_json.assetIndex.id);
+    let old_assets_path = instance_dir.join("assets");
+    if old_assets_path.exists() {
+        migrate_to_new_assets_path(&old_assets_path, &assets_path)?;
     }
-    Ok(game_arguments)
+    let assets_path_str = assets_path
+        .to_str()
+        .ok_or_else(|| LauncherError::PathBufToString(assets_path.clone()))?;
+    let game_assets_path =
+        legacy_assets::materialize(&assets_path, &version_json.assetIndex.id, &minecraft_dir)?;
+    let game_assets_path_str = game_assets_path
+        .to_str()
+        .ok_or_else(|| LauncherError::PathBufToString(game_assets_path.clone()))?;
+
+    let vars = ArgumentVars::new(
+        account,
+        &InstanceContext {
+            version_name: &version_json.id,
+            version_type,
+            game_directory: minecraft_dir_path,
+            assets_root: assets_path_str,
+            game_assets: game_assets_path_str,
+            assets_index_name: &version_json.assetIndex.id,
+        },
+    );
+    Ok(game_arguments
+        .iter()
+        .map(|argument| vars.substitute(argument))
+        .collect())
 }
 
 fn migrate_to_new_assets_path(
     old_assets_path: &Path,
     assets_path: &Path,
 ) -> Result<(), LauncherError> {
-    println!("[info] Migrating old assets to new path...");
-    copy_dir_recursive(old_assets_path, assets_path)?;
+    info!("Migrating old assets to new path...");
+    file_utils::copy_dir_recursive(old_assets_path, assets_path)?;
     std::fs::remove_dir_all(old_assets_path).map_err(io_err!(old_assets_path))?;
-    println!("[info] Finished");
-    Ok(())
-}
-
-fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), IoError> {
-    // Create the destination directory if it doesn't exist
-    if !dst.exists() {
-        std::fs::create_dir_all(dst).map_err(io_err!(dst))?;
-    }
-
-    // Iterate over the directory entries
-    for entry in std::fs::read_dir(src).map_err(io_err!(src))? {
-        let entry = entry.map_err(io_err!(src))?;
-        let path = entry.path();
-        let dest_path = dst.join(entry.file_name());
-
-        if path.is_dir() {
-            // Recursively copy the subdirectory
-            copy_dir_recursive(&path, &dest_path)?;
-        } else {
-            // Copy the file to the destination directory
-            std::fs::copy(&path, &dest_path).map_err(io_err!(path))?;
-        }
-    }
-
+    info!("Finished");
     Ok(())
 }
 
-fn get_instance_dir(instance_name: &str) -> LauncherResult<PathBuf> {
+pub(super) fn get_instance_dir(instance_name: &str) -> LauncherResult<PathBuf> {
     if instance_name.is_empty() {
         return Err(LauncherError::InstanceNotFound);
     }
@@ -358,14 +735,202 @@ fn get_instance_dir(instance_name: &str) -> LauncherResult<PathBuf> {
     Ok(instance_dir)
 }
 
-fn replace_var(string: &mut String, var: &str, value: &str) {
-    *string = string.replace(&format!("${{{}}}", var), value);
+/// Splits arguments typed into a text box, like
+/// `-Dfile.encoding=UTF-8 "-Dsome.path=C:/My Folder"`.
+///
+/// Arguments are separated by spaces, unless they're in
+/// double quotes.
+pub fn split_arguments(text: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_argument = false;
+    for c in text.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_argument = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_argument {
+                    arguments.push(std::mem::take(&mut current));
+                    has_argument = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_argument = true;
+            }
+        }
+    }
+    if has_argument {
+        arguments.push(current);
+    }
+    arguments
+}
+
+/// The opposite of [`split_arguments`], quoting
+/// arguments that have spaces in them.
+pub fn join_arguments(arguments: &[String]) -> String {
+    arguments
+        .iter()
+        .map(|argument| {
+            if argument.is_empty() || argument.contains(char::is_whitespace) {
+                format!("\"{argument}\"")
+            } else {
+                argument.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub(super) fn read_version_json(instance_dir: &Path) -> Result<VersionDetails, JsonFileError> {
+    VersionDetails::read(&instance_dir.join("details.json"))
+}
+
+/// Downloads the instance's version details (`details.json`)
+/// again, for when they were deleted or damaged.
+///
+/// The version is the one whose jar is in `.minecraft/versions`,
+/// or else the one the instance was last launched with.
+/// Instances made from a custom version JSON read it again instead.
+pub async fn redownload_version_json(instance_name: &str) -> LauncherResult<()> {
+    let instance_dir = get_instance_dir(instance_name)?;
+    // The config may be broken too, in which case the version
+    // can still be found from the jar.
+    let config_json = get_config(&instance_dir).ok();
+    let client = file_utils::create_client();
+    let version_json = match config_json
+        .as_ref()
+        .and_then(|n| n.custom_version_json.as_deref())
+    {
+        Some(custom) => {
+            info!("Reading the custom version details from {custom} again");
+            CustomVersionJson::parse(custom)
+                .read::<LauncherError>(&client)
+                .await?
+        }
+        None => download_listed_version_json(&client, &instance_dir, config_json).await?,
+    };
+    // Checked before replacing anything.
+    serde_json::from_str::<VersionDetails>(&version_json)?;
+
+    snapshots::take_or_log(instance_name, SnapshotReason::VersionJsonRedownload);
+    let path = instance_dir.join("details.json");
+    std::fs::write(&path, version_json).map_err(io_err!(path))?;
+    Ok(())
 }
 
-fn read_version_json(instance_dir: &Path) -> Result<VersionDetails, JsonFileError> {
-    let file_path = instance_dir.join("details.json");
+async fn download_listed_version_json(
+    client: &reqwest::Client,
+    instance_dir: &Path,
+    config_json: Option<InstanceConfigJson>,
+) -> LauncherResult<String> {
+    let version = find_jar_version(instance_dir)
+        .or_else(|| config_json.as_ref()?.last_launched_version.clone())
+        .ok_or(LauncherError::InstanceVersionUnknown)?;
+    info!("Downloading the version details of {version} again");
+
+    let source = config_json.map_or_else(VersionSource::default, |n| n.version_source);
+    let manifest = Manifest::download_from(source).await?;
+    let listed = manifest
+        .versions
+        .iter()
+        .find(|n| n.id == version)
+        .ok_or_else(|| LauncherError::VersionNotFoundInManifest(version.clone()))?;
+    Ok(file_utils::download_file_to_string(client, &listed.url).await?)
+}
 
-    let version_json: String = std::fs::read_to_string(&file_path).map_err(io_err!(file_path))?;
-    let version_json = serde_json::from_str(&version_json)?;
-    Ok(version_json)
+pub async fn redownload_version_json_wrapped(instance_name: String) -> Result<(), String> {
+    redownload_version_json(&instance_name)
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// The version in `.minecraft/versions/<version>/<version>.jar`,
+/// if there's exactly one.
+fn find_jar_version(instance_dir: &Path) -> Option<String> {
+    let versions_dir = instance_dir.join(".minecraft").join("versions");
+    let mut versions = std::fs::read_dir(versions_dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            entry
+                .path()
+                .join(format!("{name}.jar"))
+                .is_file()
+                .then_some(name)
+        });
+    let version = versions.next()?;
+    versions.next().is_none().then_some(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_arguments() {
+        let arguments =
+            split_arguments(r#"  -Dfile.encoding=UTF-8  "-Dsome.path=C:/My Folder" "" "#);
+        assert_eq!(
+            arguments,
+            ["-Dfile.encoding=UTF-8", "-Dsome.path=C:/My Folder", ""]
+        );
+        assert_eq!(split_arguments(&join_arguments(&arguments)), arguments);
+        assert!(split_arguments("   ").is_empty());
+    }
+
+    #[test]
+    fn test_java_args_order() {
+        let launcher_dir =
+            std::env::temp_dir().join(format!("ql_launch_test_{}", std::process::id()));
+        let instance_dir = launcher_dir.join("instances").join("test");
+        std::fs::create_dir_all(&instance_dir).unwrap();
+        std::fs::write(
+            instance_dir.join("config.json"),
+            r#"{"ram_in_mb": 2048, "mod_type": "Vanilla", "java_override": "java",
+                "java_args": ["-Dorder=instance"]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            instance_dir.join("details.json"),
+            r#"{
+                "assetIndex": {"id": "test", "sha1": "", "size": 0, "totalSize": 0, "url": ""},
+                "assets": "test",
+                "downloads": {"client": {"sha1": "", "size": 0, "url": ""}},
+                "id": "1.20.1",
+                "libraries": [],
+                "mainClass": "net.minecraft.client.main.Main",
+                "minecraftArguments": "--username ${auth_player_name}",
+                "minimumLauncherVersion": 0,
+                "releaseTime": "",
+                "time": "",
+                "type": "release"
+            }"#,
+        )
+        .unwrap();
+        file_utils::set_data_dir(Some(launcher_dir.clone()));
+
+        let options = LaunchOptions::new("test")
+            .java_args(["-Dorder=launcher".to_owned()])
+            .override_java_args(["-Dorder=once".to_owned()]);
+        let command = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(launch_dry_run(options));
+        file_utils::set_data_dir(None);
+        std::fs::remove_dir_all(&launcher_dir).unwrap();
+
+        let command = command.unwrap();
+        let order: Vec<&str> = command
+            .args
+            .iter()
+            .filter_map(|arg| arg.strip_prefix("-Dorder="))
+            .collect();
+        assert_eq!(order, ["launcher", "instance", "once"]);
+    }
 }
@@ -0,0 +1,338 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedReceiver;
+use tracing::error;
+
+use crate::{
+    error::{LauncherError, LauncherResult},
+    file_utils, io_err,
+    json_structs::json_instance_config::InstanceConfigJson,
+};
+
+/// What the launcher shows about an instance.
+///
+/// Everything except the name is optional, so that
+/// broken instances still show up (and can be deleted).
+#[derive(Debug, Clone, Serialize)]
+pub struct InstanceInfo {
+    pub name: String,
+    pub version: Option<String>,
+    pub mod_type: Option<String>,
+    /// Seconds since the Unix epoch.
+    pub last_played: Option<u64>,
+    /// Who the instance was last launched as.
+    pub last_username: Option<String>,
+    /// The offline profile the instance was last launched as.
+    pub last_profile: Option<String>,
+    /// The offline profile the instance always launches as.
+    pub offline_profile: Option<String>,
+    pub icon: Option<PathBuf>,
+}
+
+impl InstanceInfo {
+    pub fn load(instance_dir: &Path, name: String) -> Self {
+        #[derive(Deserialize)]
+        struct VersionId {
+            id: String,
+        }
+
+        let config = InstanceConfigJson::read(&instance_dir.join("config.json")).ok();
+        let version: Option<VersionId> = read_json(&instance_dir.join("details.json"));
+        let icon = instance_dir.join("icon.png");
+
+        Self {
+            name,
+            version: version.map(|n| n.id),
+            mod_type: config.as_ref().map(|n| n.mod_type.clone()),
+            last_played: config.as_ref().and_then(|n| n.last_played),
+            last_username: config.as_ref().and_then(|n| n.last_username.clone()),
+            last_profile: config.as_ref().and_then(|n| n.last_profile.clone()),
+            offline_profile: config.and_then(|n| n.offline_profile),
+            icon: icon.is_file().then_some(icon),
+        }
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let json = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Gets (and creates) `QuantumLauncher/<folder>/`, where
+/// `folder` is `instances` for clients or `servers` for servers.
+pub(crate) fn get_instances_dir(folder: &str) -> LauncherResult<PathBuf> {
+    // .config/QuantumLauncher/ OR AppData/Roaming/QuantumLauncher/
+    // (or the custom data directory)
+    let dir_path = file_utils::get_launcher_dir()?;
+    std::fs::create_dir_all(&dir_path).map_err(io_err!(dir_path))?;
+
+    // QuantumLauncher/instances/ OR QuantumLauncher/servers/
+    let dir_path = dir_path.join(folder);
+    std::fs::create_dir_all(&dir_path).map_err(io_err!(dir_path))?;
+    Ok(dir_path)
+}
+
+/// Lists every instance in the `instances` folder.
+pub fn list_instances() -> LauncherResult<Vec<InstanceInfo>> {
+    list_in("instances")
+}
+
+/// Lists every server in the `servers` folder.
+pub fn list_servers() -> LauncherResult<Vec<InstanceInfo>> {
+    list_in("servers")
+}
+
+fn list_in(folder: &str) -> LauncherResult<Vec<InstanceInfo>> {
+    let dir_path = get_instances_dir(folder)?;
+    let dir = std::fs::read_dir(&dir_path).map_err(io_err!(dir_path))?;
+
+    let instances = dir
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            if !entry.path().is_dir() {
+                return None;
+            }
+            let file_name = entry.file_name().to_str()?.to_owned();
+            Some(InstanceInfo::load(&entry.path(), file_name))
+        })
+        .collect();
+
+    Ok(instances)
+}
+
+/// The [`InstanceInfo`] of every instance, read once and kept
+/// in memory so that menus don't have to read each instance's
+/// files again. It's only updated when told to, with
+/// [`InstanceRegistry::invalidate`] or [`InstanceRegistry::apply`].
+#[derive(Debug, Clone, Default)]
+pub struct InstanceRegistry {
+    /// Sorted by name.
+    instances: Vec<InstanceInfo>,
+}
+
+impl InstanceRegistry {
+    pub fn load() -> LauncherResult<Self> {
+        let mut instances = list_instances()?;
+        instances.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(Self { instances })
+    }
+
+    pub fn instances(&self) -> &[InstanceInfo] {
+        &self.instances
+    }
+
+    pub fn get(&self, name: &str) -> Option<&InstanceInfo> {
+        self.instances.iter().find(|info| info.name == name)
+    }
+
+    /// Re-reads an instance after it changed. It's added if
+    /// it's new, and removed if its folder is gone.
+    pub fn invalidate(&mut self, name: &str) -> LauncherResult<()> {
+        let instance_dir = get_instances_dir("instances")?.join(name);
+        let position = self
+            .instances
+            .binary_search_by(|info| info.name.as_str().cmp(name));
+        match (position, instance_dir.is_dir()) {
+            (Ok(i), true) => self.instances[i] = InstanceInfo::load(&instance_dir, name.to_owned()),
+            (Ok(i), false) => _ = self.instances.remove(i),
+            (Err(i), true) => self
+                .instances
+                .insert(i, InstanceInfo::load(&instance_dir, name.to_owned())),
+            (Err(_), false) => {}
+        }
+        Ok(())
+    }
+
+    /// Picks up instances added or removed outside the launcher,
+    /// from an [`InstanceFolderWatcher`]. Instances it already
+    /// has aren't replaced. Returns whether anything changed.
+    pub fn apply(&mut self, changes: FolderChanges) -> bool {
+        let count = self.instances.len();
+        self.instances
+            .retain(|info| changes.instance_names.contains(&info.name));
+        let mut changed = self.instances.len() != count;
+
+        for info in changes.new_instances {
+            if let Err(i) = self
+                .instances
+                .binary_search_by(|other| other.name.cmp(&info.name))
+            {
+                self.instances.insert(i, info);
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// Instances and servers added or removed outside
+/// the launcher, found by an [`InstanceFolderWatcher`].
+#[derive(Debug, Clone)]
+pub struct FolderChanges {
+    /// Every instance in the `instances` folder, sorted.
+    pub instance_names: Vec<String>,
+    /// The instances that weren't there before, already read.
+    pub new_instances: Vec<InstanceInfo>,
+    /// Every server, if any were added or removed.
+    pub servers: Option<Vec<InstanceInfo>>,
+}
+
+impl FolderChanges {
+    /// `None` if the folders still have the same instances and servers.
+    fn read(old_instances: &[String], old_servers: &[String]) -> LauncherResult<Option<Self>> {
+        let instance_names = list_instance_names()?;
+        let server_names = list_server_names()?;
+        if instance_names == old_instances && server_names == old_servers {
+            return Ok(None);
+        }
+
+        let instances_dir = get_instances_dir("instances")?;
+        let new_instances = instance_names
+            .iter()
+            .filter(|name| !old_instances.contains(name))
+            .map(|name| InstanceInfo::load(&instances_dir.join(name), name.clone()))
+            .collect();
+        let servers = if server_names == old_servers {
+            None
+        } else {
+            Some(list_servers()?)
+        };
+        Ok(Some(Self {
+            instance_names,
+            new_instances,
+            servers,
+        }))
+    }
+}
+
+/// Watches the `instances` and `servers` folders, for
+/// instances copied in or deleted by hand while the
+/// launcher is open. Stops watching when dropped.
+pub struct InstanceFolderWatcher {
+    _watcher: RecommendedWatcher,
+    events: UnboundedReceiver<()>,
+    instance_names: Vec<String>,
+    server_names: Vec<String>,
+}
+
+impl InstanceFolderWatcher {
+    pub fn new() -> LauncherResult<Self> {
+        let (sender, events) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                if event.is_ok_and(|event| !event.kind.is_access()) {
+                    _ = sender.send(());
+                }
+            })
+            .map_err(LauncherError::FolderWatch)?;
+        // Only the folders themselves, as changes
+        // inside an instance don't matter here.
+        for folder in ["instances", "servers"] {
+            watcher
+                .watch(&get_instances_dir(folder)?, RecursiveMode::NonRecursive)
+                .map_err(LauncherError::FolderWatch)?;
+        }
+
+        Ok(Self {
+            _watcher: watcher,
+            events,
+            instance_names: list_instance_names()?,
+            server_names: list_server_names()?,
+        })
+    }
+
+    /// Waits until instances or servers are added or removed.
+    /// The folders are read on a blocking thread.
+    ///
+    /// `None` if the watcher stopped.
+    pub async fn next_change(&mut self) -> Option<FolderChanges> {
+        // Copying an instance in comes with a burst of events.
+        const SETTLE_TIME: Duration = Duration::from_millis(500);
+
+        loop {
+            self.events.recv().await?;
+            tokio::time::sleep(SETTLE_TIME).await;
+            while self.events.try_recv().is_ok() {}
+
+            let old_instances = self.instance_names.clone();
+            let old_servers = self.server_names.clone();
+            let result =
+                file_utils::run_blocking(move || FolderChanges::read(&old_instances, &old_servers))
+                    .await;
+            match result {
+                Ok(Some(changes)) => {
+                    self.instance_names.clone_from(&changes.instance_names);
+                    if let Some(servers) = &changes.servers {
+                        self.server_names = servers.iter().map(|n| n.name.clone()).collect();
+                        self.server_names.sort();
+                    }
+                    return Some(changes);
+                }
+                Ok(None) => {}
+                Err(err) => error!("Could not read the instance folders: {err}"),
+            }
+        }
+    }
+}
+
+/// The names of the instances in the `instances` folder, sorted.
+/// Much quicker than [`list_instances`], for noticing when
+/// instances are added or removed outside the launcher.
+pub fn list_instance_names() -> LauncherResult<Vec<String>> {
+    names_in("instances")
+}
+
+/// Like [`list_instance_names`], for the `servers` folder.
+pub fn list_server_names() -> LauncherResult<Vec<String>> {
+    names_in("servers")
+}
+
+fn names_in(folder: &str) -> LauncherResult<Vec<String>> {
+    let dir_path = get_instances_dir(folder)?;
+    let dir = std::fs::read_dir(&dir_path).map_err(io_err!(dir_path))?;
+
+    let mut names: Vec<String> = dir
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            if !entry.path().is_dir() {
+                return None;
+            }
+            entry.file_name().to_str().map(str::to_owned)
+        })
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Deletes an instance's folder, along with its worlds and mods.
+///
+/// Java installs the instance used aren't removed, see
+/// [`crate::delete_unused_java_installs`] for that.
+pub fn delete_instance(instance_name: &str) -> LauncherResult<()> {
+    delete_in("instances", instance_name)
+}
+
+/// Deletes a server's folder, along with its worlds and plugins.
+pub fn delete_server(server_name: &str) -> LauncherResult<()> {
+    delete_in("servers", server_name)
+}
+
+fn delete_in(folder: &str, instance_name: &str) -> LauncherResult<()> {
+    let instances_dir = get_instances_dir(folder)?;
+    let instance_dir = instances_dir.join(instance_name);
+
+    if instance_name.is_empty() || !instance_dir.starts_with(&instances_dir) {
+        return Err(LauncherError::InstanceOutsideDirRemove);
+    }
+    if !instance_dir.is_dir() {
+        return Err(LauncherError::InstanceNotFound);
+    }
+
+    std::fs::remove_dir_all(&instance_dir).map_err(io_err!(instance_dir))?;
+    Ok(())
+}
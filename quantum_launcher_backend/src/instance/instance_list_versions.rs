@@ -1,12 +1,81 @@
 use std::sync::Arc;
 
-use crate::{error::LauncherResult, json_structs::json_manifest::Manifest};
+use crate::{
+    error::{HasErrorCode, LauncherResult},
+    json_structs::json_manifest::{Manifest, VersionSource},
+};
 
-async fn list() -> LauncherResult<Vec<String>> {
-    let manifest = Manifest::download().await?;
-    Ok(manifest.versions.iter().map(|n| n.id.clone()).collect())
+/// April Fools versions, which Mojang's manifest
+/// lists as ordinary snapshots.
+const APRIL_FOOLS_VERSIONS: &[&str] = &[
+    "15w14a",
+    "1.RV-Pre1",
+    "3D Shareware v1.34",
+    "20w14infinite",
+    "22w13oneblockatatime",
+    "23w13a_or_b",
+    "24w14potato",
+];
+
+/// The kind of a Minecraft version, from
+/// the `type` field of Mojang's version manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VersionType {
+    Release,
+    Snapshot,
+    /// April Fools versions, and experiments like
+    /// the combat tests.
+    Experimental,
+    Beta,
+    Alpha,
+}
+
+impl VersionType {
+    pub const ALL: [VersionType; 5] = [
+        VersionType::Release,
+        VersionType::Snapshot,
+        VersionType::Experimental,
+        VersionType::Beta,
+        VersionType::Alpha,
+    ];
+
+    pub fn from_manifest(version_type: &str, id: &str) -> Self {
+        if APRIL_FOOLS_VERSIONS.contains(&id) {
+            return VersionType::Experimental;
+        }
+        match version_type {
+            "release" => VersionType::Release,
+            "experiment" | "april_fools" => VersionType::Experimental,
+            "old_beta" => VersionType::Beta,
+            "old_alpha" => VersionType::Alpha,
+            // Anything unknown is most likely some kind of test version.
+            _ => VersionType::Snapshot,
+        }
+    }
+}
+
+/// A version that can be installed, as shown in the create instance menu.
+#[derive(Debug, Clone)]
+pub struct ListedVersion {
+    pub id: String,
+    pub version_type: VersionType,
+}
+
+async fn list(source: VersionSource) -> LauncherResult<Vec<ListedVersion>> {
+    let manifest = Manifest::download_from(source).await?;
+    Ok(manifest
+        .versions
+        .iter()
+        .map(|n| ListedVersion {
+            id: n.id.clone(),
+            version_type: VersionType::from_manifest(&n.r#type, &n.id),
+        })
+        .collect())
 }
 
-pub async fn list_versions() -> Result<Arc<Vec<String>>, String> {
-    list().await.map_err(|n| n.to_string()).map(Arc::new)
+pub async fn list_versions(source: VersionSource) -> Result<Arc<Vec<ListedVersion>>, String> {
+    list(source)
+        .await
+        .map_err(|n| n.to_string_with_code())
+        .map(Arc::new)
 }
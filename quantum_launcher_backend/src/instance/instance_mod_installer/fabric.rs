@@ -1,16 +1,21 @@
-use std::fmt::Display;
+use std::{fmt::Display, path::Path};
 
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use tracing::{error, info};
 
 use crate::{
-    error::IoError,
+    error::{HasErrorCode, IoError},
+    events::{self, Event},
     file_utils::{self, RequestError},
+    instance::snapshots::{self, SnapshotReason},
     io_err,
     json_structs::{
         json_fabric::FabricJSON, json_instance_config::InstanceConfigJson,
-        json_version::VersionDetails,
+        json_version::VersionDetails, JsonFileError,
     },
+    logging,
+    progress::ProgressSender,
 };
 
 const FABRIC_URL: &str = "https://meta.fabricmc.net";
@@ -19,14 +24,87 @@ async fn download_file_to_string(client: &Client, url: &str) -> Result<String, R
     file_utils::download_file_to_string(client, &format!("{FABRIC_URL}/{url}")).await
 }
 
-pub async fn get_list_of_versions() -> Result<Vec<FabricVersion>, String> {
-    let client = Client::new();
+pub async fn get_list_of_versions() -> Result<Vec<FabricVersion>, FabricInstallError> {
+    let client = file_utils::create_client();
     // The first one is the latest version.
-    let version_list = download_file_to_string(&client, "v2/versions/loader")
+    let version_list = download_file_to_string(&client, "v2/versions/loader").await?;
+    Ok(serde_json::from_str(&version_list)?)
+}
+
+pub async fn get_list_of_versions_wrapped() -> Result<Vec<FabricVersion>, String> {
+    get_list_of_versions()
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Loader versions that work with a Minecraft version.
+#[derive(Debug, Clone)]
+pub struct FabricVersionList {
+    /// The first one is the latest version.
+    pub versions: Vec<FabricVersion>,
+    /// Whether this is a saved copy, because
+    /// the list couldn't be downloaded.
+    pub cached: bool,
+}
+
+#[derive(Deserialize)]
+struct GameLoaderVersion {
+    loader: FabricVersion,
+}
+
+/// Lists the loader versions for the instance's Minecraft version.
+///
+/// Each list is saved to `QuantumLauncher/cache/fabric/<version>.json`,
+/// and used instead if the download fails (for example, when offline).
+pub async fn get_list_of_versions_for_instance(
+    instance_name: &str,
+) -> Result<FabricVersionList, FabricInstallError> {
+    let instance_dir = file_utils::get_launcher_dir()?
+        .join("instances")
+        .join(instance_name);
+    let game_version = VersionDetails::read(&instance_dir.join("details.json"))?.id;
+
+    let client = file_utils::create_client();
+    let cache_path = file_utils::get_cache_dir()?
+        .join("fabric")
+        .join(format!("{game_version}.json"));
+    let url = format!("v2/versions/loader/{game_version}");
+    let (list, cached) = match download_file_to_string(&client, &url).await {
+        Ok(list) => {
+            if let Err(err) = save_cache(&cache_path, &list) {
+                error!("Could not save Fabric versions to cache: {err}");
+            }
+            (list, false)
+        }
+        Err(err) => match std::fs::read_to_string(&cache_path) {
+            Ok(list) => {
+                error!("Could not download Fabric versions, using cached copy: {err}");
+                (list, true)
+            }
+            Err(_) => return Err(err.into()),
+        },
+    };
+
+    let versions: Vec<GameLoaderVersion> = serde_json::from_str(&list)?;
+    Ok(FabricVersionList {
+        versions: versions.into_iter().map(|n| n.loader).collect(),
+        cached,
+    })
+}
+
+pub async fn get_list_of_versions_for_instance_wrapped(
+    instance_name: String,
+) -> Result<FabricVersionList, String> {
+    get_list_of_versions_for_instance(&instance_name)
         .await
-        .map_err(|err| err.to_string())?;
+        .map_err(|err| err.to_string_with_code())
+}
 
-    serde_json::from_str(&version_list).map_err(|err| err.to_string())
+fn save_cache(path: &Path, list: &str) -> Result<(), IoError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(io_err!(parent))?;
+    }
+    std::fs::write(path, list).map_err(io_err!(path))
 }
 
 fn get_url(name: &str) -> String {
@@ -41,20 +119,106 @@ fn get_url(name: &str) -> String {
     )
 }
 
-pub async fn install(loader_version: &str, instance_name: &str) -> Result<(), FabricInstallError> {
-    let client = Client::new();
+/// Progress of a Fabric installation, sent through
+/// the `progress` channel of [`install`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum FabricInstallProgress {
+    DownloadingJson,
+    DownloadingLibrary {
+        progress: usize,
+        out_of: usize,
+        name: String,
+    },
+    Done,
+}
+
+impl Display for FabricInstallProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FabricInstallProgress::DownloadingJson => write!(f, "Downloading Fabric JSON."),
+            FabricInstallProgress::DownloadingLibrary {
+                progress,
+                out_of,
+                name,
+            } => write!(f, "Downloading library {progress} / {out_of}: {name}"),
+            FabricInstallProgress::Done => write!(f, "Done."),
+        }
+    }
+}
+
+impl From<&FabricInstallProgress> for f32 {
+    /// How far along the installation is, from 0 to 1.
+    fn from(val: &FabricInstallProgress) -> Self {
+        match val {
+            FabricInstallProgress::DownloadingJson => 0.0,
+            FabricInstallProgress::DownloadingLibrary {
+                progress, out_of, ..
+            } => 0.1 + (*progress as f32 * 0.9 / *out_of as f32),
+            FabricInstallProgress::Done => 1.0,
+        }
+    }
+}
+
+/// Installs the Fabric mod loader into an instance,
+/// after taking a snapshot of it.
+///
+/// If you want, you can hook this up to a progress bar,
+/// by passing a `ProgressSender<FabricInstallProgress>`.
+pub async fn install(
+    loader_version: &str,
+    instance_name: &str,
+    progress: Option<&ProgressSender<FabricInstallProgress>>,
+) -> Result<(), FabricInstallError> {
+    snapshots::take_or_log(instance_name, SnapshotReason::LoaderInstall);
+    install_loader(loader_version, instance_name, progress).await
+}
+
+/// Installs the newest stable loader version for the instance's
+/// Minecraft version (or the newest unstable one, if there's no
+/// stable one yet), without a snapshot, for new instances.
+///
+/// Returns `false` if Fabric doesn't support the version.
+pub(crate) async fn install_latest(instance_name: &str) -> Result<bool, FabricInstallError> {
+    let loader_versions = get_list_of_versions_for_instance(instance_name).await?;
+    let Some(latest) = loader_versions
+        .versions
+        .iter()
+        .find(|n| n.stable)
+        .or(loader_versions.versions.first())
+    else {
+        return Ok(false);
+    };
+    install_loader(&latest.version, instance_name, None).await?;
+    Ok(true)
+}
+
+/// [`install`], without the snapshot.
+pub(crate) async fn install_loader(
+    loader_version: &str,
+    instance_name: &str,
+    progress: Option<&ProgressSender<FabricInstallProgress>>,
+) -> Result<(), FabricInstallError> {
+    // The receiver may have been dropped if the user
+    // left the menu, but the install still continues.
+    let send_progress = |message: FabricInstallProgress| {
+        if let Some(progress) = progress {
+            _ = progress.send(message);
+        }
+    };
+
+    let _timer = logging::time_step("Installing Fabric");
+    let client = file_utils::create_client();
 
     let launcher_dir = file_utils::get_launcher_dir()?;
     let instance_dir = launcher_dir.join("instances").join(instance_name);
     let libraries_dir = instance_dir.join("libraries");
 
-    let version_json_path = instance_dir.join("details.json");
-    let version_json =
-        std::fs::read_to_string(&version_json_path).map_err(io_err!(version_json_path))?;
-    let version_json: VersionDetails = serde_json::from_str(&version_json)?;
+    let version_json = VersionDetails::read(&instance_dir.join("details.json"))?;
 
     let game_version = version_json.id;
 
+    send_progress(FabricInstallProgress::DownloadingJson);
     let json_path = instance_dir.join("fabric.json");
     let json_url = format!("v2/versions/loader/{game_version}/{loader_version}/profile/json");
     let json = download_file_to_string(&client, &json_url).await?;
@@ -62,8 +226,14 @@ pub async fn install(loader_version: &str, instance_name: &str) -> Result<(), Fa
 
     let json: FabricJSON = serde_json::from_str(&json)?;
 
-    for library in json.libraries {
-        println!("[info] Downloading fabric library {}", library.name);
+    let num_libraries = json.libraries.len();
+    for (library_num, library) in json.libraries.iter().enumerate() {
+        info!("Downloading fabric library {}", library.name);
+        send_progress(FabricInstallProgress::DownloadingLibrary {
+            progress: library_num,
+            out_of: num_libraries,
+            name: library.name.clone(),
+        });
 
         let path = libraries_dir.join(library.get_path());
         let url = format!("{}{}", library.url, get_url(&library.name));
@@ -76,21 +246,165 @@ pub async fn install(loader_version: &str, instance_name: &str) -> Result<(), Fa
     }
 
     let config_path = instance_dir.join("config.json");
-    let config = std::fs::read_to_string(&config_path).map_err(io_err!(config_path))?;
-    let mut config: InstanceConfigJson = serde_json::from_str(&config)?;
+    let mut config = InstanceConfigJson::read(&config_path)?;
 
     config.mod_type = "Fabric".to_owned();
+    config.save(&config_path)?;
+
+    send_progress(FabricInstallProgress::Done);
+    events::emit(Event::LoaderInstalled {
+        instance: instance_name.to_owned(),
+        loader: "Fabric".to_owned(),
+        version: loader_version.to_owned(),
+    });
+    Ok(())
+}
+
+pub async fn install_wrapped(
+    loader_version: String,
+    instance_name: String,
+    progress: Option<ProgressSender<FabricInstallProgress>>,
+) -> Result<(), String> {
+    install(&loader_version, &instance_name, progress.as_ref())
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// The installed loader version, from the instance's `fabric.json`.
+pub fn installed_version(instance_name: &str) -> Option<String> {
+    const LOADER_LIBRARY: &str = "net.fabricmc:fabric-loader:";
+
+    let json = read_fabric_json(instance_name).ok()?;
+    json.libraries
+        .into_iter()
+        .find_map(|n| n.name.strip_prefix(LOADER_LIBRARY).map(str::to_owned))
+}
+
+fn read_fabric_json(instance_name: &str) -> Result<FabricJSON, FabricInstallError> {
+    let json_path = file_utils::get_launcher_dir()?
+        .join("instances")
+        .join(instance_name)
+        .join("fabric.json");
+    let json = std::fs::read_to_string(&json_path).map_err(io_err!(json_path))?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// A newer stable loader than the installed one, if there is one.
+pub async fn check_for_update(instance_name: &str) -> Result<Option<String>, FabricInstallError> {
+    let Some(installed) = installed_version(instance_name) else {
+        return Ok(None);
+    };
+    let list = get_list_of_versions_for_instance(instance_name).await?;
+    let position = |version: &str| list.versions.iter().position(|n| n.version == version);
+
+    let Some(latest) = list.versions.iter().find(|n| n.stable) else {
+        return Ok(None);
+    };
+    // Unknown versions (like ones installed by hand) are left alone.
+    let is_newer = matches!(
+        (position(&latest.version), position(&installed)),
+        (Some(latest), Some(installed)) if latest < installed
+    );
+    Ok(is_newer.then(|| latest.version.clone()))
+}
+
+/// Checks each instance for a loader update, returning the
+/// instances that have one, with the newer version.
+///
+/// Failed checks (usually from being offline) are only logged.
+pub async fn check_for_updates_wrapped(instance_names: Vec<String>) -> Vec<(String, String)> {
+    let mut updates = Vec::new();
+    for instance_name in instance_names {
+        match check_for_update(&instance_name).await {
+            Ok(Some(version)) => updates.push((instance_name, version)),
+            Ok(None) => {}
+            Err(err) => error!("Could not check {instance_name} for a Fabric update: {err}"),
+        }
+    }
+    updates
+}
+
+/// Switches the instance to another loader version, removing
+/// the libraries that only the old version needed.
+///
+/// The old version stays installed if this fails.
+pub async fn update(
+    loader_version: &str,
+    instance_name: &str,
+    progress: Option<&ProgressSender<FabricInstallProgress>>,
+) -> Result<(), FabricInstallError> {
+    let old_json = read_fabric_json(instance_name)?;
+    snapshots::take_or_log(instance_name, SnapshotReason::LoaderUpdate);
+    install_loader(loader_version, instance_name, progress).await?;
+    let new_json = read_fabric_json(instance_name)?;
+
+    let libraries_dir = file_utils::get_launcher_dir()?
+        .join("instances")
+        .join(instance_name)
+        .join("libraries");
+    for library in old_json.libraries {
+        if new_json.libraries.iter().any(|n| n.name == library.name) {
+            continue;
+        }
+        let path = libraries_dir.join(library.get_path());
+        if path.exists() {
+            std::fs::remove_file(&path).map_err(io_err!(path))?;
+        }
+    }
+    Ok(())
+}
+
+pub async fn update_wrapped(
+    loader_version: String,
+    instance_name: String,
+    progress: Option<ProgressSender<FabricInstallProgress>>,
+) -> Result<(), String> {
+    update(&loader_version, &instance_name, progress.as_ref())
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Removes Fabric from an instance, turning it back into a vanilla instance.
+///
+/// Mods in the `mods` folder are kept.
+pub async fn uninstall(instance_name: &str) -> Result<(), FabricInstallError> {
+    snapshots::take_or_log(instance_name, SnapshotReason::LoaderUninstall);
+    remove_loader(instance_name)
+}
+
+/// [`uninstall`], without the snapshot.
+pub(crate) fn remove_loader(instance_name: &str) -> Result<(), FabricInstallError> {
+    let launcher_dir = file_utils::get_launcher_dir()?;
+    let instance_dir = launcher_dir.join("instances").join(instance_name);
+    let libraries_dir = instance_dir.join("libraries");
 
-    let config = serde_json::to_string(&config)?;
-    std::fs::write(&config_path, config).map_err(io_err!(config_path))?;
+    let json_path = instance_dir.join("fabric.json");
+    if json_path.exists() {
+        let json = std::fs::read_to_string(&json_path).map_err(io_err!(json_path))?;
+        let json: FabricJSON = serde_json::from_str(&json)?;
+
+        for library in json.libraries {
+            let path = libraries_dir.join(library.get_path());
+            if path.exists() {
+                std::fs::remove_file(&path).map_err(io_err!(path))?;
+            }
+        }
+        std::fs::remove_file(&json_path).map_err(io_err!(json_path))?;
+    }
+
+    let config_path = instance_dir.join("config.json");
+    let mut config = InstanceConfigJson::read(&config_path)?;
+
+    config.mod_type = "Vanilla".to_owned();
+    config.save(&config_path)?;
 
     Ok(())
 }
 
-pub async fn install_wrapped(loader_version: String, instance_name: String) -> Result<(), String> {
-    install(&loader_version, &instance_name)
+pub async fn uninstall_wrapped(instance_name: String) -> Result<(), String> {
+    uninstall(&instance_name)
         .await
-        .map_err(|err| err.to_string())
+        .map_err(|err| err.to_string_with_code())
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -106,6 +420,7 @@ pub struct FabricVersion {
 pub enum FabricInstallError {
     Io(IoError),
     Json(serde_json::Error),
+    JsonFile(JsonFileError),
     RequestError(RequestError),
 }
 
@@ -121,6 +436,12 @@ impl From<serde_json::Error> for FabricInstallError {
     }
 }
 
+impl From<JsonFileError> for FabricInstallError {
+    fn from(value: JsonFileError) -> Self {
+        Self::JsonFile(value)
+    }
+}
+
 impl From<RequestError> for FabricInstallError {
     fn from(value: RequestError) -> Self {
         Self::RequestError(value)
@@ -133,6 +454,7 @@ impl Display for FabricInstallError {
             // Look, I'm not the best at programming.
             FabricInstallError::Io(err) => write!(f, "error installing fabric: {err}"),
             FabricInstallError::Json(err) => write!(f, "error installing fabric: {err}"),
+            FabricInstallError::JsonFile(err) => write!(f, "error installing fabric: {err}"),
             FabricInstallError::RequestError(err) => write!(f, "error installing fabric: {err}"),
         }
     }
@@ -1,4 +1,9 @@
 pub mod fabric;
+pub mod mod_list;
+pub mod modrinth;
+pub mod modrinth_search;
+pub mod plugins;
+pub mod server_pack;
 
 pub enum CoreMod {
     None,
@@ -0,0 +1,331 @@
+//! Lists of an instance's mods, as text, markdown or JSON,
+//! for bug reports or sharing what's in a pack without
+//! sending the jar files, and installing the mods of a list.
+
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::{
+    download_queue::{self, JobKind},
+    error::HasErrorCode,
+    file_utils, io_err,
+    json_structs::{
+        json_instance_config::{InstanceConfigJson, ModSource},
+        json_version::VersionDetails,
+    },
+    progress::ProgressSender,
+};
+
+use super::modrinth::{self, InstalledMod, ModInstallError, ModInstallProgress, MODRINTH_URL};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModListFormat {
+    Text,
+    Markdown,
+    Json,
+}
+
+/// The JSON format of a mod list.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ModList {
+    pub minecraft_version: String,
+    pub loader: String,
+    pub mods: Vec<ModListEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ModListEntry {
+    pub name: String,
+    pub file_name: String,
+    /// The rest are `None` for mods added to
+    /// the `mods` folder by hand.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<ModSource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// A project from Modrinth's `/projects` endpoint.
+#[derive(Deserialize)]
+struct ProjectName {
+    id: String,
+    slug: String,
+    title: String,
+}
+
+/// Lists the mods in the instance's `mods` folder.
+///
+/// Names of mods installed by the launcher come from
+/// Modrinth. If it can't be reached, their file names
+/// are used instead.
+pub async fn get_mod_list(instance_name: &str) -> Result<ModList, ModInstallError> {
+    let instance_dir = file_utils::get_launcher_dir()?
+        .join("instances")
+        .join(instance_name);
+    let version_json = VersionDetails::read(&instance_dir.join("details.json"))?;
+    let config = InstanceConfigJson::read(&instance_dir.join("config.json"))?;
+
+    let mods_dir = config.get_game_dir(&instance_dir).join("mods");
+    let mut file_names: Vec<String> = if mods_dir.is_dir() {
+        std::fs::read_dir(&mods_dir)
+            .map_err(io_err!(mods_dir))?
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().is_file())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    file_names.sort();
+
+    let project_ids: Vec<&str> = config
+        .mods
+        .iter()
+        .map(|managed| managed.project_id.as_str())
+        .collect();
+    let projects = match get_project_names(&project_ids).await {
+        Ok(projects) => projects,
+        Err(err) => {
+            error!("Could not get the names of the mods: {err}");
+            Vec::new()
+        }
+    };
+
+    let mods = file_names
+        .into_iter()
+        .map(|file_name| {
+            let Some(managed) = config.managed_mod(&file_name) else {
+                return ModListEntry {
+                    name: file_name.clone(),
+                    file_name,
+                    version: None,
+                    source: None,
+                    project_id: None,
+                    url: None,
+                };
+            };
+            let project = projects.iter().find(|n| n.id == managed.project_id);
+            ModListEntry {
+                name: project.map_or_else(|| file_name.clone(), |n| n.title.clone()),
+                version: Some(managed.version.clone()),
+                source: Some(managed.source),
+                project_id: Some(managed.project_id.clone()),
+                url: Some(format!(
+                    "https://modrinth.com/mod/{}",
+                    project.map_or(&managed.project_id, |n| &n.slug)
+                )),
+                file_name,
+            }
+        })
+        .collect();
+
+    Ok(ModList {
+        minecraft_version: version_json.id,
+        loader: config.mod_type,
+        mods,
+    })
+}
+
+async fn get_project_names(project_ids: &[&str]) -> Result<Vec<ProjectName>, ModInstallError> {
+    if project_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let url = reqwest::Url::parse_with_params(
+        &format!("{MODRINTH_URL}/projects"),
+        [("ids", serde_json::to_string(project_ids)?)],
+    )
+    .map_err(|_| ModInstallError::ModNotFound(project_ids.join(", ")))?;
+    let client = file_utils::create_client();
+    let projects = file_utils::download_file_to_string(&client, url.as_str()).await?;
+    Ok(serde_json::from_str(&projects)?)
+}
+
+impl ModList {
+    pub fn format(&self, format: ModListFormat) -> Result<String, serde_json::Error> {
+        let heading = format!("Minecraft {}, {}", self.minecraft_version, self.loader);
+        let mut out = String::new();
+        match format {
+            ModListFormat::Json => return serde_json::to_string_pretty(self),
+            ModListFormat::Text => {
+                _ = writeln!(out, "{heading}");
+                for entry in &self.mods {
+                    _ = write!(out, "{}", entry.name);
+                    if let Some(version) = &entry.version {
+                        _ = write!(out, " {version}");
+                    }
+                    if let Some(url) = &entry.url {
+                        _ = write!(out, " ({url})");
+                    }
+                    out.push('\n');
+                }
+            }
+            ModListFormat::Markdown => {
+                _ = writeln!(out, "**{heading}**\n");
+                for entry in &self.mods {
+                    match &entry.url {
+                        Some(url) => _ = write!(out, "- [{}]({url})", entry.name),
+                        None => _ = write!(out, "- {}", entry.name),
+                    }
+                    if let Some(version) = &entry.version {
+                        _ = write!(out, " `{version}`");
+                    }
+                    out.push('\n');
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+pub async fn export_wrapped(
+    instance_name: String,
+    format: ModListFormat,
+) -> Result<String, String> {
+    async {
+        let list = get_mod_list(&instance_name).await?;
+        Ok::<_, ModInstallError>(list.format(format)?)
+    }
+    .await
+    .map_err(|err| err.to_string_with_code())
+}
+
+/// What [`import`] installed, and the entries it couldn't.
+#[derive(Debug, Clone)]
+pub struct ImportResult {
+    pub installed: Vec<InstalledMod>,
+    /// Each entry that wasn't installed, with why.
+    pub unmatched: Vec<(String, String)>,
+}
+
+/// Gets the Modrinth projects to install from a mod list JSON,
+/// or from slugs, IDs or Modrinth links separated by spaces,
+/// commas or new lines.
+///
+/// Entries of a JSON list that aren't from Modrinth
+/// are returned separately, as they can't be installed.
+pub fn parse_import(text: &str) -> Result<(Vec<String>, Vec<String>), serde_json::Error> {
+    let text = text.trim();
+    if text.starts_with('{') {
+        let list: ModList = serde_json::from_str(text)?;
+        let (projects, others): (Vec<_>, Vec<_>) = list
+            .mods
+            .into_iter()
+            .partition(|entry| entry.source == Some(ModSource::Modrinth));
+        return Ok((
+            projects
+                .into_iter()
+                .filter_map(|entry| entry.project_id)
+                .collect(),
+            others.into_iter().map(|entry| entry.name).collect(),
+        ));
+    }
+
+    let projects = text
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .map(|entry| {
+            entry
+                .trim_start_matches("https://")
+                .trim_start_matches("modrinth.com/mod/")
+                .trim_end_matches('/')
+        })
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_owned)
+        .collect();
+    Ok((projects, Vec::new()))
+}
+
+/// Installs the mods in a mod list (see [`parse_import`])
+/// into the instance, picking the versions for its
+/// Minecraft version and mod loader.
+///
+/// Mods that can't be found, or have no version for the
+/// instance, are skipped and returned in the result.
+pub async fn import(
+    instance_name: &str,
+    text: &str,
+    progress: Option<&ProgressSender<ModInstallProgress>>,
+) -> Result<ImportResult, ModInstallError> {
+    let (projects, others) = parse_import(text)?;
+    let mut result = ImportResult {
+        installed: Vec::new(),
+        unmatched: others
+            .into_iter()
+            .map(|name| (name, "not from Modrinth".to_owned()))
+            .collect(),
+    };
+
+    download_queue::run(JobKind::Mods, instance_name.to_owned(), async {
+        for project in projects {
+            match modrinth::install(instance_name, std::slice::from_ref(&project), progress).await {
+                Ok(installed) => result.installed.extend(installed),
+                // Nothing else can be installed either.
+                Err(err @ ModInstallError::NoModLoader) => return Err(err),
+                Err(err) => result.unmatched.push((project, err.to_string())),
+            }
+        }
+        Ok(())
+    })
+    .await?;
+    Ok(result)
+}
+
+pub async fn import_wrapped(
+    instance_name: String,
+    text: String,
+    progress: Option<ProgressSender<ModInstallProgress>>,
+) -> Result<ImportResult, String> {
+    import(&instance_name, &text, progress.as_ref())
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_links_managed_mods() {
+        let list = ModList {
+            minecraft_version: "1.20.1".to_owned(),
+            loader: "Fabric".to_owned(),
+            mods: vec![
+                ModListEntry {
+                    name: "Sodium".to_owned(),
+                    file_name: "sodium-0.5.3.jar".to_owned(),
+                    version: Some("mc1.20.1-0.5.3".to_owned()),
+                    source: Some(ModSource::Modrinth),
+                    project_id: Some("AANobbMI".to_owned()),
+                    url: Some("https://modrinth.com/mod/sodium".to_owned()),
+                },
+                ModListEntry {
+                    name: "custom.jar".to_owned(),
+                    file_name: "custom.jar".to_owned(),
+                    version: None,
+                    source: None,
+                    project_id: None,
+                    url: None,
+                },
+            ],
+        };
+        assert_eq!(
+            list.format(ModListFormat::Markdown).unwrap(),
+            "**Minecraft 1.20.1, Fabric**\n\n\
+             - [Sodium](https://modrinth.com/mod/sodium) `mc1.20.1-0.5.3`\n\
+             - custom.jar\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_import_accepts_slugs_and_links() {
+        let (projects, others) =
+            parse_import("sodium, https://modrinth.com/mod/lithium/\n  iris").unwrap();
+        assert_eq!(projects, ["sodium", "lithium", "iris"]);
+        assert!(others.is_empty());
+    }
+}
@@ -0,0 +1,349 @@
+//! Installing mods from [Modrinth](https://modrinth.com).
+
+use std::{collections::HashSet, fmt::Display, path::PathBuf};
+
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::{
+    download_queue::{self, JobKind},
+    error::{HasErrorCode, IoError},
+    file_utils::{self, RequestError},
+    io_err,
+    json_structs::{
+        json_instance_config::{InstanceConfigJson, ManagedMod, ModSource},
+        json_version::VersionDetails,
+        JsonFileError,
+    },
+    progress::ProgressSender,
+};
+
+pub(super) const MODRINTH_URL: &str = "https://api.modrinth.com/v2";
+
+/// A version of a mod, from Modrinth's `/project/{id}/version` endpoint.
+#[derive(Deserialize)]
+pub(super) struct ModrinthVersion {
+    pub project_id: String,
+    pub version_number: String,
+    pub files: Vec<ModrinthFile>,
+    pub dependencies: Vec<ModrinthDependency>,
+}
+
+impl ModrinthVersion {
+    pub fn primary_file(&self) -> Option<&ModrinthFile> {
+        self.files
+            .iter()
+            .find(|file| file.primary)
+            .or(self.files.first())
+    }
+
+    pub fn required_dependencies(&self) -> impl Iterator<Item = &str> {
+        self.dependencies
+            .iter()
+            .filter(|dependency| dependency.dependency_type == "required")
+            .filter_map(|dependency| dependency.project_id.as_deref())
+    }
+}
+
+#[derive(Deserialize)]
+pub(super) struct ModrinthFile {
+    pub url: String,
+    pub filename: String,
+    primary: bool,
+}
+
+#[derive(Deserialize)]
+pub(super) struct ModrinthDependency {
+    project_id: Option<String>,
+    dependency_type: String,
+}
+
+/// Progress of a mod installation, sent through
+/// the `progress` channel of [`install_mods`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum ModInstallProgress {
+    Resolving { name: String },
+    Downloading { file_name: String },
+    Done,
+}
+
+impl Display for ModInstallProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModInstallProgress::Resolving { name } => write!(f, "Finding a version of {name}."),
+            ModInstallProgress::Downloading { file_name } => write!(f, "Downloading {file_name}."),
+            ModInstallProgress::Done => write!(f, "Done."),
+        }
+    }
+}
+
+/// A mod that was downloaded into the `mods` folder.
+#[derive(Debug, Clone, Serialize)]
+pub struct InstalledMod {
+    pub project_id: String,
+    pub version: String,
+    pub file_name: String,
+}
+
+/// Downloads mods from Modrinth into an instance, picking the
+/// latest version of each that works with the instance's
+/// Minecraft version and mod loader.
+///
+/// `mods` are project slugs (like `sodium`) or IDs. Mods they
+/// require are installed too. Mods that are already in the
+/// `mods` folder aren't downloaded again.
+pub async fn install_mods(
+    instance_name: &str,
+    mods: &[String],
+    progress: Option<&ProgressSender<ModInstallProgress>>,
+) -> Result<Vec<InstalledMod>, ModInstallError> {
+    download_queue::run(
+        JobKind::Mods,
+        instance_name.to_owned(),
+        install(instance_name, mods, progress),
+    )
+    .await
+}
+
+pub(super) async fn install(
+    instance_name: &str,
+    mods: &[String],
+    progress: Option<&ProgressSender<ModInstallProgress>>,
+) -> Result<Vec<InstalledMod>, ModInstallError> {
+    let send_progress = |message: ModInstallProgress| {
+        if let Some(progress) = progress {
+            _ = progress.send(message);
+        }
+    };
+
+    let client = file_utils::create_client();
+    let instance_dir = file_utils::get_launcher_dir()?
+        .join("instances")
+        .join(instance_name);
+
+    let version_json = VersionDetails::read(&instance_dir.join("details.json"))?;
+
+    let config_path = instance_dir.join("config.json");
+    let mut config = InstanceConfigJson::read(&config_path)?;
+    if config.mod_type == "Vanilla" {
+        return Err(ModInstallError::NoModLoader);
+    }
+    let loader = config.mod_type.to_lowercase();
+
+    let mods_dir = config.get_game_dir(&instance_dir).join("mods");
+    std::fs::create_dir_all(&mods_dir).map_err(io_err!(mods_dir))?;
+
+    let mut queue: Vec<String> = mods.to_vec();
+    let mut seen = HashSet::new();
+    let mut installed = Vec::new();
+
+    while let Some(project) = queue.pop() {
+        send_progress(ModInstallProgress::Resolving {
+            name: project.clone(),
+        });
+        let version = get_latest_version(&client, &project, &version_json.id, &[&loader]).await?;
+        // Mods can be asked for by slug and then again
+        // by ID as a dependency, so check the ID here.
+        if !seen.insert(version.project_id.clone()) {
+            continue;
+        }
+
+        queue.extend(
+            version
+                .required_dependencies()
+                .filter(|id| !seen.contains(*id))
+                .map(ToOwned::to_owned),
+        );
+
+        let Some(file) = version.primary_file() else {
+            continue;
+        };
+
+        let path = get_mod_path(&mods_dir, &file.filename)?;
+        // File names have the version in them, so
+        // an existing file is the same version.
+        let bytes = if path.exists() {
+            std::fs::read(&path).map_err(io_err!(path))?
+        } else {
+            send_progress(ModInstallProgress::Downloading {
+                file_name: file.filename.clone(),
+            });
+            info!("Downloading mod {}", file.filename);
+            let bytes = file_utils::download_file_to_bytes(&client, &file.url).await?;
+            std::fs::write(&path, &bytes).map_err(io_err!(path))?;
+            bytes
+        };
+
+        config.record_mod(ManagedMod {
+            source: ModSource::Modrinth,
+            project_id: version.project_id.clone(),
+            version: version.version_number.clone(),
+            file_name: file.filename.clone(),
+            sha1: file_utils::sha1_hex(&bytes),
+            enabled: true,
+            dependencies: version
+                .required_dependencies()
+                .map(ToOwned::to_owned)
+                .collect(),
+        });
+
+        installed.push(InstalledMod {
+            project_id: version.project_id.clone(),
+            version: version.version_number.clone(),
+            file_name: file.filename.clone(),
+        });
+    }
+
+    config.save(&config_path)?;
+
+    send_progress(ModInstallProgress::Done);
+    Ok(installed)
+}
+
+pub async fn install_mods_wrapped(
+    instance_name: String,
+    mods: Vec<String>,
+    progress: Option<ProgressSender<ModInstallProgress>>,
+) -> Result<Vec<InstalledMod>, String> {
+    install_mods(&instance_name, &mods, progress.as_ref())
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Gets the newest version of `project` for any of `loaders`.
+pub(super) async fn get_latest_version(
+    client: &Client,
+    project: &str,
+    game_version: &str,
+    loaders: &[&str],
+) -> Result<ModrinthVersion, ModInstallError> {
+    let url = Url::parse_with_params(
+        &format!("{MODRINTH_URL}/project/{project}/version"),
+        [
+            ("loaders", serde_json::to_string(loaders)?),
+            ("game_versions", serde_json::to_string(&[game_version])?),
+        ],
+    )
+    .map_err(|_| ModInstallError::ModNotFound(project.to_owned()))?;
+
+    let versions = match file_utils::download_file_to_string(client, url.as_str()).await {
+        Ok(versions) => versions,
+        Err(RequestError::DownloadError { code, .. }) if code == reqwest::StatusCode::NOT_FOUND => {
+            return Err(ModInstallError::ModNotFound(project.to_owned()))
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let versions: Vec<ModrinthVersion> = serde_json::from_str(&versions)?;
+
+    // Modrinth lists the newest version first.
+    versions
+        .into_iter()
+        .next()
+        .ok_or_else(|| ModInstallError::NoCompatibleVersion {
+            project: project.to_owned(),
+            game_version: game_version.to_owned(),
+            loader: loaders.join("/"),
+        })
+}
+
+/// Makes sure a file name from the internet can't
+/// write somewhere outside the `mods` folder.
+pub(super) fn get_mod_path(
+    mods_dir: &std::path::Path,
+    file_name: &str,
+) -> Result<PathBuf, ModInstallError> {
+    let path = mods_dir.join(file_name);
+    if path.parent() == Some(mods_dir) {
+        Ok(path)
+    } else {
+        Err(ModInstallError::InvalidFileName(file_name.to_owned()))
+    }
+}
+
+#[derive(Debug)]
+pub enum ModInstallError {
+    Io(IoError),
+    Json(serde_json::Error),
+    JsonFile(JsonFileError),
+    RequestError(RequestError),
+    NoModLoader,
+    NoPluginSupport,
+    ModNotFound(String),
+    HangarProjectNotFound(String),
+    NoCompatibleVersion {
+        project: String,
+        game_version: String,
+        loader: String,
+    },
+    InvalidFileName(String),
+}
+
+impl From<IoError> for ModInstallError {
+    fn from(value: IoError) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<JsonFileError> for ModInstallError {
+    fn from(value: JsonFileError) -> Self {
+        Self::JsonFile(value)
+    }
+}
+
+impl From<serde_json::Error> for ModInstallError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl From<RequestError> for ModInstallError {
+    fn from(value: RequestError) -> Self {
+        Self::RequestError(value)
+    }
+}
+
+impl Display for ModInstallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModInstallError::Io(err) => write!(f, "error installing mods: {err}"),
+            ModInstallError::Json(err) => write!(f, "error installing mods: {err}"),
+            ModInstallError::JsonFile(err) => write!(f, "error installing mods: {err}"),
+            ModInstallError::RequestError(err) => write!(f, "error installing mods: {err}"),
+            ModInstallError::NoModLoader => {
+                write!(
+                    f,
+                    "error installing mods: install a mod loader (like Fabric) first"
+                )
+            }
+            ModInstallError::NoPluginSupport => write!(
+                f,
+                "error installing plugins: vanilla servers can't run plugins, use Paper or Purpur"
+            ),
+            ModInstallError::HangarProjectNotFound(project) => {
+                write!(
+                    f,
+                    "error installing plugins: {project} was not found on Hangar"
+                )
+            }
+            ModInstallError::ModNotFound(project) => {
+                write!(
+                    f,
+                    "error installing mods: {project} was not found on Modrinth"
+                )
+            }
+            ModInstallError::NoCompatibleVersion {
+                project,
+                game_version,
+                loader,
+            } => write!(
+                f,
+                "error installing mods: {project} has no version for {loader} on {game_version}"
+            ),
+            ModInstallError::InvalidFileName(name) => {
+                write!(f, "error installing mods: invalid file name {name}")
+            }
+        }
+    }
+}
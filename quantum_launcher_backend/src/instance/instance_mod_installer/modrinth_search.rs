@@ -0,0 +1,196 @@
+//! Searching [Modrinth](https://modrinth.com) for mods, and getting
+//! a project's details (description, gallery and stats), for the
+//! mod browser.
+
+use std::path::Path;
+
+use reqwest::Url;
+use serde::Deserialize;
+use tracing::error;
+
+use crate::{
+    error::{HasErrorCode, IoError},
+    file_utils, io_err,
+    json_structs::{json_instance_config::InstanceConfigJson, json_version::VersionDetails},
+};
+
+use super::modrinth::{ModInstallError, MODRINTH_URL};
+
+/// How many results are asked for at once.
+/// More are loaded as the user scrolls down.
+pub const SEARCH_PAGE_SIZE: usize = 20;
+
+/// A mod in the search results.
+#[derive(Deserialize, Debug, Clone)]
+pub struct SearchHit {
+    pub project_id: String,
+    pub slug: String,
+    pub title: String,
+    /// A one-line summary.
+    pub description: String,
+    pub author: String,
+    pub downloads: u64,
+    pub follows: u64,
+    pub icon_url: Option<String>,
+}
+
+/// One page of search results.
+#[derive(Deserialize, Debug, Clone)]
+pub struct SearchPage {
+    pub hits: Vec<SearchHit>,
+    /// How many results came before this page.
+    pub offset: usize,
+    /// How many results there are across all pages.
+    pub total_hits: usize,
+}
+
+impl SearchPage {
+    /// Whether there are more results after this page.
+    pub fn has_more(&self) -> bool {
+        self.offset + self.hits.len() < self.total_hits
+    }
+}
+
+/// Everything about a mod that the browser shows.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ProjectDetails {
+    pub id: String,
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    /// The full description, in Markdown (with some HTML).
+    pub body: String,
+    pub downloads: u64,
+    pub followers: u64,
+    /// When it was last updated, like `2024-01-05T18:12:32.045Z`.
+    pub updated: String,
+    #[serde(default)]
+    pub categories: Vec<String>,
+    pub license: Option<License>,
+    pub icon_url: Option<String>,
+    #[serde(default)]
+    pub gallery: Vec<GalleryImage>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct License {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GalleryImage {
+    pub url: String,
+    pub featured: bool,
+    pub title: Option<String>,
+}
+
+impl ProjectDetails {
+    /// The project's page on the Modrinth website.
+    pub fn page_url(&self) -> String {
+        format!("https://modrinth.com/mod/{}", self.slug)
+    }
+}
+
+/// Searches for mods that work with the instance's
+/// Minecraft version and mod loader, starting
+/// from the `offset`th result.
+pub async fn search(
+    instance_name: &str,
+    query: &str,
+    offset: usize,
+) -> Result<SearchPage, ModInstallError> {
+    let instance_dir = file_utils::get_launcher_dir()?
+        .join("instances")
+        .join(instance_name);
+    let version_json = VersionDetails::read(&instance_dir.join("details.json"))?;
+    let config = InstanceConfigJson::read(&instance_dir.join("config.json"))?;
+    if config.mod_type == "Vanilla" {
+        return Err(ModInstallError::NoModLoader);
+    }
+
+    let facets = [
+        ["project_type:mod".to_owned()],
+        [format!("versions:{}", version_json.id)],
+        [format!("categories:{}", config.mod_type.to_lowercase())],
+    ];
+    let url = Url::parse_with_params(
+        &format!("{MODRINTH_URL}/search"),
+        [
+            ("query", query.to_owned()),
+            ("facets", serde_json::to_string(&facets)?),
+            ("limit", SEARCH_PAGE_SIZE.to_string()),
+            ("offset", offset.to_string()),
+        ],
+    )
+    .map_err(|_| ModInstallError::ModNotFound(query.to_owned()))?;
+
+    let client = file_utils::create_client();
+    let results = file_utils::download_file_to_string(&client, url.as_str()).await?;
+    Ok(serde_json::from_str(&results)?)
+}
+
+pub async fn search_wrapped(
+    instance_name: String,
+    query: String,
+    offset: usize,
+) -> Result<SearchPage, String> {
+    search(&instance_name, &query, offset)
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Gets a mod's details from its slug or ID.
+pub async fn get_project(project: &str) -> Result<ProjectDetails, ModInstallError> {
+    let client = file_utils::create_client();
+    let details =
+        file_utils::download_file_to_string(&client, &format!("{MODRINTH_URL}/project/{project}"))
+            .await?;
+    Ok(serde_json::from_str(&details)?)
+}
+
+pub async fn get_project_wrapped(project: String) -> Result<ProjectDetails, String> {
+    get_project(&project)
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Downloads an icon or gallery image, for showing in the GUI.
+pub async fn download_image_wrapped(url: String) -> Result<Vec<u8>, String> {
+    let client = file_utils::create_client();
+    file_utils::download_file_to_bytes(&client, &url)
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Gets a mod's icon, downloading it only if it isn't in
+/// `QuantumLauncher/cache/icons` yet, so that scrolling back
+/// through the same results doesn't download them again.
+pub async fn get_icon(url: &str) -> Result<Vec<u8>, ModInstallError> {
+    let cache_path = file_utils::get_cache_dir()?
+        .join("icons")
+        .join(file_utils::sha1_hex(url.as_bytes()));
+    if let Ok(bytes) = std::fs::read(&cache_path) {
+        return Ok(bytes);
+    }
+
+    let client = file_utils::create_client();
+    let bytes = file_utils::download_file_to_bytes(&client, url).await?;
+    if let Err(err) = save_icon(&cache_path, &bytes) {
+        error!("Could not save mod icon to cache: {err}");
+    }
+    Ok(bytes)
+}
+
+pub async fn get_icon_wrapped(url: String) -> Result<Vec<u8>, String> {
+    get_icon(&url)
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+fn save_icon(path: &Path, bytes: &[u8]) -> Result<(), IoError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(io_err!(parent))?;
+    }
+    std::fs::write(path, bytes).map_err(io_err!(path))
+}
@@ -0,0 +1,264 @@
+//! Installing plugins into Paper and Purpur servers, from
+//! [Modrinth](https://modrinth.com) or [Hangar](https://hangar.papermc.io).
+
+use std::{collections::HashMap, collections::HashSet, fmt::Display};
+
+use reqwest::{Client, Url};
+use serde::Deserialize;
+use tracing::info;
+
+use crate::{
+    error::HasErrorCode,
+    file_utils::{self, RequestError},
+    instance::server_software::ServerSoftware,
+    io_err,
+    json_structs::{json_instance_config::InstanceConfigJson, json_version::VersionDetails},
+    progress::ProgressSender,
+};
+
+use super::modrinth::{self, InstalledMod, ModInstallError, ModInstallProgress};
+
+const HANGAR_URL: &str = "https://hangar.papermc.io/api/v1";
+
+/// Where plugins are downloaded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PluginSource {
+    #[default]
+    Modrinth,
+    /// PaperMC's own plugin site.
+    Hangar,
+}
+
+impl PluginSource {
+    pub const ALL: [Self; 2] = [Self::Modrinth, Self::Hangar];
+}
+
+impl Display for PluginSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PluginSource::Modrinth => write!(f, "Modrinth"),
+            PluginSource::Hangar => write!(f, "Hangar"),
+        }
+    }
+}
+
+/// A page of versions from Hangar's `/projects/{slug}/versions` endpoint.
+#[derive(Deserialize)]
+struct HangarVersions {
+    result: Vec<HangarVersion>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HangarVersion {
+    name: String,
+    downloads: HashMap<String, HangarDownload>,
+    #[serde(default)]
+    plugin_dependencies: HashMap<String, Vec<HangarDependency>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HangarDownload {
+    file_info: Option<HangarFileInfo>,
+    /// `None` for plugins hosted on another site.
+    download_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct HangarFileInfo {
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HangarDependency {
+    name: String,
+    required: bool,
+    /// Set for dependencies that aren't on Hangar.
+    external_url: Option<String>,
+}
+
+/// A plugin version picked for downloading.
+struct PluginFile {
+    project: String,
+    version: String,
+    file_name: String,
+    url: String,
+    dependencies: Vec<String>,
+}
+
+/// Downloads plugins into a server's `plugins` folder, picking
+/// the latest version of each that works with the server's
+/// Minecraft version and software.
+///
+/// `plugins` are project slugs (like `luckperms`). Plugins they
+/// require are installed too. Plugins that are already in the
+/// `plugins` folder aren't downloaded again.
+pub async fn install_plugins(
+    server_name: &str,
+    source: PluginSource,
+    plugins: &[String],
+    progress: Option<&ProgressSender<ModInstallProgress>>,
+) -> Result<Vec<InstalledMod>, ModInstallError> {
+    let send_progress = |message: ModInstallProgress| {
+        if let Some(progress) = progress {
+            _ = progress.send(message);
+        }
+    };
+
+    let client = file_utils::create_client();
+    let server_dir = file_utils::get_launcher_dir()?
+        .join("servers")
+        .join(server_name);
+
+    let version_json = VersionDetails::read(&server_dir.join("details.json"))?;
+
+    let config = InstanceConfigJson::read(&server_dir.join("config.json"))?;
+    let software = ServerSoftware::from_mod_type(&config.mod_type).unwrap_or_default();
+    if !software.supports_plugins() {
+        return Err(ModInstallError::NoPluginSupport);
+    }
+
+    let plugins_dir = server_dir.join("plugins");
+    std::fs::create_dir_all(&plugins_dir).map_err(io_err!(plugins_dir))?;
+
+    let mut queue: Vec<String> = plugins.to_vec();
+    let mut seen = HashSet::new();
+    let mut installed = Vec::new();
+
+    while let Some(project) = queue.pop() {
+        send_progress(ModInstallProgress::Resolving {
+            name: project.clone(),
+        });
+        let file = match source {
+            PluginSource::Modrinth => {
+                get_modrinth_file(&client, &project, &version_json.id, software).await?
+            }
+            PluginSource::Hangar => get_hangar_file(&client, &project, &version_json.id).await?,
+        };
+        if !seen.insert(file.project.clone()) {
+            continue;
+        }
+        queue.extend(
+            file.dependencies
+                .iter()
+                .filter(|dependency| !seen.contains(*dependency))
+                .cloned(),
+        );
+
+        let path = modrinth::get_mod_path(&plugins_dir, &file.file_name)?;
+        if !path.exists() {
+            send_progress(ModInstallProgress::Downloading {
+                file_name: file.file_name.clone(),
+            });
+            info!("Downloading plugin {}", file.file_name);
+            let bytes = file_utils::download_file_to_bytes(&client, &file.url).await?;
+            std::fs::write(&path, &bytes).map_err(io_err!(path))?;
+        }
+
+        installed.push(InstalledMod {
+            project_id: file.project,
+            version: file.version,
+            file_name: file.file_name,
+        });
+    }
+
+    send_progress(ModInstallProgress::Done);
+    Ok(installed)
+}
+
+pub async fn install_plugins_wrapped(
+    server_name: String,
+    source: PluginSource,
+    plugins: Vec<String>,
+    progress: Option<ProgressSender<ModInstallProgress>>,
+) -> Result<Vec<InstalledMod>, String> {
+    install_plugins(&server_name, source, &plugins, progress.as_ref())
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+async fn get_modrinth_file(
+    client: &Client,
+    project: &str,
+    game_version: &str,
+    software: ServerSoftware,
+) -> Result<PluginFile, ModInstallError> {
+    let version =
+        modrinth::get_latest_version(client, project, game_version, software.plugin_loaders())
+            .await?;
+    let file = version
+        .primary_file()
+        .ok_or_else(|| ModInstallError::ModNotFound(project.to_owned()))?;
+
+    Ok(PluginFile {
+        project: version.project_id.clone(),
+        version: version.version_number.clone(),
+        file_name: file.filename.clone(),
+        url: file.url.clone(),
+        dependencies: version
+            .required_dependencies()
+            .map(ToOwned::to_owned)
+            .collect(),
+    })
+}
+
+async fn get_hangar_file(
+    client: &Client,
+    project: &str,
+    game_version: &str,
+) -> Result<PluginFile, ModInstallError> {
+    // Hangar lists Purpur servers as Paper too.
+    const PLATFORM: &str = "PAPER";
+
+    let url = Url::parse_with_params(
+        &format!("{HANGAR_URL}/projects/{project}/versions"),
+        [
+            ("platform", PLATFORM),
+            ("platformVersion", game_version),
+            ("limit", "25"),
+        ],
+    )
+    .map_err(|_| ModInstallError::HangarProjectNotFound(project.to_owned()))?;
+
+    let versions = match file_utils::download_file_to_string(client, url.as_str()).await {
+        Ok(versions) => versions,
+        Err(RequestError::DownloadError { code, .. }) if code == reqwest::StatusCode::NOT_FOUND => {
+            return Err(ModInstallError::HangarProjectNotFound(project.to_owned()))
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let versions: HangarVersions = serde_json::from_str(&versions)?;
+
+    // Hangar lists the newest version first. Versions only
+    // hosted on other sites can't be downloaded from here.
+    versions
+        .result
+        .into_iter()
+        .find_map(|mut version| {
+            let download = version.downloads.remove(PLATFORM)?;
+            let file_name = download.file_info?.name;
+            let url = download.download_url?;
+            let dependencies = version
+                .plugin_dependencies
+                .remove(PLATFORM)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|dependency| dependency.required && dependency.external_url.is_none())
+                .map(|dependency| dependency.name)
+                .collect();
+            Some(PluginFile {
+                project: project.to_owned(),
+                version: version.name,
+                file_name,
+                url,
+                dependencies,
+            })
+        })
+        .ok_or_else(|| ModInstallError::NoCompatibleVersion {
+            project: project.to_owned(),
+            game_version: game_version.to_owned(),
+            loader: PLATFORM.to_lowercase(),
+        })
+}
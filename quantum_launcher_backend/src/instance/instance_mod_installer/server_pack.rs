@@ -0,0 +1,617 @@
+//! Creating a client instance from a server pack, the zip
+//! some modpacks only publish for servers.
+//!
+//! The pack's mods are looked up on Modrinth by hash, and
+//! the ones that don't run on clients are left out. Mods
+//! Modrinth doesn't know, or doesn't know the side of, are
+//! kept but reported so the user can check them.
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::File,
+    io::{Cursor, Read},
+    path::{Component, Path, PathBuf},
+};
+
+use reqwest::header::CONTENT_TYPE;
+use serde::Deserialize;
+use tracing::{error, info};
+use zip::{result::ZipError, ZipArchive};
+
+use crate::{
+    cancel::CancelToken,
+    download::{progress::DownloadProgress, DownloadError},
+    error::{HasErrorCode, IoError, LauncherError},
+    file_utils::{self, RequestError},
+    instance::{
+        instance_create,
+        instance_launch::{get_game_dir, get_instance_dir},
+        instance_list::delete_instance,
+    },
+    io_err,
+    json_structs::{
+        json_instance_config::{InstanceConfigJson, ManagedMod, ModSource},
+        json_manifest::VersionSource,
+        JsonFileError,
+    },
+    progress::ProgressSender,
+};
+
+use super::{
+    fabric::{self, FabricInstallError},
+    modrinth::{ModrinthVersion, MODRINTH_URL},
+};
+
+/// Folders of the pack copied into the instance,
+/// besides `mods`.
+const CONFIG_DIRS: &[&str] = &["config", "defaultconfigs"];
+
+/// A mod jar in the pack.
+struct PackMod {
+    /// Its path in the zip.
+    entry: String,
+    file_name: String,
+    sha1: String,
+}
+
+/// What was found in a server pack zip.
+struct ServerPack {
+    /// The folder in the zip with the server's files,
+    /// since packs are often zipped with a folder inside.
+    root: String,
+    minecraft_version: Option<String>,
+    loader: Option<String>,
+    mods: Vec<PackMod>,
+    /// Paths in the zip of files in [`CONFIG_DIRS`].
+    configs: Vec<String>,
+}
+
+/// A project from Modrinth's `/projects` endpoint.
+#[derive(Deserialize)]
+struct ProjectSides {
+    id: String,
+    /// `required`, `optional`, `unsupported` or `unknown`.
+    client_side: String,
+}
+
+/// What [`create_from_server_pack`] did with the pack's mods.
+#[derive(Debug, Clone)]
+pub struct ServerPackImport {
+    pub minecraft_version: String,
+    pub loader: String,
+    /// Mods that only run on servers, so were left out.
+    pub skipped: Vec<String>,
+    /// Mods that were kept, but might not belong on
+    /// a client, with why.
+    pub review: Vec<(String, String)>,
+}
+
+pub async fn create_from_server_pack_wrapped(
+    instance_name: String,
+    pack_path: PathBuf,
+    fallback_version: Option<String>,
+    progress_sender: Option<ProgressSender<DownloadProgress>>,
+    download_assets: bool,
+    cancel: Option<CancelToken>,
+) -> Result<ServerPackImport, String> {
+    create_from_server_pack(
+        &instance_name,
+        pack_path,
+        fallback_version,
+        progress_sender,
+        download_assets,
+        cancel,
+    )
+    .await
+    .map_err(|err| err.to_string_with_code())
+}
+
+/// Creates a client instance with the Minecraft version,
+/// mod loader, mods and configs of a server pack zip.
+///
+/// `fallback_version` is used if the pack doesn't say which
+/// Minecraft version it's for. Only Fabric packs (and packs
+/// without mods) are supported, as the launcher can't install
+/// other loaders.
+pub async fn create_from_server_pack(
+    instance_name: &str,
+    pack_path: PathBuf,
+    fallback_version: Option<String>,
+    progress_sender: Option<ProgressSender<DownloadProgress>>,
+    download_assets: bool,
+    cancel: Option<CancelToken>,
+) -> Result<ServerPackImport, ServerPackError> {
+    info!("Reading server pack {pack_path:?}");
+    let pack = file_utils::run_blocking({
+        let pack_path = pack_path.clone();
+        move || read_pack(&pack_path)
+    })
+    .await?;
+
+    let minecraft_version = pack
+        .minecraft_version
+        .clone()
+        .or(fallback_version)
+        .ok_or(ServerPackError::UnknownVersion)?;
+    let loader = pack.loader.clone().unwrap_or_else(|| "Vanilla".to_owned());
+    if loader != "Fabric" && loader != "Vanilla" {
+        return Err(ServerPackError::UnsupportedLoader(loader));
+    }
+
+    let client = file_utils::create_client();
+    let hashes: Vec<&str> = pack.mods.iter().map(|n| n.sha1.as_str()).collect();
+    let versions = get_versions_by_hash(&client, &hashes).await?;
+    let project_ids: Vec<&str> = versions.values().map(|n| n.project_id.as_str()).collect();
+    let sides = get_client_sides(&client, &project_ids).await?;
+
+    let mut import = ServerPackImport {
+        minecraft_version: minecraft_version.clone(),
+        loader: loader.clone(),
+        skipped: Vec::new(),
+        review: Vec::new(),
+    };
+    let mut kept = Vec::new();
+    let mut managed = Vec::new();
+    for pack_mod in pack.mods {
+        let Some(version) = versions.get(&pack_mod.sha1) else {
+            import.review.push((
+                pack_mod.file_name.clone(),
+                "not on Modrinth, so it may be server-only".to_owned(),
+            ));
+            kept.push(pack_mod.entry);
+            continue;
+        };
+        match sides.get(&version.project_id).map(String::as_str) {
+            Some("unsupported") => {
+                info!("Leaving out server-only mod {}", pack_mod.file_name);
+                import.skipped.push(pack_mod.file_name);
+                continue;
+            }
+            Some("required" | "optional") => {}
+            _ => import.review.push((
+                pack_mod.file_name.clone(),
+                "Modrinth doesn't say if it works on clients".to_owned(),
+            )),
+        }
+        managed.push(ManagedMod {
+            source: ModSource::Modrinth,
+            project_id: version.project_id.clone(),
+            version: version.version_number.clone(),
+            file_name: pack_mod.file_name,
+            sha1: pack_mod.sha1,
+            enabled: true,
+            dependencies: version
+                .required_dependencies()
+                .map(ToOwned::to_owned)
+                .collect(),
+        });
+        kept.push(pack_mod.entry);
+    }
+
+    instance_create::create_instance(
+        instance_name,
+        minecraft_version,
+        VersionSource::Mojang,
+        progress_sender,
+        download_assets,
+        cancel,
+    )
+    .await?;
+
+    let mut files = kept;
+    files.extend(pack.configs);
+    let result =
+        set_up_instance(instance_name, &loader, pack_path, pack.root, files, managed).await;
+    // Don't leave a half-made instance behind.
+    if result.is_err() {
+        if let Err(err) = delete_instance(instance_name) {
+            error!("Could not remove the instance after the import failed: {err}");
+        }
+    }
+    result.map(|()| import)
+}
+
+/// Installs the loader, and copies the pack's
+/// `files` into the newly created instance.
+async fn set_up_instance(
+    instance_name: &str,
+    loader: &str,
+    pack_path: PathBuf,
+    root: String,
+    files: Vec<String>,
+    managed: Vec<ManagedMod>,
+) -> Result<(), ServerPackError> {
+    if loader == "Fabric" && !fabric::install_latest(instance_name).await? {
+        return Err(ServerPackError::UnsupportedLoader(loader.to_owned()));
+    }
+
+    let game_dir = get_game_dir(instance_name)?;
+    file_utils::run_blocking(move || extract(&pack_path, &root, &files, &game_dir)).await?;
+
+    let config_path = get_instance_dir(instance_name)?.join("config.json");
+    let mut config = InstanceConfigJson::read(&config_path)?;
+    for managed_mod in managed {
+        config.record_mod(managed_mod);
+    }
+    config.save(&config_path)?;
+    Ok(())
+}
+
+fn read_pack(pack_path: &Path) -> Result<ServerPack, ServerPackError> {
+    let file = File::open(pack_path).map_err(io_err!(pack_path))?;
+    let mut archive = ZipArchive::new(file)?;
+    let names: Vec<String> = archive.file_names().map(ToOwned::to_owned).collect();
+    let root = find_root(&names);
+    let relative = |name: &str| name.strip_prefix(root.as_str()).map(ToOwned::to_owned);
+
+    let mut minecraft_version = None;
+    let mut loader = None;
+    let mut mods = Vec::new();
+    let mut configs = Vec::new();
+    for name in &names {
+        let Some(path) = relative(name) else {
+            continue;
+        };
+        if path.ends_with('/') {
+            continue;
+        }
+        if loader.is_none() {
+            loader = detect_loader(&path);
+        }
+        if minecraft_version.is_none() {
+            minecraft_version = detect_version(&path);
+        }
+
+        if let Some(file_name) = path.strip_prefix("mods/") {
+            if file_name.contains('/') || !file_name.ends_with(".jar") {
+                continue;
+            }
+            relative_path(name, &root)?;
+            let mut bytes = Vec::new();
+            archive
+                .by_name(name)?
+                .read_to_end(&mut bytes)
+                .map_err(io_err!(pack_path))?;
+            if loader.is_none() {
+                loader = detect_jar_loader(&bytes);
+            }
+            mods.push(PackMod {
+                entry: name.clone(),
+                file_name: file_name.to_owned(),
+                sha1: file_utils::sha1_hex(&bytes),
+            });
+        } else if CONFIG_DIRS
+            .iter()
+            .any(|dir| path.starts_with(&format!("{dir}/")))
+        {
+            relative_path(name, &root)?;
+            configs.push(name.clone());
+        } else if path == "variables.txt" {
+            let mut variables = String::new();
+            archive
+                .by_name(name)?
+                .read_to_string(&mut variables)
+                .map_err(io_err!(pack_path))?;
+            let (version, variables_loader) = parse_variables(&variables);
+            minecraft_version = minecraft_version.or(version);
+            loader = loader.or(variables_loader);
+        }
+    }
+
+    Ok(ServerPack {
+        root,
+        minecraft_version,
+        loader,
+        mods,
+        configs,
+    })
+}
+
+/// The folder holding the `mods` folder, like `Pack-1.0/`,
+/// or an empty string if it's at the top of the zip.
+fn find_root(names: &[String]) -> String {
+    names
+        .iter()
+        .filter_map(|name| {
+            let index = if name.starts_with("mods/") {
+                0
+            } else {
+                name.find("/mods/")? + 1
+            };
+            Some(&name[..index])
+        })
+        .min_by_key(|root| root.len())
+        .unwrap_or_default()
+        .to_owned()
+}
+
+/// The loader whose files are at `path`, relative to the root.
+fn detect_loader(path: &str) -> Option<String> {
+    let loader = if path.starts_with(".fabric/")
+        || path.starts_with("libraries/net/fabricmc/")
+        || path == "fabric-server-launch.jar"
+    {
+        "Fabric"
+    } else if path.starts_with("libraries/org/quiltmc/") || path == "quilt-server-launch.jar" {
+        "Quilt"
+    } else if path.starts_with("libraries/net/neoforged/") {
+        "NeoForge"
+    } else if path.starts_with("libraries/net/minecraftforge/")
+        || (path.starts_with("forge-") && path.ends_with(".jar"))
+    {
+        "Forge"
+    } else {
+        return None;
+    };
+    Some(loader.to_owned())
+}
+
+/// The Minecraft version, if `path` has it, like
+/// `libraries/net/minecraft/server/1.20.1/...`.
+fn detect_version(path: &str) -> Option<String> {
+    let version = if let Some(rest) = path.strip_prefix("libraries/net/minecraft/server/") {
+        rest.split('/').next()?
+    } else if let Some(rest) = path.strip_prefix("libraries/net/minecraftforge/forge/") {
+        // Like `1.20.1-47.2.0`.
+        rest.split('/').next()?.split('-').next()?
+    } else if let Some(rest) = path.strip_prefix("minecraft_server.") {
+        rest.strip_suffix(".jar")?
+    } else {
+        return None;
+    };
+    (!version.is_empty()).then(|| version.to_owned())
+}
+
+/// The loader a mod jar is for, from its metadata file.
+fn detect_jar_loader(jar: &[u8]) -> Option<String> {
+    let archive = ZipArchive::new(Cursor::new(jar)).ok()?;
+    let has = |name: &str| archive.file_names().any(|n| n == name);
+    let loader = if has("fabric.mod.json") {
+        "Fabric"
+    } else if has("quilt.mod.json") {
+        "Quilt"
+    } else if has("META-INF/neoforge.mods.toml") {
+        "NeoForge"
+    } else if has("META-INF/mods.toml") || has("mcmod.info") {
+        "Forge"
+    } else {
+        return None;
+    };
+    Some(loader.to_owned())
+}
+
+/// Reads the Minecraft version and loader from the
+/// `variables.txt` of server starter scripts.
+fn parse_variables(variables: &str) -> (Option<String>, Option<String>) {
+    let get = |key: &str| {
+        variables.lines().find_map(|line| {
+            let value = line.trim().strip_prefix(key)?.strip_prefix('=')?;
+            let value = value.trim().trim_matches('"');
+            (!value.is_empty()).then(|| value.to_owned())
+        })
+    };
+    let loader = get("MODLOADER").map(|loader| match loader.to_lowercase().as_str() {
+        "fabric" => "Fabric".to_owned(),
+        "quilt" => "Quilt".to_owned(),
+        "forge" => "Forge".to_owned(),
+        "neoforge" => "NeoForge".to_owned(),
+        _ => loader,
+    });
+    (get("MINECRAFT_VERSION"), loader)
+}
+
+/// Copies `files` (paths in the zip) into the game
+/// directory, at their paths relative to `root`.
+fn extract(
+    pack_path: &Path,
+    root: &str,
+    files: &[String],
+    game_dir: &Path,
+) -> Result<(), ServerPackError> {
+    let file = File::open(pack_path).map_err(io_err!(pack_path))?;
+    let mut archive = ZipArchive::new(file)?;
+    for name in files {
+        let dest = game_dir.join(relative_path(name, root)?);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent).map_err(io_err!(parent))?;
+        }
+        let mut entry = archive.by_name(name)?;
+        let mut out = File::create(&dest).map_err(io_err!(dest))?;
+        std::io::copy(&mut entry, &mut out).map_err(io_err!(dest))?;
+    }
+    Ok(())
+}
+
+/// The path of a file in the zip relative to `root`, checked
+/// so that a malicious zip can't write outside the game
+/// directory. Checked when the pack is read, before the
+/// instance is created, and again when extracting.
+fn relative_path<'a>(name: &'a str, root: &str) -> Result<&'a Path, ServerPackError> {
+    let relative = Path::new(name.strip_prefix(root).unwrap_or(name));
+    if relative
+        .components()
+        .all(|n| matches!(n, Component::Normal(_)))
+    {
+        Ok(relative)
+    } else {
+        Err(ServerPackError::InvalidPath(name.to_owned()))
+    }
+}
+
+/// Looks up mod files on Modrinth by their SHA-1 hash.
+/// Files Modrinth doesn't have are left out.
+async fn get_versions_by_hash(
+    client: &reqwest::Client,
+    hashes: &[&str],
+) -> Result<HashMap<String, ModrinthVersion>, ServerPackError> {
+    if hashes.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let body = serde_json::json!({ "hashes": hashes, "algorithm": "sha1" });
+    let request = client
+        .post(format!("{MODRINTH_URL}/version_files"))
+        .header(CONTENT_TYPE, "application/json")
+        .body(body.to_string());
+    Ok(serde_json::from_str(&send(request).await?)?)
+}
+
+/// Whether each project runs on clients, by project ID.
+async fn get_client_sides(
+    client: &reqwest::Client,
+    project_ids: &[&str],
+) -> Result<HashMap<String, String>, ServerPackError> {
+    if project_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let request = client
+        .get(format!("{MODRINTH_URL}/projects"))
+        .query(&[("ids", serde_json::to_string(project_ids)?)]);
+    let projects: Vec<ProjectSides> = serde_json::from_str(&send(request).await?)?;
+    Ok(projects
+        .into_iter()
+        .map(|n| (n.id, n.client_side))
+        .collect())
+}
+
+async fn send(request: reqwest::RequestBuilder) -> Result<String, RequestError> {
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        return Err(RequestError::DownloadError {
+            code: response.status(),
+            url: response.url().clone(),
+        });
+    }
+    Ok(response.text().await?)
+}
+
+#[derive(Debug)]
+pub enum ServerPackError {
+    Io(IoError),
+    Zip(ZipError),
+    Json(serde_json::Error),
+    JsonFile(JsonFileError),
+    Request(RequestError),
+    Launcher(LauncherError),
+    Download(DownloadError),
+    Fabric(FabricInstallError),
+    /// The pack doesn't say its Minecraft
+    /// version, and none was picked.
+    UnknownVersion,
+    UnsupportedLoader(String),
+    /// A file in the zip with `..` or an absolute path.
+    InvalidPath(String),
+}
+
+impl From<IoError> for ServerPackError {
+    fn from(value: IoError) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<ZipError> for ServerPackError {
+    fn from(value: ZipError) -> Self {
+        Self::Zip(value)
+    }
+}
+
+impl From<serde_json::Error> for ServerPackError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl From<JsonFileError> for ServerPackError {
+    fn from(value: JsonFileError) -> Self {
+        Self::JsonFile(value)
+    }
+}
+
+impl From<RequestError> for ServerPackError {
+    fn from(value: RequestError) -> Self {
+        Self::Request(value)
+    }
+}
+
+impl From<LauncherError> for ServerPackError {
+    fn from(value: LauncherError) -> Self {
+        Self::Launcher(value)
+    }
+}
+
+impl From<DownloadError> for ServerPackError {
+    fn from(value: DownloadError) -> Self {
+        Self::Download(value)
+    }
+}
+
+impl From<FabricInstallError> for ServerPackError {
+    fn from(value: FabricInstallError) -> Self {
+        Self::Fabric(value)
+    }
+}
+
+impl Display for ServerPackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServerPackError::Io(err) => write!(f, "could not import server pack: {err}"),
+            ServerPackError::Zip(err) => {
+                write!(f, "could not import server pack: zip error: {err}")
+            }
+            ServerPackError::Json(err) => {
+                write!(f, "could not import server pack: json error: {err}")
+            }
+            ServerPackError::JsonFile(err) => write!(f, "could not import server pack: {err}"),
+            ServerPackError::Request(err) => {
+                write!(f, "could not look up the server pack's mods: {err}")
+            }
+            ServerPackError::Launcher(err) => write!(f, "could not import server pack: {err}"),
+            ServerPackError::Download(err) => write!(f, "could not import server pack: {err}"),
+            ServerPackError::Fabric(err) => write!(f, "could not import server pack: {err}"),
+            ServerPackError::UnknownVersion => write!(
+                f,
+                "the server pack doesn't say which Minecraft version it's for, select one"
+            ),
+            ServerPackError::UnsupportedLoader(loader) => write!(
+                f,
+                "the server pack uses {loader}, which the launcher can't install yet"
+            ),
+            ServerPackError::InvalidPath(path) => {
+                write!(f, "the server pack has an invalid file path: {path}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_pack_layout() {
+        let names = [
+            "Pack-Server/".to_owned(),
+            "Pack-Server/mods/sodium.jar".to_owned(),
+            "Pack-Server/config/mod/mods/settings.json".to_owned(),
+        ];
+        assert_eq!(find_root(&names), "Pack-Server/");
+        assert_eq!(
+            detect_version("libraries/net/minecraftforge/forge/1.20.1-47.2.0/forge.jar").as_deref(),
+            Some("1.20.1")
+        );
+        assert_eq!(
+            parse_variables("MINECRAFT_VERSION=1.19.2\nMODLOADER=Fabric\n"),
+            (Some("1.19.2".to_owned()), Some("Fabric".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(
+            relative_path("Pack/config/mod.json", "Pack/").unwrap(),
+            Path::new("config/mod.json")
+        );
+        assert!(relative_path("Pack/config/../../evil.sh", "Pack/").is_err());
+        assert!(relative_path("/etc/passwd", "").is_err());
+    }
+}
@@ -0,0 +1,254 @@
+//! Checking that an instance's files are all there and fine,
+//! so users can find (and fix) what's broken themselves
+//! instead of recreating the instance or filing a bug.
+//!
+//! Each [`Check`] can be fixed on its own with [`fix_wrapped`].
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    download::verify,
+    error::{HasErrorCode, LauncherResult},
+    file_utils, io_err,
+    json_structs::{
+        json_instance_config::InstanceConfigJson, json_version::VersionDetails, validation,
+    },
+};
+
+use super::{
+    instance_launch::{
+        get_fabric_json, get_instance_dir, read_version_json, redownload_version_json,
+    },
+    instance_mod_installer::fabric,
+};
+
+/// Something about an instance that can be checked, in the
+/// order they're checked. Later checks need the earlier
+/// ones' files, so they're skipped if those are broken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    /// `config.json` can be read.
+    Config,
+    /// `details.json` can be read.
+    VersionJson,
+    /// The Minecraft jar matches its hash.
+    ClientJar,
+    /// The libraries are there and match their hashes.
+    Libraries,
+    /// The mod loader's files match the instance's `mod_type`.
+    LoaderJson,
+    /// The assets index is downloaded.
+    AssetIndex,
+}
+
+impl Check {
+    pub const ALL: [Check; 6] = [
+        Check::Config,
+        Check::VersionJson,
+        Check::ClientJar,
+        Check::Libraries,
+        Check::LoaderJson,
+        Check::AssetIndex,
+    ];
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckStatus {
+    Passed,
+    /// What's wrong.
+    Failed(String),
+    /// It doesn't apply to the instance, or
+    /// can't be checked until something else is fixed.
+    Skipped,
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub check: Check,
+    pub status: CheckStatus,
+}
+
+/// Runs every [`Check`] on the instance. Nothing is changed.
+pub async fn verify_instance(instance_name: &str) -> LauncherResult<Vec<CheckResult>> {
+    let instance_dir = get_instance_dir(instance_name)?;
+    let mut results = Vec::new();
+    let mut push = |check, status| results.push(CheckResult { check, status });
+
+    let config = InstanceConfigJson::read(&instance_dir.join("config.json"));
+    let version_json = read_version_json(&instance_dir);
+    push(Check::Config, failed_if_err(&config));
+    push(Check::VersionJson, failed_if_err(&version_json));
+
+    let Ok(version_json) = version_json else {
+        for check in &Check::ALL[2..] {
+            push(*check, CheckStatus::Skipped);
+        }
+        return Ok(results);
+    };
+
+    push(
+        Check::ClientJar,
+        check_client_jar(jar_path(&instance_dir, &version_json), version_json.clone()).await,
+    );
+
+    let broken = verify::find_broken_libraries(&instance_dir, &version_json).await;
+    push(
+        Check::Libraries,
+        if broken.is_empty() {
+            CheckStatus::Passed
+        } else {
+            CheckStatus::Failed(format!("{} libraries are missing or damaged", broken.len()))
+        },
+    );
+
+    push(
+        Check::LoaderJson,
+        match &config {
+            Ok(config) if config.mod_type == "Fabric" => check_fabric(&instance_dir),
+            _ => CheckStatus::Skipped,
+        },
+    );
+
+    push(Check::AssetIndex, check_asset_index(&version_json));
+    Ok(results)
+}
+
+pub async fn verify_instance_wrapped(instance_name: String) -> Result<Vec<CheckResult>, String> {
+    verify_instance(&instance_name)
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+fn failed_if_err<T, E: std::fmt::Display>(result: &Result<T, E>) -> CheckStatus {
+    match result {
+        Ok(_) => CheckStatus::Passed,
+        Err(err) => CheckStatus::Failed(err.to_string()),
+    }
+}
+
+fn jar_path(instance_dir: &Path, version_json: &VersionDetails) -> PathBuf {
+    instance_dir
+        .join(".minecraft")
+        .join("versions")
+        .join(&version_json.id)
+        .join(format!("{}.jar", version_json.id))
+}
+
+async fn check_client_jar(path: PathBuf, version_json: VersionDetails) -> CheckStatus {
+    file_utils::run_blocking(move || match std::fs::read(&path) {
+        Ok(bytes)
+            if file_utils::sha1_hex(&bytes)
+                .eq_ignore_ascii_case(&version_json.downloads.client.sha1) =>
+        {
+            CheckStatus::Passed
+        }
+        Ok(_) => CheckStatus::Failed("the jar doesn't match its hash".to_owned()),
+        Err(err) => CheckStatus::Failed(format!("couldn't read {path:?}: {err}")),
+    })
+    .await
+}
+
+fn check_fabric(instance_dir: &Path) -> CheckStatus {
+    let fabric_json = match get_fabric_json(instance_dir) {
+        Ok(json) => json,
+        Err(err) => return CheckStatus::Failed(err.to_string()),
+    };
+    let libraries_dir = instance_dir.join("libraries");
+    let missing = fabric_json
+        .libraries
+        .iter()
+        .filter(|library| !libraries_dir.join(library.get_path()).is_file())
+        .count();
+    if missing == 0 {
+        CheckStatus::Passed
+    } else {
+        CheckStatus::Failed(format!("{missing} Fabric libraries are missing"))
+    }
+}
+
+fn asset_index_path(version_json: &VersionDetails) -> LauncherResult<PathBuf> {
+    let id = &version_json.assetIndex.id;
+    Ok(file_utils::get_cache_dir()?
+        .join("assets")
+        .join(id)
+        .join("indexes")
+        .join(format!("{id}.json")))
+}
+
+fn check_asset_index(version_json: &VersionDetails) -> CheckStatus {
+    let path = match asset_index_path(version_json) {
+        Ok(path) => path,
+        Err(err) => return CheckStatus::Failed(err.to_string()),
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(text) => failed_if_err(&serde_json::from_str::<serde_json::Value>(&text)),
+        Err(err) => CheckStatus::Failed(format!("couldn't read {path:?}: {err}")),
+    }
+}
+
+/// Fixes what `check` found wrong with the instance,
+/// by restoring or downloading the files again.
+pub async fn fix_wrapped(instance_name: String, check: Check) -> Result<(), String> {
+    match check {
+        // Fabric has its own errors, so it's handled separately.
+        Check::LoaderJson => reinstall_fabric(&instance_name).await,
+        _ => fix(&instance_name, check)
+            .await
+            .map_err(|err| err.to_string_with_code()),
+    }
+}
+
+async fn fix(instance_name: &str, check: Check) -> LauncherResult<()> {
+    let instance_dir = get_instance_dir(instance_name)?;
+    match check {
+        Check::Config => {
+            let path = instance_dir.join("config.json");
+            validation::restore_backup(&path)?;
+        }
+        Check::VersionJson => redownload_version_json(instance_name).await?,
+        Check::ClientJar => {
+            let version_json = read_version_json(&instance_dir)?;
+            let path = jar_path(&instance_dir, &version_json);
+            let client = file_utils::create_client();
+            let bytes =
+                file_utils::download_file_to_bytes(&client, &version_json.downloads.client.url)
+                    .await?;
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(io_err!(parent))?;
+            }
+            std::fs::write(&path, bytes).map_err(io_err!(path))?;
+        }
+        Check::Libraries => {
+            let version_json = read_version_json(&instance_dir)?;
+            verify::repair_instance(&instance_dir, &version_json).await?;
+        }
+        Check::AssetIndex => {
+            let version_json = read_version_json(&instance_dir)?;
+            let path = asset_index_path(&version_json)?;
+            let client = file_utils::create_client();
+            let index =
+                file_utils::download_file_to_string(&client, &version_json.assetIndex.url).await?;
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(io_err!(parent))?;
+            }
+            std::fs::write(&path, index).map_err(io_err!(path))?;
+        }
+        Check::LoaderJson => unreachable!("handled by fix_wrapped"),
+    }
+    Ok(())
+}
+
+/// Installs Fabric again, keeping the loader version
+/// if `fabric.json` still says what it was.
+async fn reinstall_fabric(instance_name: &str) -> Result<(), String> {
+    let loader_version = match fabric::installed_version(instance_name) {
+        Some(version) => version,
+        None => fabric::get_list_of_versions_wrapped()
+            .await?
+            .into_iter()
+            .next()
+            .map(|version| version.version)
+            .ok_or_else(|| "no Fabric versions found".to_owned())?,
+    };
+    fabric::install_wrapped(loader_version, instance_name.to_owned(), None).await
+}
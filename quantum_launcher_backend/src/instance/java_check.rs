@@ -0,0 +1,170 @@
+//! Checking, before launching, that the Java an instance runs
+//! with works with its Minecraft version and mod loader.
+//!
+//! Java chosen by the launcher always works, but a Java forced
+//! with `java_version` or `java_override` may be too old for the
+//! game, or too new for an old mod loader.
+
+use std::{path::Path, process::Command};
+
+use tracing::error;
+
+use crate::{
+    error::{HasErrorCode, LauncherResult},
+    file_utils,
+    json_structs::json_version::VersionDetails,
+};
+
+use super::instance_launch::{get_config, get_instance_dir};
+
+/// A mod loader that doesn't start on Java newer than
+/// `max_java`, for Minecraft versions in `versions`.
+struct JavaLimit {
+    loader: &'static str,
+    /// Versions, or the start of versions, like `1.16`
+    /// for `1.16` to `1.16.5`.
+    versions: &'static [&'static str],
+    max_java: usize,
+}
+
+const KNOWN_LIMITS: &[JavaLimit] = &[
+    // ModLauncher crashes on the module changes of Java 17.
+    JavaLimit {
+        loader: "Forge",
+        versions: &["1.16"],
+        max_java: 16,
+    },
+    // LaunchWrapper expects the class loader to be a
+    // URLClassLoader, which it isn't since Java 9.
+    JavaLimit {
+        loader: "Forge",
+        versions: &["1.7", "1.8", "1.9", "1.10", "1.11", "1.12"],
+        max_java: 8,
+    },
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JavaProblem {
+    /// The version needs a newer Java.
+    TooOld { required: usize, selected: usize },
+    /// The mod loader doesn't work on Java this new.
+    TooNew {
+        loader: String,
+        max: usize,
+        selected: usize,
+    },
+}
+
+impl JavaProblem {
+    /// The Java major version to use instead.
+    pub fn recommended(&self) -> usize {
+        match self {
+            JavaProblem::TooOld { required, .. } => *required,
+            JavaProblem::TooNew { max, .. } => *max,
+        }
+    }
+}
+
+/// Checks the Java the instance would launch with. Returns
+/// `None` if it works, or if its version can't be found out.
+pub fn check(instance_name: &str) -> LauncherResult<Option<JavaProblem>> {
+    let instance_dir = get_instance_dir(instance_name)?;
+    let config = get_config(&instance_dir)?;
+    let version_json = VersionDetails::read(&instance_dir.join("details.json"))?;
+    let required = version_json
+        .javaVersion
+        .as_ref()
+        .map_or(8, |n| n.majorVersion);
+
+    let selected = match &config.java_override {
+        Some(java_override) if !java_override.is_empty() => {
+            let Some(selected) = get_java_major_version(Path::new(java_override)) else {
+                return Ok(None);
+            };
+            selected
+        }
+        _ => config.java_version.unwrap_or(required),
+    };
+
+    if selected < required {
+        return Ok(Some(JavaProblem::TooOld { required, selected }));
+    }
+    let limit = KNOWN_LIMITS.iter().find(|limit| {
+        limit.loader == config.mod_type
+            && limit
+                .versions
+                .iter()
+                .any(|n| is_in_version(&version_json.id, n))
+    });
+    Ok(limit
+        .filter(|limit| selected > limit.max_java)
+        .map(|limit| JavaProblem::TooNew {
+            loader: limit.loader.to_owned(),
+            max: limit.max_java,
+            selected,
+        }))
+}
+
+pub async fn check_wrapped(instance_name: String) -> Result<Option<JavaProblem>, String> {
+    file_utils::run_blocking(move || check(&instance_name))
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Whether `version` is `prefix`, or one of its minor
+/// versions (`1.16.5` is in `1.16`, but `1.1` isn't).
+pub(crate) fn is_in_version(version: &str, prefix: &str) -> bool {
+    version == prefix
+        || version
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+/// Runs `java -version` to find out its major version.
+fn get_java_major_version(java: &Path) -> Option<usize> {
+    let output = match Command::new(java).arg("-version").output() {
+        Ok(output) => output,
+        Err(err) => {
+            error!("Could not run {java:?} to check its version: {err}");
+            return None;
+        }
+    };
+    // Java prints its version to stderr.
+    parse_java_version(&String::from_utf8_lossy(&output.stderr))
+}
+
+/// Finds the major version in the output of `java -version`,
+/// like `openjdk version "17.0.2" 2022-01-18`, or
+/// `java version "1.8.0_292"` for Java 8 and older.
+fn parse_java_version(output: &str) -> Option<usize> {
+    let version = output.split('"').nth(1)?;
+    let mut parts = version.split(['.', '_', '-', '+']);
+    match parts.next()?.parse().ok()? {
+        1 => parts.next()?.parse().ok(),
+        major => Some(major),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_java_version() {
+        assert_eq!(
+            parse_java_version("openjdk version \"17.0.2\" 2022-01-18\nOpenJDK Runtime"),
+            Some(17)
+        );
+        assert_eq!(
+            parse_java_version("java version \"1.8.0_292\"\nJava(TM) SE"),
+            Some(8)
+        );
+        assert_eq!(
+            parse_java_version("openjdk version \"21\" 2023-09-19"),
+            Some(21)
+        );
+        assert!(is_in_version("1.16.5", "1.16"));
+        assert!(!is_in_version("1.1", "1.16"));
+        assert!(!is_in_version("1.10.2", "1.1"));
+    }
+}
@@ -0,0 +1,110 @@
+//! Filling in the `${variables}` in a version's game
+//! arguments, like `--username ${auth_player_name}`.
+
+use std::collections::BTreeMap;
+
+use super::launch_options::Account;
+
+/// What the arguments need to know about the instance.
+pub struct InstanceContext<'a> {
+    /// The Minecraft version, like `1.20.4`.
+    pub version_name: &'a str,
+    /// Shown in the F3 screen, like `release`.
+    pub version_type: &'a str,
+    pub game_directory: &'a str,
+    pub assets_root: &'a str,
+    /// Where old versions read their assets from by name.
+    /// The same as `assets_root`, except for legacy indexes.
+    pub game_assets: &'a str,
+    pub assets_index_name: &'a str,
+}
+
+/// The value of each variable in the arguments,
+/// from the account and the instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgumentVars {
+    vars: BTreeMap<&'static str, String>,
+}
+
+impl ArgumentVars {
+    pub fn new(account: &Account, instance: &InstanceContext) -> Self {
+        let vars = [
+            ("auth_player_name", account.username.as_str()),
+            ("auth_uuid", &account.uuid),
+            ("auth_access_token", &account.access_token),
+            // Used by old versions instead of `auth_access_token`.
+            ("auth_session", &account.access_token),
+            ("auth_xuid", &account.xuid),
+            ("user_type", &account.user_type),
+            // Only used for telemetry, which isn't sent for this launcher.
+            ("clientid", "0"),
+            ("user_properties", "{}"),
+            ("version_name", instance.version_name),
+            ("version_type", instance.version_type),
+            ("game_directory", instance.game_directory),
+            ("assets_root", instance.assets_root),
+            // Used by old versions instead of `assets_root`.
+            ("game_assets", instance.game_assets),
+            ("assets_index_name", instance.assets_index_name),
+        ];
+        Self {
+            vars: vars
+                .into_iter()
+                .map(|(var, value)| (var, value.to_owned()))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, var: &str) -> Option<&str> {
+        self.vars.get(var).map(String::as_str)
+    }
+
+    /// `argument` with its variables filled in. Unknown
+    /// variables are left as they are.
+    pub fn substitute(&self, argument: &str) -> String {
+        let mut result = String::with_capacity(argument.len());
+        let mut rest = argument;
+        while let Some(start) = rest.find("${") {
+            let Some(len) = rest[start..].find('}') else {
+                break;
+            };
+            let var = &rest[start + 2..start + len];
+            result.push_str(&rest[..start]);
+            match self.get(var) {
+                Some(value) => result.push_str(value),
+                None => result.push_str(&rest[start..=start + len]),
+            }
+            rest = &rest[start + len + 1..];
+        }
+        result.push_str(rest);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_substitute() {
+        let vars = ArgumentVars::new(
+            &Account::offline("Steve"),
+            &InstanceContext {
+                version_name: "1.20.4",
+                version_type: "release",
+                game_directory: "/game",
+                assets_root: "/assets",
+                game_assets: "/assets",
+                assets_index_name: "12",
+            },
+        );
+        assert_eq!(vars.substitute("${auth_player_name}"), "Steve");
+        assert_eq!(vars.substitute("${auth_xuid}"), "0");
+        assert_eq!(vars.substitute("${user_type}"), "legacy");
+        assert_eq!(
+            vars.substitute("${game_assets}/${assets_index_name}.json"),
+            "/assets/12.json"
+        );
+        assert_eq!(vars.substitute("${unknown} ${"), "${unknown} ${");
+    }
+}
@@ -0,0 +1,325 @@
+use std::{fmt::Display, path::PathBuf};
+
+use crate::{
+    cancel::CancelToken, java_install::JavaInstallMessage, logging,
+    offline_profiles::OfflineProfile, progress::ProgressSender,
+};
+
+use super::instance_launch::join_arguments;
+
+/// Everything about how to launch an instance, for
+/// [`crate::launch`] and [`crate::launch_dry_run`].
+///
+/// # Example
+/// ```no_run
+/// # async fn example() -> quantum_launcher_backend::error::LauncherResult<()> {
+/// use quantum_launcher_backend::LaunchOptions;
+///
+/// let child = LaunchOptions::new("my-instance")
+///     .username("Steve")
+///     .java_args(["-Dfile.encoding=UTF-8".to_owned()])
+///     .join_server("localhost:25565")
+///     .launch()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct LaunchOptions {
+    pub(crate) instance_name: String,
+    pub(crate) account: Account,
+    pub(crate) profile: Option<String>,
+    pub(crate) java_args: Vec<String>,
+    pub(crate) override_java_args: Vec<String>,
+    pub(crate) game_args: Vec<String>,
+    pub(crate) env: Vec<(String, String)>,
+    pub(crate) wrapper: Vec<String>,
+    pub(crate) server: Option<String>,
+    pub(crate) java_install_progress: Option<ProgressSender<JavaInstallMessage>>,
+    pub(crate) cancel: Option<CancelToken>,
+    pub(crate) separate_game_dir: bool,
+    pub(crate) without_mods: bool,
+    pub(crate) allow_invalid_username: bool,
+}
+
+impl LaunchOptions {
+    /// Launches `instance_name` with the default offline
+    /// account (`Player`) and no extra arguments.
+    pub fn new(instance_name: impl Into<String>) -> Self {
+        Self {
+            instance_name: instance_name.into(),
+            account: Account::offline("Player"),
+            profile: None,
+            java_args: Vec::new(),
+            override_java_args: Vec::new(),
+            game_args: Vec::new(),
+            env: Vec::new(),
+            wrapper: Vec::new(),
+            server: None,
+            java_install_progress: None,
+            cancel: None,
+            separate_game_dir: false,
+            without_mods: false,
+            allow_invalid_username: false,
+        }
+    }
+
+    /// Plays offline as `username`. Short for
+    /// `.account(Account::offline(username))`.
+    pub fn username(self, username: impl Into<String>) -> Self {
+        self.account(Account::offline(username))
+    }
+
+    pub fn account(mut self, account: Account) -> Self {
+        self.account = account;
+        self.profile = None;
+        self
+    }
+
+    /// Plays as a saved offline profile. Its name is saved as
+    /// the instance's `last_profile`, so that it can be
+    /// launched as the same profile again.
+    pub fn offline_profile(mut self, profile: &OfflineProfile) -> Self {
+        self = self.account(profile.account());
+        self.profile = Some(profile.name.clone());
+        self
+    }
+
+    /// Extra Java arguments, added after the defaults and
+    /// before the instance's own `java_args`, so that those
+    /// take priority. Can be called more than once.
+    ///
+    /// For arguments that should override the instance's
+    /// own, use [`Self::override_java_args`].
+    pub fn java_args(mut self, args: impl IntoIterator<Item = String>) -> Self {
+        self.java_args.extend(args);
+        self
+    }
+
+    /// Java arguments added after the instance's own `java_args`,
+    /// so that they take priority over everything else, like ones
+    /// typed in for a single launch. Can be called more than once.
+    pub fn override_java_args(mut self, args: impl IntoIterator<Item = String>) -> Self {
+        self.override_java_args.extend(args);
+        self
+    }
+
+    /// Extra game arguments, added after the version's own.
+    pub fn game_args(mut self, args: impl IntoIterator<Item = String>) -> Self {
+        self.game_args.extend(args);
+        self
+    }
+
+    /// An environment variable for the game.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// A command the game is started through, like
+    /// `gamemoderun` or `prime-run`, with its arguments.
+    /// Empty (the default) starts Java directly.
+    pub fn wrapper(mut self, command: impl IntoIterator<Item = String>) -> Self {
+        self.wrapper = command.into_iter().collect();
+        self
+    }
+
+    /// Joins this server (`host` or `host:port`) once the
+    /// game has started.
+    pub fn join_server(mut self, address: impl Into<String>) -> Self {
+        self.server = Some(address.into());
+        self
+    }
+
+    /// Sends the progress of installing Java, if it's needed.
+    pub fn java_install_progress(mut self, sender: ProgressSender<JavaInstallMessage>) -> Self {
+        self.java_install_progress = Some(sender);
+        self
+    }
+
+    /// Lets the Java install be cancelled, if one is needed.
+    pub fn cancel(mut self, token: CancelToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Runs the game in a separate folder (the instance's
+    /// `.minecraft-separate`) with its own worlds, so that a
+    /// second copy of a running instance doesn't write to the
+    /// same saves. Its settings and mods are copied over first.
+    pub fn separate_game_dir(mut self) -> Self {
+        self.separate_game_dir = true;
+        self
+    }
+
+    /// Launches with only the mod loader, to tell whether a mod
+    /// or the instance itself is causing a crash. The `mods`
+    /// folder is moved aside until [`crate::restore_disabled_mods`]
+    /// (or the next launch) puts it back.
+    pub fn without_mods(mut self) -> Self {
+        self.without_mods = true;
+        self
+    }
+
+    /// Launches with an offline username that breaks Minecraft's
+    /// rules (see [`check_username`]), for servers that allow it.
+    /// Empty usernames and ones with spaces are still refused,
+    /// as they break the game's arguments.
+    pub fn allow_invalid_username(mut self) -> Self {
+        self.allow_invalid_username = true;
+        self
+    }
+
+    pub fn instance_name(&self) -> &str {
+        &self.instance_name
+    }
+
+    /// Starts the game. See [`crate::launch`].
+    pub async fn launch(self) -> crate::error::LauncherResult<std::process::Child> {
+        crate::launch(self).await
+    }
+
+    /// Works out the launch command without starting
+    /// the game. See [`crate::launch_dry_run`].
+    pub async fn dry_run(self) -> crate::error::LauncherResult<LaunchCommand> {
+        crate::launch_dry_run(self).await
+    }
+}
+
+/// Who the game is played as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Account {
+    pub username: String,
+    pub uuid: String,
+    pub access_token: String,
+    /// The Xbox user ID of a Microsoft account.
+    pub xuid: String,
+    /// `legacy`, `mojang` or `msa`.
+    pub user_type: String,
+}
+
+impl Account {
+    /// Playing without logging in, which only
+    /// works in singleplayer and on offline servers.
+    pub fn offline(username: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            uuid: "00000000-0000-0000-0000-000000000000".to_owned(),
+            access_token: "0".to_owned(),
+            xuid: "0".to_owned(),
+            user_type: "legacy".to_owned(),
+        }
+    }
+}
+
+/// Checks an offline username against Minecraft's rules:
+/// 3 to 16 letters, digits or underscores.
+///
+/// The game itself starts with other names, but servers kick
+/// players with them, and some plugins crash on them.
+pub fn check_username(username: &str) -> Result<(), UsernameProblem> {
+    let length = username.chars().count();
+    if username.is_empty() {
+        Err(UsernameProblem::Empty)
+    } else if username.contains(char::is_whitespace) {
+        Err(UsernameProblem::Space)
+    } else if let Some(c) = username
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && *c != '_')
+    {
+        Err(UsernameProblem::InvalidCharacter(c))
+    } else if length < *USERNAME_LENGTH.start() {
+        Err(UsernameProblem::TooShort)
+    } else if length > *USERNAME_LENGTH.end() {
+        Err(UsernameProblem::TooLong)
+    } else {
+        Ok(())
+    }
+}
+
+/// How long a username can be, in characters.
+pub const USERNAME_LENGTH: std::ops::RangeInclusive<usize> = 3..=16;
+
+/// What [`check_username`] found wrong with a username.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsernameProblem {
+    Empty,
+    Space,
+    TooShort,
+    TooLong,
+    InvalidCharacter(char),
+}
+
+impl UsernameProblem {
+    /// Whether the game can't be launched with the
+    /// username at all, even if invalid ones are allowed.
+    pub fn is_fatal(self) -> bool {
+        matches!(self, UsernameProblem::Empty | UsernameProblem::Space)
+    }
+}
+
+impl Display for UsernameProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UsernameProblem::Empty => write!(f, "it's empty"),
+            UsernameProblem::Space => write!(f, "it has spaces"),
+            UsernameProblem::TooShort => write!(
+                f,
+                "it's shorter than {} characters",
+                USERNAME_LENGTH.start()
+            ),
+            UsernameProblem::TooLong => {
+                write!(f, "it's longer than {} characters", USERNAME_LENGTH.end())
+            }
+            UsernameProblem::InvalidCharacter(c) => write!(
+                f,
+                "it has {c:?}, only letters, digits and underscores are allowed"
+            ),
+        }
+    }
+}
+
+/// The command that starts the game, from a dry run.
+#[derive(Debug, Clone)]
+pub struct LaunchCommand {
+    /// Java, or the wrapper command.
+    pub program: PathBuf,
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+    pub current_dir: PathBuf,
+}
+
+/// The command like it would be typed in a terminal,
+/// with access tokens hidden.
+impl Display for LaunchCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (key, value) in &self.env {
+            write!(f, "{key}={value} ")?;
+        }
+        let command = std::iter::once(self.program.to_string_lossy().into_owned())
+            .chain(self.args.iter().cloned())
+            .collect::<Vec<_>>();
+        write!(f, "{}", logging::redact_secrets(&join_arguments(&command)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_username() {
+        assert_eq!(check_username("Steve_123"), Ok(()));
+        assert_eq!(check_username("Al"), Err(UsernameProblem::TooShort));
+        assert_eq!(
+            check_username("ThisNameIsTooLong"),
+            Err(UsernameProblem::TooLong)
+        );
+        assert_eq!(
+            check_username("Café"),
+            Err(UsernameProblem::InvalidCharacter('é'))
+        );
+        assert_eq!(check_username("Steve Jobs"), Err(UsernameProblem::Space));
+        assert!(UsernameProblem::Space.is_fatal());
+    }
+}
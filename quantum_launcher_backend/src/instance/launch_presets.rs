@@ -0,0 +1,63 @@
+//! Java arguments that work around known problems of a
+//! mod loader on some Minecraft versions, added to every
+//! launch of a matching instance.
+//!
+//! The presets are kept in `launch_presets.json`, so
+//! new workarounds don't need changes to the launch code.
+
+use serde::Deserialize;
+use tracing::{error, info};
+
+use super::java_check::is_in_version;
+
+const PRESETS: &str = include_str!("launch_presets.json");
+
+#[derive(Deserialize)]
+struct LaunchPreset {
+    /// The `mod_type` of instances it's for.
+    loader: String,
+    /// Versions, or the start of versions, like `1.16`
+    /// for `1.16` to `1.16.5`.
+    versions: Vec<String>,
+    java_args: Vec<String>,
+    /// What the arguments work around.
+    reason: String,
+}
+
+fn read_presets() -> Vec<LaunchPreset> {
+    serde_json::from_str(PRESETS).unwrap_or_else(|err| {
+        error!("Could not read the launch presets: {err}");
+        Vec::new()
+    })
+}
+
+/// The preset Java arguments for an instance with
+/// this mod loader and Minecraft version.
+pub fn get_java_args(loader: &str, version: &str) -> Vec<String> {
+    read_presets()
+        .into_iter()
+        .filter(|preset| {
+            preset.loader == loader && preset.versions.iter().any(|n| is_in_version(version, n))
+        })
+        .flat_map(|preset| {
+            info!("Adding {:?}: {}", preset.java_args, preset.reason);
+            preset.java_args
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_match_loader_and_version() {
+        assert!(!read_presets().is_empty());
+        assert_eq!(
+            get_java_args("Forge", "1.16.5"),
+            ["-Dfml.earlyprogresswindow=false"]
+        );
+        assert!(get_java_args("Fabric", "1.16.5").is_empty());
+        assert!(get_java_args("Forge", "1.20.1").is_empty());
+    }
+}
@@ -1,4 +1,22 @@
-pub mod instance_mod_installer;
+pub mod applet_wrapper;
+pub mod game_options;
 pub mod instance_create;
 pub mod instance_launch;
+pub mod instance_list;
 pub mod instance_list_versions;
+pub mod instance_mod_installer;
+pub mod integrity;
+pub mod java_check;
+pub mod launch_arguments;
+pub mod launch_options;
+pub mod launch_presets;
+pub mod server_create;
+pub mod server_files;
+pub mod server_launch;
+pub mod server_schedule;
+pub mod server_software;
+pub mod snapshots;
+pub mod sync_folder;
+pub mod templates;
+pub mod world_backup;
+pub mod worlds;
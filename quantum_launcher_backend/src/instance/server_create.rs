@@ -0,0 +1,144 @@
+use std::path::Path;
+
+use tracing::info;
+
+use crate::{
+    cancel::{self, CancelToken},
+    download::{
+        constants::DEFAULT_RAM_MB_FOR_INSTANCE, progress::DownloadProgress, DownloadError,
+        GameDownloader,
+    },
+    error::HasErrorCode,
+    file_utils, io_err,
+    json_structs::{
+        json_instance_config::{default_world_backup_retention, InstanceConfigJson},
+        json_manifest::VersionSource,
+    },
+    progress::ProgressSender,
+};
+
+use super::{
+    server_files, server_launch,
+    server_software::{self, ServerSoftware},
+};
+
+pub async fn create_server_wrapped(
+    server_name: String,
+    version: String,
+    software: ServerSoftware,
+    progress_sender: Option<ProgressSender<DownloadProgress>>,
+    cancel: Option<CancelToken>,
+) -> Result<(), String> {
+    create_server(
+        &server_name,
+        &version,
+        software,
+        progress_sender,
+        cancel.as_ref(),
+    )
+    .await
+    .map_err(|n| n.to_string_with_code())
+}
+
+/// Creates a server in `QuantumLauncher/servers/`, with the
+/// server jar, a config and start scripts for running it
+/// outside the launcher.
+///
+/// For [`ServerSoftware::Paper`] and [`ServerSoftware::Purpur`]
+/// the jar is their newest build for `version`.
+///
+/// The EULA starts out unaccepted, see
+/// [`server_files::set_eula_accepted`].
+///
+/// If `cancel` is cancelled, nothing is left behind
+/// and this errors with [`DownloadError::Cancelled`].
+pub async fn create_server(
+    server_name: &str,
+    version: &str,
+    software: ServerSoftware,
+    progress_sender: Option<ProgressSender<DownloadProgress>>,
+    cancel: Option<&CancelToken>,
+) -> Result<(), DownloadError> {
+    info!("Started creating server.");
+    if let Some(ref sender) = progress_sender {
+        sender.send(DownloadProgress::Started)?;
+    }
+
+    let servers_dir = file_utils::get_launcher_dir()?.join("servers");
+    let server_dir = servers_dir.join(server_name);
+    if server_dir.exists() {
+        return Err(DownloadError::InstanceAlreadyExists);
+    }
+
+    let client = file_utils::create_client();
+    let version_json = GameDownloader::new_download_version_json(
+        &client,
+        version,
+        VersionSource::Mojang,
+        &progress_sender,
+    )
+    .await?;
+    // Some old versions only have a client. This is checked
+    // before making the folder, so nothing is left behind.
+    let Some(server_download) = &version_json.downloads.server else {
+        return Err(DownloadError::NoServerJar(version.to_owned()));
+    };
+    let jar_url = server_software::get_jar_url(&client, software, version)
+        .await?
+        .unwrap_or_else(|| server_download.url.clone());
+    cancel::check(cancel)?;
+
+    std::fs::create_dir_all(&server_dir).map_err(io_err!(server_dir))?;
+
+    info!("Downloading server jar file.");
+    if let Some(ref sender) = progress_sender {
+        sender.send(DownloadProgress::DownloadingJar)?;
+    }
+    let jar = file_utils::download_file_to_bytes(&client, &jar_url).await?;
+    if let Err(err) = cancel::check(cancel) {
+        info!("Server creation cancelled, removing {server_name}");
+        std::fs::remove_dir_all(&server_dir).map_err(io_err!(server_dir))?;
+        return Err(err.into());
+    }
+    let jar_path = server_dir.join("server.jar");
+    std::fs::write(&jar_path, jar).map_err(io_err!(jar_path))?;
+
+    let json_path = server_dir.join("details.json");
+    std::fs::write(&json_path, serde_json::to_string(&version_json)?)
+        .map_err(io_err!(json_path))?;
+
+    let config = InstanceConfigJson {
+        java_override: None,
+        java_version: None,
+        ram_in_mb: DEFAULT_RAM_MB_FOR_INSTANCE,
+        initial_ram_in_mb: None,
+        mod_type: software.name().to_owned(),
+        last_played: None,
+        last_username: None,
+        last_profile: None,
+        last_java: None,
+        offline_profile: None,
+        java_args: Vec::new(),
+        world_backup_retention: default_world_backup_retention(),
+        backup_worlds_on_version_change: false,
+        snapshot_small_files: true,
+        log4shell_mitigation: true,
+        sync_folder: false,
+        last_launched_version: None,
+        game_dir: None,
+        version_type: None,
+        version_source: VersionSource::Mojang,
+        custom_version_json: None,
+        mods: Vec::new(),
+        config_version: InstanceConfigJson::VERSION,
+    };
+    let config_path = server_dir.join("config.json");
+    config.save(&config_path)?;
+
+    server_files::write_eula(&server_dir.join("eula.txt"), false)?;
+    // Java isn't downloaded until the first launch, so until
+    // then the scripts use whichever Java is on the PATH.
+    server_launch::write_start_scripts(&server_dir, Path::new("java"), &config)?;
+
+    Ok(())
+}
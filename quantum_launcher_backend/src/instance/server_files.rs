@@ -0,0 +1,434 @@
+//! The files a Minecraft server reads its settings from:
+//! `eula.txt` and `server.properties`.
+//!
+//! Both are in the Java properties format, and the server
+//! rewrites them on startup, so lines are kept as they are
+//! and only the changed keys are touched.
+
+use std::{
+    fmt::{Display, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{ErrorCode, HasErrorCode, IoError, LauncherError, LauncherResult},
+    io_err,
+};
+
+use super::instance_list::get_instances_dir;
+
+/// Where Mojang's EULA can be read.
+pub const EULA_URL: &str = "https://aka.ms/MinecraftEULA";
+
+/// Gets the folder of a server, erroring if it doesn't exist.
+pub fn get_server_dir(server_name: &str) -> LauncherResult<PathBuf> {
+    if server_name.is_empty() {
+        return Err(LauncherError::InstanceNotFound);
+    }
+    let server_dir = get_instances_dir("servers")?.join(server_name);
+    if !server_dir.is_dir() {
+        return Err(LauncherError::InstanceNotFound);
+    }
+    Ok(server_dir)
+}
+
+/// Whether `eula=true` is set in the server's `eula.txt`.
+/// The server refuses to start otherwise.
+pub fn is_eula_accepted(server_name: &str) -> LauncherResult<bool> {
+    let properties = PropertiesFile::load(&get_server_dir(server_name)?.join("eula.txt"))?;
+    Ok(properties
+        .get("eula")
+        .is_some_and(|value| value.eq_ignore_ascii_case("true")))
+}
+
+/// Writes the user's answer to the EULA into `eula.txt`.
+///
+/// Only call this with `true` after showing the user
+/// the EULA (see [`EULA_URL`]) and asking them.
+pub fn set_eula_accepted(server_name: &str, accepted: bool) -> LauncherResult<()> {
+    let path = get_server_dir(server_name)?.join("eula.txt");
+    write_eula(&path, accepted)?;
+    Ok(())
+}
+
+pub(super) fn write_eula(path: &Path, accepted: bool) -> Result<(), IoError> {
+    let mut properties = PropertiesFile::load(path)?;
+    if properties.lines.is_empty() {
+        properties.lines.push(format!(
+            "#By changing the setting below to TRUE you are indicating your agreement to our EULA ({EULA_URL})."
+        ));
+    }
+    properties.set("eula", if accepted { "true" } else { "false" });
+    properties.save(path)
+}
+
+/// The server's `server.properties` file.
+///
+/// It doesn't exist until the server first starts,
+/// in which case it's empty here and created on save.
+pub struct ServerProperties {
+    file: PropertiesFile,
+}
+
+impl ServerProperties {
+    pub fn load(server_name: &str) -> LauncherResult<Self> {
+        let path = get_server_dir(server_name)?.join("server.properties");
+        Ok(Self {
+            file: PropertiesFile::load(&path)?,
+        })
+    }
+
+    pub fn save(&self, server_name: &str) -> LauncherResult<()> {
+        let path = get_server_dir(server_name)?.join("server.properties");
+        self.file.save(&path)?;
+        Ok(())
+    }
+
+    /// Gets a value, with escapes like `\u00e9` decoded.
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.file.get(key).map(unescape)
+    }
+
+    /// Sets a value, escaping it so the server reads it back the same.
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.file.set(key, &escape(value));
+    }
+
+    /// The settings that have an editor in the launcher. Missing
+    /// or invalid ones are the server's defaults, like it does.
+    pub fn settings(&self) -> ServerSettings {
+        let defaults = ServerSettings::default();
+        let get = |key| self.get(key);
+        ServerSettings {
+            port: get("server-port")
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(defaults.port),
+            gamemode: get("gamemode")
+                .and_then(|n| GameMode::from_property(&n))
+                .unwrap_or(defaults.gamemode),
+            difficulty: get("difficulty")
+                .and_then(|n| Difficulty::from_property(&n))
+                .unwrap_or(defaults.difficulty),
+            online_mode: get("online-mode")
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(defaults.online_mode),
+            motd: get("motd").unwrap_or(defaults.motd),
+            view_distance: get("view-distance")
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(defaults.view_distance),
+        }
+    }
+
+    pub fn set_settings(&mut self, settings: &ServerSettings) -> Result<(), ServerSettingError> {
+        settings.validate()?;
+        self.set("server-port", &settings.port.to_string());
+        self.set("gamemode", settings.gamemode.property());
+        self.set("difficulty", settings.difficulty.property());
+        self.set("online-mode", &settings.online_mode.to_string());
+        self.set("motd", &settings.motd);
+        self.set("view-distance", &settings.view_distance.to_string());
+        Ok(())
+    }
+
+    /// The whole file as text, for editing it by hand.
+    pub fn to_raw(&self) -> String {
+        self.file.lines.join("\n")
+    }
+
+    /// Reads a hand-edited file from [`ServerProperties::to_raw`].
+    pub fn from_raw(raw: &str) -> Self {
+        Self {
+            file: PropertiesFile {
+                lines: raw.lines().map(ToOwned::to_owned).collect(),
+            },
+        }
+    }
+}
+
+/// The most commonly changed settings in `server.properties`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerSettings {
+    pub port: u16,
+    pub gamemode: GameMode,
+    pub difficulty: Difficulty,
+    /// Whether players have to log in with a Microsoft account.
+    pub online_mode: bool,
+    /// The message shown in the multiplayer server list.
+    pub motd: String,
+    /// In chunks.
+    pub view_distance: u8,
+}
+
+impl Default for ServerSettings {
+    /// The defaults of the vanilla server.
+    fn default() -> Self {
+        Self {
+            port: 25565,
+            gamemode: GameMode::Survival,
+            difficulty: Difficulty::Easy,
+            online_mode: true,
+            motd: "A Minecraft Server".to_owned(),
+            view_distance: 10,
+        }
+    }
+}
+
+impl ServerSettings {
+    pub const VIEW_DISTANCE_RANGE: std::ops::RangeInclusive<u8> = 3..=32;
+
+    pub fn validate(&self) -> Result<(), ServerSettingError> {
+        parse_port(&self.port.to_string())?;
+        parse_view_distance(&self.view_distance.to_string())?;
+        validate_motd(&self.motd)
+    }
+}
+
+/// Parses a port typed in by the user.
+pub fn parse_port(input: &str) -> Result<u16, ServerSettingError> {
+    match input.trim().parse::<u16>() {
+        Ok(port) if port != 0 => Ok(port),
+        _ => Err(ServerSettingError::Port(input.to_owned())),
+    }
+}
+
+/// Parses a view distance typed in by the user.
+pub fn parse_view_distance(input: &str) -> Result<u8, ServerSettingError> {
+    match input.trim().parse::<u8>() {
+        Ok(distance) if ServerSettings::VIEW_DISTANCE_RANGE.contains(&distance) => Ok(distance),
+        _ => Err(ServerSettingError::ViewDistance(input.to_owned())),
+    }
+}
+
+pub fn validate_motd(motd: &str) -> Result<(), ServerSettingError> {
+    if motd.contains(['\n', '\r']) {
+        return Err(ServerSettingError::MotdLineBreak);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    Survival,
+    Creative,
+    Adventure,
+    Spectator,
+}
+
+impl GameMode {
+    pub const ALL: [Self; 4] = [
+        Self::Survival,
+        Self::Creative,
+        Self::Adventure,
+        Self::Spectator,
+    ];
+
+    pub fn property(self) -> &'static str {
+        match self {
+            GameMode::Survival => "survival",
+            GameMode::Creative => "creative",
+            GameMode::Adventure => "adventure",
+            GameMode::Spectator => "spectator",
+        }
+    }
+
+    /// Old servers used numbers, so those are read too.
+    fn from_property(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .enumerate()
+            .find(|(i, mode)| value == mode.property() || value == i.to_string())
+            .map(|(_, mode)| mode)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Peaceful,
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Self; 4] = [Self::Peaceful, Self::Easy, Self::Normal, Self::Hard];
+
+    pub fn property(self) -> &'static str {
+        match self {
+            Difficulty::Peaceful => "peaceful",
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    /// Old servers used numbers, so those are read too.
+    fn from_property(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .enumerate()
+            .find(|(i, difficulty)| value == difficulty.property() || value == i.to_string())
+            .map(|(_, difficulty)| difficulty)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerSettingError {
+    Port(String),
+    ViewDistance(String),
+    MotdLineBreak,
+}
+
+impl Display for ServerSettingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServerSettingError::Port(input) => {
+                write!(f, "invalid port {input:?}, it must be from 1 to 65535")
+            }
+            ServerSettingError::ViewDistance(input) => write!(
+                f,
+                "invalid view distance {input:?}, it must be from {} to {}",
+                ServerSettings::VIEW_DISTANCE_RANGE.start(),
+                ServerSettings::VIEW_DISTANCE_RANGE.end()
+            ),
+            ServerSettingError::MotdLineBreak => {
+                write!(f, "the message of the day can't have line breaks")
+            }
+        }
+    }
+}
+
+impl HasErrorCode for ServerSettingError {
+    fn code(&self) -> ErrorCode {
+        ErrorCode::SERVER_SETTING_INVALID
+    }
+}
+
+/// A `key=value` file, with comments and ordering kept intact.
+struct PropertiesFile {
+    lines: Vec<String>,
+}
+
+impl PropertiesFile {
+    fn load(path: &Path) -> Result<Self, IoError> {
+        if !path.exists() {
+            return Ok(Self { lines: Vec::new() });
+        }
+        let contents = std::fs::read_to_string(path).map_err(io_err!(path))?;
+        Ok(Self {
+            lines: contents.lines().map(ToOwned::to_owned).collect(),
+        })
+    }
+
+    fn save(&self, path: &Path) -> Result<(), IoError> {
+        let mut contents = self.lines.join("\n");
+        contents.push('\n');
+        std::fs::write(path, contents).map_err(io_err!(path))
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.lines.iter().find_map(|line| parse_line(line, key))
+    }
+
+    fn set(&mut self, key: &str, value: &str) {
+        let new_line = format!("{key}={value}");
+        match self
+            .lines
+            .iter_mut()
+            .find(|line| parse_line(line, key).is_some())
+        {
+            Some(line) => *line = new_line,
+            None => self.lines.push(new_line),
+        }
+    }
+}
+
+/// Escapes a value like Java's `Properties.store`, as the
+/// server reads the file as Latin-1 with `\uXXXX` escapes.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for (i, c) in value.chars().enumerate() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            // Leading spaces would be trimmed otherwise.
+            ' ' if i == 0 => escaped.push_str("\\ "),
+            ' '..='~' => escaped.push(c),
+            _ => {
+                let mut utf16 = [0; 2];
+                for unit in c.encode_utf16(&mut utf16) {
+                    _ = write!(escaped, "\\u{unit:04x}");
+                }
+            }
+        }
+    }
+    escaped
+}
+
+fn unescape(value: &str) -> String {
+    let mut utf16 = Vec::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 2];
+            utf16.extend_from_slice(c.encode_utf16(&mut buf));
+            continue;
+        }
+        let unit = match chars.next() {
+            Some('n') => '\n' as u16,
+            Some('r') => '\r' as u16,
+            Some('t') => '\t' as u16,
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                u16::from_str_radix(&hex, 16).unwrap_or(u16::from(b'?'))
+            }
+            Some(c) => {
+                let mut buf = [0; 2];
+                utf16.extend_from_slice(c.encode_utf16(&mut buf));
+                continue;
+            }
+            None => break,
+        };
+        utf16.push(unit);
+    }
+    String::from_utf16_lossy(&utf16)
+}
+
+/// Gets the value of `line` if it sets `key`.
+fn parse_line<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let line = line.trim_start();
+    if line.starts_with('#') || line.starts_with('!') {
+        return None;
+    }
+    let (line_key, value) = line.split_once('=')?;
+    (line_key.trim() == key).then_some(value.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_properties_escape_round_trip() {
+        let mut properties = ServerProperties::from_raw("#Minecraft server properties\nmotd=Old");
+        let motd = " Café \\o/ ☃";
+        properties.set("motd", motd);
+
+        assert_eq!(
+            properties.to_raw(),
+            "#Minecraft server properties\nmotd=\\ Caf\\u00e9 \\\\o/ \\u2603"
+        );
+        assert_eq!(properties.get("motd").as_deref(), Some(motd));
+    }
+
+    #[test]
+    fn test_settings_read_legacy_numbers() {
+        let properties = ServerProperties::from_raw("gamemode=1\ndifficulty=3\nserver-port=x");
+        let settings = properties.settings();
+
+        assert_eq!(settings.gamemode, GameMode::Creative);
+        assert_eq!(settings.difficulty, Difficulty::Hard);
+        assert_eq!(settings.port, ServerSettings::default().port);
+    }
+}
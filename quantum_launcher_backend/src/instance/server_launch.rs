@@ -0,0 +1,175 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::{Arc, Mutex},
+};
+
+use tracing::{debug, error, info};
+
+use crate::{
+    cancel::CancelToken,
+    error::{IoError, LaunchContext, LaunchPhase, LauncherError, LauncherResult},
+    file_utils, io_err,
+    java_install::{self, JavaInstallMessage},
+    json_structs::{json_instance_config::InstanceConfigJson, json_version::VersionDetails},
+    progress::ProgressSender,
+};
+
+use super::{
+    instance_launch::{
+        get_config, join_arguments, read_version_json, save_last_played, GameLaunchResult,
+    },
+    server_files,
+};
+
+/// Wraps [`launch_server`] to make it easier to use
+/// with the iced GUI toolkit, like [`crate::launch_wrapped`].
+pub async fn launch_server_wrapped(
+    server_name: String,
+    java_install_progress_sender: Option<ProgressSender<JavaInstallMessage>>,
+    cancel: Option<CancelToken>,
+) -> GameLaunchResult {
+    match launch_server(&server_name, java_install_progress_sender, cancel.as_ref()).await {
+        Ok(child) => GameLaunchResult::Ok(Arc::new(Mutex::new(child))),
+        Err(err) => GameLaunchResult::Err(err.into()),
+    }
+}
+
+/// Starts the specified server, in its own folder.
+///
+/// Like [`crate::launch`], this downloads the required
+/// version of Java if it's not already installed.
+///
+/// Errors with [`LauncherError::EulaNotAccepted`] if the
+/// EULA hasn't been accepted, as the server would
+/// just exit right away.
+///
+/// All of stdin, stdout and stderr are piped, so the caller
+/// can run commands and must read the output, or the server
+/// will hang once the pipe fills up.
+pub async fn launch_server(
+    server_name: &str,
+    java_install_progress_sender: Option<ProgressSender<JavaInstallMessage>>,
+    cancel: Option<&CancelToken>,
+) -> LauncherResult<Child> {
+    let (server_dir, mut config_json, version_json) = file_utils::run_blocking({
+        let server_name = server_name.to_owned();
+        move || read_server(&server_name)
+    })
+    .await?;
+    let config_path = server_dir.join("config.json");
+
+    let java = match &config_json.java_override {
+        Some(java_override) if !java_override.is_empty() => PathBuf::from(java_override),
+        _ => {
+            let version = config_json.get_java_version(&version_json);
+            java_install::get_java(version, java_install_progress_sender, cancel)
+                .await
+                .in_phase(LaunchPhase::Java, None)?
+        }
+    };
+
+    // Kept up to date with the settings, so starting
+    // the server without the launcher works the same.
+    write_start_scripts(&server_dir, &java, &config_json)?;
+
+    let arguments = get_arguments(&config_json);
+    info!("Starting server {server_name}");
+    debug!(
+        "Server command: {} {}",
+        java.display(),
+        join_arguments(&arguments)
+    );
+
+    let child = Command::new(&java)
+        .args(&arguments)
+        .current_dir(&server_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(LauncherError::CommandError)
+        .in_phase(LaunchPhase::Spawn, Some(&java))?;
+
+    if let Err(err) = save_last_played(&mut config_json, &config_path) {
+        error!("Could not save last played time: {err}");
+    }
+
+    Ok(child)
+}
+
+/// Finds the server and reads its config and version
+/// details, checking that the EULA was accepted.
+fn read_server(server_name: &str) -> LauncherResult<(PathBuf, InstanceConfigJson, VersionDetails)> {
+    let server_dir = server_files::get_server_dir(server_name)?;
+    if !server_files::is_eula_accepted(server_name)? {
+        return Err(LauncherError::EulaNotAccepted);
+    }
+
+    let config_json = get_config(&server_dir).in_phase(
+        LaunchPhase::ReadConfig,
+        Some(&server_dir.join("config.json")),
+    )?;
+    let version_json = read_version_json(&server_dir).in_phase(
+        LaunchPhase::ReadVersionJson,
+        Some(&server_dir.join("details.json")),
+    )?;
+    Ok((server_dir, config_json, version_json))
+}
+
+/// Runs a console command on a server started with
+/// [`launch_server`], like `say Hello` or `op Steve`.
+///
+/// `stdin` is taken from the server's [`Child`].
+pub fn send_server_command(stdin: &mut ChildStdin, command: &str) -> std::io::Result<()> {
+    writeln!(stdin, "{}", command.trim())?;
+    stdin.flush()
+}
+
+/// Asks the server to save its worlds and shut down.
+///
+/// Prefer this to killing the server, which can
+/// corrupt any chunks that were being saved.
+pub fn stop_server(stdin: &mut ChildStdin) -> std::io::Result<()> {
+    send_server_command(stdin, "stop")
+}
+
+fn get_arguments(config_json: &InstanceConfigJson) -> Vec<String> {
+    let [initial_ram, max_ram] = config_json.get_ram_arguments();
+    vec![
+        initial_ram,
+        max_ram,
+        "-jar".to_owned(),
+        "server.jar".to_owned(),
+        // The launcher has its own console.
+        "nogui".to_owned(),
+    ]
+}
+
+/// Writes `start.sh` and `start.bat`, for starting
+/// the server without the launcher.
+pub(super) fn write_start_scripts(
+    server_dir: &Path,
+    java: &Path,
+    config_json: &InstanceConfigJson,
+) -> Result<(), IoError> {
+    let java = java.to_string_lossy();
+    let arguments = get_arguments(config_json).join(" ");
+
+    let sh_path = server_dir.join("start.sh");
+    let sh = format!("#!/bin/sh\ncd \"$(dirname \"$0\")\"\n\"{java}\" {arguments}\n");
+    std::fs::write(&sh_path, sh).map_err(io_err!(sh_path))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&sh_path, std::fs::Permissions::from_mode(0o755))
+            .map_err(io_err!(sh_path))?;
+    }
+
+    let bat_path = server_dir.join("start.bat");
+    let bat = format!("@echo off\r\ncd /d \"%~dp0\"\r\n\"{java}\" {arguments}\r\npause\r\n");
+    std::fs::write(&bat_path, bat).map_err(io_err!(bat_path))?;
+
+    Ok(())
+}
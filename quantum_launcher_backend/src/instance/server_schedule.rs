@@ -0,0 +1,202 @@
+//! Backing up a server's worlds, and the schedule
+//! the launcher backs up and restarts servers on.
+
+use std::{
+    fmt::Display,
+    fs::File,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use zip::{result::ZipError, ZipWriter};
+
+use crate::{
+    error::{HasErrorCode, IoError, LauncherResult},
+    file_utils, io_err,
+};
+
+use super::server_files::{get_server_dir, ServerProperties};
+
+/// When a server running in the launcher is backed
+/// up and restarted, saved in `schedule.json`.
+///
+/// The intervals count from when the server started,
+/// and nothing is done while the launcher is closed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerSchedule {
+    /// Minutes between backups, `None` for no backups.
+    #[serde(default)]
+    pub backup_interval_minutes: Option<u32>,
+    /// How many backups to keep. The oldest ones are deleted.
+    #[serde(default = "default_backup_retention")]
+    pub backup_retention: usize,
+    /// Minutes between restarts, `None` for no restarts.
+    #[serde(default)]
+    pub restart_interval_minutes: Option<u32>,
+}
+
+fn default_backup_retention() -> usize {
+    5
+}
+
+impl Default for ServerSchedule {
+    fn default() -> Self {
+        Self {
+            backup_interval_minutes: None,
+            backup_retention: default_backup_retention(),
+            restart_interval_minutes: None,
+        }
+    }
+}
+
+impl ServerSchedule {
+    pub fn load(server_name: &str) -> LauncherResult<Self> {
+        let path = get_server_dir(server_name)?.join("schedule.json");
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let json = std::fs::read_to_string(&path).map_err(io_err!(path))?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn save(&self, server_name: &str) -> LauncherResult<()> {
+        let path = get_server_dir(server_name)?.join("schedule.json");
+        std::fs::write(&path, serde_json::to_string_pretty(self)?).map_err(io_err!(path))?;
+        Ok(())
+    }
+
+    pub fn backup_interval(&self) -> Option<Duration> {
+        minutes(self.backup_interval_minutes)
+    }
+
+    pub fn restart_interval(&self) -> Option<Duration> {
+        minutes(self.restart_interval_minutes)
+    }
+}
+
+fn minutes(minutes: Option<u32>) -> Option<Duration> {
+    minutes
+        .filter(|n| *n > 0)
+        .map(|n| Duration::from_secs(u64::from(n) * 60))
+}
+
+pub async fn backup_worlds_wrapped(server_name: String) -> Result<PathBuf, String> {
+    file_utils::run_blocking(move || backup_worlds(&server_name))
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Zips the server's worlds into its `backups` folder, then
+/// deletes the oldest backups past the schedule's retention.
+///
+/// If the server is running, saving should be turned off
+/// with `save-off` and `save-all flush` first, so the files
+/// don't change while they're being copied.
+pub fn backup_worlds(server_name: &str) -> Result<PathBuf, BackupError> {
+    let server_dir = get_server_dir(server_name)?;
+    let level_name = ServerProperties::load(server_name)?
+        .get("level-name")
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| "world".to_owned());
+
+    // Paper and Purpur keep the other dimensions in
+    // their own folders, next to the main world.
+    let worlds: Vec<String> = ["", "_nether", "_the_end"]
+        .iter()
+        .map(|suffix| format!("{level_name}{suffix}"))
+        .filter(|world| server_dir.join(world).is_dir())
+        .collect();
+    if worlds.is_empty() {
+        return Err(BackupError::NoWorld(level_name));
+    }
+
+    let backups_dir = server_dir.join("backups");
+    std::fs::create_dir_all(&backups_dir).map_err(io_err!(backups_dir))?;
+    let backup_path = backups_dir.join(format!(
+        "{}.zip",
+        file_utils::format_timestamp(SystemTime::now())
+    ));
+
+    info!("Backing up {server_name} to {backup_path:?}");
+    let file = File::create(&backup_path).map_err(io_err!(backup_path))?;
+    let mut zip = ZipWriter::new(file);
+    for world in &worlds {
+        // The lock is held by the running server, and isn't
+        // needed to restore the world.
+        file_utils::add_dir_to_zip::<BackupError>(
+            &mut zip,
+            &server_dir,
+            &server_dir.join(world),
+            &["session.lock"],
+        )?;
+    }
+    zip.finish()?;
+
+    let retention = ServerSchedule::load(server_name)?.backup_retention;
+    delete_old_backups(&backups_dir, retention.max(1))?;
+    Ok(backup_path)
+}
+
+pub(super) fn delete_old_backups(backups_dir: &Path, retention: usize) -> Result<(), IoError> {
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(backups_dir)
+        .map_err(io_err!(backups_dir))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|n| n == "zip"))
+        .collect();
+    // The names are dates, so this is oldest first.
+    backups.sort();
+
+    let excess = backups.len().saturating_sub(retention);
+    for backup in &backups[..excess] {
+        info!("Deleting old backup {backup:?}");
+        std::fs::remove_file(backup).map_err(io_err!(backup))?;
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum BackupError {
+    Launcher(crate::error::LauncherError),
+    Io(IoError),
+    Zip(ZipError),
+    NoWorld(String),
+    /// A zip file that isn't a backup of the world it's for.
+    InvalidBackup(PathBuf),
+}
+
+impl From<crate::error::LauncherError> for BackupError {
+    fn from(value: crate::error::LauncherError) -> Self {
+        Self::Launcher(value)
+    }
+}
+
+impl From<IoError> for BackupError {
+    fn from(value: IoError) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<ZipError> for BackupError {
+    fn from(value: ZipError) -> Self {
+        Self::Zip(value)
+    }
+}
+
+impl Display for BackupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackupError::Launcher(err) => write!(f, "world backup failed: {err}"),
+            BackupError::Io(err) => write!(f, "world backup failed: {err}"),
+            BackupError::Zip(err) => write!(f, "world backup failed: zip error: {err}"),
+            BackupError::NoWorld(level_name) => write!(
+                f,
+                "world backup failed: there's no world {level_name:?} yet"
+            ),
+            BackupError::InvalidBackup(path) => {
+                write!(f, "{path:?} is not a backup of this world")
+            }
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Servers other than Mojang's own, which can run plugins.
+//!
+//! [Paper](https://papermc.io) and [Purpur](https://purpurmc.org)
+//! are downloaded from their own APIs instead of Mojang's.
+
+use std::fmt::Display;
+
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::{
+    download::DownloadError, error::HasErrorCode, file_utils, json_structs::JsonDownloadError,
+};
+
+const PAPER_URL: &str = "https://api.papermc.io/v2/projects/paper";
+const PURPUR_URL: &str = "https://api.purpurmc.org/v2/purpur";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ServerSoftware {
+    #[default]
+    Vanilla,
+    Paper,
+    Purpur,
+}
+
+impl ServerSoftware {
+    pub const ALL: [Self; 3] = [Self::Vanilla, Self::Paper, Self::Purpur];
+
+    /// The name saved as the `mod_type` of the server's config.
+    pub fn name(self) -> &'static str {
+        match self {
+            ServerSoftware::Vanilla => "Vanilla",
+            ServerSoftware::Paper => "Paper",
+            ServerSoftware::Purpur => "Purpur",
+        }
+    }
+
+    pub fn from_mod_type(mod_type: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|n| n.name() == mod_type)
+    }
+
+    pub fn supports_plugins(self) -> bool {
+        self != ServerSoftware::Vanilla
+    }
+
+    /// The Modrinth loaders of plugins that run on this.
+    /// Purpur is a fork of Paper, which is a fork of Spigot,
+    /// so each runs the plugins of the ones before it.
+    pub(super) fn plugin_loaders(self) -> &'static [&'static str] {
+        match self {
+            ServerSoftware::Vanilla => &[],
+            ServerSoftware::Paper => &["paper", "spigot", "bukkit"],
+            ServerSoftware::Purpur => &["purpur", "paper", "spigot", "bukkit"],
+        }
+    }
+}
+
+impl Display for ServerSoftware {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Deserialize)]
+struct ProjectJson {
+    versions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct PaperBuildsJson {
+    builds: Vec<PaperBuild>,
+}
+
+#[derive(Deserialize)]
+struct PaperBuild {
+    build: u32,
+    channel: String,
+    downloads: PaperDownloads,
+}
+
+#[derive(Deserialize)]
+struct PaperDownloads {
+    application: PaperDownload,
+}
+
+#[derive(Deserialize)]
+struct PaperDownload {
+    name: String,
+}
+
+pub async fn list_versions_wrapped(
+    software: ServerSoftware,
+) -> Result<Option<Vec<String>>, String> {
+    list_versions(software)
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// The Minecraft versions `software` has builds for, newest first.
+///
+/// `None` for vanilla, which has every version.
+pub async fn list_versions(
+    software: ServerSoftware,
+) -> Result<Option<Vec<String>>, JsonDownloadError> {
+    let url = match software {
+        ServerSoftware::Vanilla => return Ok(None),
+        ServerSoftware::Paper => PAPER_URL,
+        ServerSoftware::Purpur => PURPUR_URL,
+    };
+
+    let client = file_utils::create_client();
+    let json = file_utils::download_file_to_string(&client, url).await?;
+    let project: ProjectJson = serde_json::from_str(&json)?;
+    // Both APIs list the oldest version first.
+    Ok(Some(project.versions.into_iter().rev().collect()))
+}
+
+/// Gets the URL of the newest server jar of `software` for
+/// `version`, or `None` for vanilla, which comes from Mojang.
+pub(super) async fn get_jar_url(
+    client: &Client,
+    software: ServerSoftware,
+    version: &str,
+) -> Result<Option<String>, DownloadError> {
+    let no_build = || DownloadError::NoServerSoftwareBuild {
+        software,
+        version: version.to_owned(),
+    };
+
+    match software {
+        ServerSoftware::Vanilla => Ok(None),
+        ServerSoftware::Paper => {
+            let builds_url = format!("{PAPER_URL}/versions/{version}/builds");
+            let builds = match file_utils::download_file_to_string(client, &builds_url).await {
+                Ok(builds) => builds,
+                Err(file_utils::RequestError::DownloadError { code, .. })
+                    if code == reqwest::StatusCode::NOT_FOUND =>
+                {
+                    return Err(no_build())
+                }
+                Err(err) => return Err(err.into()),
+            };
+            let builds: PaperBuildsJson = serde_json::from_str(&builds)?;
+
+            // Builds are listed oldest first. Experimental
+            // builds are only used if there's nothing else.
+            let build = builds
+                .builds
+                .iter()
+                .rev()
+                .find(|build| build.channel == "default")
+                .or(builds.builds.last())
+                .ok_or_else(no_build)?;
+            Ok(Some(format!(
+                "{PAPER_URL}/versions/{version}/builds/{}/downloads/{}",
+                build.build, build.downloads.application.name
+            )))
+        }
+        ServerSoftware::Purpur => {
+            let versions = list_versions(software).await?.unwrap_or_default();
+            if !versions.iter().any(|n| n == version) {
+                return Err(no_build());
+            }
+            Ok(Some(format!("{PURPUR_URL}/{version}/latest/download")))
+        }
+    }
+}
@@ -0,0 +1,337 @@
+//! Snapshots of an instance taken before changing its loader
+//! or version, so that a broken change can be rolled back.
+//!
+//! Each snapshot is a folder in
+//! `instances/<instance>/snapshots/`, named by when it was
+//! made, holding the instance's JSON files, the game's
+//! options and configs, and the names of its mods. Mods no
+//! bigger than [`SMALL_FILE_LIMIT`] are copied too, unless
+//! the instance turns that off. Only the newest
+//! [`RETENTION`] snapshots are kept.
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+use crate::{
+    error::{HasErrorCode, IoError, LauncherError},
+    file_utils, io_err,
+};
+
+use super::{
+    instance_launch::{get_config, get_game_dir, get_instance_dir},
+    instance_mod_installer::fabric::{self, FabricInstallError},
+};
+
+/// How many snapshots of each instance to keep.
+pub const RETENTION: usize = 5;
+/// Mods bigger than this (in bytes) are only
+/// listed in a snapshot, not copied.
+pub const SMALL_FILE_LIMIT: u64 = 8 * 1024 * 1024;
+
+const INFO_FILE: &str = "snapshot.json";
+
+/// What the instance was snapshotted before.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotReason {
+    LoaderInstall,
+    LoaderUpdate,
+    LoaderUninstall,
+    VersionJsonRedownload,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotJson {
+    reason: SnapshotReason,
+    /// The installed Fabric version, if any.
+    loader: Option<String>,
+    /// Every file in the `mods` folder, copied or not.
+    mods: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub path: PathBuf,
+    pub reason: SnapshotReason,
+    pub loader: Option<String>,
+    pub mods: usize,
+}
+
+fn get_snapshots_dir(instance_name: &str) -> Result<PathBuf, SnapshotError> {
+    Ok(get_instance_dir(instance_name)?.join("snapshots"))
+}
+
+/// Lists the instance's snapshots, newest first.
+pub fn list(instance_name: &str) -> Result<Vec<Snapshot>, SnapshotError> {
+    let snapshots_dir = get_snapshots_dir(instance_name)?;
+    if !snapshots_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(&snapshots_dir)
+        .map_err(io_err!(snapshots_dir))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.join(INFO_FILE).is_file())
+        .collect();
+    // The names are dates, so this is newest first.
+    paths.sort_by(|a, b| b.cmp(a));
+
+    Ok(paths
+        .into_iter()
+        .filter_map(|path| match read_info(&path) {
+            Ok(info) => Some(Snapshot {
+                reason: info.reason,
+                loader: info.loader,
+                mods: info.mods.len(),
+                path,
+            }),
+            Err(err) => {
+                error!("Could not read snapshot {path:?}: {err}");
+                None
+            }
+        })
+        .collect())
+}
+
+fn read_info(snapshot: &Path) -> Result<SnapshotJson, SnapshotError> {
+    let path = snapshot.join(INFO_FILE);
+    let json = std::fs::read_to_string(&path).map_err(io_err!(path))?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// Like [`take`], but only logs failures, so that
+/// the change it comes before still happens.
+pub(crate) fn take_or_log(instance_name: &str, reason: SnapshotReason) {
+    if let Err(err) = take(instance_name, reason) {
+        error!("Could not snapshot {instance_name}: {err}");
+    }
+}
+
+/// Snapshots the instance, then deletes the
+/// oldest snapshots past [`RETENTION`].
+pub fn take(instance_name: &str, reason: SnapshotReason) -> Result<PathBuf, SnapshotError> {
+    let instance_dir = get_instance_dir(instance_name)?;
+    let game_dir = get_game_dir(instance_name)?;
+    let copy_mods = get_config(&instance_dir)
+        .map_err(LauncherError::from)?
+        .snapshot_small_files;
+
+    let snapshots_dir = get_snapshots_dir(instance_name)?;
+    let snapshot = snapshots_dir.join(file_utils::format_timestamp(SystemTime::now()));
+    info!("Snapshotting {instance_name} to {snapshot:?}");
+
+    let saved_instance_dir = snapshot.join("instance");
+    std::fs::create_dir_all(&saved_instance_dir).map_err(io_err!(saved_instance_dir))?;
+    for path in list_files(&instance_dir)? {
+        if path.extension().is_some_and(|n| n == "json") {
+            copy_file(&path, &saved_instance_dir)?;
+        }
+    }
+
+    let saved_game_dir = snapshot.join("game");
+    std::fs::create_dir_all(&saved_game_dir).map_err(io_err!(saved_game_dir))?;
+    let options_path = game_dir.join("options.txt");
+    if options_path.is_file() {
+        copy_file(&options_path, &saved_game_dir)?;
+    }
+    let config_dir = game_dir.join("config");
+    if config_dir.is_dir() {
+        file_utils::copy_dir_recursive(&config_dir, &saved_game_dir.join("config"))?;
+    }
+
+    let mods_dir = game_dir.join("mods");
+    let saved_mods_dir = saved_game_dir.join("mods");
+    let mut mods = Vec::new();
+    for path in list_files(&mods_dir)? {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        mods.push(name.to_owned());
+        let is_small = std::fs::metadata(&path).is_ok_and(|n| n.len() <= SMALL_FILE_LIMIT);
+        if copy_mods && is_small {
+            std::fs::create_dir_all(&saved_mods_dir).map_err(io_err!(saved_mods_dir))?;
+            copy_file(&path, &saved_mods_dir)?;
+        }
+    }
+    mods.sort();
+
+    let info = SnapshotJson {
+        reason,
+        loader: fabric::installed_version(instance_name),
+        mods,
+    };
+    let info_path = snapshot.join(INFO_FILE);
+    std::fs::write(&info_path, serde_json::to_string(&info)?).map_err(io_err!(info_path))?;
+
+    delete_old_snapshots(&snapshots_dir)?;
+    Ok(snapshot)
+}
+
+fn delete_old_snapshots(snapshots_dir: &Path) -> Result<(), IoError> {
+    let mut snapshots: Vec<PathBuf> = std::fs::read_dir(snapshots_dir)
+        .map_err(io_err!(snapshots_dir))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    // The names are dates, so this is oldest first.
+    snapshots.sort();
+
+    let excess = snapshots.len().saturating_sub(RETENTION);
+    for snapshot in &snapshots[..excess] {
+        info!("Deleting old snapshot {snapshot:?}");
+        std::fs::remove_dir_all(snapshot).map_err(io_err!(snapshot))?;
+    }
+    Ok(())
+}
+
+/// The files (not folders) directly in `dir`,
+/// or nothing if it doesn't exist.
+fn list_files(dir: &Path) -> Result<Vec<PathBuf>, IoError> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    Ok(std::fs::read_dir(dir)
+        .map_err(io_err!(dir))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.is_file())
+        .collect())
+}
+
+fn copy_file(path: &Path, dest_dir: &Path) -> Result<(), IoError> {
+    let dest = dest_dir.join(path.file_name().unwrap_or_default());
+    std::fs::copy(path, &dest).map_err(io_err!(dest))?;
+    Ok(())
+}
+
+pub async fn roll_back_wrapped(
+    instance_name: String,
+    snapshot: PathBuf,
+) -> Result<Vec<String>, String> {
+    roll_back(&instance_name, &snapshot)
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Puts the instance back the way it was when the snapshot
+/// was taken: the loader, JSON files, options and configs.
+/// Mods added since are deleted, and removed ones are copied
+/// back if the snapshot has them.
+///
+/// Returns the mods that were removed since, but
+/// weren't copied, so have to be added back by hand.
+///
+/// The game must not be running.
+pub async fn roll_back(instance_name: &str, snapshot: &Path) -> Result<Vec<String>, SnapshotError> {
+    let snapshots_dir = get_snapshots_dir(instance_name)?;
+    if snapshot.parent() != Some(snapshots_dir.as_path()) {
+        return Err(SnapshotError::InvalidSnapshot(snapshot.to_owned()));
+    }
+    let info = read_info(snapshot)?;
+    let instance_dir = get_instance_dir(instance_name)?;
+    let game_dir = get_game_dir(instance_name)?;
+    info!("Rolling back {instance_name} to {snapshot:?}");
+
+    // The loader's libraries have to be removed
+    // before its JSON is replaced.
+    let loader = fabric::installed_version(instance_name);
+    if loader.is_some() && loader != info.loader {
+        fabric::remove_loader(instance_name)?;
+    }
+
+    for path in list_files(&snapshot.join("instance"))? {
+        copy_file(&path, &instance_dir)?;
+    }
+
+    if let Some(version) = &info.loader {
+        if loader.as_ref() != Some(version) {
+            fabric::install_loader(version, instance_name, None).await?;
+        }
+    }
+
+    let saved_game_dir = snapshot.join("game");
+    for path in list_files(&saved_game_dir)? {
+        copy_file(&path, &game_dir)?;
+    }
+    let saved_config_dir = saved_game_dir.join("config");
+    if saved_config_dir.is_dir() {
+        file_utils::copy_dir_recursive(&saved_config_dir, &game_dir.join("config"))?;
+    }
+
+    let mods_dir = game_dir.join("mods");
+    for path in list_files(&mods_dir)? {
+        let is_listed = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|name| info.mods.iter().any(|n| n == name));
+        if !is_listed {
+            info!("Deleting mod {path:?}, added after the snapshot");
+            std::fs::remove_file(&path).map_err(io_err!(path))?;
+        }
+    }
+
+    let saved_mods_dir = saved_game_dir.join("mods");
+    let mut missing = Vec::new();
+    for name in info.mods {
+        let saved = saved_mods_dir.join(&name);
+        if saved.is_file() {
+            std::fs::create_dir_all(&mods_dir).map_err(io_err!(mods_dir))?;
+            copy_file(&saved, &mods_dir)?;
+        } else if !mods_dir.join(&name).is_file() {
+            missing.push(name);
+        }
+    }
+    Ok(missing)
+}
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Launcher(LauncherError),
+    Io(IoError),
+    Json(serde_json::Error),
+    Fabric(FabricInstallError),
+    /// A folder that isn't one of the instance's snapshots.
+    InvalidSnapshot(PathBuf),
+}
+
+impl From<LauncherError> for SnapshotError {
+    fn from(value: LauncherError) -> Self {
+        Self::Launcher(value)
+    }
+}
+
+impl From<IoError> for SnapshotError {
+    fn from(value: IoError) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for SnapshotError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl From<FabricInstallError> for SnapshotError {
+    fn from(value: FabricInstallError) -> Self {
+        Self::Fabric(value)
+    }
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::Launcher(err) => write!(f, "instance snapshot failed: {err}"),
+            SnapshotError::Io(err) => write!(f, "instance snapshot failed: {err}"),
+            SnapshotError::Json(err) => write!(f, "instance snapshot failed: json error: {err}"),
+            SnapshotError::Fabric(err) => write!(f, "could not roll back the loader: {err}"),
+            SnapshotError::InvalidSnapshot(path) => {
+                write!(f, "{path:?} is not a snapshot of this instance")
+            }
+        }
+    }
+}
@@ -0,0 +1,392 @@
+//! Keeping an instance's user data in a `sync` folder, which
+//! Syncthing, Dropbox and the like can share between machines.
+//!
+//! The game doesn't run from the sync folder, as the sync tool
+//! would copy half-written files. Instead, [`pull`] copies the
+//! saves, options and mod list into the game directory before
+//! launching, and [`push`] copies them back once it exits.
+//!
+//! A lock file stops two machines from playing the instance at
+//! once. If both changed it anyway (say, one was offline), the
+//! last writer wins, and the other copy is kept in
+//! `sync/conflicts/` so nothing is lost.
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+use crate::{
+    error::{IoError, LauncherError},
+    events::{self, Event},
+    file_utils, io_err,
+    json_structs::json_instance_config::ManagedMod,
+};
+
+use super::instance_launch::{get_config, get_game_dir, get_instance_dir};
+
+/// The files and folders of the game directory that are synced.
+const SYNCED: &[&str] = &["saves", "options.txt", "servers.dat"];
+/// The instance's managed mods, so the other machine can
+/// see which ones it's missing. The jars aren't synced.
+const MODS_MANIFEST: &str = "mods.json";
+const STATE_FILE: &str = "sync.json";
+const LOCK_FILE: &str = "sync.lock";
+/// Which revision of the sync folder the game directory has.
+/// Kept in the instance folder, so it isn't synced.
+const BASE_FILE: &str = "sync_base.json";
+/// A lock older than this is from a machine that crashed
+/// or lost the instance, so it's ignored.
+const STALE_LOCK: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Who wrote the sync folder last.
+#[derive(Serialize, Deserialize, Default)]
+struct SyncState {
+    revision: u64,
+    machine: String,
+    /// Unix time, in seconds.
+    time: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SyncLock {
+    machine: String,
+    /// Unix time, in seconds.
+    since: u64,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct SyncBase {
+    revision: u64,
+    /// When the game directory was last pulled or pushed,
+    /// to tell if it changed since. Unix time, in seconds.
+    time: u64,
+}
+
+pub fn get_sync_dir(instance_name: &str) -> Result<PathBuf, SyncError> {
+    Ok(get_instance_dir(instance_name)?.join("sync"))
+}
+
+/// Copies the sync folder into the game directory if another
+/// machine changed it, and locks it. Does nothing unless the
+/// instance has `sync_folder` on.
+///
+/// Errors with [`SyncError::Locked`] if another machine is
+/// playing the instance.
+pub fn pull(instance_name: &str) -> Result<(), SyncError> {
+    let instance_dir = get_instance_dir(instance_name)?;
+    if !get_config(&instance_dir)
+        .map_err(LauncherError::from)?
+        .sync_folder
+    {
+        return Ok(());
+    }
+    let sync_dir = get_sync_dir(instance_name)?;
+    let game_dir = get_game_dir(instance_name)?;
+    std::fs::create_dir_all(&sync_dir).map_err(io_err!(sync_dir))?;
+
+    let machine = machine_name();
+    let lock_path = sync_dir.join(LOCK_FILE);
+    if let Some(lock) = read_json::<SyncLock>(&lock_path)? {
+        let is_stale = now().saturating_sub(lock.since) > STALE_LOCK.as_secs();
+        if lock.machine != machine && !is_stale {
+            return Err(SyncError::Locked {
+                machine: lock.machine,
+                since: lock.since,
+            });
+        }
+    }
+    write_json(
+        &lock_path,
+        &SyncLock {
+            machine,
+            since: now(),
+        },
+    )?;
+
+    let base_path = instance_dir.join(BASE_FILE);
+    let base: SyncBase = read_json(&base_path)?.unwrap_or_default();
+    let Some(state) = read_json::<SyncState>(&sync_dir.join(STATE_FILE))? else {
+        // Nothing pushed yet.
+        return Ok(());
+    };
+    if state.revision <= base.revision {
+        return Ok(());
+    }
+
+    let local_change = newest_change(&game_dir)?;
+    if local_change > base.time {
+        // Last writer wins, the other copy is kept.
+        if local_change > state.time {
+            info!("Sync conflict in {instance_name}, keeping this machine's copy");
+            keep_conflict(instance_name, &sync_dir, &sync_dir, &state.machine)?;
+            // It's pushed over the other copy once the game exits.
+            write_json(
+                &base_path,
+                &SyncBase {
+                    revision: state.revision,
+                    time: base.time,
+                },
+            )?;
+            return Ok(());
+        }
+        info!(
+            "Sync conflict in {instance_name}, keeping {}'s copy",
+            state.machine
+        );
+        keep_conflict(instance_name, &sync_dir, &game_dir, &machine_name())?;
+    }
+
+    info!("Pulling {instance_name} from the sync folder");
+    copy_synced(&sync_dir, &game_dir)?;
+    write_json(
+        &base_path,
+        &SyncBase {
+            revision: state.revision,
+            time: now(),
+        },
+    )?;
+    report_missing_mods(instance_name, &sync_dir, &game_dir);
+    Ok(())
+}
+
+/// Copies the game directory's user data into the sync folder,
+/// and unlocks it. Does nothing unless the instance has
+/// `sync_folder` on. Call this once the game has exited.
+pub fn push(instance_name: &str) -> Result<(), SyncError> {
+    let instance_dir = get_instance_dir(instance_name)?;
+    let config = get_config(&instance_dir).map_err(LauncherError::from)?;
+    if !config.sync_folder {
+        return Ok(());
+    }
+    let sync_dir = get_sync_dir(instance_name)?;
+    let game_dir = get_game_dir(instance_name)?;
+    std::fs::create_dir_all(&sync_dir).map_err(io_err!(sync_dir))?;
+
+    let base_path = instance_dir.join(BASE_FILE);
+    let base: SyncBase = read_json(&base_path)?.unwrap_or_default();
+    let state: SyncState = read_json(&sync_dir.join(STATE_FILE))?.unwrap_or_default();
+    if state.revision > base.revision {
+        // Another machine pushed while this one played.
+        // This one wrote last, so it wins.
+        keep_conflict(instance_name, &sync_dir, &sync_dir, &state.machine)?;
+    }
+
+    info!("Pushing {instance_name} to the sync folder");
+    copy_synced(&game_dir, &sync_dir)?;
+    let mods: Vec<&ManagedMod> = config.mods.iter().collect();
+    write_json(&sync_dir.join(MODS_MANIFEST), &mods)?;
+
+    let revision = state.revision.max(base.revision) + 1;
+    write_json(
+        &sync_dir.join(STATE_FILE),
+        &SyncState {
+            revision,
+            machine: machine_name(),
+            time: now(),
+        },
+    )?;
+    write_json(
+        &base_path,
+        &SyncBase {
+            revision,
+            time: now(),
+        },
+    )?;
+
+    let lock_path = sync_dir.join(LOCK_FILE);
+    if lock_path.exists() {
+        std::fs::remove_file(&lock_path).map_err(io_err!(lock_path))?;
+    }
+    Ok(())
+}
+
+/// Copies the synced files of `from` (the sync folder, or the
+/// game directory) into `conflicts/<time>-<machine>/`.
+fn keep_conflict(
+    instance_name: &str,
+    sync_dir: &Path,
+    from: &Path,
+    machine: &str,
+) -> Result<(), SyncError> {
+    let name = format!(
+        "{}-{}",
+        file_utils::format_timestamp(SystemTime::now()),
+        sanitize(machine)
+    );
+    let kept = sync_dir.join("conflicts").join(name);
+    copy_synced(from, &kept)?;
+    events::emit(Event::SyncConflict {
+        instance: instance_name.to_owned(),
+        kept: kept.clone(),
+    });
+    Ok(())
+}
+
+/// Replaces the synced files in `to` with the ones in `from`.
+fn copy_synced(from: &Path, to: &Path) -> Result<(), IoError> {
+    std::fs::create_dir_all(to).map_err(io_err!(to))?;
+    for name in SYNCED {
+        let src = from.join(name);
+        let dest = to.join(name);
+        if src.is_dir() {
+            if dest.is_dir() {
+                std::fs::remove_dir_all(&dest).map_err(io_err!(dest))?;
+            }
+            file_utils::copy_dir_recursive(&src, &dest)?;
+        } else if src.is_file() {
+            std::fs::copy(&src, &dest).map_err(io_err!(src))?;
+        }
+    }
+    Ok(())
+}
+
+/// When any synced file in `dir` was last changed.
+/// Unix time, in seconds, or 0 if there are none.
+fn newest_change(dir: &Path) -> Result<u64, IoError> {
+    fn newest_in(path: &Path) -> Result<u64, IoError> {
+        let metadata = std::fs::metadata(path).map_err(io_err!(path))?;
+        let mut newest = metadata
+            .modified()
+            .ok()
+            .and_then(|n| n.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |n| n.as_secs());
+        if metadata.is_dir() {
+            for entry in std::fs::read_dir(path).map_err(io_err!(path))? {
+                let entry = entry.map_err(io_err!(path))?;
+                newest = newest.max(newest_in(&entry.path())?);
+            }
+        }
+        Ok(newest)
+    }
+
+    let mut newest = 0;
+    for name in SYNCED {
+        let path = dir.join(name);
+        if path.exists() {
+            newest = newest.max(newest_in(&path)?);
+        }
+    }
+    Ok(newest)
+}
+
+/// Tells the user about mods the other machine has
+/// installed, that aren't in this one's `mods` folder.
+fn report_missing_mods(instance_name: &str, sync_dir: &Path, game_dir: &Path) {
+    let mods = match read_json::<Vec<ManagedMod>>(&sync_dir.join(MODS_MANIFEST)) {
+        Ok(mods) => mods.unwrap_or_default(),
+        Err(err) => {
+            error!("Could not read the synced mod list: {err}");
+            return;
+        }
+    };
+    let mods_dir = game_dir.join("mods");
+    let missing: Vec<String> = mods
+        .into_iter()
+        .filter(|n| !mods_dir.join(&n.file_name).exists())
+        .map(|n| n.file_name)
+        .collect();
+    if !missing.is_empty() {
+        events::emit(Event::SyncMissingMods {
+            instance: instance_name.to_owned(),
+            mods: missing,
+        });
+    }
+}
+
+fn read_json<T: for<'a> Deserialize<'a>>(path: &Path) -> Result<Option<T>, SyncError> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let json = std::fs::read_to_string(path).map_err(io_err!(path))?;
+    Ok(Some(serde_json::from_str(&json)?))
+}
+
+fn write_json(path: &Path, value: &impl Serialize) -> Result<(), SyncError> {
+    let json = serde_json::to_string_pretty(value)?;
+    std::fs::write(path, json).map_err(io_err!(path))?;
+    Ok(())
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |n| n.as_secs())
+}
+
+/// This computer's name, to tell machines apart in locks.
+fn machine_name() -> String {
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|n| n.trim().to_owned())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| "unknown".to_owned())
+}
+
+/// Makes a machine name safe to use in a folder name.
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+pub enum SyncError {
+    Launcher(LauncherError),
+    Io(IoError),
+    Json(serde_json::Error),
+    /// Another machine is playing the instance.
+    Locked {
+        machine: String,
+        /// Unix time, in seconds.
+        since: u64,
+    },
+}
+
+impl From<LauncherError> for SyncError {
+    fn from(value: LauncherError) -> Self {
+        Self::Launcher(value)
+    }
+}
+
+impl From<IoError> for SyncError {
+    fn from(value: IoError) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for SyncError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl Display for SyncError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyncError::Launcher(err) => write!(f, "could not sync the instance: {err}"),
+            SyncError::Io(err) => write!(f, "could not sync the instance: {err}"),
+            SyncError::Json(err) => write!(f, "could not sync the instance: json error: {err}"),
+            SyncError::Locked { machine, since } => {
+                let minutes = now().saturating_sub(*since) / 60;
+                write!(
+                    f,
+                    "the instance is being played on {machine} (for {minutes} minutes), \
+                     close it there first"
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,321 @@
+//! Instance templates, for making new instances set up
+//! like an existing one, kept in `QuantumLauncher/templates`.
+//!
+//! A template has the loader, the Modrinth mods, the Java
+//! settings and the `options.txt` of an instance, but not its
+//! mod files. New instances get the versions of the mods for
+//! their own Minecraft version, so one template works across
+//! versions.
+
+use std::{fmt::Display, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::{
+    cancel::CancelToken,
+    download::{progress::DownloadProgress, DownloadError},
+    error::{HasErrorCode, IoError, LauncherError},
+    file_utils, io_err,
+    json_structs::{
+        json_instance_config::{InstanceConfigJson, ModSource},
+        json_manifest::VersionSource,
+        json_version::VersionDetails,
+        JsonFileError,
+    },
+    progress::ProgressSender,
+};
+
+use super::{
+    game_options::GameOptions,
+    instance_create,
+    instance_launch::get_instance_dir,
+    instance_mod_installer::{
+        fabric::{self, FabricInstallError},
+        mod_list,
+        modrinth::ModInstallError,
+    },
+};
+
+/// The loaders the launcher can install by itself.
+const SUPPORTED_LOADERS: &[&str] = &["Vanilla", "Fabric"];
+
+/// How an instance was set up, to make new instances like it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct InstanceTemplate {
+    pub name: String,
+    /// The version of the instance it was saved from,
+    /// picked by default for new instances.
+    pub minecraft_version: String,
+    /// `Vanilla` or `Fabric`.
+    pub loader: String,
+    /// Modrinth project IDs. Mods that others need are left
+    /// out, as they're installed along with those.
+    pub mods: Vec<String>,
+    pub java_args: Vec<String>,
+    pub ram_in_mb: usize,
+    #[serde(default)]
+    pub initial_ram_in_mb: Option<usize>,
+    #[serde(default)]
+    pub java_version: Option<usize>,
+    /// The lines of `options.txt`, without `version`.
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+fn get_templates_dir() -> Result<PathBuf, IoError> {
+    Ok(file_utils::get_launcher_dir()?.join("templates"))
+}
+
+fn get_template_path(template_name: &str) -> Result<PathBuf, TemplateError> {
+    let is_valid = !template_name.trim().is_empty()
+        && !template_name.starts_with('.')
+        && !template_name.contains(['/', '\\', ':']);
+    if !is_valid {
+        return Err(TemplateError::InvalidName(template_name.to_owned()));
+    }
+    Ok(get_templates_dir()?.join(format!("{template_name}.json")))
+}
+
+/// Saves an instance as a template, replacing any
+/// template with the same name.
+pub fn save(instance_name: &str, template_name: &str) -> Result<InstanceTemplate, TemplateError> {
+    let path = get_template_path(template_name)?;
+    let instance_dir = get_instance_dir(instance_name)?;
+    let config = InstanceConfigJson::read(&instance_dir.join("config.json"))?;
+    if !SUPPORTED_LOADERS.contains(&config.mod_type.as_str()) {
+        return Err(TemplateError::UnsupportedLoader(config.mod_type));
+    }
+    let version = VersionDetails::read(&instance_dir.join("details.json"))?;
+
+    let mods = config
+        .mods
+        .iter()
+        .filter(|managed| managed.source == ModSource::Modrinth && managed.enabled)
+        .filter(|managed| {
+            !config
+                .mods
+                .iter()
+                .any(|other| other.dependencies.contains(&managed.project_id))
+        })
+        .map(|managed| managed.project_id.clone())
+        .collect();
+    let options = GameOptions::load(instance_name)?
+        .lines()
+        .iter()
+        .filter(|line| !line.starts_with("version:"))
+        .cloned()
+        .collect();
+
+    let template = InstanceTemplate {
+        name: template_name.to_owned(),
+        minecraft_version: version.id,
+        loader: config.mod_type,
+        mods,
+        java_args: config.java_args,
+        ram_in_mb: config.ram_in_mb,
+        initial_ram_in_mb: config.initial_ram_in_mb,
+        java_version: config.java_version,
+        options,
+    };
+
+    let dir = get_templates_dir()?;
+    std::fs::create_dir_all(&dir).map_err(io_err!(dir))?;
+    let json = serde_json::to_string_pretty(&template)?;
+    std::fs::write(&path, json).map_err(io_err!(path))?;
+    Ok(template)
+}
+
+pub async fn save_wrapped(
+    instance_name: String,
+    template_name: String,
+) -> Result<InstanceTemplate, String> {
+    file_utils::run_blocking(move || save(&instance_name, &template_name))
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// The saved templates, by name. Ones that can't be
+/// read are left out.
+pub fn list() -> Result<Vec<InstanceTemplate>, TemplateError> {
+    let dir = get_templates_dir()?;
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut templates: Vec<InstanceTemplate> = std::fs::read_dir(&dir)
+        .map_err(io_err!(dir))?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|n| n == "json"))
+        .filter_map(|path| {
+            let template = std::fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|json| serde_json::from_str(&json).map_err(|err| err.to_string()));
+            if let Err(err) = &template {
+                error!("Could not read template {path:?}: {err}");
+            }
+            template.ok()
+        })
+        .collect();
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(templates)
+}
+
+pub fn delete(template_name: &str) -> Result<(), TemplateError> {
+    let path = get_template_path(template_name)?;
+    std::fs::remove_file(&path).map_err(io_err!(path))?;
+    Ok(())
+}
+
+/// Creates an instance from a template, for the Minecraft
+/// `version` (which doesn't have to be the template's).
+///
+/// Returns the template's mods that couldn't be installed,
+/// like ones without a version for `version`, with why.
+pub async fn create_from_template(
+    instance_name: &str,
+    template: &InstanceTemplate,
+    version: String,
+    progress_sender: Option<ProgressSender<DownloadProgress>>,
+    download_assets: bool,
+    cancel: Option<CancelToken>,
+) -> Result<Vec<(String, String)>, TemplateError> {
+    if !SUPPORTED_LOADERS.contains(&template.loader.as_str()) {
+        return Err(TemplateError::UnsupportedLoader(template.loader.clone()));
+    }
+
+    instance_create::create_instance(
+        instance_name,
+        version.clone(),
+        VersionSource::Mojang,
+        progress_sender,
+        download_assets,
+        cancel,
+    )
+    .await?;
+
+    let config_path = get_instance_dir(instance_name)?.join("config.json");
+    let mut config = InstanceConfigJson::read(&config_path)?;
+    config.java_args.clone_from(&template.java_args);
+    config.ram_in_mb = template.ram_in_mb;
+    config.initial_ram_in_mb = template.initial_ram_in_mb;
+    config.java_version = template.java_version;
+    config.save(&config_path)?;
+
+    let mut options = GameOptions::load(instance_name)?;
+    options.import_lines(template.options.clone());
+    options.save(instance_name)?;
+
+    if template.loader == "Fabric" && !fabric::install_latest(instance_name).await? {
+        return Err(TemplateError::LoaderUnavailable(version));
+    }
+    if template.mods.is_empty() {
+        return Ok(Vec::new());
+    }
+    let result = mod_list::import(instance_name, &template.mods.join(" "), None).await?;
+    Ok(result.unmatched)
+}
+
+pub async fn create_from_template_wrapped(
+    instance_name: String,
+    template: InstanceTemplate,
+    version: String,
+    progress_sender: Option<ProgressSender<DownloadProgress>>,
+    download_assets: bool,
+    cancel: Option<CancelToken>,
+) -> Result<Vec<(String, String)>, String> {
+    create_from_template(
+        &instance_name,
+        &template,
+        version,
+        progress_sender,
+        download_assets,
+        cancel,
+    )
+    .await
+    .map_err(|err| err.to_string_with_code())
+}
+
+#[derive(Debug)]
+pub enum TemplateError {
+    Io(IoError),
+    Json(serde_json::Error),
+    JsonFile(JsonFileError),
+    Launcher(LauncherError),
+    Download(DownloadError),
+    Fabric(FabricInstallError),
+    Mods(ModInstallError),
+    /// Template names are file names, so they can't
+    /// be empty or have slashes.
+    InvalidName(String),
+    /// A loader the launcher can't install.
+    UnsupportedLoader(String),
+    /// The loader has no version for this Minecraft version.
+    LoaderUnavailable(String),
+}
+
+impl From<IoError> for TemplateError {
+    fn from(value: IoError) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for TemplateError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl From<JsonFileError> for TemplateError {
+    fn from(value: JsonFileError) -> Self {
+        Self::JsonFile(value)
+    }
+}
+
+impl From<LauncherError> for TemplateError {
+    fn from(value: LauncherError) -> Self {
+        Self::Launcher(value)
+    }
+}
+
+impl From<DownloadError> for TemplateError {
+    fn from(value: DownloadError) -> Self {
+        Self::Download(value)
+    }
+}
+
+impl From<FabricInstallError> for TemplateError {
+    fn from(value: FabricInstallError) -> Self {
+        Self::Fabric(value)
+    }
+}
+
+impl From<ModInstallError> for TemplateError {
+    fn from(value: ModInstallError) -> Self {
+        Self::Mods(value)
+    }
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::Io(err) => write!(f, "template error: {err}"),
+            TemplateError::Json(err) => write!(f, "template error: json error: {err}"),
+            TemplateError::JsonFile(err) => write!(f, "template error: {err}"),
+            TemplateError::Launcher(err) => write!(f, "could not create instance: {err}"),
+            TemplateError::Download(err) => write!(f, "could not create instance: {err}"),
+            TemplateError::Fabric(err) => write!(f, "could not install Fabric: {err}"),
+            TemplateError::Mods(err) => write!(f, "could not install mods: {err}"),
+            TemplateError::InvalidName(name) => {
+                write!(f, "template name {name:?} can't be a file name")
+            }
+            TemplateError::UnsupportedLoader(loader) => write!(
+                f,
+                "templates only support vanilla and Fabric instances, not {loader}"
+            ),
+            TemplateError::LoaderUnavailable(version) => {
+                write!(f, "Fabric doesn't support Minecraft {version} yet")
+            }
+        }
+    }
+}
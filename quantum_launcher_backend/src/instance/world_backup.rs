@@ -0,0 +1,220 @@
+//! Backing up and restoring the worlds of an instance.
+//!
+//! Each world's backups are zip files in
+//! `instances/<instance>/world_backups/<world>/`, named by
+//! when they were made, and only the newest
+//! `world_backup_retention` of them are kept.
+
+use std::{
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use tracing::{error, info};
+use zip::{ZipArchive, ZipWriter};
+
+use crate::{error::HasErrorCode, file_utils, io_err};
+
+use super::{
+    instance_launch::{get_config, get_game_dir, get_instance_dir},
+    server_schedule::{delete_old_backups, BackupError},
+    worlds::{self, WorldInfo},
+};
+
+/// A world in an instance's `saves` folder, and its backups.
+#[derive(Debug, Clone)]
+pub struct WorldBackups {
+    pub world: String,
+    /// Whether the world is still in the `saves` folder.
+    pub exists: bool,
+    /// `None` if the world was deleted, or its `level.dat` can't be read.
+    pub info: Option<WorldInfo>,
+    /// Newest first.
+    pub backups: Vec<PathBuf>,
+}
+
+fn get_saves_dir(instance_name: &str) -> Result<PathBuf, BackupError> {
+    Ok(get_game_dir(instance_name)?.join("saves"))
+}
+
+fn get_backups_dir(instance_name: &str, world: &str) -> Result<PathBuf, BackupError> {
+    Ok(get_instance_dir(instance_name)?
+        .join("world_backups")
+        .join(world))
+}
+
+/// Lists the instance's worlds, along with any backups of
+/// worlds that were deleted since, so they can be restored.
+pub fn list_worlds(instance_name: &str) -> Result<Vec<WorldBackups>, BackupError> {
+    let saves_dir = get_saves_dir(instance_name)?;
+    let backups_root = get_instance_dir(instance_name)?.join("world_backups");
+
+    let mut worlds: Vec<String> = [&saves_dir, &backups_root]
+        .into_iter()
+        .filter(|dir| dir.is_dir())
+        .map(|dir| {
+            Ok(std::fs::read_dir(dir)
+                .map_err(io_err!(dir))?
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|path| path.is_dir())
+                .filter_map(|path| path.file_name()?.to_str().map(str::to_owned))
+                .collect::<Vec<_>>())
+        })
+        .collect::<Result<Vec<_>, BackupError>>()?
+        .concat();
+    worlds.sort();
+    worlds.dedup();
+
+    worlds
+        .into_iter()
+        .map(|world| {
+            let backups = list_backups(&backups_root.join(&world))?;
+            let world_dir = saves_dir.join(&world);
+            let info = world_dir
+                .is_dir()
+                .then(|| worlds::read_world_info(&world_dir));
+            let info = match info {
+                Some(Ok(info)) => Some(info),
+                Some(Err(err)) => {
+                    error!("Could not read world {world}: {err}");
+                    None
+                }
+                None => None,
+            };
+            Ok(WorldBackups {
+                exists: world_dir.is_dir(),
+                info,
+                world,
+                backups,
+            })
+        })
+        .collect()
+}
+
+fn list_backups(backups_dir: &Path) -> Result<Vec<PathBuf>, BackupError> {
+    if !backups_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(backups_dir)
+        .map_err(io_err!(backups_dir))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|n| n == "zip"))
+        .collect();
+    // The names are dates, so this is newest first.
+    backups.sort_by(|a, b| b.cmp(a));
+    Ok(backups)
+}
+
+pub async fn backup_world_wrapped(instance_name: String, world: String) -> Result<PathBuf, String> {
+    file_utils::run_blocking(move || backup_world(&instance_name, &world))
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Zips the world into its backups folder, then deletes
+/// the oldest backups past the instance's retention.
+pub fn backup_world(instance_name: &str, world: &str) -> Result<PathBuf, BackupError> {
+    let backup_path = zip_world(instance_name, world)?;
+    let retention = get_config(&get_instance_dir(instance_name)?)
+        .map_err(crate::error::LauncherError::from)?
+        .world_backup_retention;
+    delete_old_backups(&get_backups_dir(instance_name, world)?, retention.max(1))?;
+    Ok(backup_path)
+}
+
+/// Backs up every world of the instance, like before
+/// launching a different Minecraft version.
+pub fn backup_all_worlds(instance_name: &str) -> Result<(), BackupError> {
+    for world in list_worlds(instance_name)? {
+        if world.exists {
+            backup_world(instance_name, &world.world)?;
+        }
+    }
+    Ok(())
+}
+
+fn zip_world(instance_name: &str, world: &str) -> Result<PathBuf, BackupError> {
+    let saves_dir = get_saves_dir(instance_name)?;
+    let world_dir = saves_dir.join(world);
+    if !world_dir.is_dir() {
+        return Err(BackupError::NoWorld(world.to_owned()));
+    }
+
+    let backups_dir = get_backups_dir(instance_name, world)?;
+    std::fs::create_dir_all(&backups_dir).map_err(io_err!(backups_dir))?;
+    let backup_path = backups_dir.join(format!(
+        "{}.zip",
+        file_utils::format_timestamp(SystemTime::now())
+    ));
+
+    info!("Backing up world {world} of {instance_name} to {backup_path:?}");
+    let file = File::create(&backup_path).map_err(io_err!(backup_path))?;
+    let mut zip = ZipWriter::new(file);
+    // The lock is only held while the game runs.
+    file_utils::add_dir_to_zip::<BackupError>(&mut zip, &saves_dir, &world_dir, &["session.lock"])?;
+    zip.finish()?;
+    Ok(backup_path)
+}
+
+pub async fn restore_world_wrapped(instance_name: String, backup: PathBuf) -> Result<(), String> {
+    file_utils::run_blocking(move || restore_world(&instance_name, &backup))
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Replaces a world with one of its backups. The world is
+/// backed up first, so restoring can be undone.
+///
+/// The game must not be running, or it would
+/// overwrite the restored world when saving.
+pub fn restore_world(instance_name: &str, backup: &Path) -> Result<(), BackupError> {
+    let world = backup
+        .parent()
+        .and_then(Path::file_name)
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| BackupError::InvalidBackup(backup.to_owned()))?
+        .to_owned();
+
+    let file = File::open(backup).map_err(io_err!(backup))?;
+    let mut archive = ZipArchive::new(file)?;
+    // Everything has to be in the world's folder, so a
+    // wrong zip file can't write anywhere else.
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i)?;
+        if !entry
+            .enclosed_name()
+            .is_some_and(|name| name.starts_with(&world))
+        {
+            return Err(BackupError::InvalidBackup(backup.to_owned()));
+        }
+    }
+
+    let saves_dir = get_saves_dir(instance_name)?;
+    let world_dir = saves_dir.join(&world);
+    if world_dir.is_dir() {
+        zip_world(instance_name, &world)?;
+        std::fs::remove_dir_all(&world_dir).map_err(io_err!(world_dir))?;
+    }
+
+    info!("Restoring world {world} of {instance_name} from {backup:?}");
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let Some(name) = entry.enclosed_name().map(Path::to_owned) else {
+            continue;
+        };
+        let dest = saves_dir.join(name);
+        if entry.is_dir() {
+            std::fs::create_dir_all(&dest).map_err(io_err!(dest))?;
+            continue;
+        }
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent).map_err(io_err!(parent))?;
+        }
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes).map_err(io_err!(backup))?;
+        std::fs::write(&dest, bytes).map_err(io_err!(dest))?;
+    }
+    Ok(())
+}
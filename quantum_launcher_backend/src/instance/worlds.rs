@@ -0,0 +1,205 @@
+//! The worlds in an instance's `saves` folder: reading their
+//! details from `level.dat`, and renaming, copying
+//! (within or between instances) and deleting them.
+//!
+//! A world is identified by its folder name, which stays the
+//! same when it's renamed, like in the game's own menu.
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use tracing::info;
+
+use crate::{
+    error::{HasErrorCode, IoError, LauncherError},
+    file_utils, io_err,
+    nbt::{self, NbtError, Tag},
+};
+
+use super::{instance_launch::get_game_dir, server_files::GameMode};
+
+/// What the world list shows about a world.
+#[derive(Debug, Clone)]
+pub struct WorldInfo {
+    /// The name shown in the game.
+    pub name: String,
+    /// The Minecraft version the world was last played in.
+    /// Versions before 1.9 don't save it.
+    pub version: Option<String>,
+    pub game_mode: Option<GameMode>,
+    pub hardcore: bool,
+    /// In seconds since the Unix epoch.
+    pub last_played: Option<u64>,
+}
+
+pub(super) fn get_saves_dir(instance_name: &str) -> Result<PathBuf, LauncherError> {
+    Ok(get_game_dir(instance_name)?.join("saves"))
+}
+
+/// Reads the details of the world in `world_dir` from its `level.dat`.
+pub fn read_world_info(world_dir: &Path) -> Result<WorldInfo, WorldError> {
+    let (_, root) = nbt::read_file(&world_dir.join("level.dat"))?;
+    let data = root
+        .get("Data")
+        .ok_or(WorldError::Nbt(NbtError::Invalid("no Data tag")))?;
+
+    Ok(WorldInfo {
+        name: data
+            .get("LevelName")
+            .and_then(Tag::as_str)
+            .map(str::to_owned)
+            .unwrap_or_else(|| folder_name(world_dir)),
+        version: data
+            .get("Version")
+            .and_then(|version| version.get("Name"))
+            .and_then(Tag::as_str)
+            .map(str::to_owned),
+        game_mode: data
+            .get("GameType")
+            .and_then(Tag::as_i64)
+            .and_then(|n| usize::try_from(n).ok())
+            .and_then(|n| GameMode::ALL.get(n).copied()),
+        hardcore: data
+            .get("hardcore")
+            .and_then(Tag::as_i64)
+            .is_some_and(|n| n != 0),
+        // The game saves milliseconds.
+        last_played: data
+            .get("LastPlayed")
+            .and_then(Tag::as_i64)
+            .and_then(|n| u64::try_from(n / 1000).ok()),
+    })
+}
+
+fn folder_name(world_dir: &Path) -> String {
+    world_dir
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Changes the name shown in the game. The folder isn't renamed,
+/// so that backups and links to the world keep working.
+///
+/// The old `level.dat` is kept as `level.dat_old`, like the game does.
+pub fn rename_world(instance_name: &str, world: &str, new_name: &str) -> Result<(), WorldError> {
+    let world_dir = get_saves_dir(instance_name)?.join(world);
+    let level_dat = world_dir.join("level.dat");
+    let (root_name, mut root) = nbt::read_file(&level_dat)?;
+    let Some(Tag::Compound(data)) = root.get_mut("Data") else {
+        return Err(WorldError::Nbt(NbtError::Invalid("no Data tag")));
+    };
+    match data.iter_mut().find(|(key, _)| key == "LevelName") {
+        Some((_, name)) => *name = Tag::String(new_name.to_owned()),
+        None => data.push(("LevelName".to_owned(), Tag::String(new_name.to_owned()))),
+    }
+
+    let old_level_dat = world_dir.join("level.dat_old");
+    std::fs::copy(&level_dat, &old_level_dat).map_err(io_err!(old_level_dat))?;
+    nbt::write_file(&level_dat, &root_name, &root)?;
+    info!("Renamed world {world} of {instance_name} to {new_name:?}");
+    Ok(())
+}
+
+pub async fn copy_world_wrapped(
+    from_instance: String,
+    world: String,
+    to_instance: String,
+) -> Result<String, String> {
+    file_utils::run_blocking(move || copy_world(&from_instance, &world, &to_instance))
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Copies a world into another instance's `saves` folder,
+/// or duplicates it if both instances are the same.
+/// If there's already a world with the same folder name,
+/// the copy gets a number at the end, like `World (2)`.
+/// Returns the folder name of the copy.
+pub fn copy_world(
+    from_instance: &str,
+    world: &str,
+    to_instance: &str,
+) -> Result<String, WorldError> {
+    let world_dir = get_saves_dir(from_instance)?.join(world);
+    if !world_dir.join("level.dat").is_file() {
+        return Err(WorldError::NotFound(world.to_owned()));
+    }
+
+    let to_saves_dir = get_saves_dir(to_instance)?;
+    let copy_name = (1..)
+        .map(|n| {
+            if n == 1 {
+                world.to_owned()
+            } else {
+                format!("{world} ({n})")
+            }
+        })
+        .find(|name| !to_saves_dir.join(name).exists())
+        .expect("there's always a free name");
+
+    info!("Copying world {world} of {from_instance} to {copy_name} of {to_instance}");
+    let copy_dir = to_saves_dir.join(&copy_name);
+    file_utils::copy_dir_recursive(&world_dir, &copy_dir)?;
+    // The lock belongs to the game that has the original open.
+    let lock = copy_dir.join("session.lock");
+    if lock.exists() {
+        std::fs::remove_file(&lock).map_err(io_err!(lock))?;
+    }
+    Ok(copy_name)
+}
+
+/// Deletes a world. Its backups are kept, so
+/// it can still be restored from them.
+pub fn delete_world(instance_name: &str, world: &str) -> Result<(), WorldError> {
+    let saves_dir = get_saves_dir(instance_name)?;
+    let world_dir = saves_dir.join(world);
+    // `world` comes from a folder listing, but this makes sure
+    // a name like `..` can't delete anything else.
+    if world_dir.parent() != Some(saves_dir.as_path()) || !world_dir.is_dir() {
+        return Err(WorldError::NotFound(world.to_owned()));
+    }
+    info!("Deleting world {world} of {instance_name}");
+    std::fs::remove_dir_all(&world_dir).map_err(io_err!(world_dir))?;
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum WorldError {
+    Launcher(LauncherError),
+    Io(IoError),
+    Nbt(NbtError),
+    NotFound(String),
+}
+
+impl From<LauncherError> for WorldError {
+    fn from(value: LauncherError) -> Self {
+        Self::Launcher(value)
+    }
+}
+
+impl From<IoError> for WorldError {
+    fn from(value: IoError) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<NbtError> for WorldError {
+    fn from(value: NbtError) -> Self {
+        Self::Nbt(value)
+    }
+}
+
+impl Display for WorldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorldError::Launcher(err) => write!(f, "{err}"),
+            WorldError::Io(err) => write!(f, "world: {err}"),
+            WorldError::Nbt(err) => write!(f, "world's level.dat: {err}"),
+            WorldError::NotFound(world) => write!(f, "world {world:?} not found"),
+        }
+    }
+}
@@ -1,23 +1,37 @@
-use std::{error::Error, fmt::Display, path::PathBuf, sync::mpsc::Sender};
+use std::{error::Error, fmt::Display, path::PathBuf};
+
+use serde::Serialize;
+use tracing::{info, warn};
 
 use crate::{
-    error::IoError,
+    cancel::{self, CancelToken, Cancelled},
+    download_queue::{self, JobKind},
+    error::{HasErrorCode, IoError},
     file_utils::{self, RequestError},
     io_err,
     json_structs::{
+        json_instance_config::InstanceConfigJson,
         json_java_files::{JavaFile, JavaFilesJson},
         json_java_list::{JavaListJson, JavaVersion},
-        JsonDownloadError,
+        json_version::VersionDetails,
+        JsonDownloadError, JsonFileError,
     },
+    logging,
+    progress::ProgressSender,
 };
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
 pub enum JavaInstallMessage {
+    #[serde(rename = "started")]
     P1Started,
+    #[serde(rename = "installing_file")]
     P2 {
         progress: usize,
         out_of: usize,
         name: String,
     },
+    #[serde(rename = "done")]
     P3Done,
 }
 
@@ -28,16 +42,20 @@ pub enum JavaInstallMessage {
 /// and if already installed, uses the existing installation.
 ///
 /// If you want, you can hook this up to a progress bar, by using a
-/// `std::sync::mpsc::channel::<JavaInstallMessage>()`, giving the
-/// sender to this function and polling the receiver frequently.
+/// `progress::channel::<JavaInstallMessage>()`, giving the
+/// sender to this function and awaiting the receiver.
 /// If not needed, simply pass `None` to the function.
+///
+/// Cancelling `cancel` stops the install and removes
+/// the partly installed Java.
 pub async fn get_java(
     version: JavaVersion,
-    java_install_progress_sender: Option<Sender<JavaInstallMessage>>,
+    java_install_progress_sender: Option<ProgressSender<JavaInstallMessage>>,
+    cancel: Option<&CancelToken>,
 ) -> Result<PathBuf, JavaInstallError> {
-    let launcher_dir = file_utils::get_launcher_dir()?;
-
-    let java_dir = launcher_dir.join("java_installs").join(version.to_string());
+    let java_dir = file_utils::get_cache_dir()?
+        .join("java_installs")
+        .join(version.to_string());
 
     let is_incomplete_install = java_dir.join("install.lock").exists();
 
@@ -48,7 +66,12 @@ pub async fn get_java(
     }
 
     if !java_dir.exists() || is_incomplete_install {
-        install_java(version, java_install_progress_sender.as_ref()).await?;
+        download_queue::run(
+            JobKind::Java,
+            version.to_string(),
+            install_java(version, java_install_progress_sender.as_ref(), cancel),
+        )
+        .await?;
     }
 
     let java_dir = java_dir.join(if cfg!(windows) {
@@ -68,37 +91,49 @@ pub async fn get_java(
 
 async fn install_java(
     version: JavaVersion,
-    java_install_progress_sender: Option<&Sender<JavaInstallMessage>>,
+    java_install_progress_sender: Option<&ProgressSender<JavaInstallMessage>>,
+    cancel: Option<&CancelToken>,
 ) -> Result<(), JavaInstallError> {
-    println!("[info] Started installing {}", version.to_string());
+    info!("Started installing {}", version);
+    let _timer = logging::time_step("Installing Java");
     let java_list_json = JavaListJson::download().await?;
     let java_files_url = java_list_json
         .get_url(version)
         .ok_or(JavaInstallError::NoUrlForJavaFiles)?;
 
-    let client = reqwest::Client::new();
+    let client = file_utils::create_client();
     let json = file_utils::download_file_to_string(&client, &java_files_url).await?;
     let json: JavaFilesJson = serde_json::from_str(&json)?;
 
-    let launcher_dir = file_utils::get_launcher_dir()?;
-
-    let java_installs_dir = launcher_dir.join("java_installs");
-    std::fs::create_dir_all(&java_installs_dir).map_err(io_err!(java_installs_dir.to_owned()))?;
+    let java_installs_dir = file_utils::get_cache_dir()?.join("java_installs");
+    tokio::fs::create_dir_all(&java_installs_dir)
+        .await
+        .map_err(io_err!(java_installs_dir.to_owned()))?;
 
     let install_dir = java_installs_dir.join(version.to_string());
-    std::fs::create_dir_all(&install_dir).map_err(io_err!(java_installs_dir.to_owned()))?;
+    tokio::fs::create_dir_all(&install_dir)
+        .await
+        .map_err(io_err!(java_installs_dir.to_owned()))?;
 
     let lock_file = install_dir.join("install.lock");
-    std::fs::write(
+    tokio::fs::write(
         &lock_file,
         "If you see this, java hasn't finished installing.",
     )
+    .await
     .map_err(io_err!(lock_file.to_owned()))?;
 
     let num_files = json.files.len();
 
     for (file_num, (file_name, file)) in json.files.iter().enumerate() {
-        println!("[info] Installing file ({file_num}/{num_files}): {file_name}");
+        if let Err(err) = cancel::check(cancel) {
+            info!("Java install cancelled, removing {install_dir:?}");
+            tokio::fs::remove_dir_all(&install_dir)
+                .await
+                .map_err(io_err!(install_dir))?;
+            return Err(err.into());
+        }
+        info!("Installing file ({file_num}/{num_files}): {file_name}");
 
         if let Some(java_install_progress_sender) = java_install_progress_sender {
             java_install_progress_sender
@@ -118,33 +153,159 @@ async fn install_java(
             } => {
                 let file_bytes =
                     file_utils::download_file_to_bytes(&client, &downloads.raw.url).await?;
-                std::fs::write(&file_path, &file_bytes).map_err(io_err!(file_path.to_owned()))?;
+                tokio::fs::write(&file_path, &file_bytes)
+                    .await
+                    .map_err(io_err!(file_path.to_owned()))?;
                 if *executable {
                     file_utils::set_executable(&file_path)?;
                 }
             }
             JavaFile::directory {} => {
-                std::fs::create_dir_all(&file_path).map_err(io_err!(file_path))?;
+                tokio::fs::create_dir_all(&file_path)
+                    .await
+                    .map_err(io_err!(file_path))?;
             }
             JavaFile::link { target } => {
-                println!("[fixme:install_java] Deal with symlink {file_name} -> {target}")
+                warn!("FIXME: Deal with symlink {file_name} -> {target}")
             }
         }
     }
 
-    std::fs::remove_file(&lock_file).map_err(io_err!(lock_file.to_owned()))?;
+    tokio::fs::remove_file(&lock_file)
+        .await
+        .map_err(io_err!(lock_file.to_owned()))?;
 
-    println!("[info] Finished installing {}", version.to_string());
+    info!("Finished installing {}", version);
     Ok(())
 }
 
+/// The launcher-managed Java installs that aren't required
+/// by any instance or server anymore (eg: `java_17_gamma`).
+///
+/// Instances with a `java_override` don't count towards
+/// any install. If any instance can't be read, this fails,
+/// to avoid removing a runtime that's still needed.
+pub async fn find_unused_java_installs() -> Result<Vec<String>, JavaInstallError> {
+    let java_installs_dir = file_utils::get_cache_dir()?.join("java_installs");
+    if !java_installs_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let launcher_dir = file_utils::get_launcher_dir()?;
+    // Servers get their Java the same way, and their
+    // start scripts point at it.
+    let dirs = [launcher_dir.join("instances"), launcher_dir.join("servers")];
+    let required_versions =
+        file_utils::run_blocking(move || get_required_java_versions(&dirs)).await?;
+
+    let mut unused = Vec::new();
+    let mut entries = tokio::fs::read_dir(&java_installs_dir)
+        .await
+        .map_err(io_err!(java_installs_dir))?;
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(io_err!(java_installs_dir))?
+    {
+        let Some(name) = entry.file_name().to_str().map(ToOwned::to_owned) else {
+            continue;
+        };
+        if entry.path().is_dir() && !required_versions.contains(&name) {
+            unused.push(name);
+        }
+    }
+    unused.sort();
+    Ok(unused)
+}
+
+pub async fn find_unused_java_installs_wrapped() -> Result<Vec<String>, String> {
+    find_unused_java_installs()
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Deletes the launcher-managed Java installs that aren't
+/// required by any instance or server anymore.
+/// See [`find_unused_java_installs`].
+///
+/// Returns the names of the removed installs (eg: `java_17_gamma`).
+pub async fn delete_unused_java_installs() -> Result<Vec<String>, JavaInstallError> {
+    let unused = find_unused_java_installs().await?;
+    remove_java_installs(unused).await
+}
+
+pub async fn delete_unused_java_installs_wrapped() -> Result<Vec<String>, String> {
+    delete_unused_java_installs()
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Deletes the Java installs in `installs` (as listed by
+/// [`find_unused_java_installs`]), skipping any that an
+/// instance has started to need since they were listed.
+///
+/// Returns the names of the removed installs.
+pub async fn delete_java_installs(installs: Vec<String>) -> Result<Vec<String>, JavaInstallError> {
+    let unused = find_unused_java_installs().await?;
+    let installs = installs
+        .into_iter()
+        .filter(|install| unused.contains(install))
+        .collect();
+    remove_java_installs(installs).await
+}
+
+pub async fn delete_java_installs_wrapped(installs: Vec<String>) -> Result<Vec<String>, String> {
+    delete_java_installs(installs)
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+async fn remove_java_installs(installs: Vec<String>) -> Result<Vec<String>, JavaInstallError> {
+    let java_installs_dir = file_utils::get_cache_dir()?.join("java_installs");
+    for name in &installs {
+        info!("Removing unused Java install: {name}");
+        let path = java_installs_dir.join(name);
+        tokio::fs::remove_dir_all(&path)
+            .await
+            .map_err(io_err!(path))?;
+    }
+    Ok(installs)
+}
+
+/// The Java installs needed by the instances in any of `dirs`.
+fn get_required_java_versions(dirs: &[PathBuf]) -> Result<Vec<String>, JavaInstallError> {
+    let mut versions = Vec::new();
+    for dir in dirs.iter().filter(|dir| dir.exists()) {
+        for entry in std::fs::read_dir(dir).map_err(io_err!(dir))? {
+            let entry = entry.map_err(io_err!(dir))?;
+            let instance_dir = entry.path();
+            if !instance_dir.is_dir() {
+                continue;
+            }
+
+            let config = InstanceConfigJson::read(&instance_dir.join("config.json"))?;
+            if config.java_override.as_ref().is_some_and(|n| !n.is_empty()) {
+                continue;
+            }
+
+            let version_json = VersionDetails::read(&instance_dir.join("details.json"))?;
+
+            versions.push(config.get_java_version(&version_json).to_string());
+        }
+    }
+
+    Ok(versions)
+}
+
 #[derive(Debug)]
 pub enum JavaInstallError {
     JsonDownload(JsonDownloadError),
     Request(RequestError),
     NoUrlForJavaFiles,
     Serde(serde_json::Error),
+    JsonFile(JsonFileError),
     Io(IoError),
+    Cancelled(Cancelled),
 }
 
 impl From<JsonDownloadError> for JavaInstallError {
@@ -153,6 +314,12 @@ impl From<JsonDownloadError> for JavaInstallError {
     }
 }
 
+impl From<JsonFileError> for JavaInstallError {
+    fn from(value: JsonFileError) -> Self {
+        Self::JsonFile(value)
+    }
+}
+
 impl From<RequestError> for JavaInstallError {
     fn from(value: RequestError) -> Self {
         Self::Request(value)
@@ -165,6 +332,12 @@ impl From<serde_json::Error> for JavaInstallError {
     }
 }
 
+impl From<Cancelled> for JavaInstallError {
+    fn from(value: Cancelled) -> Self {
+        Self::Cancelled(value)
+    }
+}
+
 impl From<IoError> for JavaInstallError {
     fn from(value: IoError) -> Self {
         Self::Io(value)
@@ -178,9 +351,56 @@ impl Display for JavaInstallError {
             JavaInstallError::NoUrlForJavaFiles => write!(f, "could not find url to download java"),
             JavaInstallError::Request(err) => write!(f, "{err}"),
             JavaInstallError::Serde(err) => write!(f, "{err}"),
+            JavaInstallError::JsonFile(err) => write!(f, "{err}"),
             JavaInstallError::Io(err) => write!(f, "{err}"),
+            JavaInstallError::Cancelled(_) => write!(f, "java install cancelled"),
         }
     }
 }
 
 impl Error for JavaInstallError {}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    const DETAILS_JSON: &str = r#"{
+        "assetIndex": {"id": "", "sha1": "", "size": 0, "totalSize": 0, "url": ""},
+        "assets": "",
+        "downloads": {"client": {"sha1": "", "size": 0, "url": ""}},
+        "id": "1.20.1",
+        "libraries": [],
+        "mainClass": "",
+        "minimumLauncherVersion": 0,
+        "releaseTime": "",
+        "time": "",
+        "type": "release"
+    }"#;
+
+    fn write_instance(dir: &Path, java_version: usize) {
+        std::fs::create_dir_all(dir).unwrap();
+        let config = format!(
+            r#"{{"ram_in_mb": 2048, "mod_type": "Vanilla", "java_version": {java_version}}}"#
+        );
+        std::fs::write(dir.join("config.json"), config).unwrap();
+        std::fs::write(dir.join("details.json"), DETAILS_JSON).unwrap();
+    }
+
+    #[test]
+    fn test_required_java_versions_include_servers() {
+        let launcher_dir =
+            std::env::temp_dir().join(format!("ql_java_test_{}", std::process::id()));
+        let dirs = [launcher_dir.join("instances"), launcher_dir.join("servers")];
+        write_instance(&dirs[0].join("client"), 8);
+        write_instance(&dirs[1].join("server"), 21);
+
+        let versions = get_required_java_versions(&dirs).unwrap();
+        assert!(versions.contains(&"java_8".to_owned()));
+        assert!(versions.contains(&"java_21".to_owned()));
+        assert_eq!(versions.len(), 2);
+
+        std::fs::remove_dir_all(&launcher_dir).unwrap();
+    }
+}
@@ -1,4 +1,30 @@
+use std::path::{Path, PathBuf};
+
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{download::constants::DEFAULT_RAM_MB_FOR_INSTANCE, io_err};
+
+use super::{
+    json_java_list::JavaVersion,
+    json_manifest::VersionSource,
+    json_version::VersionDetails,
+    migration::{self, Migration},
+    validation, JsonFileError,
+};
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    migrate: fill_in_missing_fields,
+}];
+
+/// The first versions of the launcher didn't always write these.
+fn fill_in_missing_fields(config: &mut Map<String, Value>) {
+    config.entry("mod_type").or_insert_with(|| "Vanilla".into());
+    config
+        .entry("ram_in_mb")
+        .or_insert_with(|| DEFAULT_RAM_MB_FOR_INSTANCE.into());
+}
 
 /// Configuration for a specific instance.
 ///
@@ -16,16 +42,270 @@ use serde::{Deserialize, Serialize};
 /// If you want to force the instance to use a
 /// specific Java version, you can specify it here.
 ///
+/// ## `java_version`
+/// Forces the instance to use a specific launcher-managed
+/// Java major version (`8`, `16`, `17` or `21`) instead of
+/// the one in the version JSON. It is downloaded on first launch.
+/// `java_override` takes priority over this.
+///
 /// ## `ram_in_mb`
-/// The amount of RAM in megabytes the instance should have.
+/// The most RAM in megabytes the instance can use (`-Xmx`).
+///
+/// ## `initial_ram_in_mb`
+/// How much RAM in megabytes the instance starts with (`-Xms`).
+/// `None` means [`DEFAULT_INITIAL_RAM_MB`] (or `ram_in_mb`, if
+/// that's less).
+///
+/// ## `last_played`
+/// When the instance was last launched, in seconds
+/// since the Unix epoch. `None` if it was never launched.
+///
+/// ## `last_username`
+/// The username the instance was last launched as, so
+/// that it can be launched again as the same account.
+///
+/// ## `last_profile`
+/// The name of the [`crate::offline_profiles::OfflineProfile`]
+/// the instance was last launched as. `None` if it was
+/// launched with just a username.
+///
+/// ## `last_java`
+/// The launcher-managed Java install (eg: `java_21`) the
+/// instance was last launched with. `None` if it used a
+/// `java_override`. When it changes, the Java installs that
+/// no instance needs anymore are removed.
+///
+/// ## `offline_profile`
+/// The name of the [`crate::offline_profiles::OfflineProfile`]
+/// to always launch as, instead of the one picked on
+/// the launch screen.
+///
+/// ## `java_args`
+/// Extra arguments for Java, like `-Dfile.encoding=UTF-8`.
+/// They come after the launcher-wide ones, so they can
+/// override them, and before ones given for a single launch
+/// ([`crate::LaunchOptions::override_java_args`]).
+///
+/// ## `world_backup_retention`
+/// How many backups of each world to keep.
+///
+/// ## `backup_worlds_on_version_change`
+/// Back up every world before launching, if the Minecraft
+/// version changed since the instance was last launched.
+///
+/// ## `snapshot_small_files`
+/// Copy mods no bigger than a few megabytes into the
+/// [`snapshots`] taken before loader and version changes,
+/// so that rolling back can put them back. Otherwise only
+/// their names are kept.
+///
+/// [`snapshots`]: crate::snapshots
+///
+/// ## `log4shell_mitigation`
+/// Protect versions 1.7 to 1.18 from the Log4Shell exploit
+/// (CVE-2021-44228) with Mojang's fixed logging configs, or
+/// a Java argument for versions whose Log4j supports it.
+/// Turn this off if a mod needs the version's own config.
+///
+/// ## `sync_folder`
+/// Keep the saves, options and mod list in the instance's
+/// `sync` folder too, for a sync tool like Syncthing to share
+/// with other machines. See [`sync_folder`].
+///
+/// [`sync_folder`]: crate::sync_folder
+///
+/// ## `last_launched_version`
+/// The Minecraft version the instance was last launched with.
+///
+/// ## `game_dir`
+/// A folder to run the game in instead of the instance's own
+/// `.minecraft`, like a shared folder or another instance's
+/// `.minecraft`, so that several instances can share saves,
+/// resource packs and options. Mods are installed there too.
+///
+/// ## `version_type`
+/// Shown in the F3 screen and crash reports instead of the
+/// version's type (`release`, `snapshot`, ...), like the
+/// name of a modpack. `None` uses the version's type.
+///
+/// ## `version_source`
+/// Where the instance's version JSON came from, so it's
+/// downloaded from the same place if it needs repairing.
+///
+/// ## `custom_version_json`
+/// The file or URL of the version JSON, for instances made
+/// from a custom one. It's used instead of `version_source`.
+///
+/// ## `mods`
+/// The mods the launcher installed, and where they came from.
+/// Mods added to the `mods` folder by hand aren't in here.
+///
+/// ## `config_version`
+/// The version of this format, for upgrading files from
+/// older versions of the launcher. See [`migration`].
 #[derive(Serialize, Deserialize)]
 pub struct InstanceConfigJson {
     pub java_override: Option<String>,
+    pub java_version: Option<usize>,
     pub ram_in_mb: usize,
+    #[serde(default)]
+    pub initial_ram_in_mb: Option<usize>,
     pub mod_type: String,
+    pub last_played: Option<u64>,
+    #[serde(default)]
+    pub last_username: Option<String>,
+    #[serde(default)]
+    pub last_profile: Option<String>,
+    #[serde(default)]
+    pub last_java: Option<String>,
+    #[serde(default)]
+    pub offline_profile: Option<String>,
+    #[serde(default)]
+    pub java_args: Vec<String>,
+    #[serde(default = "default_world_backup_retention")]
+    pub world_backup_retention: usize,
+    #[serde(default)]
+    pub backup_worlds_on_version_change: bool,
+    #[serde(default = "default_snapshot_small_files")]
+    pub snapshot_small_files: bool,
+    #[serde(default = "default_log4shell_mitigation")]
+    pub log4shell_mitigation: bool,
+    #[serde(default)]
+    pub sync_folder: bool,
+    #[serde(default)]
+    pub last_launched_version: Option<String>,
+    #[serde(default)]
+    pub game_dir: Option<String>,
+    #[serde(default)]
+    pub version_type: Option<String>,
+    #[serde(default)]
+    pub version_source: VersionSource,
+    #[serde(default)]
+    pub custom_version_json: Option<String>,
+    #[serde(default)]
+    pub mods: Vec<ManagedMod>,
+    #[serde(default)]
+    pub config_version: u32,
+}
+
+/// A mod installed by the launcher, so that updating,
+/// exporting and removing it doesn't have to guess
+/// from the jar file what it is.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ManagedMod {
+    pub source: ModSource,
+    /// The project ID on the source.
+    pub project_id: String,
+    pub version: String,
+    /// The file's name in the `mods` folder.
+    pub file_name: String,
+    pub sha1: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// The project IDs of the mods this one needs.
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ModSource {
+    Modrinth,
+}
+
+/// Enough for the game to start without
+/// Java having to grow the heap right away.
+pub const DEFAULT_INITIAL_RAM_MB: usize = 512;
+
+pub fn default_world_backup_retention() -> usize {
+    5
+}
+
+fn default_snapshot_small_files() -> bool {
+    true
+}
+
+fn default_log4shell_mitigation() -> bool {
+    true
 }
 
 impl InstanceConfigJson {
+    pub const VERSION: u32 = migration::current_version(MIGRATIONS);
+
+    /// Reads an instance's (or server's) `config.json`, upgrading
+    /// it if it's from an older version of the launcher.
+    pub fn read(path: &Path) -> Result<Self, JsonFileError> {
+        migration::read_migrated(path, MIGRATIONS)
+    }
+
+    /// Saves the config to `path`, keeping its backup up to
+    /// date (see [`validation`]). Everything that writes an
+    /// instance's (or server's) `config.json` goes through here.
+    pub fn save(&self, path: &Path) -> Result<(), JsonFileError> {
+        let text = serde_json::to_string(self)?;
+        std::fs::write(path, &text).map_err(io_err!(path))?;
+        validation::save_backup(path, &text);
+        Ok(())
+    }
+
+    /// Takes what the launcher keeps track of by itself (the
+    /// installed loader and mods, and the last launch) from
+    /// `saved`, the config that's on disk now.
+    ///
+    /// For screens that keep a config open while mods are
+    /// installed or the game is launched in the background,
+    /// so that saving their settings doesn't undo that.
+    pub fn keep_tracked(&mut self, saved: InstanceConfigJson) {
+        self.mod_type = saved.mod_type;
+        self.mods = saved.mods;
+        self.last_played = saved.last_played;
+        self.last_username = saved.last_username;
+        self.last_profile = saved.last_profile;
+        self.last_launched_version = saved.last_launched_version;
+    }
+
+    /// Returns the launcher-managed Java version this instance needs,
+    /// taking the instance's `java_version` setting into account.
+    pub fn get_java_version(&self, version_json: &VersionDetails) -> JavaVersion {
+        if let Some(major_version) = self.java_version {
+            JavaVersion::from_major_version(major_version)
+        } else if let Some(version) = &version_json.javaVersion {
+            version.clone().into()
+        } else {
+            JavaVersion::Java8
+        }
+    }
+
+    /// The folder the game runs in, which is the
+    /// instance's own `.minecraft` unless `game_dir` is set.
+    pub fn get_game_dir(&self, instance_dir: &Path) -> PathBuf {
+        match self.game_dir.as_deref().map(str::trim) {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => instance_dir.join(".minecraft"),
+        }
+    }
+
+    pub fn managed_mod(&self, file_name: &str) -> Option<&ManagedMod> {
+        self.mods.iter().find(|n| n.file_name == file_name)
+    }
+
+    /// Adds `managed_mod`, replacing any other
+    /// version of the same project.
+    pub fn record_mod(&mut self, managed_mod: ManagedMod) {
+        self.mods
+            .retain(|n| n.source != managed_mod.source || n.project_id != managed_mod.project_id);
+        self.mods.push(managed_mod);
+    }
+
+    /// Forgets the mod in `file_name`, after it's deleted.
+    pub fn forget_mod(&mut self, file_name: &str) {
+        self.mods.retain(|n| n.file_name != file_name);
+    }
+
     /// Returns the amount of RAM in megabytes as a String.
     ///
     /// This is the format that the Java arguments understand.
@@ -33,9 +313,90 @@ impl InstanceConfigJson {
         format!("{}M", self.ram_in_mb)
     }
 
-    /// Returns a String containing the Java argument to
-    /// allocate the configured amount of RAM.
-    pub fn get_ram_argument(&self) -> String {
-        format!("-Xmx{}", self.get_ram_in_string())
+    /// The RAM the instance starts with, in megabytes.
+    /// Never more than `ram_in_mb`, which Java wouldn't start with.
+    pub fn get_initial_ram_in_mb(&self) -> usize {
+        self.initial_ram_in_mb
+            .unwrap_or(DEFAULT_INITIAL_RAM_MB)
+            .min(self.ram_in_mb)
+    }
+
+    /// Returns the Java arguments (`-Xms` and `-Xmx`)
+    /// to allocate the configured amount of RAM.
+    pub fn get_ram_arguments(&self) -> [String; 2] {
+        [
+            format!("-Xms{}M", self.get_initial_ram_in_mb()),
+            format!("-Xmx{}", self.get_ram_in_string()),
+        ]
+    }
+}
+
+/// Reads an amount of memory as typed, like `4G`, `4096M`,
+/// `1.5 GB` or `4096` (megabytes, like Java's `-Xmx`), in megabytes.
+pub fn parse_memory_mb(text: &str) -> Option<usize> {
+    let text = text.trim().to_ascii_lowercase();
+    let number_end = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(number_end);
+    let number: f64 = number.parse().ok()?;
+
+    let mb = match unit.trim() {
+        "" | "m" | "mb" | "mib" => number,
+        "g" | "gb" | "gib" => number * 1024.0,
+        "k" | "kb" | "kib" => number / 1024.0,
+        _ => return None,
+    };
+    (mb >= 1.0).then_some(mb as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_memory_mb() {
+        assert_eq!(parse_memory_mb("4G"), Some(4096));
+        assert_eq!(parse_memory_mb(" 1.5 gb "), Some(1536));
+        assert_eq!(parse_memory_mb("4096M"), Some(4096));
+        assert_eq!(parse_memory_mb("2048"), Some(2048));
+        assert_eq!(parse_memory_mb("lots"), None);
+        assert_eq!(parse_memory_mb("4T"), None);
+        assert_eq!(parse_memory_mb("0"), None);
+    }
+
+    #[test]
+    fn test_save_keeps_tracked_fields() {
+        let dir = std::env::temp_dir().join(format!("ql_config_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.json");
+        std::fs::write(&path, r#"{"ram_in_mb": 2048, "mod_type": "Vanilla"}"#).unwrap();
+
+        // A screen opens the config...
+        let mut edited = InstanceConfigJson::read(&path).unwrap();
+        // ...while a mod is installed in the background.
+        let mut installed = InstanceConfigJson::read(&path).unwrap();
+        installed.mod_type = "Fabric".to_owned();
+        installed.record_mod(ManagedMod {
+            source: ModSource::Modrinth,
+            project_id: "P7dR8mSH".to_owned(),
+            version: "1".to_owned(),
+            file_name: "fabric-api.jar".to_owned(),
+            sha1: String::new(),
+            enabled: true,
+            dependencies: Vec::new(),
+        });
+        installed.save(&path).unwrap();
+
+        edited.ram_in_mb = 4096;
+        edited.keep_tracked(InstanceConfigJson::read(&path).unwrap());
+        edited.save(&path).unwrap();
+
+        let saved = InstanceConfigJson::read(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(saved.ram_in_mb, 4096);
+        assert_eq!(saved.mod_type, "Fabric");
+        assert!(saved.managed_mod("fabric-api.jar").is_some());
+        assert_eq!(saved.config_version, InstanceConfigJson::VERSION);
     }
 }
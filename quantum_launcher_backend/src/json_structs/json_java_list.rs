@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use serde::{Deserialize, Serialize};
 
 use crate::file_utils;
@@ -16,23 +18,29 @@ pub enum JavaVersion {
     Java8,
 }
 
-impl ToString for JavaVersion {
-    fn to_string(&self) -> String {
-        match self {
+impl Display for JavaVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
             JavaVersion::Java16 => "java_16",
             JavaVersion::Java17Beta => "java_17_beta",
             JavaVersion::Java21 => "java_21",
             JavaVersion::Java17Gamma => "java_17_gamma",
             JavaVersion::Java17GammaSnapshot => "java_17_gamma_snapshot",
             JavaVersion::Java8 => "java_8",
-        }
-        .to_owned()
+        };
+        write!(f, "{name}")
     }
 }
 
-impl From<crate::json_structs::json_version::JavaVersion> for JavaVersion {
-    fn from(version: crate::json_structs::json_version::JavaVersion) -> Self {
-        match version.majorVersion {
+impl JavaVersion {
+    /// The Java major versions that the launcher can download and manage.
+    pub const MANAGED_MAJOR_VERSIONS: [usize; 4] = [8, 16, 17, 21];
+
+    /// Picks the launcher-managed Java install for a Java major version.
+    ///
+    /// Unknown versions use the newest available Java.
+    pub fn from_major_version(major_version: usize) -> Self {
+        match major_version {
             8 => JavaVersion::Java8,
             16 => JavaVersion::Java16,
             17 => JavaVersion::Java17Gamma,
@@ -42,6 +50,12 @@ impl From<crate::json_structs::json_version::JavaVersion> for JavaVersion {
     }
 }
 
+impl From<crate::json_structs::json_version::JavaVersion> for JavaVersion {
+    fn from(version: crate::json_structs::json_version::JavaVersion) -> Self {
+        JavaVersion::from_major_version(version.majorVersion)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "kebab-case")]
 pub struct JavaListJson {
@@ -57,7 +71,7 @@ pub struct JavaListJson {
 
 impl JavaListJson {
     pub async fn download() -> Result<Self, JsonDownloadError> {
-        let client = reqwest::Client::new();
+        let client = file_utils::create_client();
         let json = file_utils::download_file_to_string(&client, JAVA_LIST_URL).await?;
         Ok(serde_json::from_str(&json)?)
     }
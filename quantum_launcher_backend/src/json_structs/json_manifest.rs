@@ -1,4 +1,8 @@
+use std::fmt::Display;
+
+use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 
 use crate::file_utils;
 
@@ -11,12 +15,93 @@ pub struct Manifest {
 }
 
 impl Manifest {
+    /// Downloads Mojang's version manifest.
     pub async fn download() -> Result<Manifest, JsonDownloadError> {
-        const VERSIONS_JSON: &str = "https://launchermeta.mojang.com/mc/game/version_manifest.json";
+        Self::download_from(VersionSource::Mojang).await
+    }
+
+    /// Downloads the version manifest of `source`. Mojang's
+    /// also gets the versions from [`EXPERIMENTS_URL`].
+    pub async fn download_from(source: VersionSource) -> Result<Manifest, JsonDownloadError> {
+        let client = file_utils::create_client();
+        let manifest = file_utils::download_file_to_string(&client, source.manifest_url()).await?;
+        let mut manifest: Manifest = serde_json::from_str(&manifest)?;
+
+        if source == VersionSource::Mojang {
+            // Only some versions are missing if this fails,
+            // so it doesn't stop anything from working.
+            match download_experiments(&client).await {
+                Ok(experiments) => manifest.add_missing(experiments),
+                Err(err) => warn!("Could not list the experimental versions: {err}"),
+            }
+        }
+        Ok(manifest)
+    }
+
+    fn add_missing(&mut self, versions: Vec<Version>) {
+        for version in versions {
+            if !self.versions.iter().any(|n| n.id == version.id) {
+                self.versions.push(version);
+            }
+        }
+    }
+}
+
+/// A list of versions that are in neither of the manifests, like
+/// the combat tests, which Mojang only ever put in forum posts.
+/// It's kept in the launcher's repository (`assets/experiments.json`),
+/// so versions can be added without a launcher update.
+pub const EXPERIMENTS_URL: &str =
+    "https://raw.githubusercontent.com/Mrmayman/quantum-launcher-rs/main/assets/experiments.json";
+
+#[derive(Deserialize)]
+struct Experiments {
+    versions: Vec<Version>,
+}
+
+async fn download_experiments(client: &Client) -> Result<Vec<Version>, JsonDownloadError> {
+    let experiments = file_utils::download_file_to_string(client, EXPERIMENTS_URL).await?;
+    Ok(serde_json::from_str::<Experiments>(&experiments)?.versions)
+}
+
+/// Where the list of versions and their JSONs come from.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionSource {
+    #[default]
+    Mojang,
+    /// [BetterJSONs](https://github.com/MCPHackers/BetterJSONs), which
+    /// has fixed JSONs for old versions (many of Mojang's `old_alpha`
+    /// ones don't start) and versions Mojang doesn't list, from
+    /// Omniarchive.
+    BetterJsons,
+}
+
+impl VersionSource {
+    pub const ALL: [VersionSource; 2] = [VersionSource::Mojang, VersionSource::BetterJsons];
+
+    pub fn manifest_url(self) -> &'static str {
+        match self {
+            VersionSource::Mojang => {
+                "https://launchermeta.mojang.com/mc/game/version_manifest.json"
+            }
+            VersionSource::BetterJsons => {
+                "https://mcphackers.org/BetterJSONs/version_manifest_v2.json"
+            }
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            VersionSource::Mojang => "Mojang",
+            VersionSource::BetterJsons => "BetterJSONs (Omniarchive)",
+        }
+    }
+}
 
-        let client = reqwest::Client::new();
-        let manifest = file_utils::download_file_to_string(&client, VERSIONS_JSON).await?;
-        Ok(serde_json::from_str(&manifest)?)
+impl Display for VersionSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
     }
 }
 
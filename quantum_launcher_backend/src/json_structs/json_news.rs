@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::{
+    error::{HasErrorCode, IoError},
+    file_utils, io_err,
+};
+
+use super::JsonDownloadError;
+
+/// Mojang's news feed, as shown in the official launcher.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct News {
+    pub entries: Vec<NewsEntry>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NewsEntry {
+    pub title: String,
+    pub category: String,
+    pub date: String,
+    pub text: String,
+    pub readMoreLink: Option<String>,
+    /// Where the entry is shown, for example `"News page"` or `"Java"`.
+    #[serde(default)]
+    pub newsType: Vec<String>,
+}
+
+impl News {
+    /// Downloads the news feed and saves a copy to
+    /// `QuantumLauncher/cache/news.json`.
+    ///
+    /// If the download fails (for example, when offline)
+    /// the saved copy is used instead.
+    pub async fn download() -> Result<News, JsonDownloadError> {
+        const NEWS_JSON: &str = "https://launchercontent.mojang.com/news.json";
+
+        let client = file_utils::create_client();
+        let news = match file_utils::download_file_to_string(&client, NEWS_JSON).await {
+            Ok(news) => {
+                if let Err(err) = save_cache(&news) {
+                    error!("Could not save news to cache: {err}");
+                }
+                news
+            }
+            Err(err) => match load_cache() {
+                Some(news) => {
+                    error!("Could not download news, using cached copy: {err}");
+                    news
+                }
+                None => return Err(err.into()),
+            },
+        };
+        Ok(serde_json::from_str(&news)?)
+    }
+
+    /// Only keeps the entries about Minecraft: Java Edition.
+    pub fn java_entries(self) -> Vec<NewsEntry> {
+        self.entries
+            .into_iter()
+            .filter(|entry| {
+                entry.category == "Minecraft: Java Edition"
+                    || entry.newsType.iter().any(|n| n == "Java")
+            })
+            .collect()
+    }
+}
+
+/// Downloads the Java Edition news, for use with the iced GUI toolkit.
+pub async fn get_news_wrapped() -> Result<Vec<NewsEntry>, String> {
+    News::download()
+        .await
+        .map(News::java_entries)
+        .map_err(|err| err.to_string_with_code())
+}
+
+fn save_cache(news: &str) -> Result<(), IoError> {
+    let cache_path = file_utils::get_cache_dir()?.join("news.json");
+    std::fs::write(&cache_path, news).map_err(io_err!(cache_path))
+}
+
+fn load_cache() -> Option<String> {
+    let cache_path = file_utils::get_cache_dir().ok()?.join("news.json");
+    std::fs::read_to_string(cache_path).ok()
+}
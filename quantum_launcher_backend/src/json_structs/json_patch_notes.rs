@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::HasErrorCode, file_utils};
+
+use super::JsonDownloadError;
+
+/// The patch notes of Java Edition versions,
+/// as shown in the official launcher.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PatchNotes {
+    pub entries: Vec<PatchNote>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PatchNote {
+    pub title: String,
+    /// The version's id, like `1.20.4` or `24w14a`.
+    pub version: String,
+    /// When it came out, like `2023-12-07T12:56:20.108Z`.
+    #[serde(default)]
+    pub date: String,
+    /// A summary of what changed, in plain text.
+    #[serde(default)]
+    pub shortText: String,
+}
+
+impl PatchNotes {
+    pub async fn download() -> Result<PatchNotes, JsonDownloadError> {
+        const PATCH_NOTES_JSON: &str = "https://launchercontent.mojang.com/v2/javaPatchNotes.json";
+
+        let client = file_utils::create_client();
+        let notes = file_utils::download_file_to_string(&client, PATCH_NOTES_JSON).await?;
+        Ok(serde_json::from_str(&notes)?)
+    }
+}
+
+impl PatchNote {
+    /// The day it came out, like `2023-12-07`.
+    pub fn day(&self) -> &str {
+        self.date.split('T').next().unwrap_or_default()
+    }
+}
+
+/// Downloads the patch notes, for use with the iced GUI toolkit.
+pub async fn get_patch_notes_wrapped() -> Result<Arc<Vec<PatchNote>>, String> {
+    PatchNotes::download()
+        .await
+        .map(|notes| Arc::new(notes.entries))
+        .map_err(|err| err.to_string_with_code())
+}
@@ -1,8 +1,10 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, path::Path};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use super::{validation, JsonFileError};
+
 #[allow(non_snake_case)]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct VersionDetails {
@@ -41,6 +43,13 @@ pub struct VersionDetails {
     pub r#type: String,
 }
 
+impl VersionDetails {
+    /// Reads an instance's (or server's) `details.json`.
+    pub fn read(path: &Path) -> Result<Self, JsonFileError> {
+        validation::read(path)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Arguments {
     pub game: Vec<Value>,
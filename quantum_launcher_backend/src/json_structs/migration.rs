@@ -0,0 +1,151 @@
+//! Upgrading the launcher's JSON files when their format changes.
+//!
+//! Each file has a `config_version`, which is missing (so `0`)
+//! in files from before versioning. When an older file is read,
+//! it's backed up, upgraded by each [`Migration`] it's missing,
+//! and saved again.
+
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use tracing::info;
+
+use crate::io_err;
+
+use super::{validation, JsonFileError};
+
+pub const VERSION_KEY: &str = "config_version";
+
+/// Upgrades a file to `version` from the version before it.
+pub struct Migration {
+    pub version: u32,
+    pub migrate: fn(&mut Map<String, Value>),
+}
+
+/// The version of files that have all of `migrations`.
+pub const fn current_version(migrations: &[Migration]) -> u32 {
+    match migrations.last() {
+        Some(migration) => migration.version,
+        None => 0,
+    }
+}
+
+/// Reads a JSON file, upgrading it first if it's older
+/// than `migrations`. The old file is kept next to it,
+/// as `<name>.v<version>.json.bak`.
+///
+/// Files from newer versions of the launcher aren't
+/// read at all, as saving them again could lose
+/// whatever the newer version added.
+pub fn read_migrated<T: DeserializeOwned>(
+    path: &Path,
+    migrations: &[Migration],
+) -> Result<T, JsonFileError> {
+    let text = std::fs::read_to_string(path).map_err(io_err!(path))?;
+    let mut json: Value = validation::parse(path, &text)?;
+
+    let old_version =
+        migrate(&mut json, migrations).map_err(|version| JsonFileError::UnsupportedVersion {
+            path: path.to_owned(),
+            version,
+        })?;
+    let text = if let Some(old_version) = old_version {
+        info!(
+            "Upgrading {path:?} from version {old_version} to {}",
+            current_version(migrations)
+        );
+        let backup_path = path.with_extension(format!("v{old_version}.json.bak"));
+        // If there's a backup already, it's
+        // the older one, so it's kept instead.
+        if !backup_path.exists() {
+            std::fs::write(&backup_path, &text).map_err(io_err!(backup_path))?;
+        }
+        let text = serde_json::to_string(&json)?;
+        std::fs::write(path, &text).map_err(io_err!(path))?;
+        text
+    } else {
+        text
+    };
+
+    // Parsed from the text rather than `json`, so
+    // errors can say which line they're on.
+    let parsed = validation::parse(path, &text)?;
+    validation::save_backup(path, &text);
+    Ok(parsed)
+}
+
+/// Upgrades `json` in place. Returns the version it was
+/// before, or `None` if it was already up to date.
+///
+/// # Errors
+/// The file's version, if it's newer than `migrations`.
+pub fn migrate(json: &mut Value, migrations: &[Migration]) -> Result<Option<u32>, u32> {
+    // Anything else fails to deserialize, with a better error.
+    let Value::Object(object) = json else {
+        return Ok(None);
+    };
+
+    let version = object
+        .get(VERSION_KEY)
+        .and_then(Value::as_u64)
+        .map_or(0, |n| u32::try_from(n).unwrap_or(u32::MAX));
+    let current = current_version(migrations);
+    if version > current {
+        return Err(version);
+    }
+    if version == current {
+        return Ok(None);
+    }
+
+    for migration in migrations.iter().filter(|n| n.version > version) {
+        (migration.migrate)(object);
+        object.insert(VERSION_KEY.to_owned(), migration.version.into());
+    }
+    Ok(Some(version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIGRATIONS: &[Migration] = &[
+        Migration {
+            version: 1,
+            migrate: |json| {
+                json.insert("added".to_owned(), true.into());
+            },
+        },
+        Migration {
+            version: 2,
+            migrate: |json| {
+                if let Some(old) = json.remove("old_name") {
+                    json.insert("new_name".to_owned(), old);
+                }
+            },
+        },
+    ];
+
+    #[test]
+    fn test_migrate() {
+        let mut json = serde_json::json!({ "old_name": 5 });
+        assert_eq!(migrate(&mut json, MIGRATIONS), Ok(Some(0)));
+        assert_eq!(
+            json,
+            serde_json::json!({ "added": true, "new_name": 5, "config_version": 2 })
+        );
+
+        // Only the missing migrations are run.
+        let mut json = serde_json::json!({ "old_name": 5, "config_version": 1 });
+        assert_eq!(migrate(&mut json, MIGRATIONS), Ok(Some(1)));
+        assert_eq!(
+            json,
+            serde_json::json!({ "new_name": 5, "config_version": 2 })
+        );
+
+        assert_eq!(migrate(&mut json, MIGRATIONS), Ok(None));
+
+        let mut json = serde_json::json!({ "config_version": 3 });
+        assert_eq!(migrate(&mut json, MIGRATIONS), Err(3));
+    }
+}
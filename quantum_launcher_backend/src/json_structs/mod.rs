@@ -1,4 +1,7 @@
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
 use crate::{error::IoError, file_utils::RequestError};
 
@@ -7,8 +10,12 @@ pub mod json_instance_config;
 pub mod json_java_files;
 pub mod json_java_list;
 pub mod json_manifest;
+pub mod json_news;
+pub mod json_patch_notes;
 pub mod json_profiles;
 pub mod json_version;
+pub mod migration;
+pub mod validation;
 
 #[derive(Debug)]
 pub enum JsonDownloadError {
@@ -45,6 +52,25 @@ impl From<serde_json::Error> for JsonDownloadError {
 pub enum JsonFileError {
     SerdeError(serde_json::Error),
     Io(IoError),
+    /// The file was written by a newer version of the launcher.
+    UnsupportedVersion {
+        path: PathBuf,
+        version: u32,
+    },
+    /// A file that doesn't parse, probably after being
+    /// edited by hand. See [`validation`].
+    Invalid(Box<validation::InvalidJsonFile>),
+}
+
+impl JsonFileError {
+    /// The broken file, if it has a working copy
+    /// that [`validation::restore_backup`] can put back.
+    pub fn restorable_file(&self) -> Option<&Path> {
+        match self {
+            JsonFileError::Invalid(err) if err.backup.is_some() => Some(&err.path),
+            _ => None,
+        }
+    }
 }
 
 impl Display for JsonFileError {
@@ -52,6 +78,11 @@ impl Display for JsonFileError {
         match self {
             JsonFileError::SerdeError(err) => write!(f, "error reading json from file: {err}"),
             JsonFileError::Io(err) => write!(f, "error reading json from file: {err}"),
+            JsonFileError::UnsupportedVersion { path, version } => write!(
+                f,
+                "{path:?} is from a newer version of the launcher (config version {version}), update the launcher to use it"
+            ),
+            JsonFileError::Invalid(err) => write!(f, "{err}"),
         }
     }
 }
@@ -0,0 +1,202 @@
+//! Reading JSON files that users might have edited by hand,
+//! like `config.json` and `details.json`.
+//!
+//! When one of them can't be read, the error says where the
+//! mistake is and what would have been accepted, instead of
+//! just serde's message. Every time a file is read fine, a
+//! copy is kept as `<name>.json.bak`, so a broken file can be
+//! put back to how it was ([`restore_backup`]).
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use serde::de::DeserializeOwned;
+use tracing::{error, info};
+
+use crate::{error::IoError, io_err};
+
+use super::JsonFileError;
+
+/// Where the last working copy of `path` is kept.
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_owned();
+    file_name.push(".bak");
+    path.with_file_name(file_name)
+}
+
+/// Reads and parses a JSON file, keeping a backup
+/// of it if it's fine. See the [module docs](self).
+pub fn read<T: DeserializeOwned>(path: &Path) -> Result<T, JsonFileError> {
+    let text = std::fs::read_to_string(path).map_err(io_err!(path))?;
+    let parsed = parse(path, &text)?;
+    save_backup(path, &text);
+    Ok(parsed)
+}
+
+/// Parses the `text` of the file at `path`, with a
+/// helpful error if it's broken. Unlike [`read`],
+/// no backup is kept.
+pub fn parse<T: DeserializeOwned>(path: &Path, text: &str) -> Result<T, JsonFileError> {
+    serde_json::from_str(text)
+        .map_err(|err| JsonFileError::Invalid(Box::new(InvalidJsonFile::new(path, text, &err))))
+}
+
+/// Keeps `text` as the last working copy of the file at `path`.
+///
+/// This is only a safety net, so failing to
+/// write it isn't an error, just logged.
+pub fn save_backup(path: &Path, text: &str) {
+    let backup_path = backup_path(path);
+    if std::fs::read_to_string(&backup_path).is_ok_and(|backup| backup == text) {
+        return;
+    }
+    if let Err(err) = std::fs::write(&backup_path, text) {
+        error!("Could not back up {path:?}: {err}");
+    }
+}
+
+/// Replaces a broken file with its last working copy.
+pub fn restore_backup(path: &Path) -> Result<(), IoError> {
+    let backup_path = backup_path(path);
+    info!("Restoring {path:?} from {backup_path:?}");
+    std::fs::copy(&backup_path, path).map_err(io_err!(backup_path))?;
+    Ok(())
+}
+
+/// Where a hand-edited JSON file went wrong.
+#[derive(Debug)]
+pub struct InvalidJsonFile {
+    pub path: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    /// The field the mistake is in, if it's in one.
+    pub field: Option<String>,
+    pub problem: String,
+    /// What would have been accepted instead, in serde's words,
+    /// like ``one of `Light`, `Dark` `` or `usize`.
+    pub expected: Option<String>,
+    /// The last working copy of the file, if there is one.
+    pub backup: Option<PathBuf>,
+}
+
+impl InvalidJsonFile {
+    fn new(path: &Path, text: &str, err: &serde_json::Error) -> Self {
+        // serde's messages end with where the error is,
+        // which is shown separately here.
+        let message = err.to_string();
+        let message = message
+            .rsplit_once(" at line ")
+            .map_or(message.as_str(), |(message, _)| message);
+        let (problem, expected) = match message.split_once(", expected ") {
+            Some((problem, expected)) => (problem.to_owned(), Some(expected.to_owned())),
+            None => (message.to_owned(), None),
+        };
+
+        let field = if err.is_data() {
+            missing_field(&problem).or_else(|| find_field(text, err.line(), err.column()))
+        } else {
+            None
+        };
+        let backup = Some(backup_path(path)).filter(|backup| backup.is_file());
+
+        Self {
+            path: path.to_owned(),
+            line: err.line(),
+            column: err.column(),
+            field,
+            problem,
+            expected,
+            backup,
+        }
+    }
+}
+
+impl Display for InvalidJsonFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} has a mistake on line {}, column {}",
+            self.path, self.line, self.column
+        )?;
+        if let Some(field) = &self.field {
+            write!(f, ", in {field:?}")?;
+        }
+        write!(f, ": {}", self.problem)?;
+        if let Some(expected) = &self.expected {
+            write!(f, "\nAccepted values: {expected}")?;
+        }
+        if let Some(backup) = &self.backup {
+            write!(
+                f,
+                "\nThe last working copy is at {backup:?}. Fix the file, or copy that over it."
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Gets `x` from serde's ``missing field `x` ``.
+fn missing_field(problem: &str) -> Option<String> {
+    problem
+        .strip_prefix("missing field `")?
+        .strip_suffix('`')
+        .map(str::to_owned)
+}
+
+/// Finds the key of the value that the error is in, which
+/// is the last `"key":` before where serde stopped.
+fn find_field(text: &str, line: usize, column: usize) -> Option<String> {
+    let offset = text
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum::<usize>()
+        + column;
+    let before = text.get(..offset.min(text.len()))?;
+
+    let mut search = before;
+    while let Some(colon) = search.rfind(':') {
+        let key = search[..colon].trim_end();
+        if let Some(key) = key.strip_suffix('"') {
+            if let Some(start) = key.rfind('"') {
+                return Some(key[start + 1..].to_owned());
+            }
+        }
+        search = &search[..colon];
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Config {
+        ram_in_mb: usize,
+        mod_type: String,
+    }
+
+    #[test]
+    fn test_invalid_json_file() {
+        let path = Path::new("config.json");
+        let text = "{\n  \"mod_type\": \"Vanilla\",\n  \"ram_in_mb\": \"lots\"\n}";
+        let Err(JsonFileError::Invalid(err)) = parse::<Config>(path, text) else {
+            panic!("should be invalid");
+        };
+        assert_eq!(err.line, 3);
+        assert_eq!(err.field.as_deref(), Some("ram_in_mb"));
+        assert_eq!(err.expected.as_deref(), Some("usize"));
+
+        let Err(JsonFileError::Invalid(err)) = parse::<Config>(path, "{\"ram_in_mb\": 2048}")
+        else {
+            panic!("should be invalid");
+        };
+        assert_eq!(err.field.as_deref(), Some("mod_type"));
+    }
+}
@@ -0,0 +1,275 @@
+//! Backing up the whole launcher into one zip file, and
+//! restoring it, before risky things like updating a modpack.
+//!
+//! A backup has the launcher's `config.json`, the configs of
+//! every instance and server, and optionally the instances'
+//! worlds. Game files (like jars, libraries and assets) aren't
+//! backed up, as they can be downloaded again.
+
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use zip::{result::ZipError, write::FileOptions, ZipArchive, ZipWriter};
+
+use crate::{
+    error::{HasErrorCode, IoError},
+    file_utils, io_err,
+};
+
+/// Describes the backup, so that it can be recognized on restore.
+const MANIFEST_NAME: &str = "backup.json";
+const FORMAT: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct BackupManifest {
+    format: u32,
+    /// Seconds since the Unix epoch.
+    created: u64,
+    includes_saves: bool,
+}
+
+/// Where backups made from the launcher are kept.
+pub fn get_backups_dir() -> Result<PathBuf, IoError> {
+    Ok(file_utils::get_launcher_dir()?.join("launcher_backups"))
+}
+
+pub async fn create_backup_wrapped(include_saves: bool) -> Result<PathBuf, String> {
+    file_utils::run_blocking(move || create_backup(include_saves))
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Backs up the launcher into a new zip file
+/// in [`get_backups_dir`], returning its path.
+pub fn create_backup(include_saves: bool) -> Result<PathBuf, LauncherBackupError> {
+    let launcher_dir = file_utils::get_launcher_dir()?;
+    let backups_dir = get_backups_dir()?;
+    std::fs::create_dir_all(&backups_dir).map_err(io_err!(backups_dir))?;
+    let backup_path = backups_dir.join(format!(
+        "launcher_{}.zip",
+        file_utils::format_timestamp(SystemTime::now())
+    ));
+
+    info!("Backing up the launcher to {backup_path:?}");
+    let file = File::create(&backup_path).map_err(io_err!(backup_path))?;
+    let mut zip = ZipWriter::new(file);
+
+    let manifest = BackupManifest {
+        format: FORMAT,
+        created: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |n| n.as_secs()),
+        includes_saves: include_saves,
+    };
+    zip.start_file(MANIFEST_NAME, FileOptions::default())?;
+    zip.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())
+        .map_err(io_err!(backup_path))?;
+
+    let config_dir = file_utils::get_config_dir()?;
+    let config_path = config_dir.join("config.json");
+    if config_path.is_file() {
+        file_utils::add_file_to_zip::<LauncherBackupError>(&mut zip, &config_dir, &config_path)?;
+    }
+
+    for kind in ["instances", "servers"] {
+        let dir = launcher_dir.join(kind);
+        if !dir.is_dir() {
+            continue;
+        }
+        for entry in std::fs::read_dir(&dir).map_err(io_err!(dir))? {
+            let instance_dir = entry.map_err(io_err!(dir))?.path();
+            if instance_dir.is_dir() {
+                add_instance(&mut zip, &launcher_dir, &instance_dir, include_saves)?;
+            }
+        }
+    }
+
+    zip.finish()?;
+    Ok(backup_path)
+}
+
+/// Adds the files directly in an instance or server's folder,
+/// like `config.json` and `details.json`. Server jars are left
+/// out, as they're big and can be downloaded again.
+fn add_instance(
+    zip: &mut ZipWriter<File>,
+    launcher_dir: &Path,
+    instance_dir: &Path,
+    include_saves: bool,
+) -> Result<(), LauncherBackupError> {
+    for entry in std::fs::read_dir(instance_dir).map_err(io_err!(instance_dir))? {
+        let path = entry.map_err(io_err!(instance_dir))?.path();
+        if path.is_file() && path.extension().is_none_or(|n| n != "jar") {
+            file_utils::add_file_to_zip::<LauncherBackupError>(zip, launcher_dir, &path)?;
+        }
+    }
+
+    let saves_dir = instance_dir.join(".minecraft").join("saves");
+    if include_saves && saves_dir.is_dir() {
+        file_utils::add_dir_to_zip::<LauncherBackupError>(
+            zip,
+            launcher_dir,
+            &saves_dir,
+            &["session.lock"],
+        )?;
+    }
+    Ok(())
+}
+
+pub async fn restore_backup_wrapped(path: PathBuf) -> Result<(), String> {
+    file_utils::run_blocking(move || restore_backup(&path))
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Restores a backup made with [`create_backup`], overwriting
+/// the files in it. Instances that aren't in the backup are
+/// left alone.
+///
+/// The whole backup is checked before anything is written, so
+/// a broken or unrelated zip file doesn't leave a mess behind.
+pub fn restore_backup(path: &Path) -> Result<(), LauncherBackupError> {
+    let file = File::open(path).map_err(io_err!(path))?;
+    let mut archive = ZipArchive::new(file)?;
+    validate(&mut archive)?;
+
+    info!("Restoring the launcher from {path:?}");
+    let launcher_dir = file_utils::get_launcher_dir()?;
+    let config_dir = file_utils::get_config_dir()?;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let Some(name) = entry.enclosed_name().map(Path::to_owned) else {
+            continue;
+        };
+        if name == Path::new(MANIFEST_NAME) {
+            continue;
+        }
+
+        let dest = if name == Path::new("config.json") {
+            config_dir.join(name)
+        } else {
+            launcher_dir.join(name)
+        };
+        if entry.is_dir() {
+            std::fs::create_dir_all(&dest).map_err(io_err!(dest))?;
+            continue;
+        }
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent).map_err(io_err!(parent))?;
+        }
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes).map_err(io_err!(path))?;
+        std::fs::write(&dest, bytes).map_err(io_err!(dest))?;
+    }
+    Ok(())
+}
+
+/// Checks that the zip file is a launcher backup, and that
+/// it only has files where a backup would put them.
+fn validate(archive: &mut ZipArchive<File>) -> Result<(), LauncherBackupError> {
+    let manifest: BackupManifest = {
+        let mut entry = archive
+            .by_name(MANIFEST_NAME)
+            .map_err(|_| LauncherBackupError::NotABackup)?;
+        let mut manifest = String::new();
+        entry
+            .read_to_string(&mut manifest)
+            .map_err(|_| LauncherBackupError::NotABackup)?;
+        serde_json::from_str(&manifest).map_err(|_| LauncherBackupError::NotABackup)?
+    };
+    if manifest.format > FORMAT {
+        return Err(LauncherBackupError::UnsupportedFormat(manifest.format));
+    }
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let raw_name = entry.name().to_owned();
+        let name = entry
+            .enclosed_name()
+            .ok_or_else(|| LauncherBackupError::UnexpectedFile(raw_name.clone()))?
+            .to_owned();
+
+        let allowed = name == Path::new(MANIFEST_NAME)
+            || name == Path::new("config.json")
+            || name.starts_with("instances")
+            || name.starts_with("servers");
+        if !allowed {
+            return Err(LauncherBackupError::UnexpectedFile(raw_name));
+        }
+
+        // A config that doesn't parse would break the launcher
+        // after restoring, so the backup is refused instead.
+        // Worlds are deeper than configs, and not checked.
+        if name.components().count() > 3 || name.extension().is_none_or(|n| n != "json") {
+            continue;
+        }
+        let mut json = String::new();
+        entry
+            .read_to_string(&mut json)
+            .map_err(|_| LauncherBackupError::InvalidJson(raw_name.clone()))?;
+        serde_json::from_str::<serde_json::Value>(&json)
+            .map_err(|_| LauncherBackupError::InvalidJson(raw_name))?;
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum LauncherBackupError {
+    Io(IoError),
+    Zip(ZipError),
+    Json(serde_json::Error),
+    NotABackup,
+    UnsupportedFormat(u32),
+    UnexpectedFile(String),
+    InvalidJson(String),
+}
+
+impl From<IoError> for LauncherBackupError {
+    fn from(value: IoError) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<ZipError> for LauncherBackupError {
+    fn from(value: ZipError) -> Self {
+        Self::Zip(value)
+    }
+}
+
+impl From<serde_json::Error> for LauncherBackupError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl Display for LauncherBackupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LauncherBackupError::Io(err) => write!(f, "launcher backup failed: {err}"),
+            LauncherBackupError::Zip(err) => write!(f, "launcher backup failed: zip error: {err}"),
+            LauncherBackupError::Json(err) => write!(f, "launcher backup failed: {err}"),
+            LauncherBackupError::NotABackup => {
+                write!(f, "this file isn't a launcher backup (no {MANIFEST_NAME})")
+            }
+            LauncherBackupError::UnsupportedFormat(format) => write!(
+                f,
+                "this backup is from a newer version of the launcher (format {format})"
+            ),
+            LauncherBackupError::UnexpectedFile(name) => write!(
+                f,
+                "this backup has a file where it shouldn't be, so it wasn't restored: {name}"
+            ),
+            LauncherBackupError::InvalidJson(name) => write!(
+                f,
+                "this backup has a broken config, so it wasn't restored: {name}"
+            ),
+        }
+    }
+}
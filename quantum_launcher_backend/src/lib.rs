@@ -1,16 +1,84 @@
+pub mod cancel;
+pub mod crash_dump;
+pub mod discord_rpc;
 mod download;
+pub mod download_queue;
 pub mod error;
+pub mod events;
 pub mod file_utils;
 mod instance;
 mod java_install;
 pub mod json_structs;
+pub mod launcher_backup;
+pub mod log_upload;
+pub mod logging;
+pub mod nbt;
+pub mod offline_profiles;
+pub mod playtime;
+pub mod progress;
+pub mod self_update;
+pub mod server_ping;
+pub mod skins;
 
 pub use download::progress::DownloadProgress;
+pub use download::CustomVersionJson;
+pub use download::DownloadError;
+pub use error::{ErrorCode, HasErrorCode, LaunchFailure, LaunchPhase};
+pub use instance::game_options;
 pub use instance::instance_create::create_instance;
+pub use instance::instance_create::create_instance_from_json;
+pub use instance::instance_create::create_instance_from_json_wrapped;
+pub use instance::instance_create::create_instance_wrapped;
+pub use instance::instance_launch::get_game_dir;
+pub use instance::instance_launch::join_arguments;
 pub use instance::instance_launch::launch;
+pub use instance::instance_launch::launch_dry_run;
 pub use instance::instance_launch::launch_wrapped;
+pub use instance::instance_launch::redownload_version_json;
+pub use instance::instance_launch::redownload_version_json_wrapped;
+pub use instance::instance_launch::restore_disabled_mods;
+pub use instance::instance_launch::split_arguments;
 pub use instance::instance_launch::GameLaunchResult;
+pub use instance::instance_list::delete_instance;
+pub use instance::instance_list::delete_server;
+pub use instance::instance_list::list_instance_names;
+pub use instance::instance_list::list_instances;
+pub use instance::instance_list::list_server_names;
+pub use instance::instance_list::list_servers;
+pub use instance::instance_list::FolderChanges;
+pub use instance::instance_list::InstanceFolderWatcher;
+pub use instance::instance_list::InstanceInfo;
+pub use instance::instance_list::InstanceRegistry;
 pub use instance::instance_list_versions::list_versions;
+pub use instance::instance_list_versions::ListedVersion;
+pub use instance::instance_list_versions::VersionType;
 pub use instance::instance_mod_installer;
+pub use instance::integrity;
+pub use instance::java_check;
+pub use instance::launch_options::{
+    check_username, Account, LaunchCommand, LaunchOptions, UsernameProblem, USERNAME_LENGTH,
+};
+pub use instance::server_create::create_server;
+pub use instance::server_create::create_server_wrapped;
+pub use instance::server_files;
+pub use instance::server_launch::launch_server;
+pub use instance::server_launch::launch_server_wrapped;
+pub use instance::server_launch::send_server_command;
+pub use instance::server_launch::stop_server;
+pub use instance::server_schedule;
+pub use instance::server_software;
+pub use instance::snapshots;
+pub use instance::sync_folder;
+pub use instance::templates;
+pub use instance::world_backup;
+pub use instance::worlds;
+pub use instance_mod_installer::fabric::FabricInstallProgress;
 pub use instance_mod_installer::fabric::FabricVersion;
+pub use instance_mod_installer::fabric::FabricVersionList;
+pub use java_install::delete_java_installs;
+pub use java_install::delete_java_installs_wrapped;
+pub use java_install::delete_unused_java_installs;
+pub use java_install::delete_unused_java_installs_wrapped;
+pub use java_install::find_unused_java_installs;
+pub use java_install::find_unused_java_installs_wrapped;
 pub use java_install::JavaInstallMessage;
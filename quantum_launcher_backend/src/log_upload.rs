@@ -0,0 +1,170 @@
+//! Sharing game logs and crash reports on [mclo.gs](https://mclo.gs),
+//! so that asking for help is just pasting a link.
+//!
+//! Logs are redacted before uploading, since the launch
+//! command (which some loaders print) has the access token in it.
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use serde::Deserialize;
+use tracing::info;
+
+use crate::{
+    error::{HasErrorCode, IoError},
+    file_utils::{self, RequestError},
+    get_game_dir, io_err, logging,
+};
+
+const UPLOAD_URL: &str = "https://api.mclo.gs/1/log";
+/// mclo.gs cuts logs off after this many lines, keeping the
+/// start. The end is what matters when the game crashed.
+const MAX_LINES: usize = 25_000;
+
+#[derive(Deserialize)]
+struct UploadResponse {
+    success: bool,
+    url: Option<String>,
+    error: Option<String>,
+}
+
+pub async fn upload_log_wrapped(text: String) -> Result<String, String> {
+    upload_log(&text)
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Redacts the log and uploads it, returning the link to it.
+pub async fn upload_log(text: &str) -> Result<String, LogUploadError> {
+    let mut content = redact_log(text);
+    let line_count = content.lines().count();
+    if line_count > MAX_LINES {
+        content = content
+            .lines()
+            .skip(line_count - MAX_LINES)
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+
+    info!("Uploading log to mclo.gs");
+    let response = file_utils::create_client()
+        .post(UPLOAD_URL)
+        .form(&[("content", content)])
+        .send()
+        .await
+        .map_err(RequestError::from)?;
+    if !response.status().is_success() {
+        return Err(RequestError::DownloadError {
+            code: response.status(),
+            url: response.url().clone(),
+        }
+        .into());
+    }
+    let response: UploadResponse =
+        serde_json::from_str(&response.text().await.map_err(RequestError::from)?)?;
+
+    match (response.success, response.url) {
+        (true, Some(url)) => Ok(url),
+        _ => Err(LogUploadError::Rejected(response.error.unwrap_or_default())),
+    }
+}
+
+/// Hides access tokens and session IDs, and the home
+/// directory, as it usually contains the user's real name.
+pub fn redact_log(text: &str) -> String {
+    let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE"));
+    let home = home.ok().filter(|n| !n.is_empty());
+
+    text.lines()
+        .map(|line| {
+            let line = logging::redact_secrets(line);
+            match &home {
+                Some(home) => line.replace(home.as_str(), "~"),
+                None => line,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The newest crash report the game wrote since `since`,
+/// if it crashed with one.
+pub fn find_crash_report(instance_name: &str, since: SystemTime) -> Option<PathBuf> {
+    let dir = get_game_dir(instance_name).ok()?.join("crash-reports");
+
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            (modified >= since).then(|| (modified, entry.path()))
+        })
+        .max()
+        .map(|(_, path)| path)
+}
+
+pub fn read_crash_report(path: &Path) -> Result<String, LogUploadError> {
+    Ok(std::fs::read_to_string(path).map_err(io_err!(path))?)
+}
+
+#[derive(Debug)]
+pub enum LogUploadError {
+    Request(RequestError),
+    Json(serde_json::Error),
+    Io(IoError),
+    /// mclo.gs didn't take the log, with its reason.
+    Rejected(String),
+}
+
+impl From<RequestError> for LogUploadError {
+    fn from(value: RequestError) -> Self {
+        Self::Request(value)
+    }
+}
+
+impl From<serde_json::Error> for LogUploadError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl From<IoError> for LogUploadError {
+    fn from(value: IoError) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl Display for LogUploadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogUploadError::Request(err) => write!(f, "could not upload log: {err}"),
+            LogUploadError::Json(err) => {
+                write!(f, "could not upload log: invalid response: {err}")
+            }
+            LogUploadError::Io(err) => write!(f, "could not read crash report: {err}"),
+            LogUploadError::Rejected(reason) => {
+                write!(f, "mclo.gs did not accept the log: {reason}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_log() {
+        let log = "Launching: java -cp a.jar Main --username Steve --accessToken abc.def --version 1.20\n\
+                   [Client thread/INFO]: Setting user: Steve\n\
+                   args: [Steve, token:secret:1234]";
+        let redacted = redact_log(log);
+        assert!(!redacted.contains("abc.def"));
+        assert!(!redacted.contains("secret"));
+        assert!(redacted.contains("--accessToken [redacted] --version 1.20"));
+        assert!(redacted.contains("Setting user: Steve"));
+    }
+}
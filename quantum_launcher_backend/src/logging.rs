@@ -0,0 +1,235 @@
+//! The launcher's own logs (not the game's), for diagnosing
+//! user reports.
+//!
+//! Everything is logged with [`tracing`], with the module
+//! as the target (like `quantum_launcher_backend::download`).
+//! Logs go to the console, to a daily log file in
+//! `QuantumLauncher/logs/` (keeping the last week), and to
+//! an in-memory list of recent lines, for showing in the launcher.
+//!
+//! Verbose logging ([`set_verbose`]) also logs the full launch
+//! commands (with secrets hidden), every HTTP request and how
+//! long each launch and install step took.
+
+use std::{
+    collections::VecDeque,
+    io::Write,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, OnceLock,
+    },
+    time::Instant,
+};
+
+use tracing::{debug, level_filters::LevelFilter};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::{
+    filter::Targets, fmt::MakeWriter, layer::SubscriberExt, reload, Layer, Registry,
+};
+
+use crate::{error::IoError, file_utils};
+
+/// How many log files are kept, one per day.
+const MAX_LOG_FILES: usize = 7;
+/// How many lines [`recent_logs`] keeps.
+const MAX_RECENT_LINES: usize = 1000;
+
+/// Arguments whose next argument is a secret.
+const SECRET_ARGS: &[&str] = &["--accessToken", "--session", "--xuid", "--clientId"];
+
+static RECENT_LINES: OnceLock<Mutex<VecDeque<String>>> = OnceLock::new();
+static FILTER: OnceLock<reload::Handle<Targets, Registry>> = OnceLock::new();
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// Where the logs are printed, besides the log file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogConsole {
+    Stdout,
+    /// For the CLI, where stdout is for the command's output.
+    Stderr,
+    None,
+}
+
+/// The launcher's log files are next to `config.json`, so
+/// they stay put when the data directory is moved.
+pub fn get_logs_dir() -> Result<PathBuf, IoError> {
+    Ok(file_utils::get_config_dir()?.join("logs"))
+}
+
+/// Starts logging. Call it once, after `--portable` is handled.
+///
+/// If the log file can't be made, logs still go to the console.
+pub fn init(console: LogConsole) {
+    let (filter, filter_handle) = reload::Layer::new(filter(is_verbose()));
+
+    let (file, file_err) = match open_log_file() {
+        Ok(file) => (Some(file), None),
+        Err(err) => (None, Some(err)),
+    };
+
+    let console_layer = match console {
+        LogConsole::Stdout => Some(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stdout)
+                .boxed(),
+        ),
+        LogConsole::Stderr => Some(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .boxed(),
+        ),
+        LogConsole::None => None,
+    };
+    let file_layer = file.map(|file| {
+        tracing_subscriber::fmt::layer()
+            .with_ansi(false)
+            .with_writer(file)
+    });
+    let recent_layer = tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .with_writer(RecentLines);
+
+    let subscriber = tracing_subscriber::registry()
+        .with(filter)
+        .with(console_layer)
+        .with(file_layer)
+        .with(recent_layer);
+    if tracing::subscriber::set_global_default(subscriber).is_err() {
+        return;
+    }
+    _ = FILTER.set(filter_handle);
+
+    if let Some(err) = file_err {
+        tracing::error!("Could not create launcher log file: {err}");
+    }
+    tracing::info!(
+        "QuantumLauncher {} on {} ({})",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+}
+
+/// The launcher's own logs, and only warnings
+/// from libraries (like the HTTP client).
+fn filter(verbose: bool) -> Targets {
+    let level = if verbose {
+        LevelFilter::DEBUG
+    } else {
+        LevelFilter::INFO
+    };
+    Targets::new()
+        .with_target("quantum_launcher", level)
+        .with_target("quantum_launcher_backend", level)
+        .with_target("quantum_launcher_cli", level)
+        .with_default(LevelFilter::WARN)
+}
+
+/// Turns verbose (debug) logging on or off. It can be
+/// called before [`init`], like for a `--verbose` argument.
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+    if let Some(handle) = FILTER.get() {
+        if let Err(err) = handle.reload(filter(verbose)) {
+            tracing::error!("Could not change log level: {err}");
+        }
+    }
+    debug!("Verbose logging is on");
+}
+
+pub fn is_verbose() -> bool {
+    VERBOSE.load(Ordering::Relaxed)
+}
+
+/// Hides access tokens and session IDs in a log line
+/// or launch command, so that logs can be shared.
+pub fn redact_secrets(line: &str) -> String {
+    let mut redact_next = false;
+    line.split(' ')
+        .map(|word| {
+            let secret = redact_next && !word.is_empty();
+            redact_next = SECRET_ARGS.contains(&word.trim_matches(|c| c == ',' || c == '"'));
+            // Old versions take a `token:<access token>:<uuid>` session.
+            if secret || word.trim_start_matches('"').starts_with("token:") {
+                "[redacted]"
+            } else {
+                word
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Logs how long a launch or install step took, when dropped.
+/// Only shown with verbose logging.
+pub struct StepTimer {
+    step: &'static str,
+    start: Instant,
+}
+
+pub fn time_step(step: &'static str) -> StepTimer {
+    debug!("{step}...");
+    StepTimer {
+        step,
+        start: Instant::now(),
+    }
+}
+
+impl Drop for StepTimer {
+    fn drop(&mut self) {
+        debug!("{} took {:.2?}", self.step, self.start.elapsed());
+    }
+}
+
+fn open_log_file() -> Result<RollingFileAppender, String> {
+    let dir = get_logs_dir().map_err(|err| err.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|err| format!("{dir:?}: {err}"))?;
+    RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("launcher")
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(dir)
+        .map_err(|err| err.to_string())
+}
+
+/// The last lines logged since the launcher started, oldest first.
+pub fn recent_logs() -> Vec<String> {
+    RECENT_LINES
+        .get()
+        .map(|lines| lines.lock().unwrap().iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Writes log lines into [`RECENT_LINES`].
+#[derive(Clone, Copy)]
+struct RecentLines;
+
+impl Write for RecentLines {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut lines = RECENT_LINES
+            .get_or_init(|| Mutex::new(VecDeque::new()))
+            .lock()
+            .unwrap();
+        for line in String::from_utf8_lossy(buf).lines() {
+            if lines.len() == MAX_RECENT_LINES {
+                lines.pop_front();
+            }
+            lines.push_back(line.to_owned());
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for RecentLines {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        *self
+    }
+}
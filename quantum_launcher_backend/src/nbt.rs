@@ -0,0 +1,390 @@
+//! Reading and writing Minecraft's NBT format, which
+//! files like `level.dat` are stored in.
+//!
+//! Only what the launcher needs is here: a whole file is read
+//! into a [`Tag`] tree, and can be written back unchanged
+//! apart from what was edited.
+
+use std::{
+    fmt::Display,
+    io::{Read, Write},
+    path::Path,
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+
+use crate::{error::IoError, io_err};
+
+/// Deeper nesting than this is treated as a broken
+/// file, instead of overflowing the stack.
+const MAX_DEPTH: usize = 512;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    /// The type ID of the elements, and the elements.
+    List(u8, Vec<Tag>),
+    Compound(Vec<(String, Tag)>),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+impl Tag {
+    fn id(&self) -> u8 {
+        match self {
+            Tag::Byte(_) => 1,
+            Tag::Short(_) => 2,
+            Tag::Int(_) => 3,
+            Tag::Long(_) => 4,
+            Tag::Float(_) => 5,
+            Tag::Double(_) => 6,
+            Tag::ByteArray(_) => 7,
+            Tag::String(_) => 8,
+            Tag::List(..) => 9,
+            Tag::Compound(_) => 10,
+            Tag::IntArray(_) => 11,
+            Tag::LongArray(_) => 12,
+        }
+    }
+
+    /// Gets a child of a compound tag.
+    pub fn get(&self, name: &str) -> Option<&Tag> {
+        match self {
+            Tag::Compound(children) => children
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, tag)| tag),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Tag> {
+        match self {
+            Tag::Compound(children) => children
+                .iter_mut()
+                .find(|(key, _)| key == name)
+                .map(|(_, tag)| tag),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Tag::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Any whole number, as the exact type
+    /// of some fields changed over the years.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Tag::Byte(n) => Some(i64::from(*n)),
+            Tag::Short(n) => Some(i64::from(*n)),
+            Tag::Int(n) => Some(i64::from(*n)),
+            Tag::Long(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+/// Reads a gzipped NBT file, like `level.dat`.
+/// Returns the root tag's name and the tag.
+pub fn read_file(path: &Path) -> Result<(String, Tag), NbtError> {
+    let file = std::fs::File::open(path).map_err(io_err!(path))?;
+    let mut bytes = Vec::new();
+    GzDecoder::new(file)
+        .read_to_end(&mut bytes)
+        .map_err(io_err!(path))?;
+    from_bytes(&bytes)
+}
+
+/// Writes a gzipped NBT file, replacing it if it exists.
+pub fn write_file(path: &Path, name: &str, tag: &Tag) -> Result<(), NbtError> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&to_bytes(name, tag))
+        .map_err(io_err!(path))?;
+    let bytes = encoder.finish().map_err(io_err!(path))?;
+    std::fs::write(path, bytes).map_err(io_err!(path))?;
+    Ok(())
+}
+
+pub fn from_bytes(bytes: &[u8]) -> Result<(String, Tag), NbtError> {
+    let mut reader = Reader { bytes, pos: 0 };
+    let id = reader.u8()?;
+    if id != 10 {
+        return Err(NbtError::Invalid("the root isn't a compound tag"));
+    }
+    let name = reader.string()?;
+    let tag = reader.payload(id, 0)?;
+    Ok((name, tag))
+}
+
+pub fn to_bytes(name: &str, tag: &Tag) -> Vec<u8> {
+    let mut bytes = vec![tag.id()];
+    write_string(&mut bytes, name);
+    write_payload(&mut bytes, tag);
+    bytes
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], NbtError> {
+        let slice = self
+            .bytes
+            .get(self.pos..self.pos.saturating_add(len))
+            .ok_or(NbtError::Invalid("the file ends too early"))?;
+        self.pos += len;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], NbtError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8, NbtError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    /// A length, which can't be more than the bytes left.
+    fn len(&mut self, element_size: usize) -> Result<usize, NbtError> {
+        let len = i32::from_be_bytes(self.array()?);
+        let len = usize::try_from(len).map_err(|_| NbtError::Invalid("negative length"))?;
+        if len.saturating_mul(element_size) > self.bytes.len() - self.pos {
+            return Err(NbtError::Invalid("the file ends too early"));
+        }
+        Ok(len)
+    }
+
+    fn string(&mut self) -> Result<String, NbtError> {
+        let len = u16::from_be_bytes(self.array()?);
+        Ok(decode_mutf8(self.take(usize::from(len))?))
+    }
+
+    fn payload(&mut self, id: u8, depth: usize) -> Result<Tag, NbtError> {
+        if depth > MAX_DEPTH {
+            return Err(NbtError::Invalid("tags are nested too deeply"));
+        }
+        Ok(match id {
+            1 => Tag::Byte(i8::from_be_bytes(self.array()?)),
+            2 => Tag::Short(i16::from_be_bytes(self.array()?)),
+            3 => Tag::Int(i32::from_be_bytes(self.array()?)),
+            4 => Tag::Long(i64::from_be_bytes(self.array()?)),
+            5 => Tag::Float(f32::from_be_bytes(self.array()?)),
+            6 => Tag::Double(f64::from_be_bytes(self.array()?)),
+            7 => {
+                let len = self.len(1)?;
+                Tag::ByteArray(self.take(len)?.iter().map(|n| *n as i8).collect())
+            }
+            8 => Tag::String(self.string()?),
+            9 => {
+                let element_id = self.u8()?;
+                let len = self.len(1)?;
+                if len > 0 && element_id == 0 {
+                    return Err(NbtError::Invalid("list of end tags"));
+                }
+                let elements = (0..len)
+                    .map(|_| self.payload(element_id, depth + 1))
+                    .collect::<Result<_, _>>()?;
+                Tag::List(element_id, elements)
+            }
+            10 => {
+                let mut children = Vec::new();
+                loop {
+                    let child_id = self.u8()?;
+                    if child_id == 0 {
+                        break;
+                    }
+                    let name = self.string()?;
+                    children.push((name, self.payload(child_id, depth + 1)?));
+                }
+                Tag::Compound(children)
+            }
+            11 => {
+                let len = self.len(4)?;
+                Tag::IntArray(
+                    (0..len)
+                        .map(|_| Ok(i32::from_be_bytes(self.array()?)))
+                        .collect::<Result<_, NbtError>>()?,
+                )
+            }
+            12 => {
+                let len = self.len(8)?;
+                Tag::LongArray(
+                    (0..len)
+                        .map(|_| Ok(i64::from_be_bytes(self.array()?)))
+                        .collect::<Result<_, NbtError>>()?,
+                )
+            }
+            _ => return Err(NbtError::Invalid("unknown tag type")),
+        })
+    }
+}
+
+fn write_payload(bytes: &mut Vec<u8>, tag: &Tag) {
+    match tag {
+        Tag::Byte(n) => bytes.extend_from_slice(&n.to_be_bytes()),
+        Tag::Short(n) => bytes.extend_from_slice(&n.to_be_bytes()),
+        Tag::Int(n) => bytes.extend_from_slice(&n.to_be_bytes()),
+        Tag::Long(n) => bytes.extend_from_slice(&n.to_be_bytes()),
+        Tag::Float(n) => bytes.extend_from_slice(&n.to_be_bytes()),
+        Tag::Double(n) => bytes.extend_from_slice(&n.to_be_bytes()),
+        Tag::ByteArray(array) => {
+            bytes.extend_from_slice(&(array.len() as i32).to_be_bytes());
+            bytes.extend(array.iter().map(|n| *n as u8));
+        }
+        Tag::String(value) => write_string(bytes, value),
+        Tag::List(element_id, elements) => {
+            bytes.push(*element_id);
+            bytes.extend_from_slice(&(elements.len() as i32).to_be_bytes());
+            for element in elements {
+                write_payload(bytes, element);
+            }
+        }
+        Tag::Compound(children) => {
+            for (name, child) in children {
+                bytes.push(child.id());
+                write_string(bytes, name);
+                write_payload(bytes, child);
+            }
+            bytes.push(0);
+        }
+        Tag::IntArray(array) => {
+            bytes.extend_from_slice(&(array.len() as i32).to_be_bytes());
+            for n in array {
+                bytes.extend_from_slice(&n.to_be_bytes());
+            }
+        }
+        Tag::LongArray(array) => {
+            bytes.extend_from_slice(&(array.len() as i32).to_be_bytes());
+            for n in array {
+                bytes.extend_from_slice(&n.to_be_bytes());
+            }
+        }
+    }
+}
+
+fn write_string(bytes: &mut Vec<u8>, value: &str) {
+    let encoded = encode_mutf8(value);
+    // Longer strings can't be stored, and aren't written by the launcher.
+    let len = encoded.len().min(usize::from(u16::MAX));
+    bytes.extend_from_slice(&(len as u16).to_be_bytes());
+    bytes.extend_from_slice(&encoded[..len]);
+}
+
+/// NBT strings are in Java's "modified UTF-8": each UTF-16
+/// unit is encoded on its own, and `\0` takes two bytes.
+fn decode_mutf8(bytes: &[u8]) -> String {
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = u16::from(bytes[i]);
+        let continuation = |offset: usize| u16::from(*bytes.get(i + offset).unwrap_or(&0) & 0x3F);
+        let (unit, len) = if byte < 0x80 {
+            (byte, 1)
+        } else if byte & 0xE0 == 0xC0 {
+            (((byte & 0x1F) << 6) | continuation(1), 2)
+        } else {
+            (
+                ((byte & 0x0F) << 12) | (continuation(1) << 6) | continuation(2),
+                3,
+            )
+        };
+        units.push(unit);
+        i += len;
+    }
+    String::from_utf16_lossy(&units)
+}
+
+fn encode_mutf8(value: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(value.len());
+    for unit in value.encode_utf16() {
+        match unit {
+            0x01..=0x7F => bytes.push(unit as u8),
+            0x00 | 0x80..=0x7FF => {
+                bytes.push(0xC0 | (unit >> 6) as u8);
+                bytes.push(0x80 | (unit & 0x3F) as u8);
+            }
+            _ => {
+                bytes.push(0xE0 | (unit >> 12) as u8);
+                bytes.push(0x80 | ((unit >> 6) & 0x3F) as u8);
+                bytes.push(0x80 | (unit & 0x3F) as u8);
+            }
+        }
+    }
+    bytes
+}
+
+#[derive(Debug)]
+pub enum NbtError {
+    Io(IoError),
+    Invalid(&'static str),
+}
+
+impl From<IoError> for NbtError {
+    fn from(value: IoError) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl Display for NbtError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NbtError::Io(err) => write!(f, "could not read nbt file: {err}"),
+            NbtError::Invalid(problem) => write!(f, "invalid nbt file: {problem}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nbt_round_trip() {
+        let tag = Tag::Compound(vec![(
+            "Data".to_owned(),
+            Tag::Compound(vec![
+                (
+                    "LevelName".to_owned(),
+                    Tag::String("Wörld \u{1F30D}".to_owned()),
+                ),
+                ("GameType".to_owned(), Tag::Int(1)),
+                ("LastPlayed".to_owned(), Tag::Long(1_700_000_000_000)),
+                (
+                    "Version".to_owned(),
+                    Tag::Compound(vec![("Name".to_owned(), Tag::String("1.20.4".to_owned()))]),
+                ),
+                ("Pos".to_owned(), Tag::List(6, vec![Tag::Double(0.5)])),
+                ("Seeds".to_owned(), Tag::LongArray(vec![-1, 2])),
+            ]),
+        )]);
+
+        let bytes = to_bytes("", &tag);
+        let (name, read) = from_bytes(&bytes).unwrap();
+        assert_eq!(name, "");
+        assert_eq!(read, tag);
+        let data = read.get("Data").unwrap();
+        assert_eq!(
+            data.get("LevelName").and_then(Tag::as_str),
+            Some("Wörld \u{1F30D}")
+        );
+        assert_eq!(data.get("GameType").and_then(Tag::as_i64), Some(1));
+
+        assert!(from_bytes(&bytes[..bytes.len() - 3]).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+//! Named offline profiles: a username with the UUID the
+//! game would give it offline, and optionally a skin.
+//!
+//! Profiles are kept in the launcher's config, and an
+//! instance can be set to always launch as one of them.
+
+use std::path::PathBuf;
+
+use md5::{Digest, Md5};
+use serde::{Deserialize, Serialize};
+
+use crate::{skins::SkinSource, Account};
+
+/// A saved offline account.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OfflineProfile {
+    /// What the profile is called in the launcher.
+    pub name: String,
+    pub username: String,
+    /// From [`offline_uuid`], so singleplayer saves and offline
+    /// servers see the same player as they would without
+    /// the launcher.
+    pub uuid: String,
+    /// A PNG skin file, shown in the launcher.
+    #[serde(default)]
+    pub skin: Option<PathBuf>,
+}
+
+impl OfflineProfile {
+    pub fn new(name: String, username: String, skin: Option<PathBuf>) -> Self {
+        Self {
+            uuid: offline_uuid(&username),
+            name,
+            username,
+            skin,
+        }
+    }
+
+    /// Its skin file, or the skin of the online
+    /// account with the same username.
+    pub fn skin_source(&self) -> SkinSource {
+        match &self.skin {
+            Some(skin) => SkinSource::File(skin.clone()),
+            None => SkinSource::Username(self.username.clone()),
+        }
+    }
+
+    pub fn account(&self) -> Account {
+        Account {
+            uuid: self.uuid.clone(),
+            ..Account::offline(self.username.clone())
+        }
+    }
+}
+
+/// The UUID Minecraft gives an offline player: a version 3
+/// UUID of `OfflinePlayer:<username>`.
+pub fn offline_uuid(username: &str) -> String {
+    let mut hash = Md5::digest(format!("OfflinePlayer:{username}"));
+    hash[6] = hash[6] & 0x0f | 0x30;
+    hash[8] = hash[8] & 0x3f | 0x80;
+
+    let hex: String = hash.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline_uuid() {
+        assert_eq!(
+            offline_uuid("Notch"),
+            "b50ad385-829d-3141-a216-7e7d7539ba7f"
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! Playtime statistics, kept in `QuantumLauncher/stats.json`.
+//!
+//! Every game session is recorded when the game exits, and
+//! the totals (per instance, per version, per week) are
+//! worked out from the sessions when they're shown.
+
+use std::{collections::HashMap, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    file_utils, io_err,
+    json_structs::{validation, JsonFileError},
+};
+
+const WEEK: u64 = 7 * 24 * 60 * 60;
+
+/// One run of the game, from launch to exit.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    pub instance: String,
+    /// The Minecraft version, if it was known.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// When the game started, in seconds since the Unix epoch.
+    pub started: u64,
+    /// How long the game ran, in seconds.
+    pub seconds: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PlaytimeStats {
+    #[serde(default)]
+    pub sessions: Vec<Session>,
+}
+
+fn stats_path() -> Result<PathBuf, JsonFileError> {
+    Ok(file_utils::get_launcher_dir()?.join("stats.json"))
+}
+
+impl PlaytimeStats {
+    /// Reads the stats file, or returns empty
+    /// stats if nothing was played yet.
+    pub fn load() -> Result<Self, JsonFileError> {
+        let path = stats_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        validation::read(&path)
+    }
+
+    pub fn save(&self) -> Result<(), JsonFileError> {
+        let path = stats_path()?;
+        std::fs::write(&path, serde_json::to_string(self)?).map_err(io_err!(path))?;
+        Ok(())
+    }
+
+    /// Adds a session to the stats file.
+    pub fn record(session: Session) -> Result<(), JsonFileError> {
+        let mut stats = Self::load()?;
+        stats.sessions.push(session);
+        stats.save()
+    }
+
+    pub fn total_seconds(&self) -> u64 {
+        self.sessions.iter().map(|n| n.seconds).sum()
+    }
+
+    /// Seconds played per instance, most played first.
+    pub fn per_instance(&self) -> Vec<(String, u64)> {
+        sum_by(
+            self.sessions
+                .iter()
+                .map(|n| (n.instance.clone(), n.seconds)),
+        )
+    }
+
+    /// Seconds played per Minecraft version, most played first.
+    pub fn per_version(&self) -> Vec<(String, u64)> {
+        sum_by(
+            self.sessions
+                .iter()
+                .filter_map(|n| Some((n.version.clone()?, n.seconds))),
+        )
+    }
+
+    /// How many sessions were started in each of the last
+    /// `weeks` weeks before `now`, oldest week first.
+    pub fn sessions_per_week(&self, now: u64, weeks: usize) -> Vec<usize> {
+        let mut counts = vec![0; weeks];
+        for session in &self.sessions {
+            let weeks_ago = (now.saturating_sub(session.started) / WEEK) as usize;
+            if weeks_ago < weeks {
+                counts[weeks - 1 - weeks_ago] += 1;
+            }
+        }
+        counts
+    }
+}
+
+fn sum_by(items: impl Iterator<Item = (String, u64)>) -> Vec<(String, u64)> {
+    let mut totals: HashMap<String, u64> = HashMap::new();
+    for (key, seconds) in items {
+        *totals.entry(key).or_default() += seconds;
+    }
+    let mut totals: Vec<(String, u64)> = totals.into_iter().collect();
+    totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(instance: &str, version: &str, started: u64, seconds: u64) -> Session {
+        Session {
+            instance: instance.to_owned(),
+            version: Some(version.to_owned()),
+            started,
+            seconds,
+        }
+    }
+
+    #[test]
+    fn test_playtime_stats() {
+        let now = 30 * WEEK;
+        let stats = PlaytimeStats {
+            sessions: vec![
+                session("Survival", "1.20.4", now - 100, 600),
+                session("Creative", "1.20.4", now - WEEK - 100, 300),
+                session("Survival", "1.8.9", now - 3 * WEEK, 1200),
+                session("Old", "b1.7.3", now - 20 * WEEK, 60),
+            ],
+        };
+
+        assert_eq!(stats.total_seconds(), 2160);
+        assert_eq!(
+            stats.per_instance(),
+            vec![
+                ("Survival".to_owned(), 1800),
+                ("Creative".to_owned(), 300),
+                ("Old".to_owned(), 60),
+            ]
+        );
+        assert_eq!(stats.per_version()[0], ("1.8.9".to_owned(), 1200));
+        assert_eq!(stats.sessions_per_week(now, 4), vec![1, 0, 1, 1]);
+    }
+}
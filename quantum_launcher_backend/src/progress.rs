@@ -0,0 +1,157 @@
+//! Progress updates from long-running operations,
+//! like [`crate::DownloadProgress`] while creating an instance.
+//!
+//! Operations take a [`ProgressSender`], which sends to any
+//! [`ProgressReporter`]:
+//! - An async channel from [`channel`]. Updates can be awaited with
+//!   [`ProgressReceiver::recv`] (like in an iced subscription), so
+//!   nothing has to poll for them, or received on another thread
+//!   with [`ProgressReceiver::blocking_recv`].
+//! - A [`std::sync::mpsc::Sender`].
+//! - A closure, like `|progress| println!("{progress}")`.
+//! - [`NoProgress`], which ignores them.
+
+use std::{fmt::Debug, sync::Arc};
+
+use tokio::sync::{
+    mpsc::{self, UnboundedReceiver, UnboundedSender},
+    Mutex,
+};
+
+pub use tokio::sync::mpsc::error::SendError;
+
+/// Something that receives progress updates.
+///
+/// Reporting shouldn't wait for long, as it's
+/// done from sync code too.
+pub trait ProgressReporter<T>: Send + Sync {
+    /// Errors if nobody is listening anymore.
+    fn report(&self, progress: T) -> Result<(), SendError<T>>;
+}
+
+impl<T: Send> ProgressReporter<T> for UnboundedSender<T> {
+    fn report(&self, progress: T) -> Result<(), SendError<T>> {
+        self.send(progress)
+    }
+}
+
+impl<T: Send> ProgressReporter<T> for std::sync::mpsc::Sender<T> {
+    fn report(&self, progress: T) -> Result<(), SendError<T>> {
+        self.send(progress).map_err(|err| SendError(err.0))
+    }
+}
+
+impl<T, F: Fn(T) + Send + Sync> ProgressReporter<T> for F {
+    fn report(&self, progress: T) -> Result<(), SendError<T>> {
+        self(progress);
+        Ok(())
+    }
+}
+
+/// Ignores all progress updates.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl<T> ProgressReporter<T> for NoProgress {
+    fn report(&self, _: T) -> Result<(), SendError<T>> {
+        Ok(())
+    }
+}
+
+/// What operations take to send progress updates,
+/// to any [`ProgressReporter`].
+pub struct ProgressSender<T>(Arc<dyn ProgressReporter<T>>);
+
+impl<T> Clone for ProgressSender<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Debug for ProgressSender<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressSender").finish_non_exhaustive()
+    }
+}
+
+impl<T> ProgressSender<T> {
+    pub fn new(reporter: impl ProgressReporter<T> + 'static) -> Self {
+        Self(Arc::new(reporter))
+    }
+
+    /// Errors if the receiver was dropped.
+    pub fn send(&self, progress: T) -> Result<(), SendError<T>> {
+        self.0.report(progress)
+    }
+}
+
+impl<T: Send + 'static> From<std::sync::mpsc::Sender<T>> for ProgressSender<T> {
+    fn from(sender: std::sync::mpsc::Sender<T>) -> Self {
+        Self::new(sender)
+    }
+}
+
+/// Receives progress updates. Clones share the same
+/// queue, so each update is only received once.
+#[derive(Debug)]
+pub struct ProgressReceiver<T>(Arc<Mutex<UnboundedReceiver<T>>>);
+
+impl<T> Clone for ProgressReceiver<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> ProgressReceiver<T> {
+    /// Waits for the next update, or returns `None`
+    /// once the operation is done and the sender is dropped.
+    pub async fn recv(&self) -> Option<T> {
+        self.0.lock().await.recv().await
+    }
+
+    /// Like [`Self::recv`], for threads outside the async runtime.
+    ///
+    /// # Panics
+    /// If called from async code.
+    pub fn blocking_recv(&self) -> Option<T> {
+        self.0.blocking_lock().blocking_recv()
+    }
+
+    /// The same for all clones of this receiver, and different
+    /// from other channels while it exists. For telling apart
+    /// subscriptions to different operations.
+    pub fn id(&self) -> usize {
+        Arc::as_ptr(&self.0) as usize
+    }
+}
+
+/// An async channel. Sending never waits.
+pub fn channel<T: Send + 'static>() -> (ProgressSender<T>, ProgressReceiver<T>) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    (
+        ProgressSender::new(sender),
+        ProgressReceiver(Arc::new(Mutex::new(receiver))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reporters() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        ProgressSender::from(sender).send(1).unwrap();
+        assert_eq!(receiver.recv(), Ok(1));
+
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let closure = ProgressSender::new({
+            let seen = seen.clone();
+            move |n| seen.lock().unwrap().push(n)
+        });
+        closure.send(2).unwrap();
+        assert_eq!(*seen.lock().unwrap(), [2]);
+
+        assert!(ProgressSender::new(NoProgress).send(3).is_ok());
+    }
+}
@@ -0,0 +1,481 @@
+//! Checking GitHub releases for a newer launcher, and updating to it.
+//!
+//! Downloads are checked against their SHA-256 checksum (from
+//! GitHub, or a `<asset>.sha256` file in the release). Builds made
+//! with the `QUANTUM_LAUNCHER_UPDATE_PUBLIC_KEY` environment variable
+//! (a hex Ed25519 public key) also require a `<asset>.sig` file
+//! with the hex signature of the download.
+//!
+//! Standalone binaries (and zips of them) replace the running
+//! launcher, which is kept as `<name>.old` until the next start.
+//! Installers (`.msi`, `.dmg`, `.deb`...) are saved to the
+//! downloads folder for the user to open.
+
+use std::{
+    fmt::Display,
+    io::{Cursor, Read},
+    path::{Path, PathBuf},
+};
+
+use ring::signature::{UnparsedPublicKey, ED25519};
+use serde::Deserialize;
+use tracing::{error, info};
+use zip::ZipArchive;
+
+use crate::{
+    error::{HasErrorCode, IoError},
+    file_utils::{self, RequestError},
+    io_err,
+};
+
+const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/Mrmayman/quantum-launcher-rs/releases/latest";
+
+/// The key release downloads must be signed with, if this build has one.
+pub const PUBLIC_KEY: Option<&str> = option_env!("QUANTUM_LAUNCHER_UPDATE_PUBLIC_KEY");
+
+/// A newer launcher release.
+#[derive(Debug, Clone)]
+pub struct Release {
+    /// Like `0.2.0`, without the `v` of the tag.
+    pub version: String,
+    /// The release notes, in Markdown.
+    pub changelog: String,
+    /// The release's page on GitHub.
+    pub page_url: String,
+    /// The download for this platform, if there is one.
+    pub asset: Option<ReleaseAsset>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub download_url: String,
+    /// From GitHub, if it lists it.
+    sha256: Option<String>,
+    checksum_url: Option<String>,
+    signature_url: Option<String>,
+}
+
+/// What [`install_update`] did.
+#[derive(Debug, Clone)]
+pub enum UpdateOutcome {
+    /// The launcher binary was replaced, and
+    /// the update is used after a restart.
+    Replaced,
+    /// The installer was saved here, for the user to open.
+    SavedInstaller(PathBuf),
+}
+
+#[derive(Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    #[serde(default)]
+    body: Option<String>,
+    html_url: String,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+    /// Like `sha256:<hex>`.
+    #[serde(default)]
+    digest: Option<String>,
+}
+
+pub async fn check_for_update_wrapped(current_version: String) -> Result<Option<Release>, String> {
+    check_for_update(&current_version)
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Returns the latest release if it's newer than `current_version`.
+pub async fn check_for_update(current_version: &str) -> Result<Option<Release>, UpdateError> {
+    let client = file_utils::create_client();
+    let json = file_utils::download_file_to_string(&client, LATEST_RELEASE_URL).await?;
+    let release: GithubRelease = serde_json::from_str(&json)?;
+
+    let version = release.tag_name.trim_start_matches('v').to_owned();
+    if !is_newer(&version, current_version) {
+        return Ok(None);
+    }
+    info!("Launcher update available: {current_version} -> {version}");
+
+    let asset = pick_asset(
+        release.assets.iter().map(|n| n.name.as_str()),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+    )
+    .and_then(|name| {
+        let find = |name: &str| release.assets.iter().find(|n| n.name == name);
+        let asset = find(name)?;
+        Some(ReleaseAsset {
+            name: asset.name.clone(),
+            download_url: asset.browser_download_url.clone(),
+            sha256: asset
+                .digest
+                .as_deref()
+                .and_then(|n| n.strip_prefix("sha256:"))
+                .map(str::to_owned),
+            checksum_url: find(&format!("{name}.sha256")).map(|n| n.browser_download_url.clone()),
+            signature_url: find(&format!("{name}.sig")).map(|n| n.browser_download_url.clone()),
+        })
+    });
+
+    Ok(Some(Release {
+        version,
+        changelog: release.body.unwrap_or_default(),
+        page_url: release.html_url,
+        asset,
+    }))
+}
+
+/// Compares versions like `0.2.10` and `0.2.9` by their numbers.
+/// Anything after a number (like `-beta`) is ignored.
+pub fn is_newer(version: &str, current_version: &str) -> bool {
+    fn parse(version: &str) -> Vec<u64> {
+        version
+            .trim_start_matches('v')
+            .split('.')
+            .map(|part| {
+                let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+                digits.parse().unwrap_or(0)
+            })
+            .collect()
+    }
+    parse(version) > parse(current_version)
+}
+
+/// Picks the download for the platform from the release's files.
+/// Files that don't say their architecture are taken to be x86_64.
+pub fn pick_asset<'a>(
+    names: impl Iterator<Item = &'a str>,
+    os: &str,
+    arch: &str,
+) -> Option<&'a str> {
+    let os_names: &[&str] = match os {
+        "windows" => &["windows", "win64", "win32"],
+        "macos" => &["macos", "mac", "darwin", "osx"],
+        other => &[if other == "linux" { "linux" } else { other }],
+    };
+    const ARCHES: &[(&str, &[&str])] = &[
+        ("x86_64", &["x86_64", "amd64", "x64"]),
+        ("aarch64", &["aarch64", "arm64"]),
+        ("x86", &["i686", "i386", "x86_32"]),
+    ];
+
+    names
+        .filter(|name| !name.ends_with(".sha256") && !name.ends_with(".sig"))
+        .filter(|name| {
+            let name = name.to_lowercase();
+            os_names.iter().any(|n| name.contains(n))
+        })
+        .filter(|name| {
+            let name = name.to_lowercase();
+            match ARCHES
+                .iter()
+                .find(|(_, aliases)| aliases.iter().any(|n| name.contains(n)))
+            {
+                Some((asset_arch, _)) => *asset_arch == arch,
+                None => arch == "x86_64",
+            }
+        })
+        // Zips of the binary are preferred over installers.
+        .min_by_key(|name| install_kind(name) != InstallKind::Zip)
+}
+
+#[derive(PartialEq, Eq)]
+enum InstallKind {
+    Binary,
+    Zip,
+    Installer,
+}
+
+fn install_kind(name: &str) -> InstallKind {
+    let name = name.to_lowercase();
+    let extension = Path::new(&name)
+        .extension()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    match extension {
+        "zip" => InstallKind::Zip,
+        "msi" | "dmg" | "pkg" | "deb" | "rpm" | "flatpak" | "snap" | "gz" | "tgz" | "xz" => {
+            InstallKind::Installer
+        }
+        // Binaries have no extension (or `.exe`, `.AppImage`), though
+        // a version in the name like `-0.2.0-linux` can look like one.
+        _ => InstallKind::Binary,
+    }
+}
+
+pub async fn install_update_wrapped(release: Release) -> Result<UpdateOutcome, String> {
+    install_update(&release)
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Downloads the release, checks it and installs it.
+pub async fn install_update(release: &Release) -> Result<UpdateOutcome, UpdateError> {
+    let asset = release.asset.as_ref().ok_or(UpdateError::NoDownload)?;
+    let client = file_utils::create_client();
+
+    let expected_sha256 = match (&asset.sha256, &asset.checksum_url) {
+        (Some(sha256), _) => sha256.clone(),
+        (None, Some(url)) => file_utils::download_file_to_string(&client, url)
+            .await?
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_owned(),
+        (None, None) => return Err(UpdateError::NoChecksum),
+    };
+
+    info!("Downloading launcher update {}", asset.name);
+    let bytes = file_utils::download_file_to_bytes(&client, &asset.download_url).await?;
+    let sha256 = to_hex(ring::digest::digest(&ring::digest::SHA256, &bytes).as_ref());
+    if !sha256.eq_ignore_ascii_case(expected_sha256.trim()) {
+        return Err(UpdateError::ChecksumMismatch);
+    }
+
+    if let Some(public_key) = PUBLIC_KEY {
+        let url = asset
+            .signature_url
+            .as_ref()
+            .ok_or(UpdateError::BadSignature)?;
+        let signature = file_utils::download_file_to_string(&client, url).await?;
+        verify_signature(public_key, signature.trim(), &bytes)?;
+    }
+
+    match install_kind(&asset.name) {
+        InstallKind::Binary => replace_current_exe(&bytes)?,
+        InstallKind::Zip => replace_current_exe(&binary_from_zip(&bytes)?)?,
+        InstallKind::Installer => {
+            let dir = dirs::download_dir().unwrap_or_else(std::env::temp_dir);
+            let path = dir.join(&asset.name);
+            std::fs::write(&path, &bytes).map_err(io_err!(path))?;
+            info!("Saved launcher update installer to {path:?}");
+            return Ok(UpdateOutcome::SavedInstaller(path));
+        }
+    }
+    Ok(UpdateOutcome::Replaced)
+}
+
+fn verify_signature(public_key: &str, signature: &str, bytes: &[u8]) -> Result<(), UpdateError> {
+    let public_key = from_hex(public_key).ok_or(UpdateError::BadSignature)?;
+    let signature = from_hex(signature).ok_or(UpdateError::BadSignature)?;
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(bytes, &signature)
+        .map_err(|_| UpdateError::BadSignature)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|n| format!("{n:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Takes the launcher out of a release zip: the file named
+/// like the running launcher, or the only file in it.
+fn binary_from_zip(bytes: &[u8]) -> Result<Vec<u8>, UpdateError> {
+    let exe_name = current_exe()?
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut archive = ZipArchive::new(Cursor::new(bytes))?;
+
+    let files: Vec<String> = archive
+        .file_names()
+        .filter(|n| !n.ends_with('/'))
+        .map(str::to_owned)
+        .collect();
+    let name = files
+        .iter()
+        .find(|n| Path::new(n).file_name().is_some_and(|n| *n == *exe_name))
+        .or(if files.len() == 1 {
+            files.first()
+        } else {
+            None
+        })
+        .ok_or(UpdateError::NoDownload)?;
+
+    let mut binary = Vec::new();
+    archive
+        .by_name(name)?
+        .read_to_end(&mut binary)
+        .map_err(io_err!(PathBuf::from(name)))?;
+    Ok(binary)
+}
+
+/// The file to replace. For AppImages it's the
+/// AppImage itself, not the mounted binary inside it.
+fn current_exe() -> Result<PathBuf, UpdateError> {
+    if let Some(appimage) = std::env::var_os("APPIMAGE") {
+        return Ok(PathBuf::from(appimage));
+    }
+    let exe = std::env::current_exe().map_err(io_err!(PathBuf::from("current_exe")))?;
+    let exe = exe.canonicalize().map_err(io_err!(exe))?;
+    // These are only written to by package managers.
+    if ["/usr/", "/nix/", "/snap/", "/app/"]
+        .iter()
+        .any(|n| exe.starts_with(n))
+    {
+        return Err(UpdateError::PackageManaged);
+    }
+    Ok(exe)
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_owned();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Swaps in the new binary. The running one is moved aside first,
+/// which works even on Windows, where it can't be overwritten.
+fn replace_current_exe(binary: &[u8]) -> Result<(), UpdateError> {
+    let exe = current_exe()?;
+    let new_path = with_suffix(&exe, ".new");
+    let old_path = with_suffix(&exe, ".old");
+
+    std::fs::write(&new_path, binary).map_err(io_err!(new_path))?;
+    file_utils::set_executable(&new_path)?;
+    if old_path.exists() {
+        std::fs::remove_file(&old_path).map_err(io_err!(old_path))?;
+    }
+    std::fs::rename(&exe, &old_path).map_err(io_err!(exe))?;
+    if let Err(err) = std::fs::rename(&new_path, &exe) {
+        _ = std::fs::rename(&old_path, &exe);
+        return Err(IoError::Io {
+            error: err,
+            path: exe,
+        }
+        .into());
+    }
+    info!("Replaced launcher binary {exe:?}");
+    Ok(())
+}
+
+/// Deletes the launcher binary left over from the last update.
+pub fn remove_old_binary() {
+    let Ok(exe) = current_exe() else {
+        return;
+    };
+    let old_path = with_suffix(&exe, ".old");
+    if old_path.exists() {
+        if let Err(err) = std::fs::remove_file(&old_path) {
+            error!("Could not delete old launcher binary {old_path:?}: {err}");
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum UpdateError {
+    Request(RequestError),
+    Json(serde_json::Error),
+    Io(IoError),
+    Zip(zip::result::ZipError),
+    /// The release has nothing for this platform.
+    NoDownload,
+    NoChecksum,
+    ChecksumMismatch,
+    BadSignature,
+    /// The launcher was installed by a package manager,
+    /// which should be used to update it.
+    PackageManaged,
+}
+
+impl From<RequestError> for UpdateError {
+    fn from(value: RequestError) -> Self {
+        Self::Request(value)
+    }
+}
+
+impl From<serde_json::Error> for UpdateError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl From<IoError> for UpdateError {
+    fn from(value: IoError) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<zip::result::ZipError> for UpdateError {
+    fn from(value: zip::result::ZipError) -> Self {
+        Self::Zip(value)
+    }
+}
+
+impl Display for UpdateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "could not update launcher: ")?;
+        match self {
+            UpdateError::Request(err) => write!(f, "{err}"),
+            UpdateError::Json(err) => write!(f, "invalid release info: {err}"),
+            UpdateError::Io(err) => write!(f, "{err}"),
+            UpdateError::Zip(err) => write!(f, "invalid zip file: {err}"),
+            UpdateError::NoDownload => write!(f, "the release has no download for this platform"),
+            UpdateError::NoChecksum => write!(f, "the release has no checksum to check against"),
+            UpdateError::ChecksumMismatch => write!(f, "the download's checksum doesn't match"),
+            UpdateError::BadSignature => write!(f, "the download isn't signed properly"),
+            UpdateError::PackageManaged => {
+                write!(
+                    f,
+                    "it was installed by a package manager, update it with that"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_update_asset() {
+        assert!(is_newer("v0.2.10", "0.2.9"));
+        assert!(is_newer("1.0", "0.9.9"));
+        assert!(!is_newer("0.1.0", "0.1.0"));
+        assert!(!is_newer("0.1.0-beta", "0.1.0"));
+
+        let names = [
+            "quantum_launcher-linux-x86_64.zip",
+            "quantum_launcher-linux-x86_64.zip.sha256",
+            "quantum_launcher-linux-aarch64",
+            "quantum_launcher-windows.zip",
+            "quantum_launcher-macos-arm64.dmg",
+        ];
+        let pick = |os, arch| pick_asset(names.into_iter(), os, arch);
+        assert_eq!(
+            pick("linux", "x86_64"),
+            Some("quantum_launcher-linux-x86_64.zip")
+        );
+        assert_eq!(
+            pick("linux", "aarch64"),
+            Some("quantum_launcher-linux-aarch64")
+        );
+        assert_eq!(
+            pick("windows", "x86_64"),
+            Some("quantum_launcher-windows.zip")
+        );
+        assert_eq!(pick("windows", "aarch64"), None);
+        assert_eq!(
+            pick("macos", "aarch64"),
+            Some("quantum_launcher-macos-arm64.dmg")
+        );
+    }
+}
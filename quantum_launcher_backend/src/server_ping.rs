@@ -0,0 +1,303 @@
+//! The [Server List Ping](https://minecraft.wiki/w/Java_Edition_protocol/Server_List_Ping)
+//! protocol, which the multiplayer menu uses to show
+//! whether a server is online, its players and its MOTD.
+//!
+//! Only servers from 1.7 onwards answer it.
+
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+use crate::error::HasErrorCode;
+
+pub const DEFAULT_PORT: u16 = 25565;
+const TIMEOUT: Duration = Duration::from_secs(5);
+/// Responses can have the server icon in them, but
+/// nothing real comes close to this.
+const MAX_PACKET_LEN: usize = 1024 * 1024;
+
+/// What a server says about itself in the multiplayer menu.
+#[derive(Debug, Clone)]
+pub struct ServerStatus {
+    /// Like `1.20.4` or `Paper 1.20.4`.
+    pub version: String,
+    pub players_online: u32,
+    pub players_max: u32,
+    /// The message of the day, without formatting codes.
+    pub motd: String,
+    pub latency: Duration,
+}
+
+#[derive(Deserialize)]
+struct StatusJson {
+    version: Option<StatusVersion>,
+    players: Option<StatusPlayers>,
+    #[serde(default)]
+    description: Value,
+}
+
+#[derive(Deserialize)]
+struct StatusVersion {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct StatusPlayers {
+    max: u32,
+    online: u32,
+}
+
+pub async fn ping_wrapped(address: String) -> Result<ServerStatus, String> {
+    ping(&address)
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+/// Asks the server at `address` (`host` or `host:port`)
+/// for its status, giving up after a few seconds.
+///
+/// SRV records aren't looked up, so servers that
+/// rely on them need their port written out.
+pub async fn ping(address: &str) -> Result<ServerStatus, PingError> {
+    let (host, port) = parse_address(address)?;
+    tokio::time::timeout(TIMEOUT, ping_inner(&host, port))
+        .await
+        .map_err(|_| PingError::Timeout)?
+}
+
+async fn ping_inner(host: &str, port: u16) -> Result<ServerStatus, PingError> {
+    let mut stream = TcpStream::connect((host, port)).await?;
+
+    let mut handshake = Vec::new();
+    write_var_int(&mut handshake, 0x00);
+    // -1 asks for the status without claiming a version.
+    write_var_int(&mut handshake, -1);
+    write_var_int(&mut handshake, host.len() as i32);
+    handshake.extend_from_slice(host.as_bytes());
+    handshake.extend_from_slice(&port.to_be_bytes());
+    // The next state, 1 being status.
+    write_var_int(&mut handshake, 1);
+    write_packet(&mut stream, &handshake).await?;
+
+    let started = Instant::now();
+    write_packet(&mut stream, &[0x00]).await?;
+    let response = read_packet(&mut stream).await?;
+    let latency = started.elapsed();
+
+    let mut response = response.as_slice();
+    if read_var_int_from_slice(&mut response)? != 0x00 {
+        return Err(PingError::InvalidResponse("unexpected packet"));
+    }
+    let json_len = read_var_int_from_slice(&mut response)? as usize;
+    let json = response
+        .get(..json_len)
+        .ok_or(PingError::InvalidResponse("truncated status"))?;
+    let status: StatusJson = serde_json::from_slice(json)?;
+
+    let players = status
+        .players
+        .unwrap_or(StatusPlayers { max: 0, online: 0 });
+    Ok(ServerStatus {
+        version: status.version.map(|n| n.name).unwrap_or_default(),
+        players_online: players.online,
+        players_max: players.max,
+        motd: strip_formatting(&chat_to_text(&status.description)),
+        latency,
+    })
+}
+
+/// Splits `host:port`, using the default port if there's none.
+pub fn parse_address(address: &str) -> Result<(String, u16), PingError> {
+    let address = address.trim();
+    let invalid = || PingError::InvalidAddress(address.to_owned());
+
+    // IPv6 addresses are written like `[::1]:25565`.
+    let (host, port) = if let Some(rest) = address.strip_prefix('[') {
+        let (host, rest) = rest.split_once(']').ok_or_else(invalid)?;
+        match rest.strip_prefix(':') {
+            Some(port) => (host, Some(port)),
+            None if rest.is_empty() => (host, None),
+            None => return Err(invalid()),
+        }
+    } else {
+        match address.split_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (address, None),
+        }
+    };
+
+    if host.is_empty() || host.contains(char::is_whitespace) {
+        return Err(invalid());
+    }
+    let port = match port {
+        Some(port) => port.parse().map_err(|_| invalid())?,
+        None => DEFAULT_PORT,
+    };
+    Ok((host.to_owned(), port))
+}
+
+/// The MOTD can be plain text or a chat component,
+/// with its text split over `extra` components.
+fn chat_to_text(component: &Value) -> String {
+    match component {
+        Value::String(text) => text.clone(),
+        Value::Array(components) => components.iter().map(chat_to_text).collect(),
+        Value::Object(object) => {
+            let mut text = object
+                .get("text")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_owned();
+            if let Some(extra) = object.get("extra") {
+                text.push_str(&chat_to_text(extra));
+            }
+            text
+        }
+        _ => String::new(),
+    }
+}
+
+/// Removes color codes like `§a`.
+fn strip_formatting(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '§' {
+            chars.next();
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
+fn write_var_int(buf: &mut Vec<u8>, value: i32) {
+    let mut value = value as u32;
+    loop {
+        if value & !0x7F == 0 {
+            buf.push(value as u8);
+            return;
+        }
+        buf.push((value & 0x7F) as u8 | 0x80);
+        value >>= 7;
+    }
+}
+
+async fn write_packet(stream: &mut TcpStream, data: &[u8]) -> Result<(), PingError> {
+    let mut packet = Vec::with_capacity(data.len() + 5);
+    write_var_int(&mut packet, data.len() as i32);
+    packet.extend_from_slice(data);
+    stream.write_all(&packet).await?;
+    Ok(())
+}
+
+async fn read_packet(stream: &mut TcpStream) -> Result<Vec<u8>, PingError> {
+    let mut len: u32 = 0;
+    for i in 0..5 {
+        let byte = stream.read_u8().await?;
+        len |= u32::from(byte & 0x7F) << (7 * i);
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    let len = len as usize;
+    if len > MAX_PACKET_LEN {
+        return Err(PingError::InvalidResponse("packet too long"));
+    }
+
+    let mut packet = vec![0; len];
+    stream.read_exact(&mut packet).await?;
+    Ok(packet)
+}
+
+fn read_var_int_from_slice(data: &mut &[u8]) -> Result<i32, PingError> {
+    let mut value: u32 = 0;
+    for i in 0..5 {
+        let (&byte, rest) = data
+            .split_first()
+            .ok_or(PingError::InvalidResponse("truncated number"))?;
+        *data = rest;
+        value |= u32::from(byte & 0x7F) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok(value as i32);
+        }
+    }
+    Err(PingError::InvalidResponse("number too long"))
+}
+
+#[derive(Debug)]
+pub enum PingError {
+    Io(std::io::Error),
+    Timeout,
+    InvalidAddress(String),
+    InvalidResponse(&'static str),
+    Json(serde_json::Error),
+}
+
+impl From<std::io::Error> for PingError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for PingError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl Display for PingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PingError::Io(err) => write!(f, "could not ping server: {err}"),
+            PingError::Timeout => write!(f, "could not ping server: no answer in time"),
+            PingError::InvalidAddress(address) => {
+                write!(f, "could not ping server: invalid address {address:?}")
+            }
+            PingError::InvalidResponse(reason) => {
+                write!(f, "could not ping server: invalid response ({reason})")
+            }
+            PingError::Json(err) => write!(f, "could not ping server: invalid status: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_address() {
+        assert_eq!(
+            parse_address("example.com").unwrap(),
+            ("example.com".to_owned(), DEFAULT_PORT)
+        );
+        assert_eq!(
+            parse_address("localhost:25566").unwrap(),
+            ("localhost".to_owned(), 25566)
+        );
+        assert_eq!(parse_address("[::1]:1").unwrap(), ("::1".to_owned(), 1));
+        assert!(parse_address("localhost:abc").is_err());
+        assert!(parse_address("").is_err());
+    }
+
+    #[test]
+    fn test_motd_from_chat_component() {
+        let description = serde_json::json!({
+            "text": "§aHello",
+            "extra": [{ "text": " world" }, "!"]
+        });
+        assert_eq!(
+            strip_formatting(&chat_to_text(&description)),
+            "Hello world!"
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! Player skins, for showing whose account is
+//! about to play on the launch and account screens.
+//!
+//! Online skins come from Mojang's session server and are
+//! cached in `<cache>/skins`, so the last known one is still
+//! shown offline. Offline profiles can use a local file.
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use base64::Engine;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use tracing::info;
+
+use crate::{
+    error::{HasErrorCode, IoError},
+    file_utils::{self, RequestError},
+    io_err,
+};
+
+const PROFILE_URL: &str = "https://api.mojang.com/users/profiles/minecraft/";
+const SESSION_URL: &str = "https://sessionserver.mojang.com/session/minecraft/profile/";
+
+/// Where a skin comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SkinSource {
+    /// A PNG file, for offline profiles.
+    File(PathBuf),
+    /// The online account with this username, if there is one.
+    Username(String),
+    /// The online account with this UUID.
+    Uuid(String),
+}
+
+/// The front of a skin's head, with the hat layer on top.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Face {
+    /// The width and height, in pixels. 8 for
+    /// normal skins, more for HD ones.
+    pub size: u32,
+    /// RGBA, row by row.
+    pub pixels: Vec<u8>,
+}
+
+/// Loads the face of a skin. `None` if there's no online
+/// account for the username or UUID.
+pub async fn load_face_wrapped(source: SkinSource) -> Result<Option<Face>, String> {
+    load_face(&source)
+        .await
+        .map_err(|err| err.to_string_with_code())
+}
+
+pub async fn load_face(source: &SkinSource) -> Result<Option<Face>, SkinError> {
+    let skin = match source {
+        SkinSource::File(path) => Some(std::fs::read(path).map_err(io_err!(path))?),
+        SkinSource::Username(username) => match lookup_uuid(username).await? {
+            Some(uuid) => download_skin(&uuid).await?,
+            None => None,
+        },
+        SkinSource::Uuid(uuid) => download_skin(&uuid.replace('-', "")).await?,
+    };
+    skin.map(|skin| face_from_png(&skin)).transpose()
+}
+
+#[derive(Deserialize)]
+struct Profile {
+    id: String,
+    #[serde(default)]
+    properties: Vec<ProfileProperty>,
+}
+
+#[derive(Deserialize)]
+struct ProfileProperty {
+    name: String,
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct Textures {
+    textures: TextureUrls,
+}
+
+#[derive(Deserialize)]
+struct TextureUrls {
+    #[serde(rename = "SKIN")]
+    skin: Option<TextureUrl>,
+}
+
+#[derive(Deserialize)]
+struct TextureUrl {
+    url: String,
+}
+
+/// The UUID (without dashes) of the online account
+/// with this username, if there is one.
+async fn lookup_uuid(username: &str) -> Result<Option<String>, SkinError> {
+    Ok(get_json::<Profile>(&format!("{PROFILE_URL}{username}"))
+        .await?
+        .map(|profile| profile.id))
+}
+
+/// Downloads an online account's skin, falling back to
+/// the cached one if that fails.
+async fn download_skin(uuid: &str) -> Result<Option<Vec<u8>>, SkinError> {
+    let cache_path = file_utils::get_cache_dir()?
+        .join("skins")
+        .join(format!("{uuid}.png"));
+    match fetch_skin(uuid).await {
+        Ok(Some(skin)) => {
+            let dir = cache_path.parent().unwrap();
+            std::fs::create_dir_all(dir).map_err(io_err!(dir))?;
+            std::fs::write(&cache_path, &skin).map_err(io_err!(cache_path))?;
+            Ok(Some(skin))
+        }
+        Ok(None) => Ok(None),
+        Err(err) if cache_path.is_file() => {
+            info!("Could not download skin, using the cached one: {err}");
+            Ok(Some(
+                std::fs::read(&cache_path).map_err(io_err!(cache_path))?,
+            ))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+async fn fetch_skin(uuid: &str) -> Result<Option<Vec<u8>>, SkinError> {
+    let Some(profile) = get_json::<Profile>(&format!("{SESSION_URL}{uuid}")).await? else {
+        return Ok(None);
+    };
+    let Some(textures) = profile
+        .properties
+        .iter()
+        .find(|property| property.name == "textures")
+    else {
+        return Ok(None);
+    };
+    let textures = base64::engine::general_purpose::STANDARD
+        .decode(&textures.value)
+        .map_err(|err| SkinError::Response(err.to_string()))?;
+    let textures: Textures =
+        serde_json::from_slice(&textures).map_err(|err| SkinError::Response(err.to_string()))?;
+    let Some(skin) = textures.textures.skin else {
+        return Ok(None);
+    };
+
+    let client = file_utils::create_client();
+    Ok(Some(
+        file_utils::download_file_to_bytes(&client, &skin.url).await?,
+    ))
+}
+
+/// `None` if Mojang has no such account, which
+/// it answers with an empty response or a 404.
+async fn get_json<T: for<'de> Deserialize<'de>>(url: &str) -> Result<Option<T>, SkinError> {
+    let response = file_utils::create_client()
+        .get(url)
+        .send()
+        .await
+        .map_err(RequestError::from)?;
+    let status = response.status();
+    if status == StatusCode::NO_CONTENT || status == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        return Err(RequestError::DownloadError {
+            code: status,
+            url: response.url().clone(),
+        }
+        .into());
+    }
+    let text = response.text().await.map_err(RequestError::from)?;
+    serde_json::from_str(&text)
+        .map(Some)
+        .map_err(|err| SkinError::Response(err.to_string()))
+}
+
+/// Cuts the face out of a skin: 64x64 (or 64x32 from
+/// before 1.8), or a multiple of that for HD skins.
+pub fn face_from_png(skin: &[u8]) -> Result<Face, SkinError> {
+    let mut decoder = png::Decoder::new(skin);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder
+        .read_info()
+        .map_err(|err| SkinError::Png(err.to_string()))?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader
+        .next_frame(&mut buffer)
+        .map_err(|err| SkinError::Png(err.to_string()))?;
+
+    let (width, height) = (info.width, info.height);
+    if width == 0 || width % 64 != 0 || (height != width && height != width / 2) {
+        return Err(SkinError::Size(width, height));
+    }
+    let channels = info.color_type.samples();
+    let pixel = |x: u32, y: u32| -> [u8; 4] {
+        let i = (y * width + x) as usize * channels;
+        match channels {
+            4 => [buffer[i], buffer[i + 1], buffer[i + 2], buffer[i + 3]],
+            3 => [buffer[i], buffer[i + 1], buffer[i + 2], 255],
+            2 => [buffer[i], buffer[i], buffer[i], buffer[i + 1]],
+            _ => [buffer[i], buffer[i], buffer[i], 255],
+        }
+    };
+
+    let scale = width / 64;
+    let size = 8 * scale;
+    let mut pixels = Vec::with_capacity((size * size * 4) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let face = pixel(8 * scale + x, 8 * scale + y);
+            let hat = pixel(40 * scale + x, 8 * scale + y);
+            // Hats are either there or not, old skins
+            // sometimes fill the layer with black instead.
+            let has_hat = hat[3] == 255 && hat != [0, 0, 0, 255];
+            pixels.extend(if has_hat {
+                hat
+            } else {
+                [face[0], face[1], face[2], 255]
+            });
+        }
+    }
+    Ok(Face { size, pixels })
+}
+
+/// Checks that a file is a skin, before it's saved
+/// with an offline profile.
+pub fn check_skin_file(path: &Path) -> Result<(), SkinError> {
+    let skin = std::fs::read(path).map_err(io_err!(path))?;
+    face_from_png(&skin).map(|_| ())
+}
+
+#[derive(Debug)]
+pub enum SkinError {
+    Io(IoError),
+    Request(RequestError),
+    /// Mojang's answer couldn't be read.
+    Response(String),
+    Png(String),
+    /// The width and height, which aren't a skin's.
+    Size(u32, u32),
+}
+
+impl From<IoError> for SkinError {
+    fn from(value: IoError) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<RequestError> for SkinError {
+    fn from(value: RequestError) -> Self {
+        Self::Request(value)
+    }
+}
+
+impl Display for SkinError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkinError::Io(err) => write!(f, "could not load skin: {err}"),
+            SkinError::Request(err) => write!(f, "could not download skin: {err}"),
+            SkinError::Response(err) => {
+                write!(f, "could not download skin: invalid response: {err}")
+            }
+            SkinError::Png(err) => write!(f, "skin is not a valid PNG: {err}"),
+            SkinError::Size(width, height) => write!(
+                f,
+                "skin is {width}x{height}, skins are 64x64 or 64x32 (or HD multiples of those)"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 64x64 RGBA PNG with the face red, one hat pixel
+    /// blue and the rest of the hat layer transparent.
+    fn test_skin() -> Vec<u8> {
+        let mut pixels = vec![0; 64 * 64 * 4];
+        for y in 8..16 {
+            for x in 8..16 {
+                let i = (y * 64 + x) * 4;
+                pixels[i..i + 4].copy_from_slice(&[255, 0, 0, 255]);
+            }
+        }
+        let hat = (8 * 64 + 40) * 4;
+        pixels[hat..hat + 4].copy_from_slice(&[0, 0, 255, 255]);
+
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, 64, 64);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&pixels).unwrap();
+        writer.finish().unwrap();
+        png
+    }
+
+    #[test]
+    fn test_face_from_png() {
+        let face = face_from_png(&test_skin()).unwrap();
+        assert_eq!(face.size, 8);
+        assert_eq!(face.pixels[0..4], [0, 0, 255, 255]);
+        assert_eq!(face.pixels[4..8], [255, 0, 0, 255]);
+        assert_eq!(face.pixels.len(), 8 * 8 * 4);
+    }
+}
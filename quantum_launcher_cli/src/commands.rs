@@ -0,0 +1,364 @@
+use std::{
+    io::{BufRead, Write},
+    process::ExitCode,
+    sync::mpsc,
+};
+
+use quantum_launcher_backend::{
+    cancel::CancelToken,
+    instance_mod_installer::{
+        self,
+        modrinth::{InstalledMod, ModInstallProgress},
+    },
+    json_structs::json_manifest::VersionSource,
+    DownloadProgress, ErrorCode, FabricInstallProgress, JavaInstallMessage, LaunchOptions,
+};
+use serde_json::{json, Value};
+
+use crate::{
+    output::{codes, CliError, Output},
+    Loader,
+};
+
+pub type CommandResult = Result<ExitCode, CliError>;
+
+pub fn list(output: Output) -> CommandResult {
+    let instances = quantum_launcher_backend::list_instances()?;
+
+    if output.json {
+        output.done("", json!({ "instances": instances }));
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if instances.is_empty() {
+        println!("No instances. Create one with `create <name> <version>`.");
+    }
+    for instance in instances {
+        println!(
+            "{}\t{}\t{}",
+            instance.name,
+            instance.version.as_deref().unwrap_or("?"),
+            instance.mod_type.as_deref().unwrap_or("?"),
+        );
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+pub struct CreateOptions {
+    pub name: String,
+    pub version: String,
+    pub loader: Loader,
+    /// `None` for the latest version.
+    pub loader_version: Option<String>,
+    pub download_assets: bool,
+    /// Modrinth slugs or project IDs.
+    pub mods: Vec<String>,
+    pub version_source: VersionSource,
+}
+
+/// Creates an instance, then installs the mod loader and mods,
+/// so a whole setup can be made with one command.
+pub async fn create(output: Output, options: CreateOptions) -> CommandResult {
+    let name = options.name.clone();
+    let result = create_instance(output, options).await?;
+    output.done(format!("Finished setting up {name}."), result);
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Does the work of [`create`], returning what was installed.
+pub async fn create_instance(output: Output, options: CreateOptions) -> Result<Value, CliError> {
+    let CreateOptions {
+        name,
+        version,
+        loader,
+        loader_version,
+        download_assets,
+        mods,
+        version_source,
+    } = options;
+
+    // Checked first, so nothing is downloaded for a command that can't work.
+    if !mods.is_empty() && loader == Loader::Vanilla {
+        return Err(CliError::new(
+            codes::MODS_NEED_LOADER,
+            "installing mods needs a mod loader, like --loader fabric",
+        ));
+    }
+
+    let (sender, receiver) = mpsc::channel::<DownloadProgress>();
+    let progress_printer = std::thread::spawn(move || {
+        for progress in receiver {
+            output.progress(&progress, &progress);
+        }
+    });
+
+    let result = quantum_launcher_backend::create_instance(
+        &name,
+        version.clone(),
+        version_source,
+        Some(sender.into()),
+        download_assets,
+        Some(cancel_on_ctrl_c()),
+    )
+    .await;
+    // The sender is dropped by now, so this finishes printing and returns.
+    _ = progress_printer.join();
+    result?;
+    output.message(format!("Created instance {name}."));
+
+    let loader_version = match loader {
+        Loader::Vanilla => None,
+        Loader::Fabric => Some(install_fabric_into(output, &name, loader_version).await?),
+    };
+
+    let installed_mods = if mods.is_empty() {
+        Vec::new()
+    } else {
+        install_mods_into(output, &name, mods).await?
+    };
+
+    Ok(json!({
+        "instance": name,
+        "version": version,
+        "loader_version": loader_version,
+        "mods": installed_mods,
+    }))
+}
+
+/// A token cancelled by Ctrl+C, so that an interrupted
+/// download doesn't leave half an instance behind.
+/// Pressing it again exits right away.
+fn cancel_on_ctrl_c() -> CancelToken {
+    let cancel = CancelToken::new();
+    let token = cancel.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            eprintln!("Cancelling, press Ctrl+C again to exit now.");
+            token.cancel();
+        }
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(130);
+        }
+    });
+    cancel
+}
+
+pub async fn launch(output: Output, options: LaunchOptions, dry_run: bool) -> CommandResult {
+    let (sender, receiver) = mpsc::channel::<JavaInstallMessage>();
+    let progress_printer = std::thread::spawn(move || {
+        for message in receiver {
+            let text = match &message {
+                JavaInstallMessage::P1Started => "Installing Java.".to_owned(),
+                JavaInstallMessage::P2 {
+                    progress,
+                    out_of,
+                    name,
+                } => format!("Installing Java file {progress} / {out_of}: {name}"),
+                JavaInstallMessage::P3Done => "Installed Java.".to_owned(),
+            };
+            output.progress(&message, text);
+        }
+    });
+
+    let name = options.instance_name().to_owned();
+    let options = options.java_install_progress(sender.into());
+
+    if dry_run {
+        let result = options.dry_run().await;
+        _ = progress_printer.join();
+        output.message(result?.to_string());
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let result = options.launch().await;
+    _ = progress_printer.join();
+    let mut child = result?;
+
+    output.message(format!("Started the game (pid {}).", child.id()));
+
+    // The game's output is piped for the GUI's log viewer,
+    // so pass it on to the terminal here.
+    let forwarders: Vec<_> = [
+        child
+            .stdout
+            .take()
+            .map(|out| forward_lines(output, out, false)),
+        child
+            .stderr
+            .take()
+            .map(|err| forward_lines(output, err, true)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let status = child
+        .wait()
+        .map_err(|err| CliError::new(ErrorCode::IO_ERROR, err))?;
+    for forwarder in forwarders {
+        _ = forwarder.join();
+    }
+    // Only does anything if it was launched `--without-mods`.
+    quantum_launcher_backend::restore_disabled_mods(&name)?;
+    // Only does anything if the instance has a sync folder.
+    quantum_launcher_backend::sync_folder::push(&name)?;
+
+    // The game's own exit code isn't passed on, as it
+    // could be mistaken for one of the launcher's codes.
+    if !status.success() {
+        return Err(CliError::new(
+            codes::GAME_CRASHED,
+            format!("the game exited with {status}"),
+        ));
+    }
+    output.done(
+        format!("Game exited with {status}."),
+        json!({ "instance": name, "exit_code": status.code() }),
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+fn forward_lines(
+    output: Output,
+    stream: impl std::io::Read + Send + 'static,
+    is_stderr: bool,
+) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        for line in std::io::BufReader::new(stream)
+            .lines()
+            .map_while(Result::ok)
+        {
+            output.game_log(&line, is_stderr);
+        }
+    })
+}
+
+pub async fn install_fabric(
+    output: Output,
+    name: String,
+    loader_version: Option<String>,
+) -> CommandResult {
+    let loader_version = install_fabric_into(output, &name, loader_version).await?;
+    output.done(
+        "Done.",
+        json!({ "instance": name, "loader_version": loader_version }),
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Installs Fabric and returns the loader version that was installed.
+///
+/// If the instance already has Fabric, it's updated instead,
+/// so the old version's libraries get cleaned up.
+pub async fn install_fabric_into(
+    output: Output,
+    name: &str,
+    loader_version: Option<String>,
+) -> Result<String, CliError> {
+    let loader_version = match loader_version {
+        Some(version) => version,
+        None => {
+            // The first one is the latest version.
+            let versions = instance_mod_installer::fabric::get_list_of_versions().await?;
+            versions
+                .into_iter()
+                .next()
+                .map(|version| version.version)
+                .ok_or_else(|| {
+                    CliError::new(
+                        ErrorCode::FABRIC_VERSION_NOT_FOUND,
+                        "no Fabric versions found",
+                    )
+                })?
+        }
+    };
+
+    let (sender, receiver) = mpsc::channel::<FabricInstallProgress>();
+    let progress_printer = std::thread::spawn(move || {
+        for progress in receiver {
+            output.progress(&progress, &progress);
+        }
+    });
+
+    let sender = sender.into();
+    let result = match instance_mod_installer::fabric::installed_version(name) {
+        Some(installed) => {
+            output.message(format!(
+                "Updating Fabric in {name} from {installed} to {loader_version}."
+            ));
+            instance_mod_installer::fabric::update(&loader_version, name, Some(&sender)).await
+        }
+        None => {
+            output.message(format!("Installing Fabric {loader_version} into {name}."));
+            instance_mod_installer::fabric::install(&loader_version, name, Some(&sender)).await
+        }
+    };
+    drop(sender);
+    _ = progress_printer.join();
+    result?;
+
+    output.message("Installed Fabric.");
+    Ok(loader_version)
+}
+
+pub async fn install_mods_into(
+    output: Output,
+    name: &str,
+    mods: Vec<String>,
+) -> Result<Vec<InstalledMod>, CliError> {
+    let (sender, receiver) = mpsc::channel::<ModInstallProgress>();
+    let progress_printer = std::thread::spawn(move || {
+        for progress in receiver {
+            output.progress(&progress, &progress);
+        }
+    });
+
+    let result =
+        instance_mod_installer::modrinth::install_mods(name, &mods, Some(&sender.into())).await;
+    _ = progress_printer.join();
+    let installed = result?;
+
+    output.message(format!("Installed {} mods.", installed.len()));
+    Ok(installed)
+}
+
+pub async fn delete(output: Output, name: &str, yes: bool) -> CommandResult {
+    if !yes {
+        // Scripts can't answer the question, so they have to pass `--yes`.
+        if output.json {
+            return Err(CliError::new(
+                codes::CONFIRMATION_REQUIRED,
+                "pass --yes to delete without asking",
+            ));
+        }
+        if !confirm(&format!(
+            "Delete the instance {name}? This can't be undone."
+        ))? {
+            return Err(CliError::new(codes::CANCELLED, "cancelled"));
+        }
+    }
+
+    quantum_launcher_backend::delete_instance(name)?;
+    // The deleted instance may have been the last one
+    // using a particular Java version.
+    quantum_launcher_backend::delete_unused_java_installs().await?;
+
+    output.done(
+        format!("Deleted instance {name}."),
+        json!({ "instance": name }),
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+fn confirm(question: &str) -> Result<bool, CliError> {
+    print!("{question} [y/N] ");
+    std::io::stdout()
+        .flush()
+        .map_err(|err| CliError::new(codes::CANCELLED, err))?;
+
+    let mut answer = String::new();
+    std::io::stdin()
+        .read_line(&mut answer)
+        .map_err(|err| CliError::new(codes::CANCELLED, err))?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
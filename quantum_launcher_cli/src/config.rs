@@ -0,0 +1,57 @@
+use std::path::PathBuf;
+
+use quantum_launcher_backend::{
+    error::LauncherError,
+    file_utils::{self, DownloadSettings},
+    io_err,
+};
+use serde::Deserialize;
+
+/// The parts of the launcher's `config.json` that the CLI uses.
+///
+/// The file is owned by the GUI, so it's only read here,
+/// and fields the CLI doesn't know about are ignored.
+#[derive(Deserialize, Default)]
+pub struct CliConfig {
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub data_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub parallel_downloads: Option<usize>,
+    #[serde(default)]
+    pub download_mirror: Option<String>,
+    #[serde(default)]
+    pub proxy: Option<String>,
+    #[serde(default)]
+    pub java_args: Vec<String>,
+    #[serde(default)]
+    pub allow_invalid_username: bool,
+}
+
+impl CliConfig {
+    /// Loads the config, and passes the data directory
+    /// and download settings on to the backend.
+    pub fn load() -> Result<Self, LauncherError> {
+        let config_path = file_utils::get_config_dir()?.join("config.json");
+        let config: Self = if config_path.exists() {
+            let config = std::fs::read_to_string(&config_path).map_err(io_err!(config_path))?;
+            serde_json::from_str(&config)?
+        } else {
+            Self::default()
+        };
+
+        file_utils::set_data_dir(config.data_dir.clone());
+        if let Err(err) = file_utils::migrate_to_cache_dir() {
+            tracing::error!("Could not move downloaded files to the cache directory: {err}");
+        }
+        file_utils::set_download_settings(DownloadSettings {
+            parallel_downloads: config
+                .parallel_downloads
+                .unwrap_or(DownloadSettings::DEFAULT.parallel_downloads),
+            mirror: config.download_mirror.clone(),
+            proxy: config.proxy.clone(),
+        });
+        Ok(config)
+    }
+}
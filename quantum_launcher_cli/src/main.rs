@@ -0,0 +1,225 @@
+//! A command line interface for QuantumLauncher,
+//! for servers, scripts and people who prefer the terminal.
+//!
+//! It shares the instances and `config.json` with the GUI.
+
+use std::{path::PathBuf, process::ExitCode};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use quantum_launcher_backend::{
+    crash_dump, file_utils,
+    json_structs::json_manifest::VersionSource,
+    logging::{self, LogConsole},
+    HasErrorCode, LaunchOptions,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::CliConfig,
+    output::{CliError, Output},
+};
+
+mod commands;
+mod config;
+mod manifest;
+mod output;
+
+#[derive(Parser)]
+#[command(name = "quantum_launcher_cli", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Print JSON lines instead of text, for other programs to read.
+    #[arg(long, global = true)]
+    json: bool,
+    /// Keep the launcher's data next to the executable, as if
+    /// there was a `portable.txt` there.
+    #[arg(long, global = true)]
+    portable: bool,
+    /// Log debugging details, like the launch command,
+    /// downloads and how long each step took.
+    #[arg(long, global = true)]
+    verbose: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List all instances.
+    List,
+    /// Create a new instance, optionally with a mod loader and mods.
+    Create {
+        name: String,
+        /// The Minecraft version, like 1.20.4.
+        #[arg(short, long)]
+        version: String,
+        #[arg(long, value_enum, default_value_t = Loader::Vanilla)]
+        loader: Loader,
+        /// The mod loader version, or "latest".
+        #[arg(long, default_value = "latest")]
+        loader_version: String,
+        /// Don't download assets. Faster, but there's no sound or music.
+        #[arg(long)]
+        no_assets: bool,
+        /// Modrinth mods to install, like "sodium,lithium".
+        /// Mods they need are installed too.
+        #[arg(long, value_delimiter = ',')]
+        mods: Vec<String>,
+        /// Where to get the version from. BetterJSONs has
+        /// fixed old versions and ones Mojang doesn't list.
+        #[arg(long, value_enum, default_value_t = Source::Mojang)]
+        source: Source,
+    },
+    /// Launch an instance and wait for the game to exit.
+    Launch {
+        name: String,
+        /// Defaults to the username set in the launcher.
+        #[arg(short, long)]
+        username: Option<String>,
+        /// Join this server (`host` or `host:port`) once the game starts.
+        #[arg(long)]
+        server: Option<String>,
+        /// Print the command that would start the game, without starting it.
+        #[arg(long)]
+        dry_run: bool,
+        /// Start with only the mod loader, putting the mods back once the game exits.
+        #[arg(long)]
+        without_mods: bool,
+        /// Use a username that breaks Minecraft's rules (3 to 16 letters,
+        /// digits or underscores), for servers that allow it.
+        #[arg(long)]
+        allow_invalid_username: bool,
+    },
+    /// Install the Fabric mod loader into an instance.
+    InstallFabric {
+        name: String,
+        /// Defaults to the latest version.
+        #[arg(long)]
+        loader_version: Option<String>,
+    },
+    /// Create and update instances to match a JSON or YAML manifest file.
+    Apply {
+        manifest: PathBuf,
+        /// Only show what would change.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Delete an instance, along with its worlds and mods.
+    Delete {
+        name: String,
+        /// Don't ask for confirmation.
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Loader {
+    #[default]
+    Vanilla,
+    Fabric,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    #[default]
+    Mojang,
+    #[value(name = "betterjsons")]
+    BetterJsons,
+}
+
+impl From<Source> for VersionSource {
+    fn from(source: Source) -> Self {
+        match source {
+            Source::Mojang => VersionSource::Mojang,
+            Source::BetterJsons => VersionSource::BetterJsons,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if cli.portable {
+        file_utils::set_portable(true);
+    }
+    logging::set_verbose(cli.verbose);
+    crash_dump::install_panic_hook("QuantumLauncher CLI", env!("CARGO_PKG_VERSION"));
+    // Logs would mix with the JSON, so they only go to the log file.
+    logging::init(if cli.json {
+        LogConsole::None
+    } else {
+        LogConsole::Stderr
+    });
+    let output = Output { json: cli.json };
+
+    match run(cli.command, output).await {
+        Ok(code) => code,
+        Err(err) => {
+            output.error(&err);
+            ExitCode::from(err.code.number)
+        }
+    }
+}
+
+async fn run(command: Command, output: Output) -> commands::CommandResult {
+    let config = CliConfig::load()
+        .map_err(|err| CliError::new(err.code(), format!("could not load config: {err}")))?;
+
+    match command {
+        Command::List => commands::list(output),
+        Command::Create {
+            name,
+            version,
+            loader,
+            loader_version,
+            no_assets,
+            mods,
+            source,
+        } => {
+            let loader_version = (loader_version != "latest").then_some(loader_version);
+            commands::create(
+                output,
+                commands::CreateOptions {
+                    name,
+                    version,
+                    loader,
+                    loader_version,
+                    download_assets: !no_assets,
+                    mods,
+                    version_source: source.into(),
+                },
+            )
+            .await
+        }
+        Command::Launch {
+            name,
+            username,
+            server,
+            dry_run,
+            without_mods,
+            allow_invalid_username,
+        } => {
+            let mut options = LaunchOptions::new(name)
+                .username(username.unwrap_or(config.username))
+                .java_args(config.java_args);
+            if let Some(server) = server {
+                options = options.join_server(server);
+            }
+            if without_mods {
+                options = options.without_mods();
+            }
+            if allow_invalid_username || config.allow_invalid_username {
+                options = options.allow_invalid_username();
+            }
+            commands::launch(output, options, dry_run).await
+        }
+        Command::InstallFabric {
+            name,
+            loader_version,
+        } => commands::install_fabric(output, name, loader_version).await,
+        Command::Apply { manifest, dry_run } => manifest::apply(output, &manifest, dry_run).await,
+        Command::Delete { name, yes } => commands::delete(output, &name, yes).await,
+    }
+}
@@ -0,0 +1,390 @@
+//! "Instances as code": a JSON or YAML file describing instances,
+//! which the `apply` command makes the launcher match. Files ending
+//! in `.yaml` or `.yml` are read as YAML, anything else as JSON.
+//!
+//! ```json
+//! {
+//!     "instances": [
+//!         {
+//!             "name": "modded",
+//!             "version": "1.20.4",
+//!             "loader": "fabric",
+//!             "mods": ["sodium", "lithium"],
+//!             "settings": { "ram_in_mb": 4096 }
+//!         }
+//!     ]
+//! }
+//! ```
+//!
+//! Instances that are missing get created, and ones that exist get
+//! their loader, mods and settings updated. A pinned `loader_version`
+//! that differs from the installed one gets Fabric reinstalled at that
+//! version (`"latest"` never does, as that would need a network lookup
+//! on every run). Nothing is ever deleted:
+//! instances not in the file are left alone, and changing the
+//! Minecraft version of an existing instance is only warned about.
+
+use std::{collections::HashSet, fmt::Display, path::Path, process::ExitCode};
+
+use quantum_launcher_backend::{
+    file_utils, instance_mod_installer, io_err,
+    json_structs::json_instance_config::InstanceConfigJson, InstanceInfo,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    commands::{self, CommandResult, CreateOptions},
+    output::{codes, CliError, Output},
+    Loader, Source,
+};
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    instances: Vec<InstanceSpec>,
+}
+
+/// How an instance should be set up.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InstanceSpec {
+    name: String,
+    version: String,
+    #[serde(default)]
+    loader: Loader,
+    /// `None` or `"latest"` for the latest version.
+    #[serde(default)]
+    loader_version: Option<String>,
+    #[serde(default = "default_download_assets")]
+    download_assets: bool,
+    /// Modrinth slugs or project IDs.
+    #[serde(default)]
+    mods: Vec<String>,
+    #[serde(default)]
+    source: Source,
+    #[serde(default)]
+    settings: InstanceSettings,
+}
+
+fn default_download_assets() -> bool {
+    true
+}
+
+/// Settings from the instance's `config.json`.
+/// Only the ones that are set are changed.
+#[derive(Deserialize, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+struct InstanceSettings {
+    ram_in_mb: Option<usize>,
+    initial_ram_in_mb: Option<usize>,
+    java_version: Option<usize>,
+    java_override: Option<String>,
+}
+
+impl InstanceSettings {
+    /// Whether applying these settings would change `config`.
+    fn differs_from(&self, config: &InstanceConfigJson) -> bool {
+        self.ram_in_mb.is_some_and(|ram| ram != config.ram_in_mb)
+            || self
+                .initial_ram_in_mb
+                .is_some_and(|ram| Some(ram) != config.initial_ram_in_mb)
+            || self
+                .java_version
+                .is_some_and(|version| Some(version) != config.java_version)
+            || self
+                .java_override
+                .as_ref()
+                .is_some_and(|java| Some(java) != config.java_override.as_ref())
+    }
+
+    fn apply_to(&self, config: &mut InstanceConfigJson) {
+        if let Some(ram) = self.ram_in_mb {
+            config.ram_in_mb = ram;
+        }
+        if let Some(ram) = self.initial_ram_in_mb {
+            config.initial_ram_in_mb = Some(ram);
+        }
+        if let Some(version) = self.java_version {
+            config.java_version = Some(version);
+        }
+        if let Some(java) = &self.java_override {
+            config.java_override = Some(java.clone());
+        }
+    }
+}
+
+/// One thing `apply` does (or would do, with `--dry-run`).
+#[derive(Serialize)]
+struct Change<'a> {
+    instance: &'a str,
+    #[serde(flatten)]
+    action: Action,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum Action {
+    Create,
+    InstallLoader {
+        loader: Loader,
+        /// The installed version, if this is a reinstall
+        /// to get to the manifest's `loader_version`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        replaces: Option<String>,
+    },
+    UninstallLoader,
+    InstallMods {
+        mods: Vec<String>,
+    },
+    UpdateSettings,
+    /// The version can't be changed without losing the
+    /// instance's worlds, so this is only reported.
+    VersionMismatch {
+        current: String,
+        wanted: String,
+    },
+    /// The instance has a loader the CLI can't manage (like Forge).
+    UnsupportedLoader {
+        current: String,
+    },
+}
+
+impl Action {
+    fn is_warning(&self) -> bool {
+        matches!(
+            self,
+            Action::VersionMismatch { .. } | Action::UnsupportedLoader { .. }
+        )
+    }
+}
+
+impl Display for Change<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let instance = self.instance;
+        match &self.action {
+            Action::Create => write!(f, "{instance}: create"),
+            Action::InstallLoader {
+                replaces: Some(current),
+                ..
+            } => write!(f, "{instance}: reinstall Fabric (is {current})"),
+            Action::InstallLoader { replaces: None, .. } => {
+                write!(f, "{instance}: install Fabric")
+            }
+            Action::UninstallLoader => write!(f, "{instance}: uninstall the mod loader"),
+            Action::InstallMods { mods } => {
+                write!(f, "{instance}: install mods {}", mods.join(", "))
+            }
+            Action::UpdateSettings => write!(f, "{instance}: update settings"),
+            Action::VersionMismatch { current, wanted } => write!(
+                f,
+                "{instance}: [warning] is {current}, not {wanted}. Delete it to recreate it"
+            ),
+            Action::UnsupportedLoader { current } => write!(
+                f,
+                "{instance}: [warning] has {current}, which can't be managed from here"
+            ),
+        }
+    }
+}
+
+pub async fn apply(output: Output, path: &Path, dry_run: bool) -> CommandResult {
+    let manifest = std::fs::read_to_string(path).map_err(io_err!(path))?;
+    let is_yaml = path
+        .extension()
+        .is_some_and(|extension| extension == "yaml" || extension == "yml");
+    let manifest: Manifest = if is_yaml {
+        serde_yaml::from_str(&manifest).map_err(|err| err.to_string())
+    } else {
+        serde_json::from_str(&manifest).map_err(|err| err.to_string())
+    }
+    .map_err(|err| {
+        CliError::new(
+            codes::MANIFEST_INVALID,
+            format!("invalid manifest {path:?}: {err}"),
+        )
+    })?;
+    validate(&manifest)?;
+
+    let existing = quantum_launcher_backend::list_instances()?;
+
+    let mut changes = Vec::new();
+    for spec in &manifest.instances {
+        let current = existing.iter().find(|instance| instance.name == spec.name);
+        for action in plan(spec, current)? {
+            changes.push((
+                spec,
+                Change {
+                    instance: &spec.name,
+                    action,
+                },
+            ));
+        }
+    }
+
+    for (_, change) in &changes {
+        output.progress(change, change);
+    }
+    if changes.is_empty() {
+        output.message("Everything is up to date.");
+    }
+
+    if !dry_run {
+        for (spec, change) in &changes {
+            execute(output, spec, &change.action).await?;
+        }
+    }
+
+    let changes: Vec<&Change> = changes.iter().map(|(_, change)| change).collect();
+    let count = changes
+        .iter()
+        .filter(|change| !change.action.is_warning())
+        .count();
+    output.done(
+        if dry_run {
+            format!("{count} changes would be made.")
+        } else {
+            format!("Made {count} changes.")
+        },
+        json!({ "changes": changes, "applied": !dry_run }),
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+fn validate(manifest: &Manifest) -> Result<(), CliError> {
+    let mut names = HashSet::new();
+    for spec in &manifest.instances {
+        if !names.insert(&spec.name) {
+            return Err(CliError::new(
+                codes::MANIFEST_INVALID,
+                format!("instance {} is in the manifest twice", spec.name),
+            ));
+        }
+        if !spec.mods.is_empty() && spec.loader == Loader::Vanilla {
+            return Err(CliError::new(
+                codes::MODS_NEED_LOADER,
+                format!("{} has mods, but no mod loader", spec.name),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Works out what needs to change for an instance to match `spec`.
+fn plan(spec: &InstanceSpec, current: Option<&InstanceInfo>) -> Result<Vec<Action>, CliError> {
+    let Some(current) = current else {
+        let mut actions = vec![Action::Create];
+        if spec.settings != InstanceSettings::default() {
+            actions.push(Action::UpdateSettings);
+        }
+        return Ok(actions);
+    };
+
+    let mut actions = Vec::new();
+    if let Some(version) = &current.version {
+        if *version != spec.version {
+            actions.push(Action::VersionMismatch {
+                current: version.clone(),
+                wanted: spec.version.clone(),
+            });
+        }
+    }
+
+    let mod_type = current.mod_type.as_deref().unwrap_or("Vanilla");
+    match (mod_type, spec.loader) {
+        ("Vanilla", Loader::Vanilla) => {}
+        ("Fabric", Loader::Fabric) => {
+            let wanted = spec
+                .loader_version
+                .as_ref()
+                .filter(|version| *version != "latest");
+            if let Some(wanted) = wanted {
+                let installed = instance_mod_installer::fabric::installed_version(&spec.name);
+                if installed.as_ref() != Some(wanted) {
+                    actions.push(Action::InstallLoader {
+                        loader: Loader::Fabric,
+                        replaces: installed,
+                    });
+                }
+            }
+        }
+        ("Vanilla", loader) => actions.push(Action::InstallLoader {
+            loader,
+            replaces: None,
+        }),
+        ("Fabric", Loader::Vanilla) => actions.push(Action::UninstallLoader),
+        (other, _) => actions.push(Action::UnsupportedLoader {
+            current: other.to_owned(),
+        }),
+    }
+
+    // Mods that are already there are skipped when installing,
+    // so this is cheap if nothing changed.
+    if !spec.mods.is_empty() {
+        actions.push(Action::InstallMods {
+            mods: spec.mods.clone(),
+        });
+    }
+
+    if spec
+        .settings
+        .differs_from(&read_instance_config(&spec.name)?)
+    {
+        actions.push(Action::UpdateSettings);
+    }
+    Ok(actions)
+}
+
+async fn execute(output: Output, spec: &InstanceSpec, action: &Action) -> Result<(), CliError> {
+    match action {
+        Action::Create => {
+            commands::create_instance(
+                output,
+                CreateOptions {
+                    name: spec.name.clone(),
+                    version: spec.version.clone(),
+                    loader: spec.loader,
+                    loader_version: spec
+                        .loader_version
+                        .clone()
+                        .filter(|version| version != "latest"),
+                    download_assets: spec.download_assets,
+                    mods: spec.mods.clone(),
+                    version_source: spec.source.into(),
+                },
+            )
+            .await?;
+        }
+        Action::InstallLoader { .. } => {
+            let loader_version = spec
+                .loader_version
+                .clone()
+                .filter(|version| version != "latest");
+            commands::install_fabric_into(output, &spec.name, loader_version).await?;
+        }
+        Action::UninstallLoader => {
+            instance_mod_installer::fabric::uninstall(&spec.name).await?;
+        }
+        Action::InstallMods { mods } => {
+            commands::install_mods_into(output, &spec.name, mods.clone()).await?;
+        }
+        Action::UpdateSettings => {
+            let mut config = read_instance_config(&spec.name)?;
+            spec.settings.apply_to(&mut config);
+            config.save(&instance_config_path(&spec.name)?)?;
+        }
+        Action::VersionMismatch { .. } | Action::UnsupportedLoader { .. } => {}
+    }
+    Ok(())
+}
+
+fn instance_config_path(name: &str) -> Result<std::path::PathBuf, CliError> {
+    Ok(file_utils::get_launcher_dir()?
+        .join("instances")
+        .join(name)
+        .join("config.json"))
+}
+
+fn read_instance_config(name: &str) -> Result<InstanceConfigJson, CliError> {
+    Ok(InstanceConfigJson::read(&instance_config_path(name)?)?)
+}
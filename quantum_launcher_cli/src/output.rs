@@ -0,0 +1,121 @@
+//! Everything the CLI prints goes through [`Output`], so
+//! that `--json` turns all of it into JSON lines.
+//!
+//! In JSON mode every line of stdout is an object with an `event`
+//! field. Lines not starting with `{` are log messages from the
+//! backend, and can be skipped.
+
+use std::fmt::Display;
+
+use quantum_launcher_backend::{ErrorCode, HasErrorCode};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Codes for errors that only the CLI reports.
+///
+/// They start at 100 so they never clash with the backend's
+/// (see [`ErrorCode`]), and stay clear of 2, which `clap` uses
+/// for invalid arguments.
+pub mod codes {
+    use quantum_launcher_backend::ErrorCode;
+
+    pub const MANIFEST_INVALID: ErrorCode = ErrorCode::new(100, "manifest_invalid");
+    pub const MODS_NEED_LOADER: ErrorCode = ErrorCode::new(101, "mods_need_loader");
+    pub const CONFIRMATION_REQUIRED: ErrorCode = ErrorCode::new(102, "confirmation_required");
+    pub const CANCELLED: ErrorCode = ErrorCode::new(103, "cancelled");
+    pub const GAME_CRASHED: ErrorCode = ErrorCode::new(104, "game_crashed");
+}
+
+/// An error shown to the user, with a code for scripts
+/// to tell failures apart. The code's number is also
+/// the exit status.
+#[derive(Debug)]
+pub struct CliError {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl CliError {
+    pub fn new(code: ErrorCode, message: impl Display) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl<E: HasErrorCode> From<E> for CliError {
+    fn from(err: E) -> Self {
+        Self::new(err.code(), err)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Output {
+    pub json: bool,
+}
+
+impl Output {
+    /// Prints one JSON line, with `event` added to `fields`.
+    fn event(self, event: &str, fields: Value) {
+        let mut value = json!({ "event": event });
+        if let (Some(value), Value::Object(fields)) = (value.as_object_mut(), fields) {
+            value.extend(fields);
+        }
+        println!("{value}");
+    }
+
+    /// A message for people, that scripts don't need.
+    pub fn message(self, message: impl Display) {
+        if self.json {
+            self.event("message", json!({ "message": message.to_string() }));
+        } else {
+            println!("{message}");
+        }
+    }
+
+    /// A progress update from the backend, like `DownloadProgress`.
+    pub fn progress<P: Serialize>(self, progress: &P, message: impl Display) {
+        if self.json {
+            self.event("progress", json!({ "progress": progress }));
+        } else {
+            println!("{message}");
+        }
+    }
+
+    /// A line printed by the game.
+    pub fn game_log(self, line: &str, is_stderr: bool) {
+        if self.json {
+            let stream = if is_stderr { "stderr" } else { "stdout" };
+            self.event("log", json!({ "stream": stream, "line": line }));
+        } else if is_stderr {
+            eprintln!("{line}");
+        } else {
+            println!("{line}");
+        }
+    }
+
+    /// The result of a command, shown last.
+    pub fn done(self, message: impl Display, fields: Value) {
+        if self.json {
+            self.event("done", fields);
+        } else {
+            println!("{message}");
+        }
+    }
+
+    pub fn error(self, err: &CliError) {
+        if self.json {
+            self.event(
+                "error",
+                json!({
+                    "code": err.code.name,
+                    "number": err.code.number,
+                    "message": err.message,
+                }),
+            );
+        } else {
+            eprintln!("[error] {} ({})", err.message, err.code);
+        }
+    }
+}